@@ -0,0 +1,200 @@
+use crate::args::simple::BenchmarkKind;
+use crate::benchmark_result::{BatchRecord, BenchmarkResult, LatencyPercentiles};
+use iggy::client::{ConsumerGroupClient, StreamClient, TopicClient, UserClient};
+use iggy::clients::client::{IggyClient, IggyClientBackgroundConfig};
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::models::user_status::UserStatus;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::test_server::{login_root, ClientFactory};
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::info;
+
+const ADMIN_TOPIC_ID: u32 = 1;
+const ADMIN_CONSUMER_GROUP_ID: u32 = 1;
+const ADMIN_USER_PASSWORD: &str = "bench-admin-secret";
+
+/// Generates the metadata changes (topics, consumer groups and users) on its own stream while the producers
+/// and consumers are running, so their throughput and latency can be compared with and without the admin traffic.
+/// Each operation counts as a single message of the result, it doesn't contribute to the throughput statistics.
+pub struct Admin {
+    client_factory: Arc<dyn ClientFactory>,
+    admin_id: u32,
+    stream_id: u32,
+    partitions_count: u32,
+    active_actors: Arc<AtomicU32>,
+}
+
+impl Admin {
+    /// The admin keeps running until the `active_actors` counter of the producers and consumers drops to zero.
+    pub fn new(
+        client_factory: Arc<dyn ClientFactory>,
+        admin_id: u32,
+        stream_id: u32,
+        partitions_count: u32,
+        active_actors: Arc<AtomicU32>,
+    ) -> Self {
+        Self {
+            client_factory,
+            admin_id,
+            stream_id,
+            partitions_count,
+            active_actors,
+        }
+    }
+
+    pub async fn run(&self) -> Result<BenchmarkResult, IggyError> {
+        let client = self.client_factory.create_client().await;
+        let client = IggyClient::create(
+            client,
+            IggyClientBackgroundConfig::default(),
+            None,
+            None,
+            None,
+        );
+        login_root(&client).await;
+        let stream_id: Identifier = self.stream_id.try_into()?;
+        let topic_id: Identifier = ADMIN_TOPIC_ID.try_into()?;
+        let group_id: Identifier = ADMIN_CONSUMER_GROUP_ID.try_into()?;
+        let username = format!("bench-admin-{}", self.admin_id);
+        let user_id = Identifier::named(&username)?;
+        let streams = client.get_streams().await?;
+        if streams.iter().any(|s| s.id == self.stream_id) {
+            client.delete_stream(&stream_id).await?;
+        }
+        client
+            .create_stream(&format!("admin {}", self.stream_id), Some(self.stream_id))
+            .await?;
+
+        info!(
+            "Admin #{} → changing the metadata of stream {} until the producers and consumers are finished...",
+            self.admin_id, self.stream_id
+        );
+
+        let start_timestamp = Instant::now();
+        let mut latencies: Vec<Duration> = Vec::new();
+        let mut batches = Vec::new();
+        let mut record = |latency: Duration| {
+            latencies.push(latency);
+            batches.push(BatchRecord {
+                elapsed: start_timestamp.elapsed(),
+                size_bytes: 0,
+                messages: 1,
+            });
+        };
+        // At least one round of the operations is performed, even if the other actors are already finished.
+        loop {
+            record(
+                measure(client.create_topic(
+                    &stream_id,
+                    "admin topic",
+                    self.partitions_count,
+                    CompressionAlgorithm::default(),
+                    None,
+                    Some(ADMIN_TOPIC_ID),
+                    IggyExpiry::NeverExpire,
+                    MaxTopicSize::Unlimited,
+                ))
+                .await?,
+            );
+            record(
+                measure(client.update_topic(
+                    &stream_id,
+                    &topic_id,
+                    "admin topic updated",
+                    CompressionAlgorithm::default(),
+                    None,
+                    IggyExpiry::NeverExpire,
+                    MaxTopicSize::Unlimited,
+                ))
+                .await?,
+            );
+            record(
+                measure(client.create_consumer_group(
+                    &stream_id,
+                    &topic_id,
+                    "admin group",
+                    Some(ADMIN_CONSUMER_GROUP_ID),
+                ))
+                .await?,
+            );
+            record(measure(client.delete_consumer_group(&stream_id, &topic_id, &group_id)).await?);
+            record(measure(client.delete_topic(&stream_id, &topic_id)).await?);
+            record(
+                measure(client.create_user(
+                    &username,
+                    ADMIN_USER_PASSWORD,
+                    UserStatus::Active,
+                    None,
+                ))
+                .await?,
+            );
+            record(measure(client.update_user(&user_id, None, Some(UserStatus::Inactive))).await?);
+            record(measure(client.delete_user(&user_id)).await?);
+            if self.active_actors.load(Ordering::Acquire) == 0 {
+                break;
+            }
+        }
+        let end_timestamp = Instant::now();
+        client.delete_stream(&stream_id).await?;
+
+        latencies.sort();
+        let last_idx = latencies.len() - 1;
+        let p50 = latencies[last_idx / 2];
+        let p90 = latencies[last_idx * 9 / 10];
+        let p95 = latencies[last_idx * 95 / 100];
+        let p99 = latencies[last_idx * 99 / 100];
+        let p999 = latencies[last_idx * 999 / 1000];
+        let latency_percentiles = LatencyPercentiles {
+            p50,
+            p90,
+            p95,
+            p99,
+            p999,
+        };
+
+        let duration = end_timestamp - start_timestamp;
+        let operations = latencies.len() as u64;
+        let average_latency: Duration = latencies.iter().sum::<Duration>() / latencies.len() as u32;
+
+        info!(
+            "Admin #{} → performed {} operations in {:.2} s, {:.0} operations/s, p50 latency: {:.2} ms, p90 latency: {:.2} ms, p95 latency: {:.2} ms, p99 latency: {:.2} ms, p999 latency: {:.2} ms, average latency: {:.2} ms",
+            self.admin_id,
+            operations,
+            duration.as_secs_f64(),
+            operations as f64 / duration.as_secs_f64(),
+            p50.as_secs_f64() * 1000.0,
+            p90.as_secs_f64() * 1000.0,
+            p95.as_secs_f64() * 1000.0,
+            p99.as_secs_f64() * 1000.0,
+            p999.as_secs_f64() * 1000.0,
+            average_latency.as_secs_f64() * 1000.0
+        );
+
+        Ok(BenchmarkResult {
+            kind: BenchmarkKind::Admin,
+            start_timestamp,
+            end_timestamp,
+            average_latency,
+            latency_percentiles,
+            total_size_bytes: 0,
+            total_messages: operations,
+            actor_id: self.admin_id,
+            batches,
+        })
+    }
+}
+
+async fn measure<T>(
+    operation: impl Future<Output = Result<T, IggyError>>,
+) -> Result<Duration, IggyError> {
+    let start = Instant::now();
+    operation.await?;
+    Ok(start.elapsed())
+}
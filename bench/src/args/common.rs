@@ -1,12 +1,14 @@
 use super::kind::BenchmarkKindCommand;
 use super::props::{BenchmarkKindProps, BenchmarkTransportProps};
+use super::simple::BenchmarkReportFormat;
 use super::{defaults::*, transport::BenchmarkTransportCommand};
 use clap::error::ErrorKind;
 use clap::{CommandFactory, Parser};
 use iggy::utils::duration::IggyDuration;
 use integration::test_server::Transport;
 use std::net::SocketAddr;
-use std::path::Path;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 #[derive(Parser, Debug)]
@@ -39,6 +41,18 @@ pub struct IggyBenchArgs {
     /// Skip server start
     #[arg(long, short = 'k', default_value_t = DEFAULT_SKIP_SERVER_START)]
     pub skip_server_start: bool,
+
+    /// Results file path, if provided the machine-readable benchmark results will be written to it
+    #[arg(long, short = 'o')]
+    pub output_file: Option<PathBuf>,
+
+    /// Results file format, if not provided it's detected from the file extension (CSV for .csv, JSON otherwise)
+    #[arg(long)]
+    pub output_format: Option<BenchmarkReportFormat>,
+
+    /// Sampling interval of the per-actor throughput time series written to the results file
+    #[arg(long, default_value_t = IggyDuration::from_str(DEFAULT_SAMPLING_INTERVAL).unwrap())]
+    pub sampling_interval: IggyDuration,
 }
 
 fn validate_server_executable_path(v: &str) -> Result<String, String> {
@@ -83,6 +97,15 @@ impl IggyBenchArgs {
                 .exit();
         }
 
+        if self.sampling_interval.get_duration().is_zero() {
+            IggyBenchArgs::command()
+                .error(
+                    ErrorKind::ValueValidation,
+                    "Sampling interval must be greater than 0.",
+                )
+                .exit();
+        }
+
         self.benchmark_kind.inner().validate()
     }
 
@@ -98,6 +121,22 @@ impl IggyBenchArgs {
         self.benchmark_kind.inner().message_size()
     }
 
+    pub fn min_message_size(&self) -> u32 {
+        self.benchmark_kind.inner().min_message_size()
+    }
+
+    pub fn max_headers(&self) -> u32 {
+        self.benchmark_kind.inner().max_headers()
+    }
+
+    pub fn admin_clients(&self) -> u32 {
+        self.benchmark_kind.inner().admin_clients()
+    }
+
+    pub fn rate_limit(&self) -> Option<NonZeroU32> {
+        self.benchmark_kind.inner().rate_limit()
+    }
+
     pub fn number_of_streams(&self) -> u32 {
         self.benchmark_kind.inner().number_of_streams()
     }
@@ -133,4 +172,14 @@ impl IggyBenchArgs {
     pub fn warmup_time(&self) -> IggyDuration {
         self.warmup_time
     }
+
+    /// Returns the results file with its format, if the results should be written to a file.
+    pub fn output(&self) -> Option<(PathBuf, BenchmarkReportFormat)> {
+        self.output_file.clone().map(|file| {
+            let format = self
+                .output_format
+                .unwrap_or_else(|| BenchmarkReportFormat::from_path(&file));
+            (file, format)
+        })
+    }
 }
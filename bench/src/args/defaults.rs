@@ -19,6 +19,9 @@ pub const DEFAULT_DISABLE_PARALLEL_CONSUMER_STREAMS: bool = false;
 pub const DEFAULT_MESSAGES_PER_BATCH: NonZeroU32 = u32!(1000);
 pub const DEFAULT_MESSAGE_BATCHES: NonZeroU32 = u32!(1000);
 pub const DEFAULT_MESSAGE_SIZE: NonZeroU32 = u32!(1000);
+pub const DEFAULT_MIXED_MIN_MESSAGE_SIZE: NonZeroU32 = u32!(100);
+pub const DEFAULT_MIXED_MAX_MESSAGE_SIZE: NonZeroU32 = u32!(10000);
+pub const DEFAULT_MIXED_MAX_HEADERS: u32 = 10;
 
 pub const DEFAULT_NUMBER_OF_STREAMS: NonZeroU32 = u32!(10);
 pub const DEFAULT_NUMBER_OF_STREAMS_CONSUMER_GROUP: NonZeroU32 = u32!(1);
@@ -27,6 +30,7 @@ pub const DEFAULT_NUMBER_OF_PARTITIONS: NonZeroU32 = u32!(1);
 pub const DEFAULT_NUMBER_OF_CONSUMERS: NonZeroU32 = u32!(10);
 pub const DEFAULT_NUMBER_OF_CONSUMER_GROUPS: NonZeroU32 = u32!(1);
 pub const DEFAULT_NUMBER_OF_PRODUCERS: NonZeroU32 = u32!(10);
+pub const DEFAULT_NUMBER_OF_ADMIN_CLIENTS: u32 = 0;
 
 pub const DEFAULT_PERFORM_CLEANUP: bool = false;
 pub const DEFAULT_SERVER_SYSTEM_PATH: &str = "local_data";
@@ -34,3 +38,7 @@ pub const DEFAULT_SERVER_STDOUT_VISIBILITY: bool = false;
 
 pub const DEFAULT_WARMUP_TIME: &str = "1 s";
 pub const DEFAULT_SKIP_SERVER_START: bool = false;
+
+pub const DEFAULT_SAMPLING_INTERVAL: &str = "100 ms";
+pub const DEFAULT_MAX_THROUGHPUT_REGRESSION: f64 = 5.0;
+pub const DEFAULT_MAX_P99_LATENCY_REGRESSION: f64 = 10.0;
@@ -29,7 +29,39 @@ const EXAMPLES: &str = r#"EXAMPLES:
 
     $ cargo r --bin iggy-bench -- send --message-size 2000 --messages-per-batch 1000 --message-batches 1000 --producers 5 --streams 5 tcp --server-address 142.250.203.142:8090
 
-4) Other options:
+4) Results export and comparison:
+
+    Write the machine-readable results (percentiles, per-actor throughput time series,
+    server version and config) to JSON or CSV file, then compare two JSON results files,
+    the comparison fails if the throughput or p99 latency regressed beyond the thresholds:
+
+    $ cargo r --bin iggy-bench -r -- -o baseline.json send tcp
+    $ cargo r --bin iggy-bench -r -- -o candidate.json send tcp
+    $ cargo r --bin iggy-bench -r -- -o candidate.csv send tcp
+    $ cargo r --bin iggy-bench -r -- compare baseline.json candidate.json --max-throughput-regression 5 --max-p99-latency-regression 10
+
+5) End to end latency, rate limited and mixed workloads:
+
+    Measure the latency from send to poll with 4 pairs of producer and consumer, each pair
+    with its own stream, while every producer sends at most 10000 messages per second:
+
+    $ cargo r --bin iggy-bench -r -- end-to-end --pairs 4 --rate-limit 10000 tcp
+
+    Rate limit the producers of the other benchmarks, so the latency is measured under
+    a steady load instead of a saturated server:
+
+    $ cargo r --bin iggy-bench -r -- send --rate-limit 50000 tcp
+
+    Send and poll the messages of random size (100-10000 bytes) with up to 10 random headers:
+
+    $ cargo r --bin iggy-bench -r -- mixed --min-message-size 100 --max-message-size 10000 --max-headers 10 tcp
+
+    Measure the impact of the concurrent metadata changes on the throughput and latency, while 2 admin
+    clients keep creating and deleting topics, consumer groups and users on their own streams:
+
+    $ cargo r --bin iggy-bench -r -- send-and-poll --admin-clients 2 tcp
+
+6) Other options:
 
     If more options are needed, please refer to the help menu:
 
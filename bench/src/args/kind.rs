@@ -6,6 +6,7 @@ use super::{common::IggyBenchArgs, simple::BenchmarkKind};
 use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand};
 use core::panic;
 use std::num::NonZeroU32;
+use std::path::PathBuf;
 
 #[derive(Subcommand, Debug)]
 pub enum BenchmarkKindCommand {
@@ -13,6 +14,11 @@ pub enum BenchmarkKindCommand {
     Poll(PollArgs),
     SendAndPoll(SendAndPollArgs),
     ConsumerGroupPoll(ConsumerGroupArgs),
+    EndToEnd(EndToEndArgs),
+    Mixed(MixedArgs),
+
+    /// Compares two benchmark results files and fails on regressions
+    Compare(CompareArgs),
 
     /// Prints examples
     Examples,
@@ -25,6 +31,11 @@ impl BenchmarkKindCommand {
             BenchmarkKindCommand::Poll(_) => BenchmarkKind::Poll,
             BenchmarkKindCommand::SendAndPoll(_) => BenchmarkKind::SendAndPoll,
             BenchmarkKindCommand::ConsumerGroupPoll(_) => BenchmarkKind::ConsumerGroupPoll,
+            BenchmarkKindCommand::EndToEnd(_) => BenchmarkKind::EndToEnd,
+            BenchmarkKindCommand::Mixed(_) => BenchmarkKind::Mixed,
+            BenchmarkKindCommand::Compare(_) => {
+                unreachable!("Comparing the results does not run a benchmark")
+            }
             BenchmarkKindCommand::Examples => {
                 print_examples();
                 std::process::exit(0);
@@ -84,6 +95,11 @@ impl BenchmarkKindProps for BenchmarkKindCommand {
             BenchmarkKindCommand::Poll(args) => args,
             BenchmarkKindCommand::SendAndPoll(args) => args,
             BenchmarkKindCommand::ConsumerGroupPoll(args) => args,
+            BenchmarkKindCommand::EndToEnd(args) => args,
+            BenchmarkKindCommand::Mixed(args) => args,
+            BenchmarkKindCommand::Compare(_) => {
+                unreachable!("Comparing the results does not run a benchmark")
+            }
             BenchmarkKindCommand::Examples => {
                 print_examples();
                 std::process::exit(0);
@@ -94,6 +110,22 @@ impl BenchmarkKindProps for BenchmarkKindCommand {
     fn validate(&self) {
         self.inner().validate()
     }
+
+    fn rate_limit(&self) -> Option<NonZeroU32> {
+        self.inner().rate_limit()
+    }
+
+    fn min_message_size(&self) -> u32 {
+        self.inner().min_message_size()
+    }
+
+    fn max_headers(&self) -> u32 {
+        self.inner().max_headers()
+    }
+
+    fn admin_clients(&self) -> u32 {
+        self.inner().admin_clients()
+    }
 }
 
 /// Sending (writing) benchmark
@@ -129,6 +161,9 @@ pub struct SendArgs {
     /// Flag, disables parallel producers
     #[arg(long, default_value_t = DEFAULT_DISABLE_PARALLEL_PRODUCER_STREAMS)]
     pub disable_parallel_producers: bool,
+    /// Messages per second sent by each producer, if not provided producers send as fast as possible
+    #[arg(long)]
+    pub rate_limit: Option<NonZeroU32>,
 }
 
 impl BenchmarkKindProps for SendArgs {
@@ -176,6 +211,10 @@ impl BenchmarkKindProps for SendArgs {
         panic!("No consumer groups in send benchmark");
     }
 
+    fn rate_limit(&self) -> Option<NonZeroU32> {
+        self.rate_limit
+    }
+
     fn validate(&self) {
         let streams = self.streams.get();
         let producers = self.producers.get();
@@ -425,6 +464,13 @@ pub struct SendAndPollArgs {
     /// Flag, disables parallel consumers
     #[arg(long, default_value_t = DEFAULT_DISABLE_PARALLEL_CONSUMER_STREAMS)]
     pub disable_parallel_consumers: bool,
+    /// Messages per second sent by each producer, if not provided producers send as fast as possible
+    #[arg(long)]
+    pub rate_limit: Option<NonZeroU32>,
+
+    /// Number of admin clients creating and deleting topics, consumer groups and users during the benchmark
+    #[arg(long, default_value_t = DEFAULT_NUMBER_OF_ADMIN_CLIENTS)]
+    pub admin_clients: u32,
 }
 
 impl BenchmarkKindProps for SendAndPollArgs {
@@ -472,6 +518,14 @@ impl BenchmarkKindProps for SendAndPollArgs {
         panic!("No consumer groups in send and poll benchmark");
     }
 
+    fn rate_limit(&self) -> Option<NonZeroU32> {
+        self.rate_limit
+    }
+
+    fn admin_clients(&self) -> u32 {
+        self.admin_clients
+    }
+
     fn validate(&self) {
         let streams = self.streams.get();
         let consumers = self.consumers.get();
@@ -495,3 +549,247 @@ impl BenchmarkKindProps for SendAndPollArgs {
         }
     }
 }
+
+/// End to end latency benchmark, producers embed the send timestamps in the headers
+/// and consumers measure the time from sending to polling the messages
+#[derive(Parser, Debug)]
+pub struct EndToEndArgs {
+    #[command(subcommand)]
+    pub transport: BenchmarkTransportCommand,
+
+    /// Number of messages per batch
+    #[arg(long, default_value_t = DEFAULT_MESSAGES_PER_BATCH)]
+    pub messages_per_batch: NonZeroU32,
+
+    /// Number of message batches
+    #[arg(long, default_value_t = DEFAULT_MESSAGE_BATCHES)]
+    pub message_batches: NonZeroU32,
+
+    /// Message size in bytes
+    #[arg(long, default_value_t = DEFAULT_MESSAGE_SIZE)]
+    pub message_size: NonZeroU32,
+
+    /// Number of producer and consumer pairs, each pair uses its own stream
+    #[arg(long, default_value_t = DEFAULT_NUMBER_OF_PRODUCERS)]
+    pub pairs: NonZeroU32,
+
+    /// Messages per second sent by each producer, if not provided producers send as fast as possible
+    #[arg(long)]
+    pub rate_limit: Option<NonZeroU32>,
+
+    /// Number of admin clients creating and deleting topics, consumer groups and users during the benchmark
+    #[arg(long, default_value_t = DEFAULT_NUMBER_OF_ADMIN_CLIENTS)]
+    pub admin_clients: u32,
+}
+
+impl BenchmarkKindProps for EndToEndArgs {
+    fn message_size(&self) -> u32 {
+        self.message_size.get()
+    }
+
+    fn messages_per_batch(&self) -> u32 {
+        self.messages_per_batch.get()
+    }
+
+    fn message_batches(&self) -> u32 {
+        self.message_batches.get()
+    }
+
+    fn number_of_streams(&self) -> u32 {
+        self.pairs.get()
+    }
+
+    fn number_of_partitions(&self) -> u32 {
+        1
+    }
+
+    fn consumers(&self) -> u32 {
+        self.pairs.get()
+    }
+
+    fn producers(&self) -> u32 {
+        self.pairs.get()
+    }
+
+    fn disable_parallel_producer_streams(&self) -> bool {
+        false
+    }
+
+    fn disable_parallel_consumer_streams(&self) -> bool {
+        false
+    }
+
+    fn transport_command(&self) -> &BenchmarkTransportCommand {
+        &self.transport
+    }
+
+    fn number_of_consumer_groups(&self) -> u32 {
+        panic!("No consumer groups in end to end benchmark");
+    }
+
+    fn validate(&self) {}
+
+    fn rate_limit(&self) -> Option<NonZeroU32> {
+        self.rate_limit
+    }
+
+    fn admin_clients(&self) -> u32 {
+        self.admin_clients
+    }
+}
+
+/// Parallel sending and polling benchmark with messages of varying sizes and header counts
+#[derive(Parser, Debug)]
+pub struct MixedArgs {
+    #[command(subcommand)]
+    pub transport: BenchmarkTransportCommand,
+
+    /// Number of messages per batch
+    #[arg(long, default_value_t = DEFAULT_MESSAGES_PER_BATCH)]
+    pub messages_per_batch: NonZeroU32,
+
+    /// Number of message batches
+    #[arg(long, default_value_t = DEFAULT_MESSAGE_BATCHES)]
+    pub message_batches: NonZeroU32,
+
+    /// Minimum message size in bytes
+    #[arg(long, default_value_t = DEFAULT_MIXED_MIN_MESSAGE_SIZE)]
+    pub min_message_size: NonZeroU32,
+
+    /// Maximum message size in bytes
+    #[arg(long, default_value_t = DEFAULT_MIXED_MAX_MESSAGE_SIZE)]
+    pub max_message_size: NonZeroU32,
+
+    /// Maximum number of headers per message
+    #[arg(long, default_value_t = DEFAULT_MIXED_MAX_HEADERS)]
+    pub max_headers: u32,
+
+    /// Number of producers
+    #[arg(long, default_value_t = DEFAULT_NUMBER_OF_PRODUCERS)]
+    pub producers: NonZeroU32,
+
+    /// Number of consumers
+    #[arg(long, default_value_t = DEFAULT_NUMBER_OF_CONSUMERS)]
+    pub consumers: NonZeroU32,
+
+    /// Number of streams
+    #[arg(long, default_value_t = DEFAULT_NUMBER_OF_STREAMS)]
+    pub streams: NonZeroU32,
+
+    /// Number of partitions
+    #[arg(long, default_value_t = DEFAULT_NUMBER_OF_PARTITIONS)]
+    pub partitions: NonZeroU32,
+
+    /// Messages per second sent by each producer, if not provided producers send as fast as possible
+    #[arg(long)]
+    pub rate_limit: Option<NonZeroU32>,
+
+    /// Number of admin clients creating and deleting topics, consumer groups and users during the benchmark
+    #[arg(long, default_value_t = DEFAULT_NUMBER_OF_ADMIN_CLIENTS)]
+    pub admin_clients: u32,
+}
+
+impl BenchmarkKindProps for MixedArgs {
+    fn message_size(&self) -> u32 {
+        self.max_message_size.get()
+    }
+
+    fn messages_per_batch(&self) -> u32 {
+        self.messages_per_batch.get()
+    }
+
+    fn message_batches(&self) -> u32 {
+        self.message_batches.get()
+    }
+
+    fn number_of_streams(&self) -> u32 {
+        self.streams.get()
+    }
+
+    fn number_of_partitions(&self) -> u32 {
+        self.partitions.get()
+    }
+
+    fn consumers(&self) -> u32 {
+        self.consumers.get()
+    }
+
+    fn producers(&self) -> u32 {
+        self.producers.get()
+    }
+
+    fn disable_parallel_producer_streams(&self) -> bool {
+        false
+    }
+
+    fn disable_parallel_consumer_streams(&self) -> bool {
+        false
+    }
+
+    fn transport_command(&self) -> &BenchmarkTransportCommand {
+        &self.transport
+    }
+
+    fn number_of_consumer_groups(&self) -> u32 {
+        panic!("No consumer groups in mixed benchmark");
+    }
+
+    fn validate(&self) {
+        let streams = self.streams.get();
+        let consumers = self.consumers.get();
+        let producers = self.producers.get();
+        let min_message_size = self.min_message_size.get();
+        let max_message_size = self.max_message_size.get();
+        let mut cmd = IggyBenchArgs::command();
+
+        if min_message_size > max_message_size {
+            cmd.error(
+                ErrorKind::ArgumentConflict,
+                format!("Minimum message size ({min_message_size}) must be less than or equal to the maximum message size ({max_message_size})."),
+            )
+            .exit();
+        }
+
+        if streams < consumers || streams < producers {
+            cmd.error(
+                ErrorKind::ArgumentConflict,
+                format!("The number of streams ({streams}) must be greater than or equal to the number of producers ({producers}) and consumers ({consumers})."),
+            )
+            .exit();
+        }
+    }
+
+    fn rate_limit(&self) -> Option<NonZeroU32> {
+        self.rate_limit
+    }
+
+    fn admin_clients(&self) -> u32 {
+        self.admin_clients
+    }
+
+    fn min_message_size(&self) -> u32 {
+        self.min_message_size.get()
+    }
+
+    fn max_headers(&self) -> u32 {
+        self.max_headers
+    }
+}
+
+/// Comparison of the results files written with the `--output-file` option
+#[derive(Parser, Debug)]
+pub struct CompareArgs {
+    /// Results file of the baseline benchmark
+    pub baseline: PathBuf,
+
+    /// Results file of the candidate benchmark
+    pub candidate: PathBuf,
+
+    /// Maximum allowed drop of the throughput, in percent
+    #[arg(long, default_value_t = DEFAULT_MAX_THROUGHPUT_REGRESSION)]
+    pub max_throughput_regression: f64,
+
+    /// Maximum allowed increase of the p99 latency, in percent
+    #[arg(long, default_value_t = DEFAULT_MAX_P99_LATENCY_REGRESSION)]
+    pub max_p99_latency_regression: f64,
+}
@@ -1,8 +1,8 @@
 pub mod common;
+pub mod kind;
 pub mod simple;
 
 mod defaults;
 mod examples;
-mod kind;
 mod props;
 mod transport;
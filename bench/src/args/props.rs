@@ -1,5 +1,6 @@
 use super::transport::BenchmarkTransportCommand;
 use integration::test_server::Transport;
+use std::num::NonZeroU32;
 
 pub trait BenchmarkKindProps {
     fn message_size(&self) -> u32;
@@ -14,6 +15,26 @@ pub trait BenchmarkKindProps {
     fn disable_parallel_consumer_streams(&self) -> bool;
     fn transport_command(&self) -> &BenchmarkTransportCommand;
     fn validate(&self);
+
+    /// Messages per second sent by each producer, if not set the producers send as fast as possible.
+    fn rate_limit(&self) -> Option<NonZeroU32> {
+        None
+    }
+
+    /// The messages are of the fixed `message_size`, unless the benchmark sends messages of varying sizes.
+    fn min_message_size(&self) -> u32 {
+        self.message_size()
+    }
+
+    fn max_headers(&self) -> u32 {
+        0
+    }
+
+    /// Number of clients changing the metadata (topics, consumer groups and users) while the messages are sent and polled.
+    fn admin_clients(&self) -> u32 {
+        0
+    }
+
     fn inner(&self) -> &dyn BenchmarkKindProps
     where
         Self: std::marker::Sized,
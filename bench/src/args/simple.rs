@@ -1,6 +1,10 @@
+use clap::ValueEnum;
 use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum BenchmarkKind {
     #[display(fmt = "send messages")]
     Send,
@@ -10,4 +14,29 @@ pub enum BenchmarkKind {
     SendAndPoll,
     #[display(fmt = "consumer group poll")]
     ConsumerGroupPoll,
+    #[display(fmt = "end to end latency")]
+    EndToEnd,
+    #[display(fmt = "mixed send and poll")]
+    Mixed,
+    #[display(fmt = "admin operations")]
+    Admin,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Display, ValueEnum)]
+pub enum BenchmarkReportFormat {
+    #[default]
+    #[display(fmt = "json")]
+    Json,
+    #[display(fmt = "csv")]
+    Csv,
+}
+
+impl BenchmarkReportFormat {
+    /// Detects the format based on the file extension, files with .csv extension are CSV, all the other ones JSON.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("csv") => Self::Csv,
+            _ => Self::Json,
+        }
+    }
 }
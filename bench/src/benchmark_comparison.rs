@@ -0,0 +1,319 @@
+use crate::args::kind::CompareArgs;
+use crate::benchmark_report::BenchmarkReport;
+use crate::benchmark_result::BenchmarkStatistics;
+use colored::Colorize;
+use derive_more::Display;
+use iggy::error::IggyError;
+use std::fmt::{Display, Formatter};
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegressionThresholds {
+    /// Maximum allowed drop of the throughput, in percent.
+    pub throughput: f64,
+    /// Maximum allowed increase of the p99 latency, in percent.
+    pub p99_latency: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum ComparedMetric {
+    #[display(fmt = "throughput")]
+    Throughput,
+    #[display(fmt = "messages per second")]
+    MessagesPerSecond,
+    #[display(fmt = "average p99 latency")]
+    P99Latency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricComparison {
+    pub group: &'static str,
+    pub metric: ComparedMetric,
+    pub baseline: f64,
+    pub candidate: f64,
+    pub regression: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkComparison {
+    pub comparisons: Vec<MetricComparison>,
+}
+
+impl ComparedMetric {
+    fn unit(&self) -> &'static str {
+        match self {
+            ComparedMetric::Throughput => "MB/s",
+            ComparedMetric::MessagesPerSecond => "messages/s",
+            ComparedMetric::P99Latency => "ms",
+        }
+    }
+
+    fn value(&self, statistics: &BenchmarkStatistics) -> f64 {
+        match self {
+            ComparedMetric::Throughput => statistics.total_throughput,
+            ComparedMetric::MessagesPerSecond => statistics.messages_per_second,
+            ComparedMetric::P99Latency => statistics.average_p99_latency,
+        }
+    }
+
+    /// Throughput regresses when it drops, latency when it grows.
+    fn is_regression(&self, change_percent: f64, thresholds: &RegressionThresholds) -> bool {
+        match self {
+            ComparedMetric::Throughput | ComparedMetric::MessagesPerSecond => {
+                change_percent < -thresholds.throughput
+            }
+            ComparedMetric::P99Latency => change_percent > thresholds.p99_latency,
+        }
+    }
+}
+
+impl MetricComparison {
+    /// Relative change of the candidate against the baseline, in percent.
+    pub fn change_percent(&self) -> Option<f64> {
+        if self.baseline == 0.0 {
+            return None;
+        }
+        Some((self.candidate - self.baseline) / self.baseline * 100.0)
+    }
+}
+
+impl BenchmarkComparison {
+    /// Compares the statistics of the groups of actors present in both reports.
+    pub fn new(
+        baseline: &BenchmarkReport,
+        candidate: &BenchmarkReport,
+        thresholds: &RegressionThresholds,
+    ) -> Self {
+        let groups = [
+            (
+                "producers",
+                &baseline.summary.producers,
+                &candidate.summary.producers,
+            ),
+            (
+                "consumers",
+                &baseline.summary.consumers,
+                &candidate.summary.consumers,
+            ),
+            ("total", &baseline.summary.total, &candidate.summary.total),
+        ];
+
+        let mut comparisons = Vec::new();
+        for (group, baseline, candidate) in groups {
+            let (Some(baseline), Some(candidate)) = (baseline, candidate) else {
+                continue;
+            };
+            for metric in [
+                ComparedMetric::Throughput,
+                ComparedMetric::MessagesPerSecond,
+                ComparedMetric::P99Latency,
+            ] {
+                let mut comparison = MetricComparison {
+                    group,
+                    metric,
+                    baseline: metric.value(baseline),
+                    candidate: metric.value(candidate),
+                    regression: false,
+                };
+                comparison.regression = comparison
+                    .change_percent()
+                    .is_some_and(|change| metric.is_regression(change, thresholds));
+                comparisons.push(comparison);
+            }
+        }
+
+        Self { comparisons }
+    }
+
+    pub fn has_regressions(&self) -> bool {
+        self.comparisons
+            .iter()
+            .any(|comparison| comparison.regression)
+    }
+}
+
+impl Display for MetricComparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let unit = self.metric.unit();
+        let change = match self.change_percent() {
+            Some(change) => format!("{change:+.2}%"),
+            None => "n/a".to_owned(),
+        };
+        let info = format!(
+            "{} {}: {:.2} {unit} → {:.2} {unit} ({change})",
+            self.group, self.metric, self.baseline, self.candidate
+        );
+        match self.regression {
+            true => write!(f, "{} {}", info.red(), "REGRESSION".red().bold()),
+            false => write!(f, "{}", info.green()),
+        }
+    }
+}
+
+impl Display for BenchmarkComparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for comparison in &self.comparisons {
+            writeln!(f, "{comparison}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the results files and returns `true` if the candidate regressed beyond the thresholds.
+pub fn compare_results(args: &CompareArgs) -> Result<bool, IggyError> {
+    let baseline = BenchmarkReport::load(&args.baseline)?;
+    let candidate = BenchmarkReport::load(&args.candidate)?;
+    if baseline.params != candidate.params {
+        warn!(
+            "Benchmark parameters of the results files differ, the comparison might be inaccurate"
+        );
+    }
+    if baseline.server.version != candidate.server.version {
+        info!(
+            "Comparing results of iggy-server version: {} with version: {}",
+            baseline.server.version, candidate.server.version
+        );
+    }
+
+    let thresholds = RegressionThresholds {
+        throughput: args.max_throughput_regression,
+        p99_latency: args.max_p99_latency_regression,
+    };
+    let comparison = BenchmarkComparison::new(&baseline, &candidate, &thresholds);
+    comparison
+        .to_string()
+        .lines()
+        .for_each(|line| info!("{}", line));
+
+    Ok(comparison.has_regressions())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::simple::BenchmarkKind;
+    use crate::benchmark_report::{BenchmarkParams, BenchmarkSummary, ServerInfo};
+
+    fn statistics(total_throughput: f64, average_p99_latency: f64) -> BenchmarkStatistics {
+        BenchmarkStatistics {
+            total_throughput,
+            messages_per_second: total_throughput * 1000.0,
+            average_p50_latency: 1.0,
+            average_p90_latency: 1.5,
+            average_p95_latency: 2.0,
+            average_p99_latency,
+            average_p999_latency: average_p99_latency * 2.0,
+            average_latency: 1.0,
+            average_throughput: total_throughput / 10.0,
+            total_duration: 10.0,
+        }
+    }
+
+    fn report(
+        producers: Option<BenchmarkStatistics>,
+        total: BenchmarkStatistics,
+    ) -> BenchmarkReport {
+        BenchmarkReport {
+            params: BenchmarkParams {
+                kind: BenchmarkKind::SendAndPoll,
+                transport: "tcp".to_owned(),
+                server_address: "127.0.0.1:8090".to_owned(),
+                messages_per_batch: 1000,
+                message_batches: 1000,
+                message_size: 1000,
+                min_message_size: 1000,
+                max_headers: 0,
+                rate_limit: None,
+                streams: 10,
+                partitions: Some(1),
+                consumer_groups: None,
+                producers: 10,
+                consumers: 10,
+                admin_clients: 0,
+                warmup_time: "1s".to_owned(),
+                sampling_interval: "100ms".to_owned(),
+            },
+            server: ServerInfo {
+                version: "0.4.0".to_owned(),
+                hostname: "localhost".to_owned(),
+                os_name: "Linux".to_owned(),
+                os_version: "1".to_owned(),
+                kernel_version: "6.0".to_owned(),
+                config: None,
+            },
+            summary: BenchmarkSummary {
+                producers,
+                consumers: None,
+                total: Some(total),
+            },
+            actors: Vec::new(),
+        }
+    }
+
+    const THRESHOLDS: RegressionThresholds = RegressionThresholds {
+        throughput: 5.0,
+        p99_latency: 10.0,
+    };
+
+    #[test]
+    fn changes_within_thresholds_should_not_be_regressions() {
+        let baseline = report(None, statistics(100.0, 2.0));
+        let candidate = report(None, statistics(96.0, 2.1));
+
+        let comparison = BenchmarkComparison::new(&baseline, &candidate, &THRESHOLDS);
+
+        assert_eq!(comparison.comparisons.len(), 3);
+        assert!(!comparison.has_regressions());
+    }
+
+    #[test]
+    fn throughput_drop_beyond_threshold_should_be_regression() {
+        let baseline = report(None, statistics(100.0, 2.0));
+        let candidate = report(None, statistics(90.0, 2.0));
+
+        let comparison = BenchmarkComparison::new(&baseline, &candidate, &THRESHOLDS);
+
+        let regressions = comparison
+            .comparisons
+            .iter()
+            .filter(|comparison| comparison.regression)
+            .map(|comparison| comparison.metric)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            regressions,
+            vec![
+                ComparedMetric::Throughput,
+                ComparedMetric::MessagesPerSecond
+            ]
+        );
+    }
+
+    #[test]
+    fn p99_latency_increase_beyond_threshold_should_be_regression() {
+        let baseline = report(None, statistics(100.0, 2.0));
+        let candidate = report(None, statistics(120.0, 2.5));
+
+        let comparison = BenchmarkComparison::new(&baseline, &candidate, &THRESHOLDS);
+
+        let regressions = comparison
+            .comparisons
+            .iter()
+            .filter(|comparison| comparison.regression)
+            .map(|comparison| comparison.metric)
+            .collect::<Vec<_>>();
+        assert_eq!(regressions, vec![ComparedMetric::P99Latency]);
+    }
+
+    #[test]
+    fn only_groups_present_in_both_reports_should_be_compared() {
+        let baseline = report(Some(statistics(50.0, 2.0)), statistics(100.0, 2.0));
+        let candidate = report(None, statistics(100.0, 2.0));
+
+        let comparison = BenchmarkComparison::new(&baseline, &candidate, &THRESHOLDS);
+
+        assert!(comparison
+            .comparisons
+            .iter()
+            .all(|comparison| comparison.group == "total"));
+    }
+}
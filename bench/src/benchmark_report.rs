@@ -0,0 +1,440 @@
+use crate::args::common::IggyBenchArgs;
+use crate::args::kind::BenchmarkKindCommand;
+use crate::args::simple::{BenchmarkKind, BenchmarkReportFormat};
+use crate::benchmark_result::{
+    BenchmarkResult, BenchmarkResults, BenchmarkStatistics, ThroughputSample,
+};
+use derive_more::Display;
+use iggy::client::SystemClient;
+use iggy::clients::client::{IggyClient, IggyClientBackgroundConfig};
+use iggy::error::IggyError;
+use integration::test_server::{login_root, ClientFactory};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+use tracing::{error, warn};
+
+/// Machine-readable results of the benchmark, written to the file and used for the comparison of the runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    pub params: BenchmarkParams,
+    pub server: ServerInfo,
+    pub summary: BenchmarkSummary,
+    pub actors: Vec<ActorReport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkParams {
+    pub kind: BenchmarkKind,
+    pub transport: String,
+    pub server_address: String,
+    pub messages_per_batch: u32,
+    pub message_batches: u32,
+    pub message_size: u32,
+    #[serde(default)]
+    pub min_message_size: u32,
+    #[serde(default)]
+    pub max_headers: u32,
+    /// Messages per second sent by each producer, if limited.
+    #[serde(default)]
+    pub rate_limit: Option<u32>,
+    pub streams: u32,
+    pub partitions: Option<u32>,
+    pub consumer_groups: Option<u32>,
+    pub producers: u32,
+    pub consumers: u32,
+    #[serde(default)]
+    pub admin_clients: u32,
+    pub warmup_time: String,
+    pub sampling_interval: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerInfo {
+    pub version: String,
+    pub hostname: String,
+    pub os_name: String,
+    pub os_version: String,
+    pub kernel_version: String,
+    /// Effective configuration of the server, available only if its data directory is accessible by the benchmark.
+    pub config: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkSummary {
+    pub producers: Option<BenchmarkStatistics>,
+    pub consumers: Option<BenchmarkStatistics>,
+    pub total: Option<BenchmarkStatistics>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActorKind {
+    #[display(fmt = "producer")]
+    Producer,
+    #[display(fmt = "consumer")]
+    Consumer,
+    #[display(fmt = "admin")]
+    Admin,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActorReport {
+    pub kind: ActorKind,
+    pub actor_id: u32,
+    pub total_messages: u64,
+    pub total_size_bytes: u64,
+    pub duration_s: f64,
+    pub throughput_mb_s: f64,
+    pub messages_per_second: f64,
+    pub latency: LatencyReport,
+    pub throughput_series: Vec<ThroughputSample>,
+}
+
+/// Latencies of the actor, in milliseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencyReport {
+    pub average_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub p999_ms: f64,
+}
+
+impl BenchmarkParams {
+    pub fn new(args: &IggyBenchArgs) -> Self {
+        let (partitions, consumer_groups) = match &args.benchmark_kind {
+            BenchmarkKindCommand::ConsumerGroupPoll(args) => {
+                (None, Some(args.consumer_groups.get()))
+            }
+            _ => (Some(args.number_of_partitions()), None),
+        };
+
+        Self {
+            kind: args.benchmark_kind.as_simple_kind(),
+            transport: args.transport().to_string(),
+            server_address: args.server_address().to_owned(),
+            messages_per_batch: args.messages_per_batch(),
+            message_batches: args.message_batches(),
+            message_size: args.message_size(),
+            min_message_size: args.min_message_size(),
+            max_headers: args.max_headers(),
+            rate_limit: args.rate_limit().map(|rate_limit| rate_limit.get()),
+            streams: args.number_of_streams(),
+            partitions,
+            consumer_groups,
+            producers: 0,
+            consumers: 0,
+            admin_clients: 0,
+            warmup_time: args.warmup_time().to_string(),
+            sampling_interval: args.sampling_interval.to_string(),
+        }
+    }
+}
+
+impl ServerInfo {
+    /// Reads the details of the server from its stats and the configuration from the data directory.
+    pub async fn fetch(
+        client_factory: &dyn ClientFactory,
+        server_system_path: &str,
+    ) -> Result<Self, IggyError> {
+        let client = client_factory.create_client().await;
+        let client = IggyClient::create(
+            client,
+            IggyClientBackgroundConfig::default(),
+            None,
+            None,
+            None,
+        );
+        login_root(&client).await;
+        let stats = client.get_stats().await?;
+
+        Ok(Self {
+            version: stats.iggy_server_version,
+            hostname: stats.hostname,
+            os_name: stats.os_name,
+            os_version: stats.os_version,
+            kernel_version: stats.kernel_version,
+            config: Self::read_config(server_system_path),
+        })
+    }
+
+    fn read_config(server_system_path: &str) -> Option<Value> {
+        let path = Path::new(server_system_path)
+            .join("runtime")
+            .join("current_config.toml");
+        let content = std::fs::read_to_string(&path).ok()?;
+        match toml::from_str::<toml::Value>(&content).map(serde_json::to_value) {
+            Ok(Ok(mut config)) => {
+                redact_secrets(&mut config);
+                Some(config)
+            }
+            _ => {
+                warn!("Failed to parse server config file: {}", path.display());
+                None
+            }
+        }
+    }
+}
+
+impl From<&BenchmarkResult> for ActorReport {
+    fn from(result: &BenchmarkResult) -> Self {
+        let duration = (result.end_timestamp - result.start_timestamp).as_secs_f64();
+        let to_ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let percentiles = &result.latency_percentiles;
+
+        Self {
+            kind: match result.kind {
+                BenchmarkKind::Send => ActorKind::Producer,
+                BenchmarkKind::Admin => ActorKind::Admin,
+                _ => ActorKind::Consumer,
+            },
+            actor_id: result.actor_id,
+            total_messages: result.total_messages,
+            total_size_bytes: result.total_size_bytes,
+            duration_s: duration,
+            throughput_mb_s: result.total_size_bytes as f64 / duration / 1e6,
+            messages_per_second: result.total_messages as f64 / duration,
+            latency: LatencyReport {
+                average_ms: to_ms(result.average_latency),
+                p50_ms: to_ms(percentiles.p50),
+                p90_ms: to_ms(percentiles.p90),
+                p95_ms: to_ms(percentiles.p95),
+                p99_ms: to_ms(percentiles.p99),
+                p999_ms: to_ms(percentiles.p999),
+            },
+            throughput_series: Vec::new(),
+        }
+    }
+}
+
+impl BenchmarkReport {
+    pub fn new(
+        mut params: BenchmarkParams,
+        server: ServerInfo,
+        results: &BenchmarkResults,
+        sampling_interval: Duration,
+    ) -> Self {
+        let mut actors = results
+            .iter()
+            .map(|result| ActorReport {
+                throughput_series: result.throughput_series(sampling_interval),
+                ..ActorReport::from(result)
+            })
+            .collect::<Vec<_>>();
+        actors.sort_by_key(|actor| (actor.kind, actor.actor_id));
+        let count =
+            |kind: ActorKind| actors.iter().filter(|actor| actor.kind == kind).count() as u32;
+        params.producers = count(ActorKind::Producer);
+        params.consumers = count(ActorKind::Consumer);
+        params.admin_clients = count(ActorKind::Admin);
+
+        Self {
+            params,
+            server,
+            summary: BenchmarkSummary {
+                producers: results.producer_statistics(),
+                consumers: results.consumer_statistics(),
+                total: results.total_statistics(),
+            },
+            actors,
+        }
+    }
+
+    /// Only the JSON results files can be loaded, CSV is meant for the external tools.
+    pub fn load(path: &Path) -> Result<Self, IggyError> {
+        let file = File::open(path)?;
+        serde_json::from_reader(BufReader::new(file)).map_err(|e| {
+            error!(
+                "Failed to read JSON results file: {}, error: {}",
+                path.display(),
+                e
+            );
+            IggyError::IoError(e.into())
+        })
+    }
+
+    pub fn save(&self, path: &Path, format: BenchmarkReportFormat) -> Result<(), IggyError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        match format {
+            BenchmarkReportFormat::Json => {
+                serde_json::to_writer_pretty(&mut writer, self).map_err(std::io::Error::from)?;
+                writeln!(writer)?;
+            }
+            BenchmarkReportFormat::Csv => writer.write_all(self.to_csv().as_bytes())?,
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// CSV is written in the long format, one value per row, so all the sections of the report,
+    /// including the throughput time series of each actor, fit into a single table.
+    pub fn to_csv(&self) -> String {
+        let mut rows = Vec::new();
+        let mut push = |section: &str, name: &str, time_s: Option<f64>, values: Value| {
+            let mut flattened = Vec::new();
+            flatten(String::new(), values, &mut flattened);
+            for (metric, value) in flattened {
+                let time_s = time_s.map(|time| format!("{time:.3}")).unwrap_or_default();
+                rows.push([section.to_owned(), name.to_owned(), time_s, metric, value]);
+            }
+        };
+
+        push("params", "", None, to_value(&self.params));
+        let mut server = to_value(&self.server);
+        let config = server
+            .as_object_mut()
+            .and_then(|server| server.remove("config"));
+        push("server", "", None, server);
+        if let Some(config) = config {
+            push("config", "", None, config);
+        }
+        for (name, statistics) in [
+            ("producers", &self.summary.producers),
+            ("consumers", &self.summary.consumers),
+            ("total", &self.summary.total),
+        ] {
+            if let Some(statistics) = statistics {
+                push("summary", name, None, to_value(statistics));
+            }
+        }
+        for actor in &self.actors {
+            let name = format!("{}_{}", actor.kind, actor.actor_id);
+            let mut values = to_value(actor);
+            if let Some(values) = values.as_object_mut() {
+                values.remove("kind");
+                values.remove("actor_id");
+                values.remove("throughput_series");
+            }
+            push("actor", &name, None, values);
+            for sample in &actor.throughput_series {
+                let mut values = to_value(sample);
+                if let Some(values) = values.as_object_mut() {
+                    values.remove("time_s");
+                }
+                push("series", &name, Some(sample.time_s), values);
+            }
+        }
+
+        let mut csv = String::from("section,name,time_s,metric,value\n");
+        for row in rows {
+            let row = row.iter().map(|cell| escape_csv(cell)).collect::<Vec<_>>();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or_default()
+}
+
+fn flatten(prefix: String, value: Value, output: &mut Vec<(String, String)>) {
+    let key = |name: &str| match prefix.is_empty() {
+        true => name.to_owned(),
+        false => format!("{prefix}.{name}"),
+    };
+    match value {
+        Value::Object(values) => {
+            for (name, value) in values {
+                flatten(key(&name), value, output);
+            }
+        }
+        Value::Array(values) => {
+            for (index, value) in values.into_iter().enumerate() {
+                flatten(key(&index.to_string()), value, output);
+            }
+        }
+        Value::Null => output.push((prefix, String::new())),
+        Value::String(value) => output.push((prefix, value)),
+        value => output.push((prefix, value.to_string())),
+    }
+}
+
+/// Secrets, passwords and encryption keys of the server config must not end up in the shared results files.
+fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(values) => {
+            for (name, value) in values.iter_mut() {
+                let is_secret =
+                    name == "key" || name.contains("secret") || name.contains("password");
+                match value {
+                    Value::String(secret) if is_secret => *secret = "***".to_owned(),
+                    value => redact_secrets(value),
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+fn escape_csv(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn nested_values_should_be_flattened_with_dotted_keys() {
+        let mut output = Vec::new();
+
+        flatten(
+            String::new(),
+            json!({"tcp": {"enabled": true, "address": "0.0.0.0:8090"}, "ids": [1, 2], "name": null}),
+            &mut output,
+        );
+
+        assert_eq!(
+            output,
+            vec![
+                ("ids.0".to_owned(), "1".to_owned()),
+                ("ids.1".to_owned(), "2".to_owned()),
+                ("name".to_owned(), "".to_owned()),
+                ("tcp.address".to_owned(), "0.0.0.0:8090".to_owned()),
+                ("tcp.enabled".to_owned(), "true".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn secrets_of_server_config_should_be_redacted() {
+        let mut config = json!({
+            "http": {"jwt": {"encoding_secret": "top_secret", "expiry": "1h"}},
+            "root_user": {"username": "iggy", "password": "iggy123"},
+            "encryption": {"enabled": false, "key": "abc"},
+            "personal_access_token": {"use_base64_secret": false},
+        });
+
+        redact_secrets(&mut config);
+
+        assert_eq!(
+            config,
+            json!({
+                "http": {"jwt": {"encoding_secret": "***", "expiry": "1h"}},
+                "root_user": {"username": "iggy", "password": "***"},
+                "encryption": {"enabled": false, "key": "***"},
+                "personal_access_token": {"use_base64_secret": false},
+            })
+        );
+    }
+
+    #[test]
+    fn csv_cells_with_special_characters_should_be_quoted() {
+        assert_eq!(escape_csv("plain"), "plain");
+        assert_eq!(escape_csv("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -1,5 +1,6 @@
 use crate::args::simple::BenchmarkKind;
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::{
     fmt::{Display, Formatter},
@@ -16,6 +17,77 @@ pub struct BenchmarkResult {
     pub latency_percentiles: LatencyPercentiles,
     pub total_size_bytes: u64,
     pub total_messages: u64,
+    pub actor_id: u32,
+    pub batches: Vec<BatchRecord>,
+}
+
+/// Record of a single batch sent or polled by the actor, used to build the throughput time series.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchRecord {
+    /// Time elapsed since the start of the benchmark when the batch was processed.
+    pub elapsed: Duration,
+    pub size_bytes: u64,
+    pub messages: u64,
+}
+
+/// Throughput of the actor within a single sampling interval.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThroughputSample {
+    /// End of the sampling interval, in seconds since the start of the benchmark.
+    pub time_s: f64,
+    pub throughput_mb_s: f64,
+    pub messages_per_second: f64,
+}
+
+impl BenchmarkResult {
+    /// Splits the batches into the consecutive intervals and calculates the throughput within each of them,
+    /// the intervals without any processed batches are included as well, so the stalls are visible.
+    pub fn throughput_series(&self, interval: Duration) -> Vec<ThroughputSample> {
+        let mut samples = Vec::new();
+        let mut interval_start = Duration::ZERO;
+        let mut size_bytes = 0;
+        let mut messages = 0;
+        for batch in &self.batches {
+            while batch.elapsed > interval_start + interval {
+                interval_start += interval;
+                samples.push(ThroughputSample::new(
+                    interval_start,
+                    interval,
+                    size_bytes,
+                    messages,
+                ));
+                size_bytes = 0;
+                messages = 0;
+            }
+            size_bytes += batch.size_bytes;
+            messages += batch.messages;
+        }
+
+        if let Some(last) = self.batches.last() {
+            let remaining = last.elapsed - interval_start;
+            if !remaining.is_zero() {
+                samples.push(ThroughputSample::new(
+                    last.elapsed,
+                    remaining,
+                    size_bytes,
+                    messages,
+                ));
+            }
+        }
+
+        samples
+    }
+}
+
+impl ThroughputSample {
+    fn new(end: Duration, duration: Duration, size_bytes: u64, messages: u64) -> Self {
+        let duration = duration.as_secs_f64();
+        Self {
+            time_s: end.as_secs_f64(),
+            throughput_mb_s: size_bytes as f64 / duration / 1e6,
+            messages_per_second: messages as f64 / duration,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -36,24 +108,51 @@ impl From<Vec<BenchmarkResult>> for BenchmarkResults {
         Self { results }
     }
 }
-#[derive(Debug, Clone, PartialEq)]
-struct BenchmarkStatistics {
-    total_throughput: f64,
-    messages_per_second: f64,
-    average_p50_latency: f64,
-    average_p90_latency: f64,
-    average_p95_latency: f64,
-    average_p99_latency: f64,
-    average_p999_latency: f64,
-    average_latency: f64,
-    average_throughput: f64,
-    total_duration: f64,
+/// Statistics aggregated over the results of the actors, throughputs are in MB/s, latencies in ms and duration in s.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkStatistics {
+    pub total_throughput: f64,
+    pub messages_per_second: f64,
+    pub average_p50_latency: f64,
+    pub average_p90_latency: f64,
+    pub average_p95_latency: f64,
+    pub average_p99_latency: f64,
+    pub average_p999_latency: f64,
+    pub average_latency: f64,
+    pub average_throughput: f64,
+    pub total_duration: f64,
 }
 
 #[derive(Debug, Clone)]
 struct ImpossibleBenchmarkKind;
 
 impl BenchmarkResults {
+    pub fn iter(&self) -> impl Iterator<Item = &BenchmarkResult> {
+        self.results.iter()
+    }
+
+    /// Returns the statistics of the producers, if there were any.
+    pub fn producer_statistics(&self) -> Option<BenchmarkStatistics> {
+        self.calculate_statistics(|x| x.kind == BenchmarkKind::Send)
+    }
+
+    /// Returns the statistics of the consumers, if there were any.
+    pub fn consumer_statistics(&self) -> Option<BenchmarkStatistics> {
+        self.calculate_statistics(|x| x.kind == BenchmarkKind::Poll)
+    }
+
+    /// Returns the statistics of the admins, if there were any, each operation is counted as a single message.
+    pub fn admin_statistics(&self) -> Option<BenchmarkStatistics> {
+        self.calculate_statistics(|x| x.kind == BenchmarkKind::Admin)
+    }
+
+    /// Returns the statistics of all the producers and consumers, if there were any.
+    pub fn total_statistics(&self) -> Option<BenchmarkStatistics> {
+        self.calculate_statistics(|x| {
+            x.kind == BenchmarkKind::Send || x.kind == BenchmarkKind::Poll
+        })
+    }
+
     fn get_test_type(&self) -> Result<BenchmarkKind, ImpossibleBenchmarkKind> {
         let result_kinds = self
             .results
@@ -71,90 +170,40 @@ impl BenchmarkResults {
         }
     }
 
-    fn calculate_statistics<F>(&self, mut predicate: F) -> BenchmarkStatistics
+    fn calculate_statistics<F>(&self, predicate: F) -> Option<BenchmarkStatistics>
     where
         F: FnMut(&&BenchmarkResult) -> bool,
     {
-        let total_size_bytes = self
-            .results
-            .iter()
-            .filter(&mut predicate)
-            .map(|r| r.total_size_bytes)
-            .sum::<u64>();
-        let total_duration = (self
-            .results
+        let results = self.results.iter().filter(predicate).collect::<Vec<_>>();
+        if results.is_empty() {
+            return None;
+        }
+
+        let count = results.len() as u32;
+        let average_ms = |duration: fn(&BenchmarkResult) -> Duration| {
+            (results.iter().map(|r| duration(r)).sum::<Duration>() / count).as_secs_f64() * 1000.0
+        };
+
+        let total_size_bytes = results.iter().map(|r| r.total_size_bytes).sum::<u64>();
+        let total_duration = (results
             .iter()
-            .filter(&mut predicate)
             .map(|r| r.end_timestamp - r.start_timestamp)
             .sum::<Duration>()
-            / self.results.len() as u32)
+            / count)
             .as_secs_f64();
-        let total_messages = self
-            .results
-            .iter()
-            .filter(&mut predicate)
-            .map(|r| r.total_messages)
-            .sum::<u64>();
-        let average_p50_latency = (self
-            .results
-            .iter()
-            .filter(&mut predicate)
-            .map(|r| r.latency_percentiles.p50)
-            .sum::<Duration>()
-            / self.results.len() as u32)
-            .as_secs_f64()
-            * 1000.0;
-        let average_p95_latency = (self
-            .results
-            .iter()
-            .filter(&mut predicate)
-            .map(|r| r.latency_percentiles.p95)
-            .sum::<Duration>()
-            / self.results.len() as u32)
-            .as_secs_f64()
-            * 1000.0;
-        let average_p90_latency = (self
-            .results
-            .iter()
-            .filter(&mut predicate)
-            .map(|r| r.latency_percentiles.p90)
-            .sum::<Duration>()
-            / self.results.len() as u32)
-            .as_secs_f64()
-            * 1000.0;
-        let average_p99_latency = (self
-            .results
-            .iter()
-            .filter(&mut predicate)
-            .map(|r| r.latency_percentiles.p99)
-            .sum::<Duration>()
-            / self.results.len() as u32)
-            .as_secs_f64()
-            * 1000.0;
-        let average_p999_latency = (self
-            .results
-            .iter()
-            .filter(&mut predicate)
-            .map(|r| r.latency_percentiles.p999)
-            .sum::<Duration>()
-            / self.results.len() as u32)
-            .as_secs_f64()
-            * 1000.0;
-        let average_latency = (self
-            .results
-            .iter()
-            .filter(&mut predicate)
-            .map(|r| r.average_latency)
-            .sum::<Duration>()
-            / self.results.len() as u32)
-            .as_secs_f64()
-            * 1000.0;
+        let total_messages = results.iter().map(|r| r.total_messages).sum::<u64>();
+        let average_p50_latency = average_ms(|r| r.latency_percentiles.p50);
+        let average_p90_latency = average_ms(|r| r.latency_percentiles.p90);
+        let average_p95_latency = average_ms(|r| r.latency_percentiles.p95);
+        let average_p99_latency = average_ms(|r| r.latency_percentiles.p99);
+        let average_p999_latency = average_ms(|r| r.latency_percentiles.p999);
+        let average_latency = average_ms(|r| r.average_latency);
         let average_throughput =
-            total_size_bytes as f64 / total_duration / 1e6 / self.results.len() as f64;
+            total_size_bytes as f64 / total_duration / 1e6 / results.len() as f64;
         let total_throughput = total_size_bytes as f64 / total_duration / 1e6;
         let messages_per_second = total_messages as f64 / total_duration;
 
-        BenchmarkStatistics {
+        Some(BenchmarkStatistics {
             total_throughput,
             messages_per_second,
             average_latency,
@@ -165,7 +214,14 @@ impl BenchmarkResults {
             average_p999_latency,
             average_throughput,
             total_duration,
-        }
+        })
+    }
+}
+
+impl Display for BenchmarkStatistics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "total throughput: {:.2} MB/s, {:.0} messages/s, average throughput: {:.2} MB/s, average p50 latency: {:.2} ms, average p90 latency: {:.2} ms, average p95 latency: {:.2} ms, average p99 latency: {:.2} ms, average p999 latency: {:.2} ms, average latency: {:.2} ms, total duration: {:.2} s",
+        self.total_throughput, self.messages_per_second, self.average_throughput, self.average_p50_latency, self.average_p90_latency, self.average_p95_latency, self.average_p99_latency, self.average_p999_latency, self.average_latency, self.total_duration)
     }
 }
 
@@ -173,25 +229,117 @@ impl Display for BenchmarkResults {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if let Ok(test_type) = self.get_test_type() {
             if test_type == BenchmarkKind::SendAndPoll {
-                let producer_statics = self.calculate_statistics(|x| x.kind == BenchmarkKind::Send);
-                let consumer_statics = self.calculate_statistics(|x| x.kind == BenchmarkKind::Poll);
+                if let (Some(producer_statics), Some(consumer_statics)) =
+                    (self.producer_statistics(), self.consumer_statistics())
+                {
+                    let producer_info = format!("Producer results: {producer_statics}").green();
+                    let consumer_info = format!("Consumer results: {consumer_statics}").green();
+                    writeln!(f, "{}, {}", producer_info, consumer_info)?;
+                }
+            }
+        }
 
-                let producer_info = format!("Producer results: total throughput: {:.2} MB/s, {:.0} messages/s, average throughput: {:.2} MB/s, average p50 latency: {:.2} ms, average p90 latency: {:.2} ms, average p95 latency: {:.2} ms, average p99 latency: {:.2} ms, average p999 latency: {:.2} ms, average latency: {:.2} ms, total duration: {:.2} s",
-                producer_statics.total_throughput, producer_statics.messages_per_second, producer_statics.average_throughput, producer_statics.average_p50_latency, producer_statics.average_p90_latency, producer_statics.average_p95_latency, producer_statics.average_p99_latency, producer_statics.average_p999_latency, producer_statics.average_latency, producer_statics.total_duration).green();
+        let Some(results) = self.total_statistics() else {
+            return writeln!(f, "{}", "No results".red());
+        };
 
-                let consumer_info = format!("Consumer results: total throughput: {:.2} MB/s, {:.0} messages/s, average throughput: {:.2} MB/s, average p50 latency: {:.2} ms, average p90 latency: {:.2} ms, average p95 latency: {:.2} ms, average p99 latency: {:.2} ms, average p999 latency: {:.2} ms, average latency: {:.2} ms, total duration: {:.2} s",
-                consumer_statics.total_throughput, consumer_statics.messages_per_second, consumer_statics.average_throughput, consumer_statics.average_p50_latency, consumer_statics.average_p90_latency, consumer_statics.average_p95_latency, consumer_statics.average_p99_latency, consumer_statics.average_p999_latency, consumer_statics.average_latency, consumer_statics.total_duration).green();
-                writeln!(f, "{}, {}", producer_info, consumer_info)?;
-            }
+        let summary_info = format!("Results: {results}").green();
+        writeln!(f, "{}", summary_info)?;
+
+        if let Some(admins) = self.admin_statistics() {
+            let admin_info = format!(
+                "Admin results: {:.0} operations/s, average p50 latency: {:.2} ms, average p99 latency: {:.2} ms, average latency: {:.2} ms",
+                admins.messages_per_second,
+                admins.average_p50_latency,
+                admins.average_p99_latency,
+                admins.average_latency
+            )
+            .green();
+            writeln!(f, "{}", admin_info)?;
         }
 
-        let results = self.calculate_statistics(|x| {
-            x.kind == BenchmarkKind::Send || x.kind == BenchmarkKind::Poll
-        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(batches: Vec<BatchRecord>) -> BenchmarkResult {
+        let start_timestamp = Instant::now();
+        let percentiles = LatencyPercentiles {
+            p50: Duration::from_millis(1),
+            p90: Duration::from_millis(1),
+            p95: Duration::from_millis(1),
+            p99: Duration::from_millis(1),
+            p999: Duration::from_millis(1),
+        };
+        BenchmarkResult {
+            kind: BenchmarkKind::Send,
+            start_timestamp,
+            end_timestamp: start_timestamp + Duration::from_secs(1),
+            average_latency: Duration::from_millis(1),
+            latency_percentiles: percentiles,
+            total_size_bytes: batches.iter().map(|batch| batch.size_bytes).sum(),
+            total_messages: batches.iter().map(|batch| batch.messages).sum(),
+            actor_id: 1,
+            batches,
+        }
+    }
+
+    fn batch(elapsed_ms: u64) -> BatchRecord {
+        BatchRecord {
+            elapsed: Duration::from_millis(elapsed_ms),
+            size_bytes: 1_000_000,
+            messages: 1000,
+        }
+    }
+
+    #[test]
+    fn throughput_series_should_include_empty_intervals_and_last_partial_interval() {
+        let result = result(vec![batch(100), batch(400), batch(1200), batch(1250)]);
+
+        let series = result.throughput_series(Duration::from_millis(500));
+
+        let series = series
+            .iter()
+            .map(|sample| {
+                (
+                    (sample.time_s * 1000.0).round() as u64,
+                    sample.throughput_mb_s.round() as u64,
+                    sample.messages_per_second.round() as u64,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(series, vec![(500, 4, 4000), (1000, 0, 0), (1250, 8, 8000)]);
+    }
+
+    #[test]
+    fn statistics_should_be_calculated_only_for_matching_results() {
+        let results = BenchmarkResults::from(vec![result(vec![batch(1000)])]);
+
+        assert!(results.consumer_statistics().is_none());
+        let statistics = results.producer_statistics().unwrap();
+        assert_eq!(statistics.total_throughput, 1.0);
+        assert_eq!(statistics.messages_per_second, 1000.0);
+        assert_eq!(statistics.average_p99_latency, 1.0);
+    }
 
-        let summary_info = format!("Results: total throughput: {:.2} MB/s, {:.0} messages/s, average throughput: {:.2} MB/s, average p50 latency: {:.2} ms, average p90 latency: {:.2} ms, average p95 latency: {:.2} ms, average p99 latency: {:.2} ms, average p999 latency: {:.2} ms, average latency: {:.2} ms, total duration: {:.2} s",
-        results.total_throughput, results.messages_per_second, results.average_throughput, results.average_p50_latency, results.average_p90_latency, results.average_p95_latency, results.average_p99_latency, results.average_p999_latency, results.average_latency, results.total_duration).green();
+    #[test]
+    fn admin_results_should_not_be_included_in_total_statistics() {
+        let admin = BenchmarkResult {
+            kind: BenchmarkKind::Admin,
+            total_size_bytes: 0,
+            total_messages: 50,
+            ..result(Vec::new())
+        };
+        let results = BenchmarkResults::from(vec![result(vec![batch(1000)]), admin]);
 
-        writeln!(f, "{}", summary_info)
+        let total = results.total_statistics().unwrap();
+        assert_eq!(total.messages_per_second, 1000.0);
+        let admins = results.admin_statistics().unwrap();
+        assert_eq!(admins.messages_per_second, 50.0);
+        assert_eq!(admins.total_throughput, 0.0);
     }
 }
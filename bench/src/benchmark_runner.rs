@@ -1,4 +1,5 @@
 use crate::args::common::IggyBenchArgs;
+use crate::benchmark_report::{BenchmarkParams, BenchmarkReport, ServerInfo};
 use crate::benchmark_result::BenchmarkResults;
 use crate::benchmarks::benchmark::Benchmarkable;
 use crate::server_starter::start_server_if_needed;
@@ -30,6 +31,11 @@ impl BenchmarkRunner {
         let server_addr = args.server_address();
         info!("Starting to benchmark: {transport} with server: {server_addr}",);
 
+        let output = args.output();
+        let params = BenchmarkParams::new(&args);
+        let sampling_interval = args.sampling_interval.get_duration();
+        let server_system_path = args.server_system_path.clone();
+
         let mut benchmark: Box<dyn Benchmarkable> = args.into();
         let mut join_handles = benchmark.run().await?;
 
@@ -53,6 +59,18 @@ impl BenchmarkRunner {
             .to_string()
             .split('\n')
             .for_each(|result| info!("{}", result));
+
+        if let Some((file, format)) = output {
+            let server =
+                ServerInfo::fetch(benchmark.client_factory().as_ref(), &server_system_path).await?;
+            let report = BenchmarkReport::new(params, server, &results, sampling_interval);
+            report.save(&file, format)?;
+            info!(
+                "Benchmark results written to file: {} in {} format",
+                file.display(),
+                format
+            );
+        }
         Ok(())
     }
 }
@@ -38,10 +38,15 @@ impl From<IggyBenchArgs> for Box<dyn Benchmarkable> {
             BenchmarkKind::ConsumerGroupPoll => {
                 Box::new(ConsumerGroupBenchmark::new(Arc::new(args), client_factory))
             }
-            BenchmarkKind::SendAndPoll => Box::new(SendAndPollMessagesBenchmark::new(
-                Arc::new(args),
-                client_factory,
-            )),
+            BenchmarkKind::SendAndPoll | BenchmarkKind::EndToEnd | BenchmarkKind::Mixed => {
+                Box::new(SendAndPollMessagesBenchmark::new(
+                    Arc::new(args),
+                    client_factory,
+                ))
+            }
+            BenchmarkKind::Admin => {
+                unreachable!("Admin operations are performed only alongside the other benchmarks")
+            }
         }
     }
 }
@@ -54,6 +54,7 @@ impl Benchmarkable for PollMessagesBenchmark {
                 messages_per_batch,
                 message_batches,
                 warmup_time,
+                None,
             );
 
             let future = Box::pin(async move { consumer.run().await });
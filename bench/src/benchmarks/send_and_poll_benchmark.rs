@@ -1,14 +1,17 @@
 use super::benchmark::{BenchmarkFutures, Benchmarkable};
+use crate::admin::Admin;
 use crate::args::common::IggyBenchArgs;
 use crate::args::simple::BenchmarkKind;
 use crate::consumer::Consumer;
-use crate::producer::Producer;
+use crate::producer::{MessagesProfile, Producer};
 use async_trait::async_trait;
 use colored::Colorize;
 use human_format::Formatter;
 use iggy::utils::byte_size::IggyByteSize;
+use iggy::utils::timestamp::IggyTimestamp;
 use integration::test_server::ClientFactory;
 use std::fmt::Display;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use tracing::info;
 
@@ -31,7 +34,7 @@ impl Display for SendAndPollMessagesBenchmark {
         let total_messages = self.total_messages();
         let processed = IggyByteSize::from(total_messages * self.args().message_size() as u64);
         let total_messages_human_readable = Formatter::new().format(total_messages as f64);
-        let info = format!("Benchmark: {}, transport: {}, total messages: {}, processed: {}, {} streams, {} messages per batch, {} batches, {} bytes per message, {} producers, {} consumers",
+        let mut info = format!("Benchmark: {}, transport: {}, total messages: {}, processed: {}, {} streams, {} messages per batch, {} batches, {}, {} producers, {} consumers",
                 self.kind(),
                 self.args().transport(),
                 total_messages_human_readable,
@@ -39,12 +42,27 @@ impl Display for SendAndPollMessagesBenchmark {
                 self.args().number_of_streams(),
                 self.args().messages_per_batch(),
                 self.args().message_batches(),
-                self.args().message_size(),
+                MessagesProfile::from(self.args()),
                 self.args().producers(),
                 self.args().consumers(),
-            ).green();
+            );
+        if let Some(rate_limit) = self.args().rate_limit() {
+            info.push_str(&format!(
+                ", rate limit: {} messages/s per producer",
+                rate_limit
+            ));
+        }
+        if self.args().admin_clients() > 0 {
+            info.push_str(&format!(
+                ", {} admin clients changing the metadata",
+                self.args().admin_clients()
+            ));
+        }
+        if self.kind() == BenchmarkKind::EndToEnd {
+            info.push_str(", latency measured from send to poll");
+        }
 
-        writeln!(f, "{}", info)
+        writeln!(f, "{}", info.green())
     }
 }
 
@@ -59,11 +77,20 @@ impl Benchmarkable for SendAndPollMessagesBenchmark {
         let parallel_consumer_streams = !self.args.disable_parallel_consumer_streams();
         let messages_per_batch = self.args.messages_per_batch();
         let message_batches = self.args.message_batches();
-        let message_size = self.args.message_size();
+        let messages = MessagesProfile::from(self.args.as_ref());
+        let rate_limit = self.args.rate_limit();
         let partitions_count = self.args.number_of_partitions();
         let warmup_time = self.args.warmup_time();
-        let mut futures: BenchmarkFutures =
-            Ok(Vec::with_capacity((producers + consumers) as usize));
+        let end_to_end_since = match self.kind() {
+            BenchmarkKind::EndToEnd => Some(IggyTimestamp::now()),
+            _ => None,
+        };
+        let admin_clients = self.args.admin_clients();
+        // The admins keep changing the metadata until all the producers and consumers are finished.
+        let active_actors = Arc::new(AtomicU32::new(producers + consumers));
+        let mut futures: BenchmarkFutures = Ok(Vec::with_capacity(
+            (producers + consumers + admin_clients) as usize,
+        ));
         for producer_id in 1..=producers {
             let stream_id = match parallel_producer_streams {
                 true => start_stream_id + producer_id,
@@ -77,10 +104,16 @@ impl Benchmarkable for SendAndPollMessagesBenchmark {
                 partitions_count,
                 messages_per_batch,
                 message_batches,
-                message_size,
+                messages,
+                rate_limit,
                 warmup_time,
             );
-            let future = Box::pin(async move { producer.run().await });
+            let active_actors = active_actors.clone();
+            let future = Box::pin(async move {
+                let result = producer.run().await;
+                active_actors.fetch_sub(1, Ordering::Release);
+                result
+            });
             futures.as_mut().unwrap().push(future);
         }
 
@@ -97,8 +130,28 @@ impl Benchmarkable for SendAndPollMessagesBenchmark {
                 messages_per_batch,
                 message_batches,
                 warmup_time,
+                end_to_end_since,
+            );
+            let active_actors = active_actors.clone();
+            let future = Box::pin(async move {
+                let result = consumer.run().await;
+                active_actors.fetch_sub(1, Ordering::Release);
+                result
+            });
+            futures.as_mut().unwrap().push(future);
+        }
+
+        // Each admin uses its own stream, placed after the streams of the producers and consumers.
+        let admin_start_stream_id = start_stream_id + self.args.number_of_streams();
+        for admin_id in 1..=admin_clients {
+            let admin = Admin::new(
+                self.client_factory.clone(),
+                admin_id,
+                admin_start_stream_id + admin_id,
+                partitions_count,
+                active_actors.clone(),
             );
-            let future = Box::pin(async move { consumer.run().await });
+            let future = Box::pin(async move { admin.run().await });
             futures.as_mut().unwrap().push(future);
         }
         info!(
@@ -109,7 +162,7 @@ impl Benchmarkable for SendAndPollMessagesBenchmark {
     }
 
     fn kind(&self) -> BenchmarkKind {
-        BenchmarkKind::SendAndPoll
+        self.args.benchmark_kind.as_simple_kind()
     }
 
     fn args(&self) -> &IggyBenchArgs {
@@ -1,7 +1,7 @@
 use super::benchmark::{BenchmarkFutures, Benchmarkable};
 use crate::args::common::IggyBenchArgs;
 use crate::args::simple::BenchmarkKind;
-use crate::producer::Producer;
+use crate::producer::{MessagesProfile, Producer};
 use async_trait::async_trait;
 use integration::test_server::ClientFactory;
 use std::sync::Arc;
@@ -30,7 +30,8 @@ impl Benchmarkable for SendMessagesBenchmark {
         let streams_number = self.args.number_of_streams();
         let messages_per_batch = self.args.messages_per_batch();
         let message_batches = self.args.message_batches();
-        let message_size = self.args.message_size();
+        let messages = MessagesProfile::from(self.args.as_ref());
+        let rate_limit = self.args.rate_limit();
         let partitions_count = self.args.number_of_partitions();
         let warmup_time = self.args.warmup_time();
 
@@ -56,7 +57,8 @@ impl Benchmarkable for SendMessagesBenchmark {
                 partitions_count,
                 messages_per_batch,
                 message_batches,
-                message_size,
+                messages,
+                rate_limit,
                 warmup_time,
             );
             let future = Box::pin(async move { producer.run().await });
@@ -89,14 +91,14 @@ impl Benchmarkable for SendMessagesBenchmark {
         let total_messages = self.total_messages();
         let total_size_bytes = total_messages * self.args().message_size() as u64;
         info!(
-                "\x1B[32mBenchmark: {}, total messages: {}, total size: {} bytes, {} streams, {} messages per batch, {} batches, {} bytes per message, {} producers\x1B[0m",
+                "\x1B[32mBenchmark: {}, total messages: {}, total size: {} bytes, {} streams, {} messages per batch, {} batches, {}, {} producers\x1B[0m",
                 self.kind(),
                 total_messages,
                 total_size_bytes,
                 self.args().number_of_streams(),
                 self.args().messages_per_batch(),
                 self.args().message_batches(),
-                self.args().message_size(),
+                MessagesProfile::from(self.args()),
                 self.args().producers(),
             );
     }
@@ -1,17 +1,25 @@
 use crate::args::simple::BenchmarkKind;
-use crate::benchmark_result::{BenchmarkResult, LatencyPercentiles};
+use crate::benchmark_result::{BatchRecord, BenchmarkResult, LatencyPercentiles};
+use crate::producer::SEND_TIMESTAMP_HEADER;
 use iggy::client::{ConsumerGroupClient, MessageClient};
 use iggy::clients::client::{IggyClient, IggyClientBackgroundConfig};
 use iggy::consumer::Consumer as IggyConsumer;
 use iggy::error::IggyError;
 use iggy::messages::poll_messages::PollingStrategy;
+use iggy::models::header::HeaderKey;
+use iggy::models::messages::PolledMessage;
 use iggy::utils::duration::IggyDuration;
+use iggy::utils::timestamp::IggyTimestamp;
 use integration::test_server::{login_root, ClientFactory};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::Instant;
+use tokio::time::{sleep, Instant};
 use tracing::{error, info, warn};
 
+/// The delay before polling again after the empty poll in the end-to-end benchmark,
+/// so that the consumers don't spin and compete for the CPU with the producers.
+const EMPTY_POLL_BACKOFF: Duration = Duration::from_millis(1);
+
 pub struct Consumer {
     client_factory: Arc<dyn ClientFactory>,
     consumer_id: u32,
@@ -20,9 +28,13 @@ pub struct Consumer {
     messages_per_batch: u32,
     message_batches: u32,
     warmup_time: IggyDuration,
+    end_to_end_since: Option<IggyTimestamp>,
 }
 
 impl Consumer {
+    /// When `end_to_end_since` is set, the latency is measured from the send timestamps of the messages,
+    /// and the messages without the timestamp or sent before the given time (e.g. warmup) are skipped.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         client_factory: Arc<dyn ClientFactory>,
         consumer_id: u32,
@@ -31,6 +43,7 @@ impl Consumer {
         messages_per_batch: u32,
         message_batches: u32,
         warmup_time: IggyDuration,
+        end_to_end_since: Option<IggyTimestamp>,
     ) -> Self {
         Self {
             client_factory,
@@ -40,6 +53,7 @@ impl Consumer {
             messages_per_batch,
             message_batches,
             warmup_time,
+            end_to_end_since,
         }
     }
 
@@ -79,13 +93,18 @@ impl Consumer {
         };
 
         let mut latencies: Vec<Duration> = Vec::with_capacity(self.message_batches as usize);
+        let mut batches = Vec::with_capacity(self.message_batches as usize);
         let mut total_size_bytes = 0;
         let mut current_iteration: u64 = 0;
         let mut received_messages = 0;
         let mut topic_not_found_counter = 0;
         let mut strategy = PollingStrategy::offset(0);
+        let timestamp_key = HeaderKey::new(SEND_TIMESTAMP_HEADER)?;
 
-        if self.warmup_time.get_duration() != Duration::from_millis(0) {
+        // In the end to end mode, the warmup messages are skipped while polling.
+        if self.end_to_end_since.is_none()
+            && self.warmup_time.get_duration() != Duration::from_millis(0)
+        {
             if let Some(cg_id) = self.consumer_group_id {
                 info!(
                     "Consumer #{}, part of consumer group #{}, → warming up for {}...",
@@ -136,10 +155,9 @@ impl Consumer {
             );
         }
 
-        current_iteration = 0;
+        let mut offset = 0;
         let start_timestamp = Instant::now();
         while received_messages < total_messages {
-            let offset = current_iteration * self.messages_per_batch as u64;
             let latency_start = Instant::now();
 
             let polled_messages = client
@@ -173,29 +191,59 @@ impl Consumer {
 
             let polled_messages = polled_messages.unwrap();
             if polled_messages.messages.is_empty() {
-                warn!(
-                    "Consumer: {} - Messages are empty for offset: {}, retrying...",
-                    self.consumer_id, offset
-                );
+                if self.end_to_end_since.is_none() {
+                    warn!(
+                        "Consumer: {} - Messages are empty for offset: {}, retrying...",
+                        self.consumer_id, offset
+                    );
+                } else {
+                    sleep(EMPTY_POLL_BACKOFF).await;
+                }
                 continue;
             }
 
-            if polled_messages.messages.len() != self.messages_per_batch as usize {
-                warn!(
-                    "Consumer #{} → expected {} messages, but got {} messages, retrying...",
-                    self.consumer_id,
-                    self.messages_per_batch,
-                    polled_messages.messages.len()
-                );
+            let (batch_messages, batch_size_bytes) = match self.end_to_end_since {
+                Some(since) => {
+                    offset += polled_messages.messages.len() as u64;
+                    self.record_end_to_end_latencies(
+                        &polled_messages.messages,
+                        &timestamp_key,
+                        since,
+                        &mut latencies,
+                    )
+                }
+                None => {
+                    if polled_messages.messages.len() != self.messages_per_batch as usize {
+                        warn!(
+                            "Consumer #{} → expected {} messages, but got {} messages, retrying...",
+                            self.consumer_id,
+                            self.messages_per_batch,
+                            polled_messages.messages.len()
+                        );
+                        continue;
+                    }
+
+                    offset += polled_messages.messages.len() as u64;
+                    latencies.push(latency_end);
+                    let batch_size_bytes = polled_messages
+                        .messages
+                        .iter()
+                        .map(|message| message.get_size_bytes() as u64)
+                        .sum::<u64>();
+                    (polled_messages.messages.len() as u64, batch_size_bytes)
+                }
+            };
+            if batch_messages == 0 {
                 continue;
             }
 
-            latencies.push(latency_end);
-            received_messages += polled_messages.messages.len() as u64;
-            for message in polled_messages.messages {
-                total_size_bytes += message.get_size_bytes() as u64;
-            }
-            current_iteration += 1;
+            batches.push(BatchRecord {
+                elapsed: start_timestamp.elapsed(),
+                size_bytes: batch_size_bytes,
+                messages: batch_messages,
+            });
+            received_messages += batch_messages;
+            total_size_bytes += batch_size_bytes;
         }
         let end_timestamp = Instant::now();
 
@@ -243,6 +291,44 @@ impl Consumer {
             latency_percentiles,
             total_size_bytes,
             total_messages,
+            actor_id: self.consumer_id,
+            batches,
         })
     }
+
+    /// Records the latency of every producer batch (messages sharing the same send timestamp)
+    /// and returns the number and size of the measured messages.
+    fn record_end_to_end_latencies(
+        &self,
+        messages: &[PolledMessage],
+        timestamp_key: &HeaderKey,
+        since: IggyTimestamp,
+        latencies: &mut Vec<Duration>,
+    ) -> (u64, u64) {
+        let received_at = IggyTimestamp::now().as_micros();
+        let mut last_sent_at = None;
+        let mut measured_messages = 0;
+        let mut measured_size_bytes = 0;
+        for message in messages {
+            let Some(sent_at) = message
+                .headers
+                .as_ref()
+                .and_then(|headers| headers.get(timestamp_key))
+                .and_then(|value| value.as_uint64().ok())
+            else {
+                continue;
+            };
+            if sent_at < since.as_micros() {
+                continue;
+            }
+
+            if last_sent_at != Some(sent_at) {
+                latencies.push(Duration::from_micros(received_at.saturating_sub(sent_at)));
+                last_sent_at = Some(sent_at);
+            }
+            measured_messages += 1;
+            measured_size_bytes += message.get_size_bytes() as u64;
+        }
+        (measured_messages, measured_size_bytes)
+    }
 }
@@ -1,4 +1,7 @@
+mod admin;
 mod args;
+mod benchmark_comparison;
+mod benchmark_report;
 mod benchmark_result;
 mod benchmark_runner;
 mod benchmarks;
@@ -7,6 +10,8 @@ mod consumer;
 mod producer;
 mod server_starter;
 
+use crate::args::kind::BenchmarkKindCommand;
+use crate::benchmark_comparison::compare_results;
 use crate::{args::common::IggyBenchArgs, benchmark_runner::BenchmarkRunner};
 use clap::Parser;
 use figlet_rs::FIGfont;
@@ -23,6 +28,14 @@ async fn main() -> Result<(), IggyError> {
         .init();
 
     let args = IggyBenchArgs::parse();
+    if let BenchmarkKindCommand::Compare(compare_args) = &args.benchmark_kind {
+        if compare_results(compare_args)? {
+            error!("Benchmark results regressed beyond the allowed thresholds");
+            std::process::exit(1);
+        }
+        info!("No regressions found in benchmark results");
+        return Ok(());
+    }
     args.validate();
 
     let mut benchmark_runner = BenchmarkRunner::new(args);
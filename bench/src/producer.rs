@@ -1,17 +1,107 @@
+use crate::args::common::IggyBenchArgs;
 use crate::args::simple::BenchmarkKind;
-use crate::benchmark_result::{BenchmarkResult, LatencyPercentiles};
+use crate::benchmark_result::{BatchRecord, BenchmarkResult, LatencyPercentiles};
 use iggy::client::MessageClient;
 use iggy::clients::client::{IggyClient, IggyClientBackgroundConfig};
 use iggy::error::IggyError;
 use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::header::{HeaderKey, HeaderValue};
 use iggy::utils::duration::IggyDuration;
+use iggy::utils::timestamp::IggyTimestamp;
 use integration::test_server::{login_root, ClientFactory};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::num::NonZeroU32;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::Instant;
+use tokio::time::{sleep_until, Instant};
 use tracing::info;
 
+/// Header with the send timestamp (in microseconds) used by the consumers to measure end to end latency.
+/// It's not set on the messages sent during the warmup, so the consumers can skip them.
+pub const SEND_TIMESTAMP_HEADER: &str = "iggy-bench-send-timestamp";
+
+/// Shape of the messages sent by the producers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MessagesProfile {
+    pub min_size: u32,
+    pub max_size: u32,
+    pub max_headers: u32,
+    pub send_timestamps: bool,
+}
+
+impl From<&IggyBenchArgs> for MessagesProfile {
+    fn from(args: &IggyBenchArgs) -> Self {
+        Self {
+            min_size: args.min_message_size(),
+            max_size: args.message_size(),
+            max_headers: args.max_headers(),
+            send_timestamps: args.benchmark_kind.as_simple_kind() == BenchmarkKind::EndToEnd,
+        }
+    }
+}
+
+impl Display for MessagesProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.min_size == self.max_size {
+            true => write!(f, "{} bytes per message", self.max_size)?,
+            false => write!(f, "{}-{} bytes per message", self.min_size, self.max_size)?,
+        }
+        if self.max_headers > 0 {
+            write!(f, ", up to {} headers per message", self.max_headers)?;
+        }
+        Ok(())
+    }
+}
+
+impl MessagesProfile {
+    /// Generates the batch of messages with random sizes and headers within the profile bounds.
+    fn create_messages(&self, count: u32) -> Result<Vec<Message>, IggyError> {
+        let mut rng = rand::thread_rng();
+        let mut messages = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let size = rng.gen_range(self.min_size..=self.max_size);
+            let mut message = Message::from_str(&Producer::create_payload(size))?;
+            let headers_count = rng.gen_range(0..=self.max_headers);
+            if headers_count > 0 {
+                message.headers = Some(Self::create_headers(headers_count, &mut rng)?);
+            }
+            messages.push(message);
+        }
+
+        Ok(messages)
+    }
+
+    fn create_headers(
+        count: u32,
+        rng: &mut impl Rng,
+    ) -> Result<HashMap<HeaderKey, HeaderValue>, IggyError> {
+        let mut headers = HashMap::with_capacity(count as usize);
+        for i in 0..count {
+            let key = HeaderKey::new(&format!("header-{i}"))?;
+            let value = match i % 3 {
+                0 => {
+                    let length = rng.gen_range(1..=32);
+                    let value = rng
+                        .sample_iter(&Alphanumeric)
+                        .take(length)
+                        .map(char::from)
+                        .collect::<String>();
+                    HeaderValue::from_str(&value)?
+                }
+                1 => HeaderValue::from_uint64(rng.gen())?,
+                _ => HeaderValue::from_bool(rng.gen())?,
+            };
+            headers.insert(key, value);
+        }
+
+        Ok(headers)
+    }
+}
+
 pub struct Producer {
     client_factory: Arc<dyn ClientFactory>,
     producer_id: u32,
@@ -19,7 +109,8 @@ pub struct Producer {
     partitions_count: u32,
     messages_per_batch: u32,
     message_batches: u32,
-    message_size: u32,
+    messages: MessagesProfile,
+    rate_limit: Option<NonZeroU32>,
     warmup_time: IggyDuration,
 }
 
@@ -32,7 +123,8 @@ impl Producer {
         partitions_count: u32,
         messages_per_batch: u32,
         message_batches: u32,
-        message_size: u32,
+        messages: MessagesProfile,
+        rate_limit: Option<NonZeroU32>,
         warmup_time: IggyDuration,
     ) -> Self {
         Producer {
@@ -42,7 +134,8 @@ impl Producer {
             partitions_count,
             messages_per_batch,
             message_batches,
-            message_size,
+            messages,
+            rate_limit,
             warmup_time,
         }
     }
@@ -64,12 +157,17 @@ impl Producer {
             "Producer #{} → preparing the test messages...",
             self.producer_id
         );
-        let payload = Self::create_payload(self.message_size);
-        let mut messages = Vec::with_capacity(self.messages_per_batch as usize);
-        for _ in 0..self.messages_per_batch {
-            let message = Message::from_str(&payload).unwrap();
-            messages.push(message);
-        }
+        let mut messages = self.messages.create_messages(self.messages_per_batch)?;
+        let batch_size_bytes = messages
+            .iter()
+            .map(|message| message.length as u64)
+            .sum::<u64>();
+        let timestamp_key = HeaderKey::new(SEND_TIMESTAMP_HEADER)?;
+        // With the rate limit, the batches are sent on a fixed schedule and the latency is measured
+        // from the scheduled time, so the stalls of the server are not hidden by the delayed sends.
+        let batch_interval = self.rate_limit.map(|rate| {
+            Duration::from_secs_f64(self.messages_per_batch as f64 / rate.get() as f64)
+        });
 
         let stream_id = self.stream_id.try_into()?;
         let topic_id = topic_id.try_into()?;
@@ -82,11 +180,17 @@ impl Producer {
             "Producer #{} → warming up for {}...",
             self.producer_id, self.warmup_time
         );
-        let warmup_end = Instant::now() + self.warmup_time.get_duration();
+        let warmup_start = Instant::now();
+        let warmup_end = warmup_start + self.warmup_time.get_duration();
+        let mut warmup_batches = 0;
         while Instant::now() < warmup_end {
+            if let Some(interval) = batch_interval {
+                sleep_until(warmup_start + interval * warmup_batches).await;
+            }
             client
                 .send_messages(&stream_id, &topic_id, &partitioning, &mut messages)
                 .await?;
+            warmup_batches += 1;
         }
 
         info!(
@@ -96,13 +200,27 @@ impl Producer {
 
         let start_timestamp = Instant::now();
         let mut latencies: Vec<Duration> = Vec::with_capacity(self.message_batches as usize);
-        for _ in 0..self.message_batches {
-            let latency_start = Instant::now();
+        let mut batches = Vec::with_capacity(self.message_batches as usize);
+        for batch in 0..self.message_batches {
+            let latency_start = match batch_interval {
+                Some(interval) => {
+                    let scheduled_start = start_timestamp + interval * batch;
+                    sleep_until(scheduled_start).await;
+                    scheduled_start
+                }
+                None => Instant::now(),
+            };
+            self.set_send_timestamp(&mut messages, &timestamp_key)?;
             client
                 .send_messages(&stream_id, &topic_id, &partitioning, &mut messages)
                 .await?;
             let latency_end = latency_start.elapsed();
             latencies.push(latency_end);
+            batches.push(BatchRecord {
+                elapsed: start_timestamp.elapsed(),
+                size_bytes: batch_size_bytes,
+                messages: self.messages_per_batch as u64,
+            });
         }
         let end_timestamp = Instant::now();
 
@@ -123,7 +241,7 @@ impl Producer {
 
         let duration = end_timestamp - start_timestamp;
         let average_latency: Duration = latencies.iter().sum::<Duration>() / latencies.len() as u32;
-        let total_size_bytes = total_messages * self.message_size as u64;
+        let total_size_bytes = batch_size_bytes * self.message_batches as u64;
         let average_throughput = total_size_bytes as f64 / duration.as_secs_f64() / 1e6;
 
         info!(
@@ -151,9 +269,30 @@ impl Producer {
             latency_percentiles,
             total_size_bytes,
             total_messages,
+            actor_id: self.producer_id,
+            batches,
         })
     }
 
+    fn set_send_timestamp(
+        &self,
+        messages: &mut [Message],
+        timestamp_key: &HeaderKey,
+    ) -> Result<(), IggyError> {
+        if !self.messages.send_timestamps {
+            return Ok(());
+        }
+
+        let timestamp = HeaderValue::from_uint64(IggyTimestamp::now().as_micros())?;
+        for message in messages {
+            message
+                .headers
+                .get_or_insert_with(HashMap::new)
+                .insert(timestamp_key.clone(), timestamp.clone());
+        }
+        Ok(())
+    }
+
     fn create_payload(size: u32) -> String {
         let mut payload = String::with_capacity(size as usize);
         for i in 0..size {
@@ -164,3 +303,44 @@ impl Producer {
         payload
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_should_be_created_within_profile_bounds() {
+        let profile = MessagesProfile {
+            min_size: 10,
+            max_size: 100,
+            max_headers: 5,
+            send_timestamps: false,
+        };
+
+        let messages = profile.create_messages(1000).unwrap();
+
+        assert_eq!(messages.len(), 1000);
+        for message in &messages {
+            assert!((10..=100).contains(&message.length));
+            let headers = message.headers.as_ref().map_or(0, |headers| headers.len());
+            assert!(headers <= 5);
+        }
+    }
+
+    #[test]
+    fn fixed_profile_should_create_messages_of_the_same_size_without_headers() {
+        let profile = MessagesProfile {
+            min_size: 64,
+            max_size: 64,
+            max_headers: 0,
+            send_timestamps: false,
+        };
+
+        let messages = profile.create_messages(10).unwrap();
+
+        assert!(messages
+            .iter()
+            .all(|message| message.length == 64 && message.headers.is_none()));
+        assert_eq!(profile.to_string(), "64 bytes per message");
+    }
+}
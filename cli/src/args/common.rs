@@ -3,10 +3,13 @@ use iggy::cli::client::get_clients::GetClientsOutput;
 use iggy::cli::consumer_group::get_consumer_groups::GetConsumerGroupsOutput;
 use iggy::cli::context::get_contexts::GetContextsOutput;
 use iggy::cli::personal_access_tokens::get_personal_access_tokens::GetPersonalAccessTokensOutput;
+use iggy::cli::roles::get_roles::GetRolesOutput;
 use iggy::cli::streams::get_streams::GetStreamsOutput;
+use iggy::cli::system::audit_log::GetAuditLogOutput;
 use iggy::cli::system::stats::GetStatsOutput;
 use iggy::cli::topics::get_topics::GetTopicsOutput;
 use iggy::cli::users::get_users::GetUsersOutput;
+use iggy::cli_command::OutputFormat;
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub(crate) enum ListMode {
@@ -50,6 +53,15 @@ impl From<ListMode> for GetUsersOutput {
     }
 }
 
+impl From<ListMode> for GetRolesOutput {
+    fn from(mode: ListMode) -> Self {
+        match mode {
+            ListMode::Table => GetRolesOutput::Table,
+            ListMode::List => GetRolesOutput::List,
+        }
+    }
+}
+
 impl From<ListMode> for GetClientsOutput {
     fn from(mode: ListMode) -> Self {
         match mode {
@@ -59,6 +71,15 @@ impl From<ListMode> for GetClientsOutput {
     }
 }
 
+impl From<ListMode> for GetAuditLogOutput {
+    fn from(mode: ListMode) -> Self {
+        match mode {
+            ListMode::Table => GetAuditLogOutput::Table,
+            ListMode::List => GetAuditLogOutput::List,
+        }
+    }
+}
+
 impl From<ListMode> for GetConsumerGroupsOutput {
     fn from(mode: ListMode) -> Self {
         match mode {
@@ -95,3 +116,23 @@ impl From<ListModeExt> for GetStatsOutput {
         }
     }
 }
+
+#[derive(Debug, Default, Clone, Copy, ValueEnum)]
+pub(crate) enum OutputMode {
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Csv,
+}
+
+impl From<OutputMode> for OutputFormat {
+    fn from(mode: OutputMode) -> Self {
+        match mode {
+            OutputMode::Table => OutputFormat::Table,
+            OutputMode::Json => OutputFormat::Json,
+            OutputMode::Yaml => OutputFormat::Yaml,
+            OutputMode::Csv => OutputFormat::Csv,
+        }
+    }
+}
@@ -1,8 +1,15 @@
-use clap::{ArgGroup, Args, Subcommand};
+use clap::{ArgGroup, Args, Subcommand, ValueEnum};
+use iggy::cli::message::export_messages::ExportRange;
+use iggy::cli::message::message_file::MessageFileFormat;
+use iggy::cli::message::payload::PayloadFormat;
+use iggy::cli::message::tail_messages::TailStart;
 use iggy::error::IggyError;
 use iggy::error::IggyError::InvalidFormat;
 use iggy::identifier::Identifier;
 use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::utils::duration::IggyDuration;
+use iggy::utils::timestamp::IggyTimestamp;
+use std::path::PathBuf;
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Subcommand)]
@@ -31,6 +38,49 @@ pub(crate) enum MessageAction {
     ///  iggy message poll --offset 0 stream topic 1
     #[clap(verbatim_doc_comment, visible_alias = "p")]
     Poll(PollMessagesArgs),
+    /// Follow messages appended to given topic ID and given stream ID
+    ///
+    /// Stream ID can be specified as a stream name or ID
+    /// Topic ID can be specified as a topic name or ID
+    /// Messages are printed as soon as they are appended to the topic
+    /// until the command is interrupted or the limit of messages is reached.
+    ///
+    /// Examples:
+    ///  iggy message tail 1 2
+    ///  iggy message tail --partition-id 1 --first stream topic
+    ///  iggy message tail --from "2024-05-01 10:00:00" stream topic
+    ///  iggy message tail --consumer-group group stream topic
+    ///  iggy message tail --payload-format json --headers key:string:value stream topic
+    #[clap(verbatim_doc_comment, visible_alias = "t")]
+    Tail(TailMessagesArgs),
+    /// Export messages from given topic ID and given stream ID to file
+    ///
+    /// Stream ID can be specified as a stream name or ID
+    /// Topic ID can be specified as a topic name or ID
+    /// Messages are exported with their partition IDs, offsets, timestamps,
+    /// IDs and headers, so they can be imported with the import command.
+    ///
+    /// Examples:
+    ///  iggy message export 1 2 messages.jsonl
+    ///  iggy message export --partition-id 1 --from-offset 10 --to-offset 20 stream topic messages.jsonl
+    ///  iggy message export --from-timestamp "2024-05-01 10:00:00" stream topic messages.jsonl
+    ///  iggy message export --format binary stream topic messages.bin
+    #[clap(verbatim_doc_comment, visible_alias = "e")]
+    Export(ExportMessagesArgs),
+    /// Import messages from file to given topic ID and given stream ID
+    ///
+    /// Stream ID can be specified as a stream name or ID
+    /// Topic ID can be specified as a topic name or ID
+    /// Messages are sent to the partitions they were exported from
+    /// along with their IDs and headers. Format of the file is detected
+    /// automatically.
+    ///
+    /// Examples:
+    ///  iggy message import 1 2 messages.jsonl
+    ///  iggy message import stream topic messages.bin
+    ///  iggy message import --partition-id 1 stream topic messages.jsonl
+    #[clap(verbatim_doc_comment, visible_alias = "i")]
+    Import(ImportMessagesArgs),
 }
 
 #[derive(Debug, Clone, Args)]
@@ -152,6 +202,232 @@ pub(crate) struct PollMessagesArgs {
     pub(crate) show_headers: bool,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub(crate) enum PayloadFormatArg {
+    String,
+    Hex,
+    Base64,
+    Json,
+    Protobuf,
+    Avro,
+}
+
+impl From<PayloadFormatArg> for PayloadFormat {
+    fn from(format: PayloadFormatArg) -> Self {
+        match format {
+            PayloadFormatArg::String => PayloadFormat::String,
+            PayloadFormatArg::Hex => PayloadFormat::Hex,
+            PayloadFormatArg::Base64 => PayloadFormat::Base64,
+            PayloadFormatArg::Json => PayloadFormat::Json,
+            PayloadFormatArg::Protobuf => PayloadFormat::Protobuf,
+            PayloadFormatArg::Avro => PayloadFormat::Avro,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Args)]
+#[command(group = ArgGroup::new("start").required(false))]
+pub(crate) struct TailMessagesArgs {
+    /// ID of the stream from which messages will be followed
+    ///
+    /// Stream ID can be specified as a stream name or ID
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) stream_id: Identifier,
+    /// ID of the topic from which messages will be followed
+    ///
+    /// Topic ID can be specified as a topic name or ID
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) topic_id: Identifier,
+    /// Partition ID from which messages will be followed
+    ///
+    /// If not specified, messages from all partitions are followed.
+    #[clap(verbatim_doc_comment)]
+    #[clap(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub(crate) partition_id: Option<u32>,
+    /// Start following from the given offset in each partition
+    ///
+    /// If no start position is specified, only the messages appended
+    /// after the command was started are printed.
+    #[clap(verbatim_doc_comment)]
+    #[clap(short, long, group = "start")]
+    pub(crate) offset: Option<u64>,
+    /// Start following from the first message in each partition
+    #[clap(short, long, default_value_t = false, group = "start")]
+    pub(crate) first: bool,
+    /// Start following from the messages appended at or after the given time
+    ///
+    /// Time can be specified as RFC 3339 date and time, UTC date and time
+    /// in "%Y-%m-%d %H:%M:%S" format or number of microseconds since Unix epoch.
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, group = "start", value_parser = clap::value_parser!(IggyTimestamp))]
+    pub(crate) from: Option<IggyTimestamp>,
+    /// Regular consumer which will poll messages
+    ///
+    /// Consumer ID can be specified as a consumer name or ID
+    #[clap(verbatim_doc_comment)]
+    #[clap(short, long, default_value_t = Identifier::default(), value_parser = clap::value_parser!(Identifier))]
+    pub(crate) consumer: Identifier,
+    /// Consumer group which will be joined to follow messages
+    ///
+    /// Messages are followed from the partitions assigned to the group member
+    /// starting from the stored consumer group offset, which is committed
+    /// automatically. Consumer group ID can be specified as a name or ID.
+    #[clap(verbatim_doc_comment)]
+    #[clap(short = 'g', long, value_parser = clap::value_parser!(Identifier), conflicts_with_all = ["partition_id", "start", "consumer"])]
+    pub(crate) consumer_group: Option<Identifier>,
+    /// Number of messages to poll in a single batch
+    #[clap(short, long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..))]
+    pub(crate) message_count: u32,
+    /// Interval between polls when there are no new messages
+    #[clap(short, long, default_value = "500ms", value_parser = clap::value_parser!(IggyDuration))]
+    pub(crate) interval: IggyDuration,
+    /// Stop after printing given number of messages
+    #[clap(short, long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub(crate) limit: Option<u64>,
+    /// Format used to render the message payload
+    ///
+    /// Protobuf format requires the schema file with the file descriptor set
+    /// (protoc --include_imports --descriptor_set_out) and the message type,
+    /// Avro format requires the schema file with the JSON schema.
+    #[clap(verbatim_doc_comment)]
+    #[clap(short = 'P', long, value_enum, default_value_t = PayloadFormatArg::String)]
+    pub(crate) payload_format: PayloadFormatArg,
+    /// Path to the schema file used to decode Protobuf or Avro payload
+    #[clap(long)]
+    pub(crate) schema_file: Option<PathBuf>,
+    /// Fully qualified name of the Protobuf message type, e.g. package.Message
+    #[clap(long)]
+    pub(crate) message_type: Option<String>,
+    /// Comma separated list of key:kind:value, only messages with all the headers are printed
+    ///
+    /// Kind can be one of the following: raw, string, bool, int8, int16, int32, int64,
+    /// int128, uint8, uint16, uint32, uint64, uint128, float32, float64
+    #[clap(verbatim_doc_comment)]
+    #[clap(short = 'H', long, value_parser = parse_key_val, value_delimiter = ',')]
+    pub(crate) headers: Vec<(HeaderKey, HeaderValue)>,
+    /// Include the message headers in the output
+    #[clap(short, long, default_value_t = false)]
+    pub(crate) show_headers: bool,
+}
+
+impl TailMessagesArgs {
+    pub(crate) fn start(&self) -> TailStart {
+        match (self.offset, self.first, self.from) {
+            (Some(offset), _, _) => TailStart::Offset(offset),
+            (None, true, _) => TailStart::First,
+            (None, false, Some(timestamp)) => TailStart::Timestamp(timestamp),
+            (None, false, None) => TailStart::End,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub(crate) enum MessageFileFormatArg {
+    Jsonl,
+    Binary,
+}
+
+impl From<MessageFileFormatArg> for MessageFileFormat {
+    fn from(format: MessageFileFormatArg) -> Self {
+        match format {
+            MessageFileFormatArg::Jsonl => MessageFileFormat::Jsonl,
+            MessageFileFormatArg::Binary => MessageFileFormat::Binary,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ExportMessagesArgs {
+    /// ID of the stream from which messages will be exported
+    ///
+    /// Stream ID can be specified as a stream name or ID
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) stream_id: Identifier,
+    /// ID of the topic from which messages will be exported
+    ///
+    /// Topic ID can be specified as a topic name or ID
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) topic_id: Identifier,
+    /// Path to the file to which messages will be exported
+    pub(crate) file: PathBuf,
+    /// Partition ID from which messages will be exported
+    ///
+    /// If not specified, messages from all partitions are exported.
+    #[clap(verbatim_doc_comment)]
+    #[clap(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub(crate) partition_id: Option<u32>,
+    /// Export messages starting from the given offset in each partition
+    #[clap(long, conflicts_with_all = ["from_timestamp", "to_timestamp"])]
+    pub(crate) from_offset: Option<u64>,
+    /// Export messages up to the given offset (inclusive) in each partition
+    #[clap(long, conflicts_with_all = ["from_timestamp", "to_timestamp"])]
+    pub(crate) to_offset: Option<u64>,
+    /// Export messages appended at or after the given time
+    ///
+    /// Time can be specified as RFC 3339 date and time, UTC date and time
+    /// in "%Y-%m-%d %H:%M:%S" format or number of microseconds since Unix epoch.
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, value_parser = clap::value_parser!(IggyTimestamp))]
+    pub(crate) from_timestamp: Option<IggyTimestamp>,
+    /// Export messages appended at or before the given time
+    ///
+    /// Time can be specified in the same formats as for the --from-timestamp option.
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, value_parser = clap::value_parser!(IggyTimestamp))]
+    pub(crate) to_timestamp: Option<IggyTimestamp>,
+    /// Format of the file
+    ///
+    /// JSON Lines format stores each message as a separate JSON object
+    /// with the payload encoded as Base64, binary format is more compact.
+    #[clap(verbatim_doc_comment)]
+    #[clap(short, long, value_enum, default_value_t = MessageFileFormatArg::Jsonl)]
+    pub(crate) format: MessageFileFormatArg,
+    /// Number of messages to poll in a single batch
+    #[clap(short, long, default_value_t = 1000, value_parser = clap::value_parser!(u32).range(1..))]
+    pub(crate) message_count: u32,
+}
+
+impl ExportMessagesArgs {
+    pub(crate) fn range(&self) -> ExportRange {
+        match (
+            self.from_offset,
+            self.to_offset,
+            self.from_timestamp,
+            self.to_timestamp,
+        ) {
+            (None, None, None, None) => ExportRange::All,
+            (from, to, None, None) => ExportRange::Offsets { from, to },
+            (_, _, from, to) => ExportRange::Timestamps { from, to },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ImportMessagesArgs {
+    /// ID of the stream to which messages will be imported
+    ///
+    /// Stream ID can be specified as a stream name or ID
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) stream_id: Identifier,
+    /// ID of the topic to which messages will be imported
+    ///
+    /// Topic ID can be specified as a topic name or ID
+    #[arg(value_parser = clap::value_parser!(Identifier))]
+    pub(crate) topic_id: Identifier,
+    /// Path to the file from which messages will be imported
+    pub(crate) file: PathBuf,
+    /// Partition ID to which all the messages will be imported
+    ///
+    /// If not specified, messages are imported to the partitions
+    /// they were exported from.
+    #[clap(verbatim_doc_comment)]
+    #[clap(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub(crate) partition_id: Option<u32>,
+    /// Maximum number of messages sent in a single batch
+    #[clap(short, long, default_value_t = 1000, value_parser = clap::value_parser!(u32).range(1..))]
+    pub(crate) batch_size: u32,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -10,15 +10,18 @@ use iggy::cli::context::common::ContextConfig;
 
 use crate::args::{
     client::ClientAction,
+    common::OutputMode,
     consumer_group::ConsumerGroupAction,
     consumer_offset::ConsumerOffsetAction,
     context::ContextAction,
     message::MessageAction,
     partition::PartitionAction,
     personal_access_token::PersonalAccessTokenAction,
+    role::RoleAction,
     stream::StreamAction,
-    system::{LoginArgs, PingArgs, StatsArgs},
+    system::{AuditLogArgs, BackupArgs, LoginArgs, PingArgs, StatsArgs},
     topic::TopicAction,
+    topology::{ApplyTopologyArgs, ExportTopologyArgs},
 };
 
 use self::user::UserAction;
@@ -32,9 +35,11 @@ pub(crate) mod message;
 pub(crate) mod partition;
 pub(crate) mod permissions;
 pub(crate) mod personal_access_token;
+pub(crate) mod role;
 pub(crate) mod stream;
 pub(crate) mod system;
 pub(crate) mod topic;
+pub(crate) mod topology;
 pub(crate) mod user;
 
 static CARGO_BIN_NAME: &str = env!("CARGO_BIN_NAME");
@@ -64,6 +69,14 @@ pub(crate) struct CliOptions {
     #[clap(short, long)]
     pub(crate) debug: Option<PathBuf>,
 
+    /// Output format of the command result
+    ///
+    /// Table format is meant to be read by humans, while json, yaml
+    /// and csv formats are meant to be processed by scripts.
+    #[clap(verbatim_doc_comment)]
+    #[clap(short, long, value_enum, default_value_t = OutputMode::Table)]
+    pub(crate) output: OutputMode,
+
     /// Iggy server username
     #[clap(short, long, group = "credentials")]
     pub(crate) username: Option<String>,
@@ -133,12 +146,38 @@ pub(crate) enum Command {
     /// Server OS name, version, etc. are also collected.
     #[clap(verbatim_doc_comment)]
     Stats(StatsArgs),
+    /// get audit log entries
+    ///
+    /// Collect the most recent audit log entries recording who (user ID, client address
+    /// and transport) performed which action on Iggy server and with which result.
+    ///
+    /// Examples:
+    ///  iggy audit-log
+    ///  iggy audit-log --count 10
+    ///  iggy audit-log -c 10 -u 1 -l list
+    #[clap(verbatim_doc_comment, visible_alias = "al")]
+    AuditLog(AuditLogArgs),
+    /// create server backup
+    ///
+    /// Create a consistent backup of the state and the messages of the running Iggy server
+    /// in its backup directory, optionally uploaded using the configured archiver.
+    /// The server can be restored from the backup on startup with --restore-backup option.
+    ///
+    /// Examples:
+    ///  iggy backup
+    ///  iggy backup --name daily
+    ///  iggy backup -n daily -a
+    #[clap(verbatim_doc_comment, visible_alias = "bk")]
+    Backup(BackupArgs),
     /// personal access token operations
     #[command(subcommand)]
     Pat(PersonalAccessTokenAction),
     /// user operations
     #[command(subcommand, visible_alias = "u")]
     User(UserAction),
+    /// role operations
+    #[command(subcommand, visible_alias = "r")]
+    Role(RoleAction),
     /// client operations
     #[command(subcommand, visible_alias = "c")]
     Client(ClientAction),
@@ -154,6 +193,31 @@ pub(crate) enum Command {
     /// context operations
     #[command(subcommand, visible_alias = "ctx")]
     Context(ContextAction),
+    /// apply topology from manifest file
+    ///
+    /// Command compares streams, topics, partitions, consumer groups, users and their
+    /// permissions declared in the manifest file with the ones existing on Iggy server
+    /// and creates, updates or deletes them, so the server matches the manifest.
+    /// Sections omitted in the manifest are not managed, while the empty ones mean
+    /// that all the existing entities of the section are deleted.
+    ///
+    /// Examples:
+    ///  iggy apply -f topology.toml
+    ///  iggy apply -f topology.yaml --dry-run
+    #[clap(verbatim_doc_comment)]
+    Apply(ApplyTopologyArgs),
+    /// export topology to manifest file
+    ///
+    /// Command exports streams, topics, consumer groups, users and their permissions
+    /// existing on Iggy server in the format accepted by the apply command.
+    /// Passwords of the users are not exported.
+    ///
+    /// Examples:
+    ///  iggy export-topology
+    ///  iggy export-topology -f topology.yaml
+    ///  iggy export-topology --format yaml
+    #[clap(verbatim_doc_comment)]
+    ExportTopology(ExportTopologyArgs),
     /// login to Iggy server
     ///
     /// Command logs in to Iggy server using provided credentials and stores session token
@@ -215,6 +279,7 @@ impl IggyMergedConsoleArgs {
         let merged_cli_options = CliOptions {
             quiet: args.cli.quiet,
             debug: args.cli.debug,
+            output: args.cli.output,
             username: args.cli.username.or(context.username),
             password: args.cli.password.or(context.password),
             token: args.cli.token.or(context.token),
@@ -0,0 +1,159 @@
+use crate::args::common::ListMode;
+use crate::args::permissions::global::GlobalPermissionsArg;
+use crate::args::permissions::stream::StreamPermissionsArg;
+use clap::{Args, Subcommand};
+use iggy::identifier::Identifier;
+
+#[derive(Debug, Clone, Subcommand)]
+pub(crate) enum RoleAction {
+    /// Create role with given name and permissions
+    ///
+    /// Examples
+    ///  iggy role create readers -g r_str,r_top,p_msg
+    ///  iggy role create writers -s 1:s_msg
+    #[clap(verbatim_doc_comment, visible_alias = "c")]
+    Create(RoleCreateArgs),
+    /// Delete role with given ID
+    ///
+    /// The role ID can be specified as either a role name or an ID.
+    /// The role is also removed from all the users having it assigned.
+    ///
+    /// Examples:
+    ///  iggy role delete 2
+    ///  iggy role delete readers
+    #[clap(verbatim_doc_comment, visible_alias = "d")]
+    Delete(RoleDeleteArgs),
+    /// Get details of a single role with given ID
+    ///
+    /// The role ID can be specified as either a role name or an ID
+    ///
+    /// Examples:
+    ///  iggy role get 2
+    ///  iggy role get readers
+    #[clap(verbatim_doc_comment, visible_alias = "g")]
+    Get(RoleGetArgs),
+    /// List all roles
+    ///
+    /// Examples:
+    ///  iggy role list
+    ///  iggy role list --list-mode table
+    ///  iggy role list -l table
+    #[clap(verbatim_doc_comment, visible_alias = "l")]
+    List(RoleListArgs),
+    /// Change name for role with given ID
+    ///
+    /// The role ID can be specified as either a role name or an ID
+    ///
+    /// Examples:
+    ///  iggy role name 2 new_role_name
+    ///  iggy role name readers consumers
+    #[clap(verbatim_doc_comment, visible_alias = "n")]
+    Name(RoleNameArgs),
+    /// Set permissions for role with given ID
+    ///
+    /// The role ID can be specified as either a role name or an ID. Permissions
+    /// are configured based on the options provided with this command. If no
+    /// options are set, the default behavior is to remove permissions for the
+    /// specified role. Permissions of all the users having the role assigned
+    /// are updated accordingly.
+    ///
+    /// Examples:
+    ///  iggy role permissions 2 -g r_str
+    ///  iggy role permissions readers -s 1:r_str,p_msg
+    #[clap(verbatim_doc_comment, visible_alias = "p")]
+    Permissions(RolePermissionsArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RoleCreateArgs {
+    /// Role name
+    ///
+    /// Unique name of the role on iggy server,
+    /// must be between 3 and 50 characters long.
+    #[clap(verbatim_doc_comment)]
+    pub(crate) name: String,
+    /// Set global permissions for created role
+    ///
+    /// Permissions are separated by comma and use the same names
+    /// as the global permissions of the users, see: iggy user create --help
+    ///
+    /// Examples:
+    ///  iggy role create readers --global-permissions r_str,r_top,p_msg
+    #[clap(short, long, verbatim_doc_comment)]
+    #[arg(value_parser = clap::value_parser!(GlobalPermissionsArg))]
+    pub(crate) global_permissions: Option<GlobalPermissionsArg>,
+    /// Set stream permissions for created role
+    ///
+    /// Stream permissions use the same format as the stream permissions
+    /// of the users, see: iggy user create --help
+    ///
+    /// Permissions format: STREAM_ID[:STREAM_PERMISSIONS][#TOPIC_ID[:TOPIC_PERMISSIONS]]
+    ///
+    /// Examples:
+    ///  iggy role create writers -s 1:s_msg
+    ///  iggy role create sender --stream-permissions 3#1:s_msg#2:s_msg
+    #[clap(short, long, verbatim_doc_comment)]
+    #[arg(value_parser = clap::value_parser!(StreamPermissionsArg))]
+    pub(crate) stream_permissions: Option<Vec<StreamPermissionsArg>>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RoleDeleteArgs {
+    /// Role ID to delete
+    ///
+    /// The role ID can be specified as either a role name or an ID
+    pub(crate) role_id: Identifier,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RoleGetArgs {
+    /// Role ID to get
+    ///
+    /// The role ID can be specified as either a role name or an ID
+    pub(crate) role_id: Identifier,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RoleListArgs {
+    /// List mode (table or list)
+    #[clap(short, long, value_enum, default_value_t = ListMode::Table)]
+    pub(crate) list_mode: ListMode,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RoleNameArgs {
+    /// Role ID to update
+    ///
+    /// The role ID can be specified as either a role name or an ID
+    pub(crate) role_id: Identifier,
+    /// New role name
+    ///
+    /// New and unique name of the role on iggy server,
+    /// must be between 3 and 50 characters long.
+    #[clap(verbatim_doc_comment)]
+    pub(crate) name: String,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RolePermissionsArgs {
+    /// Role ID to update
+    ///
+    /// The role ID can be specified as either a role name or an ID
+    pub(crate) role_id: Identifier,
+    /// Set global permissions for the role
+    ///
+    /// Permissions are separated by comma and use the same names
+    /// as the global permissions of the users, see: iggy user create --help
+    #[clap(short, long, verbatim_doc_comment)]
+    #[arg(value_parser = clap::value_parser!(GlobalPermissionsArg))]
+    pub(crate) global_permissions: Option<GlobalPermissionsArg>,
+    /// Set stream permissions for the role
+    ///
+    /// Stream permissions use the same format as the stream permissions
+    /// of the users, see: iggy user create --help
+    ///
+    /// Permissions format: STREAM_ID[:STREAM_PERMISSIONS][#TOPIC_ID[:TOPIC_PERMISSIONS]]
+    #[clap(short, long, verbatim_doc_comment)]
+    #[arg(value_parser = clap::value_parser!(StreamPermissionsArg))]
+    pub(crate) stream_permissions: Option<Vec<StreamPermissionsArg>>,
+}
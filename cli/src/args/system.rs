@@ -1,4 +1,4 @@
-use crate::args::common::ListModeExt;
+use crate::args::common::{ListMode, ListModeExt};
 use clap::Args;
 use iggy::cli::utils::login_session_expiry::LoginSessionExpiry;
 
@@ -20,6 +20,34 @@ pub(crate) struct LoginArgs {
     pub(crate) expiry: Option<Vec<LoginSessionExpiry>>,
 }
 
+#[derive(Debug, Clone, Args)]
+pub(crate) struct AuditLogArgs {
+    /// Maximum number of the most recent entries to get
+    #[arg(short, long, default_value_t = 100)]
+    pub(crate) count: u32,
+
+    /// Get entries only for the user with given ID
+    #[arg(short, long)]
+    pub(crate) user_id: Option<u32>,
+
+    /// List mode (table or list)
+    #[clap(short, long, value_enum, default_value_t = ListMode::Table)]
+    pub(crate) list_mode: ListMode,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct BackupArgs {
+    /// Unique name of the backup
+    ///
+    /// If not set, the name is generated based on the current timestamp.
+    #[arg(short, long)]
+    pub(crate) name: Option<String>,
+
+    /// Upload the backup using the archiver configured on the server
+    #[arg(short, long, default_value_t = false)]
+    pub(crate) archive: bool,
+}
+
 #[derive(Debug, Clone, Args)]
 pub(crate) struct StatsArgs {
     /// List mode (table, list, JSON, TOML)
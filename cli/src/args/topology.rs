@@ -0,0 +1,49 @@
+use clap::{Args, ValueEnum};
+use iggy::cli::topology::manifest::TopologyFormat;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ApplyTopologyArgs {
+    /// Path to the topology manifest file
+    ///
+    /// Files with .yaml or .yml extension are parsed as YAML,
+    /// all the other ones as TOML.
+    #[clap(verbatim_doc_comment)]
+    #[arg(short, long)]
+    pub(crate) file: PathBuf,
+    /// Print the plan of the changes without applying them
+    #[arg(long, default_value_t = false)]
+    pub(crate) dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub(crate) enum TopologyFormatArg {
+    Toml,
+    Yaml,
+}
+
+impl From<TopologyFormatArg> for TopologyFormat {
+    fn from(format: TopologyFormatArg) -> Self {
+        match format {
+            TopologyFormatArg::Toml => TopologyFormat::Toml,
+            TopologyFormatArg::Yaml => TopologyFormat::Yaml,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ExportTopologyArgs {
+    /// Path to the file to which topology will be exported
+    ///
+    /// If not specified, topology is printed on standard output.
+    #[clap(verbatim_doc_comment)]
+    #[arg(short, long)]
+    pub(crate) file: Option<PathBuf>,
+    /// Format of the topology manifest
+    ///
+    /// If not specified, format is detected based on the file extension
+    /// (YAML for .yaml and .yml, TOML otherwise).
+    #[clap(verbatim_doc_comment)]
+    #[arg(long, value_enum)]
+    pub(crate) format: Option<TopologyFormatArg>,
+}
@@ -82,6 +82,31 @@ pub(crate) enum UserAction {
     ///  iggy user permissions client
     #[clap(verbatim_doc_comment, visible_alias = "p")]
     Permissions(UserPermissionsArgs),
+    /// Set roles for user with given ID
+    ///
+    /// The user ID and role IDs can be specified as either names or IDs.
+    /// Given roles replace all the roles currently assigned to the user,
+    /// if no roles are provided all the roles are removed from the user.
+    /// Effective permissions of the user are the union of its own
+    /// permissions and the permissions of all its roles.
+    ///
+    /// Examples:
+    ///  iggy user roles 2 readers writers
+    ///  iggy user roles testuser 1
+    ///  iggy user roles testuser
+    #[clap(verbatim_doc_comment, visible_alias = "r")]
+    Roles(UserRolesArgs),
+    /// Unlock user with given ID
+    ///
+    /// The user ID can be specified as either a username or an ID.
+    /// User is locked temporarily after too many failed login attempts,
+    /// unlocking the user also resets its failed login attempts.
+    ///
+    /// Examples:
+    ///  iggy user unlock 2
+    ///  iggy user unlock testuser
+    #[clap(verbatim_doc_comment, visible_alias = "u")]
+    Unlock(UserUnlockArgs),
 }
 
 #[derive(Debug, Clone, Args)]
@@ -285,3 +310,23 @@ pub(crate) struct UserPermissionsArgs {
     #[arg(value_parser = clap::value_parser!(StreamPermissionsArg))]
     pub(crate) stream_permissions: Option<Vec<StreamPermissionsArg>>,
 }
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct UserRolesArgs {
+    /// User ID to update
+    ///
+    /// The user ID can be specified as either a username or an ID
+    pub(crate) user_id: Identifier,
+    /// Role IDs to assign
+    ///
+    /// Each role ID can be specified as either a role name or an ID
+    pub(crate) roles: Vec<Identifier>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct UserUnlockArgs {
+    /// User ID to unlock
+    ///
+    /// The user ID can be specified as either a username or an ID
+    pub(crate) user_id: Identifier,
+}
@@ -6,8 +6,8 @@ mod logging;
 use crate::args::{
     client::ClientAction, consumer_group::ConsumerGroupAction,
     consumer_offset::ConsumerOffsetAction, permissions::PermissionsArgs,
-    personal_access_token::PersonalAccessTokenAction, stream::StreamAction, topic::TopicAction,
-    Command, IggyConsoleArgs,
+    personal_access_token::PersonalAccessTokenAction, role::RoleAction, stream::StreamAction,
+    topic::TopicAction, Command, IggyConsoleArgs,
 };
 use crate::credentials::IggyCredentials;
 use crate::error::IggyCmdError;
@@ -27,39 +27,56 @@ use iggy::cli::{
     consumer_group::{
         create_consumer_group::CreateConsumerGroupCmd,
         delete_consumer_group::DeleteConsumerGroupCmd, get_consumer_group::GetConsumerGroupCmd,
-        get_consumer_groups::GetConsumerGroupsCmd,
+        get_consumer_group_lag::GetConsumerGroupLagCmd, get_consumer_groups::GetConsumerGroupsCmd,
     },
     consumer_offset::{
         get_consumer_offset::GetConsumerOffsetCmd, set_consumer_offset::SetConsumerOffsetCmd,
     },
     context::get_contexts::GetContextsCmd,
-    message::{poll_messages::PollMessagesCmd, send_messages::SendMessagesCmd},
+    message::{
+        export_messages::ExportMessagesCmd, import_messages::ImportMessagesCmd,
+        poll_messages::PollMessagesCmd, send_messages::SendMessagesCmd,
+        tail_messages::TailMessagesCmd,
+    },
     partitions::{create_partitions::CreatePartitionsCmd, delete_partitions::DeletePartitionsCmd},
     personal_access_tokens::{
         create_personal_access_token::CreatePersonalAccessTokenCmd,
         delete_personal_access_tokens::DeletePersonalAccessTokenCmd,
         get_personal_access_tokens::GetPersonalAccessTokensCmd,
     },
+    roles::{
+        create_role::CreateRoleCmd,
+        delete_role::DeleteRoleCmd,
+        get_role::GetRoleCmd,
+        get_roles::GetRolesCmd,
+        update_role::{UpdateRoleCmd, UpdateRoleType},
+    },
     streams::{
         create_stream::CreateStreamCmd, delete_stream::DeleteStreamCmd, get_stream::GetStreamCmd,
         get_streams::GetStreamsCmd, purge_stream::PurgeStreamCmd, update_stream::UpdateStreamCmd,
     },
-    system::{login::LoginCmd, logout::LogoutCmd, me::GetMeCmd, ping::PingCmd, stats::GetStatsCmd},
+    system::{
+        audit_log::GetAuditLogCmd, backup::CreateBackupCmd, login::LoginCmd, logout::LogoutCmd,
+        me::GetMeCmd, ping::PingCmd, stats::GetStatsCmd,
+    },
     topics::{
         create_topic::CreateTopicCmd, delete_topic::DeleteTopicCmd, get_topic::GetTopicCmd,
         get_topics::GetTopicsCmd, purge_topic::PurgeTopicCmd, update_topic::UpdateTopicCmd,
     },
+    topology::{apply_topology::ApplyTopologyCmd, export_topology::ExportTopologyCmd},
     users::{
         change_password::ChangePasswordCmd,
         create_user::CreateUserCmd,
         delete_user::DeleteUserCmd,
         get_user::GetUserCmd,
         get_users::GetUsersCmd,
+        unlock_user::UnlockUserCmd,
         update_permissions::UpdatePermissionsCmd,
+        update_roles::UpdateRolesCmd,
         update_user::{UpdateUserCmd, UpdateUserType},
     },
 };
-use iggy::cli_command::{CliCommand, PRINT_TARGET};
+use iggy::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use iggy::client_provider::{self, ClientProviderConfig};
 use iggy::clients::client::{IggyClient, IggyClientBackgroundConfig};
 use iggy::utils::crypto::{Aes256GcmEncryptor, Encryptor};
@@ -139,6 +156,12 @@ fn get_command(
         Command::Ping(args) => Box::new(PingCmd::new(args.count)),
         Command::Me => Box::new(GetMeCmd::new()),
         Command::Stats(args) => Box::new(GetStatsCmd::new(cli_options.quiet, args.output.into())),
+        Command::AuditLog(args) => Box::new(GetAuditLogCmd::new(
+            args.count,
+            args.user_id,
+            args.list_mode.into(),
+        )),
+        Command::Backup(args) => Box::new(CreateBackupCmd::new(args.name.clone(), args.archive)),
         Command::Pat(command) => match command {
             PersonalAccessTokenAction::Create(pat_create_args) => {
                 Box::new(CreatePersonalAccessTokenCmd::new(
@@ -196,6 +219,42 @@ fn get_command(
                 )
                 .into(),
             )),
+            UserAction::Roles(roles_args) => Box::new(UpdateRolesCmd::new(
+                roles_args.user_id.clone(),
+                roles_args.roles.clone(),
+            )),
+            UserAction::Unlock(unlock_args) => {
+                Box::new(UnlockUserCmd::new(unlock_args.user_id.clone()))
+            }
+        },
+        Command::Role(command) => match command {
+            RoleAction::Create(create_args) => Box::new(CreateRoleCmd::new(
+                create_args.name.clone(),
+                PermissionsArgs::new(
+                    create_args.global_permissions.clone(),
+                    create_args.stream_permissions.clone(),
+                )
+                .into(),
+            )),
+            RoleAction::Delete(delete_args) => {
+                Box::new(DeleteRoleCmd::new(delete_args.role_id.clone()))
+            }
+            RoleAction::Get(get_args) => Box::new(GetRoleCmd::new(get_args.role_id.clone())),
+            RoleAction::List(list_args) => Box::new(GetRolesCmd::new(list_args.list_mode.into())),
+            RoleAction::Name(name_args) => Box::new(UpdateRoleCmd::new(
+                name_args.role_id.clone(),
+                UpdateRoleType::Name(name_args.name.clone()),
+            )),
+            RoleAction::Permissions(permissions_args) => Box::new(UpdateRoleCmd::new(
+                permissions_args.role_id.clone(),
+                UpdateRoleType::Permissions(
+                    PermissionsArgs::new(
+                        permissions_args.global_permissions.clone(),
+                        permissions_args.stream_permissions.clone(),
+                    )
+                    .into(),
+                ),
+            )),
         },
         Command::Client(command) => match command {
             ClientAction::Get(get_args) => Box::new(GetClientCmd::new(get_args.client_id)),
@@ -225,6 +284,12 @@ fn get_command(
                 list_args.topic_id.clone(),
                 list_args.list_mode.into(),
             )),
+            ConsumerGroupAction::Lag(lag_args) => Box::new(GetConsumerGroupLagCmd::new(
+                lag_args.stream_id.clone(),
+                lag_args.topic_id.clone(),
+                lag_args.group_id.clone(),
+                lag_args.partition_id,
+            )),
         },
         Command::Message(command) => match command {
             MessageAction::Send(send_args) => Box::new(SendMessagesCmd::new(
@@ -248,6 +313,38 @@ fn get_command(
                 poll_args.consumer.clone(),
                 poll_args.show_headers,
             )),
+            MessageAction::Tail(tail_args) => Box::new(TailMessagesCmd::new(
+                tail_args.stream_id.clone(),
+                tail_args.topic_id.clone(),
+                tail_args.partition_id,
+                tail_args.consumer.clone(),
+                tail_args.consumer_group.clone(),
+                tail_args.start(),
+                tail_args.message_count,
+                tail_args.interval,
+                tail_args.limit,
+                tail_args.payload_format.into(),
+                tail_args.schema_file.clone(),
+                tail_args.message_type.clone(),
+                tail_args.headers.clone(),
+                tail_args.show_headers,
+            )),
+            MessageAction::Export(export_args) => Box::new(ExportMessagesCmd::new(
+                export_args.stream_id.clone(),
+                export_args.topic_id.clone(),
+                export_args.partition_id,
+                export_args.range(),
+                export_args.file.clone(),
+                export_args.format.into(),
+                export_args.message_count,
+            )),
+            MessageAction::Import(import_args) => Box::new(ImportMessagesCmd::new(
+                import_args.stream_id.clone(),
+                import_args.topic_id.clone(),
+                import_args.partition_id,
+                import_args.file.clone(),
+                import_args.batch_size,
+            )),
         },
         Command::ConsumerOffset(command) => match command {
             ConsumerOffsetAction::Get(get_args) => Box::new(GetConsumerOffsetCmd::new(
@@ -272,6 +369,14 @@ fn get_command(
                 Box::new(UseContextCmd::new(use_args.context_name.clone()))
             }
         },
+        Command::Apply(apply_args) => Box::new(ApplyTopologyCmd::new(
+            apply_args.file.clone(),
+            apply_args.dry_run,
+        )),
+        Command::ExportTopology(export_args) => Box::new(ExportTopologyCmd::new(
+            export_args.file.clone(),
+            export_args.format.map(|format| format.into()),
+        )),
         Command::Login(login_args) => Box::new(LoginCmd::new(
             iggy_args.get_server_address().unwrap(),
             LoginSessionExpiry::new(login_args.expiry.clone()),
@@ -334,12 +439,16 @@ async fn main() -> Result<(), IggyCmdError> {
     credentials.set_iggy_client(&client);
     credentials.login_user().await?;
 
-    if command.use_tracing() {
-        event!(target: PRINT_TARGET, Level::INFO, "Executing {}", command.explain());
-    } else {
-        println!("Executing {}", command.explain());
+    // Only the result is printed in the machine-readable output formats, so it can be parsed.
+    let output = cli_options.output.into();
+    if output == OutputFormat::Table {
+        if command.use_tracing() {
+            event!(target: PRINT_TARGET, Level::INFO, "Executing {}", command.explain());
+        } else {
+            println!("Executing {}", command.explain());
+        }
     }
-    command.execute_cmd(&client).await?;
+    command.execute_cmd(&client, output).await?;
 
     credentials.logout_user().await?;
 
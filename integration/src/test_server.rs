@@ -148,6 +148,11 @@ impl TestServer {
     }
 
     pub fn start(&mut self) {
+        self.start_with_args(&[]);
+    }
+
+    /// Starts the server with the additional command line arguments, e.g. `--restore-backup`.
+    pub fn start_with_args(&mut self, args: &[&str]) {
         self.set_server_addrs_from_env();
         self.cleanup();
         let files_path = self.local_data_path.clone();
@@ -156,6 +161,7 @@ impl TestServer {
         } else {
             Command::cargo_bin("iggy-server").unwrap()
         };
+        command.args(args);
         command.env(SYSTEM_PATH_ENV_VAR, files_path.clone());
         command.envs(self.envs.clone());
 
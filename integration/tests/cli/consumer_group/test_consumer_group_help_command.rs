@@ -19,6 +19,7 @@ Commands:
   delete  Delete consumer group with given ID for given stream ID and topic ID [aliases: d]
   get     Get details of a single consumer group with given ID for given stream ID and topic ID [aliases: g]
   list    List all consumer groups for given stream ID and topic ID [aliases: l]
+  lag     Get lag of consumer group with given ID for given stream ID and topic ID
   help    Print this message or the help of the given subcommand(s)
 
 Options:
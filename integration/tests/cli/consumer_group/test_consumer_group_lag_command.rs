@@ -0,0 +1,260 @@
+use crate::cli::common::{
+    IggyCmdCommand, IggyCmdTest, IggyCmdTestCase, TestHelpCmd, CLAP_INDENT, USAGE_PREFIX,
+};
+use assert_cmd::assert::Assert;
+use async_trait::async_trait;
+use bytes::Bytes;
+use iggy::client::Client;
+use iggy::consumer::Consumer;
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use predicates::str::{contains, starts_with};
+use serial_test::parallel;
+
+struct TestConsumerGroupLagCmd {
+    stream_id: u32,
+    topic_id: u32,
+    group_name: String,
+    partitions_count: u32,
+    messages_count: u32,
+    partition_id: Option<u32>,
+}
+
+impl TestConsumerGroupLagCmd {
+    fn new(
+        stream_id: u32,
+        topic_id: u32,
+        group_name: String,
+        partitions_count: u32,
+        messages_count: u32,
+        partition_id: Option<u32>,
+    ) -> Self {
+        Self {
+            stream_id,
+            topic_id,
+            group_name,
+            partitions_count,
+            messages_count,
+            partition_id,
+        }
+    }
+
+    fn to_args(&self) -> Vec<String> {
+        let mut command = vec![
+            format!("{}", self.stream_id),
+            format!("{}", self.topic_id),
+            self.group_name.clone(),
+        ];
+
+        if let Some(partition_id) = self.partition_id {
+            command.push("--partition-id".into());
+            command.push(format!("{}", partition_id));
+        }
+
+        command
+    }
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestConsumerGroupLagCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        let stream = client.create_stream("stream", Some(self.stream_id)).await;
+        assert!(stream.is_ok());
+
+        let topic = client
+            .create_topic(
+                &self.stream_id.try_into().unwrap(),
+                "topic",
+                self.partitions_count,
+                Default::default(),
+                None,
+                Some(self.topic_id),
+                IggyExpiry::NeverExpire,
+                MaxTopicSize::ServerDefault,
+            )
+            .await;
+        assert!(topic.is_ok());
+
+        let consumer_group = client
+            .create_consumer_group(
+                &self.stream_id.try_into().unwrap(),
+                &self.topic_id.try_into().unwrap(),
+                &self.group_name,
+                None,
+            )
+            .await;
+        assert!(consumer_group.is_ok());
+
+        let mut messages = (0..self.messages_count)
+            .map(|id| Message::new(None, Bytes::from(format!("message {id}")), None))
+            .collect::<Vec<_>>();
+        let send_status = client
+            .send_messages(
+                &self.stream_id.try_into().unwrap(),
+                &self.topic_id.try_into().unwrap(),
+                &Partitioning::partition_id(1),
+                &mut messages,
+            )
+            .await;
+        assert!(send_status.is_ok());
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("consumer-group")
+            .arg("lag")
+            .args(self.to_args())
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        let partitions = match self.partition_id {
+            Some(partition_id) => format!("partition with ID: {partition_id}"),
+            None => "all partitions".to_string(),
+        };
+        let start_message = format!(
+            "Executing get lag of consumer group with ID: {} for {} of topic with ID: {} and stream with ID: {}",
+            self.group_name, partitions, self.topic_id, self.stream_id
+        );
+
+        command_state
+            .success()
+            .stdout(starts_with(start_message))
+            .stdout(contains("Partition ID"))
+            .stdout(contains("Lag Size"))
+            .stdout(contains("Total"));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let lags = client
+            .get_consumer_lag(
+                &Consumer::group(self.group_name.clone().try_into().unwrap()),
+                &self.stream_id.try_into().unwrap(),
+                &self.topic_id.try_into().unwrap(),
+                self.partition_id,
+            )
+            .await
+            .unwrap();
+        let expected_partitions_count = match self.partition_id {
+            Some(_) => 1,
+            None => self.partitions_count as usize,
+        };
+        assert_eq!(lags.len(), expected_partitions_count);
+
+        let lag = &lags[0];
+        assert_eq!(lag.partition_id, 1);
+        assert_eq!(lag.current_offset, (self.messages_count - 1) as u64);
+        assert_eq!(lag.stored_offset, None);
+        assert_eq!(lag.lag, self.messages_count as u64);
+        assert!(lag.lag_bytes > 0);
+        for lag in lags.iter().skip(1) {
+            assert_eq!(lag.lag, 0);
+            assert_eq!(lag.lag_bytes, 0);
+        }
+
+        let stream = client
+            .delete_stream(&self.stream_id.try_into().unwrap())
+            .await;
+        assert!(stream.is_ok());
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_be_successful() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test.setup().await;
+    for partition_id in [None, Some(1)] {
+        iggy_cmd_test
+            .execute_test(TestConsumerGroupLagCmd::new(
+                1,
+                2,
+                String::from("consumer-group"),
+                3,
+                5,
+                partition_id,
+            ))
+            .await;
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["consumer-group", "lag", "--help"],
+            format!(
+                r#"Get lag of consumer group with given ID for given stream ID and topic ID
+
+Lag is the number of messages (and their size) which were not yet consumed
+by the consumer group, calculated based on its stored offset in each partition.
+Stream ID can be specified as a stream name or ID
+Topic ID can be specified as a topic name or ID
+Consumer group ID can be specified as a consumer group name or ID
+
+Examples:
+ iggy consumer-group lag 1 2 3
+ iggy consumer-group lag stream topic group
+ iggy consumer-group lag stream topic group --partition-id 1
+
+{USAGE_PREFIX} consumer-group lag [OPTIONS] <STREAM_ID> <TOPIC_ID> <GROUP_ID>
+
+Arguments:
+  <STREAM_ID>
+          Stream ID to get consumer group lag
+{CLAP_INDENT}
+          Stream ID can be specified as a stream name or ID
+
+  <TOPIC_ID>
+          Topic ID to get consumer group lag
+{CLAP_INDENT}
+          Topic ID can be specified as a topic name or ID
+
+  <GROUP_ID>
+          Consumer group ID to get lag
+{CLAP_INDENT}
+          Consumer group ID can be specified as a consumer group name or ID
+
+Options:
+  -p, --partition-id <PARTITION_ID>
+          Partition ID to get lag, lag for all partitions is returned if not specified
+
+  -h, --help
+          Print help (see a summary with '-h')
+"#,
+            ),
+        ))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_short_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["consumer-group", "lag", "-h"],
+            format!(
+                r#"Get lag of consumer group with given ID for given stream ID and topic ID
+
+{USAGE_PREFIX} consumer-group lag [OPTIONS] <STREAM_ID> <TOPIC_ID> <GROUP_ID>
+
+Arguments:
+  <STREAM_ID>  Stream ID to get consumer group lag
+  <TOPIC_ID>   Topic ID to get consumer group lag
+  <GROUP_ID>   Consumer group ID to get lag
+
+Options:
+  -p, --partition-id <PARTITION_ID>  Partition ID to get lag, lag for all partitions is returned if not specified
+  -h, --help                         Print help (see more with '--help')
+"#,
+            ),
+        ))
+        .await;
+}
@@ -0,0 +1,226 @@
+use crate::cli::common::{IggyCmdCommand, IggyCmdTest, IggyCmdTestCase};
+use assert_cmd::assert::Assert;
+use async_trait::async_trait;
+use iggy::client::Client;
+use iggy::identifier::Identifier;
+use predicates::str::{contains, diff, starts_with};
+use serde_json::Value;
+use serial_test::parallel;
+
+struct TestStreamListJsonCmd {
+    stream_id: u32,
+    name: String,
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestStreamListJsonCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        let stream = client.create_stream(&self.name, Some(self.stream_id)).await;
+        assert!(stream.is_ok());
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .opts(vec!["--output", "json"])
+            .arg("stream")
+            .arg("list")
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        let output = command_state.success().get_output().stdout.clone();
+        let streams: Value = serde_json::from_slice(&output).unwrap();
+        let streams = streams.as_array().unwrap();
+
+        assert_eq!(streams.len(), 1);
+        assert_eq!(streams[0]["id"], self.stream_id);
+        assert_eq!(streams[0]["name"], self.name.as_str());
+        assert_eq!(streams[0]["size"], "0 B");
+        assert_eq!(streams[0]["messages_count"], 0);
+        assert_eq!(streams[0]["topics_count"], 0);
+        assert!(streams[0]["created_at"].is_u64());
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let stream = client
+            .delete_stream(&Identifier::numeric(self.stream_id).unwrap())
+            .await;
+        assert!(stream.is_ok());
+    }
+}
+
+struct TestStreamGetYamlCmd {
+    stream_id: u32,
+    name: String,
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestStreamGetYamlCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        let stream = client.create_stream(&self.name, Some(self.stream_id)).await;
+        assert!(stream.is_ok());
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .opts(vec!["-o", "yaml"])
+            .arg("stream")
+            .arg("get")
+            .arg(self.stream_id.to_string())
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        command_state
+            .success()
+            .stdout(starts_with(format!("id: {}\n", self.stream_id)))
+            .stdout(contains(format!("name: {}\n", self.name)))
+            .stdout(contains("topics: []\n"));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let stream = client
+            .delete_stream(&Identifier::numeric(self.stream_id).unwrap())
+            .await;
+        assert!(stream.is_ok());
+    }
+}
+
+struct TestStreamListCsvCmd {
+    streams: Vec<(u32, String)>,
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestStreamListCsvCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        for (stream_id, name) in &self.streams {
+            let stream = client.create_stream(name, Some(*stream_id)).await;
+            assert!(stream.is_ok());
+        }
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .opts(vec!["-o", "csv"])
+            .arg("stream")
+            .arg("list")
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        let output = command_state.success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        let mut lines = output.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some("created_at,id,messages_count,name,size,topics_count")
+        );
+        let mut rows = lines
+            .map(|line| line.split(',').collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        rows.sort_by_key(|row| row[1].parse::<u32>().unwrap());
+        assert_eq!(rows.len(), self.streams.len());
+        for (row, (stream_id, name)) in rows.iter().zip(&self.streams) {
+            assert_eq!(row[1..], [&stream_id.to_string(), "0", name, "0 B", "0"]);
+        }
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        for (stream_id, _) in &self.streams {
+            let stream = client
+                .delete_stream(&Identifier::numeric(*stream_id).unwrap())
+                .await;
+            assert!(stream.is_ok());
+        }
+    }
+}
+
+struct TestStreamCreateJsonCmd {
+    stream_id: u32,
+    name: String,
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestStreamCreateJsonCmd {
+    async fn prepare_server_state(&mut self, _client: &dyn Client) {}
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .opts(vec!["-o", "json"])
+            .arg("stream")
+            .arg("create")
+            .args(vec!["-s", &self.stream_id.to_string(), &self.name])
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        command_state.success().stdout(diff(format!(
+            "{{\n  \"message\": \"Stream with name: {} and ID: {} created\"\n}}\n",
+            self.name, self.stream_id
+        )));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let stream = client
+            .get_stream(&Identifier::numeric(self.stream_id).unwrap())
+            .await;
+        assert!(stream.is_ok());
+        assert_eq!(stream.unwrap().name, self.name);
+
+        let stream = client
+            .delete_stream(&Identifier::numeric(self.stream_id).unwrap())
+            .await;
+        assert!(stream.is_ok());
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_print_json_output() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test
+        .execute_test(TestStreamListJsonCmd {
+            stream_id: 1,
+            name: String::from("production"),
+        })
+        .await;
+    iggy_cmd_test
+        .execute_test(TestStreamCreateJsonCmd {
+            stream_id: 2,
+            name: String::from("testing"),
+        })
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_print_yaml_output() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test
+        .execute_test(TestStreamGetYamlCmd {
+            stream_id: 3,
+            name: String::from("staging"),
+        })
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_print_csv_output() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test
+        .execute_test(TestStreamListCsvCmd {
+            streams: vec![
+                (4, String::from("development")),
+                (5, String::from("sandbox")),
+            ],
+        })
+        .await;
+}
@@ -32,13 +32,18 @@ Commands:
   ping             ping iggy server
   me               get current client info
   stats            get iggy server statistics
+  audit-log        get audit log entries [aliases: al]
+  backup           create server backup [aliases: bk]
   pat              personal access token operations
   user             user operations [aliases: u]
+  role             role operations [aliases: r]
   client           client operations [aliases: c]
   consumer-group   consumer group operations [aliases: g]
   consumer-offset  consumer offset operations [aliases: o]
   message          message operations [aliases: m]
   context          context operations [aliases: ctx]
+  apply            apply topology from manifest file
+  export-topology  export topology to manifest file
   login            login to Iggy server [aliases: li]
   logout           logout from Iggy server [aliases: lo]
   help             Print this message or the help of the given subcommand(s)
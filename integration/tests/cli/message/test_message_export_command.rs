@@ -0,0 +1,299 @@
+use crate::cli::common::{
+    IggyCmdCommand, IggyCmdTest, IggyCmdTestCase, TestHelpCmd, CLAP_INDENT, USAGE_PREFIX,
+};
+use assert_cmd::assert::Assert;
+use async_trait::async_trait;
+use bytes::Bytes;
+use iggy::cli::message::message_file::{MessageFileFormat, MessageFileReader};
+use iggy::client::Client;
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use predicates::str::{contains, starts_with};
+use serial_test::parallel;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::str::FromStr;
+use tempfile::TempDir;
+
+struct TestMessageExportCmd {
+    stream_name: String,
+    topic_name: String,
+    // Payloads of the messages sent to each partition, the partition ID is the index + 1.
+    partitions: Vec<Vec<String>>,
+    file: PathBuf,
+    format: MessageFileFormat,
+    args: Vec<String>,
+    // Partition ID and offset of the expected exported messages.
+    expected: Vec<(u32, u64)>,
+}
+
+impl TestMessageExportCmd {
+    fn new(
+        dir: &TempDir,
+        format: MessageFileFormat,
+        args: Vec<&str>,
+        expected: Vec<(u32, u64)>,
+    ) -> Self {
+        Self {
+            stream_name: String::from("stream"),
+            topic_name: String::from("topic"),
+            partitions: vec![
+                vec![
+                    String::from("one"),
+                    String::from("two"),
+                    String::from("three"),
+                ],
+                vec![String::from("four")],
+            ],
+            file: dir.path().join(format!("messages.{format}")),
+            format,
+            args: args.into_iter().map(String::from).collect(),
+            expected,
+        }
+    }
+
+    fn header() -> (HeaderKey, HeaderValue) {
+        (
+            HeaderKey::from_str("source").unwrap(),
+            HeaderValue::from_str("export").unwrap(),
+        )
+    }
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestMessageExportCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        let stream = client.create_stream(&self.stream_name, None).await;
+        assert!(stream.is_ok());
+
+        let topic = client
+            .create_topic(
+                &self.stream_name.as_str().try_into().unwrap(),
+                &self.topic_name,
+                self.partitions.len() as u32,
+                Default::default(),
+                None,
+                None,
+                IggyExpiry::NeverExpire,
+                MaxTopicSize::ServerDefault,
+            )
+            .await;
+        assert!(topic.is_ok());
+
+        for (index, payloads) in self.partitions.iter().enumerate() {
+            let mut messages = payloads
+                .iter()
+                .map(|payload| {
+                    Message::new(
+                        None,
+                        Bytes::from(payload.clone()),
+                        Some(HashMap::from([Self::header()])),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let send_status = client
+                .send_messages(
+                    &self.stream_name.as_str().try_into().unwrap(),
+                    &self.topic_name.as_str().try_into().unwrap(),
+                    &Partitioning::partition_id(index as u32 + 1),
+                    &mut messages,
+                )
+                .await;
+            assert!(send_status.is_ok());
+        }
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("message")
+            .arg("export")
+            .args(self.args.clone())
+            .args(vec!["--format", &self.format.to_string()])
+            .arg(self.stream_name.clone())
+            .arg(self.topic_name.clone())
+            .arg(self.file.to_str().unwrap())
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        command_state
+            .success()
+            .stdout(starts_with("Executing export messages from "))
+            .stdout(contains(format!(
+                "Exported {} messages from ",
+                self.expected.len()
+            )));
+
+        let file = File::open(&self.file).unwrap();
+        let mut reader = MessageFileReader::new(BufReader::new(file)).unwrap();
+        assert_eq!(reader.format(), self.format);
+        let mut exported = Vec::new();
+        while let Some(message) = reader.read().unwrap() {
+            exported.push(message);
+        }
+
+        assert_eq!(exported.len(), self.expected.len());
+        for (message, (partition_id, offset)) in exported.iter().zip(&self.expected) {
+            assert_eq!(message.partition_id, *partition_id);
+            assert_eq!(message.offset, *offset);
+            assert_eq!(
+                message.payload,
+                self.partitions[*partition_id as usize - 1][*offset as usize].as_bytes()
+            );
+            assert_ne!(message.id, 0);
+            assert!(message.timestamp > 0);
+            let (key, value) = Self::header();
+            assert_eq!(message.headers.as_ref().unwrap().get(&key), Some(&value));
+        }
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let stream = client
+            .delete_stream(&self.stream_name.as_str().try_into().unwrap())
+            .await;
+        assert!(stream.is_ok());
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_be_successful() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test
+        .execute_test(TestMessageExportCmd::new(
+            &dir,
+            MessageFileFormat::Jsonl,
+            vec![],
+            vec![(1, 0), (1, 1), (1, 2), (2, 0)],
+        ))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestMessageExportCmd::new(
+            &dir,
+            MessageFileFormat::Binary,
+            vec![],
+            vec![(1, 0), (1, 1), (1, 2), (2, 0)],
+        ))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestMessageExportCmd::new(
+            &dir,
+            MessageFileFormat::Jsonl,
+            vec![
+                "--partition-id",
+                "1",
+                "--from-offset",
+                "1",
+                "--to-offset",
+                "1",
+            ],
+            vec![(1, 1)],
+        ))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestMessageExportCmd::new(
+            &dir,
+            MessageFileFormat::Binary,
+            vec!["--from-offset", "1"],
+            vec![(1, 1), (1, 2)],
+        ))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestMessageExportCmd::new(
+            &dir,
+            MessageFileFormat::Jsonl,
+            vec!["--from-timestamp", "0", "--to-timestamp", "1"],
+            vec![],
+        ))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["message", "export", "--help"],
+            format!(
+                r#"Export messages from given topic ID and given stream ID to file
+
+Stream ID can be specified as a stream name or ID
+Topic ID can be specified as a topic name or ID
+Messages are exported with their partition IDs, offsets, timestamps,
+IDs and headers, so they can be imported with the import command.
+
+Examples:
+ iggy message export 1 2 messages.jsonl
+ iggy message export --partition-id 1 --from-offset 10 --to-offset 20 stream topic messages.jsonl
+ iggy message export --from-timestamp "2024-05-01 10:00:00" stream topic messages.jsonl
+ iggy message export --format binary stream topic messages.bin
+
+{USAGE_PREFIX} message export [OPTIONS] <STREAM_ID> <TOPIC_ID> <FILE>
+
+Arguments:
+  <STREAM_ID>
+          ID of the stream from which messages will be exported
+{CLAP_INDENT}
+          Stream ID can be specified as a stream name or ID
+
+  <TOPIC_ID>
+          ID of the topic from which messages will be exported
+{CLAP_INDENT}
+          Topic ID can be specified as a topic name or ID
+
+  <FILE>
+          Path to the file to which messages will be exported
+
+Options:
+  -p, --partition-id <PARTITION_ID>
+          Partition ID from which messages will be exported
+{CLAP_INDENT}
+          If not specified, messages from all partitions are exported.
+
+      --from-offset <FROM_OFFSET>
+          Export messages starting from the given offset in each partition
+
+      --to-offset <TO_OFFSET>
+          Export messages up to the given offset (inclusive) in each partition
+
+      --from-timestamp <FROM_TIMESTAMP>
+          Export messages appended at or after the given time
+{CLAP_INDENT}
+          Time can be specified as RFC 3339 date and time, UTC date and time
+          in "%Y-%m-%d %H:%M:%S" format or number of microseconds since Unix epoch.
+
+      --to-timestamp <TO_TIMESTAMP>
+          Export messages appended at or before the given time
+{CLAP_INDENT}
+          Time can be specified in the same formats as for the --from-timestamp option.
+
+  -f, --format <FORMAT>
+          Format of the file
+{CLAP_INDENT}
+          JSON Lines format stores each message as a separate JSON object
+          with the payload encoded as Base64, binary format is more compact.
+{CLAP_INDENT}
+          [default: jsonl]
+          [possible values: jsonl, binary]
+
+  -m, --message-count <MESSAGE_COUNT>
+          Number of messages to poll in a single batch
+{CLAP_INDENT}
+          [default: 1000]
+
+  -h, --help
+          Print help (see a summary with '-h')
+"#,
+            ),
+        ))
+        .await;
+}
@@ -0,0 +1,257 @@
+use crate::cli::common::{
+    IggyCmdCommand, IggyCmdTest, IggyCmdTestCase, TestHelpCmd, CLAP_INDENT, USAGE_PREFIX,
+};
+use assert_cmd::assert::Assert;
+use async_trait::async_trait;
+use bytes::Bytes;
+use iggy::cli::message::message_file::{ExportedMessage, MessageFileFormat, MessageFileWriter};
+use iggy::client::Client;
+use iggy::consumer::Consumer;
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use predicates::str::diff;
+use serial_test::parallel;
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::PathBuf;
+use std::str::FromStr;
+use tempfile::TempDir;
+
+struct TestMessageImportCmd {
+    stream_id: u32,
+    topic_id: u32,
+    partitions_count: u32,
+    file: PathBuf,
+    format: MessageFileFormat,
+    partition_id: Option<u32>,
+    messages: Vec<ExportedMessage>,
+}
+
+impl TestMessageImportCmd {
+    fn new(dir: &TempDir, format: MessageFileFormat, partition_id: Option<u32>) -> Self {
+        let message = |partition_id: u32, offset: u64, id: u128, payload: &str, header: bool| {
+            ExportedMessage {
+                partition_id,
+                offset,
+                timestamp: 1_000_000 + offset,
+                id,
+                headers: header.then(|| {
+                    HashMap::from([(
+                        HeaderKey::from_str("source").unwrap(),
+                        HeaderValue::from_uint64(offset).unwrap(),
+                    )])
+                }),
+                payload: Bytes::from(payload.to_owned()),
+            }
+        };
+
+        Self {
+            stream_id: 1,
+            topic_id: 1,
+            partitions_count: 2,
+            file: dir.path().join(format!("messages.{format}")),
+            format,
+            partition_id,
+            messages: vec![
+                message(2, 0, 100, "first", true),
+                message(1, 0, 200, "second", false),
+                message(1, 1, u128::MAX, "third", true),
+                message(2, 1, 300, "fourth", false),
+            ],
+        }
+    }
+
+    fn expected_partition_id(&self, message: &ExportedMessage) -> u32 {
+        self.partition_id.unwrap_or(message.partition_id)
+    }
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestMessageImportCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        let stream = client.create_stream("stream", Some(self.stream_id)).await;
+        assert!(stream.is_ok());
+
+        let topic = client
+            .create_topic(
+                &self.stream_id.try_into().unwrap(),
+                "topic",
+                self.partitions_count,
+                Default::default(),
+                None,
+                Some(self.topic_id),
+                IggyExpiry::NeverExpire,
+                MaxTopicSize::ServerDefault,
+            )
+            .await;
+        assert!(topic.is_ok());
+
+        let file = File::create(&self.file).unwrap();
+        let mut writer = MessageFileWriter::new(file, self.format).unwrap();
+        for message in &self.messages {
+            writer.write(message).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        let command = IggyCmdCommand::new().arg("message").arg("import");
+        let command = match self.partition_id {
+            Some(partition_id) => {
+                command.args(vec!["--partition-id".to_string(), partition_id.to_string()])
+            }
+            None => command,
+        };
+
+        command
+            .arg(self.stream_id.to_string())
+            .arg(self.topic_id.to_string())
+            .arg(self.file.to_str().unwrap())
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        let partition_info = match self.partition_id {
+            Some(partition_id) => format!("partition with ID: {partition_id}"),
+            None => String::from("original partitions"),
+        };
+        let message = format!(
+            "Executing import messages from file: {file} to {partition_info} of topic with ID: {} and stream with ID: {}\n\
+            Imported {} messages from file: {file} in {} format to {partition_info} of topic with ID: {} and stream with ID: {}\n",
+            self.topic_id,
+            self.stream_id,
+            self.messages.len(),
+            self.format,
+            self.topic_id,
+            self.stream_id,
+            file = self.file.display(),
+        );
+
+        command_state.success().stdout(diff(message));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        for partition_id in 1..=self.partitions_count {
+            let expected = self
+                .messages
+                .iter()
+                .filter(|message| self.expected_partition_id(message) == partition_id)
+                .collect::<Vec<_>>();
+            let polled = client
+                .poll_messages(
+                    &self.stream_id.try_into().unwrap(),
+                    &self.topic_id.try_into().unwrap(),
+                    Some(partition_id),
+                    &Consumer::default(),
+                    &PollingStrategy::offset(0),
+                    10,
+                    false,
+                )
+                .await;
+            assert!(polled.is_ok());
+            let polled = polled.unwrap();
+
+            assert_eq!(polled.messages.len(), expected.len());
+            for (polled, expected) in polled.messages.iter().zip(expected) {
+                assert_eq!(polled.id, expected.id);
+                assert_eq!(polled.payload, expected.payload);
+                assert_eq!(polled.headers, expected.headers);
+            }
+        }
+
+        let stream = client
+            .delete_stream(&self.stream_id.try_into().unwrap())
+            .await;
+        assert!(stream.is_ok());
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_be_successful() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test
+        .execute_test(TestMessageImportCmd::new(
+            &dir,
+            MessageFileFormat::Jsonl,
+            None,
+        ))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestMessageImportCmd::new(
+            &dir,
+            MessageFileFormat::Binary,
+            None,
+        ))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestMessageImportCmd::new(
+            &dir,
+            MessageFileFormat::Binary,
+            Some(2),
+        ))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["message", "import", "--help"],
+            format!(
+                r#"Import messages from file to given topic ID and given stream ID
+
+Stream ID can be specified as a stream name or ID
+Topic ID can be specified as a topic name or ID
+Messages are sent to the partitions they were exported from
+along with their IDs and headers. Format of the file is detected
+automatically.
+
+Examples:
+ iggy message import 1 2 messages.jsonl
+ iggy message import stream topic messages.bin
+ iggy message import --partition-id 1 stream topic messages.jsonl
+
+{USAGE_PREFIX} message import [OPTIONS] <STREAM_ID> <TOPIC_ID> <FILE>
+
+Arguments:
+  <STREAM_ID>
+          ID of the stream to which messages will be imported
+{CLAP_INDENT}
+          Stream ID can be specified as a stream name or ID
+
+  <TOPIC_ID>
+          ID of the topic to which messages will be imported
+{CLAP_INDENT}
+          Topic ID can be specified as a topic name or ID
+
+  <FILE>
+          Path to the file from which messages will be imported
+
+Options:
+  -p, --partition-id <PARTITION_ID>
+          Partition ID to which all the messages will be imported
+{CLAP_INDENT}
+          If not specified, messages are imported to the partitions
+          they were exported from.
+
+  -b, --batch-size <BATCH_SIZE>
+          Maximum number of messages sent in a single batch
+{CLAP_INDENT}
+          [default: 1000]
+
+  -h, --help
+          Print help (see a summary with '-h')
+"#,
+            ),
+        ))
+        .await;
+}
@@ -0,0 +1,499 @@
+use crate::cli::common::{
+    IggyCmdCommand, IggyCmdTest, IggyCmdTestCase, TestHelpCmd, CLAP_INDENT, USAGE_PREFIX,
+};
+use assert_cmd::assert::Assert;
+use assert_cmd::prelude::CommandCargoExt;
+use async_trait::async_trait;
+use bytes::Bytes;
+use iggy::client::Client;
+use iggy::clients::client::{IggyClient, IggyClientBackgroundConfig};
+use iggy::consumer::Consumer;
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::models::header::{HeaderKey, HeaderValue};
+use iggy::users::defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USERNAME};
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::timestamp::IggyTimestamp;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::tcp_client::TcpClientFactory;
+use integration::test_server::{login_root, ClientFactory, TestServer};
+use predicates::prelude::PredicateBooleanExt;
+use predicates::str::{contains, starts_with};
+use serde_json::Value;
+use serial_test::parallel;
+use std::collections::HashMap;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::time::Duration;
+
+const STREAM_ID: u32 = 1;
+const TOPIC_ID: u32 = 2;
+const STREAM_NAME: &str = "stream";
+const TOPIC_NAME: &str = "topic";
+
+async fn create_topic(client: &dyn Client, partitions_count: u32) {
+    let stream = client.create_stream(STREAM_NAME, Some(STREAM_ID)).await;
+    assert!(stream.is_ok());
+
+    let topic = client
+        .create_topic(
+            &STREAM_ID.try_into().unwrap(),
+            TOPIC_NAME,
+            partitions_count,
+            Default::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await;
+    assert!(topic.is_ok());
+}
+
+async fn send_messages(client: &dyn Client, partition_id: u32, messages: Vec<Message>) {
+    let mut messages = messages;
+    let send_status = client
+        .send_messages(
+            &STREAM_ID.try_into().unwrap(),
+            &TOPIC_ID.try_into().unwrap(),
+            &Partitioning::partition_id(partition_id),
+            &mut messages,
+        )
+        .await;
+    assert!(send_status.is_ok());
+}
+
+fn to_messages(payloads: &[&str]) -> Vec<Message> {
+    payloads
+        .iter()
+        .map(|payload| Message::new(None, Bytes::from(payload.to_string()), None))
+        .collect()
+}
+
+async fn delete_stream(client: &dyn Client) {
+    let stream = client.delete_stream(&STREAM_ID.try_into().unwrap()).await;
+    assert!(stream.is_ok());
+}
+
+struct TestMessageTailAllPartitionsCmd {}
+
+#[async_trait]
+impl IggyCmdTestCase for TestMessageTailAllPartitionsCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        create_topic(client, 2).await;
+        send_messages(client, 1, to_messages(&["first", "second"])).await;
+        send_messages(client, 2, to_messages(&["third"])).await;
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("message")
+            .arg("tail")
+            .args(vec!["--first", "--limit", "3", STREAM_NAME, TOPIC_NAME])
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        command_state
+            .success()
+            .stdout(starts_with(format!(
+                "Executing tail messages from all partitions of topic with ID: {TOPIC_NAME} and stream with ID: {STREAM_NAME}\n"
+            )))
+            .stdout(contains("Partition: 1 | Offset: 0 |"))
+            .stdout(contains("Partition: 1 | Offset: 1 |"))
+            .stdout(contains("Partition: 2 | Offset: 0 |"))
+            .stdout(contains("first\n"))
+            .stdout(contains("second\n"))
+            .stdout(contains("third\n"));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        delete_stream(client).await;
+    }
+}
+
+struct TestMessageTailJsonCmd {}
+
+#[async_trait]
+impl IggyCmdTestCase for TestMessageTailJsonCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        create_topic(client, 1).await;
+        let messages = (0..4)
+            .map(|number| {
+                let kind = if number % 2 == 0 { "even" } else { "odd" };
+                Message::new(
+                    None,
+                    Bytes::from(format!(r#"{{"number":{number}}}"#)),
+                    Some(HashMap::from([(
+                        HeaderKey::from_str("kind").unwrap(),
+                        HeaderValue::from_str(kind).unwrap(),
+                    )])),
+                )
+            })
+            .collect();
+        send_messages(client, 1, messages).await;
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .opts(vec!["--output", "json"])
+            .arg("message")
+            .arg("tail")
+            .args(vec![
+                "--partition-id",
+                "1",
+                "--first",
+                "--limit",
+                "2",
+                "--payload-format",
+                "json",
+                "--headers",
+                "kind:string:even",
+                &STREAM_ID.to_string(),
+                &TOPIC_ID.to_string(),
+            ])
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        let output = command_state.success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        let messages = output
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(messages.len(), 2);
+        for (message, number) in messages.iter().zip([0, 2]) {
+            assert_eq!(message["partition_id"], 1);
+            assert_eq!(message["offset"], number);
+            assert_eq!(message["headers"]["kind"], "even");
+            assert_eq!(message["payload"]["number"], number);
+            assert!(message["id"].is_string());
+            assert!(message["timestamp"].is_u64());
+        }
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        delete_stream(client).await;
+    }
+}
+
+struct TestMessageTailFromTimestampCmd {
+    from: Option<IggyTimestamp>,
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestMessageTailFromTimestampCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        create_topic(client, 1).await;
+        send_messages(client, 1, to_messages(&["old message"])).await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        self.from = Some(IggyTimestamp::now());
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        send_messages(client, 1, to_messages(&["new message"])).await;
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("message")
+            .arg("tail")
+            .args(vec![
+                "--from".to_string(),
+                self.from.unwrap().as_micros().to_string(),
+                "--limit".to_string(),
+                "1".to_string(),
+                "--payload-format".to_string(),
+                "hex".to_string(),
+                STREAM_NAME.to_string(),
+                TOPIC_NAME.to_string(),
+            ])
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        let new_message_hex = "new message"
+            .bytes()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        command_state
+            .success()
+            .stdout(contains("Partition: 1 | Offset: 1 |"))
+            .stdout(contains(new_message_hex))
+            .stdout(contains("Offset: 0").not());
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        delete_stream(client).await;
+    }
+}
+
+struct TestMessageTailConsumerGroupCmd {
+    group_id: u32,
+    group_name: String,
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestMessageTailConsumerGroupCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        create_topic(client, 1).await;
+        let group = client
+            .create_consumer_group(
+                &STREAM_ID.try_into().unwrap(),
+                &TOPIC_ID.try_into().unwrap(),
+                &self.group_name,
+                Some(self.group_id),
+            )
+            .await;
+        assert!(group.is_ok());
+        send_messages(client, 1, to_messages(&["one", "two", "three"])).await;
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("message")
+            .arg("tail")
+            .args(vec![
+                "--consumer-group",
+                &self.group_name,
+                "--limit",
+                "3",
+                STREAM_NAME,
+                TOPIC_NAME,
+            ])
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        command_state
+            .success()
+            .stdout(starts_with(format!(
+                "Executing tail messages from partitions assigned to consumer group with ID: {} of topic with ID: {TOPIC_NAME} and stream with ID: {STREAM_NAME}\n",
+                self.group_name
+            )))
+            .stdout(contains("one\n"))
+            .stdout(contains("two\n"))
+            .stdout(contains("three\n"));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let lag = client
+            .get_consumer_lag(
+                &Consumer::group(self.group_id.try_into().unwrap()),
+                &STREAM_ID.try_into().unwrap(),
+                &TOPIC_ID.try_into().unwrap(),
+                Some(1),
+            )
+            .await;
+        assert!(lag.is_ok());
+        let lag = lag.unwrap();
+        assert_eq!(lag.len(), 1);
+        assert_eq!(lag[0].stored_offset, Some(2));
+        assert_eq!(lag[0].lag, 0);
+
+        delete_stream(client).await;
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_be_successful() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test
+        .execute_test(TestMessageTailAllPartitionsCmd {})
+        .await;
+    iggy_cmd_test.execute_test(TestMessageTailJsonCmd {}).await;
+    iggy_cmd_test
+        .execute_test(TestMessageTailFromTimestampCmd { from: None })
+        .await;
+    iggy_cmd_test
+        .execute_test(TestMessageTailConsumerGroupCmd {
+            group_id: 1,
+            group_name: String::from("group"),
+        })
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_follow_appended_messages() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_address = test_server.get_raw_tcp_addr().unwrap();
+    let client = IggyClient::create(
+        TcpClientFactory {
+            server_addr: server_address.clone(),
+        }
+        .create_client()
+        .await,
+        IggyClientBackgroundConfig::default(),
+        None,
+        None,
+        None,
+    );
+    login_root(&client).await;
+    create_topic(&client, 1).await;
+    send_messages(&client, 1, to_messages(&["old message"])).await;
+
+    let mut tail = Command::cargo_bin("iggy")
+        .unwrap()
+        .env("IGGY_USERNAME", DEFAULT_ROOT_USERNAME)
+        .env("IGGY_PASSWORD", DEFAULT_ROOT_PASSWORD)
+        .args([
+            "--tcp-server-address",
+            &server_address,
+            "message",
+            "tail",
+            "--interval",
+            "50ms",
+            "--limit",
+            "1",
+            STREAM_NAME,
+            TOPIC_NAME,
+        ])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // The messages are sent until the tail picks one of them, as it only follows the messages
+    // appended after it has started, which happens at some point after spawning the process.
+    let mut exited = false;
+    for number in 0..200 {
+        send_messages(&client, 1, to_messages(&[&format!("new message {number}")])).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        if tail.try_wait().unwrap().is_some() {
+            exited = true;
+            break;
+        }
+    }
+    if !exited {
+        tail.kill().unwrap();
+    }
+
+    let output = tail.wait_with_output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(exited, "Tail did not pick any appended message: {stdout}");
+    assert!(output.status.success());
+    assert!(stdout.contains("new message"));
+    assert!(!stdout.contains("old message"));
+
+    delete_stream(&client).await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["message", "tail", "--help"],
+            format!(
+                r#"Follow messages appended to given topic ID and given stream ID
+
+Stream ID can be specified as a stream name or ID
+Topic ID can be specified as a topic name or ID
+Messages are printed as soon as they are appended to the topic
+until the command is interrupted or the limit of messages is reached.
+
+Examples:
+ iggy message tail 1 2
+ iggy message tail --partition-id 1 --first stream topic
+ iggy message tail --from "2024-05-01 10:00:00" stream topic
+ iggy message tail --consumer-group group stream topic
+ iggy message tail --payload-format json --headers key:string:value stream topic
+
+{USAGE_PREFIX} message tail [OPTIONS] <STREAM_ID> <TOPIC_ID>
+
+Arguments:
+  <STREAM_ID>
+          ID of the stream from which messages will be followed
+{CLAP_INDENT}
+          Stream ID can be specified as a stream name or ID
+
+  <TOPIC_ID>
+          ID of the topic from which messages will be followed
+{CLAP_INDENT}
+          Topic ID can be specified as a topic name or ID
+
+Options:
+  -p, --partition-id <PARTITION_ID>
+          Partition ID from which messages will be followed
+{CLAP_INDENT}
+          If not specified, messages from all partitions are followed.
+
+  -o, --offset <OFFSET>
+          Start following from the given offset in each partition
+{CLAP_INDENT}
+          If no start position is specified, only the messages appended
+          after the command was started are printed.
+
+  -f, --first
+          Start following from the first message in each partition
+
+      --from <FROM>
+          Start following from the messages appended at or after the given time
+{CLAP_INDENT}
+          Time can be specified as RFC 3339 date and time, UTC date and time
+          in "%Y-%m-%d %H:%M:%S" format or number of microseconds since Unix epoch.
+
+  -c, --consumer <CONSUMER>
+          Regular consumer which will poll messages
+{CLAP_INDENT}
+          Consumer ID can be specified as a consumer name or ID
+{CLAP_INDENT}
+          [default: 1]
+
+  -g, --consumer-group <CONSUMER_GROUP>
+          Consumer group which will be joined to follow messages
+{CLAP_INDENT}
+          Messages are followed from the partitions assigned to the group member
+          starting from the stored consumer group offset, which is committed
+          automatically. Consumer group ID can be specified as a name or ID.
+
+  -m, --message-count <MESSAGE_COUNT>
+          Number of messages to poll in a single batch
+{CLAP_INDENT}
+          [default: 100]
+
+  -i, --interval <INTERVAL>
+          Interval between polls when there are no new messages
+{CLAP_INDENT}
+          [default: 500ms]
+
+  -l, --limit <LIMIT>
+          Stop after printing given number of messages
+
+  -P, --payload-format <PAYLOAD_FORMAT>
+          Format used to render the message payload
+{CLAP_INDENT}
+          Protobuf format requires the schema file with the file descriptor set
+          (protoc --include_imports --descriptor_set_out) and the message type,
+          Avro format requires the schema file with the JSON schema.
+{CLAP_INDENT}
+          [default: string]
+          [possible values: string, hex, base64, json, protobuf, avro]
+
+      --schema-file <SCHEMA_FILE>
+          Path to the schema file used to decode Protobuf or Avro payload
+
+      --message-type <MESSAGE_TYPE>
+          Fully qualified name of the Protobuf message type, e.g. package.Message
+
+  -H, --headers <HEADERS>
+          Comma separated list of key:kind:value, only messages with all the headers are printed
+{CLAP_INDENT}
+          Kind can be one of the following: raw, string, bool, int8, int16, int32, int64,
+          int128, uint8, uint16, uint32, uint64, uint128, float32, float64
+
+  -s, --show-headers
+          Include the message headers in the output
+
+  -h, --help
+          Print help (see a summary with '-h')
+"#,
+            ),
+        ))
+        .await;
+}
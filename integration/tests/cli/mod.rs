@@ -7,7 +7,9 @@ mod general;
 mod message;
 mod partition;
 mod personal_access_token;
+mod role;
 mod stream;
 mod system;
 mod topic;
+mod topology;
 mod user;
@@ -0,0 +1,2 @@
+mod test_role_create_command;
+mod test_role_help_command;
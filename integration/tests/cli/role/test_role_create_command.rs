@@ -0,0 +1,101 @@
+use crate::cli::common::{IggyCmdCommand, IggyCmdTest, IggyCmdTestCase};
+use assert_cmd::assert::Assert;
+use async_trait::async_trait;
+use iggy::client::Client;
+use iggy::identifier::Identifier;
+use iggy::models::permissions::{GlobalPermissions, Permissions};
+use predicates::str::diff;
+use serial_test::parallel;
+
+struct TestRoleCreateCmd {
+    name: String,
+    global_permissions: Option<String>,
+    expected_permissions: Option<Permissions>,
+}
+
+impl TestRoleCreateCmd {
+    fn new(
+        name: String,
+        global_permissions: Option<String>,
+        expected_permissions: Option<Permissions>,
+    ) -> Self {
+        Self {
+            name,
+            global_permissions,
+            expected_permissions,
+        }
+    }
+
+    fn to_args(&self) -> Vec<String> {
+        let mut args = vec![self.name.clone()];
+        if let Some(global_permissions) = &self.global_permissions {
+            args.push(String::from("--global-permissions"));
+            args.push(global_permissions.clone());
+        }
+
+        args
+    }
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestRoleCreateCmd {
+    async fn prepare_server_state(&mut self, _client: &dyn Client) {}
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("role")
+            .arg("create")
+            .args(self.to_args())
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        let message = format!(
+            "Executing create role with name: {}\nRole with name: {} created\n",
+            self.name, self.name
+        );
+
+        command_state.success().stdout(diff(message));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let role = client
+            .get_role(&Identifier::named(&self.name).unwrap())
+            .await;
+        assert!(role.is_ok());
+        let role = role.unwrap();
+        assert_eq!(role.name, self.name);
+        assert_eq!(role.permissions, self.expected_permissions);
+
+        let deleted = client
+            .delete_role(&Identifier::numeric(role.id).unwrap())
+            .await;
+        assert!(deleted.is_ok());
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_be_successful() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test
+        .execute_test(TestRoleCreateCmd::new(String::from("empty"), None, None))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestRoleCreateCmd::new(
+            String::from("readers"),
+            Some(String::from("r_str,r_top,p_msg")),
+            Some(Permissions {
+                global: GlobalPermissions {
+                    read_streams: true,
+                    read_topics: true,
+                    poll_messages: true,
+                    ..Default::default()
+                },
+                streams: None,
+            }),
+        ))
+        .await;
+}
@@ -1,4 +1,6 @@
 // Disable tests due to missing keyring on macOS until #794 is implemented
+mod test_audit_log_command;
+mod test_backup_command;
 #[cfg(not(target_os = "macos"))]
 mod test_cli_session_scenario;
 mod test_login_cmd;
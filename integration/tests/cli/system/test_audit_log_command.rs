@@ -0,0 +1,131 @@
+use crate::cli::common::{
+    IggyCmdCommand, IggyCmdTest, IggyCmdTestCase, OutputFormat, TestHelpCmd, CLAP_INDENT,
+    USAGE_PREFIX,
+};
+use assert_cmd::assert::Assert;
+use async_trait::async_trait;
+use iggy::client::Client;
+use iggy::command::CREATE_STREAM;
+use iggy::identifier::Identifier;
+use predicates::str::{contains, starts_with};
+use serial_test::parallel;
+
+struct TestAuditLogCmd {
+    stream_name: String,
+    count: u32,
+    output: OutputFormat,
+}
+
+impl TestAuditLogCmd {
+    fn new(stream_name: String, count: u32, output: OutputFormat) -> Self {
+        Self {
+            stream_name,
+            count,
+            output,
+        }
+    }
+
+    fn to_args(&self) -> Vec<String> {
+        let mut args = vec![String::from("--count"), format!("{}", self.count)];
+        args.extend(self.output.to_args().into_iter().map(String::from));
+        args
+    }
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestAuditLogCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        let stream = client.create_stream(&self.stream_name, None).await;
+        assert!(stream.is_ok());
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("audit-log")
+            .args(self.to_args())
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        command_state
+            .success()
+            .stdout(starts_with(format!(
+                "Executing get {} most recent audit log entries in {} mode",
+                self.count, self.output
+            )))
+            .stdout(contains(CREATE_STREAM));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let stream = client
+            .delete_stream(&Identifier::named(&self.stream_name).unwrap())
+            .await;
+        assert!(stream.is_ok());
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_be_successful() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test
+        .execute_test(TestAuditLogCmd::new(
+            String::from("audited"),
+            1000,
+            OutputFormat::Default,
+        ))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestAuditLogCmd::new(
+            String::from("audited"),
+            1000,
+            OutputFormat::List,
+        ))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["audit-log", "--help"],
+            format!(
+                r#"get audit log entries
+
+Collect the most recent audit log entries recording who (user ID, client address
+and transport) performed which action on Iggy server and with which result.
+
+Examples:
+ iggy audit-log
+ iggy audit-log --count 10
+ iggy audit-log -c 10 -u 1 -l list
+
+{USAGE_PREFIX} audit-log [OPTIONS]
+
+Options:
+  -c, --count <COUNT>
+          Maximum number of the most recent entries to get
+{CLAP_INDENT}
+          [default: 100]
+
+  -u, --user-id <USER_ID>
+          Get entries only for the user with given ID
+
+  -l, --list-mode <LIST_MODE>
+          List mode (table or list)
+{CLAP_INDENT}
+          [default: table]
+          [possible values: table, list]
+
+  -h, --help
+          Print help (see a summary with '-h')
+"#,
+            ),
+        ))
+        .await;
+}
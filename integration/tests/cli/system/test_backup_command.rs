@@ -0,0 +1,100 @@
+use crate::cli::common::{
+    IggyCmdCommand, IggyCmdTest, IggyCmdTestCase, TestHelpCmd, CLAP_INDENT, USAGE_PREFIX,
+};
+use assert_cmd::assert::Assert;
+use async_trait::async_trait;
+use iggy::client::Client;
+use predicates::str::{contains, starts_with};
+use serial_test::parallel;
+
+struct TestBackupCmd {
+    name: String,
+}
+
+impl TestBackupCmd {
+    fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestBackupCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        let stream = client.create_stream("backup", None).await;
+        assert!(stream.is_ok());
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("backup")
+            .arg("--name")
+            .arg(self.name.clone())
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        command_state
+            .success()
+            .stdout(starts_with(format!(
+                "Executing create backup with name: {}",
+                self.name
+            )))
+            .stdout(contains("Backup created"))
+            .stdout(contains(self.name.as_str()));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let backup = client.create_backup(Some(&self.name), false).await;
+        assert!(backup.is_err());
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_be_successful() {
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test
+        .execute_test(TestBackupCmd::new(String::from("daily")))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["backup", "--help"],
+            format!(
+                r#"create server backup
+
+Create a consistent backup of the state and the messages of the running Iggy server
+in its backup directory, optionally uploaded using the configured archiver.
+The server can be restored from the backup on startup with --restore-backup option.
+
+Examples:
+ iggy backup
+ iggy backup --name daily
+ iggy backup -n daily -a
+
+{USAGE_PREFIX} backup [OPTIONS]
+
+Options:
+  -n, --name <NAME>
+          Unique name of the backup
+{CLAP_INDENT}
+          If not set, the name is generated based on the current timestamp.
+
+  -a, --archive
+          Upload the backup using the archiver configured on the server
+
+  -h, --help
+          Print help (see a summary with '-h')
+"#,
+            ),
+        ))
+        .await;
+}
@@ -80,7 +80,8 @@ impl IggyCmdTestCase for TestStatsCmd {
                     .stdout(contains("Segments Count           | 5"))
                     .stdout(contains("Message Count            | 0"))
                     .stdout(contains("Clients Count            | 2")) // 2 clients are connected during test
-                    .stdout(contains("Consumer Groups Count    | 0"));
+                    .stdout(contains("Consumer Groups Count    | 0"))
+                    .stdout(contains("Iggy Server Version"));
             }
             TestStatsCmdOutput::Set(GetStatsOutput::List) => {
                 command_state
@@ -91,7 +92,8 @@ impl IggyCmdTestCase for TestStatsCmd {
                     .stdout(contains("Segments Count|5"))
                     .stdout(contains("Message Count|0"))
                     .stdout(contains("Clients Count|2")) // 2 clients are connected during test
-                    .stdout(contains("Consumer Groups Count|0"));
+                    .stdout(contains("Consumer Groups Count|0"))
+                    .stdout(contains("Iggy Server Version|"));
             }
             TestStatsCmdOutput::Set(GetStatsOutput::Json) => {
                 command_state
@@ -102,7 +104,8 @@ impl IggyCmdTestCase for TestStatsCmd {
                     .stdout(contains(r#""segments_count": 5"#))
                     .stdout(contains(r#""messages_count": 0"#))
                     .stdout(contains(r#""clients_count": 2"#)) // 2 clients are connected during test
-                    .stdout(contains(r#""consumer_groups_count": 0"#));
+                    .stdout(contains(r#""consumer_groups_count": 0"#))
+                    .stdout(contains(r#""iggy_server_version": ""#));
             }
             TestStatsCmdOutput::Set(GetStatsOutput::Toml) => {
                 command_state
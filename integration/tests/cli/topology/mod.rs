@@ -0,0 +1,2 @@
+mod test_apply_command;
+mod test_export_topology_command;
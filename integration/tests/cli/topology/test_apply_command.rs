@@ -0,0 +1,383 @@
+use crate::cli::common::{
+    IggyCmdCommand, IggyCmdTest, IggyCmdTestCase, TestHelpCmd, CLAP_INDENT, USAGE_PREFIX,
+};
+use assert_cmd::assert::Assert;
+use async_trait::async_trait;
+use iggy::client::Client;
+use iggy::identifier::Identifier;
+use iggy::models::permissions::{Permissions, StreamPermissions};
+use iggy::models::user_status::UserStatus;
+use iggy::utils::duration::IggyDuration;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use predicates::str::diff;
+use serial_test::parallel;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use tempfile::TempDir;
+
+const MANIFEST: &str = r#"
+[[streams]]
+name = "orders"
+
+[[streams.topics]]
+name = "payments"
+partitions_count = 3
+message_expiry = "1h"
+max_topic_size = "unlimited"
+consumer_groups = [{ name = "billing" }]
+
+[[streams]]
+name = "events"
+id = 5
+
+[[streams.topics]]
+name = "clicks"
+partitions_count = 2
+
+[[users]]
+username = "alice"
+password = "secret"
+
+[users.permissions.streams.orders]
+send_messages = true
+"#;
+
+async fn create_orders_stream(client: &dyn Client, partitions_count: u32, expiry: IggyExpiry) {
+    let stream = client.create_stream("orders", None).await;
+    assert!(stream.is_ok());
+
+    let topic = client
+        .create_topic(
+            &Identifier::named("orders").unwrap(),
+            "payments",
+            partitions_count,
+            Default::default(),
+            None,
+            None,
+            expiry,
+            MaxTopicSize::Unlimited,
+        )
+        .await;
+    assert!(topic.is_ok());
+}
+
+struct TestApplyCmd {
+    file: PathBuf,
+}
+
+impl TestApplyCmd {
+    fn new(dir: &TempDir) -> Self {
+        let file = dir.path().join("apply.toml");
+        std::fs::write(&file, MANIFEST).unwrap();
+        Self { file }
+    }
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestApplyCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        create_orders_stream(client, 1, IggyExpiry::NeverExpire).await;
+        let group = client
+            .create_consumer_group(
+                &Identifier::named("orders").unwrap(),
+                &Identifier::named("payments").unwrap(),
+                "audit",
+                None,
+            )
+            .await;
+        assert!(group.is_ok());
+
+        let stream = client.create_stream("legacy", None).await;
+        assert!(stream.is_ok());
+
+        let user = client
+            .create_user("bob", "secret", UserStatus::Active, None)
+            .await;
+        assert!(user.is_ok());
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("apply")
+            .arg("--file")
+            .arg(self.file.to_str().unwrap())
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        let file = self.file.display();
+        let message = format!(
+            "Executing apply topology from file: {file}\n\
+            ~ update topic: orders/payments (message expiry: 1h, max topic size: unlimited, compression algorithm: none, replication factor: 1)\n\
+            + create 2 partitions in topic: orders/payments\n\
+            + create consumer group: orders/payments/billing\n\
+            + create stream: events\n\
+            + create topic: events/clicks (partitions: 2, message expiry: server_default, max topic size: server_default, compression algorithm: none)\n\
+            - delete consumer group: orders/payments/audit\n\
+            - delete stream: legacy\n\
+            + create user: alice (status: active)\n\
+            - delete user: bob\n\
+            Applied 9 changes from file: {file}\n"
+        );
+
+        command_state.success().stdout(diff(message));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let orders = Identifier::named("orders").unwrap();
+        let payments = Identifier::named("payments").unwrap();
+
+        let streams = client.get_streams().await.unwrap();
+        let mut stream_names = streams
+            .iter()
+            .map(|stream| stream.name.as_str())
+            .collect::<Vec<_>>();
+        stream_names.sort_unstable();
+        assert_eq!(stream_names, vec!["events", "orders"]);
+        let events = streams
+            .iter()
+            .find(|stream| stream.name == "events")
+            .unwrap();
+        assert_eq!(events.id, 5);
+
+        let topic = client.get_topic(&orders, &payments).await.unwrap();
+        assert_eq!(topic.partitions_count, 3);
+        assert_eq!(
+            topic.message_expiry,
+            IggyExpiry::ExpireDuration(IggyDuration::from_str("1h").unwrap())
+        );
+
+        let groups = client
+            .get_consumer_groups(&orders, &payments)
+            .await
+            .unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "billing");
+
+        let topic = client
+            .get_topic(
+                &Identifier::named("events").unwrap(),
+                &Identifier::named("clicks").unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(topic.partitions_count, 2);
+
+        let orders_id = streams
+            .iter()
+            .find(|stream| stream.name == "orders")
+            .unwrap()
+            .id;
+        let alice = client
+            .get_user(&Identifier::named("alice").unwrap())
+            .await
+            .unwrap();
+        assert_eq!(
+            alice.permissions,
+            Some(Permissions {
+                global: Default::default(),
+                streams: Some(HashMap::from([(
+                    orders_id,
+                    StreamPermissions {
+                        send_messages: true,
+                        ..Default::default()
+                    }
+                )])),
+            })
+        );
+
+        let mut usernames = client
+            .get_users()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|user| user.username)
+            .collect::<Vec<_>>();
+        usernames.sort_unstable();
+        assert_eq!(usernames, vec!["alice", "iggy"]);
+
+        for stream in streams {
+            let deleted = client
+                .delete_stream(&Identifier::numeric(stream.id).unwrap())
+                .await;
+            assert!(deleted.is_ok());
+        }
+        let deleted = client
+            .delete_user(&Identifier::named("alice").unwrap())
+            .await;
+        assert!(deleted.is_ok());
+    }
+}
+
+struct TestApplyDryRunCmd {
+    file: PathBuf,
+}
+
+impl TestApplyDryRunCmd {
+    fn new(dir: &TempDir) -> Self {
+        let file = dir.path().join("dry_run.yaml");
+        std::fs::write(
+            &file,
+            "streams:\n  - name: orders\n    topics:\n      - name: payments\n        partitions_count: 1\n",
+        )
+        .unwrap();
+        Self { file }
+    }
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestApplyDryRunCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        create_orders_stream(client, 2, IggyExpiry::NeverExpire).await;
+        let stream = client.create_stream("legacy", None).await;
+        assert!(stream.is_ok());
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("apply")
+            .arg("--dry-run")
+            .arg("-f")
+            .arg(self.file.to_str().unwrap())
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        let file = self.file.display();
+        let message = format!(
+            "Executing apply topology from file: {file} (dry run)\n\
+            - delete 1 partitions in topic: orders/payments\n\
+            - delete stream: legacy\n\
+            Planned 2 changes from file: {file}, nothing was applied (dry run)\n"
+        );
+
+        command_state.success().stdout(diff(message));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let topic = client
+            .get_topic(
+                &Identifier::named("orders").unwrap(),
+                &Identifier::named("payments").unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(topic.partitions_count, 2);
+
+        for stream in ["orders", "legacy"] {
+            let deleted = client
+                .delete_stream(&Identifier::named(stream).unwrap())
+                .await;
+            assert!(deleted.is_ok());
+        }
+    }
+}
+
+struct TestApplyUpToDateCmd {
+    file: PathBuf,
+}
+
+impl TestApplyUpToDateCmd {
+    fn new(dir: &TempDir) -> Self {
+        let file = dir.path().join("up_to_date.toml");
+        std::fs::write(
+            &file,
+            "[[streams]]\nname = \"orders\"\n\n[[streams.topics]]\nname = \"payments\"\npartitions_count = 1\nmessage_expiry = \"2h\"\n",
+        )
+        .unwrap();
+        Self { file }
+    }
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestApplyUpToDateCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        create_orders_stream(
+            client,
+            1,
+            IggyExpiry::ExpireDuration(IggyDuration::from_str("2h").unwrap()),
+        )
+        .await;
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        IggyCmdCommand::new()
+            .arg("apply")
+            .arg("-f")
+            .arg(self.file.to_str().unwrap())
+            .opts(vec!["--output", "json"])
+            .with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        command_state
+            .success()
+            .stdout(diff("{\n  \"dry_run\": false,\n  \"changes\": []\n}\n"));
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let deleted = client
+            .delete_stream(&Identifier::named("orders").unwrap())
+            .await;
+        assert!(deleted.is_ok());
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_be_successful() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test.execute_test(TestApplyCmd::new(&dir)).await;
+    iggy_cmd_test
+        .execute_test(TestApplyDryRunCmd::new(&dir))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestApplyUpToDateCmd::new(&dir))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["apply", "--help"],
+            format!(
+                r#"apply topology from manifest file
+
+Command compares streams, topics, partitions, consumer groups, users and their
+permissions declared in the manifest file with the ones existing on Iggy server
+and creates, updates or deletes them, so the server matches the manifest.
+Sections omitted in the manifest are not managed, while the empty ones mean
+that all the existing entities of the section are deleted.
+
+Examples:
+ iggy apply -f topology.toml
+ iggy apply -f topology.yaml --dry-run
+
+{USAGE_PREFIX} apply [OPTIONS] --file <FILE>
+
+Options:
+  -f, --file <FILE>
+          Path to the topology manifest file
+{CLAP_INDENT}
+          Files with .yaml or .yml extension are parsed as YAML,
+          all the other ones as TOML.
+
+      --dry-run
+          Print the plan of the changes without applying them
+
+  -h, --help
+          Print help (see a summary with '-h')
+"#,
+            ),
+        ))
+        .await;
+}
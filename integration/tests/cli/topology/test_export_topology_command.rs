@@ -0,0 +1,276 @@
+use crate::cli::common::{
+    IggyCmdCommand, IggyCmdTest, IggyCmdTestCase, TestHelpCmd, CLAP_INDENT, USAGE_PREFIX,
+};
+use assert_cmd::assert::Assert;
+use async_trait::async_trait;
+use iggy::cli::topology::manifest::{
+    ConsumerGroupManifest, PermissionsManifest, StreamManifest, StreamPermissionsManifest,
+    TopicManifest, TopicPermissionsManifest, TopologyFormat, TopologyManifest,
+};
+use iggy::client::Client;
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::identifier::Identifier;
+use iggy::models::permissions::{Permissions, StreamPermissions, TopicPermissions};
+use iggy::models::user_status::UserStatus;
+use iggy::utils::duration::IggyDuration;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use predicates::str::{diff, starts_with};
+use serial_test::parallel;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::str::FromStr;
+use tempfile::TempDir;
+
+struct TestExportTopologyCmd {
+    file: Option<PathBuf>,
+    format: Option<TopologyFormat>,
+}
+
+impl TestExportTopologyCmd {
+    fn new(dir: &TempDir, file_name: Option<&str>, format: Option<TopologyFormat>) -> Self {
+        Self {
+            file: file_name.map(|file_name| dir.path().join(file_name)),
+            format,
+        }
+    }
+
+    fn expected_format(&self) -> TopologyFormat {
+        self.format.unwrap_or_else(|| {
+            self.file
+                .as_deref()
+                .map(TopologyFormat::from_path)
+                .unwrap_or_default()
+        })
+    }
+
+    fn verify_manifest(&self, content: &str) {
+        let manifest = TopologyManifest::parse(content, self.expected_format()).unwrap();
+
+        assert_eq!(
+            manifest.streams,
+            Some(vec![StreamManifest {
+                name: "orders".to_string(),
+                id: Some(3),
+                topics: Some(vec![TopicManifest {
+                    name: "payments".to_string(),
+                    id: Some(2),
+                    partitions_count: 2,
+                    message_expiry: Some(IggyExpiry::ExpireDuration(
+                        IggyDuration::from_str("1h 30m").unwrap()
+                    )),
+                    max_topic_size: Some(MaxTopicSize::Unlimited),
+                    compression_algorithm: Some(CompressionAlgorithm::Gzip),
+                    replication_factor: Some(1),
+                    consumer_groups: Some(vec![ConsumerGroupManifest {
+                        name: "billing".to_string(),
+                        id: Some(4),
+                    }]),
+                }]),
+            }])
+        );
+
+        let users = manifest.users.unwrap();
+        let usernames = users
+            .iter()
+            .map(|user| user.username.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(usernames, vec!["iggy", "alice"]);
+        let alice = &users[1];
+        assert_eq!(alice.password, None);
+        assert_eq!(alice.status, Some(UserStatus::Inactive));
+        assert_eq!(
+            alice.permissions,
+            Some(PermissionsManifest {
+                global: Default::default(),
+                streams: BTreeMap::from([(
+                    "orders".to_string(),
+                    StreamPermissionsManifest {
+                        read_stream: true,
+                        topics: BTreeMap::from([(
+                            "payments".to_string(),
+                            TopicPermissionsManifest {
+                                poll_messages: true,
+                                ..Default::default()
+                            },
+                        )]),
+                        ..Default::default()
+                    },
+                )]),
+            })
+        );
+    }
+}
+
+#[async_trait]
+impl IggyCmdTestCase for TestExportTopologyCmd {
+    async fn prepare_server_state(&mut self, client: &dyn Client) {
+        let stream = client.create_stream("orders", Some(3)).await;
+        assert!(stream.is_ok());
+
+        let topic = client
+            .create_topic(
+                &Identifier::numeric(3).unwrap(),
+                "payments",
+                2,
+                CompressionAlgorithm::Gzip,
+                None,
+                Some(2),
+                IggyExpiry::ExpireDuration(IggyDuration::from_str("1h 30m").unwrap()),
+                MaxTopicSize::Unlimited,
+            )
+            .await;
+        assert!(topic.is_ok());
+
+        let group = client
+            .create_consumer_group(
+                &Identifier::numeric(3).unwrap(),
+                &Identifier::numeric(2).unwrap(),
+                "billing",
+                Some(4),
+            )
+            .await;
+        assert!(group.is_ok());
+
+        let permissions = Permissions {
+            global: Default::default(),
+            streams: Some(HashMap::from([(
+                3,
+                StreamPermissions {
+                    read_stream: true,
+                    topics: Some(HashMap::from([(
+                        2,
+                        TopicPermissions {
+                            poll_messages: true,
+                            ..Default::default()
+                        },
+                    )])),
+                    ..Default::default()
+                },
+            )])),
+        };
+        let user = client
+            .create_user("alice", "secret", UserStatus::Inactive, Some(permissions))
+            .await;
+        assert!(user.is_ok());
+    }
+
+    fn get_command(&self) -> IggyCmdCommand {
+        let command = IggyCmdCommand::new().arg("export-topology");
+        let command = match &self.file {
+            Some(file) => command.arg("--file").arg(file.to_str().unwrap()),
+            None => command,
+        };
+        let command = match self.format {
+            Some(format) => command.arg("--format").arg(format.to_string()),
+            None => command,
+        };
+
+        command.with_env_credentials()
+    }
+
+    fn verify_command(&self, command_state: Assert) {
+        let format = self.expected_format();
+        match &self.file {
+            Some(file) => {
+                let file = file.display();
+                command_state.success().stdout(diff(format!(
+                    "Executing export topology to file: {file} in {format} format\n\
+                    Exported topology with 1 streams and 2 users to file: {file} in {format} format\n"
+                )));
+                self.verify_manifest(
+                    &std::fs::read_to_string(self.file.as_ref().unwrap()).unwrap(),
+                );
+            }
+            None => {
+                let header = format!("Executing export topology in {format} format\n");
+                let command_state = command_state.success().stdout(starts_with(header.clone()));
+                let stdout = String::from_utf8(command_state.get_output().stdout.clone()).unwrap();
+                self.verify_manifest(stdout.strip_prefix(&header).unwrap());
+            }
+        }
+    }
+
+    async fn verify_server_state(&self, client: &dyn Client) {
+        let stream = client.delete_stream(&Identifier::numeric(3).unwrap()).await;
+        assert!(stream.is_ok());
+
+        let user = client
+            .delete_user(&Identifier::named("alice").unwrap())
+            .await;
+        assert!(user.is_ok());
+    }
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_be_successful() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut iggy_cmd_test = IggyCmdTest::default();
+
+    iggy_cmd_test.setup().await;
+    iggy_cmd_test
+        .execute_test(TestExportTopologyCmd::new(
+            &dir,
+            Some("topology.toml"),
+            None,
+        ))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestExportTopologyCmd::new(&dir, Some("topology.yml"), None))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestExportTopologyCmd::new(
+            &dir,
+            Some("topology"),
+            Some(TopologyFormat::Yaml),
+        ))
+        .await;
+    iggy_cmd_test
+        .execute_test(TestExportTopologyCmd::new(&dir, None, None))
+        .await;
+}
+
+#[tokio::test]
+#[parallel]
+pub async fn should_help_match() {
+    let mut iggy_cmd_test = IggyCmdTest::help_message();
+
+    iggy_cmd_test
+        .execute_test_for_help_command(TestHelpCmd::new(
+            vec!["export-topology", "--help"],
+            format!(
+                r#"export topology to manifest file
+
+Command exports streams, topics, consumer groups, users and their permissions
+existing on Iggy server in the format accepted by the apply command.
+Passwords of the users are not exported.
+
+Examples:
+ iggy export-topology
+ iggy export-topology -f topology.yaml
+ iggy export-topology --format yaml
+
+{USAGE_PREFIX} export-topology [OPTIONS]
+
+Options:
+  -f, --file <FILE>
+          Path to the file to which topology will be exported
+{CLAP_INDENT}
+          If not specified, topology is printed on standard output.
+
+      --format <FORMAT>
+          Format of the topology manifest
+{CLAP_INDENT}
+          If not specified, format is detected based on the file extension
+          (YAML for .yaml and .yml, TOML otherwise).
+{CLAP_INDENT}
+          [possible values: toml, yaml]
+
+  -h, --help
+          Print help (see a summary with '-h')
+"#,
+            ),
+        ))
+        .await;
+}
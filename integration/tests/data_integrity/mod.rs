@@ -1 +1,2 @@
+mod verify_after_backup_restore;
 mod verify_after_server_restart;
@@ -0,0 +1,167 @@
+use bytes::Bytes;
+use iggy::client::{ConsumerOffsetClient, MessageClient, StreamClient, SystemClient, TopicClient};
+use iggy::clients::client::{IggyClient, IggyClientBackgroundConfig};
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::consumer::Consumer;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::{
+    tcp_client::TcpClientFactory,
+    test_server::{login_root, ClientFactory, IpAddrKind, TestServer, SYSTEM_PATH_ENV_VAR},
+};
+use serial_test::parallel;
+use std::collections::HashMap;
+
+const STREAM_ID: u32 = 1;
+const TOPIC_ID: u32 = 1;
+const PARTITION_ID: u32 = 1;
+const MESSAGES_COUNT: u32 = 100;
+const BACKUP_NAME: &str = "test";
+
+#[tokio::test]
+#[parallel]
+async fn should_restore_data_from_backup_and_skip_data_created_after_it() {
+    // 1. Start server and fill it with data
+    let mut test_server = TestServer::new(None, false, None, IpAddrKind::V4);
+    test_server.start();
+    let local_data_path = test_server.get_local_data_path().to_owned();
+    let client = create_client(test_server.get_raw_tcp_addr().unwrap()).await;
+    client
+        .create_stream("backup", Some(STREAM_ID))
+        .await
+        .unwrap();
+    client
+        .create_topic(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            "backup",
+            1,
+            CompressionAlgorithm::default(),
+            None,
+            Some(TOPIC_ID),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await
+        .unwrap();
+    send_messages(&client, 0).await;
+    let consumer = Consumer::new(Identifier::numeric(1).unwrap());
+    client
+        .store_consumer_offset(
+            &consumer,
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            10,
+        )
+        .await
+        .unwrap();
+
+    // 2. Create backup
+    let backup = client
+        .create_backup(Some(BACKUP_NAME), false)
+        .await
+        .unwrap();
+    assert_eq!(backup.name, BACKUP_NAME);
+    assert_eq!(backup.partitions_count, 1);
+    assert_eq!(backup.messages_count, MESSAGES_COUNT as u64);
+    assert!(client
+        .create_backup(Some(BACKUP_NAME), false)
+        .await
+        .is_err());
+
+    // 3. Modify the data after the backup
+    send_messages(&client, MESSAGES_COUNT).await;
+    client.create_stream("after-backup", Some(2)).await.unwrap();
+
+    // 4. Stop server, remove the state and the streams, and restart it from the backup
+    test_server.stop();
+    drop(test_server);
+    for path in ["state", "streams", "runtime/current_config.toml"] {
+        let path = format!("{local_data_path}/{path}");
+        if std::fs::metadata(&path).unwrap().is_dir() {
+            std::fs::remove_dir_all(path).unwrap();
+        } else {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    let extra_envs = HashMap::from([(SYSTEM_PATH_ENV_VAR.to_owned(), local_data_path.clone())]);
+    let mut test_server = TestServer::new(Some(extra_envs), false, None, IpAddrKind::V4);
+    test_server.start_with_args(&["--restore-backup", "latest"]);
+    let client = create_client(test_server.get_raw_tcp_addr().unwrap()).await;
+
+    // 5. Verify the restored data
+    let streams = client.get_streams().await.unwrap();
+    assert_eq!(streams.len(), 1);
+    assert_eq!(streams[0].id, STREAM_ID);
+    let polled_messages = client
+        .poll_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+            &consumer,
+            &PollingStrategy::offset(0),
+            MESSAGES_COUNT * 2,
+            false,
+        )
+        .await
+        .unwrap();
+    assert_eq!(polled_messages.messages.len() as u32, MESSAGES_COUNT);
+    assert_eq!(
+        polled_messages.messages.last().unwrap().offset,
+        (MESSAGES_COUNT - 1) as u64
+    );
+    let offset = client
+        .get_consumer_offset(
+            &consumer,
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            Some(PARTITION_ID),
+        )
+        .await
+        .unwrap();
+    assert_eq!(offset.stored_offset, 10);
+
+    // 6. Manual cleanup
+    test_server.stop();
+    std::fs::remove_dir_all(local_data_path).unwrap();
+}
+
+async fn create_client(server_addr: String) -> IggyClient {
+    let client = TcpClientFactory { server_addr }.create_client().await;
+    let client = IggyClient::create(
+        client,
+        IggyClientBackgroundConfig::default(),
+        None,
+        None,
+        None,
+    );
+    login_root(&client).await;
+    client
+}
+
+async fn send_messages(client: &IggyClient, start_id: u32) {
+    let mut messages = (start_id..start_id + MESSAGES_COUNT)
+        .map(|id| {
+            let payload = Bytes::from(format!("message {id}"));
+            Message {
+                id: id as u128 + 1,
+                length: payload.len() as u32,
+                payload,
+                headers: None,
+            }
+        })
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &Identifier::numeric(STREAM_ID).unwrap(),
+            &Identifier::numeric(TOPIC_ID).unwrap(),
+            &Partitioning::partition_id(PARTITION_ID),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+}
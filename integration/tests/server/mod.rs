@@ -2,3 +2,5 @@ mod http_server;
 mod quic_server;
 mod scenarios;
 mod tcp_server;
+#[cfg(feature = "otel")]
+mod telemetry;
@@ -4,8 +4,22 @@ use crate::server::scenarios::{
     message_headers_scenario, message_size_scenario, stream_size_validation_scenario,
     system_scenario, user_scenario,
 };
+use bytes::Bytes;
+use iggy::binary::BinaryTransport;
+use iggy::client::{Client, MessageClient, StreamClient, TopicClient, UserClient};
+use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::{PollMessages, PollingStrategy};
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::tcp::client::TcpClient;
+use iggy::tcp::config::TcpClientConfig;
+use iggy::users::defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USERNAME};
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
 use integration::{tcp_client::TcpClientFactory, test_server::TestServer};
 use serial_test::parallel;
+use std::str::FromStr;
+use std::sync::Arc;
 
 #[tokio::test]
 #[parallel]
@@ -96,3 +110,82 @@ async fn message_size_scenario_should_be_valid() {
     let client_factory = TcpClientFactory { server_addr };
     message_size_scenario::run(&client_factory).await;
 }
+
+#[tokio::test]
+#[parallel]
+async fn poll_messages_command_should_return_messages_encoded_one_by_one() {
+    let mut test_server = TestServer::default();
+    test_server.start();
+    let server_addr = test_server.get_raw_tcp_addr().unwrap();
+    let client = TcpClient::create(Arc::new(TcpClientConfig {
+        server_address: server_addr,
+        ..TcpClientConfig::default()
+    }))
+    .unwrap();
+    client.connect().await.unwrap();
+    client
+        .login_user(DEFAULT_ROOT_USERNAME, DEFAULT_ROOT_PASSWORD)
+        .await
+        .unwrap();
+    let stream_id = Identifier::numeric(1).unwrap();
+    let topic_id = Identifier::numeric(1).unwrap();
+    client.create_stream("stream", Some(1)).await.unwrap();
+    client
+        .create_topic(
+            &stream_id,
+            "topic",
+            1,
+            CompressionAlgorithm::None,
+            None,
+            Some(1),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await
+        .unwrap();
+    let mut messages = (0..3)
+        .map(|offset| Message::from_str(&format!("message {offset}")).unwrap())
+        .collect::<Vec<_>>();
+    client
+        .send_messages(
+            &stream_id,
+            &topic_id,
+            &Partitioning::partition_id(1),
+            &mut messages,
+        )
+        .await
+        .unwrap();
+
+    // The original poll messages command keeps the response with the messages encoded one by one,
+    // the batches are returned only for the poll message batches command.
+    let response = client
+        .send_with_response(&PollMessages {
+            stream_id: stream_id.clone(),
+            topic_id: topic_id.clone(),
+            partition_id: Some(1),
+            strategy: PollingStrategy::offset(1),
+            count: 10,
+            ..PollMessages::default()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(u32::from_le_bytes(response[..4].try_into().unwrap()), 1);
+    assert_eq!(u64::from_le_bytes(response[4..12].try_into().unwrap()), 2);
+    assert_eq!(u32::from_le_bytes(response[12..16].try_into().unwrap()), 2);
+    let mut position = 16;
+    for offset in 1..3 {
+        let message_offset =
+            u64::from_le_bytes(response[position..position + 8].try_into().unwrap());
+        let headers_length =
+            u32::from_le_bytes(response[position + 37..position + 41].try_into().unwrap()) as usize;
+        position += 41 + headers_length;
+        let payload_length =
+            u32::from_le_bytes(response[position..position + 4].try_into().unwrap()) as usize;
+        let payload = response.slice(position + 4..position + 4 + payload_length);
+        position += 4 + payload_length;
+        assert_eq!(message_offset, offset);
+        assert_eq!(payload, Bytes::from(format!("message {offset}")));
+    }
+    assert_eq!(position, response.len());
+}
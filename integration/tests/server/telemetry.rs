@@ -0,0 +1,194 @@
+use bytes::Bytes;
+use iggy::client::{MessageClient, StreamClient, TopicClient};
+use iggy::clients::client::{IggyClient, IggyClientBackgroundConfig};
+use iggy::consumer::Consumer;
+use iggy::identifier::Identifier;
+use iggy::messages::poll_messages::PollingStrategy;
+use iggy::messages::send_messages::{Message, Partitioning};
+use iggy::messages::trace_context::TraceParent;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::topic_size::MaxTopicSize;
+use integration::tcp_client::TcpClientFactory;
+use integration::test_server::{login_root, ClientFactory, IpAddrKind, TestServer};
+use serial_test::parallel;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+const TRACES_PATH: &str = "/v1/traces";
+
+/// The stand-in for the OTLP/HTTP collector, which records the bodies of the exported traces.
+struct TestCollector {
+    address: SocketAddr,
+    traces: Arc<Mutex<Vec<Vec<u8>>>>,
+}
+
+impl TestCollector {
+    async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let traces = Arc::new(Mutex::new(Vec::new()));
+        let collected_traces = traces.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let traces = collected_traces.clone();
+                tokio::spawn(async move {
+                    let mut stream = BufReader::new(stream);
+                    while let Some((path, body)) = read_request(&mut stream).await {
+                        if path == TRACES_PATH {
+                            traces.lock().unwrap().push(body);
+                        }
+                        let response = "HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n";
+                        if stream.write_all(response.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        Self { address, traces }
+    }
+
+    fn contains(&self, value: &[u8]) -> bool {
+        self.traces
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|body| body.windows(value.len()).any(|window| window == value))
+    }
+}
+
+async fn read_request<R>(stream: &mut R) -> Option<(String, Vec<u8>)>
+where
+    R: AsyncBufReadExt + Unpin,
+{
+    let mut request_line = String::new();
+    if stream.read_line(&mut request_line).await.ok()? == 0 {
+        return None;
+    }
+
+    let path = request_line.split_whitespace().nth(1)?.to_string();
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        stream.read_line(&mut header).await.ok()?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().ok()?;
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    stream.read_exact(&mut body).await.ok()?;
+    Some((path, body))
+}
+
+// The collector must keep responding while the test server is being stopped, as the remaining spans are exported during shutdown.
+#[tokio::test(flavor = "multi_thread")]
+#[parallel]
+async fn spans_should_be_exported_with_trace_parent_propagated_from_message_headers() {
+    let collector = TestCollector::start().await;
+    let mut test_server = TestServer::new(
+        Some(HashMap::from([
+            ("IGGY_TELEMETRY_ENABLED".to_string(), "true".to_string()),
+            (
+                "IGGY_TELEMETRY_ENDPOINT".to_string(),
+                format!("http://{}", collector.address),
+            ),
+            (
+                "IGGY_TELEMETRY_EXPORT_INTERVAL".to_string(),
+                "1 s".to_string(),
+            ),
+        ])),
+        true,
+        None,
+        IpAddrKind::V4,
+    );
+    test_server.start();
+    let client_factory = TcpClientFactory {
+        server_addr: test_server.get_raw_tcp_addr().unwrap(),
+    };
+    let client = IggyClient::create(
+        client_factory.create_client().await,
+        IggyClientBackgroundConfig::default(),
+        None,
+        None,
+        None,
+    );
+    login_root(&client).await;
+
+    let stream_id = Identifier::numeric(1).unwrap();
+    let topic_id = Identifier::numeric(1).unwrap();
+    client.create_stream("stream", Some(1)).await.unwrap();
+    client
+        .create_topic(
+            &stream_id,
+            "topic",
+            1,
+            Default::default(),
+            None,
+            Some(1),
+            IggyExpiry::NeverExpire,
+            MaxTopicSize::ServerDefault,
+        )
+        .await
+        .unwrap();
+
+    let trace_parent = TraceParent::generate();
+    let mut message = Message::new(None, Bytes::from("message"), None);
+    message.set_trace_parent(&trace_parent);
+    client
+        .send_messages(
+            &stream_id,
+            &topic_id,
+            &Partitioning::partition_id(1),
+            &mut [message],
+        )
+        .await
+        .unwrap();
+
+    let polled_messages = client
+        .poll_messages(
+            &stream_id,
+            &topic_id,
+            Some(1),
+            &Consumer::default(),
+            &PollingStrategy::offset(0),
+            1,
+            false,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        polled_messages.messages[0].get_trace_parent(),
+        Some(trace_parent)
+    );
+
+    // The span of the sent messages belongs to the trace of the producer and is a child of its span.
+    let mut exported = false;
+    for _ in 0..100 {
+        if collector.contains(&trace_parent.trace_id.to_be_bytes())
+            && collector.contains(&trace_parent.parent_id.to_be_bytes())
+        {
+            exported = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(
+        exported,
+        "Span with the propagated trace parent was not exported."
+    );
+
+    client.delete_stream(&stream_id).await.unwrap();
+}
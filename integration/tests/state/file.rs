@@ -1,11 +1,20 @@
 use crate::state::StateSetup;
 use bytes::Bytes;
+use futures::future::join_all;
 use iggy::bytes_serializable::BytesSerializable;
 use iggy::streams::create_stream::CreateStream;
 use iggy::users::create_user::CreateUser;
+use iggy::utils::crypto::Aes256GcmEncryptor;
 use server::state::command::EntryCommand;
 use server::state::entry::StateEntry;
+use server::state::file::FileState;
+use server::state::snapshot::StateSnapshot;
 use server::state::State;
+use server::streaming::persistence::persister::FilePersister;
+use server::streaming::utils::key_ring::EncryptionKeyRing;
+use server::versioning::SemanticVersion;
+use std::str::FromStr;
+use std::sync::Arc;
 
 #[tokio::test]
 async fn should_be_empty_given_initialized_state() {
@@ -121,6 +130,244 @@ async fn should_apply_multiple_entries() {
     );
 }
 
+#[tokio::test]
+async fn should_store_concurrently_applied_entries_in_order_of_their_indexes() {
+    let setup = StateSetup::init().await;
+    let state = setup.state();
+    state.init().await.unwrap();
+
+    let user_id = 1;
+    join_all((1..=10).map(|stream_id| {
+        state.apply(
+            user_id,
+            EntryCommand::CreateStream(CreateStream {
+                stream_id: Some(stream_id),
+                name: format!("test-{stream_id}"),
+            }),
+        )
+    }))
+    .await
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+
+    let entries = state.load_entries().await.unwrap();
+    assert_eq!(entries.len(), 10);
+    assert_eq!(state.current_index(), 9);
+    for (index, entry) in entries.iter().enumerate() {
+        assert_eq!(entry.index, index as u64);
+    }
+}
+
+#[tokio::test]
+async fn should_reencrypt_entries_with_active_key() {
+    let setup = StateSetup::init_with_encryptor().await;
+    let state = setup.state();
+    state.init().await.unwrap();
+
+    let user_id = 1;
+    let create_user = EntryCommand::CreateUser(CreateUser {
+        username: "test".to_string(),
+        password: "secret".to_string(),
+        status: Default::default(),
+        permissions: None,
+    });
+    let create_stream = EntryCommand::CreateStream(CreateStream {
+        stream_id: Some(1),
+        name: "test".to_string(),
+    });
+    let create_user_bytes = create_user.to_bytes();
+    let create_stream_bytes = create_stream.to_bytes();
+    state.apply(user_id, create_user).await.unwrap();
+    state.apply(user_id, create_stream).await.unwrap();
+
+    let mut key_ring = EncryptionKeyRing::new(1, Aes256GcmEncryptor::new(&[1; 32]).unwrap());
+    key_ring
+        .rotate(2, Aes256GcmEncryptor::new(&[2; 32]).unwrap())
+        .unwrap();
+    assert_eq!(state.reencrypt(&key_ring).await.unwrap(), 2);
+    assert_eq!(state.reencrypt(&key_ring).await.unwrap(), 0);
+
+    let active_key_ring = EncryptionKeyRing::new(2, Aes256GcmEncryptor::new(&[2; 32]).unwrap());
+    let state = FileState::new(
+        &setup.log_path(),
+        &SemanticVersion::from_str("1.2.3").unwrap(),
+        Arc::new(FilePersister {}),
+        Some(Arc::new(active_key_ring)),
+    );
+    let mut entries = state.load_entries().await.unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_entry(
+        entries.remove(0),
+        0,
+        setup.version(),
+        user_id,
+        create_user_bytes,
+    );
+    assert_entry(
+        entries.remove(0),
+        1,
+        setup.version(),
+        user_id,
+        create_stream_bytes,
+    );
+}
+
+#[tokio::test]
+async fn should_snapshot_state_and_truncate_log() {
+    let setup = StateSetup::init().await;
+    let state = setup.state();
+    state.init().await.unwrap();
+
+    let user_id = 1;
+    for command in [
+        EntryCommand::CreateUser(CreateUser {
+            username: "test".to_string(),
+            password: "secret".to_string(),
+            status: Default::default(),
+            permissions: None,
+        }),
+        EntryCommand::CreateStream(CreateStream {
+            stream_id: Some(1),
+            name: "test".to_string(),
+        }),
+    ] {
+        state.apply(user_id, command).await.unwrap();
+    }
+
+    assert_eq!(state.snapshot(10).await.unwrap(), None);
+    assert_eq!(state.snapshot(2).await.unwrap(), Some(1));
+    assert_eq!(state.snapshot(0).await.unwrap(), None);
+    assert!(state.load_entries().await.unwrap().is_empty());
+    assert_eq!(state.entries_count(), 0);
+    assert_eq!(state.current_index(), 1);
+
+    let snapshot = state.load_snapshot().await.unwrap().unwrap();
+    assert_eq!(snapshot.index, 1);
+    assert_eq!(snapshot.version, setup.version());
+    assert_eq!(snapshot.state.users.len(), 1);
+    assert_eq!(snapshot.state.streams.len(), 1);
+
+    let create_stream = EntryCommand::CreateStream(CreateStream {
+        stream_id: Some(2),
+        name: "test2".to_string(),
+    });
+    let create_stream_bytes = create_stream.to_bytes();
+    state.apply(user_id, create_stream).await.unwrap();
+    assert_eq!(state.current_index(), 2);
+
+    let state = FileState::new(
+        &setup.log_path(),
+        &SemanticVersion::from_str("1.2.3").unwrap(),
+        Arc::new(FilePersister {}),
+        None,
+    );
+    let mut entries = state.init().await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(state.current_index(), 2);
+    assert_entry(
+        entries.remove(0),
+        2,
+        setup.version(),
+        user_id,
+        create_stream_bytes,
+    );
+}
+
+#[tokio::test]
+async fn should_export_snapshot_without_compacting_log() {
+    let setup = StateSetup::init().await;
+    let state = setup.state();
+    state.init().await.unwrap();
+    let export_path = setup.log_path().replace("log", "exported");
+    assert_eq!(state.export_snapshot(&export_path).await.unwrap(), None);
+
+    let user_id = 1;
+    for stream_id in 1..=3 {
+        let command = EntryCommand::CreateStream(CreateStream {
+            stream_id: Some(stream_id),
+            name: format!("test{stream_id}"),
+        });
+        state.apply(user_id, command).await.unwrap();
+        if stream_id == 2 {
+            assert_eq!(state.snapshot(0).await.unwrap(), Some(1));
+        }
+    }
+
+    assert_eq!(state.export_snapshot(&export_path).await.unwrap(), Some(2));
+    assert_eq!(state.entries_count(), 1);
+    assert_eq!(state.load_entries().await.unwrap().len(), 1);
+    assert_eq!(state.load_snapshot().await.unwrap().unwrap().index, 1);
+
+    let bytes = tokio::fs::read(&export_path).await.unwrap();
+    let snapshot = StateSnapshot::from_bytes(&bytes, None).unwrap();
+    assert_eq!(snapshot.index, 2);
+    assert_eq!(snapshot.state.streams.len(), 3);
+}
+
+#[tokio::test]
+async fn should_skip_entries_covered_by_snapshot_given_not_truncated_log() {
+    let setup = StateSetup::init().await;
+    let state = setup.state();
+    state.init().await.unwrap();
+
+    let user_id = 1;
+    for stream_id in 1..=3 {
+        let command = EntryCommand::CreateStream(CreateStream {
+            stream_id: Some(stream_id),
+            name: format!("test{stream_id}"),
+        });
+        state.apply(user_id, command).await.unwrap();
+    }
+    let log = tokio::fs::read(setup.log_path()).await.unwrap();
+    state.snapshot(0).await.unwrap();
+    // Simulates the server stopped after writing the snapshot, but before truncating the log.
+    tokio::fs::write(setup.log_path(), log).await.unwrap();
+
+    let state = FileState::new(
+        &setup.log_path(),
+        &SemanticVersion::from_str("1.2.3").unwrap(),
+        Arc::new(FilePersister {}),
+        None,
+    );
+    let entries = state.init().await.unwrap();
+    assert!(entries.is_empty());
+    assert_eq!(state.current_index(), 2);
+}
+
+#[tokio::test]
+async fn should_reencrypt_snapshot_with_active_key() {
+    let setup = StateSetup::init_with_encryptor().await;
+    let state = setup.state();
+    state.init().await.unwrap();
+
+    let user_id = 1;
+    let create_stream = EntryCommand::CreateStream(CreateStream {
+        stream_id: Some(1),
+        name: "test".to_string(),
+    });
+    state.apply(user_id, create_stream).await.unwrap();
+    state.snapshot(0).await.unwrap();
+
+    let mut key_ring = EncryptionKeyRing::new(1, Aes256GcmEncryptor::new(&[1; 32]).unwrap());
+    key_ring
+        .rotate(2, Aes256GcmEncryptor::new(&[2; 32]).unwrap())
+        .unwrap();
+    assert_eq!(state.reencrypt(&key_ring).await.unwrap(), 1);
+    assert_eq!(state.reencrypt(&key_ring).await.unwrap(), 0);
+
+    let active_key_ring = EncryptionKeyRing::new(2, Aes256GcmEncryptor::new(&[2; 32]).unwrap());
+    let state = FileState::new(
+        &setup.log_path(),
+        &SemanticVersion::from_str("1.2.3").unwrap(),
+        Arc::new(FilePersister {}),
+        Some(Arc::new(active_key_ring)),
+    );
+    let snapshot = state.load_snapshot().await.unwrap().unwrap();
+    assert_eq!(snapshot.index, 0);
+    assert_eq!(snapshot.state.streams.get(&1).unwrap().name, "test");
+}
+
 fn assert_entry(entry: StateEntry, index: u64, version: u32, user_id: u32, command: Bytes) {
     assert_eq!(entry.index, index);
     assert_eq!(entry.term, 0);
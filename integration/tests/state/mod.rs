@@ -49,6 +49,10 @@ impl StateSetup {
         &self.state
     }
 
+    pub fn log_path(&self) -> String {
+        format!("{}/log", self.directory_path)
+    }
+
     pub fn version(&self) -> u32 {
         self.version
     }
@@ -1,13 +1,17 @@
 use crate::state::StateSetup;
+use iggy::identifier::Identifier;
 use iggy::partitions::create_partitions::CreatePartitions;
 use iggy::personal_access_tokens::create_personal_access_token::CreatePersonalAccessToken;
+use iggy::roles::create_role::CreateRole;
+use iggy::roles::delete_role::DeleteRole;
 use iggy::streams::create_stream::CreateStream;
 use iggy::streams::delete_stream::DeleteStream;
 use iggy::topics::create_topic::CreateTopic;
+use iggy::users::change_password::ChangePassword;
 use iggy::users::create_user::CreateUser;
 use iggy::utils::expiry::IggyExpiry;
 use server::state::command::EntryCommand;
-use server::state::models::CreatePersonalAccessTokenWithHash;
+use server::state::models::{CreatePersonalAccessTokenWithHash, CreateRoleWithId, RehashPassword};
 use server::state::system::SystemState;
 use server::state::State;
 
@@ -174,3 +178,144 @@ async fn should_be_initialized_based_on_state_entries() {
     assert_eq!(topic.name, create_topic1_clone.name);
     assert_eq!(topic.partitions.len(), 3);
 }
+
+#[tokio::test]
+async fn should_track_password_history_based_on_state_entries() {
+    let setup = StateSetup::init().await;
+    let state = setup.state();
+    state.init().await.unwrap();
+
+    let user_id = 1;
+    let create_user = CreateUser {
+        username: "user".to_string(),
+        password: "hash1".to_string(),
+        status: Default::default(),
+        permissions: None,
+    };
+    let change_password = ChangePassword {
+        user_id: Identifier::numeric(user_id).unwrap(),
+        current_password: "".to_string(),
+        new_password: "hash2".to_string(),
+    };
+    let rehash_password = RehashPassword {
+        user_id,
+        password_hash: "hash3".to_string(),
+    };
+    let change_password_again = ChangePassword {
+        user_id: Identifier::numeric(user_id).unwrap(),
+        current_password: "".to_string(),
+        new_password: "hash4".to_string(),
+    };
+
+    state
+        .apply(user_id, EntryCommand::CreateUser(create_user))
+        .await
+        .unwrap();
+    for command in [
+        EntryCommand::ChangePassword(change_password),
+        EntryCommand::RehashPassword(rehash_password),
+        EntryCommand::ChangePassword(change_password_again),
+    ] {
+        state.apply(user_id, command).await.unwrap();
+    }
+
+    let entries = state.load_entries().await.unwrap();
+    let system = SystemState::init(entries).await.unwrap();
+
+    let user = system.users.get(&user_id).unwrap();
+    assert_eq!(user.password_hash, "hash4");
+    assert_eq!(user.password_history, vec!["hash3", "hash1"]);
+}
+
+#[tokio::test]
+async fn should_be_restored_from_snapshot_and_entries_appended_after_it() {
+    let setup = StateSetup::init().await;
+    let state = setup.state();
+    state.init().await.unwrap();
+
+    let user_id = 1;
+    let create_user = |username: &str| {
+        EntryCommand::CreateUser(CreateUser {
+            username: username.to_string(),
+            password: "secret".to_string(),
+            status: Default::default(),
+            permissions: None,
+        })
+    };
+    let create_stream = |name: &str| {
+        EntryCommand::CreateStream(CreateStream {
+            stream_id: None,
+            name: name.to_string(),
+        })
+    };
+    state.apply(user_id, create_user("user1")).await.unwrap();
+    state
+        .apply(user_id, create_stream("stream1"))
+        .await
+        .unwrap();
+    state
+        .apply(
+            user_id,
+            EntryCommand::DeleteStream(DeleteStream {
+                stream_id: Identifier::numeric(1).unwrap(),
+            }),
+        )
+        .await
+        .unwrap();
+    state.snapshot(0).await.unwrap();
+    state.apply(user_id, create_user("user2")).await.unwrap();
+    state
+        .apply(user_id, create_stream("stream2"))
+        .await
+        .unwrap();
+
+    let snapshot = state.load_snapshot().await.unwrap().unwrap();
+    let entries = state.init().await.unwrap();
+    assert_eq!(entries.len(), 2);
+    let system = SystemState::restore(Some(snapshot.state), entries)
+        .await
+        .unwrap();
+
+    assert_eq!(system.users.len(), 2);
+    assert_eq!(system.users.get(&2).unwrap().username, "user2");
+    assert_eq!(system.streams.len(), 1);
+    assert_eq!(system.streams.get(&2).unwrap().name, "stream2");
+}
+
+#[tokio::test]
+async fn should_restore_roles_with_assigned_ids() {
+    let setup = StateSetup::init().await;
+    let state = setup.state();
+    state.init().await.unwrap();
+
+    let user_id = 1;
+    let create_role = |role_id: u32, name: &str| {
+        EntryCommand::CreateRole(CreateRoleWithId {
+            role_id,
+            command: CreateRole {
+                name: name.to_string(),
+                permissions: None,
+            },
+        })
+    };
+    state.apply(user_id, create_role(1, "role1")).await.unwrap();
+    state.apply(user_id, create_role(2, "role2")).await.unwrap();
+    state
+        .apply(
+            user_id,
+            EntryCommand::DeleteRole(DeleteRole {
+                role_id: Identifier::numeric(2).unwrap(),
+            }),
+        )
+        .await
+        .unwrap();
+    state.apply(user_id, create_role(2, "role3")).await.unwrap();
+
+    let entries = state.load_entries().await.unwrap();
+    let system = SystemState::init(entries).await.unwrap();
+
+    assert_eq!(system.roles.len(), 2);
+    assert_eq!(system.roles.get(&1).unwrap().name, "role1");
+    assert_eq!(system.roles.get(&2).unwrap().name, "role3");
+    assert_eq!(system.current_role_id, 2);
+}
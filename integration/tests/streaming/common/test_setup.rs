@@ -1,5 +1,7 @@
 use server::configs::system::SystemConfig;
+use server::streaming::diagnostics::metrics::StorageMetrics;
 use server::streaming::persistence::persister::FilePersister;
+use server::streaming::persistence::reader::FileSegmentReader;
 use server::streaming::storage::SystemStorage;
 use std::sync::Arc;
 use tokio::fs;
@@ -21,7 +23,12 @@ impl TestSetup {
         let config = Arc::new(config);
         fs::create_dir(config.get_system_path()).await.unwrap();
         let persister = FilePersister {};
-        let storage = Arc::new(SystemStorage::new(config.clone(), Arc::new(persister)));
+        let storage = Arc::new(SystemStorage::new(
+            config.clone(),
+            Arc::new(persister),
+            Arc::new(FileSegmentReader),
+            StorageMetrics::default(),
+        ));
         TestSetup { config, storage }
     }
 
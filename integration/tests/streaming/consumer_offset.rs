@@ -1,54 +1,267 @@
 use crate::streaming::common::test_setup::TestSetup;
+use crate::streaming::create_messages;
+use futures::future::join_all;
 use iggy::consumer::ConsumerKind;
-use server::configs::system::SystemConfig;
-use server::streaming::partitions::partition::ConsumerOffset;
-use server::streaming::storage::PartitionStorage;
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::timestamp::IggyTimestamp;
+use server::state::system::PartitionState;
+use server::streaming::batching::appendable_batch_info::AppendableBatchInfo;
+use server::streaming::partitions::offsets_log::ENTRY_SIZE;
+use server::streaming::partitions::partition::{ConsumerOffset, Partition};
+use server::streaming::polling_consumer::PollingConsumer;
+use std::sync::atomic::{AtomicU32, AtomicU64};
 use std::sync::Arc;
 use tokio::fs;
 
+const STREAM_ID: u32 = 1;
+const TOPIC_ID: u32 = 2;
+const PARTITION_ID: u32 = 3;
+
 #[tokio::test]
 async fn should_persist_consumer_offsets_and_then_load_them_from_disk() {
     let setup = TestSetup::init().await;
     let storage = setup.storage.partition.as_ref();
-    assert_persisted_offsets(&setup.config, storage, ConsumerKind::Consumer).await;
-    assert_persisted_offsets(&setup.config, storage, ConsumerKind::ConsumerGroup).await;
-}
-
-async fn assert_persisted_offsets(
-    config: &Arc<SystemConfig>,
-    storage: &dyn PartitionStorage,
-    kind: ConsumerKind,
-) {
+    let path = format!("{}/offsets_log", setup.config.get_system_path());
     let consumer_ids_count = 3;
     let offsets_count = 5;
-    let path = match kind {
-        ConsumerKind::Consumer => "consumer_offsets",
-        ConsumerKind::ConsumerGroup => "consumer_group_offsets",
-    };
-    let path = format!("{}/{}", config.get_system_path(), path);
-    fs::create_dir(&path).await.unwrap();
-    for consumer_id in 1..=consumer_ids_count {
-        let expected_offsets_count = consumer_id;
-        for offset in 0..=offsets_count {
-            let consumer_offset = ConsumerOffset::new(kind, consumer_id, offset, &path);
-            assert_persisted_offset(&path, storage, &consumer_offset, expected_offsets_count).await;
-        }
+    for offset in 0..=offsets_count {
+        let consumer_offsets = [ConsumerKind::Consumer, ConsumerKind::ConsumerGroup]
+            .into_iter()
+            .flat_map(|kind| {
+                (1..=consumer_ids_count)
+                    .map(move |consumer_id| ConsumerOffset::new(kind, consumer_id, offset))
+            })
+            .collect::<Vec<_>>();
+        storage
+            .save_consumer_offsets(&path, &consumer_offsets)
+            .await
+            .unwrap();
     }
+    storage
+        .delete_consumer_offset(&path, ConsumerKind::ConsumerGroup, 2)
+        .await
+        .unwrap();
+
+    let consumer_offsets_log = storage.load_consumer_offsets(&path).await.unwrap();
+
+    let expected_entries_count = (offsets_count + 1) * 2 * consumer_ids_count as u64 + 1;
+    assert_eq!(consumer_offsets_log.entries_count, expected_entries_count);
+    assert_eq!(
+        consumer_offsets_log.offsets,
+        vec![
+            ConsumerOffset::new(ConsumerKind::Consumer, 1, offsets_count),
+            ConsumerOffset::new(ConsumerKind::Consumer, 2, offsets_count),
+            ConsumerOffset::new(ConsumerKind::Consumer, 3, offsets_count),
+            ConsumerOffset::new(ConsumerKind::ConsumerGroup, 1, offsets_count),
+            ConsumerOffset::new(ConsumerKind::ConsumerGroup, 3, offsets_count),
+        ]
+    );
 }
 
-async fn assert_persisted_offset(
-    path: &str,
-    storage: &dyn PartitionStorage,
-    consumer_offset: &ConsumerOffset,
-    expected_offsets_count: u32,
-) {
-    storage.save_consumer_offset(consumer_offset).await.unwrap();
-    let consumer_offsets = storage
-        .load_consumer_offsets(consumer_offset.kind, path)
+#[tokio::test]
+async fn should_truncate_partial_entry_at_the_end_of_the_log() {
+    let setup = TestSetup::init().await;
+    let storage = setup.storage.partition.as_ref();
+    let path = format!("{}/offsets_log", setup.config.get_system_path());
+    let consumer_offset = ConsumerOffset::new(ConsumerKind::Consumer, 1, 10);
+    storage
+        .save_consumer_offsets(&path, std::slice::from_ref(&consumer_offset))
         .await
         .unwrap();
-    let expected_offsets_count = expected_offsets_count as usize;
-    assert_eq!(consumer_offsets.len(), expected_offsets_count);
-    let loaded_consumer_offset = consumer_offsets.get(expected_offsets_count - 1).unwrap();
-    assert_eq!(loaded_consumer_offset, consumer_offset);
+    let mut bytes = fs::read(&path).await.unwrap();
+    bytes.extend_from_slice(&[1, 0, 1]);
+    fs::write(&path, &bytes).await.unwrap();
+
+    let consumer_offsets_log = storage.load_consumer_offsets(&path).await.unwrap();
+
+    assert_eq!(consumer_offsets_log.entries_count, 1);
+    assert_eq!(consumer_offsets_log.offsets, vec![consumer_offset]);
+    let log_size = fs::metadata(&path).await.unwrap().len();
+    assert_eq!(log_size, ENTRY_SIZE as u64);
+}
+
+#[tokio::test]
+async fn should_keep_consumer_offsets_saved_concurrently_to_new_log() {
+    let setup = TestSetup::init().await;
+    let storage = setup.storage.partition.as_ref();
+    let path = format!("{}/offsets_log", setup.config.get_system_path());
+    let consumer_offsets = (1..=10)
+        .map(|consumer_id| ConsumerOffset::new(ConsumerKind::Consumer, consumer_id, 1))
+        .collect::<Vec<_>>();
+
+    join_all(consumer_offsets.iter().map(|consumer_offset| {
+        storage.save_consumer_offsets(&path, std::slice::from_ref(consumer_offset))
+    }))
+    .await
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+
+    let mut consumer_offsets_log = storage.load_consumer_offsets(&path).await.unwrap();
+    consumer_offsets_log
+        .offsets
+        .sort_by_key(|consumer_offset| consumer_offset.consumer_id);
+    assert_eq!(consumer_offsets_log.entries_count, 10);
+    assert_eq!(consumer_offsets_log.offsets, consumer_offsets);
+}
+
+#[tokio::test]
+async fn should_flush_buffered_consumer_offsets_and_load_them_from_disk() {
+    let setup = TestSetup::init().await;
+    let partition = create_partition(&setup).await;
+    partition
+        .store_consumer_offset(PollingConsumer::Consumer(1, PARTITION_ID), 1)
+        .await
+        .unwrap();
+    partition
+        .store_consumer_offset(PollingConsumer::Consumer(1, PARTITION_ID), 2)
+        .await
+        .unwrap();
+    partition
+        .store_consumer_offset(PollingConsumer::ConsumerGroup(1, PARTITION_ID), 3)
+        .await
+        .unwrap();
+    assert!(fs::metadata(&partition.consumer_offsets_log_path)
+        .await
+        .is_err());
+
+    let saved_offsets = partition.flush_consumer_offsets().await.unwrap();
+    assert_eq!(saved_offsets, 2);
+    assert_eq!(partition.flush_consumer_offsets().await.unwrap(), 0);
+
+    let loaded_partition = load_partition(&setup).await;
+    assert_eq!(
+        loaded_partition
+            .get_consumer_offset(PollingConsumer::Consumer(1, PARTITION_ID))
+            .await
+            .unwrap(),
+        2
+    );
+    let lag = loaded_partition
+        .get_consumer_lag(ConsumerKind::ConsumerGroup, 1)
+        .await
+        .unwrap();
+    assert_eq!(lag.stored_offset, Some(3));
+}
+
+#[tokio::test]
+async fn should_compact_consumer_offsets_log_when_threshold_is_reached() {
+    let setup = TestSetup::init().await;
+    let mut partition = create_partition(&setup).await;
+    let threshold = setup.config.consumer_offsets.compaction_threshold;
+    for _ in 0..threshold {
+        partition
+            .store_consumer_offset(PollingConsumer::Consumer(1, PARTITION_ID), 4)
+            .await
+            .unwrap();
+        partition.flush_consumer_offsets().await.unwrap();
+    }
+    assert!(!partition.compact_consumer_offsets().await.unwrap());
+
+    partition
+        .store_consumer_offset(PollingConsumer::Consumer(1, PARTITION_ID), 5)
+        .await
+        .unwrap();
+    partition.flush_consumer_offsets().await.unwrap();
+    assert!(partition.compact_consumer_offsets().await.unwrap());
+
+    let log_size = fs::metadata(&partition.consumer_offsets_log_path)
+        .await
+        .unwrap()
+        .len();
+    assert_eq!(log_size, ENTRY_SIZE as u64);
+    let loaded_partition = load_partition(&setup).await;
+    assert_eq!(
+        loaded_partition
+            .get_consumer_offset(PollingConsumer::Consumer(1, PARTITION_ID))
+            .await
+            .unwrap(),
+        5
+    );
+}
+
+#[tokio::test]
+async fn should_convert_legacy_consumer_offsets_when_partition_is_loaded() {
+    let setup = TestSetup::init().await;
+    create_partition(&setup).await;
+    let legacy_paths = [
+        setup
+            .config
+            .get_consumer_offsets_path(STREAM_ID, TOPIC_ID, PARTITION_ID),
+        setup
+            .config
+            .get_consumer_group_offsets_path(STREAM_ID, TOPIC_ID, PARTITION_ID),
+    ];
+    for (offset, path) in legacy_paths.iter().enumerate() {
+        fs::create_dir_all(path).await.unwrap();
+        fs::write(format!("{path}/1"), (offset as u64 + 1).to_le_bytes())
+            .await
+            .unwrap();
+    }
+
+    let loaded_partition = load_partition(&setup).await;
+
+    assert_eq!(
+        loaded_partition
+            .get_consumer_offset(PollingConsumer::Consumer(1, PARTITION_ID))
+            .await
+            .unwrap(),
+        1
+    );
+    let lag = loaded_partition
+        .get_consumer_lag(ConsumerKind::ConsumerGroup, 1)
+        .await
+        .unwrap();
+    assert_eq!(lag.stored_offset, Some(2));
+    for path in legacy_paths {
+        assert!(fs::metadata(path).await.is_err());
+    }
+    assert!(fs::metadata(&loaded_partition.consumer_offsets_log_path)
+        .await
+        .is_ok());
+}
+
+async fn create_partition(setup: &TestSetup) -> Partition {
+    setup.create_partitions_directory(STREAM_ID, TOPIC_ID).await;
+    let mut partition = new_partition(setup, true);
+    partition.persist().await.unwrap();
+    let messages = create_messages();
+    let appendable_batch_info = AppendableBatchInfo::new(
+        messages.iter().map(|msg| msg.get_size_bytes() as u64).sum(),
+        partition.partition_id,
+    );
+    partition
+        .append_messages(appendable_batch_info, messages)
+        .await
+        .unwrap();
+    partition.persist().await.unwrap();
+    partition
+}
+
+async fn load_partition(setup: &TestSetup) -> Partition {
+    let mut partition = new_partition(setup, false);
+    let partition_state = PartitionState {
+        id: PARTITION_ID,
+        created_at: IggyTimestamp::now(),
+    };
+    partition.load(partition_state).await.unwrap();
+    partition
+}
+
+fn new_partition(setup: &TestSetup, with_segment: bool) -> Partition {
+    Partition::create(
+        STREAM_ID,
+        TOPIC_ID,
+        PARTITION_ID,
+        with_segment,
+        setup.config.clone(),
+        setup.storage.clone(),
+        IggyExpiry::NeverExpire,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU32::new(0)),
+        IggyTimestamp::now(),
+    )
 }
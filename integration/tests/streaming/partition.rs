@@ -1,11 +1,16 @@
 use crate::streaming::common::test_setup::TestSetup;
 use crate::streaming::create_messages;
+use iggy::consumer::ConsumerKind;
+use iggy::utils::duration::IggyDuration;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::timestamp::IggyTimestamp;
+use server::configs::system::SystemConfig;
 use server::state::system::PartitionState;
 use server::streaming::batching::appendable_batch_info::AppendableBatchInfo;
 use server::streaming::partitions::partition::Partition;
+use server::streaming::polling_consumer::PollingConsumer;
 use server::streaming::segments::segment::{INDEX_EXTENSION, LOG_EXTENSION, TIME_INDEX_EXTENSION};
+use std::str::FromStr;
 use std::sync::atomic::{AtomicU32, AtomicU64};
 use std::sync::Arc;
 use tokio::fs;
@@ -194,6 +199,191 @@ async fn should_purge_existing_partition_on_disk() {
     }
 }
 
+#[tokio::test]
+async fn should_calculate_consumer_group_lag_based_on_stored_offset() {
+    let setup = TestSetup::init().await;
+    let stream_id = 1;
+    let topic_id = 2;
+    let partition_id = 3;
+    let consumer_group_id = 1;
+    setup.create_partitions_directory(stream_id, topic_id).await;
+    let partition = Partition::create(
+        stream_id,
+        topic_id,
+        partition_id,
+        true,
+        setup.config.clone(),
+        setup.storage.clone(),
+        IggyExpiry::NeverExpire,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU32::new(0)),
+        IggyTimestamp::now(),
+    );
+    partition.persist().await.unwrap();
+    let lag = partition
+        .get_consumer_lag(ConsumerKind::ConsumerGroup, consumer_group_id)
+        .await
+        .unwrap();
+    assert_eq!(lag.lag, 0);
+    assert_eq!(lag.lag_bytes, 0);
+
+    let mut partition = partition;
+    let batch_messages_count = create_messages().len() as u64;
+    let messages_count = 2 * batch_messages_count;
+    let mut batches_size_bytes = Vec::new();
+    for _ in 0..2 {
+        let messages = create_messages();
+        let appendable_batch_info = AppendableBatchInfo::new(
+            messages.iter().map(|msg| msg.get_size_bytes() as u64).sum(),
+            partition.partition_id,
+        );
+        let size_bytes = partition.get_segments()[0].size_bytes as u64;
+        partition
+            .append_messages(appendable_batch_info, messages)
+            .await
+            .unwrap();
+        batches_size_bytes.push(partition.get_segments()[0].size_bytes as u64 - size_bytes);
+    }
+    let size_bytes = partition.get_segments()[0].size_bytes as u64;
+
+    let lag = partition
+        .get_consumer_lag(ConsumerKind::ConsumerGroup, consumer_group_id)
+        .await
+        .unwrap();
+    assert_eq!(lag.partition_id, partition_id);
+    assert_eq!(lag.current_offset, messages_count - 1);
+    assert_eq!(lag.stored_offset, None);
+    assert_eq!(lag.lag, messages_count);
+    assert_eq!(lag.lag_bytes, size_bytes);
+
+    // The unsaved and the persisted batches are both counted from the batch containing the next offset.
+    for persist in [false, true] {
+        if persist {
+            partition.get_segments_mut()[0]
+                .persist_messages()
+                .await
+                .unwrap();
+        }
+
+        partition
+            .store_consumer_offset(PollingConsumer::ConsumerGroup(consumer_group_id, 1), 2)
+            .await
+            .unwrap();
+        let lag = partition
+            .get_consumer_lag(ConsumerKind::ConsumerGroup, consumer_group_id)
+            .await
+            .unwrap();
+        assert_eq!(lag.stored_offset, Some(2));
+        assert_eq!(lag.lag, messages_count - 3);
+        assert_eq!(lag.lag_bytes, size_bytes);
+
+        partition
+            .store_consumer_offset(
+                PollingConsumer::ConsumerGroup(consumer_group_id, 1),
+                batch_messages_count + 1,
+            )
+            .await
+            .unwrap();
+        let lag = partition
+            .get_consumer_lag(ConsumerKind::ConsumerGroup, consumer_group_id)
+            .await
+            .unwrap();
+        assert_eq!(lag.lag, batch_messages_count - 2);
+        assert_eq!(lag.lag_bytes, batches_size_bytes[1]);
+    }
+
+    partition
+        .store_consumer_offset(
+            PollingConsumer::ConsumerGroup(consumer_group_id, 1),
+            messages_count - 1,
+        )
+        .await
+        .unwrap();
+    let lag = partition
+        .get_consumer_lag(ConsumerKind::ConsumerGroup, consumer_group_id)
+        .await
+        .unwrap();
+    assert_eq!(lag.lag, 0);
+    assert_eq!(lag.lag_bytes, 0);
+}
+
+#[tokio::test]
+async fn should_close_aged_segment_and_append_next_messages_to_new_segment() {
+    let mut config = SystemConfig::default();
+    config.segment.max_age = IggyDuration::from_str("1h").unwrap();
+    config.partition.messages_required_to_save = 1000;
+    let setup = TestSetup::init_with_config(config).await;
+    let stream_id = 1;
+    let topic_id = 2;
+    let partition_id = 3;
+    setup.create_partitions_directory(stream_id, topic_id).await;
+    let mut partition = Partition::create(
+        stream_id,
+        topic_id,
+        partition_id,
+        true,
+        setup.config.clone(),
+        setup.storage.clone(),
+        IggyExpiry::NeverExpire,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU32::new(0)),
+        IggyTimestamp::now(),
+    );
+    partition.persist().await.unwrap();
+    let now = IggyTimestamp::now();
+    assert!(!partition.close_aged_segment(now).await.unwrap());
+
+    let messages = create_messages();
+    let messages_count = messages.len() as u64;
+    let appendable_batch_info = AppendableBatchInfo::new(
+        messages.iter().map(|msg| msg.get_size_bytes() as u64).sum(),
+        partition.partition_id,
+    );
+    partition
+        .append_messages(appendable_batch_info, messages)
+        .await
+        .unwrap();
+    assert!(!partition.close_aged_segment(now).await.unwrap());
+
+    // The unsaved messages are persisted when the aged segment is closed.
+    let later = IggyTimestamp::from(now.as_micros() + 2 * 3600 * 1_000_000);
+    assert!(partition.close_aged_segment(later).await.unwrap());
+    assert_eq!(partition.unsaved_messages_count, 0);
+    let segment = &partition.get_segments()[0];
+    assert!(segment.is_closed);
+    assert_eq!(segment.end_offset, messages_count - 1);
+    assert_eq!(
+        fs::metadata(&segment.log_path).await.unwrap().len(),
+        segment.size_bytes as u64
+    );
+    assert!(!partition.close_aged_segment(later).await.unwrap());
+
+    let messages = create_messages();
+    let appendable_batch_info = AppendableBatchInfo::new(
+        messages.iter().map(|msg| msg.get_size_bytes() as u64).sum(),
+        partition.partition_id,
+    );
+    partition
+        .append_messages(appendable_batch_info, messages)
+        .await
+        .unwrap();
+    let segments = partition.get_segments();
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[1].start_offset, messages_count);
+    assert!(!segments[1].is_closed);
+    let loaded_messages = partition
+        .get_messages_by_offset(0, 2 * messages_count as u32)
+        .await
+        .unwrap();
+    assert_eq!(loaded_messages.len(), 2 * messages_count as usize);
+}
+
 async fn assert_persisted_partition(partition_path: &str, with_segment: bool) {
     assert!(fs::metadata(&partition_path).await.is_ok());
 
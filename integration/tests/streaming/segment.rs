@@ -2,15 +2,24 @@ use crate::streaming::common::test_setup::TestSetup;
 use bytes::{Bytes, BytesMut};
 use iggy::bytes_serializable::BytesSerializable;
 use iggy::models::messages::{MessageState, PolledMessage};
+use iggy::utils::byte_size::IggyByteSize;
+use iggy::utils::crypto::{Aes256GcmEncryptor, Encryptor};
+use iggy::utils::duration::IggyDuration;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::{checksum, timestamp::IggyTimestamp};
+use server::configs::system::SystemConfig;
 use server::streaming::batching::message_batch::RetainedMessageBatch;
+use server::streaming::batching::polled_batches::BatchesChunk;
 use server::streaming::models::messages::RetainedMessage;
 use server::streaming::segments::segment;
 use server::streaming::segments::segment::{INDEX_EXTENSION, LOG_EXTENSION, TIME_INDEX_EXTENSION};
+use server::streaming::utils::key_ring::EncryptionKeyRing;
+use std::io::SeekFrom;
+use std::str::FromStr;
 use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use tokio::fs;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
 
 #[tokio::test]
 async fn should_persist_segment() {
@@ -378,6 +387,587 @@ async fn given_at_least_one_not_expired_message_segment_should_not_be_expired()
     assert!(!is_expired);
 }
 
+#[tokio::test]
+async fn should_reencrypt_messages_of_closed_segment_with_active_key() {
+    let setup = TestSetup::init().await;
+    let previous_key = Aes256GcmEncryptor::new(&[1; 32]).unwrap();
+    let mut segment = create_segment_with_encrypted_messages(&setup, &previous_key).await;
+    let mut key_ring = EncryptionKeyRing::new(1, previous_key);
+    key_ring
+        .rotate(2, Aes256GcmEncryptor::new(&[2; 32]).unwrap())
+        .unwrap();
+    assert_eq!(reencrypt_segment(&mut segment, &key_ring).await, 0);
+    segment.is_closed = true;
+    assert_eq!(segment.get_messages(5, 5).await.unwrap().len(), 5);
+    assert_eq!(reencrypt_segment(&mut segment, &key_ring).await, 10);
+    assert_eq!(reencrypt_segment(&mut segment, &key_ring).await, 0);
+    assert_reencrypted_messages(&segment).await;
+
+    let loaded_segment = load_segment(&setup).await;
+    assert_eq!(loaded_segment.size_bytes, segment.size_bytes);
+    assert_reencrypted_messages(&loaded_segment).await;
+}
+
+#[tokio::test]
+async fn should_complete_interrupted_swap_of_reencrypted_segment_files_on_load() {
+    let setup = TestSetup::init().await;
+    let previous_key = Aes256GcmEncryptor::new(&[1; 32]).unwrap();
+    let mut segment = create_segment_with_encrypted_messages(&setup, &previous_key).await;
+    let mut key_ring = EncryptionKeyRing::new(1, previous_key);
+    key_ring
+        .rotate(2, Aes256GcmEncryptor::new(&[2; 32]).unwrap())
+        .unwrap();
+    segment.is_closed = true;
+    let reencryption = segment.prepare_reencryption().unwrap();
+    reencryption.rewrite(&key_ring).await.unwrap().unwrap();
+    // The server stopped after recording the renames in the manifest and renaming only the log file.
+    let manifest = format!(
+        "{}\n{}\n{}",
+        segment.log_path, segment.index_path, segment.time_index_path
+    );
+    fs::write(format!("{}.reencryption", segment.log_path), manifest)
+        .await
+        .unwrap();
+    fs::rename(
+        format!("{}.reencrypted", segment.log_path),
+        &segment.log_path,
+    )
+    .await
+    .unwrap();
+
+    let loaded_segment = load_segment(&setup).await;
+
+    assert_reencrypted_messages(&loaded_segment).await;
+    for path in [
+        &segment.log_path,
+        &segment.index_path,
+        &segment.time_index_path,
+    ] {
+        assert!(fs::metadata(format!("{path}.reencrypted")).await.is_err());
+    }
+    assert!(fs::metadata(format!("{}.reencryption", segment.log_path))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn should_remove_reencrypted_segment_files_of_interrupted_rewrite_on_load() {
+    let setup = TestSetup::init().await;
+    let previous_key = Aes256GcmEncryptor::new(&[1; 32]).unwrap();
+    let mut segment = create_segment_with_encrypted_messages(&setup, &previous_key).await;
+    let mut key_ring = EncryptionKeyRing::new(1, previous_key);
+    key_ring
+        .rotate(2, Aes256GcmEncryptor::new(&[2; 32]).unwrap())
+        .unwrap();
+    segment.is_closed = true;
+    let reencryption = segment.prepare_reencryption().unwrap();
+    reencryption.rewrite(&key_ring).await.unwrap().unwrap();
+
+    let loaded_segment = load_segment(&setup).await;
+
+    assert_eq!(loaded_segment.size_bytes, segment.size_bytes);
+    assert!(fs::metadata(format!("{}.reencrypted", segment.log_path))
+        .await
+        .is_err());
+    let messages = loaded_segment.get_messages(0, 10).await.unwrap();
+    assert_eq!(messages.len(), 10);
+    for message in messages {
+        assert!(key_ring.reencrypt(&message.payload).unwrap().is_some());
+    }
+}
+
+#[tokio::test]
+async fn should_not_repair_consistent_segment() {
+    let setup = TestSetup::init().await;
+    let segment = create_segment_with_batches(&setup, 3).await;
+
+    let recovery = segment.recover().await.unwrap();
+
+    assert!(!recovery.is_repaired());
+}
+
+#[tokio::test]
+async fn should_truncate_partial_batch_at_the_tail_of_segment() {
+    let setup = TestSetup::init().await;
+    let segment = create_segment_with_batches(&setup, 3).await;
+    let log_size = fs::metadata(&segment.log_path).await.unwrap().len();
+    let mut torn_batch = BytesMut::new();
+    create_batch(30, 10).extend(&mut torn_batch);
+    let torn_batch = &torn_batch[..torn_batch.len() / 2];
+    append_bytes(&segment.log_path, torn_batch).await;
+
+    let recovery = segment.recover().await.unwrap();
+
+    assert!(recovery.is_repaired());
+    assert_eq!(recovery.valid_batches, 3);
+    assert_eq!(recovery.truncated_bytes, torn_batch.len() as u64);
+    assert!(!recovery.rebuilt_indexes);
+    assert!(!recovery.rebuilt_time_indexes);
+    assert_eq!(
+        fs::metadata(&segment.log_path).await.unwrap().len(),
+        log_size
+    );
+    assert_loaded_segment(&setup, 30).await;
+}
+
+#[tokio::test]
+async fn should_not_truncate_partial_batch_of_closed_segment() {
+    let setup = TestSetup::init().await;
+    let mut segment = create_segment_with_batches(&setup, 3).await;
+    let mut torn_batch = BytesMut::new();
+    create_batch(30, 10).extend(&mut torn_batch);
+    let torn_batch = &torn_batch[..torn_batch.len() / 2];
+    append_bytes(&segment.log_path, torn_batch).await;
+    let log_size = fs::metadata(&segment.log_path).await.unwrap().len();
+    segment.is_closed = true;
+
+    assert!(segment.recover().await.is_err());
+    assert_eq!(
+        fs::metadata(&segment.log_path).await.unwrap().len(),
+        log_size
+    );
+}
+
+#[tokio::test]
+async fn should_rebuild_indexes_given_batch_saved_without_indexes() {
+    let mut config = SystemConfig::default();
+    config.segment.index_interval = IggyByteSize::from(0);
+    let setup = TestSetup::init_with_config(config).await;
+    let segment = create_segment_with_batches(&setup, 3).await;
+    let mut batch = BytesMut::new();
+    create_batch(30, 10).extend(&mut batch);
+    append_bytes(&segment.log_path, &batch).await;
+
+    let recovery = segment.recover().await.unwrap();
+
+    assert_eq!(recovery.valid_batches, 4);
+    assert_eq!(recovery.truncated_bytes, 0);
+    assert!(recovery.rebuilt_indexes);
+    assert!(recovery.rebuilt_time_indexes);
+    assert_loaded_segment(&setup, 40).await;
+}
+
+#[tokio::test]
+async fn should_rebuild_indexes_given_indexes_pointing_past_the_end_of_log() {
+    let mut config = SystemConfig::default();
+    config.segment.index_interval = IggyByteSize::from(0);
+    let setup = TestSetup::init_with_config(config).await;
+    let segment = create_segment_with_batches(&setup, 3).await;
+    let log_size = fs::metadata(&segment.log_path).await.unwrap().len();
+    let log = fs::OpenOptions::new()
+        .write(true)
+        .open(&segment.log_path)
+        .await
+        .unwrap();
+    log.set_len(log_size - 10).await.unwrap();
+
+    let recovery = segment.recover().await.unwrap();
+
+    assert_eq!(recovery.valid_batches, 2);
+    assert!(recovery.truncated_bytes > 0);
+    assert!(recovery.rebuilt_indexes);
+    assert!(recovery.rebuilt_time_indexes);
+    assert_loaded_segment(&setup, 20).await;
+}
+
+#[tokio::test]
+async fn should_not_rebuild_indexes_given_batch_saved_without_indexes_within_index_interval() {
+    let setup = TestSetup::init().await;
+    let segment = create_segment_with_batches(&setup, 3).await;
+    let mut batch = BytesMut::new();
+    create_batch(30, 10).extend(&mut batch);
+    append_bytes(&segment.log_path, &batch).await;
+
+    let recovery = segment.recover().await.unwrap();
+
+    assert!(!recovery.is_repaired());
+    assert_loaded_segment(&setup, 40).await;
+}
+
+#[tokio::test]
+async fn should_rebuild_missing_indexes() {
+    let setup = TestSetup::init().await;
+    let segment = create_segment_with_batches(&setup, 3).await;
+    fs::remove_file(&segment.index_path).await.unwrap();
+    fs::remove_file(&segment.time_index_path).await.unwrap();
+
+    let recovery = segment.recover().await.unwrap();
+
+    assert_eq!(recovery.valid_batches, 3);
+    assert_eq!(recovery.truncated_bytes, 0);
+    assert!(recovery.rebuilt_indexes);
+    assert!(recovery.rebuilt_time_indexes);
+    assert_loaded_segment(&setup, 30).await;
+}
+
+#[tokio::test]
+async fn should_truncate_batch_with_invalid_checksum_given_checksum_validation() {
+    let mut config = SystemConfig::default();
+    config.partition.validate_checksum = true;
+    let setup = TestSetup::init_with_config(config).await;
+    let segment = create_segment_with_batches(&setup, 3).await;
+    let log_size = fs::metadata(&segment.log_path).await.unwrap().len();
+    let mut log = fs::OpenOptions::new()
+        .write(true)
+        .open(&segment.log_path)
+        .await
+        .unwrap();
+    log.seek(SeekFrom::Start(log_size - 1)).await.unwrap();
+    log.write_all(b"x").await.unwrap();
+
+    let recovery = segment.recover().await.unwrap();
+
+    assert_eq!(recovery.valid_batches, 2);
+    assert!(recovery.truncated_bytes > 0);
+    assert_loaded_segment(&setup, 20).await;
+}
+
+#[tokio::test]
+async fn should_return_persisted_batches_as_file_region_and_unsaved_batches_from_memory() {
+    let setup = TestSetup::init().await;
+    let mut segment = create_segment_with_batches(&setup, 3).await;
+    segment
+        .append_batch(Arc::new(create_batch(30, 10)))
+        .await
+        .unwrap();
+
+    let mut chunks = Vec::new();
+    let messages_count = segment
+        .get_batches_chunks(15, 34, &mut chunks)
+        .await
+        .unwrap();
+
+    assert_eq!(messages_count, 20);
+    assert_eq!(chunks.len(), 2);
+    assert!(matches!(chunks[0], BatchesChunk::File { .. }));
+    assert!(matches!(chunks[1], BatchesChunk::Memory(_)));
+    let mut bytes = BytesMut::new();
+    for chunk in chunks {
+        chunk.read_into(&mut bytes).await.unwrap();
+    }
+    assert_eq!(get_batches_base_offsets(&bytes), vec![10, 20, 30]);
+
+    let mut chunks = Vec::new();
+    let messages_count = segment
+        .get_batches_chunks(0, 100, &mut chunks)
+        .await
+        .unwrap();
+    assert_eq!(messages_count, 40);
+    let mut bytes = BytesMut::new();
+    for chunk in chunks {
+        chunk.read_into(&mut bytes).await.unwrap();
+    }
+    assert_eq!(get_batches_base_offsets(&bytes), vec![0, 10, 20, 30]);
+}
+
+#[tokio::test]
+async fn should_index_only_batches_starting_after_index_interval() {
+    for cache_indexes in [true, false] {
+        let mut config = SystemConfig::default();
+        // Each batch of 10 messages takes 514 bytes, so every other batch is indexed.
+        config.segment.index_interval = IggyByteSize::from(1000);
+        config.segment.cache_indexes = cache_indexes;
+        config.segment.cache_time_indexes = cache_indexes;
+        let setup = TestSetup::init_with_config(config).await;
+        let segment = create_segment_with_batches(&setup, 9).await;
+
+        let indexes = fs::read(&segment.index_path).await.unwrap();
+        let indexed_offsets = indexes
+            .chunks(8)
+            .map(|index| u32::from_le_bytes(index[..4].try_into().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(indexed_offsets, vec![9, 29, 49, 69, 89]);
+        assert_eq!(
+            fs::metadata(&segment.time_index_path).await.unwrap().len(),
+            5 * 12
+        );
+        assert!(!segment.recover().await.unwrap().is_repaired());
+
+        let mut segment = create_segment(&setup);
+        segment.load().await.unwrap();
+        assert_eq!(segment.current_offset, 89);
+        let messages = segment.get_messages(35, 20).await.unwrap();
+        assert_eq!(
+            messages
+                .iter()
+                .map(|message| message.offset)
+                .collect::<Vec<_>>(),
+            (35..55).collect::<Vec<_>>()
+        );
+
+        let mut chunks = Vec::new();
+        let messages_count = segment
+            .get_batches_chunks(35, 54, &mut chunks)
+            .await
+            .unwrap();
+        assert_eq!(messages_count, 20);
+        let mut bytes = BytesMut::new();
+        for chunk in chunks {
+            chunk.read_into(&mut bytes).await.unwrap();
+        }
+        assert_eq!(get_batches_base_offsets(&bytes), vec![30, 40, 50]);
+
+        // The next appended batch continues the interval from the last indexed batch.
+        segment
+            .append_batch(Arc::new(create_batch(90, 10)))
+            .await
+            .unwrap();
+        segment.persist_messages().await.unwrap();
+        assert_eq!(
+            fs::metadata(&segment.index_path).await.unwrap().len(),
+            5 * 8
+        );
+        assert_loaded_segment(&setup, 100).await;
+    }
+}
+
+#[tokio::test]
+async fn should_close_segment_given_first_batch_older_than_max_age() {
+    let max_age = IggyDuration::from_str("1h").unwrap();
+    let mut config = SystemConfig::default();
+    config.segment.max_age = max_age;
+    let setup = TestSetup::init_with_config(config).await;
+    let segment = create_segment_with_batches(&setup, 1).await;
+    assert!(!segment.is_closed);
+    let mut segment = create_segment(&setup);
+    segment.load().await.unwrap();
+    assert!(!segment.is_closed);
+
+    // The age is counted from the first batch, so the segment with the old messages is closed right away.
+    let mut config = SystemConfig::default();
+    config.segment.max_age = max_age;
+    let setup = TestSetup::init_with_config(config).await;
+    let old_timestamp =
+        IggyTimestamp::from(IggyTimestamp::now().as_micros() - 2 * max_age.as_micros());
+    let mut segment = create_segment(&setup);
+    setup
+        .create_partition_directory(segment.stream_id, segment.topic_id, segment.partition_id)
+        .await;
+    segment.persist().await.unwrap();
+    segment
+        .append_batch(Arc::new(create_batch_with_timestamp(0, 10, old_timestamp)))
+        .await
+        .unwrap();
+    assert!(segment.is_full().await);
+    segment.persist_messages().await.unwrap();
+    assert!(segment.is_closed);
+    assert_eq!(segment.end_offset, 9);
+
+    let mut segment = create_segment(&setup);
+    segment.load().await.unwrap();
+    assert!(segment.is_closed);
+    assert_eq!(segment.current_offset, 9);
+}
+
+#[tokio::test]
+async fn should_preallocate_segment_log_file_without_changing_its_size() {
+    let mut config = SystemConfig::default();
+    config.segment.preallocate = true;
+    config.segment.size = IggyByteSize::from(16 * 1024);
+    let setup = TestSetup::init_with_config(config).await;
+    let segment = create_segment_with_batches(&setup, 3).await;
+    assert!(!segment.is_closed);
+    assert_eq!(
+        fs::metadata(&segment.log_path).await.unwrap().len(),
+        segment.size_bytes as u64
+    );
+    assert!(!segment.recover().await.unwrap().is_repaired());
+    assert_loaded_segment(&setup, 30).await;
+
+    let mut segment = create_segment(&setup);
+    segment.load().await.unwrap();
+    while !segment.is_closed {
+        let base_offset = segment.current_offset + 1;
+        segment
+            .append_batch(Arc::new(create_batch(base_offset, 10)))
+            .await
+            .unwrap();
+        segment.persist_messages().await.unwrap();
+    }
+    assert_eq!(
+        fs::metadata(&segment.log_path).await.unwrap().len(),
+        segment.size_bytes as u64
+    );
+    assert_loaded_segment(&setup, segment.current_offset + 1).await;
+}
+
+async fn create_segment_with_encrypted_messages(
+    setup: &TestSetup,
+    key: &Aes256GcmEncryptor,
+) -> segment::Segment {
+    let mut segment = create_segment(setup);
+    setup.create_partition_directory(1, 2, 3).await;
+    segment.persist().await.unwrap();
+
+    let batches_count = 2;
+    let messages_per_batch = 5;
+    for batch_index in 0..batches_count {
+        let base_offset = batch_index * messages_per_batch;
+        let mut last_timestamp = 0;
+        let mut batch_buffer = BytesMut::new();
+        for offset in base_offset..base_offset + messages_per_batch {
+            let payload = Bytes::from(key.encrypt(b"test").unwrap());
+            let message = RetainedMessage {
+                id: offset as u128,
+                offset,
+                timestamp: IggyTimestamp::now().as_micros(),
+                checksum: checksum::calculate(&payload),
+                message_state: MessageState::Available,
+                headers: None,
+                payload,
+            };
+            last_timestamp = message.timestamp;
+            message.extend(&mut batch_buffer);
+        }
+        let batch = Arc::new(RetainedMessageBatch::new(
+            base_offset,
+            messages_per_batch as u32 - 1,
+            last_timestamp,
+            batch_buffer.len() as u32,
+            batch_buffer.freeze(),
+        ));
+        segment.append_batch(batch).await.unwrap();
+        segment.persist_messages().await.unwrap();
+    }
+    segment
+}
+
+async fn reencrypt_segment(segment: &mut segment::Segment, key_ring: &EncryptionKeyRing) -> u64 {
+    let Some(reencryption) = segment.prepare_reencryption() else {
+        return 0;
+    };
+
+    match reencryption.rewrite(key_ring).await.unwrap() {
+        Some(reencrypted_segment) => segment
+            .complete_reencryption(reencrypted_segment)
+            .await
+            .unwrap(),
+        None => 0,
+    }
+}
+
+async fn load_segment(setup: &TestSetup) -> segment::Segment {
+    let mut segment = create_segment(setup);
+    segment.load().await.unwrap();
+    segment
+}
+
+async fn assert_reencrypted_messages(segment: &segment::Segment) {
+    let active_key_ring = EncryptionKeyRing::new(2, Aes256GcmEncryptor::new(&[2; 32]).unwrap());
+    let messages = segment.get_messages(5, 5).await.unwrap();
+    assert_eq!(messages.len(), 5);
+    for (index, message) in messages.iter().enumerate() {
+        assert_eq!(message.offset, 5 + index as u64);
+        assert_eq!(message.checksum, checksum::calculate(&message.payload));
+        let payload = active_key_ring.decrypt(&message.payload).unwrap();
+        assert_eq!(payload, b"test");
+    }
+}
+
+async fn create_segment_with_batches(setup: &TestSetup, batches_count: u64) -> segment::Segment {
+    let mut segment = create_segment(setup);
+    setup
+        .create_partition_directory(segment.stream_id, segment.topic_id, segment.partition_id)
+        .await;
+    segment.persist().await.unwrap();
+    for batch in 0..batches_count {
+        segment
+            .append_batch(Arc::new(create_batch(batch * 10, 10)))
+            .await
+            .unwrap();
+        segment.persist_messages().await.unwrap();
+    }
+    segment
+}
+
+async fn assert_loaded_segment(setup: &TestSetup, messages_count: u64) {
+    let mut segment = create_segment(setup);
+    segment.load().await.unwrap();
+    assert_eq!(segment.current_offset, messages_count - 1);
+    let messages = segment
+        .get_messages(0, messages_count as u32 + 10)
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), messages_count as usize);
+    for (offset, message) in messages.iter().enumerate() {
+        assert_eq!(message.offset, offset as u64);
+    }
+}
+
+fn create_segment(setup: &TestSetup) -> segment::Segment {
+    segment::Segment::create(
+        1,
+        2,
+        3,
+        0,
+        setup.config.clone(),
+        setup.storage.clone(),
+        IggyExpiry::NeverExpire,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+    )
+}
+
+fn create_batch(base_offset: u64, messages_count: u64) -> RetainedMessageBatch {
+    create_batch_with_timestamp(base_offset, messages_count, IggyTimestamp::now())
+}
+
+fn create_batch_with_timestamp(
+    base_offset: u64,
+    messages_count: u64,
+    timestamp: IggyTimestamp,
+) -> RetainedMessageBatch {
+    let mut batch_buffer = BytesMut::new();
+    let mut last_timestamp = 0;
+    for offset in base_offset..base_offset + messages_count {
+        let message = create_message(offset, "test", timestamp);
+        last_timestamp = message.timestamp;
+        let retained_message = RetainedMessage {
+            id: message.id,
+            offset: message.offset,
+            timestamp: message.timestamp,
+            checksum: message.checksum,
+            message_state: message.state,
+            headers: message.headers.map(|headers| headers.to_bytes()),
+            payload: message.payload.clone(),
+        };
+        retained_message.extend(&mut batch_buffer);
+    }
+    RetainedMessageBatch::new(
+        base_offset,
+        messages_count as u32 - 1,
+        last_timestamp,
+        batch_buffer.len() as u32,
+        batch_buffer.freeze(),
+    )
+}
+
+fn get_batches_base_offsets(bytes: &[u8]) -> Vec<u64> {
+    let mut base_offsets = Vec::new();
+    let mut position = 0;
+    while position < bytes.len() {
+        base_offsets.push(u64::from_le_bytes(
+            bytes[position..position + 8].try_into().unwrap(),
+        ));
+        let length = u32::from_le_bytes(bytes[position + 8..position + 12].try_into().unwrap());
+        position += 24 + length as usize;
+    }
+    base_offsets
+}
+
+async fn append_bytes(path: &str, bytes: &[u8]) {
+    let mut file = fs::OpenOptions::new()
+        .append(true)
+        .open(path)
+        .await
+        .unwrap();
+    file.write_all(bytes).await.unwrap();
+}
+
 async fn assert_persisted_segment(partition_path: &str, start_offset: u64) {
     let segment_path = format!("{}/{:0>20}", partition_path, start_offset);
     let log_path = format!("{}.{}", segment_path, LOG_EXTENSION);
@@ -1,6 +1,7 @@
 use crate::streaming::common::test_setup::TestSetup;
 use crate::streaming::create_messages;
 use iggy::identifier::Identifier;
+use iggy::locking::IggySharedMutFn;
 use iggy::messages::poll_messages::PollingStrategy;
 use iggy::messages::send_messages::Partitioning;
 use iggy::utils::expiry::IggyExpiry;
@@ -127,6 +128,7 @@ async fn should_purge_existing_stream_on_disk() {
         let topic = stream
             .get_topic(&Identifier::numeric(topic_id).unwrap())
             .unwrap();
+        let topic = topic.read().await;
         let batch_size = messages.iter().map(|msg| msg.get_size_bytes() as u64).sum();
         topic
             .append_messages(batch_size, Partitioning::partition_id(1), messages)
@@ -1,5 +1,6 @@
 use crate::streaming::common::test_setup::TestSetup;
 use iggy::identifier::Identifier;
+use iggy::locking::IggySharedMutFn;
 use server::configs::server::{DataMaintenanceConfig, PersonalAccessTokenConfig};
 use server::streaming::session::Session;
 use server::streaming::systems::system::System;
@@ -25,8 +26,9 @@ async fn should_initialize_system_and_base_directories() {
         names.push(entry.file_name().into_string().unwrap());
     }
 
-    assert_eq!(names.len(), 3);
+    assert_eq!(names.len(), 4);
     assert!(names.contains(&setup.config.stream.path));
+    assert!(names.contains(&setup.config.audit.path));
 }
 
 #[tokio::test]
@@ -91,6 +93,8 @@ async fn should_delete_persisted_stream() {
     let stream_path = system
         .get_stream(&Identifier::numeric(stream_id).unwrap())
         .unwrap()
+        .read()
+        .await
         .path
         .clone();
 
@@ -2,10 +2,12 @@ use crate::binary::binary_client::BinaryClient;
 use crate::binary::{fail_if_not_authenticated, mapper};
 use crate::client::ConsumerOffsetClient;
 use crate::consumer::Consumer;
+use crate::consumer_offsets::get_consumer_lag::GetConsumerLag;
 use crate::consumer_offsets::get_consumer_offset::GetConsumerOffset;
 use crate::consumer_offsets::store_consumer_offset::StoreConsumerOffset;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
+use crate::models::consumer_lag_info::ConsumerLagInfo;
 use crate::models::consumer_offset_info::ConsumerOffsetInfo;
 
 #[async_trait::async_trait]
@@ -48,4 +50,23 @@ impl<B: BinaryClient> ConsumerOffsetClient for B {
             .await?;
         mapper::map_consumer_offset(response)
     }
+
+    async fn get_consumer_lag(
+        &self,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+    ) -> Result<Vec<ConsumerLagInfo>, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self
+            .send_with_response(&GetConsumerLag {
+                consumer: consumer.clone(),
+                stream_id: stream_id.clone(),
+                topic_id: topic_id.clone(),
+                partition_id,
+            })
+            .await?;
+        mapper::map_consumer_lag(response)
+    }
 }
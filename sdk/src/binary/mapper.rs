@@ -1,14 +1,18 @@
 use crate::bytes_serializable::BytesSerializable;
 use crate::compression::compression_algorithm::CompressionAlgorithm;
 use crate::error::IggyError;
+use crate::models::audit_log::AuditLogEntry;
+use crate::models::backup_info::BackupInfo;
 use crate::models::client_info::{ClientInfo, ClientInfoDetails, ConsumerGroupInfo};
 use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails, ConsumerGroupMember};
+use crate::models::consumer_lag_info::ConsumerLagInfo;
 use crate::models::consumer_offset_info::ConsumerOffsetInfo;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::messages::{MessageState, PolledMessage, PolledMessages};
 use crate::models::partition::Partition;
 use crate::models::permissions::Permissions;
 use crate::models::personal_access_token::{PersonalAccessTokenInfo, RawPersonalAccessToken};
+use crate::models::role_info::{RoleInfo, RoleInfoDetails};
 use crate::models::stats::Stats;
 use crate::models::stream::{Stream, StreamDetails};
 use crate::models::topic::{Topic, TopicDetails};
@@ -26,8 +30,10 @@ const EMPTY_TOPICS: Vec<Topic> = vec![];
 const EMPTY_STREAMS: Vec<Stream> = vec![];
 const EMPTY_CLIENTS: Vec<ClientInfo> = vec![];
 const EMPTY_USERS: Vec<UserInfo> = vec![];
+const EMPTY_ROLES: Vec<RoleInfo> = vec![];
 const EMPTY_PERSONAL_ACCESS_TOKENS: Vec<PersonalAccessTokenInfo> = vec![];
 const EMPTY_CONSUMER_GROUPS: Vec<ConsumerGroup> = vec![];
+const EMPTY_AUDIT_LOG: Vec<AuditLogEntry> = vec![];
 
 pub fn map_stats(payload: Bytes) -> Result<Stats, IggyError> {
     let process_id = u32::from_le_bytes(payload[..4].try_into()?);
@@ -71,6 +77,19 @@ pub fn map_stats(payload: Bytes) -> Result<Stats, IggyError> {
     let kernel_version =
         from_utf8(&payload[current_position + 4..current_position + 4 + kernel_version_length])?
             .to_string();
+    current_position += 4 + kernel_version_length;
+    // The older servers don't send their version.
+    let iggy_server_version = if current_position < payload.len() {
+        let iggy_server_version_length =
+            u32::from_le_bytes(payload[current_position..current_position + 4].try_into()?)
+                as usize;
+        from_utf8(
+            &payload[current_position + 4..current_position + 4 + iggy_server_version_length],
+        )?
+        .to_string()
+    } else {
+        Stats::default().iggy_server_version
+    };
 
     Ok(Stats {
         process_id,
@@ -95,6 +114,7 @@ pub fn map_stats(payload: Bytes) -> Result<Stats, IggyError> {
         os_name,
         os_version,
         kernel_version,
+        iggy_server_version,
     })
 }
 
@@ -109,17 +129,44 @@ pub fn map_consumer_offset(payload: Bytes) -> Result<ConsumerOffsetInfo, IggyErr
     })
 }
 
+pub fn map_consumer_lag(payload: Bytes) -> Result<Vec<ConsumerLagInfo>, IggyError> {
+    const CONSUMER_LAG_SIZE: usize = 37;
+    let mut lags = Vec::with_capacity(payload.len() / CONSUMER_LAG_SIZE);
+    let mut position = 0;
+    while position + CONSUMER_LAG_SIZE <= payload.len() {
+        let partition_id = u32::from_le_bytes(payload[position..position + 4].try_into()?);
+        let current_offset = u64::from_le_bytes(payload[position + 4..position + 12].try_into()?);
+        let has_stored_offset = payload[position + 12] == 1;
+        let stored_offset = u64::from_le_bytes(payload[position + 13..position + 21].try_into()?);
+        let lag = u64::from_le_bytes(payload[position + 21..position + 29].try_into()?);
+        let lag_bytes = u64::from_le_bytes(payload[position + 29..position + 37].try_into()?);
+        lags.push(ConsumerLagInfo {
+            partition_id,
+            current_offset,
+            stored_offset: has_stored_offset.then_some(stored_offset),
+            lag,
+            lag_bytes,
+        });
+        position += CONSUMER_LAG_SIZE;
+    }
+    Ok(lags)
+}
+
 pub fn map_user(payload: Bytes) -> Result<UserInfoDetails, IggyError> {
-    let (user, position) = map_to_user_info(payload.clone(), 0)?;
-    let has_permissions = payload[position];
-    let permissions = if has_permissions == 1 {
-        let permissions_length =
-            u32::from_le_bytes(payload[position + 1..position + 5].try_into()?) as usize;
-        let permissions = payload.slice(position + 5..position + 5 + permissions_length);
-        Some(Permissions::from_bytes(permissions)?)
-    } else {
-        None
-    };
+    let (user, mut position) = map_to_user_info(payload.clone(), 0)?;
+    let (permissions, read_bytes) = map_to_permissions(payload.clone(), position)?;
+    position += read_bytes;
+    let mut roles = Vec::new();
+    if position < payload.len() {
+        let roles_count = u32::from_le_bytes(payload[position..position + 4].try_into()?);
+        position += 4;
+        for _ in 0..roles_count {
+            roles.push(u32::from_le_bytes(
+                payload[position..position + 4].try_into()?,
+            ));
+            position += 4;
+        }
+    }
 
     let user = UserInfoDetails {
         id: user.id,
@@ -127,10 +174,40 @@ pub fn map_user(payload: Bytes) -> Result<UserInfoDetails, IggyError> {
         status: user.status,
         username: user.username,
         permissions,
+        roles,
     };
     Ok(user)
 }
 
+pub fn map_role(payload: Bytes) -> Result<RoleInfoDetails, IggyError> {
+    let (role, position) = map_to_role_info(payload.clone(), 0)?;
+    let (permissions, _) = map_to_permissions(payload, position)?;
+    let role = RoleInfoDetails {
+        id: role.id,
+        created_at: role.created_at,
+        name: role.name,
+        permissions,
+    };
+    Ok(role)
+}
+
+pub fn map_roles(payload: Bytes) -> Result<Vec<RoleInfo>, IggyError> {
+    if payload.is_empty() {
+        return Ok(EMPTY_ROLES);
+    }
+
+    let mut roles = Vec::new();
+    let length = payload.len();
+    let mut position = 0;
+    while position < length {
+        let (role, read_bytes) = map_to_role_info(payload.clone(), position)?;
+        roles.push(role);
+        position += read_bytes;
+    }
+    roles.sort_by(|x, y| x.id.cmp(&y.id));
+    Ok(roles)
+}
+
 pub fn map_users(payload: Bytes) -> Result<Vec<UserInfo>, IggyError> {
     if payload.is_empty() {
         return Ok(EMPTY_USERS);
@@ -229,6 +306,80 @@ pub fn map_clients(payload: Bytes) -> Result<Vec<ClientInfo>, IggyError> {
     Ok(clients)
 }
 
+pub fn map_audit_log(payload: Bytes) -> Result<Vec<AuditLogEntry>, IggyError> {
+    if payload.is_empty() {
+        return Ok(EMPTY_AUDIT_LOG);
+    }
+
+    let mut entries = Vec::new();
+    let length = payload.len();
+    let mut position = 0;
+    while position < length {
+        let timestamp = u64::from_le_bytes(payload[position..position + 8].try_into()?);
+        let user_id = u32::from_le_bytes(payload[position + 8..position + 12].try_into()?);
+        let user_id = if user_id == 0 { None } else { Some(user_id) };
+        position += 12;
+        let (username, read_bytes) = map_to_string(&payload, position, 1)?;
+        let username = if username.is_empty() {
+            None
+        } else {
+            Some(username)
+        };
+        position += read_bytes;
+        let (address, read_bytes) = map_to_string(&payload, position, 1)?;
+        position += read_bytes;
+        let (transport, read_bytes) = map_to_string(&payload, position, 1)?;
+        position += read_bytes;
+        let (action, read_bytes) = map_to_string(&payload, position, 2)?;
+        position += read_bytes;
+        let (result, read_bytes) = map_to_string(&payload, position, 1)?;
+        position += read_bytes;
+        entries.push(AuditLogEntry {
+            timestamp: timestamp.into(),
+            user_id,
+            username,
+            address,
+            transport,
+            action,
+            result,
+        });
+    }
+    Ok(entries)
+}
+
+pub fn map_backup_info(payload: Bytes) -> Result<BackupInfo, IggyError> {
+    let created_at = u64::from_le_bytes(payload[..8].try_into()?);
+    let state_index = u64::from_le_bytes(payload[8..16].try_into()?);
+    let partitions_count = u32::from_le_bytes(payload[16..20].try_into()?);
+    let segments_count = u32::from_le_bytes(payload[20..24].try_into()?);
+    let messages_count = u64::from_le_bytes(payload[24..32].try_into()?);
+    let size = u64::from_le_bytes(payload[32..40].try_into()?);
+    let (name, _) = map_to_string(&payload, 40, 1)?;
+    Ok(BackupInfo {
+        name,
+        created_at: created_at.into(),
+        state_index,
+        partitions_count,
+        segments_count,
+        messages_count,
+        size: size.into(),
+    })
+}
+
+fn map_to_string(
+    payload: &Bytes,
+    position: usize,
+    length_size: usize,
+) -> Result<(String, usize), IggyError> {
+    let length = match length_size {
+        1 => payload[position] as usize,
+        _ => u16::from_le_bytes(payload[position..position + 2].try_into()?) as usize,
+    };
+    let start = position + length_size;
+    let value = from_utf8(&payload[start..start + length])?.to_string();
+    Ok((value, length_size + length))
+}
+
 pub fn map_polled_messages(payload: Bytes) -> Result<PolledMessages, IggyError> {
     if payload.is_empty() {
         return Ok(PolledMessages {
@@ -293,6 +444,111 @@ pub fn map_polled_messages(payload: Bytes) -> Result<PolledMessages, IggyError>
     })
 }
 
+/// The response of the poll message batches command contains the whole batches, in the same format as they're stored in the segment files:
+/// `base_offset (8) | length (4) | last_offset_delta (4) | max_timestamp (8) | messages`, where each message is
+/// `length (4) | offset (8) | state (1) | timestamp (8) | id (16) | checksum (4) | headers_length (4) | headers | payload`.
+/// The first and the last batch might contain the messages outside of the polled range, so the messages before
+/// the first offset are skipped and at most the messages count of them is returned.
+pub fn map_polled_message_batches(payload: Bytes) -> Result<PolledMessages, IggyError> {
+    if payload.is_empty() {
+        return Ok(PolledMessages {
+            messages: EMPTY_MESSAGES,
+            partition_id: 0,
+            current_offset: 0,
+        });
+    }
+
+    let length = payload.len();
+    if length < 24 {
+        return Err(IggyError::InvalidFormat);
+    }
+
+    let partition_id = u32::from_le_bytes(payload[..4].try_into()?);
+    let current_offset = u64::from_le_bytes(payload[4..12].try_into()?);
+    let messages_count = u32::from_le_bytes(payload[12..16].try_into()?) as usize;
+    let first_offset = u64::from_le_bytes(payload[16..24].try_into()?);
+    let mut position = 24;
+    // The count is read from the payload, so the capacity is capped by the number of the smallest (45 bytes) messages fitting in it.
+    let mut messages = Vec::with_capacity(messages_count.min((length - position) / 45));
+    while position < length && messages.len() < messages_count {
+        if position + 24 > length {
+            return Err(IggyError::InvalidFormat);
+        }
+
+        let batch_length = u32::from_le_bytes(payload[position + 8..position + 12].try_into()?);
+        let batch_end = position + 24 + batch_length as usize;
+        if batch_end > length {
+            return Err(IggyError::InvalidFormat);
+        }
+
+        position += 24;
+        while position < batch_end && messages.len() < messages_count {
+            if position + 4 > batch_end {
+                return Err(IggyError::InvalidFormat);
+            }
+
+            let message_length = u32::from_le_bytes(payload[position..position + 4].try_into()?);
+            let message_end = position + 4 + message_length as usize;
+            if message_end > batch_end {
+                return Err(IggyError::InvalidFormat);
+            }
+
+            let message = map_to_polled_message(&payload, position + 4, message_end)?;
+            if message.offset >= first_offset {
+                messages.push(message);
+            }
+            position = message_end;
+        }
+        position = batch_end;
+    }
+
+    Ok(PolledMessages {
+        partition_id,
+        current_offset,
+        messages,
+    })
+}
+
+fn map_to_polled_message(
+    payload: &Bytes,
+    position: usize,
+    end: usize,
+) -> Result<PolledMessage, IggyError> {
+    if position + 41 > end {
+        return Err(IggyError::InvalidFormat);
+    }
+
+    let offset = u64::from_le_bytes(payload[position..position + 8].try_into()?);
+    let state = MessageState::from_code(payload[position + 8])?;
+    let timestamp = u64::from_le_bytes(payload[position + 9..position + 17].try_into()?);
+    let id = u128::from_le_bytes(payload[position + 17..position + 33].try_into()?);
+    let checksum = u32::from_le_bytes(payload[position + 33..position + 37].try_into()?);
+    let headers_length = u32::from_le_bytes(payload[position + 37..position + 41].try_into()?);
+    let headers_end = position + 41 + headers_length as usize;
+    if headers_end > end {
+        return Err(IggyError::InvalidFormat);
+    }
+
+    let headers = if headers_length > 0 {
+        Some(HashMap::from_bytes(
+            payload.slice(position + 41..headers_end),
+        )?)
+    } else {
+        None
+    };
+    let message_payload = payload.slice(headers_end..end);
+    Ok(PolledMessage {
+        offset,
+        timestamp,
+        state,
+        checksum,
+        id,
+        headers,
+        length: message_payload.len() as u32,
+        payload: message_payload,
+    })
+}
+
 pub fn map_streams(payload: Bytes) -> Result<Vec<Stream>, IggyError> {
     if payload.is_empty() {
         return Ok(EMPTY_STREAMS);
@@ -608,6 +864,43 @@ fn map_to_user_info(payload: Bytes, position: usize) -> Result<(UserInfo, usize)
     ))
 }
 
+fn map_to_role_info(payload: Bytes, position: usize) -> Result<(RoleInfo, usize), IggyError> {
+    let id = u32::from_le_bytes(payload[position..position + 4].try_into()?);
+    let created_at = u64::from_le_bytes(payload[position + 4..position + 12].try_into()?);
+    let created_at = created_at.into();
+    let name_length = payload[position + 12];
+    let name =
+        from_utf8(&payload[position + 13..position + 13 + name_length as usize])?.to_string();
+    let read_bytes = 4 + 8 + 1 + name_length as usize;
+
+    Ok((
+        RoleInfo {
+            id,
+            created_at,
+            name,
+        },
+        read_bytes,
+    ))
+}
+
+fn map_to_permissions(
+    payload: Bytes,
+    position: usize,
+) -> Result<(Option<Permissions>, usize), IggyError> {
+    let has_permissions = payload[position];
+    if has_permissions != 1 {
+        return Ok((None, 1));
+    }
+
+    let permissions_length =
+        u32::from_le_bytes(payload[position + 1..position + 5].try_into()?) as usize;
+    let permissions = payload.slice(position + 5..position + 5 + permissions_length);
+    Ok((
+        Some(Permissions::from_bytes(permissions)?),
+        5 + permissions_length,
+    ))
+}
+
 fn map_to_pat_info(
     payload: Bytes,
     position: usize,
@@ -623,3 +916,146 @@ fn map_to_pat_info(
     let read_bytes = 1 + name_length as usize + 8;
     Ok((PersonalAccessTokenInfo { name, expiry_at }, read_bytes))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::header::{HeaderKey, HeaderValue};
+    use bytes::{BufMut, BytesMut};
+    use std::str::FromStr;
+
+    #[test]
+    fn stats_without_server_version_should_be_mapped_with_unknown_version() {
+        let mut payload = BytesMut::new();
+        payload.put_bytes(0, 108);
+        for value in ["hostname", "os_name", "os_version", "kernel_version"] {
+            payload.put_u32_le(value.len() as u32);
+            payload.put_slice(value.as_bytes());
+        }
+
+        let stats = map_stats(payload.freeze()).unwrap();
+
+        assert_eq!(stats.hostname, "hostname");
+        assert_eq!(stats.kernel_version, "kernel_version");
+        assert_eq!(
+            stats.iggy_server_version,
+            Stats::default().iggy_server_version
+        );
+    }
+
+    #[test]
+    fn polled_messages_should_be_mapped_from_batches_skipping_messages_outside_of_range() {
+        let headers = HashMap::from([(
+            HeaderKey::new("key").unwrap(),
+            HeaderValue::from_str("value").unwrap(),
+        )]);
+        let mut payload = BytesMut::new();
+        payload.put_u32_le(7);
+        payload.put_u64_le(9);
+        payload.put_u32_le(3);
+        payload.put_u64_le(2);
+        extend_batch(&mut payload, 0, 3, None);
+        extend_batch(&mut payload, 3, 3, Some(&headers));
+
+        let polled_messages = map_polled_message_batches(payload.freeze()).unwrap();
+
+        assert_eq!(polled_messages.partition_id, 7);
+        assert_eq!(polled_messages.current_offset, 9);
+        assert_eq!(polled_messages.messages.len(), 3);
+        for (message, offset) in polled_messages.messages.iter().zip(2..) {
+            assert_eq!(message.offset, offset);
+            assert_eq!(message.id, offset as u128);
+            assert_eq!(message.state, MessageState::Available);
+            assert_eq!(message.payload, Bytes::from(format!("message {offset}")));
+            assert_eq!(message.length, message.payload.len() as u32);
+        }
+        assert!(polled_messages.messages[0].headers.is_none());
+        assert_eq!(polled_messages.messages[1].headers, Some(headers));
+    }
+
+    #[test]
+    fn polled_messages_should_be_mapped_one_by_one() {
+        let mut payload = BytesMut::new();
+        payload.put_u32_le(7);
+        payload.put_u64_le(9);
+        payload.put_u32_le(2);
+        for offset in 1..3u64 {
+            let message = format!("message {offset}");
+            payload.put_u64_le(offset);
+            payload.put_u8(MessageState::Available.as_code());
+            payload.put_u64_le(offset * 1000);
+            payload.put_u128_le(offset as u128);
+            payload.put_u32_le(0);
+            payload.put_u32_le(0);
+            payload.put_u32_le(message.len() as u32);
+            payload.put_slice(message.as_bytes());
+        }
+
+        let polled_messages = map_polled_messages(payload.freeze()).unwrap();
+
+        assert_eq!(polled_messages.partition_id, 7);
+        assert_eq!(polled_messages.current_offset, 9);
+        assert_eq!(polled_messages.messages.len(), 2);
+        for (message, offset) in polled_messages.messages.iter().zip(1..) {
+            assert_eq!(message.offset, offset);
+            assert_eq!(message.id, offset as u128);
+            assert_eq!(message.payload, Bytes::from(format!("message {offset}")));
+        }
+    }
+
+    #[test]
+    fn truncated_header_should_not_be_mapped() {
+        let mut payload = BytesMut::new();
+        payload.put_u32_le(1);
+        payload.put_u64_le(2);
+        payload.put_u32_le(u32::MAX);
+
+        let result = map_polled_message_batches(payload.freeze());
+
+        assert!(matches!(result, Err(IggyError::InvalidFormat)));
+    }
+
+    #[test]
+    fn truncated_batch_should_not_be_mapped() {
+        let mut payload = BytesMut::new();
+        payload.put_u32_le(1);
+        payload.put_u64_le(2);
+        payload.put_u32_le(3);
+        payload.put_u64_le(0);
+        extend_batch(&mut payload, 0, 3, None);
+        payload.truncate(payload.len() - 1);
+
+        let result = map_polled_message_batches(payload.freeze());
+
+        assert!(matches!(result, Err(IggyError::InvalidFormat)));
+    }
+
+    fn extend_batch(
+        bytes: &mut BytesMut,
+        base_offset: u64,
+        messages_count: u64,
+        headers: Option<&HashMap<HeaderKey, HeaderValue>>,
+    ) {
+        let mut messages = BytesMut::new();
+        for offset in base_offset..base_offset + messages_count {
+            let payload = format!("message {offset}");
+            let headers = headers
+                .map(|headers| headers.to_bytes())
+                .unwrap_or_default();
+            messages.put_u32_le(41 + headers.len() as u32 + payload.len() as u32);
+            messages.put_u64_le(offset);
+            messages.put_u8(MessageState::Available.as_code());
+            messages.put_u64_le(offset * 1000);
+            messages.put_u128_le(offset as u128);
+            messages.put_u32_le(0);
+            messages.put_u32_le(headers.len() as u32);
+            messages.put_slice(&headers);
+            messages.put_slice(payload.as_bytes());
+        }
+        bytes.put_u64_le(base_offset);
+        bytes.put_u32_le(messages.len() as u32);
+        bytes.put_u32_le(messages_count as u32 - 1);
+        bytes.put_u64_le((base_offset + messages_count - 1) * 1000);
+        bytes.put_slice(&messages);
+    }
+}
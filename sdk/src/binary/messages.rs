@@ -1,7 +1,7 @@
 use crate::binary::binary_client::BinaryClient;
 use crate::binary::{fail_if_not_authenticated, mapper};
 use crate::client::MessageClient;
-use crate::command::{POLL_MESSAGES_CODE, SEND_MESSAGES_CODE};
+use crate::command::{POLL_MESSAGES_CODE, POLL_MESSAGE_BATCHES_CODE, SEND_MESSAGES_CODE};
 use crate::consumer::Consumer;
 use crate::error::IggyError;
 use crate::identifier::Identifier;
@@ -23,21 +23,31 @@ impl<B: BinaryClient> MessageClient for B {
         auto_commit: bool,
     ) -> Result<PolledMessages, IggyError> {
         fail_if_not_authenticated(self).await?;
-        let response = self
-            .send_raw_with_response(
-                POLL_MESSAGES_CODE,
-                poll_messages::as_bytes(
-                    stream_id,
-                    topic_id,
-                    partition_id,
-                    consumer,
-                    strategy,
-                    count,
-                    auto_commit,
-                ),
-            )
-            .await?;
-        mapper::map_polled_messages(response)
+        let payload = poll_messages::as_bytes(
+            stream_id,
+            topic_id,
+            partition_id,
+            consumer,
+            strategy,
+            count,
+            auto_commit,
+        );
+        match self
+            .send_raw_with_response(POLL_MESSAGE_BATCHES_CODE, payload.clone())
+            .await
+        {
+            Ok(response) => mapper::map_polled_message_batches(response),
+            // The servers older than the poll message batches command reject it, the messages are then polled one by one.
+            Err(IggyError::InvalidResponse(status, _, _))
+                if status == IggyError::InvalidCommand.as_code() =>
+            {
+                let response = self
+                    .send_raw_with_response(POLL_MESSAGES_CODE, payload)
+                    .await?;
+                mapper::map_polled_messages(response)
+            }
+            Err(error) => Err(error),
+        }
     }
 
     async fn send_messages(
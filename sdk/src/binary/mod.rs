@@ -17,6 +17,8 @@ pub mod partitions;
 #[allow(deprecated)]
 pub mod personal_access_tokens;
 #[allow(deprecated)]
+pub mod roles;
+#[allow(deprecated)]
 pub mod streams;
 #[allow(deprecated)]
 pub mod system;
@@ -2,8 +2,13 @@ use crate::binary::binary_client::BinaryClient;
 use crate::binary::{fail_if_not_authenticated, mapper};
 use crate::client::SystemClient;
 use crate::error::IggyError;
+use crate::models::audit_log::AuditLogEntry;
+use crate::models::backup_info::BackupInfo;
 use crate::models::client_info::{ClientInfo, ClientInfoDetails};
 use crate::models::stats::Stats;
+use crate::models::user_info::UserId;
+use crate::system::create_backup::CreateBackup;
+use crate::system::get_audit_log::GetAuditLog;
 use crate::system::get_client::GetClient;
 use crate::system::get_clients::GetClients;
 use crate::system::get_me::GetMe;
@@ -36,6 +41,33 @@ impl<B: BinaryClient> SystemClient for B {
         mapper::map_clients(response)
     }
 
+    async fn get_audit_log(
+        &self,
+        count: u32,
+        user_id: Option<UserId>,
+    ) -> Result<Vec<AuditLogEntry>, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self
+            .send_with_response(&GetAuditLog { count, user_id })
+            .await?;
+        mapper::map_audit_log(response)
+    }
+
+    async fn create_backup(
+        &self,
+        name: Option<&str>,
+        archive: bool,
+    ) -> Result<BackupInfo, IggyError> {
+        fail_if_not_authenticated(self).await?;
+        let response = self
+            .send_with_response(&CreateBackup {
+                name: name.map(|name| name.to_string()),
+                archive,
+            })
+            .await?;
+        mapper::map_backup_info(response)
+    }
+
     async fn ping(&self) -> Result<(), IggyError> {
         self.send_with_response(&Ping {}).await?;
         Ok(())
@@ -5,6 +5,7 @@ use crate::error::IggyError;
 use crate::identifier::Identifier;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::permissions::Permissions;
+use crate::models::role_info::RoleId;
 use crate::models::user_info::{UserInfo, UserInfoDetails};
 use crate::models::user_status::UserStatus;
 use crate::users::change_password::ChangePassword;
@@ -14,7 +15,9 @@ use crate::users::get_user::GetUser;
 use crate::users::get_users::GetUsers;
 use crate::users::login_user::LoginUser;
 use crate::users::logout_user::LogoutUser;
+use crate::users::unlock_user::UnlockUser;
 use crate::users::update_permissions::UpdatePermissions;
+use crate::users::update_roles::UpdateRoles;
 use crate::users::update_user::UpdateUser;
 
 #[async_trait::async_trait]
@@ -92,6 +95,29 @@ impl<B: BinaryClient> UserClient for B {
         Ok(())
     }
 
+    async fn update_roles(
+        &self,
+        user_id: &Identifier,
+        roles: Vec<RoleId>,
+    ) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&UpdateRoles {
+            user_id: user_id.clone(),
+            roles,
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn unlock_user(&self, user_id: &Identifier) -> Result<(), IggyError> {
+        fail_if_not_authenticated(self).await?;
+        self.send_with_response(&UnlockUser {
+            user_id: user_id.clone(),
+        })
+        .await?;
+        Ok(())
+    }
+
     async fn change_password(
         &self,
         user_id: &Identifier,
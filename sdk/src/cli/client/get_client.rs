@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::system::get_client::GetClient;
 use anyhow::Context;
@@ -24,7 +25,11 @@ impl CliCommand for GetClientCmd {
         format!("get client with ID: {}", self.get_client.client_id)
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let client_details = client
             .get_client(self.get_client.client_id)
             .await
@@ -35,6 +40,10 @@ impl CliCommand for GetClientCmd {
                 )
             })?;
 
+        if !output.is_table() {
+            return print_output(output, &client_details);
+        }
+
         let mut table = Table::new();
 
         table.set_header(vec!["Property", "Value"]);
@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::system::get_clients::GetClients;
 use anyhow::Context;
@@ -44,12 +45,20 @@ impl CliCommand for GetClientsCmd {
         format!("list clients in {mode} mode")
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let clients = client
             .get_clients()
             .await
             .with_context(|| String::from("Problem getting list of clients"))?;
 
+        if !output.is_table() {
+            return print_output(output, &clients);
+        }
+
         if clients.is_empty() {
             event!(target: PRINT_TARGET, Level::INFO, "No clients found!");
             return Ok(());
@@ -1,10 +1,10 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::consumer_groups::create_consumer_group::CreateConsumerGroup;
 use crate::identifier::Identifier;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct CreateConsumerGroupCmd {
     create_consumer_group: CreateConsumerGroup,
@@ -47,7 +47,11 @@ impl CliCommand for CreateConsumerGroupCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .create_consumer_group(&self.create_consumer_group.stream_id, &self.create_consumer_group.topic_id, &self.create_consumer_group.name, self.create_consumer_group.group_id)
             .await
@@ -58,13 +62,16 @@ impl CliCommand for CreateConsumerGroupCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Consumer group: {}, name: {} created for topic with ID: {} and stream with ID: {}",
-            self.get_group_id_info(),
-            self.create_consumer_group.name,
-            self.create_consumer_group.topic_id,
-            self.create_consumer_group.stream_id,
-        );
+        print_message(
+            output,
+            &format!(
+                "Consumer group: {}, name: {} created for topic with ID: {} and stream with ID: {}",
+                self.get_group_id_info(),
+                self.create_consumer_group.name,
+                self.create_consumer_group.topic_id,
+                self.create_consumer_group.stream_id
+            ),
+        )?;
 
         Ok(())
     }
@@ -1,10 +1,10 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::consumer_groups::delete_consumer_group::DeleteConsumerGroup;
 use crate::identifier::Identifier;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct DeleteConsumerGroupCmd {
     delete_consumer_group: DeleteConsumerGroup,
@@ -33,7 +33,11 @@ impl CliCommand for DeleteConsumerGroupCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .delete_consumer_group(&self.delete_consumer_group.stream_id, &self.delete_consumer_group.topic_id, &self.delete_consumer_group.group_id)
             .await
@@ -44,12 +48,15 @@ impl CliCommand for DeleteConsumerGroupCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Consumer group with ID: {} deleted for topic with ID: {} and stream with ID: {}",
-            self.delete_consumer_group.group_id,
-            self.delete_consumer_group.topic_id,
-            self.delete_consumer_group.stream_id,
-        );
+        print_message(
+            output,
+            &format!(
+                "Consumer group with ID: {} deleted for topic with ID: {} and stream with ID: {}",
+                self.delete_consumer_group.group_id,
+                self.delete_consumer_group.topic_id,
+                self.delete_consumer_group.stream_id
+            ),
+        )?;
 
         Ok(())
     }
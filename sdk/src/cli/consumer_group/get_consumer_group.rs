@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::consumer_groups::get_consumer_group::GetConsumerGroup;
 use crate::identifier::Identifier;
@@ -34,7 +35,11 @@ impl CliCommand for GetConsumerGroupCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let consumer_group = client
             .get_consumer_group(&self.get_consumer_group.stream_id, &self.get_consumer_group.topic_id, &self.get_consumer_group.group_id)
             .await
@@ -45,6 +50,10 @@ impl CliCommand for GetConsumerGroupCmd {
                 )
             })?;
 
+        if !output.is_table() {
+            return print_output(output, &consumer_group);
+        }
+
         let mut table = Table::new();
 
         table.set_header(vec!["Property", "Value"]);
@@ -0,0 +1,112 @@
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
+use crate::client::Client;
+use crate::consumer::Consumer;
+use crate::consumer_offsets::get_consumer_lag::GetConsumerLag;
+use crate::identifier::Identifier;
+use crate::utils::byte_size::IggyByteSize;
+use anyhow::Context;
+use async_trait::async_trait;
+use comfy_table::Table;
+use tracing::{event, Level};
+
+pub struct GetConsumerGroupLagCmd {
+    get_consumer_lag: GetConsumerLag,
+}
+
+impl GetConsumerGroupLagCmd {
+    pub fn new(
+        stream_id: Identifier,
+        topic_id: Identifier,
+        consumer_group_id: Identifier,
+        partition_id: Option<u32>,
+    ) -> Self {
+        Self {
+            get_consumer_lag: GetConsumerLag {
+                consumer: Consumer::group(consumer_group_id),
+                stream_id,
+                topic_id,
+                partition_id,
+            },
+        }
+    }
+
+    fn get_partition_info(&self) -> String {
+        match self.get_consumer_lag.partition_id {
+            Some(partition_id) => format!("partition with ID: {partition_id}"),
+            None => "all partitions".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for GetConsumerGroupLagCmd {
+    fn explain(&self) -> String {
+        format!(
+            "get lag of consumer group with ID: {} for {} of topic with ID: {} and stream with ID: {}",
+            self.get_consumer_lag.consumer.id,
+            self.get_partition_info(),
+            self.get_consumer_lag.topic_id,
+            self.get_consumer_lag.stream_id,
+        )
+    }
+
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
+        let lags = client
+            .get_consumer_lag(
+                &self.get_consumer_lag.consumer,
+                &self.get_consumer_lag.stream_id,
+                &self.get_consumer_lag.topic_id,
+                self.get_consumer_lag.partition_id,
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem getting lag of consumer group with ID: {} for {} of topic with ID: {} and stream with ID: {}",
+                    self.get_consumer_lag.consumer.id,
+                    self.get_partition_info(),
+                    self.get_consumer_lag.topic_id,
+                    self.get_consumer_lag.stream_id
+                )
+            })?;
+
+        if !output.is_table() {
+            return print_output(output, &lags);
+        }
+
+        let mut table = Table::new();
+        table.set_header(vec![
+            "Partition ID",
+            "Current Offset",
+            "Stored Offset",
+            "Lag",
+            "Lag Size",
+        ]);
+        lags.iter().for_each(|lag| {
+            table.add_row(vec![
+                format!("{}", lag.partition_id),
+                format!("{}", lag.current_offset),
+                lag.stored_offset
+                    .map(|offset| format!("{offset}"))
+                    .unwrap_or_else(|| "-".to_string()),
+                format!("{}", lag.lag),
+                IggyByteSize::from(lag.lag_bytes).as_human_string(),
+            ]);
+        });
+        table.add_row(vec![
+            "Total".to_string(),
+            "".to_string(),
+            "".to_string(),
+            format!("{}", lags.iter().map(|lag| lag.lag).sum::<u64>()),
+            IggyByteSize::from(lags.iter().map(|lag| lag.lag_bytes).sum::<u64>()).as_human_string(),
+        ]);
+
+        event!(target: PRINT_TARGET, Level::INFO, "{table}");
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::consumer_groups::get_consumer_groups::GetConsumerGroups;
 use crate::identifier::Identifier;
@@ -54,7 +55,11 @@ impl CliCommand for GetConsumerGroupsCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let consumer_groups = client
             .get_consumer_groups(
                 &self.get_consumer_groups.stream_id,
@@ -68,6 +73,10 @@ impl CliCommand for GetConsumerGroupsCmd {
                 )
             })?;
 
+        if !output.is_table() {
+            return print_output(output, &consumer_groups);
+        }
+
         match self.output {
             GetConsumerGroupsOutput::Table => {
                 let mut table = Table::new();
@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::consumer::{Consumer, ConsumerKind};
 use crate::consumer_offsets::get_consumer_offset::GetConsumerOffset;
@@ -57,7 +58,11 @@ impl CliCommand for GetConsumerOffsetCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let consumer_offset = client.get_consumer_offset(&self.get_consumer_offset.consumer, &self.get_consumer_offset.stream_id, &self.get_consumer_offset.topic_id, self.get_consumer_offset.partition_id).await.with_context(|| {
             format!(
                 "Problem getting consumer offset for {} for stream with ID: {} and topic with ID: {} and partition with ID: {}",
@@ -65,6 +70,10 @@ impl CliCommand for GetConsumerOffsetCmd {
             )
         })?;
 
+        if !output.is_table() {
+            return print_output(output, &consumer_offset);
+        }
+
         let mut table = Table::new();
 
         table.set_header(vec!["Property", "Value"]);
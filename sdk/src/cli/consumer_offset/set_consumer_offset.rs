@@ -1,11 +1,11 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::consumer::{Consumer, ConsumerKind};
 use crate::consumer_offsets::store_consumer_offset::StoreConsumerOffset;
 use crate::identifier::Identifier;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct SetConsumerOffsetCmd {
     set_consumer_offset: StoreConsumerOffset,
@@ -47,7 +47,11 @@ impl CliCommand for SetConsumerOffsetCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .store_consumer_offset(&self.set_consumer_offset.consumer, &self.set_consumer_offset.stream_id, &self.set_consumer_offset.topic_id, self.set_consumer_offset.partition_id, self.set_consumer_offset.offset)
             .await
@@ -58,14 +62,14 @@ impl CliCommand for SetConsumerOffsetCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Consumer offset for consumer with ID: {} for stream with ID: {} and topic with ID: {} and partition with ID: {} set to {}",
-            self.set_consumer_offset.consumer.id,
+        print_message(
+output,
+&format!("Consumer offset for consumer with ID: {} for stream with ID: {} and topic with ID: {} and partition with ID: {} set to {}", self.set_consumer_offset.consumer.id,
             self.set_consumer_offset.stream_id,
             self.set_consumer_offset.topic_id,
             self.set_consumer_offset.partition_id.unwrap(),
-            self.set_consumer_offset.offset,
-        );
+            self.set_consumer_offset.offset),
+)?;
 
         Ok(())
     }
@@ -1,12 +1,21 @@
 use async_trait::async_trait;
 use comfy_table::Table;
+use serde::Serialize;
 use tracing::{event, Level};
 
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 
 use super::common::ContextManager;
 
+/// The context printed in the machine-readable output, the credentials are never included.
+#[derive(Serialize)]
+struct ContextInfo<'a> {
+    name: &'a str,
+    active: bool,
+}
+
 pub enum GetContextsOutput {
     Table,
     List,
@@ -56,11 +65,27 @@ impl CliCommand for GetContextsCmd {
         false
     }
 
-    async fn execute_cmd(&mut self, _client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        _client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let mut context_mgr = ContextManager::default();
         let contexts_map = context_mgr.get_contexts().await?;
         let active_context_key = context_mgr.get_active_context_key().await?;
 
+        if !output.is_table() {
+            let mut contexts = contexts_map
+                .keys()
+                .map(|name| ContextInfo {
+                    name,
+                    active: name.eq(&active_context_key),
+                })
+                .collect::<Vec<_>>();
+            contexts.sort_by_key(|context| context.name);
+            return print_output(output, &contexts);
+        }
+
         match self.output {
             GetContextsOutput::Table => {
                 let mut table = Table::new();
@@ -1,7 +1,7 @@
 use async_trait::async_trait;
-use tracing::{event, Level};
 
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 
 use super::common::{ContextManager, DEFAULT_CONTEXT_NAME};
@@ -39,14 +39,21 @@ impl CliCommand for UseContextCmd {
         false
     }
 
-    async fn execute_cmd(&mut self, _client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        _client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let mut context_mgr = ContextManager::default();
 
         context_mgr
             .set_active_context_key(&self.context_name)
             .await?;
 
-        event!(target: PRINT_TARGET, Level::INFO, "active context set to '{}'", self.context_name);
+        print_message(
+            output,
+            &format!("active context set to '{}'", self.context_name),
+        )?;
 
         return Ok(());
     }
@@ -0,0 +1,208 @@
+use crate::cli::message::message_file::{ExportedMessage, MessageFileFormat, MessageFileWriter};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
+use crate::client::Client;
+use crate::consumer::Consumer;
+use crate::identifier::Identifier;
+use crate::messages::poll_messages::PollingStrategy;
+use crate::models::messages::PolledMessage;
+use crate::utils::timestamp::IggyTimestamp;
+use anyhow::Context;
+use async_trait::async_trait;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+/// The range of the exported messages, the bounds are inclusive.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ExportRange {
+    /// All the messages stored in the partitions.
+    #[default]
+    All,
+    /// The messages with the offsets within the given range.
+    Offsets { from: Option<u64>, to: Option<u64> },
+    /// The messages appended within the given time range.
+    Timestamps {
+        from: Option<IggyTimestamp>,
+        to: Option<IggyTimestamp>,
+    },
+}
+
+impl ExportRange {
+    fn start(&self) -> PollingStrategy {
+        match self {
+            ExportRange::Offsets {
+                from: Some(offset), ..
+            } => PollingStrategy::offset(*offset),
+            ExportRange::Timestamps {
+                from: Some(timestamp),
+                ..
+            } => PollingStrategy::timestamp(*timestamp),
+            _ => PollingStrategy::offset(0),
+        }
+    }
+
+    /// Checks if the message is beyond the end of the range, as the messages are ordered
+    /// by both offset and timestamp, all the following messages are beyond the range too.
+    fn is_past_end(&self, message: &PolledMessage) -> bool {
+        match self {
+            ExportRange::Offsets { to: Some(to), .. } => message.offset > *to,
+            ExportRange::Timestamps { to: Some(to), .. } => message.timestamp > to.as_micros(),
+            _ => false,
+        }
+    }
+}
+
+pub struct ExportMessagesCmd {
+    stream_id: Identifier,
+    topic_id: Identifier,
+    partition_id: Option<u32>,
+    range: ExportRange,
+    file: PathBuf,
+    format: MessageFileFormat,
+    message_count: u32,
+}
+
+impl ExportMessagesCmd {
+    pub fn new(
+        stream_id: Identifier,
+        topic_id: Identifier,
+        partition_id: Option<u32>,
+        range: ExportRange,
+        file: PathBuf,
+        format: MessageFileFormat,
+        message_count: u32,
+    ) -> Self {
+        Self {
+            stream_id,
+            topic_id,
+            partition_id,
+            range,
+            file,
+            format,
+            message_count,
+        }
+    }
+
+    fn get_partition_info(&self) -> String {
+        match self.partition_id {
+            Some(partition_id) => format!("partition with ID: {partition_id}"),
+            None => "all partitions".to_owned(),
+        }
+    }
+
+    /// Exports the messages from the single partition, returns the number of exported messages.
+    async fn export_partition<W: Write>(
+        &self,
+        client: &dyn Client,
+        partition_id: u32,
+        writer: &mut MessageFileWriter<W>,
+    ) -> anyhow::Result<u64> {
+        let consumer = Consumer::default();
+        let mut strategy = self.range.start();
+        let mut exported = 0;
+        loop {
+            let polled = client
+                .poll_messages(
+                    &self.stream_id,
+                    &self.topic_id,
+                    Some(partition_id),
+                    &consumer,
+                    &strategy,
+                    self.message_count,
+                    false,
+                )
+                .await
+                .with_context(|| {
+                    format!(
+                        "Problem polling messages from partition with ID: {} of topic with ID: {} and stream with ID: {}",
+                        partition_id, self.topic_id, self.stream_id
+                    )
+                })?;
+
+            let Some(last) = polled.messages.last() else {
+                return Ok(exported);
+            };
+            for message in &polled.messages {
+                if self.range.is_past_end(message) {
+                    return Ok(exported);
+                }
+                writer.write(&ExportedMessage::from_polled(partition_id, message))?;
+                exported += 1;
+            }
+            if last.offset >= polled.current_offset {
+                return Ok(exported);
+            }
+            strategy = PollingStrategy::offset(last.offset + 1);
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for ExportMessagesCmd {
+    fn explain(&self) -> String {
+        format!(
+            "export messages from {} of topic with ID: {} and stream with ID: {} to file: {}",
+            self.get_partition_info(),
+            self.topic_id,
+            self.stream_id,
+            self.file.display()
+        )
+    }
+
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
+        let topic = client
+            .get_topic(&self.stream_id, &self.topic_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem getting topic with ID: {} and stream with ID: {}",
+                    self.topic_id, self.stream_id
+                )
+            })?;
+        let partitions = topic
+            .partitions
+            .iter()
+            .filter(|partition| self.partition_id.is_none_or(|id| id == partition.id))
+            .map(|partition| partition.id)
+            .collect::<Vec<_>>();
+        if partitions.is_empty() {
+            anyhow::bail!(
+                "Partition with ID: {} was not found in topic with ID: {}",
+                self.partition_id.unwrap_or_default(),
+                self.topic_id
+            );
+        }
+
+        let file = File::create(&self.file)
+            .with_context(|| format!("Problem creating file: {}", self.file.display()))?;
+        let mut writer = MessageFileWriter::new(BufWriter::new(file), self.format)?;
+        let mut exported = 0;
+        for partition_id in partitions {
+            exported += self
+                .export_partition(client, partition_id, &mut writer)
+                .await?;
+        }
+        writer
+            .finish()
+            .with_context(|| format!("Problem writing file: {}", self.file.display()))?;
+
+        print_message(
+            output,
+            &format!(
+                "Exported {exported} messages from {} of topic with ID: {} and stream with ID: {} to file: {} in {} format",
+                self.get_partition_info(),
+                self.topic_id,
+                self.stream_id,
+                self.file.display(),
+                self.format
+            ),
+        )?;
+
+        Ok(())
+    }
+}
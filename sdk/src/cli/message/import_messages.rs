@@ -0,0 +1,137 @@
+use crate::cli::message::message_file::{ExportedMessage, MessageFileReader};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
+use crate::client::Client;
+use crate::identifier::Identifier;
+use crate::messages::send_messages::{Message, Partitioning};
+use anyhow::Context;
+use async_trait::async_trait;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+pub struct ImportMessagesCmd {
+    stream_id: Identifier,
+    topic_id: Identifier,
+    partition_id: Option<u32>,
+    file: PathBuf,
+    batch_size: u32,
+}
+
+impl ImportMessagesCmd {
+    pub fn new(
+        stream_id: Identifier,
+        topic_id: Identifier,
+        partition_id: Option<u32>,
+        file: PathBuf,
+        batch_size: u32,
+    ) -> Self {
+        Self {
+            stream_id,
+            topic_id,
+            partition_id,
+            file,
+            batch_size,
+        }
+    }
+
+    fn get_partition_info(&self) -> String {
+        match self.partition_id {
+            Some(partition_id) => format!("partition with ID: {partition_id}"),
+            None => "original partitions".to_owned(),
+        }
+    }
+
+    async fn send_batch(
+        &self,
+        client: &dyn Client,
+        partition_id: u32,
+        messages: &mut Vec<Message>,
+    ) -> anyhow::Result<()> {
+        if messages.is_empty() {
+            return Ok(());
+        }
+
+        client
+            .send_messages(
+                &self.stream_id,
+                &self.topic_id,
+                &Partitioning::partition_id(partition_id),
+                messages,
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem sending messages to partition with ID: {} of topic with ID: {} and stream with ID: {}",
+                    partition_id, self.topic_id, self.stream_id
+                )
+            })?;
+        messages.clear();
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CliCommand for ImportMessagesCmd {
+    fn explain(&self) -> String {
+        format!(
+            "import messages from file: {} to {} of topic with ID: {} and stream with ID: {}",
+            self.file.display(),
+            self.get_partition_info(),
+            self.topic_id,
+            self.stream_id
+        )
+    }
+
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
+        let file = File::open(&self.file)
+            .with_context(|| format!("Problem opening file: {}", self.file.display()))?;
+        let mut reader = MessageFileReader::new(BufReader::new(file))?;
+
+        // The consecutive messages of the same partition are sent in batches,
+        // so the order of the messages within each partition is preserved.
+        let mut batch = Vec::new();
+        let mut batch_partition_id = 0;
+        let mut imported = 0;
+        while let Some(message) = reader
+            .read()
+            .with_context(|| format!("Problem reading file: {}", self.file.display()))?
+        {
+            let ExportedMessage {
+                partition_id,
+                id,
+                headers,
+                payload,
+                ..
+            } = message;
+            let partition_id = self.partition_id.unwrap_or(partition_id);
+            if partition_id != batch_partition_id || batch.len() >= self.batch_size as usize {
+                self.send_batch(client, batch_partition_id, &mut batch)
+                    .await?;
+                batch_partition_id = partition_id;
+            }
+            batch.push(Message::new(Some(id), payload, headers));
+            imported += 1;
+        }
+        self.send_batch(client, batch_partition_id, &mut batch)
+            .await?;
+
+        print_message(
+            output,
+            &format!(
+                "Imported {imported} messages from file: {} in {} format to {} of topic with ID: {} and stream with ID: {}",
+                self.file.display(),
+                reader.format(),
+                self.get_partition_info(),
+                self.topic_id,
+                self.stream_id
+            ),
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,337 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::error::IggyError;
+use crate::models::header::{HeaderKey, HeaderValue};
+use crate::models::messages::PolledMessage;
+use anyhow::Context;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use serde_with::base64::Base64;
+use serde_with::{serde_as, DisplayFromStr};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::{BufRead, Write};
+
+/// The magic bytes at the beginning of the binary file, the last byte is the version of the format.
+const BINARY_FILE_MAGIC: &[u8; 8] = b"IGGYMSG\x01";
+/// Partition ID + Offset + Timestamp + ID + Headers length + Payload length
+const EXPORTED_MESSAGE_METADATA: usize = 4 + 8 + 8 + 16 + 4 + 4;
+
+/// The format of the file with the exported messages.
+/// - `Jsonl`: each line is the JSON object with the message, the ID is written as a string
+///   and the payload and the header values are encoded as Base64.
+/// - `Binary`: the magic bytes followed by the records prefixed with their length (u32, little endian),
+///   each record is encoded in the same way as the messages in the binary protocol.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum MessageFileFormat {
+    #[default]
+    Jsonl,
+    Binary,
+}
+
+impl Display for MessageFileFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessageFileFormat::Jsonl => write!(f, "jsonl"),
+            MessageFileFormat::Binary => write!(f, "binary"),
+        }
+    }
+}
+
+/// The message exported to the file along with the partition it was polled from.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedMessage {
+    /// The identifier of the partition.
+    pub partition_id: u32,
+    /// The offset of the message.
+    pub offset: u64,
+    /// The timestamp of the message.
+    pub timestamp: u64,
+    /// The identifier of the message.
+    #[serde_as(as = "DisplayFromStr")]
+    pub id: u128,
+    /// The optional headers of the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub headers: Option<HashMap<HeaderKey, HeaderValue>>,
+    /// The binary payload of the message.
+    #[serde_as(as = "Base64")]
+    pub payload: Bytes,
+}
+
+impl ExportedMessage {
+    pub fn from_polled(partition_id: u32, message: &PolledMessage) -> Self {
+        ExportedMessage {
+            partition_id,
+            offset: message.offset,
+            timestamp: message.timestamp,
+            id: message.id,
+            headers: message.headers.clone(),
+            payload: message.payload.clone(),
+        }
+    }
+}
+
+impl BytesSerializable for ExportedMessage {
+    fn to_bytes(&self) -> Bytes {
+        let headers = match &self.headers {
+            Some(headers) => headers.to_bytes(),
+            None => Bytes::new(),
+        };
+        let mut bytes =
+            BytesMut::with_capacity(EXPORTED_MESSAGE_METADATA + headers.len() + self.payload.len());
+        bytes.put_u32_le(self.partition_id);
+        bytes.put_u64_le(self.offset);
+        bytes.put_u64_le(self.timestamp);
+        bytes.put_u128_le(self.id);
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u32_le(headers.len() as u32);
+        bytes.put_slice(&headers);
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u32_le(self.payload.len() as u32);
+        bytes.put_slice(&self.payload);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError> {
+        if bytes.len() < EXPORTED_MESSAGE_METADATA {
+            return Err(IggyError::InvalidFormat);
+        }
+
+        let partition_id = u32::from_le_bytes(bytes[..4].try_into()?);
+        let offset = u64::from_le_bytes(bytes[4..12].try_into()?);
+        let timestamp = u64::from_le_bytes(bytes[12..20].try_into()?);
+        let id = u128::from_le_bytes(bytes[20..36].try_into()?);
+        let headers_length = u32::from_le_bytes(bytes[36..40].try_into()?) as usize;
+        let mut position = 40;
+        if bytes.len() < EXPORTED_MESSAGE_METADATA + headers_length {
+            return Err(IggyError::InvalidFormat);
+        }
+        let headers = match headers_length {
+            0 => None,
+            _ => Some(HashMap::from_bytes(
+                bytes.slice(position..position + headers_length),
+            )?),
+        };
+        position += headers_length;
+        let payload_length = u32::from_le_bytes(bytes[position..position + 4].try_into()?) as usize;
+        position += 4;
+        if bytes.len() != position + payload_length {
+            return Err(IggyError::InvalidFormat);
+        }
+
+        Ok(ExportedMessage {
+            partition_id,
+            offset,
+            timestamp,
+            id,
+            headers,
+            payload: bytes.slice(position..),
+        })
+    }
+}
+
+/// Writes the exported messages to the file in the given format.
+pub struct MessageFileWriter<W: Write> {
+    writer: W,
+    format: MessageFileFormat,
+}
+
+impl<W: Write> MessageFileWriter<W> {
+    pub fn new(mut writer: W, format: MessageFileFormat) -> anyhow::Result<Self> {
+        if format == MessageFileFormat::Binary {
+            writer
+                .write_all(BINARY_FILE_MAGIC)
+                .with_context(|| "Problem writing file header".to_owned())?;
+        }
+
+        Ok(Self { writer, format })
+    }
+
+    pub fn write(&mut self, message: &ExportedMessage) -> anyhow::Result<()> {
+        match self.format {
+            MessageFileFormat::Jsonl => {
+                serde_json::to_writer(&mut self.writer, message)
+                    .with_context(|| "Problem serializing message to JSON".to_owned())?;
+                self.writer.write_all(b"\n")?;
+            }
+            MessageFileFormat::Binary => {
+                let bytes = message.to_bytes();
+                #[allow(clippy::cast_possible_truncation)]
+                self.writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+                self.writer.write_all(&bytes)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Flushes the buffered data and returns the underlying writer.
+    pub fn finish(mut self) -> anyhow::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads the exported messages from the file, the format is detected based on the magic bytes
+/// at the beginning of the file, otherwise the file is read as JSON Lines.
+pub struct MessageFileReader<R: BufRead> {
+    reader: R,
+    format: MessageFileFormat,
+    line: String,
+    line_number: usize,
+}
+
+impl<R: BufRead> MessageFileReader<R> {
+    pub fn new(mut reader: R) -> anyhow::Result<Self> {
+        let buffer = reader
+            .fill_buf()
+            .with_context(|| "Problem reading file header".to_owned())?;
+        let format = if buffer.starts_with(BINARY_FILE_MAGIC) {
+            reader.consume(BINARY_FILE_MAGIC.len());
+            MessageFileFormat::Binary
+        } else {
+            MessageFileFormat::Jsonl
+        };
+
+        Ok(Self {
+            reader,
+            format,
+            line: String::new(),
+            line_number: 0,
+        })
+    }
+
+    pub fn format(&self) -> MessageFileFormat {
+        self.format
+    }
+
+    /// Reads the next message from the file, returns `None` when the end of the file is reached.
+    pub fn read(&mut self) -> anyhow::Result<Option<ExportedMessage>> {
+        match self.format {
+            MessageFileFormat::Jsonl => self.read_line(),
+            MessageFileFormat::Binary => self.read_record(),
+        }
+    }
+
+    fn read_line(&mut self) -> anyhow::Result<Option<ExportedMessage>> {
+        loop {
+            self.line.clear();
+            self.line_number += 1;
+            if self.reader.read_line(&mut self.line)? == 0 {
+                return Ok(None);
+            }
+            if self.line.trim().is_empty() {
+                continue;
+            }
+
+            let message = serde_json::from_str(&self.line).with_context(|| {
+                format!("Problem parsing message in line: {}", self.line_number)
+            })?;
+            return Ok(Some(message));
+        }
+    }
+
+    fn read_record(&mut self) -> anyhow::Result<Option<ExportedMessage>> {
+        if self.reader.fill_buf()?.is_empty() {
+            return Ok(None);
+        }
+
+        let mut length = [0u8; 4];
+        self.reader
+            .read_exact(&mut length)
+            .with_context(|| "Problem reading record length".to_owned())?;
+        let mut record = vec![0u8; u32::from_le_bytes(length) as usize];
+        self.reader
+            .read_exact(&mut record)
+            .with_context(|| "Problem reading record, file is truncated".to_owned())?;
+        let message = ExportedMessage::from_bytes(Bytes::from(record))
+            .with_context(|| "Problem decoding record".to_owned())?;
+        Ok(Some(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn messages() -> Vec<ExportedMessage> {
+        vec![
+            ExportedMessage {
+                partition_id: 1,
+                offset: 0,
+                timestamp: 1_000_000,
+                id: u128::MAX,
+                headers: Some(HashMap::from([(
+                    HeaderKey::from_str("key").unwrap(),
+                    HeaderValue::from_str("value").unwrap(),
+                )])),
+                payload: Bytes::from("first"),
+            },
+            ExportedMessage {
+                partition_id: 2,
+                offset: 7,
+                timestamp: 2_000_000,
+                id: 2,
+                headers: None,
+                payload: Bytes::from_static(&[0, 159, 146, 150]),
+            },
+        ]
+    }
+
+    fn write_and_read(format: MessageFileFormat) -> (Vec<u8>, Vec<ExportedMessage>) {
+        let mut writer = MessageFileWriter::new(Vec::new(), format).unwrap();
+        for message in messages() {
+            writer.write(&message).unwrap();
+        }
+        let file = writer.finish().unwrap();
+
+        let mut reader = MessageFileReader::new(file.as_slice()).unwrap();
+        assert_eq!(reader.format(), format);
+        let mut read = Vec::new();
+        while let Some(message) = reader.read().unwrap() {
+            read.push(message);
+        }
+        (file, read)
+    }
+
+    #[test]
+    fn messages_should_be_written_and_read_as_json_lines() {
+        let (file, read) = write_and_read(MessageFileFormat::Jsonl);
+
+        assert_eq!(read, messages());
+        let file = String::from_utf8(file).unwrap();
+        let first_line =
+            serde_json::from_str::<serde_json::Value>(file.lines().next().unwrap()).unwrap();
+        assert_eq!(first_line["id"], u128::MAX.to_string());
+        assert_eq!(first_line["payload"], "Zmlyc3Q=");
+        assert_eq!(first_line["headers"]["key"]["kind"], "string");
+    }
+
+    #[test]
+    fn messages_should_be_written_and_read_as_binary() {
+        let (file, read) = write_and_read(MessageFileFormat::Binary);
+
+        assert!(file.starts_with(BINARY_FILE_MAGIC));
+        assert_eq!(read, messages());
+    }
+
+    #[test]
+    fn truncated_binary_file_should_fail() {
+        let mut writer = MessageFileWriter::new(Vec::new(), MessageFileFormat::Binary).unwrap();
+        writer.write(&messages()[0]).unwrap();
+        let mut file = writer.finish().unwrap();
+        file.truncate(file.len() - 1);
+
+        let mut reader = MessageFileReader::new(file.as_slice()).unwrap();
+        assert!(reader.read().is_err());
+    }
+
+    #[test]
+    fn empty_file_should_have_no_messages() {
+        let mut reader = MessageFileReader::new([].as_slice()).unwrap();
+
+        assert_eq!(reader.format(), MessageFileFormat::Jsonl);
+        assert!(reader.read().unwrap().is_none());
+    }
+}
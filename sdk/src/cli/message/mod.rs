@@ -1,2 +1,7 @@
+pub mod export_messages;
+pub mod import_messages;
+pub mod message_file;
+pub mod payload;
 pub mod poll_messages;
 pub mod send_messages;
+pub mod tail_messages;
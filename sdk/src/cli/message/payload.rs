@@ -0,0 +1,249 @@
+use anyhow::{bail, Context};
+use apache_avro::Schema;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor};
+use serde_json::Value;
+use std::fmt::Display;
+use std::path::Path;
+
+/// The format used to render the message payload.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum PayloadFormat {
+    #[default]
+    String,
+    Hex,
+    Base64,
+    Json,
+    Protobuf,
+    Avro,
+}
+
+impl Display for PayloadFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PayloadFormat::String => write!(f, "string"),
+            PayloadFormat::Hex => write!(f, "hex"),
+            PayloadFormat::Base64 => write!(f, "base64"),
+            PayloadFormat::Json => write!(f, "json"),
+            PayloadFormat::Protobuf => write!(f, "protobuf"),
+            PayloadFormat::Avro => write!(f, "avro"),
+        }
+    }
+}
+
+/// `PayloadDecoder` renders the binary payload of the message.
+/// The string, hex and base64 formats render the payload as a string, while the JSON, Protobuf and Avro
+/// formats decode the payload into the JSON value.
+/// Protobuf requires the file descriptor set (e.g. `protoc --include_imports --descriptor_set_out=schema.desc`)
+/// and the fully qualified name of the message type, Avro requires the JSON schema of the record
+/// and expects the payload to be the binary encoded datum without any header.
+pub enum PayloadDecoder {
+    String,
+    Hex,
+    Base64,
+    Json,
+    Protobuf(MessageDescriptor),
+    Avro(Schema),
+}
+
+impl PayloadDecoder {
+    pub fn new(
+        format: PayloadFormat,
+        schema_file: Option<&Path>,
+        message_type: Option<&str>,
+    ) -> anyhow::Result<Self> {
+        let decoder = match format {
+            PayloadFormat::String => PayloadDecoder::String,
+            PayloadFormat::Hex => PayloadDecoder::Hex,
+            PayloadFormat::Base64 => PayloadDecoder::Base64,
+            PayloadFormat::Json => PayloadDecoder::Json,
+            PayloadFormat::Protobuf => {
+                let schema = read_schema_file(format, schema_file)?;
+                let Some(message_type) = message_type else {
+                    bail!("Message type is required for {format} payload format");
+                };
+                Self::protobuf(&schema, message_type)?
+            }
+            PayloadFormat::Avro => {
+                let schema = read_schema_file(format, schema_file)?;
+                Self::avro(&String::from_utf8(schema).with_context(|| {
+                    "Avro schema file must contain valid UTF-8 JSON schema".to_owned()
+                })?)?
+            }
+        };
+
+        Ok(decoder)
+    }
+
+    /// Creates the Protobuf decoder based on the encoded file descriptor set.
+    pub fn protobuf(file_descriptor_set: &[u8], message_type: &str) -> anyhow::Result<Self> {
+        let pool = DescriptorPool::decode(file_descriptor_set)
+            .with_context(|| "Problem decoding Protobuf file descriptor set".to_owned())?;
+        let Some(descriptor) = pool.get_message_by_name(message_type) else {
+            bail!("Message type: {message_type} was not found in Protobuf file descriptor set");
+        };
+
+        Ok(PayloadDecoder::Protobuf(descriptor))
+    }
+
+    /// Creates the Avro decoder based on the JSON schema.
+    pub fn avro(schema: &str) -> anyhow::Result<Self> {
+        let schema =
+            Schema::parse_str(schema).with_context(|| "Problem parsing Avro schema".to_owned())?;
+        Ok(PayloadDecoder::Avro(schema))
+    }
+
+    pub fn decode(&self, payload: &[u8]) -> anyhow::Result<Value> {
+        let value = match self {
+            PayloadDecoder::String => Value::String(String::from_utf8_lossy(payload).to_string()),
+            PayloadDecoder::Hex => Value::String(
+                payload
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect::<String>(),
+            ),
+            PayloadDecoder::Base64 => Value::String(STANDARD.encode(payload)),
+            PayloadDecoder::Json => serde_json::from_slice(payload)
+                .with_context(|| "Payload is not a valid JSON".to_owned())?,
+            PayloadDecoder::Protobuf(descriptor) => {
+                let message =
+                    DynamicMessage::decode(descriptor.clone(), payload).with_context(|| {
+                        format!("Payload is not a valid {} message", descriptor.full_name())
+                    })?;
+                serde_json::to_value(&message)?
+            }
+            PayloadDecoder::Avro(schema) => {
+                let value = apache_avro::from_avro_datum(schema, &mut &payload[..], None)
+                    .with_context(|| "Payload is not a valid Avro datum".to_owned())?;
+                Value::try_from(value)?
+            }
+        };
+
+        Ok(value)
+    }
+
+    /// Renders the payload as a human-readable string, the structured payloads are pretty-printed.
+    pub fn render(&self, payload: &[u8]) -> String {
+        match self.decode(payload) {
+            Ok(Value::String(value)) => value,
+            Ok(value) => serde_json::to_string_pretty(&value).unwrap_or_default(),
+            Err(error) => format!("<{error:#}>"),
+        }
+    }
+}
+
+fn read_schema_file(format: PayloadFormat, schema_file: Option<&Path>) -> anyhow::Result<Vec<u8>> {
+    let Some(schema_file) = schema_file else {
+        bail!("Schema file is required for {format} payload format");
+    };
+
+    std::fs::read(schema_file)
+        .with_context(|| format!("Problem reading schema file: {}", schema_file.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apache_avro::types::Record;
+    use prost_reflect::prost::Message;
+    use prost_reflect::prost_types::field_descriptor_proto::{Label, Type};
+    use prost_reflect::prost_types::{
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn payload_should_be_rendered_as_string_hex_and_base64() {
+        let payload = b"hello";
+
+        assert_eq!(PayloadDecoder::String.render(payload), "hello");
+        assert_eq!(PayloadDecoder::Hex.render(payload), "68656c6c6f");
+        assert_eq!(PayloadDecoder::Base64.render(payload), "aGVsbG8=");
+    }
+
+    #[test]
+    fn json_payload_should_be_pretty_printed() {
+        let payload = br#"{"id":1,"name":"test"}"#;
+
+        assert_eq!(
+            PayloadDecoder::Json.decode(payload).unwrap(),
+            json!({ "id": 1, "name": "test" })
+        );
+        assert_eq!(
+            PayloadDecoder::Json.render(payload),
+            "{\n  \"id\": 1,\n  \"name\": \"test\"\n}"
+        );
+        assert!(PayloadDecoder::Json.decode(b"hello").is_err());
+    }
+
+    #[test]
+    fn protobuf_payload_should_be_decoded() {
+        let field = |name: &str, number: i32, kind: Type| FieldDescriptorProto {
+            name: Some(name.to_owned()),
+            number: Some(number),
+            label: Some(Label::Optional.into()),
+            r#type: Some(kind.into()),
+            json_name: Some(name.to_owned()),
+            ..Default::default()
+        };
+        let file_descriptor_set = FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("order.proto".to_owned()),
+                package: Some("shop".to_owned()),
+                message_type: vec![DescriptorProto {
+                    name: Some("Order".to_owned()),
+                    field: vec![
+                        field("id", 1, Type::Uint32),
+                        field("product", 2, Type::String),
+                    ],
+                    ..Default::default()
+                }],
+                syntax: Some("proto3".to_owned()),
+                ..Default::default()
+            }],
+        };
+        let decoder =
+            PayloadDecoder::protobuf(&file_descriptor_set.encode_to_vec(), "shop.Order").unwrap();
+        // Field 1 (varint) = 7, field 2 (length-delimited) = "book".
+        let payload = [0x08, 0x07, 0x12, 0x04, b'b', b'o', b'o', b'k'];
+
+        assert_eq!(
+            decoder.decode(&payload).unwrap(),
+            json!({ "id": 7, "product": "book" })
+        );
+        assert!(PayloadDecoder::protobuf(&file_descriptor_set.encode_to_vec(), "Order").is_err());
+    }
+
+    #[test]
+    fn avro_payload_should_be_decoded() {
+        let schema = r#"{
+            "type": "record",
+            "name": "Order",
+            "fields": [
+                { "name": "id", "type": "long" },
+                { "name": "product", "type": "string" }
+            ]
+        }"#;
+        let decoder = PayloadDecoder::avro(schema).unwrap();
+        let PayloadDecoder::Avro(parsed_schema) = &decoder else {
+            panic!("Expected Avro decoder");
+        };
+        let mut record = Record::new(parsed_schema).unwrap();
+        record.put("id", 7i64);
+        record.put("product", "book");
+        let payload = apache_avro::to_avro_datum(parsed_schema, record).unwrap();
+
+        assert_eq!(
+            decoder.decode(&payload).unwrap(),
+            json!({ "id": 7, "product": "book" })
+        );
+    }
+
+    #[test]
+    fn schema_file_should_be_required_for_protobuf_and_avro() {
+        assert!(PayloadDecoder::new(PayloadFormat::Protobuf, None, Some("shop.Order")).is_err());
+        assert!(PayloadDecoder::new(PayloadFormat::Avro, None, None).is_err());
+        assert!(PayloadDecoder::new(PayloadFormat::Hex, None, None).is_ok());
+    }
+}
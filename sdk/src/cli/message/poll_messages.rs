@@ -1,19 +1,54 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::consumer::Consumer;
 use crate::identifier::Identifier;
 use crate::messages::poll_messages::{PollMessages, PollingStrategy};
+use crate::models::header::HeaderValue;
 use crate::models::header::{HeaderKey, HeaderKind};
-use crate::models::messages::PolledMessages;
+use crate::models::messages::{MessageState, PolledMessage, PolledMessages};
 use crate::utils::timestamp::IggyTimestamp;
 use crate::utils::{byte_size::IggyByteSize, duration::IggyDuration};
 use anyhow::Context;
 use async_trait::async_trait;
+use bytes::Bytes;
 use comfy_table::{Cell, CellAlignment, Row, Table};
-use std::collections::HashSet;
+use serde::Serialize;
+use serde_with::base64::Base64;
+use serde_with::serde_as;
+use std::collections::{HashMap, HashSet};
 use std::mem::size_of_val;
 use tracing::{event, Level};
 
+/// The polled message printed as a single CSV row. The ID is written as a string,
+/// as the CSV conversion doesn't support 128-bit numbers.
+#[serde_as]
+#[derive(Serialize)]
+struct MessageRecord<'a> {
+    offset: u64,
+    state: MessageState,
+    timestamp: u64,
+    id: String,
+    checksum: u32,
+    headers: &'a Option<HashMap<HeaderKey, HeaderValue>>,
+    #[serde_as(as = "Base64")]
+    payload: &'a Bytes,
+}
+
+impl<'a> From<&'a PolledMessage> for MessageRecord<'a> {
+    fn from(message: &'a PolledMessage) -> Self {
+        Self {
+            offset: message.offset,
+            state: message.state,
+            timestamp: message.timestamp,
+            id: message.id.to_string(),
+            checksum: message.checksum,
+            headers: &message.headers,
+            payload: &message.payload,
+        }
+    }
+}
+
 pub struct PollMessagesCmd {
     poll_messages: PollMessages,
     show_headers: bool,
@@ -141,7 +176,11 @@ impl CliCommand for PollMessagesCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let start = std::time::Instant::now();
         let messages = client
             .poll_messages(
@@ -162,6 +201,19 @@ impl CliCommand for PollMessagesCmd {
             })?;
         let elapsed = IggyDuration::new(start.elapsed());
 
+        match output {
+            OutputFormat::Table => {}
+            OutputFormat::Csv => {
+                let records = messages
+                    .messages
+                    .iter()
+                    .map(MessageRecord::from)
+                    .collect::<Vec<_>>();
+                return print_output(output, &records);
+            }
+            _ => return print_output(output, &messages),
+        }
+
         event!(target: PRINT_TARGET, Level::INFO,
             "Polled messages from topic with ID: {} and stream with ID: {} (from partition with ID: {})",
             self.poll_messages.topic_id,
@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::messages::send_messages::{Message, Partitioning};
@@ -7,7 +8,6 @@ use anyhow::Context;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::io::{self, Read};
-use tracing::{event, Level};
 
 pub struct SendMessagesCmd {
     stream_id: Identifier,
@@ -72,7 +72,11 @@ impl CliCommand for SendMessagesCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let mut messages = match &self.messages {
             Some(messages) => messages
                 .iter()
@@ -103,11 +107,13 @@ impl CliCommand for SendMessagesCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Sent messages to topic with ID: {} and stream with ID: {}",
-            self.topic_id,
-            self.stream_id,
-        );
+        print_message(
+            output,
+            &format!(
+                "Sent messages to topic with ID: {} and stream with ID: {}",
+                self.topic_id, self.stream_id
+            ),
+        )?;
 
         Ok(())
     }
@@ -0,0 +1,399 @@
+use crate::cli::message::payload::{PayloadDecoder, PayloadFormat};
+use crate::cli::utils::output::print_output_item;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
+use crate::client::Client;
+use crate::consumer::Consumer;
+use crate::identifier::Identifier;
+use crate::messages::poll_messages::PollingStrategy;
+use crate::models::header::{HeaderKey, HeaderValue};
+use crate::models::messages::PolledMessage;
+use crate::utils::duration::IggyDuration;
+use crate::utils::timestamp::IggyTimestamp;
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tokio::time::sleep;
+use tracing::{event, Level};
+
+/// The position in the partitions from which the messages are followed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TailStart {
+    /// Only the messages appended after the command was started.
+    #[default]
+    End,
+    /// All the messages from the beginning of the partitions.
+    First,
+    /// The messages starting from the given offset in each partition.
+    Offset(u64),
+    /// The messages appended at or after the given time.
+    Timestamp(IggyTimestamp),
+}
+
+/// The followed message printed in the machine-readable output formats.
+/// The ID is written as a string, as it's a 128-bit number and the header values are rendered as strings.
+#[derive(Serialize)]
+struct TailedMessage {
+    partition_id: u32,
+    offset: u64,
+    timestamp: u64,
+    id: String,
+    headers: BTreeMap<String, String>,
+    payload: Value,
+}
+
+struct PartitionCursor {
+    partition_id: u32,
+    strategy: PollingStrategy,
+}
+
+pub struct TailMessagesCmd {
+    stream_id: Identifier,
+    topic_id: Identifier,
+    partition_id: Option<u32>,
+    consumer: Identifier,
+    consumer_group: Option<Identifier>,
+    start: TailStart,
+    message_count: u32,
+    interval: IggyDuration,
+    limit: Option<u64>,
+    payload_format: PayloadFormat,
+    schema_file: Option<PathBuf>,
+    message_type: Option<String>,
+    header_filters: Vec<(HeaderKey, HeaderValue)>,
+    show_headers: bool,
+}
+
+impl TailMessagesCmd {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        stream_id: Identifier,
+        topic_id: Identifier,
+        partition_id: Option<u32>,
+        consumer: Identifier,
+        consumer_group: Option<Identifier>,
+        start: TailStart,
+        message_count: u32,
+        interval: IggyDuration,
+        limit: Option<u64>,
+        payload_format: PayloadFormat,
+        schema_file: Option<PathBuf>,
+        message_type: Option<String>,
+        header_filters: Vec<(HeaderKey, HeaderValue)>,
+        show_headers: bool,
+    ) -> Self {
+        Self {
+            stream_id,
+            topic_id,
+            partition_id,
+            consumer,
+            consumer_group,
+            start,
+            message_count,
+            interval,
+            limit,
+            payload_format,
+            schema_file,
+            message_type,
+            header_filters,
+            show_headers,
+        }
+    }
+
+    fn get_partition_info(&self) -> String {
+        match (&self.consumer_group, self.partition_id) {
+            (Some(group_id), _) => {
+                format!("partitions assigned to consumer group with ID: {group_id}")
+            }
+            (None, Some(partition_id)) => format!("partition with ID: {partition_id}"),
+            (None, None) => "all partitions".to_owned(),
+        }
+    }
+
+    fn matches_headers(&self, message: &PolledMessage) -> bool {
+        self.header_filters.iter().all(|(key, value)| {
+            message
+                .headers
+                .as_ref()
+                .and_then(|headers| headers.get(key))
+                .is_some_and(|header| header == value)
+        })
+    }
+
+    async fn create_cursors(&self, client: &dyn Client) -> anyhow::Result<Vec<PartitionCursor>> {
+        let topic = client
+            .get_topic(&self.stream_id, &self.topic_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem getting topic with ID: {} and stream with ID: {}",
+                    self.topic_id, self.stream_id
+                )
+            })?;
+
+        let partitions = topic
+            .partitions
+            .iter()
+            .filter(|partition| self.partition_id.is_none_or(|id| id == partition.id))
+            .collect::<Vec<_>>();
+        if partitions.is_empty() {
+            anyhow::bail!(
+                "Partition with ID: {} was not found in topic with ID: {}",
+                self.partition_id.unwrap_or_default(),
+                self.topic_id
+            );
+        }
+
+        let cursors = partitions
+            .into_iter()
+            .map(|partition| PartitionCursor {
+                partition_id: partition.id,
+                strategy: match self.start {
+                    TailStart::End if partition.messages_count == 0 => PollingStrategy::offset(0),
+                    TailStart::End => PollingStrategy::offset(partition.current_offset + 1),
+                    TailStart::First => PollingStrategy::first(),
+                    TailStart::Offset(offset) => PollingStrategy::offset(offset),
+                    TailStart::Timestamp(timestamp) => PollingStrategy::timestamp(timestamp),
+                },
+            })
+            .collect();
+        Ok(cursors)
+    }
+
+    /// Polls the single batch of messages from each followed partition and prints them,
+    /// returns the number of printed messages.
+    async fn poll_batch(
+        &self,
+        client: &dyn Client,
+        group: Option<&Consumer>,
+        cursors: &mut [PartitionCursor],
+        decoder: &PayloadDecoder,
+        output: OutputFormat,
+        printed: &mut u64,
+    ) -> anyhow::Result<usize> {
+        let mut polled = 0;
+        if let Some(group) = group {
+            let messages = client
+                .poll_messages(
+                    &self.stream_id,
+                    &self.topic_id,
+                    None,
+                    group,
+                    &PollingStrategy::next(),
+                    self.message_count,
+                    true,
+                )
+                .await
+                .with_context(|| self.poll_error_context())?;
+            polled += messages.messages.len();
+            self.print_messages(
+                messages.partition_id,
+                &messages.messages,
+                decoder,
+                output,
+                printed,
+            )?;
+            return Ok(polled);
+        }
+
+        let consumer = Consumer::new(self.consumer.clone());
+        for cursor in cursors.iter_mut() {
+            let messages = client
+                .poll_messages(
+                    &self.stream_id,
+                    &self.topic_id,
+                    Some(cursor.partition_id),
+                    &consumer,
+                    &cursor.strategy,
+                    self.message_count,
+                    false,
+                )
+                .await
+                .with_context(|| self.poll_error_context())?;
+            if let Some(last) = messages.messages.last() {
+                cursor.strategy = PollingStrategy::offset(last.offset + 1);
+            }
+            polled += messages.messages.len();
+            self.print_messages(
+                cursor.partition_id,
+                &messages.messages,
+                decoder,
+                output,
+                printed,
+            )?;
+            if self.is_limit_reached(*printed) {
+                break;
+            }
+        }
+
+        Ok(polled)
+    }
+
+    fn print_messages(
+        &self,
+        partition_id: u32,
+        messages: &[PolledMessage],
+        decoder: &PayloadDecoder,
+        output: OutputFormat,
+        printed: &mut u64,
+    ) -> anyhow::Result<()> {
+        for message in messages {
+            if self.is_limit_reached(*printed) {
+                break;
+            }
+            if !self.matches_headers(message) {
+                continue;
+            }
+
+            let headers = message
+                .headers
+                .iter()
+                .flatten()
+                .map(|(key, value)| (key.as_str().to_owned(), value.value_only_to_string()))
+                .collect::<BTreeMap<_, _>>();
+
+            if output.is_table() {
+                let mut line = format!(
+                    "Partition: {partition_id} | Offset: {} | Timestamp: {} | ID: {}",
+                    message.offset,
+                    IggyTimestamp::from(message.timestamp).to_local_string("%Y-%m-%d %H:%M:%S%.6f"),
+                    message.id
+                );
+                if self.show_headers && !headers.is_empty() {
+                    let headers = headers
+                        .iter()
+                        .map(|(key, value)| format!("{key}={value}"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    line.push_str(&format!(" | Headers: {headers}"));
+                }
+                event!(target: PRINT_TARGET, Level::INFO, "{line}\n{}", decoder.render(&message.payload));
+            } else {
+                let payload = decoder
+                    .decode(&message.payload)
+                    .unwrap_or_else(|error| Value::String(format!("<{error:#}>")));
+                let tailed_message = TailedMessage {
+                    partition_id,
+                    offset: message.offset,
+                    timestamp: message.timestamp,
+                    id: message.id.to_string(),
+                    headers,
+                    payload,
+                };
+                print_output_item(output, &tailed_message, *printed == 0)?;
+            }
+            *printed += 1;
+        }
+
+        Ok(())
+    }
+
+    fn is_limit_reached(&self, printed: u64) -> bool {
+        self.limit.is_some_and(|limit| printed >= limit)
+    }
+
+    fn poll_error_context(&self) -> String {
+        format!(
+            "Problem polling messages from {} of topic with ID: {} and stream with ID: {}",
+            self.get_partition_info(),
+            self.topic_id,
+            self.stream_id
+        )
+    }
+
+    async fn follow(
+        &self,
+        client: &dyn Client,
+        group: Option<&Consumer>,
+        decoder: &PayloadDecoder,
+        output: OutputFormat,
+    ) -> anyhow::Result<()> {
+        let mut cursors = match group {
+            Some(_) => vec![],
+            None => self.create_cursors(client).await?,
+        };
+        let mut printed = 0;
+        loop {
+            let polled = self
+                .poll_batch(client, group, &mut cursors, decoder, output, &mut printed)
+                .await?;
+            if self.is_limit_reached(printed) {
+                return Ok(());
+            }
+
+            // Keep polling without waiting as long as there are messages to catch up with.
+            if polled == 0 {
+                tokio::select! {
+                    _ = sleep(self.interval.get_duration()) => {}
+                    _ = tokio::signal::ctrl_c() => return Ok(()),
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for TailMessagesCmd {
+    fn explain(&self) -> String {
+        format!(
+            "tail messages from {} of topic with ID: {} and stream with ID: {}",
+            self.get_partition_info(),
+            self.topic_id,
+            self.stream_id
+        )
+    }
+
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
+        let decoder = PayloadDecoder::new(
+            self.payload_format,
+            self.schema_file.as_deref(),
+            self.message_type.as_deref(),
+        )?;
+
+        let Some(group_id) = &self.consumer_group else {
+            return self.follow(client, None, &decoder, output).await;
+        };
+
+        // The server resolves the consumer group of the polling consumer only by its numeric ID.
+        let group = client
+            .get_consumer_group(&self.stream_id, &self.topic_id, group_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem getting consumer group with ID: {} for topic with ID: {} and stream with ID: {}",
+                    group_id, self.topic_id, self.stream_id
+                )
+            })?;
+        let group = Consumer::group(Identifier::numeric(group.id)?);
+
+        client
+            .join_consumer_group(&self.stream_id, &self.topic_id, group_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem joining consumer group with ID: {} for topic with ID: {} and stream with ID: {}",
+                    group_id, self.topic_id, self.stream_id
+                )
+            })?;
+
+        let result = self.follow(client, Some(&group), &decoder, output).await;
+
+        client
+            .leave_consumer_group(&self.stream_id, &self.topic_id, group_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "Problem leaving consumer group with ID: {} for topic with ID: {} and stream with ID: {}",
+                    group_id, self.topic_id, self.stream_id
+                )
+            })?;
+
+        result
+    }
+}
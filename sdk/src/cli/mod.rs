@@ -5,8 +5,10 @@ pub mod context;
 pub mod message;
 pub mod partitions;
 pub mod personal_access_tokens;
+pub mod roles;
 pub mod streams;
 pub mod system;
 pub mod topics;
+pub mod topology;
 pub mod users;
 pub mod utils;
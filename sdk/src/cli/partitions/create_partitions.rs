@@ -1,10 +1,10 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::partitions::create_partitions::CreatePartitions;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct CreatePartitionsCmd {
     create_partition: CreatePartitions,
@@ -38,7 +38,11 @@ impl CliCommand for CreatePartitionsCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let mut partitions = String::from("partition");
         if self.create_partition.partitions_count > 1 {
             partitions.push('s');
@@ -60,12 +64,15 @@ impl CliCommand for CreatePartitionsCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Created {} {partitions} for topic with ID: {} and stream with ID: {}",
-            self.create_partition.partitions_count,
-            self.create_partition.topic_id,
-            self.create_partition.stream_id,
-        );
+        print_message(
+            output,
+            &format!(
+                "Created {} {partitions} for topic with ID: {} and stream with ID: {}",
+                self.create_partition.partitions_count,
+                self.create_partition.topic_id,
+                self.create_partition.stream_id
+            ),
+        )?;
 
         Ok(())
     }
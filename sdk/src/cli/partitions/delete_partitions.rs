@@ -1,10 +1,10 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::partitions::delete_partitions::DeletePartitions;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct DeletePartitionsCmd {
     delete_partitions: DeletePartitions,
@@ -38,7 +38,11 @@ impl CliCommand for DeletePartitionsCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let mut partitions = String::from("partition");
         if self.delete_partitions.partitions_count > 1 {
             partitions.push('s');
@@ -60,12 +64,15 @@ impl CliCommand for DeletePartitionsCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Deleted {} {partitions} for topic with ID: {} and stream with ID: {}",
-            self.delete_partitions.partitions_count,
-            self.delete_partitions.topic_id,
-            self.delete_partitions.stream_id,
-        );
+        print_message(
+            output,
+            &format!(
+                "Deleted {} {partitions} for topic with ID: {} and stream with ID: {}",
+                self.delete_partitions.partitions_count,
+                self.delete_partitions.topic_id,
+                self.delete_partitions.stream_id
+            ),
+        )?;
 
         Ok(())
     }
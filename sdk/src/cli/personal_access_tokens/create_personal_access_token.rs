@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::{print_message, print_output};
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::personal_access_tokens::create_personal_access_token::CreatePersonalAccessToken;
 use crate::utils::personal_access_token_expiry::PersonalAccessTokenExpiry;
@@ -52,7 +53,11 @@ impl CliCommand for CreatePersonalAccessTokenCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let token = client
             .create_personal_access_token(&self.create_token.name, self.create_token.expiry)
             .await
@@ -69,16 +74,21 @@ impl CliCommand for CreatePersonalAccessTokenCmd {
             entry.set_password(&token.token)?;
             event!(target: PRINT_TARGET, Level::DEBUG,"Stored token under service: {} and name: {}", server_address,
                     self.create_token.name);
-            event!(target: PRINT_TARGET, Level::INFO,
-                "Personal access token with name: {} and {} created",
-                self.create_token.name,
-                match &self.token_expiry {
-                    Some(value) => format!("token expire time: {}", value),
-                    None => String::from("without token expire time"),
-                },
-            );
+            print_message(
+                output,
+                &format!(
+                    "Personal access token with name: {} and {} created",
+                    self.create_token.name,
+                    match &self.token_expiry {
+                        Some(value) => format!("token expire time: {}", value),
+                        None => String::from("without token expire time"),
+                    },
+                ),
+            )?;
         } else if self.quiet_mode {
             println!("{}", token.token);
+        } else if !output.is_table() {
+            print_output(output, &token)?;
         } else {
             event!(target: PRINT_TARGET, Level::INFO,
                 "Personal access token with name: {} and {} created",
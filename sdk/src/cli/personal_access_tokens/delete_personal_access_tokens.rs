@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::personal_access_tokens::delete_personal_access_token::DeletePersonalAccessToken;
 use anyhow::Context;
@@ -29,7 +30,11 @@ impl CliCommand for DeletePersonalAccessTokenCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .delete_personal_access_token(&self.delete_token.name)
             .await
@@ -48,9 +53,13 @@ impl CliCommand for DeletePersonalAccessTokenCmd {
             event!(target: PRINT_TARGET, Level::DEBUG, "{}", e);
         };
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Personal access token with name: {} deleted", self.delete_token.name
-        );
+        print_message(
+            output,
+            &format!(
+                "Personal access token with name: {} deleted",
+                self.delete_token.name
+            ),
+        )?;
 
         Ok(())
     }
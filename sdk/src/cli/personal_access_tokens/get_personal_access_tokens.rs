@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::personal_access_tokens::get_personal_access_tokens::GetPersonalAccessTokens;
 use anyhow::Context;
@@ -35,12 +36,20 @@ impl CliCommand for GetPersonalAccessTokensCmd {
         format!("list personal access tokens in {mode} mode")
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let tokens = client
             .get_personal_access_tokens()
             .await
             .with_context(|| String::from("Problem getting list of personal access tokens"))?;
 
+        if !output.is_table() {
+            return print_output(output, &tokens);
+        }
+
         match self.output {
             GetPersonalAccessTokensOutput::Table => {
                 let mut table = Table::new();
@@ -0,0 +1,46 @@
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
+use crate::client::Client;
+use crate::models::permissions::Permissions;
+use crate::roles::create_role::CreateRole;
+use anyhow::Context;
+use async_trait::async_trait;
+
+pub struct CreateRoleCmd {
+    create_role: CreateRole,
+}
+
+impl CreateRoleCmd {
+    pub fn new(name: String, permissions: Option<Permissions>) -> Self {
+        Self {
+            create_role: CreateRole { name, permissions },
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for CreateRoleCmd {
+    fn explain(&self) -> String {
+        format!("create role with name: {}", self.create_role.name)
+    }
+
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
+        client
+            .create_role(&self.create_role.name, self.create_role.permissions.clone())
+            .await
+            .with_context(|| {
+                format!("Problem creating role with name: {}", self.create_role.name)
+            })?;
+
+        print_message(
+            output,
+            &format!("Role with name: {} created", self.create_role.name),
+        )?;
+
+        Ok(())
+    }
+}
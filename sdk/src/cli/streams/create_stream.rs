@@ -1,9 +1,9 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::streams::create_stream::CreateStream;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct CreateStreamCmd {
     create_stream: CreateStream,
@@ -34,7 +34,11 @@ impl CliCommand for CreateStreamCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .create_stream(&self.create_stream.name, self.create_stream.stream_id)
             .await
@@ -46,11 +50,14 @@ impl CliCommand for CreateStreamCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Stream with name: {} and {} created",
-            self.create_stream.name,
-            self.get_stream_id_info(),
-        );
+        print_message(
+            output,
+            &format!(
+                "Stream with name: {} and {} created",
+                self.create_stream.name,
+                self.get_stream_id_info()
+            ),
+        )?;
 
         Ok(())
     }
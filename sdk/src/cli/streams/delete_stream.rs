@@ -1,10 +1,10 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::streams::delete_stream::DeleteStream;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct DeleteStreamCmd {
     delete_stream: DeleteStream,
@@ -24,7 +24,11 @@ impl CliCommand for DeleteStreamCmd {
         format!("delete stream with ID: {}", self.delete_stream.stream_id)
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .delete_stream(&self.delete_stream.stream_id)
             .await
@@ -35,7 +39,10 @@ impl CliCommand for DeleteStreamCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO, "Stream with ID: {} deleted", self.delete_stream.stream_id);
+        print_message(
+            output,
+            &format!("Stream with ID: {} deleted", self.delete_stream.stream_id),
+        )?;
 
         Ok(())
     }
@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::streams::get_stream::GetStream;
@@ -25,7 +26,11 @@ impl CliCommand for GetStreamCmd {
         format!("get stream with ID: {}", self.get_stream.stream_id)
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let stream = client
             .get_stream(&self.get_stream.stream_id)
             .await
@@ -36,6 +41,10 @@ impl CliCommand for GetStreamCmd {
                 )
             })?;
 
+        if !output.is_table() {
+            return print_output(output, &stream);
+        }
+
         let mut table = Table::new();
 
         table.set_header(vec!["Property", "Value"]);
@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::streams::get_streams::GetStreams;
 use anyhow::Context;
@@ -44,12 +45,20 @@ impl CliCommand for GetStreamsCmd {
         format!("list streams in {mode} mode")
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let streams = client
             .get_streams()
             .await
             .with_context(|| String::from("Problem getting list of streams"))?;
 
+        if !output.is_table() {
+            return print_output(output, &streams);
+        }
+
         if streams.is_empty() {
             event!(target: PRINT_TARGET, Level::INFO, "No streams found!");
             return Ok(());
@@ -1,10 +1,10 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::streams::purge_stream::PurgeStream;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct PurgeStreamCmd {
     purge_stream: PurgeStream,
@@ -24,7 +24,11 @@ impl CliCommand for PurgeStreamCmd {
         format!("purge stream with ID: {}", self.purge_stream.stream_id)
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .purge_stream(&self.purge_stream.stream_id)
             .await
@@ -35,7 +39,10 @@ impl CliCommand for PurgeStreamCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO, "Stream with ID: {} purged", self.purge_stream.stream_id);
+        print_message(
+            output,
+            &format!("Stream with ID: {} purged", self.purge_stream.stream_id),
+        )?;
 
         Ok(())
     }
@@ -1,10 +1,10 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::streams::update_stream::UpdateStream;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct UpdateStreamCmd {
     update_stream: UpdateStream,
@@ -27,7 +27,11 @@ impl CliCommand for UpdateStreamCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .update_stream(&self.update_stream.stream_id, &self.update_stream.name)
             .await
@@ -38,10 +42,13 @@ impl CliCommand for UpdateStreamCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Stream with ID: {} updated name: {}",
-            self.update_stream.stream_id, self.update_stream.name
-        );
+        print_message(
+            output,
+            &format!(
+                "Stream with ID: {} updated name: {}",
+                self.update_stream.stream_id, self.update_stream.name
+            ),
+        )?;
 
         Ok(())
     }
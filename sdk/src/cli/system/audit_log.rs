@@ -0,0 +1,120 @@
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
+use crate::client::Client;
+use crate::models::user_info::UserId;
+use crate::system::get_audit_log::GetAuditLog;
+use anyhow::Context;
+use async_trait::async_trait;
+use comfy_table::Table;
+use tracing::{event, Level};
+
+pub enum GetAuditLogOutput {
+    Table,
+    List,
+}
+
+pub struct GetAuditLogCmd {
+    get_audit_log: GetAuditLog,
+    output: GetAuditLogOutput,
+}
+
+impl GetAuditLogCmd {
+    pub fn new(count: u32, user_id: Option<UserId>, output: GetAuditLogOutput) -> Self {
+        GetAuditLogCmd {
+            get_audit_log: GetAuditLog { count, user_id },
+            output,
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for GetAuditLogCmd {
+    fn explain(&self) -> String {
+        let mode = match self.output {
+            GetAuditLogOutput::Table => "table",
+            GetAuditLogOutput::List => "list",
+        };
+        match self.get_audit_log.user_id {
+            Some(user_id) => format!(
+                "get {} most recent audit log entries for user with ID: {user_id} in {mode} mode",
+                self.get_audit_log.count
+            ),
+            None => format!(
+                "get {} most recent audit log entries in {mode} mode",
+                self.get_audit_log.count
+            ),
+        }
+    }
+
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
+        let entries = client
+            .get_audit_log(self.get_audit_log.count, self.get_audit_log.user_id)
+            .await
+            .with_context(|| String::from("Problem getting audit log entries"))?;
+
+        if !output.is_table() {
+            return print_output(output, &entries);
+        }
+
+        if entries.is_empty() {
+            event!(target: PRINT_TARGET, Level::INFO, "No audit log entries found!");
+            return Ok(());
+        }
+
+        match self.output {
+            GetAuditLogOutput::Table => {
+                let mut table = Table::new();
+
+                table.set_header(vec![
+                    "Timestamp",
+                    "User ID",
+                    "Username",
+                    "Address",
+                    "Transport",
+                    "Action",
+                    "Result",
+                ]);
+
+                entries.iter().for_each(|entry| {
+                    table.add_row(vec![
+                        entry.timestamp.to_local_string("%Y-%m-%d %H:%M:%S"),
+                        match entry.user_id {
+                            Some(user_id) => format!("{}", user_id),
+                            None => String::from(""),
+                        },
+                        entry.username.clone().unwrap_or_default(),
+                        entry.address.clone(),
+                        entry.transport.clone(),
+                        entry.action.clone(),
+                        entry.result.clone(),
+                    ]);
+                });
+
+                event!(target: PRINT_TARGET, Level::INFO, "{table}");
+            }
+            GetAuditLogOutput::List => {
+                entries.iter().for_each(|entry| {
+                    event!(target: PRINT_TARGET, Level::INFO,
+                        "{}|{}|{}|{}|{}|{}|{}",
+                        entry.timestamp.to_local_string("%Y-%m-%d %H:%M:%S"),
+                        match entry.user_id {
+                            Some(user_id) => format!("{}", user_id),
+                            None => String::from(""),
+                        },
+                        entry.username.as_deref().unwrap_or_default(),
+                        entry.address,
+                        entry.transport,
+                        entry.action,
+                        entry.result
+                    );
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
+use crate::client::Client;
+use crate::system::create_backup::CreateBackup;
+use anyhow::Context;
+use async_trait::async_trait;
+use comfy_table::Table;
+use tracing::{event, Level};
+
+pub struct CreateBackupCmd {
+    create_backup: CreateBackup,
+}
+
+impl CreateBackupCmd {
+    pub fn new(name: Option<String>, archive: bool) -> Self {
+        Self {
+            create_backup: CreateBackup { name, archive },
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand for CreateBackupCmd {
+    fn explain(&self) -> String {
+        let archive = if self.create_backup.archive {
+            " and archive it"
+        } else {
+            ""
+        };
+        match &self.create_backup.name {
+            Some(name) => format!("create backup with name: {name}{archive}"),
+            None => format!("create backup{archive}"),
+        }
+    }
+
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
+        let backup = client
+            .create_backup(
+                self.create_backup.name.as_deref(),
+                self.create_backup.archive,
+            )
+            .await
+            .with_context(|| String::from("Problem creating backup"))?;
+
+        if !output.is_table() {
+            return print_output(output, &backup);
+        }
+
+        let mut table = Table::new();
+
+        table.set_header(vec!["Property", "Value"]);
+        table.add_row(vec!["Backup name", backup.name.as_str()]);
+        table.add_row(vec![
+            "Created",
+            backup
+                .created_at
+                .to_local_string("%Y-%m-%d %H:%M:%S")
+                .as_str(),
+        ]);
+        table.add_row(vec![
+            "State index",
+            format!("{}", backup.state_index).as_str(),
+        ]);
+        table.add_row(vec![
+            "Partitions count",
+            format!("{}", backup.partitions_count).as_str(),
+        ]);
+        table.add_row(vec![
+            "Segments count",
+            format!("{}", backup.segments_count).as_str(),
+        ]);
+        table.add_row(vec![
+            "Messages count",
+            format!("{}", backup.messages_count).as_str(),
+        ]);
+        table.add_row(vec!["Backup size", format!("{}", backup.size).as_str()]);
+
+        event!(target: PRINT_TARGET, Level::INFO, "Backup created:\n{table}");
+
+        Ok(())
+    }
+}
@@ -1,10 +1,10 @@
 use crate::cli::system::session::ServerSession;
 use crate::cli::utils::login_session_expiry::LoginSessionExpiry;
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 const DEFAULT_LOGIN_SESSION_TIMEOUT: u64 = 1000 * 15 * 60;
 
@@ -28,10 +28,19 @@ impl CliCommand for LoginCmd {
         "login command".to_owned()
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         if self.server_session.is_active() {
-            event!(target: PRINT_TARGET, Level::INFO, "Already logged into Iggy server {}", self.server_session.get_server_address());
-            return Ok(());
+            return print_message(
+                output,
+                &format!(
+                    "Already logged into Iggy server {}",
+                    self.server_session.get_server_address()
+                ),
+            );
         }
 
         let tokens = client.get_personal_access_tokens().await.with_context(|| {
@@ -77,10 +86,13 @@ impl CliCommand for LoginCmd {
 
         self.server_session.store(&token.token)?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Successfully logged into Iggy server {}",
-            self.server_session.get_server_address(),
-        );
+        print_message(
+            output,
+            &format!(
+                "Successfully logged into Iggy server {}",
+                self.server_session.get_server_address()
+            ),
+        )?;
 
         Ok(())
     }
@@ -1,9 +1,9 @@
 use crate::cli::system::session::ServerSession;
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct LogoutCmd {
     server_session: ServerSession,
@@ -23,7 +23,11 @@ impl CliCommand for LogoutCmd {
         "logout command".to_owned()
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         if self.server_session.is_active() {
             client
                 .delete_personal_access_token(&self.server_session.get_token_name())
@@ -37,7 +41,13 @@ impl CliCommand for LogoutCmd {
 
             self.server_session.delete()?;
         }
-        event!(target: PRINT_TARGET, Level::INFO, "Successfully logged out from Iggy server {}", self.server_session.get_server_address());
+        print_message(
+            output,
+            &format!(
+                "Successfully logged out from Iggy server {}",
+                self.server_session.get_server_address()
+            ),
+        )?;
 
         Ok(())
     }
@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::system::get_me::GetMe;
 use anyhow::Context;
@@ -28,12 +29,20 @@ impl CliCommand for GetMeCmd {
         "me command".to_owned()
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let client_info = client
             .get_me()
             .await
             .with_context(|| "Problem sending get_me command".to_owned())?;
 
+        if !output.is_table() {
+            return print_output(output, &client_info);
+        }
+
         let mut table = Table::new();
 
         table.set_header(vec!["Property", "Value"]);
@@ -1,3 +1,5 @@
+pub mod audit_log;
+pub mod backup;
 pub mod login;
 pub mod logout;
 pub mod me;
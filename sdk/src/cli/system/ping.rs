@@ -1,8 +1,10 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::system::ping::Ping;
 use anyhow::Context;
 use async_trait::async_trait;
+use serde::Serialize;
 use std::fmt::{Display, Formatter, Result};
 use std::time::Duration;
 use tokio::time::{sleep, Instant};
@@ -22,6 +24,16 @@ impl PingCmd {
     }
 }
 
+/// The ping statistics printed in the machine-readable output, the times are in milliseconds.
+#[derive(Serialize)]
+struct PingSummary {
+    count: usize,
+    min_ms: f64,
+    avg_ms: f64,
+    max_ms: f64,
+    mdev_ms: f64,
+}
+
 struct PingStats {
     samples: Vec<u128>,
 }
@@ -70,6 +82,19 @@ impl PingStats {
     }
 }
 
+impl From<&PingStats> for PingSummary {
+    fn from(ping_stats: &PingStats) -> Self {
+        let (min, avg, max, std_dev) = ping_stats.get_stats();
+        Self {
+            count: ping_stats.count(),
+            min_ms: nano_to_ms(min),
+            avg_ms: nano_to_ms(avg),
+            max_ms: nano_to_ms(max),
+            mdev_ms: nano_to_ms(std_dev),
+        }
+    }
+}
+
 fn nano_to_ms(nanoseconds: u128) -> f64 {
     nanoseconds as f64 / 1_000_000.0
 }
@@ -98,7 +123,11 @@ impl CliCommand for PingCmd {
         false
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let print_width = (self.count.ilog10() + 1) as usize;
         let mut ping_stats = PingStats::new();
 
@@ -110,10 +139,16 @@ impl CliCommand for PingCmd {
                 .with_context(|| "Problem sending ping command".to_owned())?;
             let ping_duration = time_start.elapsed();
             ping_stats.add(&ping_duration);
-            event!(target: PRINT_TARGET, Level::INFO, "Ping sequence id: {:width$} time: {:.2} ms", i, nano_to_ms(ping_duration.as_nanos()), width = print_width);
+            if output.is_table() {
+                event!(target: PRINT_TARGET, Level::INFO, "Ping sequence id: {:width$} time: {:.2} ms", i, nano_to_ms(ping_duration.as_nanos()), width = print_width);
+            }
             sleep(Duration::from_secs(1)).await;
         }
 
+        if !output.is_table() {
+            return print_output(output, &PingSummary::from(&ping_stats));
+        }
+
         event!(target: PRINT_TARGET, Level::INFO, "");
         event!(target: PRINT_TARGET, Level::INFO, "Ping statistics for {} ping commands", ping_stats.count());
         event!(target: PRINT_TARGET, Level::INFO, "{ping_stats}");
@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::format_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::system::get_stats::GetStats;
 use anyhow::Context;
@@ -49,13 +50,19 @@ impl CliCommand for GetStatsCmd {
         "stats command".to_owned()
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let stats = client
             .get_stats()
             .await
             .with_context(|| "Problem sending get_stats command".to_owned())?;
 
-        let output = match self.output {
+        // The machine-readable global output format takes precedence over the stats output.
+        let formatted = match self.output {
+            _ if !output.is_table() => format_output(output, &stats)?.trim_end().to_string(),
             GetStatsOutput::Table => {
                 let mut table = Table::new();
 
@@ -144,6 +151,10 @@ impl CliCommand for GetStatsCmd {
                 table.add_row(vec!["OS Name", stats.os_name.as_str()]);
                 table.add_row(vec!["OS Version", stats.os_version.as_str()]);
                 table.add_row(vec!["Kernel Version", stats.kernel_version.as_str()]);
+                table.add_row(vec![
+                    "Iggy Server Version",
+                    stats.iggy_server_version.as_str(),
+                ]);
 
                 table.to_string()
             }
@@ -193,6 +204,7 @@ impl CliCommand for GetStatsCmd {
                 list.push(format!("OS Name|{}", stats.os_name));
                 list.push(format!("OS Version|{}", stats.os_version));
                 list.push(format!("Kernel Version|{}", stats.kernel_version));
+                list.push(format!("Iggy Server Version|{}", stats.iggy_server_version));
 
                 list.join("\n")
             }
@@ -201,9 +213,9 @@ impl CliCommand for GetStatsCmd {
         };
 
         if self.quiet_mode {
-            println!("{output}");
+            println!("{formatted}");
         } else {
-            event!(target: PRINT_TARGET, Level::INFO,"{output}");
+            event!(target: PRINT_TARGET, Level::INFO,"{formatted}");
         }
 
         Ok(())
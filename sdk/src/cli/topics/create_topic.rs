@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::compression::compression_algorithm::CompressionAlgorithm;
 use crate::identifier::Identifier;
@@ -8,7 +9,6 @@ use crate::utils::topic_size::MaxTopicSize;
 use anyhow::Context;
 use async_trait::async_trait;
 use core::fmt;
-use tracing::{event, Level};
 
 pub struct CreateTopicCmd {
     create_topic: CreateTopic,
@@ -60,7 +60,11 @@ impl CliCommand for CreateTopicCmd {
         format!("{}", self)
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .create_topic(&self.create_topic.stream_id, &self.create_topic.name, self.create_topic.partitions_count, self.create_topic.compression_algorithm, self.create_topic.replication_factor, self.create_topic.topic_id, self.create_topic.message_expiry, self.create_topic.max_topic_size)
             .await
@@ -71,17 +75,17 @@ impl CliCommand for CreateTopicCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Topic with name: {}, {}, partitions count: {}, compression algorithm: {}, message expiry: {}, max topic size: {}, replication factor: {} created in stream with ID: {}",
-            self.create_topic.name,
+        print_message(
+output,
+&format!("Topic with name: {}, {}, partitions count: {}, compression algorithm: {}, message expiry: {}, max topic size: {}, replication factor: {} created in stream with ID: {}", self.create_topic.name,
             self.get_topic_id_info(),
             self.create_topic.partitions_count,
             self.create_topic.compression_algorithm,
             self.message_expiry,
             self.max_topic_size,
             self.replication_factor,
-            self.create_topic.stream_id,
-        );
+            self.create_topic.stream_id),
+)?;
 
         Ok(())
     }
@@ -1,10 +1,10 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::topics::delete_topic::DeleteTopic;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct DeleteTopicCmd {
     delete_topic: DeleteTopic,
@@ -30,7 +30,11 @@ impl CliCommand for DeleteTopicCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .delete_topic(&self.delete_topic.stream_id, &self.delete_topic.topic_id)
             .await
@@ -41,10 +45,13 @@ impl CliCommand for DeleteTopicCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Topic with ID: {} in stream with ID: {} deleted",
-            self.delete_topic.topic_id, self.delete_topic.stream_id
-        );
+        print_message(
+            output,
+            &format!(
+                "Topic with ID: {} in stream with ID: {} deleted",
+                self.delete_topic.topic_id, self.delete_topic.stream_id
+            ),
+        )?;
 
         Ok(())
     }
@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::topics::get_topic::GetTopic;
@@ -32,7 +33,11 @@ impl CliCommand for GetTopicCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let topic = client
             .get_topic(&self.get_topic.stream_id, &self.get_topic.topic_id)
             .await
@@ -43,6 +48,10 @@ impl CliCommand for GetTopicCmd {
                 )
             })?;
 
+        if !output.is_table() {
+            return print_output(output, &topic);
+        }
+
         let mut table = Table::new();
 
         table.set_header(vec!["Property", "Value"]);
@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::topics::get_topics::GetTopics;
@@ -48,7 +49,11 @@ impl CliCommand for GetTopicsCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let topics = client
             .get_topics(&self.get_topics.stream_id)
             .await
@@ -59,6 +64,10 @@ impl CliCommand for GetTopicsCmd {
                 )
             })?;
 
+        if !output.is_table() {
+            return print_output(output, &topics);
+        }
+
         match self.output {
             GetTopicsOutput::Table => {
                 let mut table = Table::new();
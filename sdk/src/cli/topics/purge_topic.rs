@@ -1,10 +1,10 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::topics::purge_topic::PurgeTopic;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct PurgeTopicCmd {
     purge_topic: PurgeTopic,
@@ -30,7 +30,11 @@ impl CliCommand for PurgeTopicCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .purge_topic(&self.purge_topic.stream_id, &self.purge_topic.topic_id)
             .await
@@ -41,9 +45,13 @@ impl CliCommand for PurgeTopicCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Topic with ID: {} in stream with ID: {} purged",
-            self.purge_topic.topic_id, self.purge_topic.stream_id);
+        print_message(
+            output,
+            &format!(
+                "Topic with ID: {} in stream with ID: {} purged",
+                self.purge_topic.topic_id, self.purge_topic.stream_id
+            ),
+        )?;
 
         Ok(())
     }
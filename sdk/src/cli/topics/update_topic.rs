@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::compression::compression_algorithm::CompressionAlgorithm;
 use crate::identifier::Identifier;
@@ -8,7 +9,6 @@ use crate::utils::topic_size::MaxTopicSize;
 use anyhow::Context;
 use async_trait::async_trait;
 use core::fmt;
-use tracing::{event, Level};
 
 pub struct UpdateTopicCmd {
     update_topic: UpdateTopic,
@@ -50,7 +50,11 @@ impl CliCommand for UpdateTopicCmd {
         format!("{}", self)
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .update_topic(&self.update_topic.stream_id, &self.update_topic.topic_id, &self.update_topic.name, self.update_topic.compression_algorithm, self.replication_factor.into(), self.message_expiry, self.max_topic_size)
             .await
@@ -64,14 +68,14 @@ impl CliCommand for UpdateTopicCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Topic with ID: {} updated name: {}, updated message expiry: {}, updated compression algorithm: {} in stream with ID: {}",
-            self.update_topic.topic_id,
+        print_message(
+output,
+&format!("Topic with ID: {} updated name: {}, updated message expiry: {}, updated compression algorithm: {} in stream with ID: {}", self.update_topic.topic_id,
             self.update_topic.name,
             self.message_expiry,
             self.update_topic.compression_algorithm,
-            self.update_topic.stream_id,
-        );
+            self.update_topic.stream_id),
+)?;
 
         Ok(())
     }
@@ -0,0 +1,288 @@
+use crate::cli::topology::manifest::{StreamManifest, TopologyFormat, TopologyManifest};
+use crate::cli::topology::plan::{plan, TopologyChange};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
+use crate::client::Client;
+use crate::identifier::Identifier;
+use crate::users::defaults::DEFAULT_ROOT_USER_ID;
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::Serialize;
+use std::path::PathBuf;
+use tracing::{event, Level};
+
+pub struct ApplyTopologyCmd {
+    file: PathBuf,
+    dry_run: bool,
+}
+
+#[derive(Serialize)]
+struct ApplyTopologyOutput<'a> {
+    dry_run: bool,
+    changes: &'a [TopologyChange],
+}
+
+impl ApplyTopologyCmd {
+    pub fn new(file: PathBuf, dry_run: bool) -> Self {
+        Self { file, dry_run }
+    }
+
+    /// The root user and the user applying the changes are never deleted.
+    async fn get_protected_users(&self, client: &dyn Client) -> anyhow::Result<Vec<String>> {
+        let mut user_ids = vec![DEFAULT_ROOT_USER_ID];
+        if let Some(user_id) = client
+            .get_me()
+            .await
+            .with_context(|| "Problem getting current client info")?
+            .user_id
+        {
+            user_ids.push(user_id);
+        }
+
+        let mut usernames = Vec::new();
+        for user_id in user_ids {
+            let user = client
+                .get_user(&Identifier::numeric(user_id)?)
+                .await
+                .with_context(|| format!("Problem getting user with ID: {user_id}"))?;
+            usernames.push(user.username);
+        }
+        Ok(usernames)
+    }
+
+    async fn apply_change(
+        &self,
+        client: &dyn Client,
+        change: &TopologyChange,
+        streams: &mut Option<Vec<StreamManifest>>,
+    ) -> anyhow::Result<()> {
+        match change {
+            TopologyChange::CreateStream { stream, id } => {
+                client.create_stream(stream, *id).await?;
+            }
+            TopologyChange::CreateTopic {
+                stream,
+                topic,
+                id,
+                partitions_count,
+                message_expiry,
+                max_topic_size,
+                compression_algorithm,
+                replication_factor,
+            } => {
+                client
+                    .create_topic(
+                        &Identifier::named(stream)?,
+                        topic,
+                        *partitions_count,
+                        *compression_algorithm,
+                        *replication_factor,
+                        *id,
+                        *message_expiry,
+                        *max_topic_size,
+                    )
+                    .await?;
+            }
+            TopologyChange::UpdateTopic {
+                stream,
+                topic,
+                message_expiry,
+                max_topic_size,
+                compression_algorithm,
+                replication_factor,
+            } => {
+                client
+                    .update_topic(
+                        &Identifier::named(stream)?,
+                        &Identifier::named(topic)?,
+                        topic,
+                        *compression_algorithm,
+                        Some(*replication_factor),
+                        *message_expiry,
+                        *max_topic_size,
+                    )
+                    .await?;
+            }
+            TopologyChange::CreatePartitions {
+                stream,
+                topic,
+                count,
+            } => {
+                client
+                    .create_partitions(
+                        &Identifier::named(stream)?,
+                        &Identifier::named(topic)?,
+                        *count,
+                    )
+                    .await?;
+            }
+            TopologyChange::CreateConsumerGroup {
+                stream,
+                topic,
+                group,
+                id,
+            } => {
+                client
+                    .create_consumer_group(
+                        &Identifier::named(stream)?,
+                        &Identifier::named(topic)?,
+                        group,
+                        *id,
+                    )
+                    .await?;
+            }
+            TopologyChange::DeleteConsumerGroup {
+                stream,
+                topic,
+                group,
+            } => {
+                client
+                    .delete_consumer_group(
+                        &Identifier::named(stream)?,
+                        &Identifier::named(topic)?,
+                        &Identifier::named(group)?,
+                    )
+                    .await?;
+            }
+            TopologyChange::DeletePartitions {
+                stream,
+                topic,
+                count,
+            } => {
+                client
+                    .delete_partitions(
+                        &Identifier::named(stream)?,
+                        &Identifier::named(topic)?,
+                        *count,
+                    )
+                    .await?;
+            }
+            TopologyChange::DeleteTopic { stream, topic } => {
+                client
+                    .delete_topic(&Identifier::named(stream)?, &Identifier::named(topic)?)
+                    .await?;
+            }
+            TopologyChange::DeleteStream { stream } => {
+                client.delete_stream(&Identifier::named(stream)?).await?;
+            }
+            TopologyChange::CreateUser {
+                username,
+                password,
+                status,
+                permissions,
+            } => {
+                let permissions = match permissions {
+                    Some(permissions) => Some(
+                        permissions.to_permissions(Self::get_streams(client, streams).await?)?,
+                    ),
+                    None => None,
+                };
+                client
+                    .create_user(username, password, *status, permissions)
+                    .await?;
+            }
+            TopologyChange::UpdateUserStatus { username, status } => {
+                client
+                    .update_user(&Identifier::named(username)?, None, Some(*status))
+                    .await?;
+            }
+            TopologyChange::UpdatePermissions {
+                username,
+                permissions,
+            } => {
+                let permissions =
+                    permissions.to_permissions(Self::get_streams(client, streams).await?)?;
+                client
+                    .update_permissions(&Identifier::named(username)?, Some(permissions))
+                    .await?;
+            }
+            TopologyChange::DeleteUser { username } => {
+                client.delete_user(&Identifier::named(username)?).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The streams are read once all the changes of the streams are applied,
+    /// so the permissions of the users can be resolved against the created streams and topics.
+    async fn get_streams<'a>(
+        client: &dyn Client,
+        streams: &'a mut Option<Vec<StreamManifest>>,
+    ) -> anyhow::Result<&'a [StreamManifest]> {
+        if streams.is_none() {
+            *streams = Some(TopologyManifest::fetch_streams(client).await?);
+        }
+        Ok(streams.as_deref().unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl CliCommand for ApplyTopologyCmd {
+    fn explain(&self) -> String {
+        let mode = match self.dry_run {
+            true => " (dry run)",
+            false => "",
+        };
+        format!("apply topology from file: {}{mode}", self.file.display())
+    }
+
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
+        let content = std::fs::read_to_string(&self.file)
+            .with_context(|| format!("Problem reading file: {}", self.file.display()))?;
+        let desired = TopologyManifest::parse(&content, TopologyFormat::from_path(&self.file))
+            .with_context(|| format!("Problem parsing file: {}", self.file.display()))?;
+
+        let streams = TopologyManifest::fetch_streams(client).await?;
+        let (users, protected_users) = match desired.users {
+            Some(_) => (
+                Some(TopologyManifest::fetch_users(client, &streams).await?),
+                self.get_protected_users(client).await?,
+            ),
+            None => (None, Vec::new()),
+        };
+        let current = TopologyManifest {
+            streams: Some(streams),
+            users,
+        };
+        let changes = plan(&desired, &current, &protected_users)?;
+
+        let mut streams = None;
+        for change in &changes {
+            if !self.dry_run {
+                self.apply_change(client, change, &mut streams)
+                    .await
+                    .with_context(|| format!("Problem applying change: {change}"))?;
+            }
+            if output.is_table() {
+                event!(target: PRINT_TARGET, Level::INFO, "{change}");
+            }
+        }
+
+        if !output.is_table() {
+            return print_output(
+                output,
+                &ApplyTopologyOutput {
+                    dry_run: self.dry_run,
+                    changes: &changes,
+                },
+            );
+        }
+
+        let file = self.file.display();
+        let summary = match (changes.len(), self.dry_run) {
+            (0, _) => format!("Topology is up to date with file: {file}"),
+            (count, true) => {
+                format!("Planned {count} changes from file: {file}, nothing was applied (dry run)")
+            }
+            (count, false) => format!("Applied {count} changes from file: {file}"),
+        };
+        event!(target: PRINT_TARGET, Level::INFO, "{summary}");
+
+        Ok(())
+    }
+}
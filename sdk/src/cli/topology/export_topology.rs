@@ -0,0 +1,68 @@
+use crate::cli::topology::manifest::{TopologyFormat, TopologyManifest};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
+use crate::client::Client;
+use anyhow::Context;
+use async_trait::async_trait;
+use std::path::PathBuf;
+use tracing::{event, Level};
+
+pub struct ExportTopologyCmd {
+    file: Option<PathBuf>,
+    format: TopologyFormat,
+}
+
+impl ExportTopologyCmd {
+    /// The format is detected based on the extension of the file, unless it's given explicitly.
+    pub fn new(file: Option<PathBuf>, format: Option<TopologyFormat>) -> Self {
+        let format = format.unwrap_or_else(|| {
+            file.as_deref()
+                .map(TopologyFormat::from_path)
+                .unwrap_or_default()
+        });
+        Self { file, format }
+    }
+}
+
+#[async_trait]
+impl CliCommand for ExportTopologyCmd {
+    fn explain(&self) -> String {
+        match &self.file {
+            Some(file) => format!(
+                "export topology to file: {} in {} format",
+                file.display(),
+                self.format
+            ),
+            None => format!("export topology in {} format", self.format),
+        }
+    }
+
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
+        let manifest = TopologyManifest::fetch(client).await?;
+        let content = manifest.to_string(self.format)?;
+
+        let Some(file) = &self.file else {
+            event!(target: PRINT_TARGET, Level::INFO, "{}", content.trim_end());
+            return Ok(());
+        };
+
+        std::fs::write(file, content)
+            .with_context(|| format!("Problem writing file: {}", file.display()))?;
+        print_message(
+            output,
+            &format!(
+                "Exported topology with {} streams and {} users to file: {} in {} format",
+                manifest.streams.as_deref().unwrap_or_default().len(),
+                manifest.users.as_deref().unwrap_or_default().len(),
+                file.display(),
+                self.format
+            ),
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,677 @@
+use crate::client::Client;
+use crate::compression::compression_algorithm::CompressionAlgorithm;
+use crate::identifier::Identifier;
+use crate::models::permissions::{
+    GlobalPermissions, Permissions, StreamPermissions, TopicPermissions,
+};
+use crate::models::user_status::UserStatus;
+use crate::utils::byte_size::IggyByteSize;
+use crate::utils::expiry::IggyExpiry;
+use crate::utils::topic_size::MaxTopicSize;
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::path::Path;
+
+/// The format of the topology manifest file.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum TopologyFormat {
+    #[default]
+    Toml,
+    Yaml,
+}
+
+impl TopologyFormat {
+    /// Detects the format based on the extension of the file, YAML is used for the `.yaml`
+    /// and `.yml` extensions, TOML for all the other ones.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("yaml" | "yml") => TopologyFormat::Yaml,
+            _ => TopologyFormat::Toml,
+        }
+    }
+}
+
+impl Display for TopologyFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TopologyFormat::Toml => write!(f, "toml"),
+            TopologyFormat::Yaml => write!(f, "yaml"),
+        }
+    }
+}
+
+/// The desired topology of the server. Entities are matched with the ones existing on the server
+/// by their names, the IDs are used only when the entities are created.
+///
+/// The omitted section (`streams`, `users`, `topics` of a stream or `consumer_groups` of a topic)
+/// is not managed at all, while the empty one means that no entities should exist in it,
+/// so the existing ones are deleted. The same applies to the optional settings of the topics
+/// and users, the omitted ones are left as they are on the server.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TopologyManifest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub streams: Option<Vec<StreamManifest>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub users: Option<Vec<UserManifest>>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StreamManifest {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topics: Option<Vec<TopicManifest>>,
+}
+
+#[serde_as]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TopicManifest {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    pub partitions_count: u32,
+    /// Message expiry time in human-readable format like 1h 15min, "none" or "server_default".
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_expiry: Option<IggyExpiry>,
+    /// Maximum size of the topic like 10GB, "unlimited" or "server_default".
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "max_topic_size_format"
+    )]
+    pub max_topic_size: Option<MaxTopicSize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression_algorithm: Option<CompressionAlgorithm>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replication_factor: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consumer_groups: Option<Vec<ConsumerGroupManifest>>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConsumerGroupManifest {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+}
+
+/// The user is matched by the username. The password is required only to create the user,
+/// it's never changed for the existing users and never exported.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserManifest {
+    pub username: String,
+    #[serde(default, skip_serializing)]
+    pub password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<UserStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<PermissionsManifest>,
+}
+
+/// The permissions of the user, the streams and topics are referenced by their names,
+/// so the permissions can be declared for the streams and topics created by the same manifest.
+/// All the omitted permissions are not granted.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PermissionsManifest {
+    pub global: GlobalPermissionsManifest,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub streams: BTreeMap<String, StreamPermissionsManifest>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GlobalPermissionsManifest {
+    pub manage_servers: bool,
+    pub read_servers: bool,
+    pub manage_users: bool,
+    pub read_users: bool,
+    pub manage_streams: bool,
+    pub read_streams: bool,
+    pub manage_topics: bool,
+    pub read_topics: bool,
+    pub poll_messages: bool,
+    pub send_messages: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StreamPermissionsManifest {
+    pub manage_stream: bool,
+    pub read_stream: bool,
+    pub manage_topics: bool,
+    pub read_topics: bool,
+    pub poll_messages: bool,
+    pub send_messages: bool,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub topics: BTreeMap<String, TopicPermissionsManifest>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TopicPermissionsManifest {
+    pub manage_topic: bool,
+    pub read_topic: bool,
+    pub poll_messages: bool,
+    pub send_messages: bool,
+}
+
+impl TopologyManifest {
+    pub fn parse(content: &str, format: TopologyFormat) -> anyhow::Result<Self> {
+        match format {
+            TopologyFormat::Toml => {
+                toml::from_str(content).with_context(|| "Problem parsing TOML topology manifest")
+            }
+            TopologyFormat::Yaml => serde_yaml::from_str(content)
+                .with_context(|| "Problem parsing YAML topology manifest"),
+        }
+    }
+
+    pub fn to_string(&self, format: TopologyFormat) -> anyhow::Result<String> {
+        match format {
+            TopologyFormat::Toml => toml::to_string(self)
+                .with_context(|| "Problem serializing topology manifest to TOML"),
+            TopologyFormat::Yaml => serde_yaml::to_string(self)
+                .with_context(|| "Problem serializing topology manifest to YAML"),
+        }
+    }
+
+    /// Reads the current topology of the server, all the sections and settings are present
+    /// along with the IDs of the entities, except for the passwords of the users.
+    pub async fn fetch(client: &dyn Client) -> anyhow::Result<Self> {
+        let streams = Self::fetch_streams(client).await?;
+        let users = Self::fetch_users(client, &streams).await?;
+
+        Ok(TopologyManifest {
+            streams: Some(streams),
+            users: Some(users),
+        })
+    }
+
+    /// Reads the streams existing on the server along with their topics and consumer groups.
+    pub async fn fetch_streams(client: &dyn Client) -> anyhow::Result<Vec<StreamManifest>> {
+        let mut streams = Vec::new();
+        let mut stream_ids = client
+            .get_streams()
+            .await
+            .with_context(|| "Problem getting list of streams")?
+            .into_iter()
+            .map(|stream| stream.id)
+            .collect::<Vec<_>>();
+        stream_ids.sort_unstable();
+        for stream_id in stream_ids {
+            let stream_identifier = Identifier::numeric(stream_id)?;
+            let mut stream = client
+                .get_stream(&stream_identifier)
+                .await
+                .with_context(|| format!("Problem getting stream with ID: {stream_id}"))?;
+            stream.topics.sort_by_key(|topic| topic.id);
+
+            let mut topics = Vec::new();
+            for topic in stream.topics {
+                let mut consumer_groups = client
+                    .get_consumer_groups(&stream_identifier, &Identifier::numeric(topic.id)?)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Problem getting consumer groups for topic with ID: {} and stream with ID: {stream_id}",
+                            topic.id
+                        )
+                    })?;
+                consumer_groups.sort_by_key(|group| group.id);
+
+                topics.push(TopicManifest {
+                    name: topic.name,
+                    id: Some(topic.id),
+                    partitions_count: topic.partitions_count,
+                    message_expiry: Some(topic.message_expiry),
+                    max_topic_size: Some(topic.max_topic_size),
+                    compression_algorithm: Some(topic.compression_algorithm),
+                    replication_factor: Some(topic.replication_factor),
+                    consumer_groups: Some(
+                        consumer_groups
+                            .into_iter()
+                            .map(|group| ConsumerGroupManifest {
+                                name: group.name,
+                                id: Some(group.id),
+                            })
+                            .collect(),
+                    ),
+                });
+            }
+
+            streams.push(StreamManifest {
+                name: stream.name,
+                id: Some(stream.id),
+                topics: Some(topics),
+            });
+        }
+
+        Ok(streams)
+    }
+
+    /// Reads the users existing on the server, the given streams are used to resolve
+    /// the stream and topic IDs in the permissions to their names.
+    pub async fn fetch_users(
+        client: &dyn Client,
+        streams: &[StreamManifest],
+    ) -> anyhow::Result<Vec<UserManifest>> {
+        let mut users = Vec::new();
+        let mut user_ids = client
+            .get_users()
+            .await
+            .with_context(|| "Problem getting list of users")?
+            .into_iter()
+            .map(|user| user.id)
+            .collect::<Vec<_>>();
+        user_ids.sort_unstable();
+        for user_id in user_ids {
+            let user = client
+                .get_user(&Identifier::numeric(user_id)?)
+                .await
+                .with_context(|| format!("Problem getting user with ID: {user_id}"))?;
+            users.push(UserManifest {
+                username: user.username,
+                password: None,
+                status: Some(user.status),
+                permissions: user.permissions.map(|permissions| {
+                    PermissionsManifest::from_permissions(&permissions, streams)
+                }),
+            });
+        }
+
+        Ok(users)
+    }
+}
+
+impl PermissionsManifest {
+    /// Converts the permissions returned by the server, the permissions of the streams
+    /// and topics which no longer exist are skipped.
+    pub fn from_permissions(permissions: &Permissions, streams: &[StreamManifest]) -> Self {
+        let mut streams_permissions = BTreeMap::new();
+        for (stream_id, stream_permissions) in permissions.streams.iter().flatten() {
+            let Some(stream) = streams.iter().find(|stream| stream.id == Some(*stream_id)) else {
+                continue;
+            };
+
+            let mut topics_permissions = BTreeMap::new();
+            for (topic_id, topic_permissions) in stream_permissions.topics.iter().flatten() {
+                let Some(topic) = stream
+                    .topics
+                    .iter()
+                    .flatten()
+                    .find(|topic| topic.id == Some(*topic_id))
+                else {
+                    continue;
+                };
+                topics_permissions.insert(topic.name.clone(), topic_permissions.into());
+            }
+
+            streams_permissions.insert(
+                stream.name.clone(),
+                StreamPermissionsManifest {
+                    manage_stream: stream_permissions.manage_stream,
+                    read_stream: stream_permissions.read_stream,
+                    manage_topics: stream_permissions.manage_topics,
+                    read_topics: stream_permissions.read_topics,
+                    poll_messages: stream_permissions.poll_messages,
+                    send_messages: stream_permissions.send_messages,
+                    topics: topics_permissions,
+                },
+            );
+        }
+
+        PermissionsManifest {
+            global: (&permissions.global).into(),
+            streams: streams_permissions,
+        }
+    }
+
+    /// Resolves the names of the streams and topics to their IDs using the given streams
+    /// existing on the server.
+    pub fn to_permissions(&self, streams: &[StreamManifest]) -> anyhow::Result<Permissions> {
+        let mut streams_permissions = HashMap::new();
+        for (stream_name, stream_permissions) in &self.streams {
+            let stream = streams
+                .iter()
+                .find(|stream| &stream.name == stream_name)
+                .with_context(|| format!("Stream: {stream_name} was not found"))?;
+
+            let mut topics_permissions = HashMap::new();
+            for (topic_name, topic_permissions) in &stream_permissions.topics {
+                let topic_id = stream
+                    .topics
+                    .iter()
+                    .flatten()
+                    .find(|topic| &topic.name == topic_name)
+                    .and_then(|topic| topic.id)
+                    .with_context(|| {
+                        format!("Topic: {topic_name} was not found in stream: {stream_name}")
+                    })?;
+                topics_permissions.insert(topic_id, topic_permissions.into());
+            }
+
+            streams_permissions.insert(
+                stream
+                    .id
+                    .with_context(|| format!("Stream: {stream_name} has no ID"))?,
+                StreamPermissions {
+                    manage_stream: stream_permissions.manage_stream,
+                    read_stream: stream_permissions.read_stream,
+                    manage_topics: stream_permissions.manage_topics,
+                    read_topics: stream_permissions.read_topics,
+                    poll_messages: stream_permissions.poll_messages,
+                    send_messages: stream_permissions.send_messages,
+                    topics: (!topics_permissions.is_empty()).then_some(topics_permissions),
+                },
+            );
+        }
+
+        Ok(Permissions {
+            global: (&self.global).into(),
+            streams: (!streams_permissions.is_empty()).then_some(streams_permissions),
+        })
+    }
+}
+
+impl From<&GlobalPermissions> for GlobalPermissionsManifest {
+    fn from(permissions: &GlobalPermissions) -> Self {
+        GlobalPermissionsManifest {
+            manage_servers: permissions.manage_servers,
+            read_servers: permissions.read_servers,
+            manage_users: permissions.manage_users,
+            read_users: permissions.read_users,
+            manage_streams: permissions.manage_streams,
+            read_streams: permissions.read_streams,
+            manage_topics: permissions.manage_topics,
+            read_topics: permissions.read_topics,
+            poll_messages: permissions.poll_messages,
+            send_messages: permissions.send_messages,
+        }
+    }
+}
+
+impl From<&GlobalPermissionsManifest> for GlobalPermissions {
+    fn from(permissions: &GlobalPermissionsManifest) -> Self {
+        GlobalPermissions {
+            manage_servers: permissions.manage_servers,
+            read_servers: permissions.read_servers,
+            manage_users: permissions.manage_users,
+            read_users: permissions.read_users,
+            manage_streams: permissions.manage_streams,
+            read_streams: permissions.read_streams,
+            manage_topics: permissions.manage_topics,
+            read_topics: permissions.read_topics,
+            poll_messages: permissions.poll_messages,
+            send_messages: permissions.send_messages,
+        }
+    }
+}
+
+impl From<&TopicPermissions> for TopicPermissionsManifest {
+    fn from(permissions: &TopicPermissions) -> Self {
+        TopicPermissionsManifest {
+            manage_topic: permissions.manage_topic,
+            read_topic: permissions.read_topic,
+            poll_messages: permissions.poll_messages,
+            send_messages: permissions.send_messages,
+        }
+    }
+}
+
+impl From<&TopicPermissionsManifest> for TopicPermissions {
+    fn from(permissions: &TopicPermissionsManifest) -> Self {
+        TopicPermissions {
+            manage_topic: permissions.manage_topic,
+            read_topic: permissions.read_topic,
+            poll_messages: permissions.poll_messages,
+            send_messages: permissions.send_messages,
+        }
+    }
+}
+
+/// The maximum topic size is written in human-readable format when it's exact,
+/// otherwise as the number of bytes, so it's not rounded when the topology is exported.
+/// Parsing of the maximum topic size treats "unlimited" as the server default (zero bytes),
+/// hence it's handled explicitly.
+mod max_topic_size_format {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+    use std::str::FromStr;
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<MaxTopicSize>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(MaxTopicSize::Custom(size)) => {
+                let human = size.as_human_string();
+                match IggyByteSize::from_str(&human) {
+                    Ok(parsed) if parsed == *size => serializer.serialize_str(&human),
+                    _ => serializer.serialize_str(&size.as_bytes_u64().to_string()),
+                }
+            }
+            Some(value) => serializer.serialize_str(&value.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<MaxTopicSize>, D::Error> {
+        let value = Option::<String>::deserialize(deserializer)?;
+        value
+            .map(|value| match value.as_str() {
+                "unlimited" => Ok(MaxTopicSize::Unlimited),
+                value => MaxTopicSize::from_str(value).map_err(serde::de::Error::custom),
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::duration::IggyDuration;
+    use std::str::FromStr;
+
+    const TOML_MANIFEST: &str = r#"
+[[streams]]
+name = "orders"
+id = 7
+
+[[streams.topics]]
+name = "payments"
+partitions_count = 3
+message_expiry = "1h 30m"
+max_topic_size = "10 GB"
+compression_algorithm = "gzip"
+
+[[streams.topics.consumer_groups]]
+name = "billing"
+
+[[users]]
+username = "alice"
+password = "secret"
+status = "inactive"
+
+[users.permissions.global]
+read_streams = true
+
+[users.permissions.streams.orders]
+send_messages = true
+
+[users.permissions.streams.orders.topics.payments]
+poll_messages = true
+"#;
+
+    const YAML_MANIFEST: &str = r#"
+streams:
+  - name: orders
+    id: 7
+    topics:
+      - name: payments
+        partitions_count: 3
+        message_expiry: 1h 30m
+        max_topic_size: 10 GB
+        compression_algorithm: gzip
+        consumer_groups:
+          - name: billing
+users:
+  - username: alice
+    password: secret
+    status: inactive
+    permissions:
+      global:
+        read_streams: true
+      streams:
+        orders:
+          send_messages: true
+          topics:
+            payments:
+              poll_messages: true
+"#;
+
+    fn manifest() -> TopologyManifest {
+        TopologyManifest {
+            streams: Some(vec![StreamManifest {
+                name: "orders".to_string(),
+                id: Some(7),
+                topics: Some(vec![TopicManifest {
+                    name: "payments".to_string(),
+                    id: None,
+                    partitions_count: 3,
+                    message_expiry: Some(IggyExpiry::ExpireDuration(
+                        IggyDuration::from_str("1h 30m").unwrap(),
+                    )),
+                    max_topic_size: Some(MaxTopicSize::Custom(
+                        IggyByteSize::from_str("10 GB").unwrap(),
+                    )),
+                    compression_algorithm: Some(CompressionAlgorithm::Gzip),
+                    replication_factor: None,
+                    consumer_groups: Some(vec![ConsumerGroupManifest {
+                        name: "billing".to_string(),
+                        id: None,
+                    }]),
+                }]),
+            }]),
+            users: Some(vec![UserManifest {
+                username: "alice".to_string(),
+                password: Some("secret".to_string()),
+                status: Some(UserStatus::Inactive),
+                permissions: Some(PermissionsManifest {
+                    global: GlobalPermissionsManifest {
+                        read_streams: true,
+                        ..Default::default()
+                    },
+                    streams: BTreeMap::from([(
+                        "orders".to_string(),
+                        StreamPermissionsManifest {
+                            send_messages: true,
+                            topics: BTreeMap::from([(
+                                "payments".to_string(),
+                                TopicPermissionsManifest {
+                                    poll_messages: true,
+                                    ..Default::default()
+                                },
+                            )]),
+                            ..Default::default()
+                        },
+                    )]),
+                }),
+            }]),
+        }
+    }
+
+    #[test]
+    fn toml_manifest_should_be_parsed() {
+        let parsed = TopologyManifest::parse(TOML_MANIFEST, TopologyFormat::Toml).unwrap();
+
+        assert_eq!(parsed, manifest());
+    }
+
+    #[test]
+    fn yaml_manifest_should_be_parsed() {
+        let parsed = TopologyManifest::parse(YAML_MANIFEST, TopologyFormat::Yaml).unwrap();
+
+        assert_eq!(parsed, manifest());
+    }
+
+    #[test]
+    fn manifest_should_be_serialized_without_passwords() {
+        let mut expected = manifest();
+        expected.users.as_mut().unwrap()[0].password = None;
+
+        for format in [TopologyFormat::Toml, TopologyFormat::Yaml] {
+            let serialized = manifest().to_string(format).unwrap();
+
+            assert!(!serialized.contains("secret"));
+            assert_eq!(
+                TopologyManifest::parse(&serialized, format).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn inexact_max_topic_size_should_be_serialized_as_bytes() {
+        let manifest = TopologyManifest {
+            streams: Some(vec![StreamManifest {
+                name: "stream".to_string(),
+                id: None,
+                topics: Some(vec![TopicManifest {
+                    name: "topic".to_string(),
+                    partitions_count: 1,
+                    max_topic_size: Some(MaxTopicSize::from(1_234_567)),
+                    ..Default::default()
+                }]),
+            }]),
+            users: None,
+        };
+
+        let serialized = manifest.to_string(TopologyFormat::Toml).unwrap();
+
+        assert!(serialized.contains(r#"max_topic_size = "1234567""#));
+        assert_eq!(
+            TopologyManifest::parse(&serialized, TopologyFormat::Toml).unwrap(),
+            manifest
+        );
+    }
+
+    #[test]
+    fn unknown_fields_should_be_rejected() {
+        let content = "[[streams]]\nname = \"orders\"\npartitions = 3\n";
+
+        assert!(TopologyManifest::parse(content, TopologyFormat::Toml).is_err());
+    }
+
+    #[test]
+    fn format_should_be_detected_from_extension() {
+        assert_eq!(
+            TopologyFormat::from_path(Path::new("topology.yaml")),
+            TopologyFormat::Yaml
+        );
+        assert_eq!(
+            TopologyFormat::from_path(Path::new("topology.yml")),
+            TopologyFormat::Yaml
+        );
+        assert_eq!(
+            TopologyFormat::from_path(Path::new("topology.toml")),
+            TopologyFormat::Toml
+        );
+        assert_eq!(
+            TopologyFormat::from_path(Path::new("topology")),
+            TopologyFormat::Toml
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod apply_topology;
+pub mod export_topology;
+pub mod manifest;
+pub mod plan;
@@ -0,0 +1,766 @@
+use crate::cli::topology::manifest::{
+    PermissionsManifest, StreamManifest, TopicManifest, TopologyManifest, UserManifest,
+};
+use crate::compression::compression_algorithm::CompressionAlgorithm;
+use crate::models::user_status::UserStatus;
+use crate::utils::expiry::IggyExpiry;
+use crate::utils::topic_size::MaxTopicSize;
+use anyhow::Context;
+use serde::Serialize;
+use serde_with::{serde_as, DisplayFromStr};
+use std::collections::HashSet;
+use std::fmt::Display;
+
+/// The single change required to converge the server with the topology manifest.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TopologyChange {
+    CreateStream {
+        stream: String,
+        id: Option<u32>,
+    },
+    CreateTopic {
+        stream: String,
+        topic: String,
+        id: Option<u32>,
+        partitions_count: u32,
+        #[serde_as(as = "DisplayFromStr")]
+        message_expiry: IggyExpiry,
+        #[serde_as(as = "DisplayFromStr")]
+        max_topic_size: MaxTopicSize,
+        compression_algorithm: CompressionAlgorithm,
+        replication_factor: Option<u8>,
+    },
+    UpdateTopic {
+        stream: String,
+        topic: String,
+        #[serde_as(as = "DisplayFromStr")]
+        message_expiry: IggyExpiry,
+        #[serde_as(as = "DisplayFromStr")]
+        max_topic_size: MaxTopicSize,
+        compression_algorithm: CompressionAlgorithm,
+        replication_factor: u8,
+    },
+    CreatePartitions {
+        stream: String,
+        topic: String,
+        count: u32,
+    },
+    CreateConsumerGroup {
+        stream: String,
+        topic: String,
+        group: String,
+        id: Option<u32>,
+    },
+    DeleteConsumerGroup {
+        stream: String,
+        topic: String,
+        group: String,
+    },
+    DeletePartitions {
+        stream: String,
+        topic: String,
+        count: u32,
+    },
+    DeleteTopic {
+        stream: String,
+        topic: String,
+    },
+    DeleteStream {
+        stream: String,
+    },
+    CreateUser {
+        username: String,
+        #[serde(skip)]
+        password: String,
+        status: UserStatus,
+        permissions: Option<PermissionsManifest>,
+    },
+    UpdateUserStatus {
+        username: String,
+        status: UserStatus,
+    },
+    UpdatePermissions {
+        username: String,
+        permissions: PermissionsManifest,
+    },
+    DeleteUser {
+        username: String,
+    },
+}
+
+impl Display for TopologyChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TopologyChange::CreateStream { stream, .. } => write!(f, "+ create stream: {stream}"),
+            TopologyChange::CreateTopic {
+                stream,
+                topic,
+                partitions_count,
+                message_expiry,
+                max_topic_size,
+                compression_algorithm,
+                ..
+            } => write!(
+                f,
+                "+ create topic: {stream}/{topic} (partitions: {partitions_count}, message expiry: {message_expiry}, \
+                max topic size: {max_topic_size}, compression algorithm: {compression_algorithm})"
+            ),
+            TopologyChange::UpdateTopic {
+                stream,
+                topic,
+                message_expiry,
+                max_topic_size,
+                compression_algorithm,
+                replication_factor,
+            } => write!(
+                f,
+                "~ update topic: {stream}/{topic} (message expiry: {message_expiry}, max topic size: {max_topic_size}, \
+                compression algorithm: {compression_algorithm}, replication factor: {replication_factor})"
+            ),
+            TopologyChange::CreatePartitions {
+                stream,
+                topic,
+                count,
+            } => write!(f, "+ create {count} partitions in topic: {stream}/{topic}"),
+            TopologyChange::CreateConsumerGroup {
+                stream,
+                topic,
+                group,
+                ..
+            } => write!(f, "+ create consumer group: {stream}/{topic}/{group}"),
+            TopologyChange::DeleteConsumerGroup {
+                stream,
+                topic,
+                group,
+            } => write!(f, "- delete consumer group: {stream}/{topic}/{group}"),
+            TopologyChange::DeletePartitions {
+                stream,
+                topic,
+                count,
+            } => write!(f, "- delete {count} partitions in topic: {stream}/{topic}"),
+            TopologyChange::DeleteTopic { stream, topic } => {
+                write!(f, "- delete topic: {stream}/{topic}")
+            }
+            TopologyChange::DeleteStream { stream } => write!(f, "- delete stream: {stream}"),
+            TopologyChange::CreateUser {
+                username, status, ..
+            } => write!(f, "+ create user: {username} (status: {status})"),
+            TopologyChange::UpdateUserStatus { username, status } => {
+                write!(f, "~ update status of user: {username} to: {status}")
+            }
+            TopologyChange::UpdatePermissions { username, .. } => {
+                write!(f, "~ update permissions of user: {username}")
+            }
+            TopologyChange::DeleteUser { username } => write!(f, "- delete user: {username}"),
+        }
+    }
+}
+
+/// Computes the changes required to converge the current topology of the server with the desired one.
+///
+/// The streams, topics, partitions and consumer groups are created and updated first, then the deleted ones
+/// are removed and finally the users are converged, so their permissions can refer to the created streams.
+/// The protected users (e.g. the root user and the one applying the changes) are never deleted.
+pub fn plan(
+    desired: &TopologyManifest,
+    current: &TopologyManifest,
+    protected_users: &[String],
+) -> anyhow::Result<Vec<TopologyChange>> {
+    let mut changes = Vec::new();
+    let mut deletions = Vec::new();
+
+    if let Some(desired_streams) = &desired.streams {
+        ensure_unique(desired_streams.iter().map(|stream| &stream.name), "stream")?;
+        let current_streams = current.streams.as_deref().unwrap_or_default();
+        for stream in desired_streams {
+            match current_streams
+                .iter()
+                .find(|current| current.name == stream.name)
+            {
+                Some(current_stream) => {
+                    plan_topics(stream, current_stream, &mut changes, &mut deletions)?
+                }
+                None => {
+                    changes.push(TopologyChange::CreateStream {
+                        stream: stream.name.clone(),
+                        id: stream.id,
+                    });
+                    let empty_stream = StreamManifest {
+                        topics: Some(Vec::new()),
+                        ..Default::default()
+                    };
+                    plan_topics(stream, &empty_stream, &mut changes, &mut deletions)?;
+                }
+            }
+        }
+
+        for current_stream in current_streams {
+            if !desired_streams
+                .iter()
+                .any(|stream| stream.name == current_stream.name)
+            {
+                deletions.push(TopologyChange::DeleteStream {
+                    stream: current_stream.name.clone(),
+                });
+            }
+        }
+    }
+
+    changes.append(&mut deletions);
+
+    if let Some(desired_users) = &desired.users {
+        ensure_unique(desired_users.iter().map(|user| &user.username), "user")?;
+        let current_users = current.users.as_deref().unwrap_or_default();
+        plan_users(desired_users, current_users, protected_users, &mut changes)?;
+    }
+
+    Ok(changes)
+}
+
+fn plan_topics(
+    stream: &StreamManifest,
+    current_stream: &StreamManifest,
+    changes: &mut Vec<TopologyChange>,
+    deletions: &mut Vec<TopologyChange>,
+) -> anyhow::Result<()> {
+    let Some(desired_topics) = &stream.topics else {
+        return Ok(());
+    };
+
+    ensure_unique(desired_topics.iter().map(|topic| &topic.name), "topic")?;
+    let current_topics = current_stream.topics.as_deref().unwrap_or_default();
+    for topic in desired_topics {
+        let current_topic = current_topics
+            .iter()
+            .find(|current| current.name == topic.name);
+        let current_groups = match current_topic {
+            Some(current_topic) => {
+                plan_topic_update(&stream.name, topic, current_topic, changes, deletions);
+                current_topic.consumer_groups.as_deref().unwrap_or_default()
+            }
+            None => {
+                changes.push(TopologyChange::CreateTopic {
+                    stream: stream.name.clone(),
+                    topic: topic.name.clone(),
+                    id: topic.id,
+                    partitions_count: topic.partitions_count,
+                    message_expiry: topic.message_expiry.unwrap_or_default(),
+                    max_topic_size: topic.max_topic_size.unwrap_or_default(),
+                    compression_algorithm: topic.compression_algorithm.unwrap_or_default(),
+                    replication_factor: topic.replication_factor,
+                });
+                &[]
+            }
+        };
+
+        let Some(desired_groups) = &topic.consumer_groups else {
+            continue;
+        };
+        ensure_unique(
+            desired_groups.iter().map(|group| &group.name),
+            "consumer group",
+        )?;
+        for group in desired_groups {
+            if !current_groups
+                .iter()
+                .any(|current| current.name == group.name)
+            {
+                changes.push(TopologyChange::CreateConsumerGroup {
+                    stream: stream.name.clone(),
+                    topic: topic.name.clone(),
+                    group: group.name.clone(),
+                    id: group.id,
+                });
+            }
+        }
+        for current_group in current_groups {
+            if !desired_groups
+                .iter()
+                .any(|group| group.name == current_group.name)
+            {
+                deletions.push(TopologyChange::DeleteConsumerGroup {
+                    stream: stream.name.clone(),
+                    topic: topic.name.clone(),
+                    group: current_group.name.clone(),
+                });
+            }
+        }
+    }
+
+    for current_topic in current_topics {
+        if !desired_topics
+            .iter()
+            .any(|topic| topic.name == current_topic.name)
+        {
+            deletions.push(TopologyChange::DeleteTopic {
+                stream: stream.name.clone(),
+                topic: current_topic.name.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn plan_topic_update(
+    stream: &str,
+    topic: &TopicManifest,
+    current_topic: &TopicManifest,
+    changes: &mut Vec<TopologyChange>,
+    deletions: &mut Vec<TopologyChange>,
+) {
+    // The server resolves the default settings when the topic is created,
+    // hence they are compared only when they are set explicitly.
+    let message_expiry = topic
+        .message_expiry
+        .filter(|expiry| *expiry != IggyExpiry::ServerDefault)
+        .or(current_topic.message_expiry)
+        .unwrap_or_default();
+    let max_topic_size = topic
+        .max_topic_size
+        .filter(|size| *size != MaxTopicSize::ServerDefault)
+        .or(current_topic.max_topic_size)
+        .unwrap_or_default();
+    let compression_algorithm = topic
+        .compression_algorithm
+        .or(current_topic.compression_algorithm)
+        .unwrap_or_default();
+    let replication_factor = topic
+        .replication_factor
+        .or(current_topic.replication_factor)
+        .unwrap_or_default();
+
+    if Some(message_expiry) != current_topic.message_expiry
+        || Some(max_topic_size) != current_topic.max_topic_size
+        || Some(compression_algorithm) != current_topic.compression_algorithm
+        || Some(replication_factor) != current_topic.replication_factor
+    {
+        changes.push(TopologyChange::UpdateTopic {
+            stream: stream.to_owned(),
+            topic: topic.name.clone(),
+            message_expiry,
+            max_topic_size,
+            compression_algorithm,
+            replication_factor,
+        });
+    }
+
+    if topic.partitions_count > current_topic.partitions_count {
+        changes.push(TopologyChange::CreatePartitions {
+            stream: stream.to_owned(),
+            topic: topic.name.clone(),
+            count: topic.partitions_count - current_topic.partitions_count,
+        });
+    } else if topic.partitions_count < current_topic.partitions_count {
+        deletions.push(TopologyChange::DeletePartitions {
+            stream: stream.to_owned(),
+            topic: topic.name.clone(),
+            count: current_topic.partitions_count - topic.partitions_count,
+        });
+    }
+}
+
+fn plan_users(
+    desired_users: &[UserManifest],
+    current_users: &[UserManifest],
+    protected_users: &[String],
+    changes: &mut Vec<TopologyChange>,
+) -> anyhow::Result<()> {
+    for user in desired_users {
+        let Some(current_user) = current_users
+            .iter()
+            .find(|current| current.username == user.username)
+        else {
+            changes.push(TopologyChange::CreateUser {
+                username: user.username.clone(),
+                password: user.password.clone().with_context(|| {
+                    format!("Password is required to create user: {}", user.username)
+                })?,
+                status: user.status.unwrap_or_default(),
+                permissions: user.permissions.clone(),
+            });
+            continue;
+        };
+
+        if let Some(status) = user.status {
+            if current_user.status != Some(status) {
+                changes.push(TopologyChange::UpdateUserStatus {
+                    username: user.username.clone(),
+                    status,
+                });
+            }
+        }
+
+        if let Some(permissions) = &user.permissions {
+            if *permissions != current_user.permissions.clone().unwrap_or_default() {
+                changes.push(TopologyChange::UpdatePermissions {
+                    username: user.username.clone(),
+                    permissions: permissions.clone(),
+                });
+            }
+        }
+    }
+
+    for current_user in current_users {
+        if !protected_users.contains(&current_user.username)
+            && !desired_users
+                .iter()
+                .any(|user| user.username == current_user.username)
+        {
+            changes.push(TopologyChange::DeleteUser {
+                username: current_user.username.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn ensure_unique<'a>(names: impl Iterator<Item = &'a String>, kind: &str) -> anyhow::Result<()> {
+    let mut unique = HashSet::new();
+    for name in names {
+        if !unique.insert(name) {
+            anyhow::bail!("Duplicated {kind}: {name} in topology manifest");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::topology::manifest::{
+        ConsumerGroupManifest, GlobalPermissionsManifest, TopologyFormat,
+    };
+    use crate::utils::duration::IggyDuration;
+    use std::str::FromStr;
+
+    fn current() -> TopologyManifest {
+        TopologyManifest::parse(
+            r#"
+[[streams]]
+name = "orders"
+id = 1
+
+[[streams.topics]]
+name = "payments"
+id = 1
+partitions_count = 3
+message_expiry = "none"
+max_topic_size = "unlimited"
+compression_algorithm = "none"
+replication_factor = 1
+consumer_groups = [{ name = "billing", id = 1 }, { name = "audit", id = 2 }]
+
+[[streams.topics]]
+name = "refunds"
+id = 2
+partitions_count = 1
+message_expiry = "none"
+max_topic_size = "unlimited"
+compression_algorithm = "none"
+replication_factor = 1
+consumer_groups = []
+
+[[streams]]
+name = "legacy"
+id = 2
+topics = []
+
+[[users]]
+username = "iggy"
+status = "active"
+
+[[users]]
+username = "alice"
+status = "active"
+
+[[users]]
+username = "bob"
+status = "active"
+"#,
+            TopologyFormat::Toml,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn current_topology_should_have_no_changes() {
+        let current = current();
+
+        assert!(plan(&current, &current, &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn empty_manifest_should_have_no_changes() {
+        let changes = plan(&TopologyManifest::default(), &current(), &[]).unwrap();
+
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn changes_should_converge_to_desired_topology() {
+        let desired = TopologyManifest::parse(
+            r#"
+[[streams]]
+name = "orders"
+
+[[streams.topics]]
+name = "payments"
+partitions_count = 5
+message_expiry = "1h"
+consumer_groups = [{ name = "billing" }, { name = "shipping", id = 3 }]
+
+[[streams]]
+name = "events"
+id = 5
+
+[[streams.topics]]
+name = "clicks"
+partitions_count = 2
+consumer_groups = [{ name = "analytics" }]
+
+[[users]]
+username = "alice"
+status = "inactive"
+permissions = { global = { read_streams = true } }
+
+[[users]]
+username = "carol"
+password = "secret"
+"#,
+            TopologyFormat::Toml,
+        )
+        .unwrap();
+
+        let changes = plan(&desired, &current(), &["iggy".to_string()]).unwrap();
+
+        assert_eq!(
+            changes,
+            vec![
+                TopologyChange::UpdateTopic {
+                    stream: "orders".to_string(),
+                    topic: "payments".to_string(),
+                    message_expiry: IggyExpiry::ExpireDuration(
+                        IggyDuration::from_str("1h").unwrap()
+                    ),
+                    max_topic_size: MaxTopicSize::Unlimited,
+                    compression_algorithm: CompressionAlgorithm::None,
+                    replication_factor: 1,
+                },
+                TopologyChange::CreatePartitions {
+                    stream: "orders".to_string(),
+                    topic: "payments".to_string(),
+                    count: 2,
+                },
+                TopologyChange::CreateConsumerGroup {
+                    stream: "orders".to_string(),
+                    topic: "payments".to_string(),
+                    group: "shipping".to_string(),
+                    id: Some(3),
+                },
+                TopologyChange::CreateStream {
+                    stream: "events".to_string(),
+                    id: Some(5),
+                },
+                TopologyChange::CreateTopic {
+                    stream: "events".to_string(),
+                    topic: "clicks".to_string(),
+                    id: None,
+                    partitions_count: 2,
+                    message_expiry: IggyExpiry::ServerDefault,
+                    max_topic_size: MaxTopicSize::ServerDefault,
+                    compression_algorithm: CompressionAlgorithm::None,
+                    replication_factor: None,
+                },
+                TopologyChange::CreateConsumerGroup {
+                    stream: "events".to_string(),
+                    topic: "clicks".to_string(),
+                    group: "analytics".to_string(),
+                    id: None,
+                },
+                TopologyChange::DeleteConsumerGroup {
+                    stream: "orders".to_string(),
+                    topic: "payments".to_string(),
+                    group: "audit".to_string(),
+                },
+                TopologyChange::DeleteTopic {
+                    stream: "orders".to_string(),
+                    topic: "refunds".to_string(),
+                },
+                TopologyChange::DeleteStream {
+                    stream: "legacy".to_string(),
+                },
+                TopologyChange::UpdateUserStatus {
+                    username: "alice".to_string(),
+                    status: UserStatus::Inactive,
+                },
+                TopologyChange::UpdatePermissions {
+                    username: "alice".to_string(),
+                    permissions: PermissionsManifest {
+                        global: GlobalPermissionsManifest {
+                            read_streams: true,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                },
+                TopologyChange::CreateUser {
+                    username: "carol".to_string(),
+                    password: "secret".to_string(),
+                    status: UserStatus::Active,
+                    permissions: None,
+                },
+                TopologyChange::DeleteUser {
+                    username: "bob".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn server_defaults_and_omitted_sections_should_not_be_compared() {
+        let desired = TopologyManifest {
+            streams: Some(vec![
+                StreamManifest {
+                    name: "orders".to_string(),
+                    id: None,
+                    topics: Some(vec![
+                        TopicManifest {
+                            name: "payments".to_string(),
+                            partitions_count: 3,
+                            message_expiry: Some(IggyExpiry::ServerDefault),
+                            max_topic_size: Some(MaxTopicSize::ServerDefault),
+                            ..Default::default()
+                        },
+                        TopicManifest {
+                            name: "refunds".to_string(),
+                            partitions_count: 1,
+                            consumer_groups: Some(vec![]),
+                            ..Default::default()
+                        },
+                    ]),
+                },
+                StreamManifest {
+                    name: "legacy".to_string(),
+                    id: None,
+                    topics: None,
+                },
+            ]),
+            users: None,
+        };
+
+        assert!(plan(&desired, &current(), &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn partitions_should_be_deleted_after_creations() {
+        let mut desired = current();
+        let streams = desired.streams.as_mut().unwrap();
+        let topics = streams[0].topics.as_mut().unwrap();
+        topics[0].partitions_count = 1;
+        topics[1].consumer_groups = Some(vec![ConsumerGroupManifest {
+            name: "refunds".to_string(),
+            id: None,
+        }]);
+
+        let changes = plan(&desired, &current(), &[]).unwrap();
+
+        assert_eq!(
+            changes,
+            vec![
+                TopologyChange::CreateConsumerGroup {
+                    stream: "orders".to_string(),
+                    topic: "refunds".to_string(),
+                    group: "refunds".to_string(),
+                    id: None,
+                },
+                TopologyChange::DeletePartitions {
+                    stream: "orders".to_string(),
+                    topic: "payments".to_string(),
+                    count: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn protected_users_should_not_be_deleted() {
+        let desired = TopologyManifest {
+            streams: None,
+            users: Some(vec![]),
+        };
+
+        let changes = plan(
+            &desired,
+            &current(),
+            &["iggy".to_string(), "bob".to_string()],
+        )
+        .unwrap();
+
+        assert_eq!(
+            changes,
+            vec![TopologyChange::DeleteUser {
+                username: "alice".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn new_user_without_password_should_fail() {
+        let desired = TopologyManifest {
+            streams: None,
+            users: Some(vec![UserManifest {
+                username: "carol".to_string(),
+                ..Default::default()
+            }]),
+        };
+
+        assert!(plan(&desired, &current(), &[]).is_err());
+    }
+
+    #[test]
+    fn duplicated_names_should_fail() {
+        let stream = StreamManifest {
+            name: "orders".to_string(),
+            id: None,
+            topics: None,
+        };
+        let desired = TopologyManifest {
+            streams: Some(vec![stream.clone(), stream]),
+            users: None,
+        };
+
+        assert!(plan(&desired, &current(), &[]).is_err());
+    }
+
+    #[test]
+    fn changes_should_be_displayed_as_plan() {
+        let changes = [
+            TopologyChange::CreateStream {
+                stream: "events".to_string(),
+                id: None,
+            },
+            TopologyChange::UpdateUserStatus {
+                username: "alice".to_string(),
+                status: UserStatus::Inactive,
+            },
+            TopologyChange::DeletePartitions {
+                stream: "orders".to_string(),
+                topic: "payments".to_string(),
+                count: 2,
+            },
+        ];
+
+        assert_eq!(
+            changes.map(|change| change.to_string()),
+            [
+                "+ create stream: events",
+                "~ update status of user: alice to: inactive",
+                "- delete 2 partitions in topic: orders/payments",
+            ]
+        );
+    }
+}
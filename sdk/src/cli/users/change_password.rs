@@ -1,10 +1,10 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use anyhow::Context;
 use async_trait::async_trait;
 use passterm::{isatty, prompt_password_stdin, prompt_password_tty, Stream};
-use tracing::{event, Level};
 
 pub struct ChangePasswordCmd {
     user_id: Identifier,
@@ -40,7 +40,11 @@ impl CliCommand for ChangePasswordCmd {
         self.use_tracing()
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let current_password = match &self.current_password {
             Some(password) => password.clone(),
             None => {
@@ -73,10 +77,11 @@ impl CliCommand for ChangePasswordCmd {
                 )
             })?;
 
-        if self.use_tracing() {
-            event!(target: PRINT_TARGET, Level::INFO, "Password for user with ID: {} changed", self.user_id);
+        let message = format!("Password for user with ID: {} changed", self.user_id);
+        if self.use_tracing() || !output.is_table() {
+            print_message(output, &message)?;
         } else {
-            println!("Password for user with ID: {} changed", self.user_id);
+            println!("{message}");
         }
 
         Ok(())
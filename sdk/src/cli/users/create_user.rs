@@ -1,11 +1,11 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::models::permissions::Permissions;
 use crate::models::user_status::UserStatus;
 use crate::users::create_user::CreateUser;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct CreateUserCmd {
     create_user: CreateUser,
@@ -38,7 +38,11 @@ impl CliCommand for CreateUserCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .create_user(
                 &self.create_user.username,
@@ -54,10 +58,13 @@ impl CliCommand for CreateUserCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "User with username: {} and password: {} created",
-            self.create_user.username, self.create_user.password
-        );
+        print_message(
+            output,
+            &format!(
+                "User with username: {} and password: {} created",
+                self.create_user.username, self.create_user.password
+            ),
+        )?;
 
         Ok(())
     }
@@ -1,10 +1,10 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::users::delete_user::DeleteUser;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct DeleteUserCmd {
     delete_user: DeleteUser,
@@ -24,7 +24,11 @@ impl CliCommand for DeleteUserCmd {
         format!("delete user with ID: {}", self.delete_user.user_id)
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .delete_user(&self.delete_user.user_id)
             .await
@@ -35,7 +39,10 @@ impl CliCommand for DeleteUserCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO, "User with ID: {} deleted", self.delete_user.user_id);
+        print_message(
+            output,
+            &format!("User with ID: {} deleted", self.delete_user.user_id),
+        )?;
 
         Ok(())
     }
@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::models::permissions::{GlobalPermissions, StreamPermissions, TopicPermissions};
@@ -135,12 +136,20 @@ impl CliCommand for GetUserCmd {
         format!("get user with ID: {}", self.get_user.user_id)
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let user = client
             .get_user(&self.get_user.user_id)
             .await
             .with_context(|| format!("Problem getting user with ID: {}", self.get_user.user_id))?;
 
+        if !output.is_table() {
+            return print_output(output, &user);
+        }
+
         let mut table = Table::new();
 
         table.set_header(vec!["Property", "Value"]);
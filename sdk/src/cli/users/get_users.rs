@@ -1,4 +1,5 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_output;
+use crate::cli_command::{CliCommand, OutputFormat, PRINT_TARGET};
 use crate::client::Client;
 use crate::users::get_users::GetUsers;
 use anyhow::Context;
@@ -44,12 +45,20 @@ impl CliCommand for GetUsersCmd {
         format!("list users in {mode} mode")
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         let users = client
             .get_users()
             .await
             .with_context(|| String::from("Problem getting list of users"))?;
 
+        if !output.is_table() {
+            return print_output(output, &users);
+        }
+
         if users.is_empty() {
             event!(target: PRINT_TARGET, Level::INFO, "No users found!");
             return Ok(());
@@ -3,5 +3,7 @@ pub mod create_user;
 pub mod delete_user;
 pub mod get_user;
 pub mod get_users;
+pub mod unlock_user;
 pub mod update_permissions;
+pub mod update_roles;
 pub mod update_user;
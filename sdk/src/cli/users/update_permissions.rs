@@ -1,11 +1,11 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::models::permissions::Permissions;
 use crate::users::update_permissions::UpdatePermissions;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 pub struct UpdatePermissionsCmd {
     update_permissions: UpdatePermissions,
@@ -31,7 +31,11 @@ impl CliCommand for UpdatePermissionsCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .update_permissions(
                 &self.update_permissions.user_id,
@@ -45,10 +49,13 @@ impl CliCommand for UpdatePermissionsCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "Permissions for user with ID: {} updated",
-            self.update_permissions.user_id
-        );
+        print_message(
+            output,
+            &format!(
+                "Permissions for user with ID: {} updated",
+                self.update_permissions.user_id
+            ),
+        )?;
 
         Ok(())
     }
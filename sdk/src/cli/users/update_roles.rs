@@ -0,0 +1,53 @@
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
+use crate::client::Client;
+use crate::identifier::Identifier;
+use anyhow::Context;
+use async_trait::async_trait;
+
+pub struct UpdateRolesCmd {
+    user_id: Identifier,
+    roles: Vec<Identifier>,
+}
+
+impl UpdateRolesCmd {
+    pub fn new(user_id: Identifier, roles: Vec<Identifier>) -> Self {
+        Self { user_id, roles }
+    }
+}
+
+#[async_trait]
+impl CliCommand for UpdateRolesCmd {
+    fn explain(&self) -> String {
+        format!("update roles for user with ID: {}", self.user_id)
+    }
+
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
+        let mut role_ids = Vec::with_capacity(self.roles.len());
+        for role_id in &self.roles {
+            let role = client
+                .get_role(role_id)
+                .await
+                .with_context(|| format!("Problem getting role with ID: {}", role_id))?;
+            role_ids.push(role.id);
+        }
+
+        client
+            .update_roles(&self.user_id, role_ids)
+            .await
+            .with_context(|| {
+                format!("Problem updating roles for user with ID: {}", self.user_id)
+            })?;
+
+        print_message(
+            output,
+            &format!("Roles for user with ID: {} updated", self.user_id),
+        )?;
+
+        Ok(())
+    }
+}
@@ -1,11 +1,11 @@
-use crate::cli_command::{CliCommand, PRINT_TARGET};
+use crate::cli::utils::output::print_message;
+use crate::cli_command::{CliCommand, OutputFormat};
 use crate::client::Client;
 use crate::identifier::Identifier;
 use crate::models::user_status::UserStatus;
 use crate::users::update_user::UpdateUser;
 use anyhow::Context;
 use async_trait::async_trait;
-use tracing::{event, Level};
 
 #[derive(Debug, Clone)]
 pub enum UpdateUserType {
@@ -53,7 +53,11 @@ impl CliCommand for UpdateUserCmd {
         )
     }
 
-    async fn execute_cmd(&mut self, client: &dyn Client) -> anyhow::Result<(), anyhow::Error> {
+    async fn execute_cmd(
+        &mut self,
+        client: &dyn Client,
+        output: OutputFormat,
+    ) -> anyhow::Result<(), anyhow::Error> {
         client
             .update_user(
                 &self.update_user.user_id,
@@ -69,10 +73,14 @@ impl CliCommand for UpdateUserCmd {
                 )
             })?;
 
-        event!(target: PRINT_TARGET, Level::INFO,
-            "User with ID: {} updated with {}",
-            self.update_user.user_id, self.get_message()
-        );
+        print_message(
+            output,
+            &format!(
+                "User with ID: {} updated with {}",
+                self.update_user.user_id,
+                self.get_message()
+            ),
+        )?;
 
         Ok(())
     }
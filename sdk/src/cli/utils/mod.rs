@@ -1 +1,2 @@
 pub mod login_session_expiry;
+pub mod output;
@@ -0,0 +1,232 @@
+use crate::cli_command::{OutputFormat, PRINT_TARGET};
+use anyhow::Context;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use tracing::{event, Level};
+
+const CSV_VALUE_COLUMN: &str = "value";
+
+/// Prints the data in the requested machine-readable format, the data is serialized
+/// with the same schema as the one returned by the HTTP API.
+/// The table format is rendered by the commands themselves, hence it's not supported here.
+pub fn print_output<T: Serialize + ?Sized>(output: OutputFormat, data: &T) -> anyhow::Result<()> {
+    let formatted = format_output(output, data)?;
+    event!(target: PRINT_TARGET, Level::INFO, "{}", formatted.trim_end());
+    Ok(())
+}
+
+/// Prints the message describing the result of the command, which doesn't return any data.
+/// For the machine-readable formats the message is wrapped in the object with the `message` field.
+pub fn print_message(output: OutputFormat, message: &str) -> anyhow::Result<()> {
+    if output.is_table() {
+        event!(target: PRINT_TARGET, Level::INFO, "{message}");
+        return Ok(());
+    }
+
+    print_output(output, &json!({ "message": message }))
+}
+
+/// Prints the single item of the continuously printed results, e.g. the followed messages,
+/// so each item can be processed as soon as it's printed. JSON items are printed as separate lines (JSON Lines),
+/// YAML items as separate documents and the CSV header is printed only before the first item.
+pub fn print_output_item<T: Serialize + ?Sized>(
+    output: OutputFormat,
+    item: &T,
+    first: bool,
+) -> anyhow::Result<()> {
+    let formatted = format_output_item(output, item, first)?;
+    event!(target: PRINT_TARGET, Level::INFO, "{}", formatted.trim_end());
+    Ok(())
+}
+
+fn format_output_item<T: Serialize + ?Sized>(
+    output: OutputFormat,
+    item: &T,
+    first: bool,
+) -> anyhow::Result<String> {
+    let formatted = match output {
+        OutputFormat::Json => {
+            serde_json::to_string(item).with_context(|| "Problem serializing output to JSON")?
+        }
+        OutputFormat::Yaml => format!("---\n{}", format_output(output, item)?),
+        OutputFormat::Csv if !first => format_output(output, item)?
+            .split_once('\n')
+            .map(|(_, record)| record.to_owned())
+            .unwrap_or_default(),
+        _ => format_output(output, item)?,
+    };
+    Ok(formatted)
+}
+
+pub fn format_output<T: Serialize + ?Sized>(
+    output: OutputFormat,
+    data: &T,
+) -> anyhow::Result<String> {
+    match output {
+        OutputFormat::Table => anyhow::bail!("Table output must be rendered by the command"),
+        OutputFormat::Json => {
+            serde_json::to_string_pretty(data).with_context(|| "Problem serializing output to JSON")
+        }
+        OutputFormat::Yaml => {
+            serde_yaml::to_string(data).with_context(|| "Problem serializing output to YAML")
+        }
+        OutputFormat::Csv => {
+            let value =
+                serde_json::to_value(data).with_context(|| "Problem serializing output to CSV")?;
+            Ok(to_csv(value))
+        }
+    }
+}
+
+/// Converts the value into CSV with the header row. Each item of the array is a separate row,
+/// the columns are the fields of the objects sorted by name (the order of the fields isn't preserved in the JSON map),
+/// and the fields missing in the preceding rows are appended in order of their first occurrence.
+/// Nested objects and arrays are written as JSON, null values as empty cells.
+fn to_csv(value: Value) -> String {
+    let rows = match value {
+        Value::Array(items) => items.into_iter().map(to_row).collect::<Vec<_>>(),
+        value => vec![to_row(value)],
+    };
+
+    let mut columns: Vec<String> = Vec::new();
+    for row in &rows {
+        for key in row.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+
+    let mut csv = String::new();
+    csv.push_str(&to_record(columns.iter().map(|column| column.as_str())));
+    for row in &rows {
+        let cells = columns
+            .iter()
+            .map(|column| row.get(column).map(to_cell).unwrap_or_default())
+            .collect::<Vec<_>>();
+        csv.push_str(&to_record(cells.iter().map(|cell| cell.as_str())));
+    }
+    csv
+}
+
+fn to_row(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(fields) => fields,
+        value => Map::from_iter([(CSV_VALUE_COLUMN.to_string(), value)]),
+    }
+}
+
+fn to_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn to_record<'a>(cells: impl Iterator<Item = &'a str>) -> String {
+    let mut record = cells.map(escape_cell).collect::<Vec<_>>().join(",");
+    record.push('\n');
+    record
+}
+
+fn escape_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Item {
+        id: u32,
+        name: String,
+        tags: Vec<String>,
+        parent: Option<u32>,
+    }
+
+    #[test]
+    fn array_should_be_formatted_as_csv_row_per_item() {
+        let items = vec![
+            Item {
+                id: 1,
+                name: "first".to_string(),
+                tags: vec!["a".to_string(), "b".to_string()],
+                parent: None,
+            },
+            Item {
+                id: 2,
+                name: "second, \"quoted\"".to_string(),
+                tags: vec![],
+                parent: Some(1),
+            },
+        ];
+
+        let csv = format_output(OutputFormat::Csv, &items).unwrap();
+
+        assert_eq!(
+            csv,
+            "id,name,parent,tags\n1,first,,\"[\"\"a\"\",\"\"b\"\"]\"\n2,\"second, \"\"quoted\"\"\",1,[]\n"
+        );
+    }
+
+    #[test]
+    fn object_should_be_formatted_as_single_csv_row() {
+        let csv = format_output(OutputFormat::Csv, &json!({ "message": "done" })).unwrap();
+
+        assert_eq!(csv, "message\ndone\n");
+    }
+
+    #[test]
+    fn scalars_should_be_formatted_as_csv_value_column() {
+        let csv = format_output(OutputFormat::Csv, &[1, 2]).unwrap();
+
+        assert_eq!(csv, "value\n1\n2\n");
+    }
+
+    #[test]
+    fn data_should_be_formatted_as_json_and_yaml() {
+        let data = json!({ "id": 1, "name": "stream" });
+
+        assert_eq!(
+            format_output(OutputFormat::Json, &data).unwrap(),
+            "{\n  \"id\": 1,\n  \"name\": \"stream\"\n}"
+        );
+        assert_eq!(
+            format_output(OutputFormat::Yaml, &data).unwrap(),
+            "id: 1\nname: stream\n"
+        );
+    }
+
+    #[test]
+    fn items_should_be_formatted_one_by_one() {
+        let item = json!({ "id": 2, "name": "second" });
+
+        assert_eq!(
+            format_output_item(OutputFormat::Csv, &item, true).unwrap(),
+            "id,name\n2,second\n"
+        );
+        assert_eq!(
+            format_output_item(OutputFormat::Csv, &item, false).unwrap(),
+            "2,second\n"
+        );
+        assert_eq!(
+            format_output_item(OutputFormat::Json, &item, false).unwrap(),
+            r#"{"id":2,"name":"second"}"#
+        );
+        assert_eq!(
+            format_output_item(OutputFormat::Yaml, &item, false).unwrap(),
+            "---\nid: 2\nname: second\n"
+        );
+    }
+
+    #[test]
+    fn table_should_not_be_formatted() {
+        assert!(format_output(OutputFormat::Table, &json!({})).is_err());
+    }
+}
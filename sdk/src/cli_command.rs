@@ -1,9 +1,38 @@
 use crate::client::Client;
 use anyhow::{Error, Result};
 use async_trait::async_trait;
+use std::fmt::Display;
 
 pub static PRINT_TARGET: &str = "iggy::cli::output";
 
+/// The format of the command output. The table format is meant to be read by humans,
+/// while the other ones are meant for automation and follow the schemas of the SDK models.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Csv,
+}
+
+impl OutputFormat {
+    pub fn is_table(&self) -> bool {
+        *self == OutputFormat::Table
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Table => write!(f, "table"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Yaml => write!(f, "yaml"),
+            OutputFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
 #[async_trait]
 pub trait CliCommand {
     fn explain(&self) -> String;
@@ -16,5 +45,6 @@ pub trait CliCommand {
     fn connection_required(&self) -> bool {
         true
     }
-    async fn execute_cmd(&mut self, client: &dyn Client) -> Result<(), Error>;
+    async fn execute_cmd(&mut self, client: &dyn Client, output: OutputFormat)
+        -> Result<(), Error>;
 }
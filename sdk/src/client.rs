@@ -4,17 +4,21 @@ use crate::error::IggyError;
 use crate::identifier::Identifier;
 use crate::messages::poll_messages::PollingStrategy;
 use crate::messages::send_messages::{Message, Partitioning};
+use crate::models::audit_log::AuditLogEntry;
+use crate::models::backup_info::BackupInfo;
 use crate::models::client_info::{ClientInfo, ClientInfoDetails};
 use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails};
+use crate::models::consumer_lag_info::ConsumerLagInfo;
 use crate::models::consumer_offset_info::ConsumerOffsetInfo;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::messages::PolledMessages;
 use crate::models::permissions::Permissions;
 use crate::models::personal_access_token::{PersonalAccessTokenInfo, RawPersonalAccessToken};
+use crate::models::role_info::{RoleId, RoleInfo, RoleInfoDetails};
 use crate::models::stats::Stats;
 use crate::models::stream::{Stream, StreamDetails};
 use crate::models::topic::{Topic, TopicDetails};
-use crate::models::user_info::{UserInfo, UserInfoDetails};
+use crate::models::user_info::{UserId, UserInfo, UserInfoDetails};
 use crate::models::user_status::UserStatus;
 use crate::utils::expiry::IggyExpiry;
 use crate::utils::personal_access_token_expiry::PersonalAccessTokenExpiry;
@@ -29,6 +33,7 @@ use std::fmt::Debug;
 pub trait Client:
     SystemClient
     + UserClient
+    + RoleClient
     + PersonalAccessTokenClient
     + StreamClient
     + TopicClient
@@ -67,6 +72,23 @@ pub trait SystemClient {
     ///
     /// Authentication is required, and the permission to read the server info.
     async fn get_clients(&self) -> Result<Vec<ClientInfo>, IggyError>;
+    /// Get the most recent entries of the audit log, optionally filtered by the unique user ID.
+    ///
+    /// Authentication is required, and the permission to manage the servers.
+    async fn get_audit_log(
+        &self,
+        count: u32,
+        user_id: Option<UserId>,
+    ) -> Result<Vec<AuditLogEntry>, IggyError>;
+    /// Create a consistent backup of the data directory, optionally uploaded by the configured archiver.
+    /// If the name is not provided, it's generated based on the current timestamp.
+    ///
+    /// Authentication is required, and the permission to manage the servers.
+    async fn create_backup(
+        &self,
+        name: Option<&str>,
+        archive: bool,
+    ) -> Result<BackupInfo, IggyError>;
     /// Ping the server to check if it's alive.
     async fn ping(&self) -> Result<(), IggyError>;
 }
@@ -113,6 +135,16 @@ pub trait UserClient {
         user_id: &Identifier,
         permissions: Option<Permissions>,
     ) -> Result<(), IggyError>;
+    /// Replace the roles assigned to a user by unique ID or username.
+    /// The effective permissions of the user are the union of its own permissions and the permissions of all its roles.
+    ///
+    /// Authentication is required, and the permission to manage the users.
+    async fn update_roles(&self, user_id: &Identifier, roles: Vec<RoleId>)
+        -> Result<(), IggyError>;
+    /// Unlock a user by unique ID or username, who was locked due to too many failed login attempts.
+    ///
+    /// Authentication is required, and the permission to manage the users.
+    async fn unlock_user(&self, user_id: &Identifier) -> Result<(), IggyError>;
     /// Change the password of a user by unique ID or username.
     ///
     /// Authentication is required, and the permission to manage the users, unless the provided user ID is the same as the authenticated user.
@@ -128,6 +160,40 @@ pub trait UserClient {
     async fn logout_user(&self) -> Result<(), IggyError>;
 }
 
+/// This trait defines the methods to interact with the role module.
+#[async_trait]
+pub trait RoleClient {
+    /// Get the info about a specific role by unique ID or name.
+    ///
+    /// Authentication is required, and the permission to read the users.
+    async fn get_role(&self, role_id: &Identifier) -> Result<RoleInfoDetails, IggyError>;
+    /// Get the info about all the roles.
+    ///
+    /// Authentication is required, and the permission to read the users.
+    async fn get_roles(&self) -> Result<Vec<RoleInfo>, IggyError>;
+    /// Create a new role.
+    ///
+    /// Authentication is required, and the permission to manage the users.
+    async fn create_role(
+        &self,
+        name: &str,
+        permissions: Option<Permissions>,
+    ) -> Result<(), IggyError>;
+    /// Delete a role by unique ID or name. The role is also removed from all the users it was assigned to.
+    ///
+    /// Authentication is required, and the permission to manage the users.
+    async fn delete_role(&self, role_id: &Identifier) -> Result<(), IggyError>;
+    /// Update the name and/or the permissions of a role by unique ID or name.
+    ///
+    /// Authentication is required, and the permission to manage the users.
+    async fn update_role(
+        &self,
+        role_id: &Identifier,
+        name: Option<&str>,
+        permissions: Option<Permissions>,
+    ) -> Result<(), IggyError>;
+}
+
 /// This trait defines the methods to interact with the personal access token module.
 #[async_trait]
 pub trait PersonalAccessTokenClient {
@@ -319,6 +385,17 @@ pub trait ConsumerOffsetClient {
         topic_id: &Identifier,
         partition_id: Option<u32>,
     ) -> Result<ConsumerOffsetInfo, IggyError>;
+    /// Get the lag of a specific consumer or consumer group for the given stream and topic by unique IDs or names.
+    /// The lag is returned for the given partition, or for all the partitions of the topic if the partition ID is not specified.
+    ///
+    /// Authentication is required, and the permission to poll the messages.
+    async fn get_consumer_lag(
+        &self,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+    ) -> Result<Vec<ConsumerLagInfo>, IggyError>;
 }
 
 /// This trait defines the methods to interact with the consumer group module.
@@ -1,6 +1,6 @@
 use crate::client::{
     Client, ConsumerGroupClient, ConsumerOffsetClient, MessageClient, PartitionClient,
-    PersonalAccessTokenClient, StreamClient, SystemClient, TopicClient, UserClient,
+    PersonalAccessTokenClient, RoleClient, StreamClient, SystemClient, TopicClient, UserClient,
 };
 use crate::consumer::Consumer;
 use crate::error::IggyError;
@@ -9,16 +9,20 @@ use crate::locking::IggySharedMut;
 use crate::locking::IggySharedMutFn;
 use crate::message_handler::MessageHandler;
 use crate::messages::send_messages::{Message, Partitioning, PartitioningKind, SendMessages};
+use crate::models::audit_log::AuditLogEntry;
+use crate::models::backup_info::BackupInfo;
 use crate::models::client_info::{ClientInfo, ClientInfoDetails};
 use crate::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails};
+use crate::models::consumer_lag_info::ConsumerLagInfo;
 use crate::models::consumer_offset_info::ConsumerOffsetInfo;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::messages::{PolledMessage, PolledMessages};
 use crate::models::personal_access_token::{PersonalAccessTokenInfo, RawPersonalAccessToken};
+use crate::models::role_info::{RoleId, RoleInfo, RoleInfoDetails};
 use crate::models::stats::Stats;
 use crate::models::stream::{Stream, StreamDetails};
 use crate::models::topic::{Topic, TopicDetails};
-use crate::models::user_info::{UserInfo, UserInfoDetails};
+use crate::models::user_info::{UserId, UserInfo, UserInfoDetails};
 use crate::partitioner::Partitioner;
 use crate::tcp::client::TcpClient;
 use crate::utils::crypto::Encryptor;
@@ -535,6 +539,18 @@ impl UserClient for IggyClient {
             .await
     }
 
+    async fn update_roles(
+        &self,
+        user_id: &Identifier,
+        roles: Vec<RoleId>,
+    ) -> Result<(), IggyError> {
+        self.client.read().await.update_roles(user_id, roles).await
+    }
+
+    async fn unlock_user(&self, user_id: &Identifier) -> Result<(), IggyError> {
+        self.client.read().await.unlock_user(user_id).await
+    }
+
     async fn change_password(
         &self,
         user_id: &Identifier,
@@ -561,6 +577,46 @@ impl UserClient for IggyClient {
     }
 }
 
+#[async_trait]
+impl RoleClient for IggyClient {
+    async fn get_role(&self, role_id: &Identifier) -> Result<RoleInfoDetails, IggyError> {
+        self.client.read().await.get_role(role_id).await
+    }
+
+    async fn get_roles(&self) -> Result<Vec<RoleInfo>, IggyError> {
+        self.client.read().await.get_roles().await
+    }
+
+    async fn create_role(
+        &self,
+        name: &str,
+        permissions: Option<Permissions>,
+    ) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .create_role(name, permissions)
+            .await
+    }
+
+    async fn delete_role(&self, role_id: &Identifier) -> Result<(), IggyError> {
+        self.client.read().await.delete_role(role_id).await
+    }
+
+    async fn update_role(
+        &self,
+        role_id: &Identifier,
+        name: Option<&str>,
+        permissions: Option<Permissions>,
+    ) -> Result<(), IggyError> {
+        self.client
+            .read()
+            .await
+            .update_role(role_id, name, permissions)
+            .await
+    }
+}
+
 #[async_trait]
 impl PersonalAccessTokenClient for IggyClient {
     async fn get_personal_access_tokens(&self) -> Result<Vec<PersonalAccessTokenInfo>, IggyError> {
@@ -617,6 +673,22 @@ impl SystemClient for IggyClient {
         self.client.read().await.get_clients().await
     }
 
+    async fn get_audit_log(
+        &self,
+        count: u32,
+        user_id: Option<UserId>,
+    ) -> Result<Vec<AuditLogEntry>, IggyError> {
+        self.client.read().await.get_audit_log(count, user_id).await
+    }
+
+    async fn create_backup(
+        &self,
+        name: Option<&str>,
+        archive: bool,
+    ) -> Result<BackupInfo, IggyError> {
+        self.client.read().await.create_backup(name, archive).await
+    }
+
     async fn ping(&self) -> Result<(), IggyError> {
         self.client.read().await.ping().await
     }
@@ -861,6 +933,20 @@ impl ConsumerOffsetClient for IggyClient {
             .get_consumer_offset(consumer, stream_id, topic_id, partition_id)
             .await
     }
+
+    async fn get_consumer_lag(
+        &self,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+    ) -> Result<Vec<ConsumerLagInfo>, IggyError> {
+        self.client
+            .read()
+            .await
+            .get_consumer_lag(consumer, stream_id, topic_id, partition_id)
+            .await
+    }
 }
 
 #[async_trait]
@@ -11,6 +11,10 @@ pub const PING: &str = "ping";
 pub const PING_CODE: u32 = 1;
 pub const GET_STATS: &str = "stats";
 pub const GET_STATS_CODE: u32 = 10;
+pub const GET_AUDIT_LOG: &str = "audit_log.get";
+pub const GET_AUDIT_LOG_CODE: u32 = 11;
+pub const CREATE_BACKUP: &str = "backup.create";
+pub const CREATE_BACKUP_CODE: u32 = 12;
 pub const GET_ME: &str = "me";
 pub const GET_ME_CODE: u32 = 20;
 pub const GET_CLIENT: &str = "client.get";
@@ -35,6 +39,12 @@ pub const LOGIN_USER: &str = "user.login";
 pub const LOGIN_USER_CODE: u32 = 38;
 pub const LOGOUT_USER: &str = "user.logout";
 pub const LOGOUT_USER_CODE: u32 = 39;
+pub const UPDATE_ROLES: &str = "user.roles";
+pub const UPDATE_ROLES_CODE: u32 = 40;
+pub const UNLOCK_USER: &str = "user.unlock";
+pub const UNLOCK_USER_CODE: u32 = 45;
+/// Reserved for the password rehash stored in the server state, it's not sent by the clients.
+pub const REHASH_PASSWORD_CODE: u32 = 46;
 pub const GET_PERSONAL_ACCESS_TOKENS: &str = "personal_access_token.list";
 pub const GET_PERSONAL_ACCESS_TOKENS_CODE: u32 = 41;
 pub const CREATE_PERSONAL_ACCESS_TOKEN: &str = "personal_access_token.create";
@@ -43,14 +53,28 @@ pub const DELETE_PERSONAL_ACCESS_TOKEN: &str = "personal_access_token.delete";
 pub const DELETE_PERSONAL_ACCESS_TOKEN_CODE: u32 = 43;
 pub const LOGIN_WITH_PERSONAL_ACCESS_TOKEN: &str = "personal_access_token.login";
 pub const LOGIN_WITH_PERSONAL_ACCESS_TOKEN_CODE: u32 = 44;
+pub const GET_ROLE: &str = "role.get";
+pub const GET_ROLE_CODE: u32 = 51;
+pub const GET_ROLES: &str = "role.list";
+pub const GET_ROLES_CODE: u32 = 52;
+pub const CREATE_ROLE: &str = "role.create";
+pub const CREATE_ROLE_CODE: u32 = 53;
+pub const DELETE_ROLE: &str = "role.delete";
+pub const DELETE_ROLE_CODE: u32 = 54;
+pub const UPDATE_ROLE: &str = "role.update";
+pub const UPDATE_ROLE_CODE: u32 = 55;
 pub const POLL_MESSAGES: &str = "message.poll";
 pub const POLL_MESSAGES_CODE: u32 = 100;
 pub const SEND_MESSAGES: &str = "message.send";
 pub const SEND_MESSAGES_CODE: u32 = 101;
+pub const POLL_MESSAGE_BATCHES: &str = "message.poll_batches";
+pub const POLL_MESSAGE_BATCHES_CODE: u32 = 102;
 pub const GET_CONSUMER_OFFSET: &str = "consumer_offset.get";
 pub const GET_CONSUMER_OFFSET_CODE: u32 = 120;
 pub const STORE_CONSUMER_OFFSET: &str = "consumer_offset.store";
 pub const STORE_CONSUMER_OFFSET_CODE: u32 = 121;
+pub const GET_CONSUMER_LAG: &str = "consumer_offset.lag";
+pub const GET_CONSUMER_LAG_CODE: u32 = 122;
 pub const GET_STREAM: &str = "stream.get";
 pub const GET_STREAM_CODE: u32 = 200;
 pub const GET_STREAMS: &str = "stream.list";
@@ -96,6 +120,8 @@ pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
     match code {
         PING_CODE => Ok(PING),
         GET_STATS_CODE => Ok(GET_STATS),
+        GET_AUDIT_LOG_CODE => Ok(GET_AUDIT_LOG),
+        CREATE_BACKUP_CODE => Ok(CREATE_BACKUP),
         GET_ME_CODE => Ok(GET_ME),
         GET_CLIENT_CODE => Ok(GET_CLIENT),
         GET_CLIENTS_CODE => Ok(GET_CLIENTS),
@@ -108,14 +134,23 @@ pub fn get_name_from_code(code: u32) -> Result<&'static str, IggyError> {
         CHANGE_PASSWORD_CODE => Ok(CHANGE_PASSWORD),
         LOGIN_USER_CODE => Ok(LOGIN_USER),
         LOGOUT_USER_CODE => Ok(LOGOUT_USER),
+        UPDATE_ROLES_CODE => Ok(UPDATE_ROLES),
+        UNLOCK_USER_CODE => Ok(UNLOCK_USER),
         GET_PERSONAL_ACCESS_TOKENS_CODE => Ok(GET_PERSONAL_ACCESS_TOKENS),
         CREATE_PERSONAL_ACCESS_TOKEN_CODE => Ok(CREATE_PERSONAL_ACCESS_TOKEN),
         DELETE_PERSONAL_ACCESS_TOKEN_CODE => Ok(DELETE_PERSONAL_ACCESS_TOKEN),
         LOGIN_WITH_PERSONAL_ACCESS_TOKEN_CODE => Ok(LOGIN_WITH_PERSONAL_ACCESS_TOKEN),
+        GET_ROLE_CODE => Ok(GET_ROLE),
+        GET_ROLES_CODE => Ok(GET_ROLES),
+        CREATE_ROLE_CODE => Ok(CREATE_ROLE),
+        DELETE_ROLE_CODE => Ok(DELETE_ROLE),
+        UPDATE_ROLE_CODE => Ok(UPDATE_ROLE),
         SEND_MESSAGES_CODE => Ok(SEND_MESSAGES),
         POLL_MESSAGES_CODE => Ok(POLL_MESSAGES),
+        POLL_MESSAGE_BATCHES_CODE => Ok(POLL_MESSAGE_BATCHES),
         STORE_CONSUMER_OFFSET_CODE => Ok(STORE_CONSUMER_OFFSET),
         GET_CONSUMER_OFFSET_CODE => Ok(GET_CONSUMER_OFFSET),
+        GET_CONSUMER_LAG_CODE => Ok(GET_CONSUMER_LAG),
         GET_STREAM_CODE => Ok(GET_STREAM),
         GET_STREAMS_CODE => Ok(GET_STREAMS),
         CREATE_STREAM_CODE => Ok(CREATE_STREAM),
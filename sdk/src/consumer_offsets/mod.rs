@@ -1,2 +1,3 @@
+pub mod get_consumer_lag;
 pub mod get_consumer_offset;
 pub mod store_consumer_offset;
@@ -84,10 +84,24 @@ pub enum IggyError {
     PersonalAccessTokenExpired(String, u32) = 54,
     #[error("Users limit reached.")]
     UsersLimitReached = 55,
+    #[error("Invalid role name")]
+    InvalidRoleName = 56,
+    #[error("Role already exists")]
+    RoleAlreadyExists = 57,
+    #[error("Roles limit reached.")]
+    RolesLimitReached = 58,
+    #[error("User with ID: {0} is locked due to too many failed login attempts.")]
+    UserLocked(u32) = 59,
+    #[error("Too many failed login attempts, try again later.")]
+    TooManyLoginAttempts = 60,
     #[error("Not connected")]
     NotConnected = 61,
     #[error("Request error")]
     RequestError(#[from] reqwest::Error) = 62,
+    #[error("Password does not meet the password policy: {0}")]
+    PasswordPolicyViolation(String) = 63,
+    #[error("Password was used recently and cannot be reused")]
+    PasswordReused = 64,
     #[error("Invalid encryption key")]
     InvalidEncryptionKey = 70,
     #[error("Cannot encrypt data")]
@@ -110,6 +124,18 @@ pub enum IggyError {
     ClientNotFound(u32) = 100,
     #[error("Invalid client ID")]
     InvalidClientId = 101,
+    #[error("Invalid audit log count")]
+    InvalidAuditLogCount = 110,
+    #[error("Invalid backup name")]
+    InvalidBackupName = 111,
+    #[error("Backup with name: {0} already exists.")]
+    BackupAlreadyExists(String) = 112,
+    #[error("Backup with name: {0} was not found.")]
+    BackupNotFound(String) = 113,
+    #[error("Cannot create backup: {0}")]
+    CannotCreateBackup(String) = 114,
+    #[error("Cannot restore backup: {0}")]
+    CannotRestoreBackup(String) = 115,
     #[error("IO error")]
     IoError(#[from] std::io::Error) = 200,
     #[error("Write error")]
@@ -322,6 +348,8 @@ pub enum IggyError {
     InvalidKeyValueLength = 4028,
     #[error("Command length error: {0}")]
     CommandLengthError(String) = 4029,
+    #[error("Invalid trace parent")]
+    InvalidTraceParent = 4030,
     #[error("Invalid offset: {0}")]
     InvalidOffset(u64) = 4100,
     #[error("Consumer group with ID: {0} for topic with ID: {1} was not found.")]
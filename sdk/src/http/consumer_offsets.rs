@@ -1,11 +1,13 @@
 use crate::client::ConsumerOffsetClient;
 use crate::consumer::Consumer;
+use crate::consumer_offsets::get_consumer_lag::GetConsumerLag;
 use crate::consumer_offsets::get_consumer_offset::GetConsumerOffset;
 use crate::consumer_offsets::store_consumer_offset::StoreConsumerOffset;
 use crate::error::IggyError;
 use crate::http::client::HttpClient;
 use crate::http::HttpTransport;
 use crate::identifier::Identifier;
+use crate::models::consumer_lag_info::ConsumerLagInfo;
 use crate::models::consumer_offset_info::ConsumerOffsetInfo;
 use async_trait::async_trait;
 
@@ -54,8 +56,34 @@ impl ConsumerOffsetClient for HttpClient {
         let offset = response.json().await?;
         Ok(offset)
     }
+
+    async fn get_consumer_lag(
+        &self,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+    ) -> Result<Vec<ConsumerLagInfo>, IggyError> {
+        let response = self
+            .get_with_query(
+                &get_lag_path(&stream_id.as_cow_str(), &topic_id.as_cow_str()),
+                &GetConsumerLag {
+                    consumer: consumer.clone(),
+                    stream_id: stream_id.clone(),
+                    topic_id: topic_id.clone(),
+                    partition_id,
+                },
+            )
+            .await?;
+        let lag = response.json().await?;
+        Ok(lag)
+    }
 }
 
 fn get_path(stream_id: &str, topic_id: &str) -> String {
     format!("streams/{stream_id}/topics/{topic_id}/consumer-offsets")
 }
+
+fn get_lag_path(stream_id: &str, topic_id: &str) -> String {
+    format!("streams/{stream_id}/topics/{topic_id}/consumer-lag")
+}
@@ -0,0 +1,67 @@
+use crate::client::RoleClient;
+use crate::error::IggyError;
+use crate::http::client::HttpClient;
+use crate::http::HttpTransport;
+use crate::identifier::Identifier;
+use crate::models::permissions::Permissions;
+use crate::models::role_info::{RoleInfo, RoleInfoDetails};
+use crate::roles::create_role::CreateRole;
+use crate::roles::update_role::UpdateRole;
+use async_trait::async_trait;
+
+const PATH: &str = "/roles";
+
+#[async_trait]
+impl RoleClient for HttpClient {
+    async fn get_role(&self, role_id: &Identifier) -> Result<RoleInfoDetails, IggyError> {
+        let response = self.get(&format!("{PATH}/{}", role_id)).await?;
+        let role = response.json().await?;
+        Ok(role)
+    }
+
+    async fn get_roles(&self) -> Result<Vec<RoleInfo>, IggyError> {
+        let response = self.get(PATH).await?;
+        let roles = response.json().await?;
+        Ok(roles)
+    }
+
+    async fn create_role(
+        &self,
+        name: &str,
+        permissions: Option<Permissions>,
+    ) -> Result<(), IggyError> {
+        self.post(
+            PATH,
+            &CreateRole {
+                name: name.to_string(),
+                permissions,
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_role(&self, role_id: &Identifier) -> Result<(), IggyError> {
+        self.delete(&format!("{PATH}/{}", &role_id.as_cow_str()))
+            .await?;
+        Ok(())
+    }
+
+    async fn update_role(
+        &self,
+        role_id: &Identifier,
+        name: Option<&str>,
+        permissions: Option<Permissions>,
+    ) -> Result<(), IggyError> {
+        self.put(
+            &format!("{PATH}/{}", &role_id.as_cow_str()),
+            &UpdateRole {
+                role_id: role_id.clone(),
+                name: name.map(|s| s.to_string()),
+                permissions,
+            },
+        )
+        .await?;
+        Ok(())
+    }
+}
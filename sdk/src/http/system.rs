@@ -2,13 +2,20 @@ use crate::client::SystemClient;
 use crate::error::IggyError;
 use crate::http::client::HttpClient;
 use crate::http::HttpTransport;
+use crate::models::audit_log::AuditLogEntry;
+use crate::models::backup_info::BackupInfo;
 use crate::models::client_info::{ClientInfo, ClientInfoDetails};
 use crate::models::stats::Stats;
+use crate::models::user_info::UserId;
+use crate::system::create_backup::CreateBackup;
+use crate::system::get_audit_log::GetAuditLog;
 use async_trait::async_trait;
 
 const PING: &str = "/ping";
 const CLIENTS: &str = "/clients";
 const STATS: &str = "/stats";
+const AUDIT_LOG: &str = "/audit-log";
+const BACKUPS: &str = "/backups";
 
 #[async_trait]
 impl SystemClient for HttpClient {
@@ -34,6 +41,36 @@ impl SystemClient for HttpClient {
         Ok(clients)
     }
 
+    async fn get_audit_log(
+        &self,
+        count: u32,
+        user_id: Option<UserId>,
+    ) -> Result<Vec<AuditLogEntry>, IggyError> {
+        let response = self
+            .get_with_query(AUDIT_LOG, &GetAuditLog { count, user_id })
+            .await?;
+        let entries = response.json().await?;
+        Ok(entries)
+    }
+
+    async fn create_backup(
+        &self,
+        name: Option<&str>,
+        archive: bool,
+    ) -> Result<BackupInfo, IggyError> {
+        let response = self
+            .post(
+                BACKUPS,
+                &CreateBackup {
+                    name: name.map(|name| name.to_string()),
+                    archive,
+                },
+            )
+            .await?;
+        let backup = response.json().await?;
+        Ok(backup)
+    }
+
     async fn ping(&self) -> Result<(), IggyError> {
         self.get(PING).await?;
         Ok(())
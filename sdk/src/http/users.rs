@@ -5,12 +5,15 @@ use crate::http::HttpTransport;
 use crate::identifier::Identifier;
 use crate::models::identity_info::IdentityInfo;
 use crate::models::permissions::Permissions;
+use crate::models::role_info::RoleId;
 use crate::models::user_info::{UserInfo, UserInfoDetails};
 use crate::models::user_status::UserStatus;
 use crate::users::change_password::ChangePassword;
 use crate::users::create_user::CreateUser;
 use crate::users::login_user::LoginUser;
+use crate::users::unlock_user::UnlockUser;
 use crate::users::update_permissions::UpdatePermissions;
+use crate::users::update_roles::UpdateRoles;
 use crate::users::update_user::UpdateUser;
 use async_trait::async_trait;
 
@@ -90,6 +93,33 @@ impl UserClient for HttpClient {
         Ok(())
     }
 
+    async fn update_roles(
+        &self,
+        user_id: &Identifier,
+        roles: Vec<RoleId>,
+    ) -> Result<(), IggyError> {
+        self.put(
+            &format!("{PATH}/{}/roles", &user_id.as_cow_str()),
+            &UpdateRoles {
+                user_id: user_id.clone(),
+                roles,
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn unlock_user(&self, user_id: &Identifier) -> Result<(), IggyError> {
+        self.put(
+            &format!("{PATH}/{}/unlock", &user_id.as_cow_str()),
+            &UnlockUser {
+                user_id: user_id.clone(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
     async fn change_password(
         &self,
         user_id: &Identifier,
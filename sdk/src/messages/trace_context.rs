@@ -0,0 +1,218 @@
+use crate::error::IggyError;
+use crate::messages::send_messages::Message;
+use crate::models::header::{HeaderKey, HeaderValue};
+use crate::models::messages::PolledMessage;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The name of the message header holding the W3C trace context.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+const VERSION: u8 = 0;
+const SAMPLED_FLAG: u8 = 0x01;
+
+/// `TraceParent` represents the W3C `traceparent` (https://www.w3.org/TR/trace-context/),
+/// which can be propagated through the message headers, so the span of the producer can be correlated
+/// with the spans of the server and the consumers processing the message.
+/// It consists of the following fields:
+/// - `trace_id`: the identifier of the whole trace, must not be zero.
+/// - `parent_id`: the identifier of the span which produced or processed the message, must not be zero.
+/// - `flags`: the trace flags, currently only the sampled flag is defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceParent {
+    /// The identifier of the whole trace.
+    pub trace_id: u128,
+    /// The identifier of the parent span.
+    pub parent_id: u64,
+    /// The trace flags.
+    pub flags: u8,
+}
+
+impl TraceParent {
+    /// Creates a new trace parent, the trace ID and the parent ID must not be zero.
+    pub fn new(trace_id: u128, parent_id: u64, flags: u8) -> Result<Self, IggyError> {
+        if trace_id == 0 || parent_id == 0 {
+            return Err(IggyError::InvalidTraceParent);
+        }
+
+        Ok(Self {
+            trace_id,
+            parent_id,
+            flags,
+        })
+    }
+
+    /// Creates a new sampled trace parent starting the new trace.
+    pub fn generate() -> Self {
+        Self {
+            trace_id: uuid::Uuid::new_v4().as_u128(),
+            parent_id: generate_span_id(),
+            flags: SAMPLED_FLAG,
+        }
+    }
+
+    /// Creates the trace parent for the child span within the same trace.
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id,
+            parent_id: generate_span_id(),
+            flags: self.flags,
+        }
+    }
+
+    /// Returns `true` if the trace is sampled, i.e. the spans should be recorded.
+    pub fn is_sampled(&self) -> bool {
+        self.flags & SAMPLED_FLAG == SAMPLED_FLAG
+    }
+
+    /// Injects the trace parent into the headers, the existing value is replaced.
+    pub fn inject(&self, headers: &mut HashMap<HeaderKey, HeaderValue>) {
+        headers.insert(
+            HeaderKey::new(TRACEPARENT_HEADER).unwrap(),
+            HeaderValue::from_str(&self.to_string()).unwrap(),
+        );
+    }
+
+    /// Extracts the trace parent from the headers, `None` is returned if the header is missing or invalid.
+    pub fn extract(headers: &HashMap<HeaderKey, HeaderValue>) -> Option<Self> {
+        let value = headers.get(&HeaderKey::new(TRACEPARENT_HEADER).unwrap())?;
+        value.as_str().ok()?.parse().ok()
+    }
+}
+
+fn generate_span_id() -> u64 {
+    loop {
+        let span_id = uuid::Uuid::new_v4().as_u64_pair().0;
+        if span_id != 0 {
+            return span_id;
+        }
+    }
+}
+
+impl FromStr for TraceParent {
+    type Err = IggyError;
+
+    /// Parses the trace parent in the format `00-<trace_id>-<parent_id>-<flags>`,
+    /// where the values are lowercase hex encoded (32, 16 and 2 characters respectively).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.trim().split('-').collect::<Vec<_>>();
+        if parts.len() != 4 {
+            return Err(IggyError::InvalidTraceParent);
+        }
+
+        let lengths = [2, 32, 16, 2];
+        for (part, length) in parts.iter().zip(lengths) {
+            if part.len() != length
+                || !part
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+            {
+                return Err(IggyError::InvalidTraceParent);
+            }
+        }
+
+        let version =
+            u8::from_str_radix(parts[0], 16).map_err(|_| IggyError::InvalidTraceParent)?;
+        if version != VERSION {
+            return Err(IggyError::InvalidTraceParent);
+        }
+
+        Self::new(
+            u128::from_str_radix(parts[1], 16).map_err(|_| IggyError::InvalidTraceParent)?,
+            u64::from_str_radix(parts[2], 16).map_err(|_| IggyError::InvalidTraceParent)?,
+            u8::from_str_radix(parts[3], 16).map_err(|_| IggyError::InvalidTraceParent)?,
+        )
+    }
+}
+
+impl Display for TraceParent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{VERSION:02x}-{:032x}-{:016x}-{:02x}",
+            self.trace_id, self.parent_id, self.flags
+        )
+    }
+}
+
+impl Message {
+    /// Sets the trace parent in the message headers, so the trace can be continued by the consumers.
+    pub fn set_trace_parent(&mut self, trace_parent: &TraceParent) {
+        trace_parent.inject(self.headers.get_or_insert_with(HashMap::new));
+    }
+
+    /// Returns the trace parent from the message headers, if present.
+    pub fn get_trace_parent(&self) -> Option<TraceParent> {
+        TraceParent::extract(self.headers.as_ref()?)
+    }
+}
+
+impl PolledMessage {
+    /// Returns the trace parent from the message headers, if present.
+    pub fn get_trace_parent(&self) -> Option<TraceParent> {
+        TraceParent::extract(self.headers.as_ref()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+
+    #[test]
+    fn trace_parent_should_be_parsed_and_formatted() {
+        let value = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let trace_parent = TraceParent::from_str(value).unwrap();
+
+        assert_eq!(trace_parent.trace_id, 0x4bf92f3577b34da6a3ce929d0e0e4736);
+        assert_eq!(trace_parent.parent_id, 0x00f067aa0ba902b7);
+        assert!(trace_parent.is_sampled());
+        assert_eq!(trace_parent.to_string(), value);
+    }
+
+    #[test]
+    fn invalid_trace_parent_should_not_be_parsed() {
+        let values = [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-+0f067aa0ba902b7-01",
+        ];
+
+        for value in values {
+            assert!(TraceParent::from_str(value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn child_should_continue_the_trace() {
+        let trace_parent = TraceParent::generate();
+        let child = trace_parent.child();
+
+        assert!(trace_parent.is_sampled());
+        assert_eq!(child.trace_id, trace_parent.trace_id);
+        assert_eq!(child.flags, trace_parent.flags);
+        assert_ne!(child.parent_id, trace_parent.parent_id);
+    }
+
+    #[test]
+    fn trace_parent_should_be_injected_into_and_extracted_from_message_headers() {
+        let trace_parent = TraceParent::generate();
+        let mut message = Message::new(None, Bytes::from("hello"), None);
+        assert!(message.get_trace_parent().is_none());
+
+        message.set_trace_parent(&trace_parent);
+        let headers = message.headers.as_ref().unwrap();
+        let header = headers
+            .get(&HeaderKey::new(TRACEPARENT_HEADER).unwrap())
+            .unwrap();
+
+        assert_eq!(header.as_str().unwrap(), trace_parent.to_string());
+        assert_eq!(message.get_trace_parent(), Some(trace_parent));
+    }
+}
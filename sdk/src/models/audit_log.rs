@@ -0,0 +1,41 @@
+use crate::models::user_info::UserId;
+use crate::utils::timestamp::IggyTimestamp;
+use serde::{Deserialize, Serialize};
+
+/// `AuditLogEntry` represents a single record of the audit log.
+/// It consists of the following fields:
+/// - `timestamp`: the timestamp when the action was performed.
+/// - `user_id`: the unique identifier of the user. This field is optional, as the action might be performed by an unauthenticated client (e.g. failed login).
+/// - `username`: the username used to log in. This field is optional, as it's recorded only for the login attempts.
+/// - `address`: the remote address of the client.
+/// - `transport`: the transport protocol used by the client.
+/// - `action`: the performed action, e.g. the command name or the HTTP method and path.
+/// - `result`: the result of the action, `ok` if succeeded, otherwise the error.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct AuditLogEntry {
+    /// The timestamp when the action was performed.
+    pub timestamp: IggyTimestamp,
+    /// The unique identifier of the user. This field is optional, as the action might be performed by an unauthenticated client (e.g. failed login).
+    pub user_id: Option<UserId>,
+    /// The username used to log in. This field is optional, as it's recorded only for the login attempts (also the failed ones).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// The remote address of the client.
+    pub address: String,
+    /// The transport protocol used by the client.
+    pub transport: String,
+    /// The performed action, e.g. the command name or the HTTP method and path.
+    pub action: String,
+    /// The result of the action, `ok` if succeeded, otherwise the error.
+    pub result: String,
+}
+
+impl AuditLogEntry {
+    /// The result of the successful action.
+    pub const OK_RESULT: &'static str = "ok";
+
+    /// Returns true if the action succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.result == Self::OK_RESULT
+    }
+}
@@ -0,0 +1,30 @@
+use crate::utils::byte_size::IggyByteSize;
+use crate::utils::timestamp::IggyTimestamp;
+use serde::{Deserialize, Serialize};
+
+/// `BackupInfo` represents the manifest of the created backup.
+/// It consists of the following fields:
+/// - `name`: the unique name of the backup.
+/// - `created_at`: the timestamp when the backup was created.
+/// - `state_index`: the index of the last state entry included in the backup.
+/// - `partitions_count`: the number of partitions included in the backup.
+/// - `segments_count`: the number of segments included in the backup.
+/// - `messages_count`: the number of messages included in the backup.
+/// - `size`: the total size of the backup.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct BackupInfo {
+    /// The unique name of the backup.
+    pub name: String,
+    /// The timestamp when the backup was created.
+    pub created_at: IggyTimestamp,
+    /// The index of the last state entry included in the backup.
+    pub state_index: u64,
+    /// The number of partitions included in the backup.
+    pub partitions_count: u32,
+    /// The number of segments included in the backup.
+    pub segments_count: u32,
+    /// The number of messages included in the backup.
+    pub messages_count: u64,
+    /// The total size of the backup.
+    pub size: IggyByteSize,
+}
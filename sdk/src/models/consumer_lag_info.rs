@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// `ConsumerLagInfo` represents the information about the lag of a consumer or consumer group in a partition.
+/// It consists of the following fields:
+/// - `partition_id`: the unique identifier of the partition.
+/// - `current_offset`: the current offset of the partition.
+/// - `stored_offset`: the stored offset by the consumer in the partition, if any.
+/// - `lag`: the number of messages which were not yet consumed.
+/// - `lag_bytes`: the size of the messages which were not yet consumed.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConsumerLagInfo {
+    /// The unique identifier of the partition.
+    pub partition_id: u32,
+    /// The current offset of the partition.
+    pub current_offset: u64,
+    /// The stored offset by the consumer in the partition, `None` if the offset was never stored.
+    pub stored_offset: Option<u64>,
+    /// The number of messages which were not yet consumed.
+    pub lag: u64,
+    /// The size of the messages which were not yet consumed, estimated based on the size of the message batches.
+    pub lag_bytes: u64,
+}
@@ -1,5 +1,8 @@
+pub mod audit_log;
+pub mod backup_info;
 pub mod client_info;
 pub mod consumer_group;
+pub mod consumer_lag_info;
 pub mod consumer_offset_info;
 pub mod header;
 pub mod identity_info;
@@ -7,6 +10,7 @@ pub mod messages;
 pub mod partition;
 pub mod permissions;
 pub mod personal_access_token;
+pub mod role_info;
 pub mod stats;
 pub mod stream;
 pub mod topic;
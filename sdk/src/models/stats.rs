@@ -48,6 +48,8 @@ pub struct Stats {
     pub os_version: String,
     /// The version of the kernel.
     pub kernel_version: String,
+    /// The version of the Iggy server.
+    pub iggy_server_version: String,
 }
 
 impl Default for Stats {
@@ -75,6 +77,7 @@ impl Default for Stats {
             os_name: "unknown_os_name".to_string(),
             os_version: "unknown_os_version".to_string(),
             kernel_version: "unknown_kernel_version".to_string(),
+            iggy_server_version: "unknown_iggy_version".to_string(),
         }
     }
 }
@@ -1,4 +1,5 @@
 use crate::models::permissions::Permissions;
+use crate::models::role_info::RoleId;
 use crate::models::user_status::UserStatus;
 use crate::utils::timestamp::IggyTimestamp;
 use serde::{Deserialize, Serialize};
@@ -35,6 +36,7 @@ pub struct UserInfo {
 /// - `status`: the status of the user.
 /// - `username`: the username of the user.
 /// - `permissions`: the optional permissions of the user.
+/// - `roles`: the unique identifiers of the roles assigned to the user.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UserInfoDetails {
     /// The unique identifier (numeric) of the user.
@@ -47,4 +49,7 @@ pub struct UserInfoDetails {
     pub username: String,
     /// The optional permissions of the user.
     pub permissions: Option<Permissions>,
+    /// The unique identifiers of the roles assigned to the user.
+    #[serde(default)]
+    pub roles: Vec<RoleId>,
 }
@@ -12,6 +12,9 @@ pub enum UserStatus {
     Active,
     /// The user is inactive.
     Inactive,
+    /// The user is temporarily locked due to too many failed login attempts.
+    /// This status cannot be set directly, use the `unlock_user` method to unlock the user.
+    Locked,
 }
 
 impl FromStr for UserStatus {
@@ -20,6 +23,7 @@ impl FromStr for UserStatus {
         match input {
             "active" => Ok(UserStatus::Active),
             "inactive" => Ok(UserStatus::Inactive),
+            "locked" => Ok(UserStatus::Locked),
             _ => Err(IggyError::InvalidUserStatus),
         }
     }
@@ -30,6 +34,7 @@ impl Display for UserStatus {
         match self {
             UserStatus::Active => write!(f, "active"),
             UserStatus::Inactive => write!(f, "inactive"),
+            UserStatus::Locked => write!(f, "locked"),
         }
     }
 }
@@ -40,6 +45,7 @@ impl UserStatus {
         match self {
             UserStatus::Active => 1,
             UserStatus::Inactive => 2,
+            UserStatus::Locked => 3,
         }
     }
 
@@ -48,6 +54,7 @@ impl UserStatus {
         match code {
             1 => Ok(UserStatus::Active),
             2 => Ok(UserStatus::Inactive),
+            3 => Ok(UserStatus::Locked),
             _ => Err(IggyError::InvalidCommand),
         }
     }
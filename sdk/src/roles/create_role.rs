@@ -0,0 +1,183 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, CREATE_ROLE_CODE};
+use crate::error::IggyError;
+use crate::models::permissions::Permissions;
+use crate::users::defaults::*;
+use crate::utils::text;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::from_utf8;
+
+/// `CreateRole` command is used to create a new role which can be then assigned to the users.
+/// It has additional payload:
+/// - `name` - unique name of the role, must be between 3 and 50 characters long. The name will be always converted to lowercase and all whitespaces will be replaced with dots.
+/// - `permissions` - optional permissions of the role. If not provided, role will grant no permissions.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct CreateRole {
+    /// Unique name of the role, must be between 3 and 50 characters long.
+    pub name: String,
+    /// Optional permissions of the role. If not provided, role will grant no permissions.
+    pub permissions: Option<Permissions>,
+}
+
+impl Command for CreateRole {
+    fn code(&self) -> u32 {
+        CREATE_ROLE_CODE
+    }
+}
+
+impl Default for CreateRole {
+    fn default() -> Self {
+        CreateRole {
+            name: "role".to_string(),
+            permissions: None,
+        }
+    }
+}
+
+impl Validatable<IggyError> for CreateRole {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.name.is_empty()
+            || self.name.len() > MAX_ROLE_NAME_LENGTH
+            || self.name.len() < MIN_ROLE_NAME_LENGTH
+        {
+            return Err(IggyError::InvalidRoleName);
+        }
+
+        if !text::is_resource_name_valid(&self.name) {
+            return Err(IggyError::InvalidRoleName);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for CreateRole {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(2 + self.name.len());
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u8(self.name.len() as u8);
+        bytes.put_slice(self.name.as_bytes());
+        if let Some(permissions) = &self.permissions {
+            bytes.put_u8(1);
+            let permissions = permissions.to_bytes();
+            #[allow(clippy::cast_possible_truncation)]
+            bytes.put_u32_le(permissions.len() as u32);
+            bytes.put_slice(&permissions);
+        } else {
+            bytes.put_u8(0);
+        }
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<CreateRole, IggyError> {
+        if bytes.len() < 5 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let name_length = bytes[0];
+        let name = from_utf8(&bytes[1..1 + name_length as usize])?.to_string();
+        if name.len() != name_length as usize {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut position = 1 + name_length as usize;
+        let has_permissions = bytes[position];
+        if has_permissions > 1 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        position += 1;
+        let permissions = if has_permissions == 1 {
+            let permissions_length = u32::from_le_bytes(bytes[position..position + 4].try_into()?);
+            position += 4;
+            Some(Permissions::from_bytes(
+                bytes.slice(position..position + permissions_length as usize),
+            )?)
+        } else {
+            None
+        };
+
+        let command = CreateRole { name, permissions };
+        Ok(command)
+    }
+}
+
+impl Display for CreateRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let permissions = if let Some(permissions) = &self.permissions {
+            permissions.to_string()
+        } else {
+            "no_permissions".to_string()
+        };
+        write!(f, "{}|{}", self.name, permissions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::permissions::GlobalPermissions;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = CreateRole {
+            name: "readers".to_string(),
+            permissions: Some(get_permissions()),
+        };
+
+        let bytes = command.to_bytes();
+        let name_length = bytes[0];
+        let name = from_utf8(&bytes[1..1 + name_length as usize]).unwrap();
+        let mut position = 1 + name_length as usize;
+        let has_permissions = bytes[position];
+        position += 1;
+        let permissions_length =
+            u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap());
+        position += 4;
+        let permissions =
+            Permissions::from_bytes(bytes.slice(position..position + permissions_length as usize))
+                .unwrap();
+
+        assert!(!bytes.is_empty());
+        assert_eq!(name, command.name);
+        assert_eq!(has_permissions, 1);
+        assert_eq!(permissions, command.permissions.unwrap());
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let name = "readers";
+        let permissions = get_permissions();
+        let mut bytes = BytesMut::new();
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u8(name.len() as u8);
+        bytes.put_slice(name.as_bytes());
+        bytes.put_u8(1);
+        let permissions_bytes = permissions.to_bytes();
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u32_le(permissions_bytes.len() as u32);
+        bytes.put_slice(&permissions_bytes);
+
+        let command = CreateRole::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.name, name);
+        assert_eq!(command.permissions.unwrap(), permissions);
+    }
+
+    fn get_permissions() -> Permissions {
+        Permissions {
+            global: GlobalPermissions {
+                read_streams: true,
+                read_topics: true,
+                poll_messages: true,
+                ..Default::default()
+            },
+            streams: None,
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, UPDATE_ROLE_CODE};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::models::permissions::Permissions;
+use crate::users::defaults::*;
+use crate::utils::text;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::from_utf8;
+
+/// `UpdateRole` command is used to update a role's name and permissions.
+/// The permissions of all the users having this role assigned are updated accordingly.
+/// It has additional payload:
+/// - `role_id` - unique role ID (numeric or name).
+/// - `name` - new name (optional), if provided, must be between 3 and 50 characters long.
+/// - `permissions` - new permissions (optional), if not provided, the existing role's permissions will remain unchanged.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct UpdateRole {
+    /// Unique role ID (numeric or name).
+    #[serde(skip)]
+    pub role_id: Identifier,
+    /// New name (optional), if provided, must be between 3 and 50 characters long.
+    pub name: Option<String>,
+    /// New permissions (optional), if not provided, the existing role's permissions will remain unchanged.
+    pub permissions: Option<Permissions>,
+}
+
+impl Command for UpdateRole {
+    fn code(&self) -> u32 {
+        UPDATE_ROLE_CODE
+    }
+}
+
+impl Validatable<IggyError> for UpdateRole {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.name.is_none() {
+            return Ok(());
+        }
+
+        let name = self.name.as_ref().unwrap();
+        if name.is_empty() || name.len() > MAX_ROLE_NAME_LENGTH || name.len() < MIN_ROLE_NAME_LENGTH
+        {
+            return Err(IggyError::InvalidRoleName);
+        }
+
+        if !text::is_resource_name_valid(name) {
+            return Err(IggyError::InvalidRoleName);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for UpdateRole {
+    fn to_bytes(&self) -> Bytes {
+        let role_id_bytes = self.role_id.to_bytes();
+        let mut bytes = BytesMut::new();
+        bytes.put_slice(&role_id_bytes);
+        if let Some(name) = &self.name {
+            bytes.put_u8(1);
+            #[allow(clippy::cast_possible_truncation)]
+            bytes.put_u8(name.len() as u8);
+            bytes.put_slice(name.as_bytes());
+        } else {
+            bytes.put_u8(0);
+        }
+        if let Some(permissions) = &self.permissions {
+            bytes.put_u8(1);
+            let permissions = permissions.to_bytes();
+            #[allow(clippy::cast_possible_truncation)]
+            bytes.put_u32_le(permissions.len() as u32);
+            bytes.put_slice(&permissions);
+        } else {
+            bytes.put_u8(0);
+        }
+
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<UpdateRole, IggyError> {
+        if bytes.len() < 5 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let role_id = Identifier::from_bytes(bytes.clone())?;
+        let mut position = role_id.get_size_bytes() as usize;
+        let has_name = bytes[position];
+        if has_name > 1 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        position += 1;
+        let name = if has_name == 1 {
+            let name_length = bytes[position];
+            position += 1;
+            let name = from_utf8(&bytes[position..position + name_length as usize])?.to_string();
+            position += name_length as usize;
+            Some(name)
+        } else {
+            None
+        };
+
+        let has_permissions = bytes[position];
+        if has_permissions > 1 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        position += 1;
+        let permissions = if has_permissions == 1 {
+            let permissions_length = u32::from_le_bytes(bytes[position..position + 4].try_into()?);
+            position += 4;
+            Some(Permissions::from_bytes(
+                bytes.slice(position..position + permissions_length as usize),
+            )?)
+        } else {
+            None
+        };
+
+        let command = UpdateRole {
+            role_id,
+            name,
+            permissions,
+        };
+        Ok(command)
+    }
+}
+
+impl Display for UpdateRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self.name.as_deref().unwrap_or("");
+        let permissions = if let Some(permissions) = &self.permissions {
+            permissions.to_string()
+        } else {
+            "unchanged_permissions".to_string()
+        };
+        write!(f, "{}|{}|{}", self.role_id, name, permissions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::permissions::GlobalPermissions;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = UpdateRole {
+            role_id: Identifier::numeric(1).unwrap(),
+            name: Some("writers".to_string()),
+            permissions: Some(get_permissions()),
+        };
+
+        let bytes = command.to_bytes();
+        let role_id = Identifier::from_bytes(bytes.clone()).unwrap();
+        let mut position = role_id.get_size_bytes() as usize;
+        let has_name = bytes[position];
+        position += 1;
+        let name_length = bytes[position];
+        position += 1;
+        let name = from_utf8(&bytes[position..position + name_length as usize])
+            .unwrap()
+            .to_string();
+        position += name_length as usize;
+        let has_permissions = bytes[position];
+        position += 1;
+        let permissions_length =
+            u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap());
+        position += 4;
+        let permissions =
+            Permissions::from_bytes(bytes.slice(position..position + permissions_length as usize))
+                .unwrap();
+
+        assert!(!bytes.is_empty());
+        assert_eq!(role_id, command.role_id);
+        assert_eq!(has_name, 1);
+        assert_eq!(name, command.name.unwrap());
+        assert_eq!(has_permissions, 1);
+        assert_eq!(permissions, command.permissions.unwrap());
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let role_id = Identifier::numeric(1).unwrap();
+        let name = "writers";
+        let mut bytes = BytesMut::new();
+        bytes.put_slice(&role_id.to_bytes());
+        bytes.put_u8(1);
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u8(name.len() as u8);
+        bytes.put_slice(name.as_bytes());
+        bytes.put_u8(0);
+
+        let command = UpdateRole::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.role_id, role_id);
+        assert_eq!(command.name.unwrap(), name);
+        assert!(command.permissions.is_none());
+    }
+
+    fn get_permissions() -> Permissions {
+        Permissions {
+            global: GlobalPermissions {
+                send_messages: true,
+                ..Default::default()
+            },
+            streams: None,
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, CREATE_BACKUP_CODE};
+use crate::error::IggyError;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::from_utf8;
+
+const MAX_NAME_LENGTH: usize = 255;
+
+/// `CreateBackup` command is used to create a consistent backup of the data directory while the server is running.
+/// It has additional payload:
+/// - `name` - optional unique name of the backup, if not provided, the name is generated based on the current timestamp.
+///   Must be between 1 and 255 characters long and can contain only alphanumeric characters, `-`, `_` and `.`.
+/// - `archive` - whether the backup should be additionally uploaded by the configured archiver (e.g. to S3).
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct CreateBackup {
+    /// Optional unique name of the backup, if not provided, the name is generated based on the current timestamp.
+    pub name: Option<String>,
+    /// Whether the backup should be additionally uploaded by the configured archiver (e.g. to S3).
+    #[serde(default)]
+    pub archive: bool,
+}
+
+impl Command for CreateBackup {
+    fn code(&self) -> u32 {
+        CREATE_BACKUP_CODE
+    }
+}
+
+impl Validatable<IggyError> for CreateBackup {
+    fn validate(&self) -> Result<(), IggyError> {
+        let Some(name) = &self.name else {
+            return Ok(());
+        };
+
+        if name.is_empty()
+            || name.len() > MAX_NAME_LENGTH
+            || name.starts_with('.')
+            || !name
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || matches!(char, '-' | '_' | '.'))
+        {
+            return Err(IggyError::InvalidBackupName);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for CreateBackup {
+    fn to_bytes(&self) -> Bytes {
+        let name = self.name.as_deref().unwrap_or_default();
+        let mut bytes = BytesMut::with_capacity(2 + name.len());
+        bytes.put_u8(if self.archive { 1 } else { 0 });
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u8(name.len() as u8);
+        bytes.put_slice(name.as_bytes());
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<CreateBackup, IggyError> {
+        if bytes.len() < 2 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let archive = match bytes[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(IggyError::InvalidCommand),
+        };
+        let name_length = bytes[1] as usize;
+        if bytes.len() != 2 + name_length {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let name = match name_length {
+            0 => None,
+            _ => Some(from_utf8(&bytes[2..])?.to_string()),
+        };
+        let command = CreateBackup { name, archive };
+        Ok(command)
+    }
+}
+
+impl Display for CreateBackup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}",
+            self.name.as_deref().unwrap_or_default(),
+            self.archive
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = CreateBackup {
+            name: Some("backup-1".to_string()),
+            archive: true,
+        };
+
+        let bytes = command.to_bytes();
+        let archive = bytes[0];
+        let name_length = bytes[1] as usize;
+        let name = from_utf8(&bytes[2..2 + name_length]).unwrap();
+
+        assert!(!bytes.is_empty());
+        assert_eq!(archive, 1);
+        assert_eq!(name, command.name.unwrap());
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let name = "backup-1";
+        let mut bytes = BytesMut::with_capacity(2 + name.len());
+        bytes.put_u8(0);
+        bytes.put_u8(name.len() as u8);
+        bytes.put_slice(name.as_bytes());
+
+        let command = CreateBackup::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert!(!command.archive);
+        assert_eq!(command.name.unwrap(), name);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes_given_empty_name() {
+        let command = CreateBackup::from_bytes(Bytes::from_static(&[1, 0])).unwrap();
+
+        assert!(command.archive);
+        assert!(command.name.is_none());
+    }
+
+    #[test]
+    fn should_not_be_valid_given_name_with_path_separators() {
+        for name in ["", "..", "../backup", "backups/1", ".hidden"] {
+            let command = CreateBackup {
+                name: Some(name.to_string()),
+                archive: false,
+            };
+
+            assert!(command.validate().is_err());
+        }
+    }
+}
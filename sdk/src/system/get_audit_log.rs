@@ -0,0 +1,125 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, GET_AUDIT_LOG_CODE};
+use crate::error::IggyError;
+use crate::models::user_info::UserId;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+const DEFAULT_COUNT: u32 = 100;
+
+/// `GetAuditLog` command is used to get the most recent entries of the audit log.
+/// It has additional payload:
+/// - `count` - the maximum number of the most recent entries to return, must be greater than 0.
+/// - `user_id` - optional unique ID (numeric) of the user to return the entries for, if not provided, the entries of all users are returned.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct GetAuditLog {
+    /// The maximum number of the most recent entries to return, must be greater than 0.
+    #[serde(default = "default_count")]
+    pub count: u32,
+    /// Optional unique ID (numeric) of the user to return the entries for, if not provided, the entries of all users are returned.
+    pub user_id: Option<UserId>,
+}
+
+impl Default for GetAuditLog {
+    fn default() -> Self {
+        GetAuditLog {
+            count: default_count(),
+            user_id: None,
+        }
+    }
+}
+
+impl Command for GetAuditLog {
+    fn code(&self) -> u32 {
+        GET_AUDIT_LOG_CODE
+    }
+}
+
+impl Validatable<IggyError> for GetAuditLog {
+    fn validate(&self) -> Result<(), IggyError> {
+        if self.count == 0 {
+            return Err(IggyError::InvalidAuditLogCount);
+        }
+
+        Ok(())
+    }
+}
+
+impl BytesSerializable for GetAuditLog {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(8);
+        bytes.put_u32_le(self.count);
+        bytes.put_u32_le(self.user_id.unwrap_or(0));
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<GetAuditLog, IggyError> {
+        if bytes.len() != 8 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let count = u32::from_le_bytes(bytes[..4].try_into()?);
+        let user_id = u32::from_le_bytes(bytes[4..8].try_into()?);
+        let user_id = if user_id == 0 { None } else { Some(user_id) };
+        let command = GetAuditLog { count, user_id };
+        Ok(command)
+    }
+}
+
+impl Display for GetAuditLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.count, self.user_id.unwrap_or(0))
+    }
+}
+
+fn default_count() -> u32 {
+    DEFAULT_COUNT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = GetAuditLog {
+            count: 10,
+            user_id: Some(2),
+        };
+
+        let bytes = command.to_bytes();
+        let count = u32::from_le_bytes(bytes[..4].try_into().unwrap());
+        let user_id = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+
+        assert!(!bytes.is_empty());
+        assert_eq!(count, command.count);
+        assert_eq!(user_id, command.user_id.unwrap());
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let count = 10u32;
+        let mut bytes = BytesMut::with_capacity(8);
+        bytes.put_u32_le(count);
+        bytes.put_u32_le(0);
+
+        let command = GetAuditLog::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.count, count);
+        assert!(command.user_id.is_none());
+    }
+
+    #[test]
+    fn should_not_be_valid_given_zero_count() {
+        let command = GetAuditLog {
+            count: 0,
+            user_id: None,
+        };
+
+        assert!(command.validate().is_err());
+    }
+}
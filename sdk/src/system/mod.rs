@@ -1,3 +1,5 @@
+pub mod create_backup;
+pub mod get_audit_log;
 pub mod get_client;
 pub mod get_clients;
 pub mod get_me;
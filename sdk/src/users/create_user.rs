@@ -48,6 +48,10 @@ impl Default for CreateUser {
 
 impl Validatable<IggyError> for CreateUser {
     fn validate(&self) -> Result<(), IggyError> {
+        if self.status == UserStatus::Locked {
+            return Err(IggyError::InvalidUserStatus);
+        }
+
         if self.username.is_empty()
             || self.username.len() > MAX_USERNAME_LENGTH
             || self.username.len() < MIN_USERNAME_LENGTH
@@ -6,5 +6,7 @@ pub mod get_user;
 pub mod get_users;
 pub mod login_user;
 pub mod logout_user;
+pub mod unlock_user;
 pub mod update_permissions;
+pub mod update_roles;
 pub mod update_user;
@@ -0,0 +1,143 @@
+use crate::bytes_serializable::BytesSerializable;
+use crate::command::{Command, UPDATE_ROLES_CODE};
+use crate::error::IggyError;
+use crate::identifier::Identifier;
+use crate::models::role_info::RoleId;
+use crate::validatable::Validatable;
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// `UpdateRoles` command is used to replace the roles assigned to a user.
+/// The effective permissions of the user are the union of its own permissions and the permissions of all its roles.
+/// It has additional payload:
+/// - `user_id` - unique user ID (numeric or name).
+/// - `roles` - unique IDs of the roles to assign, an empty list removes all the roles from the user.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct UpdateRoles {
+    /// Unique user ID (numeric or name).
+    #[serde(skip)]
+    pub user_id: Identifier,
+    /// Unique IDs of the roles to assign, an empty list removes all the roles from the user.
+    pub roles: Vec<RoleId>,
+}
+
+impl Command for UpdateRoles {
+    fn code(&self) -> u32 {
+        UPDATE_ROLES_CODE
+    }
+}
+
+impl Validatable<IggyError> for UpdateRoles {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl BytesSerializable for UpdateRoles {
+    fn to_bytes(&self) -> Bytes {
+        let user_id_bytes = self.user_id.to_bytes();
+        let mut bytes = BytesMut::with_capacity(user_id_bytes.len() + 4 + 4 * self.roles.len());
+        bytes.put_slice(&user_id_bytes);
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u32_le(self.roles.len() as u32);
+        for role_id in &self.roles {
+            bytes.put_u32_le(*role_id);
+        }
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<UpdateRoles, IggyError> {
+        if bytes.len() < 7 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let user_id = Identifier::from_bytes(bytes.clone())?;
+        let mut position = user_id.get_size_bytes() as usize;
+        let roles_count = u32::from_le_bytes(bytes[position..position + 4].try_into()?);
+        position += 4;
+        if bytes.len() != position + 4 * roles_count as usize {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let mut roles = Vec::with_capacity(roles_count as usize);
+        for _ in 0..roles_count {
+            roles.push(u32::from_le_bytes(
+                bytes[position..position + 4].try_into()?,
+            ));
+            position += 4;
+        }
+
+        let command = UpdateRoles { user_id, roles };
+        Ok(command)
+    }
+}
+
+impl Display for UpdateRoles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let roles = self
+            .roles
+            .iter()
+            .map(|role_id| role_id.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+        write!(f, "{}|{}", self.user_id, roles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_be_serialized_as_bytes() {
+        let command = UpdateRoles {
+            user_id: Identifier::numeric(1).unwrap(),
+            roles: vec![1, 3],
+        };
+
+        let bytes = command.to_bytes();
+        let user_id = Identifier::from_bytes(bytes.clone()).unwrap();
+        let mut position = user_id.get_size_bytes() as usize;
+        let roles_count = u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap());
+        position += 4;
+        let first_role_id = u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap());
+        position += 4;
+        let second_role_id = u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap());
+
+        assert!(!bytes.is_empty());
+        assert_eq!(user_id, command.user_id);
+        assert_eq!(roles_count, 2);
+        assert_eq!(first_role_id, 1);
+        assert_eq!(second_role_id, 3);
+    }
+
+    #[test]
+    fn should_be_deserialized_from_bytes() {
+        let user_id = Identifier::named("user").unwrap();
+        let mut bytes = BytesMut::new();
+        bytes.put_slice(&user_id.to_bytes());
+        bytes.put_u32_le(2);
+        bytes.put_u32_le(2);
+        bytes.put_u32_le(5);
+
+        let command = UpdateRoles::from_bytes(bytes.freeze());
+        assert!(command.is_ok());
+
+        let command = command.unwrap();
+        assert_eq!(command.user_id, user_id);
+        assert_eq!(command.roles, vec![2, 5]);
+    }
+
+    #[test]
+    fn should_not_be_deserialized_given_invalid_roles_count() {
+        let user_id = Identifier::numeric(1).unwrap();
+        let mut bytes = BytesMut::new();
+        bytes.put_slice(&user_id.to_bytes());
+        bytes.put_u32_le(2);
+        bytes.put_u32_le(2);
+
+        let command = UpdateRoles::from_bytes(bytes.freeze());
+        assert!(command.is_err());
+    }
+}
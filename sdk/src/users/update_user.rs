@@ -32,6 +32,10 @@ impl Command for UpdateUser {
 
 impl Validatable<IggyError> for UpdateUser {
     fn validate(&self) -> Result<(), IggyError> {
+        if self.status == Some(UserStatus::Locked) {
+            return Err(IggyError::InvalidUserStatus);
+        }
+
         if self.username.is_none() {
             return Ok(());
         }
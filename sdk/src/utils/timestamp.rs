@@ -1,4 +1,5 @@
-use chrono::{DateTime, Local, Utc};
+use crate::error::IggyError;
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
 use core::fmt;
 use serde::{
     de::{self, Visitor},
@@ -6,6 +7,7 @@ use serde::{
 };
 use std::{
     ops::Add,
+    str::FromStr,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
@@ -79,6 +81,28 @@ impl Default for IggyTimestamp {
     }
 }
 
+impl FromStr for IggyTimestamp {
+    type Err = IggyError;
+
+    /// Parses the timestamp given as the number of microseconds since the Unix epoch,
+    /// the RFC 3339 date and time (e.g. `2024-05-01T10:00:00+02:00`)
+    /// or the UTC date and time in the `%Y-%m-%d %H:%M:%S` format with optional fractional seconds.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(micros) = s.parse::<u64>() {
+            return Ok(IggyTimestamp::from(micros));
+        }
+
+        if let Ok(date_time) = DateTime::parse_from_rfc3339(s) {
+            return Ok(IggyTimestamp(date_time.into()));
+        }
+
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f")
+            .map(|date_time| IggyTimestamp(date_time.and_utc().into()))
+            .map_err(|_| IggyError::InvalidFormat)
+    }
+}
+
 impl fmt::Display for IggyTimestamp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.to_utc_string(UTC_TIME_FORMAT))
@@ -145,6 +169,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_timestamp_from_str() {
+        let expected = IggyTimestamp::from(1694968446131680);
+        for value in [
+            "1694968446131680",
+            "2023-09-17T16:34:06.131680Z",
+            "2023-09-17T18:34:06.131680+02:00",
+            "2023-09-17 16:34:06.131680",
+        ] {
+            assert_eq!(IggyTimestamp::from_str(value).unwrap(), expected);
+        }
+        assert_eq!(
+            IggyTimestamp::from_str("2023-09-17 16:34:06")
+                .unwrap()
+                .to_string(),
+            "2023-09-17 16:34:06"
+        );
+        assert!(IggyTimestamp::from_str("yesterday").is_err());
+    }
+
     #[test]
     fn test_timestamp_from_u64() {
         let timestamp = IggyTimestamp::from(1663472051111);
@@ -5,4 +5,9 @@ use clap::Parser;
 pub struct Args {
     #[arg(short, long, default_value = "file")]
     pub config_provider: String,
+
+    /// Restores the state and the streams from the backup with the given name (or `latest`) before starting,
+    /// the data directory must not contain any state or streams
+    #[arg(long)]
+    pub restore_backup: Option<String>,
 }
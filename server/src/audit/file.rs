@@ -0,0 +1,195 @@
+use crate::audit::AuditLog;
+use async_trait::async_trait;
+use iggy::error::IggyError;
+use iggy::models::audit_log::AuditLogEntry;
+use iggy::models::user_info::UserId;
+use iggy::utils::byte_size::IggyByteSize;
+use std::path::Path;
+use tokio::fs;
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+const FILE_NAME: &str = "audit.log";
+
+/// The audit log stored as JSON lines in the rotating files.
+/// The current file is `audit.log`, and once it exceeds the configured size, it's renamed to `audit.log.1`,
+/// the previously rotated files are shifted by one (`audit.log.1` -> `audit.log.2` etc.) and the oldest ones are deleted.
+#[derive(Debug)]
+pub struct FileAuditLog {
+    path: String,
+    max_file_size: u64,
+    max_files: u32,
+    current_size: Mutex<u64>,
+}
+
+impl FileAuditLog {
+    pub fn new(path: &str, max_file_size: IggyByteSize, max_files: u32) -> Self {
+        Self {
+            path: path.to_string(),
+            max_file_size: max_file_size.as_bytes_u64(),
+            max_files,
+            current_size: Mutex::new(0),
+        }
+    }
+
+    fn get_file_path(&self, index: u32) -> String {
+        match index {
+            0 => format!("{}/{FILE_NAME}", self.path),
+            _ => format!("{}/{FILE_NAME}.{index}", self.path),
+        }
+    }
+
+    async fn rotate(&self) -> Result<(), IggyError> {
+        let oldest_file_path = self.get_file_path(self.max_files);
+        if Path::new(&oldest_file_path).exists() {
+            fs::remove_file(&oldest_file_path).await?;
+        }
+
+        for index in (0..self.max_files).rev() {
+            let file_path = self.get_file_path(index);
+            if Path::new(&file_path).exists() {
+                fs::rename(&file_path, self.get_file_path(index + 1)).await?;
+            }
+        }
+
+        let current_file_path = self.get_file_path(0);
+        if Path::new(&current_file_path).exists() {
+            fs::remove_file(&current_file_path).await?;
+        }
+
+        info!("Rotated audit log files at: {}", self.path);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AuditLog for FileAuditLog {
+    async fn init(&self) -> Result<(), IggyError> {
+        if !Path::new(&self.path).exists() {
+            info!("Creating audit log directory: {}", self.path);
+            fs::create_dir_all(&self.path).await?;
+        }
+
+        let file_path = self.get_file_path(0);
+        let mut current_size = self.current_size.lock().await;
+        *current_size = match fs::metadata(&file_path).await {
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        };
+        Ok(())
+    }
+
+    async fn record(&self, entry: AuditLogEntry) -> Result<(), IggyError> {
+        let mut line = serde_json::to_vec(&entry)
+            .map_err(|error| IggyError::CannotSerializeResource(error.into()))?;
+        line.push(b'\n');
+
+        let mut current_size = self.current_size.lock().await;
+        if *current_size > 0 && *current_size + line.len() as u64 > self.max_file_size {
+            self.rotate().await?;
+            *current_size = 0;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.get_file_path(0))
+            .await?;
+        file.write_all(&line).await?;
+        *current_size += line.len() as u64;
+        Ok(())
+    }
+
+    async fn load_entries(
+        &self,
+        count: u32,
+        user_id: Option<UserId>,
+    ) -> Result<Vec<AuditLogEntry>, IggyError> {
+        let count = count as usize;
+        let mut entries = Vec::with_capacity(count.min(1000));
+        let _current_size = self.current_size.lock().await;
+        for index in 0..=self.max_files {
+            let file_path = self.get_file_path(index);
+            if !Path::new(&file_path).exists() {
+                continue;
+            }
+
+            let content = fs::read_to_string(&file_path).await?;
+            for line in content.lines().rev() {
+                if line.is_empty() {
+                    continue;
+                }
+
+                let entry = match serde_json::from_str::<AuditLogEntry>(line) {
+                    Ok(entry) => entry,
+                    Err(error) => {
+                        warn!("Invalid audit log entry in file: {file_path}, error: {error}");
+                        continue;
+                    }
+                };
+
+                if user_id.is_some() && entry.user_id != user_id {
+                    continue;
+                }
+
+                entries.push(entry);
+                if entries.len() == count {
+                    return Ok(entries);
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iggy::utils::timestamp::IggyTimestamp;
+
+    fn create_entry(user_id: Option<UserId>, action: &str) -> AuditLogEntry {
+        AuditLogEntry {
+            timestamp: IggyTimestamp::now(),
+            user_id,
+            username: None,
+            address: "127.0.0.1:1234".to_string(),
+            transport: "TCP".to_string(),
+            action: action.to_string(),
+            result: AuditLogEntry::OK_RESULT.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn entries_should_be_loaded_newest_first_across_rotated_files() {
+        let path = format!("local_data_audit_{}", uuid::Uuid::new_v4().to_u128_le());
+        let audit_log = FileAuditLog::new(&path, IggyByteSize::from(200), 2);
+        audit_log.init().await.unwrap();
+        for index in 1..=6 {
+            audit_log
+                .record(create_entry(
+                    Some(index % 2 + 1),
+                    &format!("action.{index}"),
+                ))
+                .await
+                .unwrap();
+        }
+
+        let entries = audit_log.load_entries(10, None).await.unwrap();
+        let user_entries = audit_log.load_entries(10, Some(1)).await.unwrap();
+        let limited_entries = audit_log.load_entries(1, None).await.unwrap();
+        fs::remove_dir_all(&path).await.unwrap();
+
+        let actions = entries
+            .iter()
+            .map(|entry| entry.action.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(actions, vec!["action.6", "action.5", "action.4"]);
+        assert_eq!(user_entries.len(), 2);
+        assert!(user_entries.iter().all(|entry| entry.user_id == Some(1)));
+        assert_eq!(limited_entries.len(), 1);
+        assert_eq!(limited_entries[0].action, "action.6");
+    }
+}
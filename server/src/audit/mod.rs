@@ -0,0 +1,18 @@
+pub mod file;
+
+use async_trait::async_trait;
+use iggy::error::IggyError;
+use iggy::models::audit_log::AuditLogEntry;
+use iggy::models::user_info::UserId;
+use std::fmt::Debug;
+
+#[async_trait]
+pub trait AuditLog: Send + Sync + Debug {
+    async fn init(&self) -> Result<(), IggyError>;
+    async fn record(&self, entry: AuditLogEntry) -> Result<(), IggyError>;
+    async fn load_entries(
+        &self,
+        count: u32,
+        user_id: Option<UserId>,
+    ) -> Result<Vec<AuditLogEntry>, IggyError>;
+}
@@ -9,32 +9,94 @@ use crate::binary::handlers::personal_access_tokens::{
     create_personal_access_token_handler, delete_personal_access_token_handler,
     get_personal_access_tokens_handler, login_with_personal_access_token_handler,
 };
+use crate::binary::handlers::roles::{
+    create_role_handler, delete_role_handler, get_role_handler, get_roles_handler,
+    update_role_handler,
+};
 use crate::binary::handlers::streams::*;
 use crate::binary::handlers::system::*;
 use crate::binary::handlers::topics::*;
 use crate::binary::handlers::users::{
     change_password_handler, create_user_handler, delete_user_handler, get_user_handler,
-    get_users_handler, login_user_handler, logout_user_handler, update_permissions_handler,
-    update_user_handler,
+    get_users_handler, login_user_handler, logout_user_handler, unlock_user_handler,
+    update_permissions_handler, update_roles_handler, update_user_handler,
 };
 use crate::binary::sender::Sender;
 use crate::command::ServerCommand;
+use crate::log::telemetry;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
+use iggy::command::get_name_from_code;
 use iggy::error::IggyError;
-use tracing::{debug, error};
+use iggy::models::audit_log::AuditLogEntry;
+use iggy::models::user_info::UserId;
+use std::time::Instant;
+use tracing::{debug, error, field, info_span, Instrument, Span};
 
 pub async fn handle(
     command: ServerCommand,
     sender: &mut dyn Sender,
     session: &Session,
     system: SharedSystem,
+    transport: &'static str,
 ) -> Result<(), IggyError> {
-    match try_handle(command, sender, session, &system).await {
+    let audited_action = get_audited_action(&command);
+    let login_username = match &command {
+        ServerCommand::LoginUser(command) => Some(command.username.clone()),
+        _ => None,
+    };
+    let is_send_messages = matches!(command, ServerCommand::SendMessages(_));
+    let is_poll_messages = matches!(
+        command,
+        ServerCommand::PollMessages(_) | ServerCommand::PollMessageBatches(_)
+    );
+    let user_id = session.get_user_id();
+    let span = create_span(&command, session, transport);
+    let started_at = Instant::now();
+    let result = try_handle(command, sender, session, &system)
+        .instrument(span.clone())
+        .await;
+    if result.is_err() {
+        span.record("otel.status_code", "ERROR");
+    }
+    if is_send_messages || is_poll_messages {
+        let latency = started_at.elapsed();
+        let system = system.read().await;
+        if is_send_messages {
+            system
+                .metrics
+                .observe_send_messages_latency(transport, latency);
+        } else {
+            system
+                .metrics
+                .observe_poll_messages_latency(transport, latency);
+        }
+    }
+    if let Some((action, is_data_command)) = audited_action {
+        audit(
+            action,
+            is_data_command,
+            user_id,
+            login_username.as_deref(),
+            session,
+            &system,
+            result.as_ref().map(|_| ()),
+        )
+        .await;
+    }
+
+    match result {
         Ok(_) => {
             debug!("Command was handled successfully, session: {session}.");
             Ok(())
         }
+        // The response was interrupted after its length had been sent, so the connection is closed instead.
+        Err(IggyError::ConnectionClosed) => {
+            debug!(
+                "Command was not handled successfully, session: {session}, closing the connection."
+            );
+            Err(IggyError::ConnectionClosed)
+        }
         Err(error) => {
             error!("Command was not handled successfully, session: {session}, error: {error}");
             sender.send_error_response(error).await
@@ -42,6 +104,85 @@ pub async fn handle(
     }
 }
 
+/// Creates the span for the command, the trace parent of the first sent message (if any)
+/// becomes its parent, so the span can be correlated with the producer and the consumers.
+fn create_span(command: &ServerCommand, session: &Session, transport: &'static str) -> Span {
+    let name = get_name_from_code(command.code()).unwrap_or("unknown");
+    let span = info_span!(
+        "command",
+        otel.name = name,
+        otel.kind = "server",
+        otel.status_code = field::Empty,
+        transport,
+        client_id = session.client_id,
+    );
+    if let ServerCommand::SendMessages(command) = command {
+        if let Some(trace_parent) = command
+            .messages
+            .first()
+            .and_then(|message| message.get_trace_parent())
+        {
+            telemetry::set_parent(&span, &trace_parent);
+        }
+    }
+    span
+}
+
+/// Returns the name of the command to be recorded in the audit log and whether it's a data command.
+fn get_audited_action(command: &ServerCommand) -> Option<(&'static str, bool)> {
+    let is_data_command = match command {
+        ServerCommand::Ping(_) => return None,
+        ServerCommand::SendMessages(_)
+        | ServerCommand::PollMessages(_)
+        | ServerCommand::PollMessageBatches(_)
+        | ServerCommand::StoreConsumerOffset(_)
+        | ServerCommand::GetConsumerOffset(_)
+        | ServerCommand::GetConsumerLag(_) => true,
+        _ => false,
+    };
+    let action = get_name_from_code(command.code()).ok()?;
+    Some((action, is_data_command))
+}
+
+async fn audit(
+    action: &str,
+    is_data_command: bool,
+    user_id: UserId,
+    username: Option<&str>,
+    session: &Session,
+    system: &SharedSystem,
+    result: Result<(), &IggyError>,
+) {
+    let system = system.read().await;
+    if !system.is_audited(is_data_command) {
+        return;
+    }
+
+    // The user might have been just logged in or logged out by the command.
+    let user_id = match session.get_user_id() {
+        0 => user_id,
+        current_user_id => current_user_id,
+    };
+    let transport = system
+        .get_client_transport(session.client_id)
+        .await
+        .unwrap_or_default();
+    let result = match result {
+        Ok(_) => AuditLogEntry::OK_RESULT,
+        Err(error) => error.as_string(),
+    };
+    system
+        .record_audit_entry(
+            user_id,
+            username,
+            &session.ip_address,
+            &transport,
+            action,
+            result,
+        )
+        .await;
+}
+
 async fn try_handle(
     command: ServerCommand,
     sender: &mut dyn Sender,
@@ -63,6 +204,12 @@ async fn try_handle(
         ServerCommand::GetClients(command) => {
             get_clients_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::GetAuditLog(command) => {
+            get_audit_log_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::CreateBackup(command) => {
+            create_backup_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::GetUser(command) => {
             get_user_handler::handle(command, sender, session, system).await
         }
@@ -81,6 +228,9 @@ async fn try_handle(
         ServerCommand::UpdatePermissions(command) => {
             update_permissions_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::UpdateRoles(command) => {
+            update_roles_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::ChangePassword(command) => {
             change_password_handler::handle(command, sender, session, system).await
         }
@@ -90,6 +240,9 @@ async fn try_handle(
         ServerCommand::LogoutUser(command) => {
             logout_user_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::UnlockUser(command) => {
+            unlock_user_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::GetPersonalAccessTokens(command) => {
             get_personal_access_tokens_handler::handle(command, sender, session, system).await
         }
@@ -102,15 +255,36 @@ async fn try_handle(
         ServerCommand::LoginWithPersonalAccessToken(command) => {
             login_with_personal_access_token_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::GetRole(command) => {
+            get_role_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::GetRoles(command) => {
+            get_roles_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::CreateRole(command) => {
+            create_role_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::DeleteRole(command) => {
+            delete_role_handler::handle(command, sender, session, system).await
+        }
+        ServerCommand::UpdateRole(command) => {
+            update_role_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::SendMessages(command) => {
             send_messages_handler::handle(command, sender, session, system).await
         }
         ServerCommand::PollMessages(command) => {
             poll_messages_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::PollMessageBatches(command) => {
+            poll_message_batches_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::GetConsumerOffset(command) => {
             get_consumer_offset_handler::handle(command, sender, session, system).await
         }
+        ServerCommand::GetConsumerLag(command) => {
+            get_consumer_lag_handler::handle(command, sender, session, system).await
+        }
         ServerCommand::StoreConsumerOffset(command) => {
             store_consumer_offset_handler::handle(command, sender, session, system).await
         }
@@ -6,31 +6,32 @@ use crate::streaming::systems::system::SharedSystem;
 use anyhow::Result;
 use iggy::consumer_groups::create_consumer_group::CreateConsumerGroup;
 use iggy::error::IggyError;
+use iggy::locking::IggySharedMutFn;
 use tracing::debug;
 
 pub async fn handle(
-    command: CreateConsumerGroup,
+    mut command: CreateConsumerGroup,
     sender: &mut dyn Sender,
     session: &Session,
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    let consumer_group_bytes;
-    {
-        let mut system = system.write().await;
-        let consumer_group = system
-            .create_consumer_group(
-                session,
-                &command.stream_id,
-                &command.topic_id,
-                command.group_id,
-                &command.name,
-            )
-            .await?;
-        let consumer_group = consumer_group.read().await;
-        consumer_group_bytes = mapper::map_consumer_group(&consumer_group).await;
-    }
     let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    let consumer_group = system
+        .create_consumer_group(
+            session,
+            &command.stream_id,
+            &command.topic_id,
+            command.group_id,
+            &command.name,
+        )
+        .await?;
+    let consumer_group = consumer_group.read().await;
+    let consumer_group_bytes = mapper::map_consumer_group(&consumer_group).await;
+    // The assigned ID is stored in the state entry, as the consumer groups might be created concurrently.
+    command.group_id = Some(consumer_group.group_id);
+    drop(consumer_group);
     system
         .state
         .apply(
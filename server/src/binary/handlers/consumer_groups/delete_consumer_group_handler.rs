@@ -14,18 +14,16 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    {
-        let mut system = system.write().await;
-        system
-            .delete_consumer_group(
-                session,
-                &command.stream_id,
-                &command.topic_id,
-                &command.group_id,
-            )
-            .await?;
-    }
     let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .delete_consumer_group(
+            session,
+            &command.stream_id,
+            &command.topic_id,
+            &command.group_id,
+        )
+        .await?;
     system
         .state
         .apply(
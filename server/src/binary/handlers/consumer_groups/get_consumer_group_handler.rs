@@ -5,6 +5,7 @@ use crate::streaming::systems::system::SharedSystem;
 use anyhow::Result;
 use iggy::consumer_groups::get_consumer_group::GetConsumerGroup;
 use iggy::error::IggyError;
+use iggy::locking::IggySharedMutFn;
 use tracing::debug;
 
 pub async fn handle(
@@ -15,12 +16,14 @@ pub async fn handle(
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
     let system = system.read().await;
-    let consumer_group = system.get_consumer_group(
-        session,
-        &command.stream_id,
-        &command.topic_id,
-        &command.group_id,
-    )?;
+    let consumer_group = system
+        .get_consumer_group(
+            session,
+            &command.stream_id,
+            &command.topic_id,
+            &command.group_id,
+        )
+        .await?;
     let consumer_group = consumer_group.read().await;
     let consumer_group = mapper::map_consumer_group(&consumer_group).await;
     sender.send_ok_response(&consumer_group).await?;
@@ -15,8 +15,9 @@ pub async fn handle(
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
     let system = system.read().await;
-    let consumer_groups =
-        system.get_consumer_groups(session, &command.stream_id, &command.topic_id)?;
+    let consumer_groups = system
+        .get_consumer_groups(session, &command.stream_id, &command.topic_id)
+        .await?;
     let consumer_groups = mapper::map_consumer_groups(&consumer_groups).await;
     sender.send_ok_response(&consumer_groups).await?;
     Ok(())
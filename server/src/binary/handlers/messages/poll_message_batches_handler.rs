@@ -0,0 +1,39 @@
+use crate::binary::mapper;
+use crate::binary::sender::Sender;
+use crate::streaming::polling_consumer::PollingConsumer;
+use crate::streaming::session::Session;
+use crate::streaming::systems::messages::PollingArgs;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use iggy::error::IggyError;
+use iggy::messages::poll_messages::PollMessages;
+use tracing::debug;
+
+pub async fn handle(
+    command: PollMessages,
+    sender: &mut dyn Sender,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let consumer =
+        PollingConsumer::from_consumer(&command.consumer, session.client_id, command.partition_id);
+    let system = system.read().await;
+    let polled_batches = system
+        .poll_batches(
+            session,
+            consumer,
+            &command.stream_id,
+            &command.topic_id,
+            PollingArgs::new(command.strategy, command.count, command.auto_commit),
+        )
+        .await?;
+    // The locks are released before sending the batches, the regions of the segment files remain readable
+    // through the already opened files, even if the segments are deleted in the meantime.
+    drop(system);
+    let header = mapper::map_polled_batches_header(&polled_batches);
+    sender
+        .send_ok_response_with_batches(&header, polled_batches.chunks)
+        .await?;
+    Ok(())
+}
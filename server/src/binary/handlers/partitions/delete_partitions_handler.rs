@@ -14,19 +14,16 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    {
-        let mut system = system.write().await;
-        system
-            .delete_partitions(
-                session,
-                &command.stream_id,
-                &command.topic_id,
-                command.partitions_count,
-            )
-            .await?;
-    }
-
     let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .delete_partitions(
+            session,
+            &command.stream_id,
+            &command.topic_id,
+            command.partitions_count,
+        )
+        .await?;
     system
         .state
         .apply(
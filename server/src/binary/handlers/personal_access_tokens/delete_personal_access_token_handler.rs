@@ -14,14 +14,11 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    {
-        let mut system = system.write().await;
-        system
-            .delete_personal_access_token(session, &command.name)
-            .await?;
-    }
-
     let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .delete_personal_access_token(session, &command.name)
+        .await?;
     system
         .state
         .apply(
@@ -14,8 +14,12 @@ pub async fn handle(
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
     let system = system.read().await;
-    let personal_access_tokens = system.get_personal_access_tokens(session).await?;
-    let personal_access_tokens = mapper::map_personal_access_tokens(&personal_access_tokens);
+    let personal_access_tokens = {
+        let user = system.get_personal_access_tokens(session).await?;
+        mapper::map_personal_access_tokens(
+            &user.personal_access_tokens.values().collect::<Vec<_>>(),
+        )
+    };
     sender.send_ok_response(&personal_access_tokens).await?;
     Ok(())
 }
@@ -15,10 +15,10 @@ pub async fn handle(
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
     let system = system.read().await;
-    let user = system
+    let user_id = system
         .login_with_personal_access_token(&command.token, Some(session))
         .await?;
-    let identity_info = mapper::map_identity_info(user.id);
+    let identity_info = mapper::map_identity_info(user_id);
     sender.send_ok_response(&identity_info).await?;
     Ok(())
 }
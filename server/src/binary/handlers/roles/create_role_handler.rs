@@ -0,0 +1,23 @@
+use crate::binary::sender::Sender;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use iggy::error::IggyError;
+use iggy::roles::create_role::CreateRole;
+use tracing::debug;
+
+pub async fn handle(
+    command: CreateRole,
+    sender: &mut dyn Sender,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .create_role(session, &command.name, command.permissions)
+        .await?;
+    sender.send_empty_ok_response().await?;
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use crate::binary::sender::Sender;
+use crate::state::command::EntryCommand;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use iggy::error::IggyError;
+use iggy::roles::delete_role::DeleteRole;
+use tracing::debug;
+
+pub async fn handle(
+    command: DeleteRole,
+    sender: &mut dyn Sender,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system.delete_role(session, &command.role_id).await?;
+    system
+        .state
+        .apply(session.get_user_id(), EntryCommand::DeleteRole(command))
+        .await?;
+    sender.send_empty_ok_response().await?;
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use crate::binary::mapper;
+use crate::binary::sender::Sender;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use iggy::error::IggyError;
+use iggy::roles::get_role::GetRole;
+use tracing::debug;
+
+pub async fn handle(
+    command: GetRole,
+    sender: &mut dyn Sender,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let system = system.read().await;
+    let bytes = mapper::map_role(&*system.find_role(session, &command.role_id).await?);
+    sender.send_ok_response(&bytes).await?;
+    Ok(())
+}
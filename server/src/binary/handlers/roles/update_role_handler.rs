@@ -0,0 +1,33 @@
+use crate::binary::sender::Sender;
+use crate::state::command::EntryCommand;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use iggy::error::IggyError;
+use iggy::roles::update_role::UpdateRole;
+use tracing::debug;
+
+pub async fn handle(
+    command: UpdateRole,
+    sender: &mut dyn Sender,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .update_role(
+            session,
+            &command.role_id,
+            command.name.clone(),
+            command.permissions.clone(),
+        )
+        .await?;
+    system
+        .state
+        .apply(session.get_user_id(), EntryCommand::UpdateRole(command))
+        .await?;
+    sender.send_empty_ok_response().await?;
+    Ok(())
+}
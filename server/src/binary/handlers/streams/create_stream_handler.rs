@@ -14,17 +14,21 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    {
-        let mut system = system.write().await;
-        system
-            .create_stream(session, command.stream_id, &command.name)
-            .await?;
-    }
-
     let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    let stream_id = system
+        .create_stream(session, command.stream_id, &command.name)
+        .await?;
+    // The assigned ID is stored in the state entry, as the streams might be created concurrently.
     system
         .state
-        .apply(session.get_user_id(), EntryCommand::CreateStream(command))
+        .apply(
+            session.get_user_id(),
+            EntryCommand::CreateStream(CreateStream {
+                stream_id: Some(stream_id),
+                name: command.name,
+            }),
+        )
         .await?;
     sender.send_empty_ok_response().await?;
     Ok(())
@@ -4,6 +4,7 @@ use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
 use anyhow::Result;
 use iggy::error::IggyError;
+use iggy::locking::IggySharedMutFn;
 use iggy::streams::get_stream::GetStream;
 use tracing::debug;
 
@@ -16,7 +17,8 @@ pub async fn handle(
     debug!("session: {session}, command: {command}");
     let system = system.read().await;
     let stream = system.find_stream(session, &command.stream_id)?;
-    let stream = mapper::map_stream(stream).await;
+    let stream = stream.read().await;
+    let stream = mapper::map_stream(&stream).await;
     sender.send_ok_response(&stream).await?;
     Ok(())
 }
@@ -14,14 +14,11 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    {
-        let mut system = system.write().await;
-        system
-            .update_stream(session, &command.stream_id, &command.name)
-            .await?;
-    }
-
     let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .update_stream(session, &command.stream_id, &command.name)
+        .await?;
     system
         .state
         .apply(session.get_user_id(), EntryCommand::UpdateStream(command))
@@ -0,0 +1,25 @@
+use crate::binary::mapper;
+use crate::binary::sender::Sender;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use iggy::error::IggyError;
+use iggy::system::create_backup::CreateBackup;
+use tracing::debug;
+
+pub async fn handle(
+    command: CreateBackup,
+    sender: &mut dyn Sender,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let backup = system
+        .write()
+        .await
+        .prepare_backup(session, command.name.as_deref(), command.archive)
+        .await?;
+    let backup = backup.complete().await?;
+    let backup = mapper::map_backup_info(&backup);
+    sender.send_ok_response(&backup).await?;
+    Ok(())
+}
@@ -1,3 +1,5 @@
+pub mod create_backup_handler;
+pub mod get_audit_log_handler;
 pub mod get_client_handler;
 pub mod get_clients_handler;
 pub mod get_me_handler;
@@ -8,30 +8,29 @@ use iggy::topics::create_topic::CreateTopic;
 use tracing::debug;
 
 pub async fn handle(
-    command: CreateTopic,
+    mut command: CreateTopic,
     sender: &mut dyn Sender,
     session: &Session,
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    {
-        let mut system = system.write().await;
-        system
-            .create_topic(
-                session,
-                &command.stream_id,
-                command.topic_id,
-                &command.name,
-                command.partitions_count,
-                command.message_expiry,
-                command.compression_algorithm,
-                command.max_topic_size,
-                command.replication_factor,
-            )
-            .await?;
-    }
-
     let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    let topic_id = system
+        .create_topic(
+            session,
+            &command.stream_id,
+            command.topic_id,
+            &command.name,
+            command.partitions_count,
+            command.message_expiry,
+            command.compression_algorithm,
+            command.max_topic_size,
+            command.replication_factor,
+        )
+        .await?;
+    // The assigned ID is stored in the state entry, as the topics might be created concurrently.
+    command.topic_id = Some(topic_id);
     system
         .state
         .apply(session.get_user_id(), EntryCommand::CreateTopic(command))
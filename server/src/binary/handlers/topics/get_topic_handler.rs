@@ -4,6 +4,7 @@ use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
 use anyhow::Result;
 use iggy::error::IggyError;
+use iggy::locking::IggySharedMutFn;
 use iggy::topics::get_topic::GetTopic;
 use tracing::debug;
 
@@ -15,8 +16,11 @@ pub async fn handle(
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
     let system = system.read().await;
-    let topic = system.find_topic(session, &command.stream_id, &command.topic_id)?;
-    let topic = mapper::map_topic(topic).await;
+    let topic = system
+        .find_topic(session, &command.stream_id, &command.topic_id)
+        .await?;
+    let topic = topic.read().await;
+    let topic = mapper::map_topic(&topic).await;
     sender.send_ok_response(&topic).await?;
     Ok(())
 }
@@ -15,7 +15,7 @@ pub async fn handle(
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
     let system = system.read().await;
-    let topics = system.find_topics(session, &command.stream_id)?;
+    let topics = system.find_topics(session, &command.stream_id).await?;
     let topics = mapper::map_topics(&topics).await;
     sender.send_ok_response(&topics).await?;
     Ok(())
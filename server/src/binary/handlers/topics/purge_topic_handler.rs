@@ -15,6 +15,7 @@ pub async fn handle(
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
     let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
     system
         .purge_topic(session, &command.stream_id, &command.topic_id)
         .await?;
@@ -14,23 +14,20 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    {
-        let mut system = system.write().await;
-        system
-            .update_topic(
-                session,
-                &command.stream_id,
-                &command.topic_id,
-                &command.name,
-                command.message_expiry,
-                command.compression_algorithm,
-                command.max_topic_size,
-                command.replication_factor,
-            )
-            .await?;
-    }
-
     let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .update_topic(
+            session,
+            &command.stream_id,
+            &command.topic_id,
+            &command.name,
+            command.message_expiry,
+            command.compression_algorithm,
+            command.max_topic_size,
+            command.replication_factor,
+        )
+        .await?;
     system
         .state
         .apply(session.get_user_id(), EntryCommand::UpdateTopic(command))
@@ -2,7 +2,6 @@ use crate::binary::sender::Sender;
 use crate::state::command::EntryCommand;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
-use crate::streaming::utils::crypto;
 use anyhow::Result;
 use iggy::error::IggyError;
 use iggy::users::change_password::ChangePassword;
@@ -15,20 +14,18 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    {
-        let mut system = system.write().await;
-        system
-            .change_password(
-                session,
-                &command.user_id,
-                &command.current_password,
-                &command.new_password,
-            )
-            .await?;
-    }
-
-    // For the security of the system, we hash the password before storing it in metadata.
     let system = system.read().await;
+    // For the security of the system, we hash the password before storing it in metadata.
+    let password_change = system
+        .prepare_password_change(
+            session,
+            &command.user_id,
+            &command.current_password,
+            &command.new_password,
+        )
+        .await?;
+    let _state_changes = system.state_changes.lock().await;
+    system.change_password(session, &password_change).await?;
     system
         .state
         .apply(
@@ -36,7 +33,7 @@ pub async fn handle(
             EntryCommand::ChangePassword(ChangePassword {
                 user_id: command.user_id.to_owned(),
                 current_password: "".into(),
-                new_password: crypto::hash_password(&command.new_password),
+                new_password: password_change.new_password_hash,
             }),
         )
         .await?;
@@ -1,8 +1,6 @@
 use crate::binary::sender::Sender;
-use crate::state::command::EntryCommand;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
-use crate::streaming::utils::crypto;
 use anyhow::Result;
 use iggy::error::IggyError;
 use iggy::users::create_user::CreateUser;
@@ -15,31 +13,17 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    {
-        let mut system = system.write().await;
-        system
-            .create_user(
-                session,
-                &command.username,
-                &command.password,
-                command.status,
-                command.permissions.clone(),
-            )
-            .await?;
-    }
-
-    // For the security of the system, we hash the password before storing it in metadata.
     let system = system.read().await;
+    // For the security of the system, we hash the password before storing it in metadata.
+    let password_hash = system.hash_new_password(session, &command.password)?;
+    let _state_changes = system.state_changes.lock().await;
     system
-        .state
-        .apply(
-            session.get_user_id(),
-            EntryCommand::CreateUser(CreateUser {
-                username: command.username.to_owned(),
-                password: crypto::hash_password(&command.password),
-                status: command.status,
-                permissions: command.permissions.clone(),
-            }),
+        .create_user(
+            session,
+            &command.username,
+            &password_hash,
+            command.status,
+            command.permissions,
         )
         .await?;
     sender.send_empty_ok_response().await?;
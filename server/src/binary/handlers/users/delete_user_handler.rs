@@ -14,12 +14,9 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    {
-        let mut system = system.write().await;
-        system.delete_user(session, &command.user_id).await?;
-    }
-
     let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system.delete_user(session, &command.user_id).await?;
     system
         .state
         .apply(session.get_user_id(), EntryCommand::DeleteUser(command))
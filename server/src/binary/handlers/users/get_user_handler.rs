@@ -14,8 +14,7 @@ pub async fn handle(
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
     let system = system.read().await;
-    let user = system.find_user(session, &command.user_id)?;
-    let bytes = mapper::map_user(user);
+    let bytes = mapper::map_user(&*system.find_user(session, &command.user_id).await?);
     sender.send_ok_response(&bytes).await?;
     Ok(())
 }
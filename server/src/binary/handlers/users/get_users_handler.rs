@@ -14,8 +14,10 @@ pub async fn handle(
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
     let system = system.read().await;
-    let users = system.get_users(session).await?;
-    let users = mapper::map_users(&users);
+    let users = {
+        let users = system.get_users(session).await?;
+        mapper::map_users(&users.values().collect::<Vec<_>>())
+    };
     sender.send_ok_response(&users).await?;
     Ok(())
 }
@@ -5,7 +5,8 @@ use crate::streaming::systems::system::SharedSystem;
 use anyhow::Result;
 use iggy::error::IggyError;
 use iggy::users::login_user::LoginUser;
-use tracing::debug;
+use tokio::time::sleep;
+use tracing::{debug, warn};
 
 pub async fn handle(
     command: LoginUser,
@@ -15,10 +16,34 @@ pub async fn handle(
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
     let system = system.read().await;
-    let user = system
-        .login_user(&command.username, &command.password, Some(session))
-        .await?;
-    let identity_info = mapper::map_identity_info(user.id);
+    let user_id = match system
+        .login_user(
+            &command.username,
+            &command.password,
+            session.ip_address.ip(),
+            Some(session),
+        )
+        .await
+    {
+        Ok(user_id) => user_id,
+        Err(error) => {
+            // The failed login response is delayed, so that the passwords cannot be guessed at full speed.
+            let delay = system.get_failed_login_delay(&command.username);
+            drop(system);
+            sleep(delay).await;
+            return Err(error);
+        }
+    };
+
+    // The password is rehashed without locking the users, only the new hash is applied exclusively.
+    if let Some(password_rehash) = system.rehash_password(user_id, &command.password).await {
+        let _state_changes = system.state_changes.lock().await;
+        if let Err(error) = system.apply_password_rehash(password_rehash).await {
+            warn!("Cannot rehash password for user with ID: {user_id}, error: {error}");
+        }
+    }
+
+    let identity_info = mapper::map_identity_info(user_id);
     sender.send_ok_response(&identity_info).await?;
     Ok(())
 }
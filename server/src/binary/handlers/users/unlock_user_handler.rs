@@ -0,0 +1,20 @@
+use crate::binary::sender::Sender;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::SharedSystem;
+use anyhow::Result;
+use iggy::error::IggyError;
+use iggy::users::unlock_user::UnlockUser;
+use tracing::debug;
+
+pub async fn handle(
+    command: UnlockUser,
+    sender: &mut dyn Sender,
+    session: &Session,
+    system: &SharedSystem,
+) -> Result<(), IggyError> {
+    debug!("session: {session}, command: {command}");
+    let system = system.read().await;
+    system.unlock_user(session, &command.user_id).await?;
+    sender.send_empty_ok_response().await?;
+    Ok(())
+}
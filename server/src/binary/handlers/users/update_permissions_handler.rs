@@ -14,14 +14,11 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    {
-        let mut system = system.write().await;
-        system
-            .update_permissions(session, &command.user_id, command.permissions.clone())
-            .await?;
-    }
-
     let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .update_permissions(session, &command.user_id, command.permissions.clone())
+        .await?;
     system
         .state
         .apply(
@@ -14,19 +14,16 @@ pub async fn handle(
     system: &SharedSystem,
 ) -> Result<(), IggyError> {
     debug!("session: {session}, command: {command}");
-    {
-        let mut system = system.write().await;
-        system
-            .update_user(
-                session,
-                &command.user_id,
-                command.username.clone(),
-                command.status,
-            )
-            .await?;
-    }
-
     let system = system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .update_user(
+            session,
+            &command.user_id,
+            command.username.clone(),
+            command.status,
+        )
+        .await?;
     system
         .state
         .apply(session.get_user_id(), EntryCommand::UpdateUser(command))
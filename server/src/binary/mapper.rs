@@ -1,18 +1,23 @@
+use crate::streaming::batching::polled_batches::PolledBatches;
 use crate::streaming::clients::client_manager::{Client, Transport};
 use crate::streaming::partitions::partition::Partition;
 use crate::streaming::personal_access_tokens::personal_access_token::PersonalAccessToken;
 use crate::streaming::streams::stream::Stream;
 use crate::streaming::topics::consumer_group::ConsumerGroup;
 use crate::streaming::topics::topic::Topic;
+use crate::streaming::users::role::Role;
 use crate::streaming::users::user::User;
 use bytes::{BufMut, Bytes, BytesMut};
 use iggy::bytes_serializable::BytesSerializable;
 use iggy::locking::{IggySharedMut, IggySharedMutFn};
+use iggy::models::audit_log::AuditLogEntry;
+use iggy::models::backup_info::BackupInfo;
+use iggy::models::consumer_lag_info::ConsumerLagInfo;
 use iggy::models::consumer_offset_info::ConsumerOffsetInfo;
 use iggy::models::messages::PolledMessages;
+use iggy::models::permissions::Permissions;
 use iggy::models::stats::Stats;
 use iggy::models::user_info::UserId;
-use tokio::sync::RwLock;
 
 pub fn map_stats(stats: &Stats) -> Bytes {
     let mut bytes = BytesMut::with_capacity(104);
@@ -42,6 +47,8 @@ pub fn map_stats(stats: &Stats) -> Bytes {
     bytes.put_slice(stats.os_version.as_bytes());
     bytes.put_u32_le(stats.kernel_version.len() as u32);
     bytes.put_slice(stats.kernel_version.as_bytes());
+    bytes.put_u32_le(stats.iggy_server_version.len() as u32);
+    bytes.put_slice(stats.iggy_server_version.as_bytes());
     bytes.freeze()
 }
 
@@ -53,6 +60,19 @@ pub fn map_consumer_offset(offset: &ConsumerOffsetInfo) -> Bytes {
     bytes.freeze()
 }
 
+pub fn map_consumer_lag(lags: &[ConsumerLagInfo]) -> Bytes {
+    let mut bytes = BytesMut::with_capacity(37 * lags.len());
+    for lag in lags {
+        bytes.put_u32_le(lag.partition_id);
+        bytes.put_u64_le(lag.current_offset);
+        bytes.put_u8(lag.stored_offset.is_some() as u8);
+        bytes.put_u64_le(lag.stored_offset.unwrap_or_default());
+        bytes.put_u64_le(lag.lag);
+        bytes.put_u64_le(lag.lag_bytes);
+    }
+    bytes.freeze()
+}
+
 pub async fn map_client(client: &Client) -> Bytes {
     let mut bytes = BytesMut::new();
     extend_client(client, &mut bytes);
@@ -73,17 +93,47 @@ pub async fn map_clients(clients: &[IggySharedMut<Client>]) -> Bytes {
     bytes.freeze()
 }
 
+pub fn map_audit_log(entries: &[AuditLogEntry]) -> Bytes {
+    let mut bytes = BytesMut::new();
+    for entry in entries {
+        bytes.put_u64_le(entry.timestamp.into());
+        bytes.put_u32_le(entry.user_id.unwrap_or(0));
+        extend_short_string(entry.username.as_deref().unwrap_or_default(), &mut bytes);
+        extend_short_string(&entry.address, &mut bytes);
+        extend_short_string(&entry.transport, &mut bytes);
+        let action = &entry.action.as_bytes()[..entry.action.len().min(u16::MAX as usize)];
+        bytes.put_u16_le(action.len() as u16);
+        bytes.put_slice(action);
+        extend_short_string(&entry.result, &mut bytes);
+    }
+    bytes.freeze()
+}
+
+pub fn map_backup_info(backup: &BackupInfo) -> Bytes {
+    let mut bytes = BytesMut::new();
+    bytes.put_u64_le(backup.created_at.into());
+    bytes.put_u64_le(backup.state_index);
+    bytes.put_u32_le(backup.partitions_count);
+    bytes.put_u32_le(backup.segments_count);
+    bytes.put_u64_le(backup.messages_count);
+    bytes.put_u64_le(backup.size.as_bytes_u64());
+    extend_short_string(&backup.name, &mut bytes);
+    bytes.freeze()
+}
+
+fn extend_short_string(value: &str, bytes: &mut BytesMut) {
+    let value = &value.as_bytes()[..value.len().min(u8::MAX as usize)];
+    bytes.put_u8(value.len() as u8);
+    bytes.put_slice(value);
+}
+
 pub fn map_user(user: &User) -> Bytes {
     let mut bytes = BytesMut::new();
     extend_user(user, &mut bytes);
-    if let Some(permissions) = &user.permissions {
-        bytes.put_u8(1);
-        let permissions = permissions.to_bytes();
-        #[allow(clippy::cast_possible_truncation)]
-        bytes.put_u32_le(permissions.len() as u32);
-        bytes.put_slice(&permissions);
-    } else {
-        bytes.put_u32_le(0);
+    extend_permissions(&user.permissions, &mut bytes);
+    bytes.put_u32_le(user.roles.len() as u32);
+    for role_id in &user.roles {
+        bytes.put_u32_le(*role_id);
     }
     bytes.freeze()
 }
@@ -96,6 +146,21 @@ pub fn map_users(users: &[&User]) -> Bytes {
     bytes.freeze()
 }
 
+pub fn map_role(role: &Role) -> Bytes {
+    let mut bytes = BytesMut::new();
+    extend_role(role, &mut bytes);
+    extend_permissions(&role.permissions, &mut bytes);
+    bytes.freeze()
+}
+
+pub fn map_roles(roles: &[&Role]) -> Bytes {
+    let mut bytes = BytesMut::new();
+    for role in roles {
+        extend_role(role, &mut bytes);
+    }
+    bytes.freeze()
+}
+
 pub fn map_identity_info(user_id: UserId) -> Bytes {
     let mut bytes = BytesMut::with_capacity(4);
     bytes.put_u32_le(user_id);
@@ -136,27 +201,40 @@ pub fn map_polled_messages(polled_messages: &PolledMessages) -> Bytes {
     bytes.freeze()
 }
 
+/// Maps the header of the polled batches, which are sent after it in the same format as stored in the segment files.
+pub fn map_polled_batches_header(polled_batches: &PolledBatches) -> Bytes {
+    let mut bytes = BytesMut::with_capacity(24);
+    bytes.put_u32_le(polled_batches.partition_id);
+    bytes.put_u64_le(polled_batches.current_offset);
+    bytes.put_u32_le(polled_batches.messages_count);
+    bytes.put_u64_le(polled_batches.first_offset);
+    bytes.freeze()
+}
+
 pub async fn map_stream(stream: &Stream) -> Bytes {
     let mut bytes = BytesMut::new();
     extend_stream(stream, &mut bytes).await;
     for topic in stream.get_topics() {
-        extend_topic(topic, &mut bytes).await;
+        let topic = topic.read().await;
+        extend_topic(&topic, &mut bytes).await;
     }
     bytes.freeze()
 }
 
-pub async fn map_streams(streams: &[&Stream]) -> Bytes {
+pub async fn map_streams(streams: &[IggySharedMut<Stream>]) -> Bytes {
     let mut bytes = BytesMut::new();
     for stream in streams {
-        extend_stream(stream, &mut bytes).await;
+        let stream = stream.read().await;
+        extend_stream(&stream, &mut bytes).await;
     }
     bytes.freeze()
 }
 
-pub async fn map_topics(topics: &[&Topic]) -> Bytes {
+pub async fn map_topics(topics: &[IggySharedMut<Topic>]) -> Bytes {
     let mut bytes = BytesMut::new();
     for topic in topics {
-        extend_topic(topic, &mut bytes).await;
+        let topic = topic.read().await;
+        extend_topic(&topic, &mut bytes).await;
     }
     bytes.freeze()
 }
@@ -187,7 +265,7 @@ pub async fn map_consumer_group(consumer_group: &ConsumerGroup) -> Bytes {
     bytes.freeze()
 }
 
-pub async fn map_consumer_groups(consumer_groups: &[&RwLock<ConsumerGroup>]) -> Bytes {
+pub async fn map_consumer_groups(consumer_groups: &[IggySharedMut<ConsumerGroup>]) -> Bytes {
     let mut bytes = BytesMut::new();
     for consumer_group in consumer_groups {
         let consumer_group = consumer_group.read().await;
@@ -254,11 +332,30 @@ fn extend_client(client: &Client, bytes: &mut BytesMut) {
 fn extend_user(user: &User, bytes: &mut BytesMut) {
     bytes.put_u32_le(user.id);
     bytes.put_u64_le(user.created_at.into());
-    bytes.put_u8(user.status.as_code());
+    bytes.put_u8(user.get_status().as_code());
     bytes.put_u8(user.username.len() as u8);
     bytes.put_slice(user.username.as_bytes());
 }
 
+fn extend_role(role: &Role, bytes: &mut BytesMut) {
+    bytes.put_u32_le(role.id);
+    bytes.put_u64_le(role.created_at.into());
+    bytes.put_u8(role.name.len() as u8);
+    bytes.put_slice(role.name.as_bytes());
+}
+
+fn extend_permissions(permissions: &Option<Permissions>, bytes: &mut BytesMut) {
+    if let Some(permissions) = permissions {
+        bytes.put_u8(1);
+        let permissions = permissions.to_bytes();
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.put_u32_le(permissions.len() as u32);
+        bytes.put_slice(&permissions);
+    } else {
+        bytes.put_u8(0);
+    }
+}
+
 fn extend_pat(personal_access_token: &PersonalAccessToken, bytes: &mut BytesMut) {
     bytes.put_u8(personal_access_token.name.len() as u8);
     bytes.put_slice(personal_access_token.name.as_bytes());
@@ -1,4 +1,6 @@
+use crate::streaming::batching::polled_batches::BatchesChunk;
 use async_trait::async_trait;
+use bytes::{BufMut, BytesMut};
 use iggy::error::IggyError;
 
 #[async_trait]
@@ -7,4 +9,20 @@ pub trait Sender: Sync + Send {
     async fn send_empty_ok_response(&mut self) -> Result<(), IggyError>;
     async fn send_ok_response(&mut self, payload: &[u8]) -> Result<(), IggyError>;
     async fn send_error_response(&mut self, error: IggyError) -> Result<(), IggyError>;
+
+    /// Sends the OK response with the payload consisting of the header and the chunks of the polled batches.
+    /// By default, the regions of the segment files are read into memory and sent along with the rest of the payload.
+    async fn send_ok_response_with_batches(
+        &mut self,
+        header: &[u8],
+        chunks: Vec<BatchesChunk>,
+    ) -> Result<(), IggyError> {
+        let size = chunks.iter().map(|chunk| chunk.len()).sum::<u64>();
+        let mut payload = BytesMut::with_capacity(header.len() + size as usize);
+        payload.put_slice(header);
+        for chunk in chunks {
+            chunk.read_into(&mut payload).await?;
+        }
+        self.send_ok_response(&payload).await
+    }
 }
@@ -6,7 +6,7 @@ use flume::Sender;
 use iggy::utils::duration::IggyDuration;
 use iggy::utils::timestamp::IggyTimestamp;
 use tokio::time;
-use tracing::{debug, error, info};
+use tracing::{error, info};
 
 pub struct PersonalAccessTokenCleaner {
     enabled: bool,
@@ -61,31 +61,10 @@ impl PersonalAccessTokenCleaner {
 #[async_trait]
 impl ServerCommand<CleanPersonalAccessTokensCommand> for CleanPersonalAccessTokensExecutor {
     async fn execute(&mut self, system: &SharedSystem, _command: CleanPersonalAccessTokensCommand) {
-        // TODO: System write lock, investigate if it's necessary.
-        let mut system = system.write().await;
-        let now = IggyTimestamp::now();
-        let mut deleted_tokens_count = 0;
-        for (_, user) in system.users.iter_mut() {
-            let expired_tokens = user
-                .personal_access_tokens
-                .values()
-                .filter(|token| token.is_expired(now))
-                .map(|token| token.token.clone())
-                .collect::<Vec<_>>();
-
-            for token in expired_tokens {
-                debug!(
-                    "Personal access token: {token} for user with ID: {} is expired.",
-                    user.id
-                );
-                deleted_tokens_count += 1;
-                user.personal_access_tokens.remove(&token);
-                debug!(
-                    "Deleted personal access token: {token} for user with ID: {}.",
-                    user.id
-                );
-            }
-        }
+        let system = system.read().await;
+        let deleted_tokens_count = system
+            .delete_expired_personal_access_tokens(IggyTimestamp::now())
+            .await;
         info!("Deleted {deleted_tokens_count} expired personal access tokens.");
     }
 
@@ -0,0 +1,97 @@
+use crate::channels::server_command::ServerCommand;
+use crate::configs::server::ServerConfig;
+use crate::configs::system::ConsumerOffsetsConfig;
+use crate::streaming::systems::system::SharedSystem;
+use async_trait::async_trait;
+use flume::{Receiver, Sender};
+use iggy::utils::duration::IggyDuration;
+use tokio::time;
+use tracing::{error, info, warn};
+
+pub struct ConsumerOffsetsCompactor {
+    interval: IggyDuration,
+    sender: Sender<CompactConsumerOffsetsCommand>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct CompactConsumerOffsetsCommand;
+
+#[derive(Debug, Default, Clone)]
+pub struct CompactConsumerOffsetsExecutor;
+
+impl ConsumerOffsetsCompactor {
+    pub fn new(
+        config: &ConsumerOffsetsConfig,
+        sender: Sender<CompactConsumerOffsetsCommand>,
+    ) -> Self {
+        Self {
+            interval: config.compaction_interval,
+            sender,
+        }
+    }
+
+    pub fn start(&self) {
+        if self.interval.is_zero() {
+            info!("Consumer offsets compactor is disabled, offsets logs will be compacted only on startup.");
+            return;
+        }
+
+        let interval = self.interval;
+        let sender = self.sender.clone();
+        info!("Consumer offsets compactor is enabled, offsets logs will be compacted every: {interval}.");
+        tokio::spawn(async move {
+            let mut interval_timer = time::interval(interval.get_duration());
+            loop {
+                interval_timer.tick().await;
+                sender
+                    .send(CompactConsumerOffsetsCommand)
+                    .unwrap_or_else(|e| {
+                        error!("Failed to send CompactConsumerOffsetsCommand. Error: {e}");
+                    });
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl ServerCommand<CompactConsumerOffsetsCommand> for CompactConsumerOffsetsExecutor {
+    async fn execute(&mut self, system: &SharedSystem, _command: CompactConsumerOffsetsCommand) {
+        let system = system.read().await;
+        match system.compact_consumer_offsets().await {
+            Ok(n) => {
+                if n > 0 {
+                    info!("Compacted {n} consumer offsets logs.");
+                }
+            }
+            Err(e) => {
+                error!("Couldn't compact consumer offsets logs. Error: {e}");
+            }
+        }
+    }
+
+    fn start_command_sender(
+        &mut self,
+        _system: SharedSystem,
+        config: &ServerConfig,
+        sender: Sender<CompactConsumerOffsetsCommand>,
+    ) {
+        let consumer_offsets_compactor =
+            ConsumerOffsetsCompactor::new(&config.system.consumer_offsets, sender);
+        consumer_offsets_compactor.start();
+    }
+
+    fn start_command_consumer(
+        mut self,
+        system: SharedSystem,
+        _config: &ServerConfig,
+        receiver: Receiver<CompactConsumerOffsetsCommand>,
+    ) {
+        tokio::spawn(async move {
+            let system = system.clone();
+            while let Ok(command) = receiver.recv_async().await {
+                self.execute(&system, command).await;
+            }
+            warn!("Server command handler stopped receiving commands.");
+        });
+    }
+}
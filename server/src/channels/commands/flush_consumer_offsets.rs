@@ -0,0 +1,99 @@
+use crate::channels::server_command::ServerCommand;
+use crate::configs::server::ServerConfig;
+use crate::configs::system::ConsumerOffsetsConfig;
+use crate::streaming::systems::system::SharedSystem;
+use async_trait::async_trait;
+use flume::{Receiver, Sender};
+use iggy::utils::duration::IggyDuration;
+use tokio::time;
+use tracing::{error, info, trace, warn};
+
+pub struct ConsumerOffsetsFlusher {
+    interval: IggyDuration,
+    sender: Sender<FlushConsumerOffsetsCommand>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct FlushConsumerOffsetsCommand;
+
+#[derive(Debug, Default, Clone)]
+pub struct FlushConsumerOffsetsExecutor;
+
+impl ConsumerOffsetsFlusher {
+    pub fn new(
+        config: &ConsumerOffsetsConfig,
+        sender: Sender<FlushConsumerOffsetsCommand>,
+    ) -> Self {
+        Self {
+            interval: config.flush_interval,
+            sender,
+        }
+    }
+
+    pub fn start(&self) {
+        if self.interval.is_zero() {
+            info!(
+                "Consumer offsets flusher is disabled, stored offsets will be saved immediately."
+            );
+            return;
+        }
+
+        let interval = self.interval;
+        let sender = self.sender.clone();
+        info!("Consumer offsets flusher is enabled, buffered consumer offsets will be automatically saved every: {interval}.");
+        tokio::spawn(async move {
+            let mut interval_timer = time::interval(interval.get_duration());
+            loop {
+                interval_timer.tick().await;
+                sender
+                    .send(FlushConsumerOffsetsCommand)
+                    .unwrap_or_else(|e| {
+                        error!("Failed to send FlushConsumerOffsetsCommand. Error: {e}");
+                    });
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl ServerCommand<FlushConsumerOffsetsCommand> for FlushConsumerOffsetsExecutor {
+    async fn execute(&mut self, system: &SharedSystem, _command: FlushConsumerOffsetsCommand) {
+        let system = system.read().await;
+        match system.flush_consumer_offsets().await {
+            Ok(n) => {
+                if n > 0 {
+                    trace!("Saved {n} buffered consumer offsets on disk.");
+                }
+            }
+            Err(e) => {
+                error!("Couldn't save buffered consumer offsets on disk. Error: {e}");
+            }
+        }
+    }
+
+    fn start_command_sender(
+        &mut self,
+        _system: SharedSystem,
+        config: &ServerConfig,
+        sender: Sender<FlushConsumerOffsetsCommand>,
+    ) {
+        let consumer_offsets_flusher =
+            ConsumerOffsetsFlusher::new(&config.system.consumer_offsets, sender);
+        consumer_offsets_flusher.start();
+    }
+
+    fn start_command_consumer(
+        mut self,
+        system: SharedSystem,
+        _config: &ServerConfig,
+        receiver: Receiver<FlushConsumerOffsetsCommand>,
+    ) {
+        tokio::spawn(async move {
+            let system = system.clone();
+            while let Ok(command) = receiver.recv_async().await {
+                self.execute(&system, command).await;
+            }
+            warn!("Server command handler stopped receiving commands.");
+        });
+    }
+}
@@ -81,15 +81,26 @@ impl ServerCommand<MaintainMessagesCommand> for MaintainMessagesExecutor {
         let system = system.read().await;
         let streams = system.get_streams();
         for stream in streams {
+            let stream = stream.read().await;
             let topics = stream.get_topics();
             for topic in topics {
+                let topic = topic.read().await;
+                if !system.config.segment.max_age.is_zero() {
+                    if let Err(error) = close_aged_segments(&topic).await {
+                        error!(
+                            "Failed to close aged segments for stream ID: {}, topic ID: {}. Error: {}",
+                            topic.stream_id, topic.topic_id, error
+                        );
+                    }
+                }
+
                 let archiver = if command.archive_messages {
                     system.archiver.clone()
                 } else {
                     None
                 };
                 let expired_segments = handle_expired_segments(
-                    topic,
+                    &topic,
                     archiver.clone(),
                     system.config.segment.archive_expired,
                     command.clean_messages,
@@ -104,7 +115,7 @@ impl ServerCommand<MaintainMessagesCommand> for MaintainMessagesExecutor {
                 }
 
                 let oldest_segments = handle_oldest_segments(
-                    topic,
+                    &topic,
                     archiver.clone(),
                     system.config.topic.delete_oldest_segments,
                 )
@@ -193,6 +204,15 @@ impl ServerCommand<MaintainMessagesCommand> for MaintainMessagesExecutor {
     }
 }
 
+async fn close_aged_segments(topic: &Topic) -> Result<(), IggyError> {
+    let now = IggyTimestamp::now();
+    for partition in topic.partitions.values() {
+        let mut partition = partition.write().await;
+        partition.close_aged_segment(now).await?;
+    }
+    Ok(())
+}
+
 async fn handle_expired_segments(
     topic: &Topic,
     archiver: Option<Arc<dyn Archiver>>,
@@ -1,5 +1,9 @@
 pub mod archive_state;
 pub mod clean_personal_access_tokens;
+pub mod compact_consumer_offsets;
+pub mod flush_consumer_offsets;
 pub mod maintain_messages;
 pub mod print_sysinfo;
+pub mod reencrypt_data;
 pub mod save_messages;
+pub mod snapshot_state;
@@ -0,0 +1,185 @@
+use crate::channels::server_command::ServerCommand;
+use crate::configs::server::EncryptionMaintenanceConfig;
+use crate::streaming::systems::system::SharedSystem;
+use async_trait::async_trait;
+use flume::Sender;
+use iggy::locking::IggySharedMutFn;
+use iggy::utils::duration::IggyDuration;
+use std::collections::HashSet;
+use tokio::time;
+use tracing::{error, info, warn};
+
+pub struct DataReencryptor {
+    interval: IggyDuration,
+    sender: Sender<ReencryptDataCommand>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ReencryptDataCommand;
+
+/// The executor keeps track of the already re-encrypted state and segments,
+/// as the active key cannot change while the server is running.
+#[derive(Debug, Default, Clone)]
+pub struct ReencryptDataExecutor {
+    state_reencrypted: bool,
+    reencrypted_segments: HashSet<String>,
+}
+
+impl DataReencryptor {
+    pub fn new(config: &EncryptionMaintenanceConfig, sender: Sender<ReencryptDataCommand>) -> Self {
+        Self {
+            interval: config.interval,
+            sender,
+        }
+    }
+
+    pub fn start(&self) {
+        let interval = self.interval;
+        let sender = self.sender.clone();
+        info!("Data re-encryption is enabled, data will be re-encrypted every: {interval}.");
+        tokio::spawn(async move {
+            let mut interval_timer = time::interval(interval.get_duration());
+            loop {
+                interval_timer.tick().await;
+                sender.send(ReencryptDataCommand).unwrap_or_else(|err| {
+                    error!("Failed to send ReencryptDataCommand. Error: {}", err);
+                });
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl ServerCommand<ReencryptDataCommand> for ReencryptDataExecutor {
+    async fn execute(&mut self, system: &SharedSystem, _command: ReencryptDataCommand) {
+        let key_ring = system.read().await.encryptor.clone();
+        let Some(key_ring) = key_ring else {
+            warn!("Encryption is disabled, data will not be re-encrypted.");
+            return;
+        };
+
+        if !self.state_reencrypted {
+            // The write lock prevents appending the new state entries while the state log is being rewritten.
+            let system = system.write().await;
+            match system.state.reencrypt(&key_ring).await {
+                Ok(_) => self.state_reencrypted = true,
+                Err(error) => error!("Failed to re-encrypt state. Error: {}", error),
+            }
+        }
+
+        // The segments are only collected under the locks, which are all released before rewriting them,
+        // so that the whole rewrite doesn't block the other tasks (and the clients) waiting for any of them.
+        let mut segments = Vec::new();
+        {
+            let system = system.read().await;
+            for stream in system.get_streams() {
+                let stream = stream.read().await;
+                for topic in stream.get_topics() {
+                    let topic = topic.read().await;
+                    for partition in topic.partitions.values() {
+                        let partition_guard = partition.read().await;
+                        for segment_reencryption in partition_guard
+                            .get_segments()
+                            .iter()
+                            .filter(|segment| {
+                                !self.reencrypted_segments.contains(&segment.log_path)
+                            })
+                            .filter_map(|segment| segment.prepare_reencryption())
+                        {
+                            segments.push((
+                                topic.stream_id,
+                                topic.topic_id,
+                                partition_guard.partition_id,
+                                partition.clone(),
+                                segment_reencryption,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut reencrypted_messages = 0;
+        for (stream_id, topic_id, partition_id, partition, segment_reencryption) in segments {
+            // The segment is rewritten without holding any lock, only the swap of the files requires the partition lock.
+            let start_offset = segment_reencryption.start_offset;
+            let result = match segment_reencryption.rewrite(&key_ring).await {
+                Ok(Some(reencrypted_segment)) => {
+                    let mut partition = partition.write().await;
+                    match partition
+                        .get_segments_mut()
+                        .iter_mut()
+                        .find(|segment| segment.start_offset == start_offset)
+                    {
+                        Some(segment) => segment.complete_reencryption(reencrypted_segment).await,
+                        None => {
+                            reencrypted_segment.discard().await;
+                            Ok(0)
+                        }
+                    }
+                }
+                Ok(None) => Ok(0),
+                Err(error) => Err(error),
+            };
+
+            match result {
+                Ok(messages_count) => {
+                    reencrypted_messages += messages_count;
+                    self.reencrypted_segments
+                        .insert(segment_reencryption.log_path().to_owned());
+                }
+                Err(error) => {
+                    error!(
+                        "Failed to re-encrypt segment with start offset: {start_offset} for stream ID: {stream_id}, topic ID: {topic_id}, partition ID: {partition_id}. Error: {}",
+                        error
+                    );
+                }
+            }
+        }
+
+        if reencrypted_messages > 0 {
+            info!(
+                "Re-encrypted {reencrypted_messages} messages with the key with ID: {}.",
+                key_ring.get_active_key_id()
+            );
+        }
+    }
+
+    fn start_command_sender(
+        &mut self,
+        _system: SharedSystem,
+        config: &crate::configs::server::ServerConfig,
+        sender: Sender<ReencryptDataCommand>,
+    ) {
+        if !config.system.encryption.enabled
+            || !config.data_maintenance.encryption.reencryption_enabled
+        {
+            info!("Data re-encryption is disabled.");
+            return;
+        }
+
+        let data_reencryptor = DataReencryptor::new(&config.data_maintenance.encryption, sender);
+        data_reencryptor.start();
+    }
+
+    fn start_command_consumer(
+        mut self,
+        system: SharedSystem,
+        config: &crate::configs::server::ServerConfig,
+        receiver: flume::Receiver<ReencryptDataCommand>,
+    ) {
+        if !config.system.encryption.enabled
+            || !config.data_maintenance.encryption.reencryption_enabled
+        {
+            return;
+        }
+
+        tokio::spawn(async move {
+            let system = system.clone();
+            while let Ok(command) = receiver.recv_async().await {
+                self.execute(&system, command).await;
+            }
+            info!("Data re-encryption receiver stopped.");
+        });
+    }
+}
@@ -0,0 +1,94 @@
+use crate::channels::server_command::ServerCommand;
+use crate::configs::server::StateMaintenanceConfig;
+use crate::streaming::systems::system::SharedSystem;
+use async_trait::async_trait;
+use flume::Sender;
+use iggy::utils::duration::IggyDuration;
+use tokio::time;
+use tracing::{error, info};
+
+pub struct StateSnapshotter {
+    enabled: bool,
+    interval: IggyDuration,
+    sender: Sender<SnapshotStateCommand>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct SnapshotStateCommand {
+    min_entries: u64,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct SnapshotStateExecutor;
+
+impl StateSnapshotter {
+    pub fn new(config: &StateMaintenanceConfig, sender: Sender<SnapshotStateCommand>) -> Self {
+        Self {
+            enabled: config.snapshot_enabled,
+            interval: config.snapshot_interval,
+            sender,
+        }
+    }
+
+    pub fn start(&self, min_entries: u64) {
+        if !self.enabled {
+            info!("State snapshotter is disabled.");
+            return;
+        }
+
+        let interval = self.interval;
+        let sender = self.sender.clone();
+        info!("State snapshotter is enabled, state snapshot will be created every: {interval}.");
+        tokio::spawn(async move {
+            let mut interval_timer = time::interval(interval.get_duration());
+            loop {
+                interval_timer.tick().await;
+                sender
+                    .send(SnapshotStateCommand { min_entries })
+                    .unwrap_or_else(|err| {
+                        error!("Failed to send SnapshotStateCommand. Error: {}", err);
+                    });
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl ServerCommand<SnapshotStateCommand> for SnapshotStateExecutor {
+    async fn execute(&mut self, system: &SharedSystem, command: SnapshotStateCommand) {
+        // The write lock prevents appending the new state entries while the state log is being truncated.
+        let system = system.write().await;
+        if let Err(error) = system.state.snapshot(command.min_entries).await {
+            error!("Failed to create state snapshot. Error: {}", error);
+        }
+    }
+
+    fn start_command_sender(
+        &mut self,
+        _system: SharedSystem,
+        config: &crate::configs::server::ServerConfig,
+        sender: Sender<SnapshotStateCommand>,
+    ) {
+        let state_snapshotter = StateSnapshotter::new(&config.data_maintenance.state, sender);
+        state_snapshotter.start(config.data_maintenance.state.snapshot_min_entries);
+    }
+
+    fn start_command_consumer(
+        mut self,
+        system: SharedSystem,
+        config: &crate::configs::server::ServerConfig,
+        receiver: flume::Receiver<SnapshotStateCommand>,
+    ) {
+        if !config.data_maintenance.state.snapshot_enabled {
+            return;
+        }
+
+        tokio::spawn(async move {
+            let system = system.clone();
+            while let Ok(command) = receiver.recv_async().await {
+                self.execute(&system, command).await;
+            }
+            info!("State snapshotter receiver stopped.");
+        });
+    }
+}
@@ -7,6 +7,7 @@ use iggy::consumer_groups::get_consumer_group::GetConsumerGroup;
 use iggy::consumer_groups::get_consumer_groups::GetConsumerGroups;
 use iggy::consumer_groups::join_consumer_group::JoinConsumerGroup;
 use iggy::consumer_groups::leave_consumer_group::LeaveConsumerGroup;
+use iggy::consumer_offsets::get_consumer_lag::GetConsumerLag;
 use iggy::consumer_offsets::get_consumer_offset::GetConsumerOffset;
 use iggy::consumer_offsets::store_consumer_offset::StoreConsumerOffset;
 use iggy::error::IggyError;
@@ -18,12 +19,19 @@ use iggy::personal_access_tokens::create_personal_access_token::CreatePersonalAc
 use iggy::personal_access_tokens::delete_personal_access_token::DeletePersonalAccessToken;
 use iggy::personal_access_tokens::get_personal_access_tokens::GetPersonalAccessTokens;
 use iggy::personal_access_tokens::login_with_personal_access_token::LoginWithPersonalAccessToken;
+use iggy::roles::create_role::CreateRole;
+use iggy::roles::delete_role::DeleteRole;
+use iggy::roles::get_role::GetRole;
+use iggy::roles::get_roles::GetRoles;
+use iggy::roles::update_role::UpdateRole;
 use iggy::streams::create_stream::CreateStream;
 use iggy::streams::delete_stream::DeleteStream;
 use iggy::streams::get_stream::GetStream;
 use iggy::streams::get_streams::GetStreams;
 use iggy::streams::purge_stream::PurgeStream;
 use iggy::streams::update_stream::UpdateStream;
+use iggy::system::create_backup::CreateBackup;
+use iggy::system::get_audit_log::GetAuditLog;
 use iggy::system::get_client::GetClient;
 use iggy::system::get_clients::GetClients;
 use iggy::system::get_me::GetMe;
@@ -42,7 +50,9 @@ use iggy::users::get_user::GetUser;
 use iggy::users::get_users::GetUsers;
 use iggy::users::login_user::LoginUser;
 use iggy::users::logout_user::LogoutUser;
+use iggy::users::unlock_user::UnlockUser;
 use iggy::users::update_permissions::UpdatePermissions;
+use iggy::users::update_roles::UpdateRoles;
 use iggy::users::update_user::UpdateUser;
 use iggy::validatable::Validatable;
 use std::fmt::{Display, Formatter};
@@ -55,22 +65,33 @@ pub enum ServerCommand {
     GetMe(GetMe),
     GetClient(GetClient),
     GetClients(GetClients),
+    GetAuditLog(GetAuditLog),
+    CreateBackup(CreateBackup),
     GetUser(GetUser),
     GetUsers(GetUsers),
     CreateUser(CreateUser),
     DeleteUser(DeleteUser),
     UpdateUser(UpdateUser),
     UpdatePermissions(UpdatePermissions),
+    UpdateRoles(UpdateRoles),
     ChangePassword(ChangePassword),
     LoginUser(LoginUser),
     LogoutUser(LogoutUser),
+    UnlockUser(UnlockUser),
     GetPersonalAccessTokens(GetPersonalAccessTokens),
     CreatePersonalAccessToken(CreatePersonalAccessToken),
     DeletePersonalAccessToken(DeletePersonalAccessToken),
     LoginWithPersonalAccessToken(LoginWithPersonalAccessToken),
+    GetRole(GetRole),
+    GetRoles(GetRoles),
+    CreateRole(CreateRole),
+    DeleteRole(DeleteRole),
+    UpdateRole(UpdateRole),
     SendMessages(SendMessages),
     PollMessages(PollMessages),
+    PollMessageBatches(PollMessages),
     GetConsumerOffset(GetConsumerOffset),
+    GetConsumerLag(GetConsumerLag),
     StoreConsumerOffset(StoreConsumerOffset),
     GetStream(GetStream),
     GetStreams(GetStreams),
@@ -94,6 +115,66 @@ pub enum ServerCommand {
     LeaveConsumerGroup(LeaveConsumerGroup),
 }
 
+impl ServerCommand {
+    pub fn code(&self) -> u32 {
+        match self {
+            ServerCommand::Ping(payload) => payload.code(),
+            ServerCommand::GetStats(payload) => payload.code(),
+            ServerCommand::GetMe(payload) => payload.code(),
+            ServerCommand::GetClient(payload) => payload.code(),
+            ServerCommand::GetClients(payload) => payload.code(),
+            ServerCommand::GetAuditLog(payload) => payload.code(),
+            ServerCommand::CreateBackup(payload) => payload.code(),
+            ServerCommand::GetUser(payload) => payload.code(),
+            ServerCommand::GetUsers(payload) => payload.code(),
+            ServerCommand::CreateUser(payload) => payload.code(),
+            ServerCommand::DeleteUser(payload) => payload.code(),
+            ServerCommand::UpdateUser(payload) => payload.code(),
+            ServerCommand::UpdatePermissions(payload) => payload.code(),
+            ServerCommand::UpdateRoles(payload) => payload.code(),
+            ServerCommand::ChangePassword(payload) => payload.code(),
+            ServerCommand::LoginUser(payload) => payload.code(),
+            ServerCommand::LogoutUser(payload) => payload.code(),
+            ServerCommand::UnlockUser(payload) => payload.code(),
+            ServerCommand::GetPersonalAccessTokens(payload) => payload.code(),
+            ServerCommand::CreatePersonalAccessToken(payload) => payload.code(),
+            ServerCommand::DeletePersonalAccessToken(payload) => payload.code(),
+            ServerCommand::LoginWithPersonalAccessToken(payload) => payload.code(),
+            ServerCommand::GetRole(payload) => payload.code(),
+            ServerCommand::GetRoles(payload) => payload.code(),
+            ServerCommand::CreateRole(payload) => payload.code(),
+            ServerCommand::DeleteRole(payload) => payload.code(),
+            ServerCommand::UpdateRole(payload) => payload.code(),
+            ServerCommand::SendMessages(payload) => payload.code(),
+            ServerCommand::PollMessages(payload) => payload.code(),
+            ServerCommand::PollMessageBatches(_) => POLL_MESSAGE_BATCHES_CODE,
+            ServerCommand::StoreConsumerOffset(payload) => payload.code(),
+            ServerCommand::GetConsumerOffset(payload) => payload.code(),
+            ServerCommand::GetConsumerLag(payload) => payload.code(),
+            ServerCommand::GetStream(payload) => payload.code(),
+            ServerCommand::GetStreams(payload) => payload.code(),
+            ServerCommand::CreateStream(payload) => payload.code(),
+            ServerCommand::DeleteStream(payload) => payload.code(),
+            ServerCommand::UpdateStream(payload) => payload.code(),
+            ServerCommand::PurgeStream(payload) => payload.code(),
+            ServerCommand::GetTopic(payload) => payload.code(),
+            ServerCommand::GetTopics(payload) => payload.code(),
+            ServerCommand::CreateTopic(payload) => payload.code(),
+            ServerCommand::DeleteTopic(payload) => payload.code(),
+            ServerCommand::UpdateTopic(payload) => payload.code(),
+            ServerCommand::PurgeTopic(payload) => payload.code(),
+            ServerCommand::CreatePartitions(payload) => payload.code(),
+            ServerCommand::DeletePartitions(payload) => payload.code(),
+            ServerCommand::GetConsumerGroup(payload) => payload.code(),
+            ServerCommand::GetConsumerGroups(payload) => payload.code(),
+            ServerCommand::CreateConsumerGroup(payload) => payload.code(),
+            ServerCommand::DeleteConsumerGroup(payload) => payload.code(),
+            ServerCommand::JoinConsumerGroup(payload) => payload.code(),
+            ServerCommand::LeaveConsumerGroup(payload) => payload.code(),
+        }
+    }
+}
+
 impl BytesSerializable for ServerCommand {
     fn to_bytes(&self) -> Bytes {
         match self {
@@ -102,23 +183,36 @@ impl BytesSerializable for ServerCommand {
             ServerCommand::GetMe(payload) => as_bytes(payload),
             ServerCommand::GetClient(payload) => as_bytes(payload),
             ServerCommand::GetClients(payload) => as_bytes(payload),
+            ServerCommand::GetAuditLog(payload) => as_bytes(payload),
+            ServerCommand::CreateBackup(payload) => as_bytes(payload),
             ServerCommand::GetUser(payload) => as_bytes(payload),
             ServerCommand::GetUsers(payload) => as_bytes(payload),
             ServerCommand::CreateUser(payload) => as_bytes(payload),
             ServerCommand::DeleteUser(payload) => as_bytes(payload),
             ServerCommand::UpdateUser(payload) => as_bytes(payload),
             ServerCommand::UpdatePermissions(payload) => as_bytes(payload),
+            ServerCommand::UpdateRoles(payload) => as_bytes(payload),
             ServerCommand::ChangePassword(payload) => as_bytes(payload),
             ServerCommand::LoginUser(payload) => as_bytes(payload),
             ServerCommand::LogoutUser(payload) => as_bytes(payload),
+            ServerCommand::UnlockUser(payload) => as_bytes(payload),
             ServerCommand::GetPersonalAccessTokens(payload) => as_bytes(payload),
             ServerCommand::CreatePersonalAccessToken(payload) => as_bytes(payload),
             ServerCommand::DeletePersonalAccessToken(payload) => as_bytes(payload),
             ServerCommand::LoginWithPersonalAccessToken(payload) => as_bytes(payload),
+            ServerCommand::GetRole(payload) => as_bytes(payload),
+            ServerCommand::GetRoles(payload) => as_bytes(payload),
+            ServerCommand::CreateRole(payload) => as_bytes(payload),
+            ServerCommand::DeleteRole(payload) => as_bytes(payload),
+            ServerCommand::UpdateRole(payload) => as_bytes(payload),
             ServerCommand::SendMessages(payload) => as_bytes(payload),
             ServerCommand::PollMessages(payload) => as_bytes(payload),
+            ServerCommand::PollMessageBatches(payload) => {
+                as_bytes_with_code(POLL_MESSAGE_BATCHES_CODE, payload)
+            }
             ServerCommand::StoreConsumerOffset(payload) => as_bytes(payload),
             ServerCommand::GetConsumerOffset(payload) => as_bytes(payload),
+            ServerCommand::GetConsumerLag(payload) => as_bytes(payload),
             ServerCommand::GetStream(payload) => as_bytes(payload),
             ServerCommand::GetStreams(payload) => as_bytes(payload),
             ServerCommand::CreateStream(payload) => as_bytes(payload),
@@ -151,6 +245,12 @@ impl BytesSerializable for ServerCommand {
             GET_ME_CODE => Ok(ServerCommand::GetMe(GetMe::from_bytes(payload)?)),
             GET_CLIENT_CODE => Ok(ServerCommand::GetClient(GetClient::from_bytes(payload)?)),
             GET_CLIENTS_CODE => Ok(ServerCommand::GetClients(GetClients::from_bytes(payload)?)),
+            GET_AUDIT_LOG_CODE => Ok(ServerCommand::GetAuditLog(GetAuditLog::from_bytes(
+                payload,
+            )?)),
+            CREATE_BACKUP_CODE => Ok(ServerCommand::CreateBackup(CreateBackup::from_bytes(
+                payload,
+            )?)),
             GET_USER_CODE => Ok(ServerCommand::GetUser(GetUser::from_bytes(payload)?)),
             GET_USERS_CODE => Ok(ServerCommand::GetUsers(GetUsers::from_bytes(payload)?)),
             CREATE_USER_CODE => Ok(ServerCommand::CreateUser(CreateUser::from_bytes(payload)?)),
@@ -159,11 +259,15 @@ impl BytesSerializable for ServerCommand {
             UPDATE_PERMISSIONS_CODE => Ok(ServerCommand::UpdatePermissions(
                 UpdatePermissions::from_bytes(payload)?,
             )),
+            UPDATE_ROLES_CODE => Ok(ServerCommand::UpdateRoles(UpdateRoles::from_bytes(
+                payload,
+            )?)),
             CHANGE_PASSWORD_CODE => Ok(ServerCommand::ChangePassword(ChangePassword::from_bytes(
                 payload,
             )?)),
             LOGIN_USER_CODE => Ok(ServerCommand::LoginUser(LoginUser::from_bytes(payload)?)),
             LOGOUT_USER_CODE => Ok(ServerCommand::LogoutUser(LogoutUser::from_bytes(payload)?)),
+            UNLOCK_USER_CODE => Ok(ServerCommand::UnlockUser(UnlockUser::from_bytes(payload)?)),
             GET_PERSONAL_ACCESS_TOKENS_CODE => Ok(ServerCommand::GetPersonalAccessTokens(
                 GetPersonalAccessTokens::from_bytes(payload)?,
             )),
@@ -178,18 +282,29 @@ impl BytesSerializable for ServerCommand {
                     LoginWithPersonalAccessToken::from_bytes(payload)?,
                 ))
             }
+            GET_ROLE_CODE => Ok(ServerCommand::GetRole(GetRole::from_bytes(payload)?)),
+            GET_ROLES_CODE => Ok(ServerCommand::GetRoles(GetRoles::from_bytes(payload)?)),
+            CREATE_ROLE_CODE => Ok(ServerCommand::CreateRole(CreateRole::from_bytes(payload)?)),
+            DELETE_ROLE_CODE => Ok(ServerCommand::DeleteRole(DeleteRole::from_bytes(payload)?)),
+            UPDATE_ROLE_CODE => Ok(ServerCommand::UpdateRole(UpdateRole::from_bytes(payload)?)),
             SEND_MESSAGES_CODE => Ok(ServerCommand::SendMessages(SendMessages::from_bytes(
                 payload,
             )?)),
             POLL_MESSAGES_CODE => Ok(ServerCommand::PollMessages(PollMessages::from_bytes(
                 payload,
             )?)),
+            POLL_MESSAGE_BATCHES_CODE => Ok(ServerCommand::PollMessageBatches(
+                PollMessages::from_bytes(payload)?,
+            )),
             STORE_CONSUMER_OFFSET_CODE => Ok(ServerCommand::StoreConsumerOffset(
                 StoreConsumerOffset::from_bytes(payload)?,
             )),
             GET_CONSUMER_OFFSET_CODE => Ok(ServerCommand::GetConsumerOffset(
                 GetConsumerOffset::from_bytes(payload)?,
             )),
+            GET_CONSUMER_LAG_CODE => Ok(ServerCommand::GetConsumerLag(GetConsumerLag::from_bytes(
+                payload,
+            )?)),
             GET_STREAM_CODE => Ok(ServerCommand::GetStream(GetStream::from_bytes(payload)?)),
             GET_STREAMS_CODE => Ok(ServerCommand::GetStreams(GetStreams::from_bytes(payload)?)),
             CREATE_STREAM_CODE => Ok(ServerCommand::CreateStream(CreateStream::from_bytes(
@@ -246,9 +361,13 @@ impl BytesSerializable for ServerCommand {
 }
 
 fn as_bytes<T: Command>(command: &T) -> Bytes {
+    as_bytes_with_code(command.code(), command)
+}
+
+fn as_bytes_with_code<T: Command>(code: u32, command: &T) -> Bytes {
     let payload = command.to_bytes();
     let mut bytes = BytesMut::with_capacity(4 + payload.len());
-    bytes.put_u32_le(command.code());
+    bytes.put_u32_le(code);
     bytes.put_slice(&payload);
     bytes.freeze()
 }
@@ -261,23 +380,34 @@ impl Validatable<IggyError> for ServerCommand {
             ServerCommand::GetMe(command) => command.validate(),
             ServerCommand::GetClient(command) => command.validate(),
             ServerCommand::GetClients(command) => command.validate(),
+            ServerCommand::GetAuditLog(command) => command.validate(),
+            ServerCommand::CreateBackup(command) => command.validate(),
             ServerCommand::GetUser(command) => command.validate(),
             ServerCommand::GetUsers(command) => command.validate(),
             ServerCommand::CreateUser(command) => command.validate(),
             ServerCommand::DeleteUser(command) => command.validate(),
             ServerCommand::UpdateUser(command) => command.validate(),
             ServerCommand::UpdatePermissions(command) => command.validate(),
+            ServerCommand::UpdateRoles(command) => command.validate(),
             ServerCommand::ChangePassword(command) => command.validate(),
             ServerCommand::LoginUser(command) => command.validate(),
             ServerCommand::LogoutUser(command) => command.validate(),
+            ServerCommand::UnlockUser(command) => command.validate(),
             ServerCommand::GetPersonalAccessTokens(command) => command.validate(),
             ServerCommand::CreatePersonalAccessToken(command) => command.validate(),
             ServerCommand::DeletePersonalAccessToken(command) => command.validate(),
             ServerCommand::LoginWithPersonalAccessToken(command) => command.validate(),
+            ServerCommand::GetRole(command) => command.validate(),
+            ServerCommand::GetRoles(command) => command.validate(),
+            ServerCommand::CreateRole(command) => command.validate(),
+            ServerCommand::DeleteRole(command) => command.validate(),
+            ServerCommand::UpdateRole(command) => command.validate(),
             ServerCommand::SendMessages(command) => command.validate(),
             ServerCommand::PollMessages(command) => command.validate(),
+            ServerCommand::PollMessageBatches(command) => command.validate(),
             ServerCommand::StoreConsumerOffset(command) => command.validate(),
             ServerCommand::GetConsumerOffset(command) => command.validate(),
+            ServerCommand::GetConsumerLag(command) => command.validate(),
             ServerCommand::GetStream(command) => command.validate(),
             ServerCommand::GetStreams(command) => command.validate(),
             ServerCommand::CreateStream(command) => command.validate(),
@@ -310,6 +440,8 @@ impl Display for ServerCommand {
             ServerCommand::GetMe(_) => write!(formatter, "{GET_ME}"),
             ServerCommand::GetClient(payload) => write!(formatter, "{GET_CLIENT}|{payload}"),
             ServerCommand::GetClients(_) => write!(formatter, "{GET_CLIENTS}"),
+            ServerCommand::GetAuditLog(payload) => write!(formatter, "{GET_AUDIT_LOG}|{payload}"),
+            ServerCommand::CreateBackup(payload) => write!(formatter, "{CREATE_BACKUP}|{payload}"),
             ServerCommand::GetUser(payload) => write!(formatter, "{GET_USER}|{payload}"),
             ServerCommand::GetUsers(_) => write!(formatter, "{GET_USERS}"),
             ServerCommand::CreateUser(payload) => write!(formatter, "{CREATE_USER}|{payload}"),
@@ -318,11 +450,13 @@ impl Display for ServerCommand {
             ServerCommand::UpdatePermissions(payload) => {
                 write!(formatter, "{UPDATE_PERMISSIONS}|{payload}")
             }
+            ServerCommand::UpdateRoles(payload) => write!(formatter, "{UPDATE_ROLES}|{payload}"),
             ServerCommand::ChangePassword(payload) => {
                 write!(formatter, "{CHANGE_PASSWORD}|{payload}")
             }
             ServerCommand::LoginUser(payload) => write!(formatter, "{LOGIN_USER}|{payload}"),
             ServerCommand::LogoutUser(_) => write!(formatter, "{LOGOUT_USER}"),
+            ServerCommand::UnlockUser(payload) => write!(formatter, "{UNLOCK_USER}|{payload}"),
             ServerCommand::GetPersonalAccessTokens(_) => {
                 write!(formatter, "{GET_PERSONAL_ACCESS_TOKENS}")
             }
@@ -335,6 +469,11 @@ impl Display for ServerCommand {
             ServerCommand::LoginWithPersonalAccessToken(payload) => {
                 write!(formatter, "{LOGIN_WITH_PERSONAL_ACCESS_TOKEN}|{payload}")
             }
+            ServerCommand::GetRole(payload) => write!(formatter, "{GET_ROLE}|{payload}"),
+            ServerCommand::GetRoles(_) => write!(formatter, "{GET_ROLES}"),
+            ServerCommand::CreateRole(payload) => write!(formatter, "{CREATE_ROLE}|{payload}"),
+            ServerCommand::DeleteRole(payload) => write!(formatter, "{DELETE_ROLE}|{payload}"),
+            ServerCommand::UpdateRole(payload) => write!(formatter, "{UPDATE_ROLE}|{payload}"),
             ServerCommand::GetStream(payload) => write!(formatter, "{GET_STREAM}|{payload}"),
             ServerCommand::GetStreams(_) => write!(formatter, "{GET_STREAMS}"),
             ServerCommand::CreateStream(payload) => write!(formatter, "{CREATE_STREAM}|{payload}"),
@@ -354,6 +493,9 @@ impl Display for ServerCommand {
                 write!(formatter, "{DELETE_PARTITIONS}|{payload}")
             }
             ServerCommand::PollMessages(payload) => write!(formatter, "{POLL_MESSAGES}|{payload}"),
+            ServerCommand::PollMessageBatches(payload) => {
+                write!(formatter, "{POLL_MESSAGE_BATCHES}|{payload}")
+            }
             ServerCommand::SendMessages(payload) => write!(formatter, "{SEND_MESSAGES}|{payload}"),
             ServerCommand::StoreConsumerOffset(payload) => {
                 write!(formatter, "{STORE_CONSUMER_OFFSET}|{payload}")
@@ -361,6 +503,9 @@ impl Display for ServerCommand {
             ServerCommand::GetConsumerOffset(payload) => {
                 write!(formatter, "{GET_CONSUMER_OFFSET}|{payload}")
             }
+            ServerCommand::GetConsumerLag(payload) => {
+                write!(formatter, "{GET_CONSUMER_LAG}|{payload}")
+            }
             ServerCommand::GetConsumerGroup(payload) => {
                 write!(formatter, "{GET_CONSUMER_GROUP}|{payload}")
             }
@@ -414,6 +559,16 @@ mod tests {
             GET_CLIENTS_CODE,
             &GetClients::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::GetAuditLog(GetAuditLog::default()),
+            GET_AUDIT_LOG_CODE,
+            &GetAuditLog::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::CreateBackup(CreateBackup::default()),
+            CREATE_BACKUP_CODE,
+            &CreateBackup::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::GetUser(GetUser::default()),
             GET_USER_CODE,
@@ -444,6 +599,11 @@ mod tests {
             UPDATE_PERMISSIONS_CODE,
             &UpdatePermissions::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::UpdateRoles(UpdateRoles::default()),
+            UPDATE_ROLES_CODE,
+            &UpdateRoles::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::ChangePassword(ChangePassword::default()),
             CHANGE_PASSWORD_CODE,
@@ -459,6 +619,11 @@ mod tests {
             LOGOUT_USER_CODE,
             &LogoutUser::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::UnlockUser(UnlockUser::default()),
+            UNLOCK_USER_CODE,
+            &UnlockUser::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::GetPersonalAccessTokens(GetPersonalAccessTokens::default()),
             GET_PERSONAL_ACCESS_TOKENS_CODE,
@@ -479,6 +644,31 @@ mod tests {
             LOGIN_WITH_PERSONAL_ACCESS_TOKEN_CODE,
             &LoginWithPersonalAccessToken::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::GetRole(GetRole::default()),
+            GET_ROLE_CODE,
+            &GetRole::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::GetRoles(GetRoles::default()),
+            GET_ROLES_CODE,
+            &GetRoles::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::CreateRole(CreateRole::default()),
+            CREATE_ROLE_CODE,
+            &CreateRole::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::DeleteRole(DeleteRole::default()),
+            DELETE_ROLE_CODE,
+            &DeleteRole::default(),
+        );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::UpdateRole(UpdateRole::default()),
+            UPDATE_ROLE_CODE,
+            &UpdateRole::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::SendMessages(SendMessages::default()),
             SEND_MESSAGES_CODE,
@@ -489,6 +679,11 @@ mod tests {
             POLL_MESSAGES_CODE,
             &PollMessages::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::PollMessageBatches(PollMessages::default()),
+            POLL_MESSAGE_BATCHES_CODE,
+            &PollMessages::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::StoreConsumerOffset(StoreConsumerOffset::default()),
             STORE_CONSUMER_OFFSET_CODE,
@@ -499,6 +694,11 @@ mod tests {
             GET_CONSUMER_OFFSET_CODE,
             &GetConsumerOffset::default(),
         );
+        assert_serialized_as_bytes_and_deserialized_from_bytes(
+            &ServerCommand::GetConsumerLag(GetConsumerLag::default()),
+            GET_CONSUMER_LAG_CODE,
+            &GetConsumerLag::default(),
+        );
         assert_serialized_as_bytes_and_deserialized_from_bytes(
             &ServerCommand::GetStream(GetStream::default()),
             GET_STREAM_CODE,
@@ -1,2 +1,3 @@
 pub mod message_conversion;
+pub mod offsets_conversion;
 pub mod storage_conversion;
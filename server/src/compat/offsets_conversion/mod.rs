@@ -0,0 +1,92 @@
+use crate::configs::system::SystemConfig;
+use crate::streaming::partitions::partition::ConsumerOffset;
+use crate::streaming::storage::PartitionStorage;
+use crate::streaming::utils::file;
+use iggy::consumer::ConsumerKind;
+use iggy::error::IggyError;
+use std::path::Path;
+use tokio::fs;
+use tokio::io::AsyncReadExt;
+use tracing::{error, info, trace};
+
+/// Converts the legacy consumer offsets of the partition, stored as the separate files (one per consumer)
+/// in the `consumers` and `groups` directories, into the offsets log, and removes the legacy directories.
+/// Returns the number of the converted offsets.
+pub async fn convert_legacy_consumer_offsets(
+    storage: &dyn PartitionStorage,
+    config: &SystemConfig,
+    stream_id: u32,
+    topic_id: u32,
+    partition_id: u32,
+) -> Result<usize, IggyError> {
+    let legacy_paths = [
+        (
+            ConsumerKind::Consumer,
+            config.get_consumer_offsets_path(stream_id, topic_id, partition_id),
+        ),
+        (
+            ConsumerKind::ConsumerGroup,
+            config.get_consumer_group_offsets_path(stream_id, topic_id, partition_id),
+        ),
+    ];
+    if legacy_paths
+        .iter()
+        .all(|(_, path)| !Path::new(path).exists())
+    {
+        return Ok(0);
+    }
+
+    let log_path = config.get_consumer_offsets_log_path(stream_id, topic_id, partition_id);
+    let mut consumer_offsets = Vec::new();
+    // The log is replaced atomically, so if it already exists, the previous conversion was interrupted
+    // after writing it, and only the legacy directories have to be removed.
+    if !Path::new(&log_path).exists() {
+        info!("Converting legacy consumer offsets for partition with ID: {partition_id} for topic with ID: {topic_id} and stream with ID: {stream_id}...");
+        for (kind, path) in &legacy_paths {
+            consumer_offsets.extend(load_legacy_consumer_offsets(*kind, path).await?);
+        }
+        storage
+            .compact_consumer_offsets(&log_path, &consumer_offsets)
+            .await?;
+    }
+
+    for (_, path) in &legacy_paths {
+        storage.delete_consumer_offsets(path).await?;
+    }
+    info!("Converted {} legacy consumer offsets for partition with ID: {partition_id} for topic with ID: {topic_id} and stream with ID: {stream_id}, log: {log_path}.", consumer_offsets.len());
+    Ok(consumer_offsets.len())
+}
+
+async fn load_legacy_consumer_offsets(
+    kind: ConsumerKind,
+    path: &str,
+) -> Result<Vec<ConsumerOffset>, IggyError> {
+    if !Path::new(path).exists() {
+        return Ok(Vec::new());
+    }
+
+    trace!("Loading legacy consumer offsets from path: {path}...");
+    let Ok(mut dir_entries) = fs::read_dir(path).await else {
+        return Err(IggyError::CannotReadConsumerOffsets(path.to_owned()));
+    };
+
+    let mut consumer_offsets = Vec::new();
+    while let Some(dir_entry) = dir_entries.next_entry().await? {
+        if dir_entry.metadata().await?.is_dir() {
+            continue;
+        }
+
+        let name = dir_entry.file_name().to_string_lossy().to_string();
+        let Ok(consumer_id) = name.parse::<u32>() else {
+            error!("Invalid consumer ID file with name: '{name}'.");
+            continue;
+        };
+
+        let mut file = file::open(&dir_entry.path().to_string_lossy()).await?;
+        let offset = file.read_u64_le().await?;
+        consumer_offsets.push(ConsumerOffset::new(kind, consumer_id, offset));
+    }
+
+    consumer_offsets.sort_by_key(|consumer_offset| consumer_offset.consumer_id);
+    Ok(consumer_offsets)
+}
@@ -91,6 +91,7 @@ pub async fn convert(
             stream.stream_id
         );
         for topic in stream.topics.into_values() {
+            let topic = topic.read().await;
             state
                 .apply(
                     0,
@@ -106,7 +107,7 @@ pub async fn convert(
                         } else {
                             None
                         },
-                        name: topic.name,
+                        name: topic.name.clone(),
                     }),
                 )
                 .await?;
@@ -116,7 +117,7 @@ pub async fn convert(
                 topic.consumer_groups.len(),
                 topic.topic_id,
             );
-            for group in topic.consumer_groups.into_values() {
+            for group in topic.consumer_groups.values() {
                 let group = group.read().await;
                 state
                     .apply(
@@ -136,7 +137,7 @@ pub async fn convert(
                 topic.partitions.len(),
                 topic.topic_id,
             );
-            for partition in topic.partitions.into_values() {
+            for partition in topic.partitions.values() {
                 let partition = partition.read().await;
 
                 if !Path::new(&partition.offsets_path).exists()
@@ -153,51 +154,18 @@ pub async fn convert(
                     ));
                 }
 
-                info!("Creating consumer offsets directory for partition with ID: {} for stream with ID: {} and topic with ID: {}, path: {}",
-                    partition.partition_id, partition.stream_id, partition.topic_id, partition.consumer_offsets_path);
-                if !Path::new(&partition.consumer_offsets_path).exists()
-                    && create_dir(&partition.consumer_offsets_path).await.is_err()
-                {
-                    error!(
-                "Failed to create consumer offsets directory for partition with ID: {} for stream with ID: {} and topic with ID: {}.",
-                partition.partition_id, partition.stream_id, partition.topic_id
-            );
-                    return Err(IggyError::CannotCreatePartition(
-                        partition.partition_id,
-                        partition.stream_id,
-                        partition.topic_id,
-                    ));
-                }
-
-                info!("Creating consumer group offsets directory for partition with ID: {} for stream with ID: {} and topic with ID: {}, path: {}",
-                    partition.partition_id, partition.stream_id, partition.topic_id, partition.consumer_group_offsets_path);
-                if !Path::new(&partition.consumer_group_offsets_path).exists()
-                    && create_dir(&partition.consumer_group_offsets_path)
-                        .await
-                        .is_err()
-                {
-                    error!(
-                "Failed to create consumer group offsets directory for partition with ID: {} for stream with ID: {} and topic with ID: {}.",
-                partition.partition_id, partition.stream_id, partition.topic_id
-            );
-                    return Err(IggyError::CannotCreatePartition(
-                        partition.partition_id,
-                        partition.stream_id,
-                        partition.topic_id,
-                    ));
-                }
-
-                info!("Converting {} consumer offsets for partition with ID: {} for stream with ID: {} and topic with ID: {}",
-                    partition.consumer_offsets.len(), partition.partition_id, partition.stream_id, partition.topic_id);
-                for offset in partition.consumer_offsets.iter() {
-                    storage.partition.save_consumer_offset(&offset).await?;
-                }
-
-                info!("Converting {} consumer group offsets for partition with ID: {} for stream with ID: {} and topic with ID: {}",
-                    partition.consumer_group_offsets.len(), partition.partition_id, partition.stream_id, partition.topic_id);
-                for offset in partition.consumer_group_offsets.iter() {
-                    storage.partition.save_consumer_offset(&offset).await?;
-                }
+                let consumer_offsets = partition
+                    .consumer_offsets
+                    .iter()
+                    .chain(partition.consumer_group_offsets.iter())
+                    .map(|offset| offset.clone())
+                    .collect::<Vec<_>>();
+                info!("Converting {} consumer offsets and {} consumer group offsets for partition with ID: {} for stream with ID: {} and topic with ID: {}",
+                    partition.consumer_offsets.len(), partition.consumer_group_offsets.len(), partition.partition_id, partition.stream_id, partition.topic_id);
+                storage
+                    .partition
+                    .save_consumer_offsets(&partition.consumer_offsets_log_path, &consumer_offsets)
+                    .await?;
             }
         }
     }
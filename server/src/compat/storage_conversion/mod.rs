@@ -6,9 +6,12 @@ use crate::configs::system::SystemConfig;
 use crate::state::system::{PartitionState, StreamState, TopicState};
 use crate::state::State;
 use crate::streaming::batching::message_batch::RetainedMessageBatch;
+use crate::streaming::diagnostics::metrics::StorageMetrics;
+use crate::streaming::partitions::offsets_log::ConsumerOffsetsLog;
 use crate::streaming::partitions::partition::{ConsumerOffset, Partition};
 use crate::streaming::persistence::persister::Persister;
 use crate::streaming::segments::index::{Index, IndexRange};
+use crate::streaming::segments::recovery::SegmentRecovery;
 use crate::streaming::segments::segment::Segment;
 use crate::streaming::segments::time_index::TimeIndex;
 use crate::streaming::storage::{
@@ -73,6 +76,7 @@ pub async fn init(
         partition: Arc::new(NoopPartitionStorage {}),
         segment: Arc::new(NoopSegmentStorage {}),
         persister: Arc::new(NoopPersister {}),
+        metrics: StorageMetrics::default(),
     };
     let noop_storage = Arc::new(noop_storage);
     let mut dir_entries = dir_entries.unwrap();
@@ -86,7 +90,7 @@ pub async fn init(
 
         let stream_id = stream_id.unwrap();
         let mut stream = Stream::empty(stream_id, "stream", config.clone(), noop_storage.clone());
-        streams::load(&config, &db, &mut stream).await?;
+        streams::load(&db, &mut stream).await?;
         streams.push(stream);
     }
 
@@ -180,23 +184,36 @@ impl PartitionStorage for NoopPartitionStorage {
         Ok(())
     }
 
-    async fn save_consumer_offset(&self, _offset: &ConsumerOffset) -> Result<(), IggyError> {
+    async fn save_consumer_offsets(
+        &self,
+        _path: &str,
+        _offsets: &[ConsumerOffset],
+    ) -> Result<(), IggyError> {
         Ok(())
     }
 
-    async fn load_consumer_offsets(
+    async fn load_consumer_offsets(&self, _path: &str) -> Result<ConsumerOffsetsLog, IggyError> {
+        Ok(ConsumerOffsetsLog::default())
+    }
+
+    async fn compact_consumer_offsets(
         &self,
-        _kind: ConsumerKind,
         _path: &str,
-    ) -> Result<Vec<ConsumerOffset>, IggyError> {
-        Ok(vec![])
+        _offsets: &[ConsumerOffset],
+    ) -> Result<(), IggyError> {
+        Ok(())
     }
 
     async fn delete_consumer_offsets(&self, _path: &str) -> Result<(), IggyError> {
         Ok(())
     }
 
-    async fn delete_consumer_offset(&self, _path: &str) -> Result<(), IggyError> {
+    async fn delete_consumer_offset(
+        &self,
+        _path: &str,
+        _kind: ConsumerKind,
+        _consumer_id: u32,
+    ) -> Result<(), IggyError> {
         Ok(())
     }
 }
@@ -215,6 +232,10 @@ impl SegmentStorage for NoopSegmentStorage {
         Ok(())
     }
 
+    async fn recover(&self, _segment: &Segment) -> Result<SegmentRecovery, IggyError> {
+        Ok(SegmentRecovery::default())
+    }
+
     async fn load_message_batches(
         &self,
         _segment: &Segment,
@@ -276,10 +297,7 @@ impl SegmentStorage for NoopSegmentStorage {
         Ok(vec![])
     }
 
-    async fn load_last_time_index(
-        &self,
-        _segment: &Segment,
-    ) -> Result<Option<TimeIndex>, IggyError> {
+    async fn load_last_index(&self, _segment: &Segment) -> Result<Option<Index>, IggyError> {
         Ok(None)
     }
 
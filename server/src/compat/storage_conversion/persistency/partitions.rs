@@ -1,4 +1,3 @@
-use crate::configs::system::SystemConfig;
 use crate::streaming::partitions::partition::{ConsumerOffset, Partition};
 use crate::streaming::segments::segment::{Segment, LOG_EXTENSION};
 use anyhow::Context;
@@ -13,7 +12,6 @@ use tracing::{info, warn};
 
 pub async fn load_consumer_offsets(
     db: &Db,
-    config: &SystemConfig,
     kind: ConsumerKind,
     stream_id: u32,
     topic_id: u32,
@@ -53,32 +51,18 @@ pub async fn load_consumer_offsets(
     let consumer_offsets = consumer_offsets
         .into_iter()
         .map(|consumer_offset| {
-            let path = match kind {
-                ConsumerKind::Consumer => {
-                    config.get_consumer_offsets_path(stream_id, topic_id, partition_id)
-                }
-                ConsumerKind::ConsumerGroup => {
-                    config.get_consumer_group_offsets_path(stream_id, topic_id, partition_id)
-                }
-            };
-            let path = format!("{path}/{}", consumer_offset.consumer_id);
-            ConsumerOffset {
-                kind: consumer_offset.kind,
-                consumer_id: consumer_offset.consumer_id,
-                offset: consumer_offset.offset,
-                path,
-            }
+            ConsumerOffset::new(
+                consumer_offset.kind,
+                consumer_offset.consumer_id,
+                consumer_offset.offset,
+            )
         })
         .collect::<Vec<ConsumerOffset>>();
 
     Ok(consumer_offsets)
 }
 
-pub async fn load(
-    config: &SystemConfig,
-    db: &Db,
-    partition: &mut Partition,
-) -> Result<(), IggyError> {
+pub async fn load(db: &Db, partition: &mut Partition) -> Result<(), IggyError> {
     info!(
             "Loading partition with ID: {} for stream with ID: {} and topic with ID: {}, for path: {} from disk...",
             partition.partition_id, partition.stream_id, partition.topic_id, partition.partition_path
@@ -122,7 +106,6 @@ pub async fn load(
 
     let consumer_offsets_for_consumer = load_consumer_offsets(
         db,
-        config,
         ConsumerKind::Consumer,
         partition.stream_id,
         partition.topic_id,
@@ -132,7 +115,6 @@ pub async fn load(
 
     let consumer_offsets_for_group = load_consumer_offsets(
         db,
-        config,
         ConsumerKind::ConsumerGroup,
         partition.stream_id,
         partition.topic_id,
@@ -1,9 +1,9 @@
 use crate::compat::storage_conversion::persistency::topics;
-use crate::configs::system::SystemConfig;
 use crate::streaming::streams::stream::Stream;
 use crate::streaming::topics::topic::Topic;
 use anyhow::Context;
 use iggy::error::IggyError;
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
 use iggy::utils::timestamp::IggyTimestamp;
 use serde::{Deserialize, Serialize};
 use sled::Db;
@@ -17,7 +17,7 @@ struct StreamData {
     created_at: IggyTimestamp,
 }
 
-pub async fn load(config: &SystemConfig, db: &Db, stream: &mut Stream) -> Result<(), IggyError> {
+pub async fn load(db: &Db, stream: &mut Stream) -> Result<(), IggyError> {
     info!("Loading stream with ID: {} from disk...", stream.stream_id);
     if !Path::new(&stream.path).exists() {
         return Err(IggyError::StreamIdNotFound(stream.stream_id));
@@ -82,7 +82,7 @@ pub async fn load(config: &SystemConfig, db: &Db, stream: &mut Stream) -> Result
             stream.config.clone(),
             stream.storage.clone(),
         );
-        topics::load(config, db, &mut topic).await?;
+        topics::load(db, &mut topic).await?;
         topics.push(topic);
     }
 
@@ -104,7 +104,9 @@ pub async fn load(config: &SystemConfig, db: &Db, stream: &mut Stream) -> Result
         }
 
         stream.topics_ids.insert(topic.name.clone(), topic.topic_id);
-        stream.topics.insert(topic.topic_id, topic);
+        stream
+            .topics
+            .insert(topic.topic_id, IggySharedMut::new(topic));
     }
 
     info!(
@@ -1,5 +1,4 @@
 use crate::compat::storage_conversion::persistency::partitions;
-use crate::configs::system::SystemConfig;
 use crate::streaming::partitions::partition::Partition;
 use crate::streaming::topics::consumer_group::ConsumerGroup;
 use crate::streaming::topics::topic::Topic;
@@ -16,7 +15,6 @@ use serde::{Deserialize, Serialize};
 use sled::Db;
 use std::path::Path;
 use tokio::fs;
-use tokio::sync::RwLock;
 use tracing::{error, info};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -25,7 +23,7 @@ struct ConsumerGroupData {
     name: String,
 }
 
-pub async fn load(config: &SystemConfig, db: &Db, topic: &mut Topic) -> Result<(), IggyError> {
+pub async fn load(db: &Db, topic: &mut Topic) -> Result<(), IggyError> {
     info!("Loading topic {} from disk...", topic);
     if !Path::new(&topic.path).exists() {
         return Err(IggyError::TopicIdNotFound(topic.topic_id, topic.stream_id));
@@ -103,7 +101,7 @@ pub async fn load(config: &SystemConfig, db: &Db, topic: &mut Topic) -> Result<(
             topic.segments_count_of_parent_stream.clone(),
             IggyTimestamp::zero(),
         );
-        partitions::load(config, db, &mut partition).await?;
+        partitions::load(db, &mut partition).await?;
         topic
             .partitions
             .insert(partition.partition_id, IggySharedMut::new(partition));
@@ -112,7 +110,7 @@ pub async fn load(config: &SystemConfig, db: &Db, topic: &mut Topic) -> Result<(
     let consumer_groups = load_consumer_groups(db, topic).await?;
     topic.consumer_groups = consumer_groups
         .into_iter()
-        .map(|group| (group.group_id, RwLock::new(group)))
+        .map(|group| (group.group_id, IggySharedMut::new(group)))
         .collect();
     info!("Loaded topic {topic}");
     Ok(())
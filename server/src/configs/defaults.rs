@@ -3,14 +3,15 @@ use crate::configs::http::{
 };
 use crate::configs::quic::{QuicCertificateConfig, QuicConfig};
 use crate::configs::server::{
-    ArchiverConfig, DataMaintenanceConfig, MessageSaverConfig, MessagesMaintenanceConfig,
-    PersonalAccessTokenCleanerConfig, PersonalAccessTokenConfig, ServerConfig,
-    StateMaintenanceConfig,
+    ArchiverConfig, DataMaintenanceConfig, EncryptionMaintenanceConfig, MessageSaverConfig,
+    MessagesMaintenanceConfig, PersonalAccessTokenCleanerConfig, PersonalAccessTokenConfig,
+    ServerConfig, StateMaintenanceConfig, TelemetryConfig,
 };
 use crate::configs::system::{
-    BackupConfig, CacheConfig, CompatibilityConfig, CompressionConfig, EncryptionConfig,
-    LoggingConfig, MessageDeduplicationConfig, PartitionConfig, RuntimeConfig, SegmentConfig,
-    StreamConfig, SystemConfig, TopicConfig,
+    AuditConfig, BackupConfig, CacheConfig, CompatibilityConfig, CompressionConfig,
+    ConsumerOffsetsConfig, EncryptionConfig, LoggingConfig, LoginProtectionConfig,
+    MessageDeduplicationConfig, PartitionConfig, PasswordHashingConfig, PasswordPolicyConfig,
+    RuntimeConfig, SegmentConfig, StreamConfig, SystemConfig, TopicConfig,
 };
 use crate::configs::tcp::{TcpConfig, TcpTlsConfig};
 use std::sync::Arc;
@@ -30,6 +31,7 @@ impl Default for ServerConfig {
             quic: QuicConfig::default(),
             tcp: TcpConfig::default(),
             http: HttpConfig::default(),
+            telemetry: TelemetryConfig::default(),
         }
     }
 }
@@ -76,6 +78,31 @@ impl Default for StateMaintenanceConfig {
                 .interval
                 .parse()
                 .unwrap(),
+            snapshot_enabled: SERVER_CONFIG.data_maintenance.state.snapshot_enabled,
+            snapshot_interval: SERVER_CONFIG
+                .data_maintenance
+                .state
+                .snapshot_interval
+                .parse()
+                .unwrap(),
+            snapshot_min_entries: SERVER_CONFIG.data_maintenance.state.snapshot_min_entries as u64,
+        }
+    }
+}
+
+impl Default for EncryptionMaintenanceConfig {
+    fn default() -> EncryptionMaintenanceConfig {
+        EncryptionMaintenanceConfig {
+            reencryption_enabled: SERVER_CONFIG
+                .data_maintenance
+                .encryption
+                .reencryption_enabled,
+            interval: SERVER_CONFIG
+                .data_maintenance
+                .encryption
+                .interval
+                .parse()
+                .unwrap(),
         }
     }
 }
@@ -242,6 +269,17 @@ impl Default for MessageSaverConfig {
     }
 }
 
+impl Default for TelemetryConfig {
+    fn default() -> TelemetryConfig {
+        TelemetryConfig {
+            enabled: SERVER_CONFIG.telemetry.enabled,
+            service_name: SERVER_CONFIG.telemetry.service_name.parse().unwrap(),
+            endpoint: SERVER_CONFIG.telemetry.endpoint.parse().unwrap(),
+            export_interval: SERVER_CONFIG.telemetry.export_interval.parse().unwrap(),
+        }
+    }
+}
+
 impl Default for PersonalAccessTokenConfig {
     fn default() -> PersonalAccessTokenConfig {
         PersonalAccessTokenConfig {
@@ -278,9 +316,14 @@ impl Default for SystemConfig {
             encryption: EncryptionConfig::default(),
             topic: TopicConfig::default(),
             partition: PartitionConfig::default(),
+            consumer_offsets: ConsumerOffsetsConfig::default(),
             segment: SegmentConfig::default(),
             compression: CompressionConfig::default(),
             message_deduplication: MessageDeduplicationConfig::default(),
+            audit: AuditConfig::default(),
+            login_protection: LoginProtectionConfig::default(),
+            password_policy: PasswordPolicyConfig::default(),
+            password_hashing: PasswordHashingConfig::default(),
         }
     }
 }
@@ -361,6 +404,8 @@ impl Default for EncryptionConfig {
         EncryptionConfig {
             enabled: SERVER_CONFIG.system.encryption.enabled,
             key: SERVER_CONFIG.system.encryption.key.parse().unwrap(),
+            key_id: SERVER_CONFIG.system.encryption.key_id as u32,
+            previous_keys: Vec::new(),
         }
     }
 }
@@ -391,6 +436,27 @@ impl Default for PartitionConfig {
                 as u32,
             enforce_fsync: SERVER_CONFIG.system.partition.enforce_fsync,
             validate_checksum: SERVER_CONFIG.system.partition.validate_checksum,
+            io_backend: SERVER_CONFIG.system.partition.io_backend.parse().unwrap(),
+        }
+    }
+}
+
+impl Default for ConsumerOffsetsConfig {
+    fn default() -> ConsumerOffsetsConfig {
+        ConsumerOffsetsConfig {
+            flush_interval: SERVER_CONFIG
+                .system
+                .consumer_offsets
+                .flush_interval
+                .parse()
+                .unwrap(),
+            compaction_interval: SERVER_CONFIG
+                .system
+                .consumer_offsets
+                .compaction_interval
+                .parse()
+                .unwrap(),
+            compaction_threshold: SERVER_CONFIG.system.consumer_offsets.compaction_threshold as u64,
         }
     }
 }
@@ -401,6 +467,9 @@ impl Default for SegmentConfig {
             size: SERVER_CONFIG.system.segment.size.parse().unwrap(),
             cache_indexes: SERVER_CONFIG.system.segment.cache_indexes,
             cache_time_indexes: SERVER_CONFIG.system.segment.cache_time_indexes,
+            index_interval: SERVER_CONFIG.system.segment.index_interval.parse().unwrap(),
+            max_age: SERVER_CONFIG.system.segment.max_age.parse().unwrap(),
+            preallocate: SERVER_CONFIG.system.segment.preallocate,
             message_expiry: SERVER_CONFIG.system.segment.message_expiry.parse().unwrap(),
             archive_expired: SERVER_CONFIG.system.segment.archive_expired,
         }
@@ -421,3 +490,79 @@ impl Default for MessageDeduplicationConfig {
         }
     }
 }
+
+impl Default for AuditConfig {
+    fn default() -> AuditConfig {
+        AuditConfig {
+            enabled: SERVER_CONFIG.system.audit.enabled,
+            path: SERVER_CONFIG.system.audit.path.parse().unwrap(),
+            max_file_size: SERVER_CONFIG.system.audit.max_file_size.parse().unwrap(),
+            max_files: SERVER_CONFIG.system.audit.max_files as u32,
+            include_data_commands: SERVER_CONFIG.system.audit.include_data_commands,
+        }
+    }
+}
+
+impl Default for PasswordPolicyConfig {
+    fn default() -> PasswordPolicyConfig {
+        PasswordPolicyConfig {
+            min_length: SERVER_CONFIG.system.password_policy.min_length as u32,
+            require_uppercase: SERVER_CONFIG.system.password_policy.require_uppercase,
+            require_lowercase: SERVER_CONFIG.system.password_policy.require_lowercase,
+            require_digit: SERVER_CONFIG.system.password_policy.require_digit,
+            require_special_character: SERVER_CONFIG
+                .system
+                .password_policy
+                .require_special_character,
+            history_size: SERVER_CONFIG.system.password_policy.history_size as u32,
+        }
+    }
+}
+
+impl Default for PasswordHashingConfig {
+    fn default() -> PasswordHashingConfig {
+        PasswordHashingConfig {
+            algorithm: SERVER_CONFIG
+                .system
+                .password_hashing
+                .algorithm
+                .parse()
+                .unwrap(),
+            bcrypt_cost: SERVER_CONFIG.system.password_hashing.bcrypt_cost as u32,
+            argon2_memory_cost: SERVER_CONFIG.system.password_hashing.argon_2_memory_cost as u32,
+            argon2_iterations: SERVER_CONFIG.system.password_hashing.argon_2_iterations as u32,
+            argon2_parallelism: SERVER_CONFIG.system.password_hashing.argon_2_parallelism as u32,
+        }
+    }
+}
+
+impl Default for LoginProtectionConfig {
+    fn default() -> LoginProtectionConfig {
+        LoginProtectionConfig {
+            enabled: SERVER_CONFIG.system.login_protection.enabled,
+            max_failed_attempts: SERVER_CONFIG.system.login_protection.max_failed_attempts as u32,
+            max_failed_attempts_per_ip: SERVER_CONFIG
+                .system
+                .login_protection
+                .max_failed_attempts_per_ip as u32,
+            base_delay: SERVER_CONFIG
+                .system
+                .login_protection
+                .base_delay
+                .parse()
+                .unwrap(),
+            max_delay: SERVER_CONFIG
+                .system
+                .login_protection
+                .max_delay
+                .parse()
+                .unwrap(),
+            lockout_duration: SERVER_CONFIG
+                .system
+                .login_protection
+                .lockout_duration
+                .parse()
+                .unwrap(),
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use crate::configs::quic::{QuicCertificateConfig, QuicConfig};
 use crate::configs::server::{
-    ArchiverConfig, DataMaintenanceConfig, DiskArchiverConfig, MessagesMaintenanceConfig,
-    S3ArchiverConfig, StateMaintenanceConfig,
+    ArchiverConfig, DataMaintenanceConfig, DiskArchiverConfig, EncryptionMaintenanceConfig,
+    MessagesMaintenanceConfig, S3ArchiverConfig, StateMaintenanceConfig, TelemetryConfig,
+};
+use crate::configs::system::{
+    AuditConfig, ConsumerOffsetsConfig, LoginProtectionConfig, MessageDeduplicationConfig,
+    PasswordHashingConfig, PasswordPolicyConfig,
 };
-use crate::configs::system::MessageDeduplicationConfig;
 use crate::configs::{
     http::{HttpConfig, HttpCorsConfig, HttpJwtConfig, HttpMetricsConfig, HttpTlsConfig},
     resource_quota::MemoryResourceQuota,
@@ -118,8 +121,8 @@ impl Display for DataMaintenanceConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ archiver: {}, messages: {}, state: {} }}",
-            self.archiver, self.messages, self.state
+            "{{ archiver: {}, messages: {}, state: {}, encryption: {} }}",
+            self.archiver, self.messages, self.state, self.encryption
         )
     }
 }
@@ -167,8 +170,23 @@ impl Display for StateMaintenanceConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ archiver_enabled: {}, overwrite: {}, interval: {} }}",
-            self.archiver_enabled, self.overwrite, self.interval
+            "{{ archiver_enabled: {}, overwrite: {}, interval: {}, snapshot_enabled: {}, snapshot_interval: {}, snapshot_min_entries: {} }}",
+            self.archiver_enabled,
+            self.overwrite,
+            self.interval,
+            self.snapshot_enabled,
+            self.snapshot_interval,
+            self.snapshot_min_entries
+        )
+    }
+}
+
+impl Display for EncryptionMaintenanceConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ reencryption_enabled: {}, interval: {} }}",
+            self.reencryption_enabled, self.interval
         )
     }
 }
@@ -177,8 +195,18 @@ impl Display for ServerConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ data_maintenance: {}, message_saver: {}, system: {}, quic: {}, tcp: {}, http: {} }}",
-            self.data_maintenance, self.message_saver, self.system, self.quic, self.tcp, self.http
+            "{{ data_maintenance: {}, message_saver: {}, system: {}, quic: {}, tcp: {}, http: {}, telemetry: {} }}",
+            self.data_maintenance, self.message_saver, self.system, self.quic, self.tcp, self.http, self.telemetry
+        )
+    }
+}
+
+impl Display for TelemetryConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, service_name: {}, endpoint: {}, export_interval: {} }}",
+            self.enabled, self.service_name, self.endpoint, self.export_interval
         )
     }
 }
@@ -201,7 +229,17 @@ impl Display for CacheConfig {
 
 impl Display for EncryptionConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{{ enabled: {} }}", self.enabled)
+        let previous_key_ids = self
+            .previous_keys
+            .iter()
+            .map(|key| key.id.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(
+            f,
+            "{{ enabled: {}, key_id: {}, previous_key_ids: [{}] }}",
+            self.enabled, self.key_id, previous_key_ids
+        )
     }
 }
 
@@ -225,15 +263,26 @@ impl Display for PartitionConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
           f,
-          "{{ path: {}, messages_required_to_save: {}, enforce_fsync: {}, validate_checksum: {} }}",
+          "{{ path: {}, messages_required_to_save: {}, enforce_fsync: {}, validate_checksum: {}, io_backend: {} }}",
           self.path,
           self.messages_required_to_save,
           self.enforce_fsync,
-          self.validate_checksum
+          self.validate_checksum,
+          self.io_backend
       )
     }
 }
 
+impl Display for ConsumerOffsetsConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ flush_interval: {}, compaction_interval: {}, compaction_threshold: {} }}",
+            self.flush_interval, self.compaction_interval, self.compaction_threshold
+        )
+    }
+}
+
 impl Display for MessageDeduplicationConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -244,12 +293,52 @@ impl Display for MessageDeduplicationConfig {
     }
 }
 
+impl Display for AuditConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, path: {}, max_file_size: {}, max_files: {}, include_data_commands: {} }}",
+            self.enabled, self.path, self.max_file_size, self.max_files, self.include_data_commands
+        )
+    }
+}
+
+impl Display for LoginProtectionConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ enabled: {}, max_failed_attempts: {}, max_failed_attempts_per_ip: {}, base_delay: {}, max_delay: {}, lockout_duration: {} }}",
+            self.enabled, self.max_failed_attempts, self.max_failed_attempts_per_ip, self.base_delay, self.max_delay, self.lockout_duration
+        )
+    }
+}
+
+impl Display for PasswordPolicyConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ min_length: {}, require_uppercase: {}, require_lowercase: {}, require_digit: {}, require_special_character: {}, history_size: {} }}",
+            self.min_length, self.require_uppercase, self.require_lowercase, self.require_digit, self.require_special_character, self.history_size
+        )
+    }
+}
+
+impl Display for PasswordHashingConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ algorithm: {}, bcrypt_cost: {}, argon2_memory_cost: {}, argon2_iterations: {}, argon2_parallelism: {} }}",
+            self.algorithm, self.bcrypt_cost, self.argon2_memory_cost, self.argon2_iterations, self.argon2_parallelism
+        )
+    }
+}
+
 impl Display for SegmentConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ size_bytes: {}, cache_indexes: {}, cache_time_indexes: {}, message_expiry: {}, archive_expired: {} }}",
-            self.size, self.cache_indexes, self.cache_time_indexes, self.message_expiry, self.archive_expired
+            "{{ size_bytes: {}, cache_indexes: {}, cache_time_indexes: {}, index_interval: {}, max_age: {}, preallocate: {}, message_expiry: {}, archive_expired: {} }}",
+            self.size, self.cache_indexes, self.cache_time_indexes, self.index_interval, self.max_age, self.preallocate, self.message_expiry, self.archive_expired
         )
     }
 }
@@ -291,15 +380,20 @@ impl Display for SystemConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
           f,
-          "{{ path: {}, logging: {}, cache: {}, stream: {}, topic: {}, partition: {}, segment: {}, encryption: {} }}",
+          "{{ path: {}, logging: {}, cache: {}, stream: {}, topic: {}, partition: {}, consumer_offsets: {}, segment: {}, encryption: {}, audit: {}, login_protection: {}, password_policy: {}, password_hashing: {} }}",
           self.path,
           self.logging,
           self.cache,
           self.stream,
           self.topic,
           self.partition,
+          self.consumer_offsets,
           self.segment,
-          self.encryption
+          self.encryption,
+          self.audit,
+          self.login_protection,
+          self.password_policy,
+          self.password_hashing
       )
     }
 }
@@ -21,6 +21,7 @@ pub struct ServerConfig {
     pub quic: QuicConfig,
     pub tcp: TcpConfig,
     pub http: HttpConfig,
+    pub telemetry: TelemetryConfig,
 }
 
 #[serde_as]
@@ -29,6 +30,7 @@ pub struct DataMaintenanceConfig {
     pub archiver: ArchiverConfig,
     pub messages: MessagesMaintenanceConfig,
     pub state: StateMaintenanceConfig,
+    pub encryption: EncryptionMaintenanceConfig,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -55,6 +57,18 @@ pub struct StateMaintenanceConfig {
     pub overwrite: bool,
     #[serde_as(as = "DisplayFromStr")]
     pub interval: IggyDuration,
+    pub snapshot_enabled: bool,
+    #[serde_as(as = "DisplayFromStr")]
+    pub snapshot_interval: IggyDuration,
+    pub snapshot_min_entries: u64,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EncryptionMaintenanceConfig {
+    pub reencryption_enabled: bool,
+    #[serde_as(as = "DisplayFromStr")]
+    pub interval: IggyDuration,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -81,6 +95,16 @@ pub struct MessageSaverConfig {
     pub interval: IggyDuration,
 }
 
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TelemetryConfig {
+    pub enabled: bool,
+    pub service_name: String,
+    pub endpoint: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub export_interval: IggyDuration,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PersonalAccessTokenConfig {
     pub max_tokens_per_user: u32,
@@ -1,4 +1,5 @@
 use crate::configs::resource_quota::MemoryResourceQuota;
+use derive_more::Display;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
@@ -8,6 +9,7 @@ use iggy::{
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use serde_with::DisplayFromStr;
+use std::str::FromStr;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SystemConfig {
@@ -20,10 +22,15 @@ pub struct SystemConfig {
     pub stream: StreamConfig,
     pub topic: TopicConfig,
     pub partition: PartitionConfig,
+    pub consumer_offsets: ConsumerOffsetsConfig,
     pub segment: SegmentConfig,
     pub encryption: EncryptionConfig,
     pub compression: CompressionConfig,
     pub message_deduplication: MessageDeduplicationConfig,
+    pub audit: AuditConfig,
+    pub login_protection: LoginProtectionConfig,
+    pub password_policy: PasswordPolicyConfig,
+    pub password_hashing: PasswordHashingConfig,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -75,6 +82,15 @@ pub struct CacheConfig {
 pub struct EncryptionConfig {
     pub enabled: bool,
     pub key: String,
+    pub key_id: u32,
+    #[serde(default)]
+    pub previous_keys: Vec<EncryptionKeyConfig>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EncryptionKeyConfig {
+    pub id: u32,
+    pub key: String,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -97,6 +113,38 @@ pub struct PartitionConfig {
     pub messages_required_to_save: u32,
     pub enforce_fsync: bool,
     pub validate_checksum: bool,
+    pub io_backend: IoBackend,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Default, Display, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum IoBackend {
+    #[default]
+    #[display(fmt = "tokio")]
+    Tokio,
+    #[display(fmt = "io_uring")]
+    IoUring,
+}
+
+impl FromStr for IoBackend {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "tokio" => Ok(IoBackend::Tokio),
+            "io_uring" => Ok(IoBackend::IoUring),
+            _ => Err(format!("Unknown I/O backend: {}", s)),
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ConsumerOffsetsConfig {
+    #[serde_as(as = "DisplayFromStr")]
+    pub flush_interval: IggyDuration,
+    #[serde_as(as = "DisplayFromStr")]
+    pub compaction_interval: IggyDuration,
+    pub compaction_threshold: u64,
 }
 
 #[serde_as]
@@ -108,12 +156,79 @@ pub struct MessageDeduplicationConfig {
     pub expiry: IggyDuration,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AuditConfig {
+    pub enabled: bool,
+    pub path: String,
+    pub max_file_size: IggyByteSize,
+    pub max_files: u32,
+    pub include_data_commands: bool,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LoginProtectionConfig {
+    pub enabled: bool,
+    pub max_failed_attempts: u32,
+    pub max_failed_attempts_per_ip: u32,
+    #[serde_as(as = "DisplayFromStr")]
+    pub base_delay: IggyDuration,
+    #[serde_as(as = "DisplayFromStr")]
+    pub max_delay: IggyDuration,
+    #[serde_as(as = "DisplayFromStr")]
+    pub lockout_duration: IggyDuration,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PasswordPolicyConfig {
+    pub min_length: u32,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_digit: bool,
+    pub require_special_character: bool,
+    pub history_size: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PasswordHashingConfig {
+    pub algorithm: PasswordHashingAlgorithm,
+    pub bcrypt_cost: u32,
+    pub argon2_memory_cost: u32,
+    pub argon2_iterations: u32,
+    pub argon2_parallelism: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Default, Display, Copy, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum PasswordHashingAlgorithm {
+    #[default]
+    #[display(fmt = "bcrypt")]
+    Bcrypt,
+    #[display(fmt = "argon2id")]
+    Argon2id,
+}
+
+impl FromStr for PasswordHashingAlgorithm {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bcrypt" => Ok(PasswordHashingAlgorithm::Bcrypt),
+            "argon2id" => Ok(PasswordHashingAlgorithm::Argon2id),
+            _ => Err(format!("Unknown password hashing algorithm: {}", s)),
+        }
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SegmentConfig {
     pub size: IggyByteSize,
     pub cache_indexes: bool,
     pub cache_time_indexes: bool,
+    pub index_interval: IggyByteSize,
+    #[serde_as(as = "DisplayFromStr")]
+    pub max_age: IggyDuration,
+    pub preallocate: bool,
     #[serde_as(as = "DisplayFromStr")]
     pub message_expiry: IggyExpiry,
     pub archive_expired: bool,
@@ -141,6 +256,10 @@ impl SystemConfig {
     pub fn get_state_info_path(&self) -> String {
         format!("{}/info", self.get_state_path())
     }
+
+    pub fn get_state_snapshot_path(&self) -> String {
+        format!("{}/snapshot", self.get_state_path())
+    }
     pub fn get_state_tokens_path(&self) -> String {
         format!("{}/tokens", self.get_state_path())
     }
@@ -157,6 +276,10 @@ impl SystemConfig {
         )
     }
 
+    pub fn get_audit_path(&self) -> String {
+        format!("{}/{}", self.get_system_path(), self.audit.path)
+    }
+
     pub fn get_runtime_path(&self) -> String {
         format!("{}/{}", self.get_system_path(), self.runtime.path)
     }
@@ -200,6 +323,20 @@ impl SystemConfig {
         )
     }
 
+    pub fn get_consumer_offsets_log_path(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+    ) -> String {
+        format!(
+            "{}/log",
+            self.get_offsets_path(stream_id, topic_id, partition_id)
+        )
+    }
+
+    /// Returns the path of the legacy directory with the consumer offsets stored as the separate files,
+    /// which is converted into the offsets log on startup.
     pub fn get_consumer_offsets_path(
         &self,
         stream_id: u32,
@@ -212,6 +349,8 @@ impl SystemConfig {
         )
     }
 
+    /// Returns the path of the legacy directory with the consumer group offsets stored as the separate files,
+    /// which is converted into the offsets log on startup.
     pub fn get_consumer_group_offsets_path(
         &self,
         stream_id: u32,
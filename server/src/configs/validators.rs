@@ -1,17 +1,22 @@
 extern crate sysinfo;
 
 use super::server::{
-    ArchiverConfig, DataMaintenanceConfig, MessageSaverConfig, MessagesMaintenanceConfig,
-    StateMaintenanceConfig,
+    ArchiverConfig, DataMaintenanceConfig, EncryptionMaintenanceConfig, MessageSaverConfig,
+    MessagesMaintenanceConfig, StateMaintenanceConfig, TelemetryConfig,
+};
+use super::system::{
+    CompressionConfig, EncryptionConfig, IoBackend, LoginProtectionConfig, PartitionConfig,
+    PasswordHashingAlgorithm, PasswordHashingConfig, PasswordPolicyConfig,
 };
-use super::system::CompressionConfig;
 use crate::archiver::ArchiverKind;
 use crate::configs::server::{PersonalAccessTokenConfig, ServerConfig};
-use crate::configs::system::{CacheConfig, SegmentConfig};
+use crate::configs::system::{CacheConfig, ConsumerOffsetsConfig, SegmentConfig};
 use crate::server_error::ServerError;
 use crate::streaming::segments::segment;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
+use iggy::users::defaults::{MAX_PASSWORD_LENGTH, MIN_PASSWORD_LENGTH};
 use iggy::utils::byte_size::IggyByteSize;
+use iggy::utils::crypto::Aes256GcmEncryptor;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use iggy::validatable::Validatable;
@@ -22,9 +27,16 @@ impl Validatable<ServerError> for ServerConfig {
     fn validate(&self) -> Result<(), ServerError> {
         self.data_maintenance.validate()?;
         self.personal_access_token.validate()?;
+        self.telemetry.validate()?;
+        self.system.partition.validate()?;
+        self.system.consumer_offsets.validate()?;
         self.system.segment.validate()?;
         self.system.cache.validate()?;
         self.system.compression.validate()?;
+        self.system.encryption.validate()?;
+        self.system.password_policy.validate()?;
+        self.system.password_hashing.validate()?;
+        self.system.login_protection.validate()?;
 
         let topic_size = match self.system.topic.max_size {
             MaxTopicSize::Custom(size) => Ok(size.as_bytes_u64()),
@@ -75,6 +87,41 @@ impl Validatable<ServerError> for CompressionConfig {
     }
 }
 
+impl Validatable<ServerError> for EncryptionConfig {
+    fn validate(&self) -> Result<(), ServerError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if Aes256GcmEncryptor::from_base64_key(&self.key).is_err() {
+            return Err(ServerError::InvalidConfiguration(
+                "Encryption key must be a base64 encoded 32 bytes length key.".into(),
+            ));
+        }
+
+        let mut key_ids = vec![self.key_id];
+        for previous_key in &self.previous_keys {
+            if key_ids.contains(&previous_key.id) {
+                return Err(ServerError::InvalidConfiguration(format!(
+                    "Encryption key ID: {} is not unique.",
+                    previous_key.id
+                )));
+            }
+
+            if Aes256GcmEncryptor::from_base64_key(&previous_key.key).is_err() {
+                return Err(ServerError::InvalidConfiguration(format!(
+                    "Previous encryption key with ID: {} must be a base64 encoded 32 bytes length key.",
+                    previous_key.id
+                )));
+            }
+
+            key_ids.push(previous_key.id);
+        }
+
+        Ok(())
+    }
+}
+
 impl Validatable<ServerError> for CacheConfig {
     fn validate(&self) -> Result<(), ServerError> {
         let limit_bytes = self.size.clone().into();
@@ -121,6 +168,101 @@ impl Validatable<ServerError> for SegmentConfig {
             )));
         }
 
+        if self.index_interval.as_bytes_u64() > self.size.as_bytes_u64() {
+            return Err(ServerError::InvalidConfiguration(format!(
+                "Segment index interval: {} cannot be greater than segment size: {}.",
+                self.index_interval, self.size
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ServerError> for ConsumerOffsetsConfig {
+    fn validate(&self) -> Result<(), ServerError> {
+        if self.compaction_threshold == 0 {
+            return Err(ServerError::InvalidConfiguration(
+                "Consumer offsets compaction threshold cannot be zero, it must be greater than 0."
+                    .into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ServerError> for PasswordPolicyConfig {
+    fn validate(&self) -> Result<(), ServerError> {
+        let min_length = self.min_length as usize;
+        if !(MIN_PASSWORD_LENGTH..=MAX_PASSWORD_LENGTH).contains(&min_length) {
+            return Err(ServerError::InvalidConfiguration(format!(
+                "Password policy min length must be between {MIN_PASSWORD_LENGTH} and {MAX_PASSWORD_LENGTH}, but was: {min_length}."
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ServerError> for LoginProtectionConfig {
+    fn validate(&self) -> Result<(), ServerError> {
+        if self.max_failed_attempts == 0 {
+            return Err(ServerError::InvalidConfiguration(
+                "Login protection max failed attempts cannot be 0.".into(),
+            ));
+        }
+
+        if self.max_failed_attempts_per_ip == 0 {
+            return Err(ServerError::InvalidConfiguration(
+                "Login protection max failed attempts per IP address cannot be 0.".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ServerError> for PartitionConfig {
+    fn validate(&self) -> Result<(), ServerError> {
+        if self.io_backend == IoBackend::IoUring
+            && !cfg!(all(target_os = "linux", feature = "io-uring"))
+        {
+            return Err(ServerError::InvalidConfiguration(
+                "io_uring backend requires Linux and the server built with the `io-uring` feature."
+                    .into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ServerError> for PasswordHashingConfig {
+    fn validate(&self) -> Result<(), ServerError> {
+        match self.algorithm {
+            PasswordHashingAlgorithm::Bcrypt => {
+                if !(4..=31).contains(&self.bcrypt_cost) {
+                    return Err(ServerError::InvalidConfiguration(format!(
+                        "Bcrypt cost must be between 4 and 31, but was: {}.",
+                        self.bcrypt_cost
+                    )));
+                }
+            }
+            PasswordHashingAlgorithm::Argon2id => {
+                if let Err(error) = argon2::Params::new(
+                    self.argon2_memory_cost,
+                    self.argon2_iterations,
+                    self.argon2_parallelism,
+                    None,
+                ) {
+                    return Err(ServerError::InvalidConfiguration(format!(
+                        "Invalid argon2id parameters: {error}."
+                    )));
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -142,6 +284,7 @@ impl Validatable<ServerError> for DataMaintenanceConfig {
         self.archiver.validate()?;
         self.messages.validate()?;
         self.state.validate()?;
+        self.encryption.validate()?;
         Ok(())
     }
 }
@@ -234,6 +377,53 @@ impl Validatable<ServerError> for StateMaintenanceConfig {
             ));
         }
 
+        if self.snapshot_enabled && self.snapshot_interval.is_zero() {
+            return Err(ServerError::InvalidConfiguration(
+                "State snapshot interval cannot be zero, it must be greater than 0.".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ServerError> for EncryptionMaintenanceConfig {
+    fn validate(&self) -> Result<(), ServerError> {
+        if self.reencryption_enabled && self.interval.is_zero() {
+            return Err(ServerError::InvalidConfiguration(
+                "Re-encryption interval cannot be zero, it must be greater than 0.".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Validatable<ServerError> for TelemetryConfig {
+    fn validate(&self) -> Result<(), ServerError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if self.service_name.is_empty() {
+            return Err(ServerError::InvalidConfiguration(
+                "Telemetry service name cannot be empty.".into(),
+            ));
+        }
+
+        if !self.endpoint.starts_with("http://") && !self.endpoint.starts_with("https://") {
+            return Err(ServerError::InvalidConfiguration(format!(
+                "Telemetry endpoint must be an HTTP(S) URL, but was: {}.",
+                self.endpoint
+            )));
+        }
+
+        if self.export_interval.is_zero() {
+            return Err(ServerError::InvalidConfiguration(
+                "Telemetry export interval cannot be zero, it must be greater than 0.".into(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,88 @@
+use crate::http::jwt::json_web_token::Identity;
+use crate::http::shared::{AppState, RequestDetails};
+use axum::body::{to_bytes, Body};
+use axum::{
+    extract::State,
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use iggy::models::audit_log::AuditLogEntry;
+use iggy::users::login_user::LoginUser;
+use std::sync::Arc;
+
+const TRANSPORT: &str = "HTTP";
+const UNAUDITED_PATHS: &[&str] = &["/", "/metrics", "/ping"];
+const DATA_PATHS: &[&str] = &["/messages", "/consumer-offsets"];
+const LOGIN_PATH: &str = "/users/login";
+const MAX_LOGIN_BODY_SIZE: usize = 64 * 1024;
+
+pub async fn audit(
+    State(state): State<Arc<AppState>>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let path = request.uri().path();
+    if UNAUDITED_PATHS.contains(&path) {
+        return Ok(next.run(request).await);
+    }
+
+    let is_data_command = DATA_PATHS.iter().any(|data_path| path.ends_with(data_path));
+    if !state.system.read().await.is_audited(is_data_command) {
+        return Ok(next.run(request).await);
+    }
+
+    let action = format!("{} {}", request.method(), path);
+    let ip_address = request
+        .extensions()
+        .get::<RequestDetails>()
+        .map(|request_details| request_details.ip_address);
+    let (request, username) = if path == LOGIN_PATH {
+        read_login_username(request).await?
+    } else {
+        (request, None)
+    };
+    let response = next.run(request).await;
+    let Some(ip_address) = ip_address else {
+        return Ok(response);
+    };
+
+    // The identity is available only for the authenticated requests, thus it's read from the response.
+    let user_id = response
+        .extensions()
+        .get::<Identity>()
+        .map(|identity| identity.user_id)
+        .unwrap_or_default();
+    let system = state.system.read().await;
+
+    let status = response.status();
+    let result = if status.is_client_error() || status.is_server_error() {
+        status.to_string()
+    } else {
+        AuditLogEntry::OK_RESULT.to_string()
+    };
+    system
+        .record_audit_entry(
+            user_id,
+            username.as_deref(),
+            &ip_address,
+            TRANSPORT,
+            &action,
+            &result,
+        )
+        .await;
+    Ok(response)
+}
+
+async fn read_login_username(
+    request: Request<Body>,
+) -> Result<(Request<Body>, Option<String>), StatusCode> {
+    let (parts, body) = request.into_parts();
+    let bytes = to_bytes(body, MAX_LOGIN_BODY_SIZE)
+        .await
+        .map_err(|_| StatusCode::PAYLOAD_TOO_LARGE)?;
+    let username = serde_json::from_slice::<LoginUser>(&bytes)
+        .ok()
+        .map(|command| command.username);
+    Ok((Request::from_parts(parts, Body::from(bytes)), username))
+}
@@ -11,6 +11,7 @@ use axum::{Extension, Json, Router};
 use iggy::consumer_groups::create_consumer_group::CreateConsumerGroup;
 use iggy::consumer_groups::delete_consumer_group::DeleteConsumerGroup;
 use iggy::identifier::Identifier;
+use iggy::locking::IggySharedMutFn;
 use iggy::models::consumer_group::{ConsumerGroup, ConsumerGroupDetails};
 use iggy::validatable::Validatable;
 use std::sync::Arc;
@@ -37,12 +38,14 @@ async fn get_consumer_group(
     let topic_id = Identifier::from_str_value(&topic_id)?;
     let group_id = Identifier::from_str_value(&group_id)?;
     let system = state.system.read().await;
-    let consumer_group = system.get_consumer_group(
-        &Session::stateless(identity.user_id, identity.ip_address),
-        &stream_id,
-        &topic_id,
-        &group_id,
-    )?;
+    let consumer_group = system
+        .get_consumer_group(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &stream_id,
+            &topic_id,
+            &group_id,
+        )
+        .await?;
     let consumer_group = consumer_group.read().await;
     let consumer_group = mapper::map_consumer_group(&consumer_group).await;
     Ok(Json(consumer_group))
@@ -56,11 +59,13 @@ async fn get_consumer_groups(
     let stream_id = Identifier::from_str_value(&stream_id)?;
     let topic_id = Identifier::from_str_value(&topic_id)?;
     let system = state.system.read().await;
-    let consumer_groups = system.get_consumer_groups(
-        &Session::stateless(identity.user_id, identity.ip_address),
-        &stream_id,
-        &topic_id,
-    )?;
+    let consumer_groups = system
+        .get_consumer_groups(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &stream_id,
+            &topic_id,
+        )
+        .await?;
     let consumer_groups = mapper::map_consumer_groups(&consumer_groups).await;
     Ok(Json(consumer_groups))
 }
@@ -74,23 +79,22 @@ async fn create_consumer_group(
     command.stream_id = Identifier::from_str_value(&stream_id)?;
     command.topic_id = Identifier::from_str_value(&topic_id)?;
     command.validate()?;
-    let consumer_group_details;
-    {
-        let mut system = state.system.write().await;
-        let consumer_group = system
-            .create_consumer_group(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &command.stream_id,
-                &command.topic_id,
-                command.group_id,
-                &command.name,
-            )
-            .await?;
-        let consumer_group = consumer_group.read().await;
-        consumer_group_details = mapper::map_consumer_group(&consumer_group).await;
-    }
-
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    let consumer_group = system
+        .create_consumer_group(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.stream_id,
+            &command.topic_id,
+            command.group_id,
+            &command.name,
+        )
+        .await?;
+    let consumer_group = consumer_group.read().await;
+    let consumer_group_details = mapper::map_consumer_group(&consumer_group).await;
+    // The assigned ID is stored in the state entry, as the consumer groups might be created concurrently.
+    command.group_id = Some(consumer_group.group_id);
+    drop(consumer_group);
     system
         .state
         .apply(identity.user_id, EntryCommand::CreateConsumerGroup(command))
@@ -107,19 +111,16 @@ async fn delete_consumer_group(
     let stream_id = Identifier::from_str_value(&stream_id)?;
     let topic_id = Identifier::from_str_value(&topic_id)?;
     let group_id = Identifier::from_str_value(&group_id)?;
-    {
-        let mut system = state.system.write().await;
-        system
-            .delete_consumer_group(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &stream_id,
-                &topic_id,
-                &group_id,
-            )
-            .await?;
-    }
-
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .delete_consumer_group(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &stream_id,
+            &topic_id,
+            &group_id,
+        )
+        .await?;
     system
         .state
         .apply(
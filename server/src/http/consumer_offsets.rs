@@ -7,9 +7,11 @@ use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::routing::get;
 use axum::{Extension, Json, Router};
+use iggy::consumer_offsets::get_consumer_lag::GetConsumerLag;
 use iggy::consumer_offsets::get_consumer_offset::GetConsumerOffset;
 use iggy::consumer_offsets::store_consumer_offset::StoreConsumerOffset;
 use iggy::identifier::Identifier;
+use iggy::models::consumer_lag_info::ConsumerLagInfo;
 use iggy::models::consumer_offset_info::ConsumerOffsetInfo;
 use iggy::validatable::Validatable;
 use std::sync::Arc;
@@ -20,6 +22,10 @@ pub fn router(state: Arc<AppState>) -> Router {
             "/streams/:stream_id/topics/:topic_id/consumer-offsets",
             get(get_consumer_offset).put(store_consumer_offset),
         )
+        .route(
+            "/streams/:stream_id/topics/:topic_id/consumer-lag",
+            get(get_consumer_lag),
+        )
         .with_state(state)
 }
 
@@ -46,6 +52,28 @@ async fn get_consumer_offset(
     Ok(Json(offset))
 }
 
+async fn get_consumer_lag(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path((stream_id, topic_id)): Path<(String, String)>,
+    mut query: Query<GetConsumerLag>,
+) -> Result<Json<Vec<ConsumerLagInfo>>, CustomError> {
+    query.stream_id = Identifier::from_str_value(&stream_id)?;
+    query.topic_id = Identifier::from_str_value(&topic_id)?;
+    query.validate()?;
+    let system = state.system.read().await;
+    let lags = system
+        .get_consumer_lag(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &query.consumer,
+            &query.stream_id,
+            &query.topic_id,
+            query.partition_id,
+        )
+        .await?;
+    Ok(Json(lags))
+}
+
 async fn store_consumer_offset(
     State(state): State<Arc<AppState>>,
     Extension(identity): Extension<Identity>,
@@ -1,15 +1,17 @@
 use crate::http::shared::RequestDetails;
+use crate::log::telemetry;
 use crate::streaming::utils::random_id;
 use axum::body::Body;
 use axum::{
-    extract::ConnectInfo,
+    extract::{ConnectInfo, MatchedPath},
     http::{Request, StatusCode},
     middleware::Next,
     response::Response,
 };
+use iggy::messages::trace_context::{TraceParent, TRACEPARENT_HEADER};
 use std::net::SocketAddr;
 use tokio::time::Instant;
-use tracing::{debug, error};
+use tracing::{debug, error, field, info_span, Instrument};
 
 pub async fn request_diagnostics(
     ConnectInfo(ip_address): ConnectInfo<SocketAddr>,
@@ -31,10 +33,37 @@ pub async fn request_diagnostics(
         request_id,
         ip_address,
     });
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str())
+        .unwrap_or_else(|| request.uri().path());
+    let span = info_span!(
+        "http_request",
+        otel.name = format!("{} {route}", request.method()),
+        otel.kind = "server",
+        otel.status_code = field::Empty,
+        http.request.method = %request.method(),
+        http.response.status_code = field::Empty,
+        url.path = request.uri().path(),
+        request_id = %request_id,
+    );
+    if let Some(trace_parent) = request
+        .headers()
+        .get(TRACEPARENT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<TraceParent>().ok())
+    {
+        telemetry::set_parent(&span, &trace_parent);
+    }
     let now = Instant::now();
-    let result = Ok(next.run(request).await);
+    let result = Ok(next.run(request).instrument(span.clone()).await);
     if let Ok(response) = &result {
         let status = response.status();
+        span.record("http.response.status_code", status.as_u16());
+        if status >= StatusCode::INTERNAL_SERVER_ERROR {
+            span.record("otel.status_code", "ERROR");
+        }
         if status >= StatusCode::BAD_REQUEST {
             error!("Returning an invalid status code: {status}, IP address: {ip_address}, request ID: {request_id}");
         }
@@ -31,6 +31,7 @@ impl IntoResponse for CustomError {
                     IggyError::PartitionNotFound(_, _, _) => StatusCode::NOT_FOUND,
                     IggyError::SegmentNotFound => StatusCode::NOT_FOUND,
                     IggyError::ClientNotFound(_) => StatusCode::NOT_FOUND,
+                    IggyError::BackupNotFound(_) => StatusCode::NOT_FOUND,
                     IggyError::ConsumerGroupIdNotFound(_, _) => StatusCode::NOT_FOUND,
                     IggyError::ConsumerGroupNameNotFound(_, _) => StatusCode::NOT_FOUND,
                     IggyError::ConsumerGroupMemberNotFound(_, _, _) => StatusCode::NOT_FOUND,
@@ -43,6 +44,7 @@ impl IntoResponse for CustomError {
                     IggyError::CannotParseUtf8(_) => StatusCode::INTERNAL_SERVER_ERROR,
                     IggyError::Unauthenticated => StatusCode::UNAUTHORIZED,
                     IggyError::Unauthorized => StatusCode::FORBIDDEN,
+                    IggyError::TooManyLoginAttempts => StatusCode::TOO_MANY_REQUESTS,
                     _ => StatusCode::BAD_REQUEST,
                 };
                 (status_code, Json(ErrorResponse::from_error(error)))
@@ -64,6 +66,8 @@ impl ErrorResponse {
                 IggyError::PartitionNotFound(_, _, _) => Some("partition_id".to_string()),
                 IggyError::SegmentNotFound => Some("segment_id".to_string()),
                 IggyError::ClientNotFound(_) => Some("client_id".to_string()),
+                IggyError::InvalidBackupName => Some("name".to_string()),
+                IggyError::BackupAlreadyExists(_) => Some("name".to_string()),
                 IggyError::InvalidStreamName => Some("name".to_string()),
                 IggyError::StreamNameAlreadyExists(_) => Some("name".to_string()),
                 IggyError::InvalidTopicName => Some("name".to_string()),
@@ -80,6 +84,8 @@ impl ErrorResponse {
                 IggyError::ConsumerGroupNameAlreadyExists(_, _) => Some("name".to_string()),
                 IggyError::UserAlreadyExists => Some("username".to_string()),
                 IggyError::PersonalAccessTokenAlreadyExists(_, _) => Some("name".to_string()),
+                IggyError::InvalidRoleName => Some("name".to_string()),
+                IggyError::RoleAlreadyExists => Some("name".to_string()),
                 _ => None,
             },
         }
@@ -1,4 +1,5 @@
 use crate::configs::http::{HttpConfig, HttpCorsConfig};
+use crate::http::audit::audit;
 use crate::http::diagnostics::request_diagnostics;
 use crate::http::jwt::cleaner::start_expired_tokens_cleaner;
 use crate::http::jwt::jwt_manager::JwtManager;
@@ -31,6 +32,7 @@ pub async fn start(config: HttpConfig, system: SharedSystem) -> SocketAddr {
         .merge(system::router(app_state.clone(), &config.metrics))
         .merge(personal_access_tokens::router(app_state.clone()))
         .merge(users::router(app_state.clone()))
+        .merge(roles::router(app_state.clone()))
         .merge(streams::router(app_state.clone()))
         .merge(topics::router(app_state.clone()))
         .merge(consumer_groups::router(app_state.clone()))
@@ -40,7 +42,8 @@ pub async fn start(config: HttpConfig, system: SharedSystem) -> SocketAddr {
         .layer(DefaultBodyLimit::max(
             config.max_request_size.as_bytes_u64() as usize,
         ))
-        .layer(middleware::from_fn_with_state(app_state.clone(), jwt_auth));
+        .layer(middleware::from_fn_with_state(app_state.clone(), jwt_auth))
+        .layer(middleware::from_fn_with_state(app_state.clone(), audit));
 
     if config.cors.enabled {
         app = app.layer(configure_cors(config.cors));
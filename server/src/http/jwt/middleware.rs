@@ -63,6 +63,9 @@ pub async fn jwt_auth(
         user_id: jwt_claims.claims.sub,
         ip_address: request_details.ip_address,
     };
-    request.extensions_mut().insert(identity);
-    Ok(next.run(request).await)
+    request.extensions_mut().insert(identity.clone());
+    let mut response = next.run(request).await;
+    // Exposed to the outer middlewares (e.g. audit) which run before the authentication.
+    response.extensions_mut().insert(identity);
+    Ok(response)
 }
@@ -4,6 +4,7 @@ use crate::streaming::personal_access_tokens::personal_access_token::PersonalAcc
 use crate::streaming::streams::stream::Stream;
 use crate::streaming::topics::consumer_group::ConsumerGroup;
 use crate::streaming::topics::topic::Topic;
+use crate::streaming::users::role::Role;
 use crate::streaming::users::user::User;
 use iggy::locking::IggySharedMut;
 use iggy::locking::IggySharedMutFn;
@@ -11,10 +12,10 @@ use iggy::models::client_info::ConsumerGroupInfo;
 use iggy::models::consumer_group::{ConsumerGroupDetails, ConsumerGroupMember};
 use iggy::models::identity_info::{IdentityInfo, TokenInfo};
 use iggy::models::personal_access_token::PersonalAccessTokenInfo;
+use iggy::models::role_info::{RoleInfo, RoleInfoDetails};
 use iggy::models::stream::StreamDetails;
 use iggy::models::topic::TopicDetails;
 use iggy::models::user_info::{UserInfo, UserInfoDetails};
-use tokio::sync::RwLock;
 
 pub async fn map_stream(stream: &Stream) -> StreamDetails {
     let topics = map_topics(&stream.get_topics()).await;
@@ -31,9 +32,10 @@ pub async fn map_stream(stream: &Stream) -> StreamDetails {
     stream_details
 }
 
-pub async fn map_streams(streams: &[&Stream]) -> Vec<iggy::models::stream::Stream> {
+pub async fn map_streams(streams: &[IggySharedMut<Stream>]) -> Vec<iggy::models::stream::Stream> {
     let mut streams_data = Vec::with_capacity(streams.len());
     for stream in streams {
+        let stream = stream.read().await;
         let stream = iggy::models::stream::Stream {
             id: stream.stream_id,
             created_at: stream.created_at,
@@ -49,9 +51,10 @@ pub async fn map_streams(streams: &[&Stream]) -> Vec<iggy::models::stream::Strea
     streams_data
 }
 
-pub async fn map_topics(topics: &[&Topic]) -> Vec<iggy::models::topic::Topic> {
+pub async fn map_topics(topics: &[IggySharedMut<Topic>]) -> Vec<iggy::models::topic::Topic> {
     let mut topics_data = Vec::with_capacity(topics.len());
     for topic in topics {
+        let topic = topic.read().await;
         let topic = iggy::models::topic::Topic {
             id: topic.topic_id,
             created_at: topic.created_at,
@@ -106,8 +109,9 @@ pub fn map_user(user: &User) -> UserInfoDetails {
         id: user.id,
         username: user.username.clone(),
         created_at: user.created_at,
-        status: user.status,
+        status: user.get_status(),
         permissions: user.permissions.clone(),
+        roles: user.roles.clone(),
     }
 }
 
@@ -118,7 +122,7 @@ pub fn map_users(users: &[&User]) -> Vec<UserInfo> {
             id: user.id,
             username: user.username.clone(),
             created_at: user.created_at,
-            status: user.status,
+            status: user.get_status(),
         };
         users_data.push(user);
     }
@@ -126,6 +130,29 @@ pub fn map_users(users: &[&User]) -> Vec<UserInfo> {
     users_data
 }
 
+pub fn map_role(role: &Role) -> RoleInfoDetails {
+    RoleInfoDetails {
+        id: role.id,
+        created_at: role.created_at,
+        name: role.name.clone(),
+        permissions: role.permissions.clone(),
+    }
+}
+
+pub fn map_roles(roles: &[&Role]) -> Vec<RoleInfo> {
+    let mut roles_data = Vec::with_capacity(roles.len());
+    for role in roles {
+        let role = RoleInfo {
+            id: role.id,
+            created_at: role.created_at,
+            name: role.name.clone(),
+        };
+        roles_data.push(role);
+    }
+    roles_data.sort_by(|a, b| a.id.cmp(&b.id));
+    roles_data
+}
+
 pub fn map_personal_access_tokens(
     personal_access_tokens: &[&PersonalAccessToken],
 ) -> Vec<PersonalAccessTokenInfo> {
@@ -182,7 +209,7 @@ pub async fn map_clients(
 }
 
 pub async fn map_consumer_groups(
-    consumer_groups: &[&RwLock<ConsumerGroup>],
+    consumer_groups: &[IggySharedMut<ConsumerGroup>],
 ) -> Vec<iggy::models::consumer_group::ConsumerGroup> {
     let mut groups = Vec::new();
     for consumer_group in consumer_groups {
@@ -1,6 +1,7 @@
 use crate::http::error::CustomError;
 use crate::http::jwt::json_web_token::Identity;
 use crate::http::shared::AppState;
+use crate::streaming::diagnostics::metrics::HTTP_TRANSPORT;
 use crate::streaming::polling_consumer::PollingConsumer;
 use crate::streaming::session::Session;
 use crate::streaming::systems::messages::PollingArgs;
@@ -15,6 +16,7 @@ use iggy::messages::send_messages::SendMessages;
 use iggy::models::messages::PolledMessages;
 use iggy::validatable::Validatable;
 use std::sync::Arc;
+use std::time::Instant;
 
 pub fn router(state: Arc<AppState>) -> Router {
     Router::new()
@@ -38,6 +40,7 @@ async fn poll_messages(
     let partition_id = query.partition_id.unwrap_or(0);
     let consumer_id = PollingConsumer::resolve_consumer_id(&query.consumer.id);
     let consumer = PollingConsumer::Consumer(consumer_id, partition_id);
+    let started_at = Instant::now();
     let system = state.system.read().await;
     let polled_messages = system
         .poll_messages(
@@ -47,8 +50,11 @@ async fn poll_messages(
             &query.topic_id,
             PollingArgs::new(query.strategy, query.count, query.auto_commit),
         )
-        .await?;
-    Ok(Json(polled_messages))
+        .await;
+    system
+        .metrics
+        .observe_poll_messages_latency(HTTP_TRANSPORT, started_at.elapsed());
+    Ok(Json(polled_messages?))
 }
 
 async fn send_messages(
@@ -71,8 +77,9 @@ async fn send_messages(
     let stream_id = command.stream_id;
     let topic_id = command.topic_id;
     let partitioning = command.partitioning;
+    let started_at = Instant::now();
     let system = state.system.read().await;
-    system
+    let result = system
         .append_messages(
             &Session::stateless(identity.user_id, identity.ip_address),
             stream_id,
@@ -80,6 +87,10 @@ async fn send_messages(
             partitioning,
             messages,
         )
-        .await?;
+        .await;
+    system
+        .metrics
+        .observe_send_messages_latency(HTTP_TRANSPORT, started_at.elapsed());
+    result?;
     Ok(StatusCode::CREATED)
 }
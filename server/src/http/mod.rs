@@ -1,3 +1,4 @@
+pub mod audit;
 pub mod consumer_groups;
 pub mod consumer_offsets;
 pub mod diagnostics;
@@ -9,6 +10,7 @@ pub mod messages;
 pub mod metrics;
 pub mod partitions;
 pub mod personal_access_tokens;
+pub mod roles;
 mod shared;
 pub mod streams;
 pub mod system;
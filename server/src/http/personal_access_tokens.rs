@@ -41,10 +41,12 @@ async fn get_personal_access_tokens(
     Extension(identity): Extension<Identity>,
 ) -> Result<Json<Vec<PersonalAccessTokenInfo>>, CustomError> {
     let system = state.system.read().await;
-    let personal_access_tokens = system
+    let user = system
         .get_personal_access_tokens(&Session::stateless(identity.user_id, identity.ip_address))
         .await?;
-    let personal_access_tokens = mapper::map_personal_access_tokens(&personal_access_tokens);
+    let personal_access_tokens = mapper::map_personal_access_tokens(
+        &user.personal_access_tokens.values().collect::<Vec<_>>(),
+    );
     Ok(Json(personal_access_tokens))
 }
 
@@ -54,19 +56,15 @@ async fn create_personal_access_token(
     Json(command): Json<CreatePersonalAccessToken>,
 ) -> Result<Json<RawPersonalAccessToken>, CustomError> {
     command.validate()?;
-    let token;
-    {
-        let mut system = state.system.write().await;
-        token = system
-            .create_personal_access_token(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &command.name,
-                command.expiry,
-            )
-            .await?;
-    }
-
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    let token = system
+        .create_personal_access_token(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.name,
+            command.expiry,
+        )
+        .await?;
     let token_hash = PersonalAccessToken::hash_token(&token);
     system
         .state
@@ -86,17 +84,14 @@ async fn delete_personal_access_token(
     Extension(identity): Extension<Identity>,
     Path(name): Path<String>,
 ) -> Result<StatusCode, CustomError> {
-    {
-        let mut system = state.system.write().await;
-        system
-            .delete_personal_access_token(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &name,
-            )
-            .await?;
-    }
-
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .delete_personal_access_token(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &name,
+        )
+        .await?;
     system
         .state
         .apply(
@@ -113,9 +108,9 @@ async fn login_with_personal_access_token(
 ) -> Result<Json<IdentityInfo>, CustomError> {
     command.validate()?;
     let system = state.system.read().await;
-    let user = system
+    let user_id = system
         .login_with_personal_access_token(&command.token, None)
         .await?;
-    let tokens = state.jwt_manager.generate(user.id)?;
+    let tokens = state.jwt_manager.generate(user_id)?;
     Ok(Json(map_generated_access_token_to_identity_info(tokens)))
 }
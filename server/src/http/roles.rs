@@ -0,0 +1,123 @@
+use crate::http::error::CustomError;
+use crate::http::jwt::json_web_token::Identity;
+use crate::http::mapper;
+use crate::http::shared::AppState;
+use crate::state::command::EntryCommand;
+use crate::streaming::session::Session;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use iggy::identifier::Identifier;
+use iggy::models::role_info::{RoleInfo, RoleInfoDetails};
+use iggy::roles::create_role::CreateRole;
+use iggy::roles::delete_role::DeleteRole;
+use iggy::roles::update_role::UpdateRole;
+use iggy::validatable::Validatable;
+use std::sync::Arc;
+
+pub fn router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/roles", get(get_roles).post(create_role))
+        .route(
+            "/roles/:role_id",
+            get(get_role).put(update_role).delete(delete_role),
+        )
+        .with_state(state)
+}
+
+async fn get_role(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path(role_id): Path<String>,
+) -> Result<Json<RoleInfoDetails>, CustomError> {
+    let role_id = Identifier::from_str_value(&role_id)?;
+    let system = state.system.read().await;
+    let role = system
+        .find_role(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &role_id,
+        )
+        .await?;
+    let role = mapper::map_role(&role);
+    Ok(Json(role))
+}
+
+async fn get_roles(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Json<Vec<RoleInfo>>, CustomError> {
+    let system = state.system.read().await;
+    let roles = system
+        .get_roles(&Session::stateless(identity.user_id, identity.ip_address))
+        .await?;
+    let roles = mapper::map_roles(&roles.values().collect::<Vec<_>>());
+    Ok(Json(roles))
+}
+
+async fn create_role(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Json(command): Json<CreateRole>,
+) -> Result<StatusCode, CustomError> {
+    command.validate()?;
+    let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .create_role(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.name,
+            command.permissions,
+        )
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn update_role(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path(role_id): Path<String>,
+    Json(mut command): Json<UpdateRole>,
+) -> Result<StatusCode, CustomError> {
+    command.role_id = Identifier::from_str_value(&role_id)?;
+    command.validate()?;
+    let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .update_role(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.role_id,
+            command.name.clone(),
+            command.permissions.clone(),
+        )
+        .await?;
+    system
+        .state
+        .apply(identity.user_id, EntryCommand::UpdateRole(command))
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn delete_role(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path(role_id): Path<String>,
+) -> Result<StatusCode, CustomError> {
+    let role_id = Identifier::from_str_value(&role_id)?;
+    let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .delete_role(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &role_id,
+        )
+        .await?;
+    system
+        .state
+        .apply(
+            identity.user_id,
+            EntryCommand::DeleteRole(DeleteRole { role_id }),
+        )
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -8,6 +8,7 @@ use axum::http::StatusCode;
 use axum::routing::{delete, get};
 use axum::{Extension, Json, Router};
 use iggy::identifier::Identifier;
+use iggy::locking::IggySharedMutFn;
 use iggy::models::stream::{Stream, StreamDetails};
 use iggy::streams::create_stream::CreateStream;
 use iggy::streams::delete_stream::DeleteStream;
@@ -40,7 +41,8 @@ async fn get_stream(
         &Session::stateless(identity.user_id, identity.ip_address),
         &stream_id,
     )?;
-    let stream = mapper::map_stream(stream).await;
+    let stream = stream.read().await;
+    let stream = mapper::map_stream(&stream).await;
     Ok(Json(stream))
 }
 
@@ -61,21 +63,25 @@ async fn create_stream(
     Json(command): Json<CreateStream>,
 ) -> Result<StatusCode, CustomError> {
     command.validate()?;
-    {
-        let mut system = state.system.write().await;
-        system
-            .create_stream(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                command.stream_id,
-                &command.name,
-            )
-            .await?;
-    }
-
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    let stream_id = system
+        .create_stream(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            command.stream_id,
+            &command.name,
+        )
+        .await?;
+    // The assigned ID is stored in the state entry, as the streams might be created concurrently.
     system
         .state
-        .apply(identity.user_id, EntryCommand::CreateStream(command))
+        .apply(
+            identity.user_id,
+            EntryCommand::CreateStream(CreateStream {
+                stream_id: Some(stream_id),
+                name: command.name,
+            }),
+        )
         .await?;
     Ok(StatusCode::CREATED)
 }
@@ -88,18 +94,15 @@ async fn update_stream(
 ) -> Result<StatusCode, CustomError> {
     command.stream_id = Identifier::from_str_value(&stream_id)?;
     command.validate()?;
-    {
-        let mut system = state.system.write().await;
-        system
-            .update_stream(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &command.stream_id,
-                &command.name,
-            )
-            .await?;
-    }
-
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .update_stream(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.stream_id,
+            &command.name,
+        )
+        .await?;
     system
         .state
         .apply(identity.user_id, EntryCommand::UpdateStream(command))
@@ -113,17 +116,14 @@ async fn delete_stream(
     Path(stream_id): Path<String>,
 ) -> Result<StatusCode, CustomError> {
     let stream_id = Identifier::from_str_value(&stream_id)?;
-    {
-        let mut system = state.system.write().await;
-        system
-            .delete_stream(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &stream_id,
-            )
-            .await?;
-    }
-
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .delete_stream(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &stream_id,
+        )
+        .await?;
     system
         .state
         .apply(
@@ -141,6 +141,7 @@ async fn purge_stream(
 ) -> Result<StatusCode, CustomError> {
     let stream_id = Identifier::from_str_value(&stream_id)?;
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
     system
         .purge_stream(
             &Session::stateless(identity.user_id, identity.ip_address),
@@ -4,12 +4,17 @@ use crate::http::jwt::json_web_token::Identity;
 use crate::http::mapper;
 use crate::http::shared::AppState;
 use crate::streaming::session::Session;
-use axum::extract::{Path, State};
-use axum::routing::get;
+use axum::extract::{Path, Query, State};
+use axum::routing::{get, post};
 use axum::{Extension, Json, Router};
 use iggy::locking::IggySharedMutFn;
+use iggy::models::audit_log::AuditLogEntry;
+use iggy::models::backup_info::BackupInfo;
 use iggy::models::client_info::{ClientInfo, ClientInfoDetails};
 use iggy::models::stats::Stats;
+use iggy::system::create_backup::CreateBackup;
+use iggy::system::get_audit_log::GetAuditLog;
+use iggy::validatable::Validatable;
 use std::sync::Arc;
 
 const NAME: &str = "Iggy HTTP";
@@ -21,7 +26,9 @@ pub fn router(state: Arc<AppState>, metrics_config: &HttpMetricsConfig) -> Route
         .route("/ping", get(|| async { PONG }))
         .route("/stats", get(get_stats))
         .route("/clients", get(get_clients))
-        .route("/clients/:client_id", get(get_client));
+        .route("/clients/:client_id", get(get_client))
+        .route("/audit-log", get(get_audit_log))
+        .route("/backups", post(create_backup));
     if metrics_config.enabled {
         router = router.route(&metrics_config.endpoint, get(get_metrics));
     }
@@ -31,7 +38,7 @@ pub fn router(state: Arc<AppState>, metrics_config: &HttpMetricsConfig) -> Route
 
 async fn get_metrics(State(state): State<Arc<AppState>>) -> Result<String, CustomError> {
     let system = state.system.read().await;
-    Ok(system.metrics.get_formatted_output())
+    Ok(system.get_metrics().await)
 }
 
 async fn get_stats(
@@ -73,3 +80,40 @@ async fn get_clients(
     let clients = mapper::map_clients(&clients).await;
     Ok(Json(clients))
 }
+
+async fn get_audit_log(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    query: Query<GetAuditLog>,
+) -> Result<Json<Vec<AuditLogEntry>>, CustomError> {
+    query.validate()?;
+    let system = state.system.read().await;
+    let entries = system
+        .get_audit_log(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            query.count,
+            query.user_id,
+        )
+        .await?;
+    Ok(Json(entries))
+}
+
+async fn create_backup(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Json(command): Json<CreateBackup>,
+) -> Result<Json<BackupInfo>, CustomError> {
+    command.validate()?;
+    let backup = state
+        .system
+        .write()
+        .await
+        .prepare_backup(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            command.name.as_deref(),
+            command.archive,
+        )
+        .await?;
+    let backup = backup.complete().await?;
+    Ok(Json(backup))
+}
@@ -9,6 +9,7 @@ use axum::http::StatusCode;
 use axum::routing::{delete, get};
 use axum::{Extension, Json, Router};
 use iggy::identifier::Identifier;
+use iggy::locking::IggySharedMutFn;
 use iggy::models::topic::{Topic, TopicDetails};
 use iggy::topics::create_topic::CreateTopic;
 use iggy::topics::delete_topic::DeleteTopic;
@@ -42,12 +43,15 @@ async fn get_topic(
     let system = state.system.read().await;
     let stream_id = Identifier::from_str_value(&stream_id)?;
     let topic_id = Identifier::from_str_value(&topic_id)?;
-    let topic = system.find_topic(
-        &Session::stateless(identity.user_id, identity.ip_address),
-        &stream_id,
-        &topic_id,
-    )?;
-    let topic = mapper::map_topic(topic).await;
+    let topic = system
+        .find_topic(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &stream_id,
+            &topic_id,
+        )
+        .await?;
+    let topic = topic.read().await;
+    let topic = mapper::map_topic(&topic).await;
     Ok(Json(topic))
 }
 
@@ -58,10 +62,12 @@ async fn get_topics(
 ) -> Result<Json<Vec<Topic>>, CustomError> {
     let stream_id = Identifier::from_str_value(&stream_id)?;
     let system = state.system.read().await;
-    let topics = system.find_topics(
-        &Session::stateless(identity.user_id, identity.ip_address),
-        &stream_id,
-    )?;
+    let topics = system
+        .find_topics(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &stream_id,
+        )
+        .await?;
     let topics = mapper::map_topics(&topics).await;
     Ok(Json(topics))
 }
@@ -74,24 +80,23 @@ async fn create_topic(
 ) -> Result<StatusCode, CustomError> {
     command.stream_id = Identifier::from_str_value(&stream_id)?;
     command.validate()?;
-    {
-        let mut system = state.system.write().await;
-        system
-            .create_topic(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &command.stream_id,
-                command.topic_id,
-                &command.name,
-                command.partitions_count,
-                command.message_expiry,
-                command.compression_algorithm,
-                command.max_topic_size,
-                command.replication_factor,
-            )
-            .await?;
-    }
-
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    let topic_id = system
+        .create_topic(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.stream_id,
+            command.topic_id,
+            &command.name,
+            command.partitions_count,
+            command.message_expiry,
+            command.compression_algorithm,
+            command.max_topic_size,
+            command.replication_factor,
+        )
+        .await?;
+    // The assigned ID is stored in the state entry, as the topics might be created concurrently.
+    command.topic_id = Some(topic_id);
     system
         .state
         .apply(identity.user_id, EntryCommand::CreateTopic(command))
@@ -108,23 +113,20 @@ async fn update_topic(
     command.stream_id = Identifier::from_str_value(&stream_id)?;
     command.topic_id = Identifier::from_str_value(&topic_id)?;
     command.validate()?;
-    {
-        let mut system = state.system.write().await;
-        system
-            .update_topic(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &command.stream_id,
-                &command.topic_id,
-                &command.name,
-                command.message_expiry,
-                command.compression_algorithm,
-                command.max_topic_size,
-                command.replication_factor,
-            )
-            .await?;
-    }
-
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .update_topic(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.stream_id,
+            &command.topic_id,
+            &command.name,
+            command.message_expiry,
+            command.compression_algorithm,
+            command.max_topic_size,
+            command.replication_factor,
+        )
+        .await?;
     system
         .state
         .apply(identity.user_id, EntryCommand::UpdateTopic(command))
@@ -139,18 +141,15 @@ async fn delete_topic(
 ) -> Result<StatusCode, CustomError> {
     let stream_id = Identifier::from_str_value(&stream_id)?;
     let topic_id = Identifier::from_str_value(&topic_id)?;
-    {
-        let mut system = state.system.write().await;
-        system
-            .delete_topic(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &stream_id,
-                &topic_id,
-            )
-            .await?;
-    }
-
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .delete_topic(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &stream_id,
+            &topic_id,
+        )
+        .await?;
     system
         .state
         .apply(
@@ -172,6 +171,7 @@ async fn purge_topic(
     let stream_id = Identifier::from_str_value(&stream_id)?;
     let topic_id = Identifier::from_str_value(&topic_id)?;
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
     system
         .purge_topic(
             &Session::stateless(identity.user_id, identity.ip_address),
@@ -2,10 +2,9 @@ use crate::http::error::CustomError;
 use crate::http::jwt::json_web_token::Identity;
 use crate::http::mapper;
 use crate::http::mapper::map_generated_access_token_to_identity_info;
-use crate::http::shared::AppState;
+use crate::http::shared::{AppState, RequestDetails};
 use crate::state::command::EntryCommand;
 use crate::streaming::session::Session;
-use crate::streaming::utils::crypto;
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use axum::routing::{delete, get, post, put};
@@ -18,10 +17,13 @@ use iggy::users::create_user::CreateUser;
 use iggy::users::delete_user::DeleteUser;
 use iggy::users::login_user::LoginUser;
 use iggy::users::update_permissions::UpdatePermissions;
+use iggy::users::update_roles::UpdateRoles;
 use iggy::users::update_user::UpdateUser;
 use iggy::validatable::Validatable;
 use serde::Deserialize;
 use std::sync::Arc;
+use tokio::time::sleep;
+use tracing::warn;
 
 pub fn router(state: Arc<AppState>) -> Router {
     Router::new()
@@ -31,6 +33,8 @@ pub fn router(state: Arc<AppState>) -> Router {
             get(get_user).put(update_user).delete(delete_user),
         )
         .route("/users/:user_id/permissions", put(update_permissions))
+        .route("/users/:user_id/roles", put(update_roles))
+        .route("/users/:user_id/unlock", put(unlock_user))
         .route("/users/:user_id/password", put(change_password))
         .route("/users/login", post(login_user))
         .route("/users/logout", delete(logout_user))
@@ -45,11 +49,13 @@ async fn get_user(
 ) -> Result<Json<UserInfoDetails>, CustomError> {
     let user_id = Identifier::from_str_value(&user_id)?;
     let system = state.system.read().await;
-    let user = system.find_user(
-        &Session::stateless(identity.user_id, identity.ip_address),
-        &user_id,
-    )?;
-    let user = mapper::map_user(user);
+    let user = system
+        .find_user(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &user_id,
+        )
+        .await?;
+    let user = mapper::map_user(&user);
     Ok(Json(user))
 }
 
@@ -61,7 +67,7 @@ async fn get_users(
     let users = system
         .get_users(&Session::stateless(identity.user_id, identity.ip_address))
         .await?;
-    let users = mapper::map_users(&users);
+    let users = mapper::map_users(&users.values().collect::<Vec<_>>());
     Ok(Json(users))
 }
 
@@ -71,31 +77,18 @@ async fn create_user(
     Json(command): Json<CreateUser>,
 ) -> Result<StatusCode, CustomError> {
     command.validate()?;
-    {
-        let mut system = state.system.write().await;
-        system
-            .create_user(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &command.username,
-                &command.password,
-                command.status,
-                command.permissions.clone(),
-            )
-            .await?;
-    }
-
-    // For the security of the system, we hash the password before storing it in metadata.
+    let session = Session::stateless(identity.user_id, identity.ip_address);
     let system = state.system.read().await;
+    // For the security of the system, we hash the password before storing it in metadata.
+    let password_hash = system.hash_new_password(&session, &command.password)?;
+    let _state_changes = system.state_changes.lock().await;
     system
-        .state
-        .apply(
-            identity.user_id,
-            EntryCommand::CreateUser(CreateUser {
-                username: command.username,
-                password: crypto::hash_password(&command.password),
-                status: command.status,
-                permissions: command.permissions,
-            }),
+        .create_user(
+            &session,
+            &command.username,
+            &password_hash,
+            command.status,
+            command.permissions,
         )
         .await?;
     Ok(StatusCode::NO_CONTENT)
@@ -109,19 +102,16 @@ async fn update_user(
 ) -> Result<StatusCode, CustomError> {
     command.user_id = Identifier::from_str_value(&user_id)?;
     command.validate()?;
-    {
-        let mut system = state.system.write().await;
-        system
-            .update_user(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &command.user_id,
-                command.username.clone(),
-                command.status,
-            )
-            .await?;
-    }
-
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .update_user(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.user_id,
+            command.username.clone(),
+            command.status,
+        )
+        .await?;
     system
         .state
         .apply(identity.user_id, EntryCommand::UpdateUser(command))
@@ -137,18 +127,15 @@ async fn update_permissions(
 ) -> Result<StatusCode, CustomError> {
     command.user_id = Identifier::from_str_value(&user_id)?;
     command.validate()?;
-    {
-        let mut system = state.system.write().await;
-        system
-            .update_permissions(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &command.user_id,
-                command.permissions.clone(),
-            )
-            .await?;
-    }
-
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .update_permissions(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.user_id,
+            command.permissions.clone(),
+        )
+        .await?;
     system
         .state
         .apply(identity.user_id, EntryCommand::UpdatePermissions(command))
@@ -156,6 +143,30 @@ async fn update_permissions(
     Ok(StatusCode::NO_CONTENT)
 }
 
+async fn update_roles(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path(user_id): Path<String>,
+    Json(mut command): Json<UpdateRoles>,
+) -> Result<StatusCode, CustomError> {
+    command.user_id = Identifier::from_str_value(&user_id)?;
+    command.validate()?;
+    let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .update_roles(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &command.user_id,
+            command.roles.clone(),
+        )
+        .await?;
+    system
+        .state
+        .apply(identity.user_id, EntryCommand::UpdateRoles(command))
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 async fn change_password(
     State(state): State<Arc<AppState>>,
     Extension(identity): Extension<Identity>,
@@ -164,20 +175,19 @@ async fn change_password(
 ) -> Result<StatusCode, CustomError> {
     command.user_id = Identifier::from_str_value(&user_id)?;
     command.validate()?;
-    {
-        let mut system = state.system.write().await;
-        system
-            .change_password(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &command.user_id,
-                &command.current_password,
-                &command.new_password,
-            )
-            .await?;
-    }
-
-    // For the security of the system, we hash the password before storing it in metadata.
+    let session = Session::stateless(identity.user_id, identity.ip_address);
     let system = state.system.read().await;
+    // For the security of the system, we hash the password before storing it in metadata.
+    let password_change = system
+        .prepare_password_change(
+            &session,
+            &command.user_id,
+            &command.current_password,
+            &command.new_password,
+        )
+        .await?;
+    let _state_changes = system.state_changes.lock().await;
+    system.change_password(&session, &password_change).await?;
     system
         .state
         .apply(
@@ -185,7 +195,7 @@ async fn change_password(
             EntryCommand::ChangePassword(ChangePassword {
                 user_id: command.user_id,
                 current_password: "".into(),
-                new_password: crypto::hash_password(&command.new_password),
+                new_password: password_change.new_password_hash,
             }),
         )
         .await?;
@@ -198,17 +208,14 @@ async fn delete_user(
     Path(user_id): Path<String>,
 ) -> Result<StatusCode, CustomError> {
     let user_id = Identifier::from_str_value(&user_id)?;
-    {
-        let mut system = state.system.write().await;
-        system
-            .delete_user(
-                &Session::stateless(identity.user_id, identity.ip_address),
-                &user_id,
-            )
-            .await?;
-    }
-
     let system = state.system.read().await;
+    let _state_changes = system.state_changes.lock().await;
+    system
+        .delete_user(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &user_id,
+        )
+        .await?;
     system
         .state
         .apply(
@@ -219,16 +226,56 @@ async fn delete_user(
     Ok(StatusCode::NO_CONTENT)
 }
 
+async fn unlock_user(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<Identity>,
+    Path(user_id): Path<String>,
+) -> Result<StatusCode, CustomError> {
+    let user_id = Identifier::from_str_value(&user_id)?;
+    let system = state.system.read().await;
+    system
+        .unlock_user(
+            &Session::stateless(identity.user_id, identity.ip_address),
+            &user_id,
+        )
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 async fn login_user(
     State(state): State<Arc<AppState>>,
+    Extension(request_details): Extension<RequestDetails>,
     Json(command): Json<LoginUser>,
 ) -> Result<Json<IdentityInfo>, CustomError> {
     command.validate()?;
     let system = state.system.read().await;
-    let user = system
-        .login_user(&command.username, &command.password, None)
-        .await?;
-    let tokens = state.jwt_manager.generate(user.id)?;
+    let user_id = match system
+        .login_user(
+            &command.username,
+            &command.password,
+            request_details.ip_address.ip(),
+            None,
+        )
+        .await
+    {
+        Ok(user_id) => user_id,
+        Err(error) => {
+            let delay = system.get_failed_login_delay(&command.username);
+            drop(system);
+            sleep(delay).await;
+            return Err(error.into());
+        }
+    };
+
+    // The password is rehashed without locking the users, only the new hash is applied exclusively.
+    if let Some(password_rehash) = system.rehash_password(user_id, &command.password).await {
+        let _state_changes = system.state_changes.lock().await;
+        if let Err(error) = system.apply_password_rehash(password_rehash).await {
+            warn!("Cannot rehash password for user with ID: {user_id}, error: {error}");
+        }
+    }
+
+    let tokens = state.jwt_manager.generate(user_id)?;
     Ok(Json(map_generated_access_token_to_identity_info(tokens)))
 }
 
@@ -10,6 +10,7 @@ static GLOBAL: Jemalloc = Jemalloc;
 
 pub mod archiver;
 pub mod args;
+pub mod audit;
 pub mod binary;
 pub mod channels;
 mod command;
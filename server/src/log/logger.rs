@@ -1,4 +1,6 @@
+use crate::configs::server::TelemetryConfig;
 use crate::configs::system::LoggingConfig;
+use crate::log::telemetry::Telemetry;
 use crate::server_error::ServerError;
 use std::io::{self, Write};
 use std::path::PathBuf;
@@ -88,6 +90,8 @@ pub struct Logging {
     filtering_file_reload_handle: Option<ReloadHandle>,
 
     early_logs_buffer: Arc<Mutex<Vec<String>>>,
+
+    telemetry: Telemetry,
 }
 
 impl Logging {
@@ -100,6 +104,7 @@ impl Logging {
             filtering_stdout_reload_handle: None,
             filtering_file_reload_handle: None,
             early_logs_buffer: Arc::new(Mutex::new(vec![])),
+            telemetry: Telemetry::new(),
         }
     }
 
@@ -109,6 +114,7 @@ impl Logging {
         // Second layer will just consume drain log entries and has first layer as a dependency
         // Third layer will write to a safe buffer and has first layer as a dependency
         // All layers will be replaced during late_init
+        // Telemetry layer will start exporting the spans once it's initialized during late_init
         let mut layers = vec![];
 
         let filtering_level = Self::get_filtering_level(None);
@@ -136,7 +142,9 @@ impl Logging {
         self.file_reload_handle = Some(file_layer_reload_handle);
         layers.push(file_layer.and_then(filtering_file_layer));
 
-        let subscriber = tracing_subscriber::registry().with(layers);
+        let subscriber = tracing_subscriber::registry()
+            .with(layers)
+            .with(self.telemetry.layer());
 
         tracing::subscriber::set_global_default(subscriber)
             .expect("Setting global default subscriber failed");
@@ -162,6 +170,7 @@ impl Logging {
         &mut self,
         base_directory: String,
         config: &LoggingConfig,
+        telemetry_config: &TelemetryConfig,
     ) -> Result<(), ServerError> {
         // Write to stdout and file at the same time.
         // Use the non_blocking appender to avoid blocking the threads.
@@ -239,9 +248,14 @@ impl Logging {
             LevelFilter::TRACE => event!(Level::TRACE, "{}", print),
         }
 
+        self.telemetry.init(telemetry_config)?;
         Ok(())
     }
 
+    pub fn shutdown(&self) {
+        self.telemetry.shutdown();
+    }
+
     // RUST_LOG always takes precedence over config
     fn get_filtering_level(config: Option<&LoggingConfig>) -> LevelFilter {
         if let Ok(rust_log) = std::env::var("RUST_LOG") {
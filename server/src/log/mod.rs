@@ -1,5 +1,7 @@
 #[cfg(not(feature = "tokio-console"))]
 pub mod logger;
 
+pub mod telemetry;
+
 #[cfg(feature = "tokio-console")]
 pub mod tokio_console;
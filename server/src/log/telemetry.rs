@@ -0,0 +1,215 @@
+use crate::configs::server::TelemetryConfig;
+use crate::server_error::ServerError;
+use iggy::messages::trace_context::TraceParent;
+use tracing::Span;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+#[cfg(feature = "otel")]
+use {
+    opentelemetry::trace::{
+        SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState, TracerProvider as _,
+    },
+    opentelemetry::KeyValue,
+    opentelemetry_otlp::WithExportConfig,
+    opentelemetry_sdk::trace::{BatchConfigBuilder, Config, Tracer, TracerProvider},
+    opentelemetry_sdk::{runtime, Resource},
+    std::any::TypeId,
+    std::sync::{Arc, OnceLock},
+    tracing::span::{Attributes, Id, Record},
+    tracing::{info, Event, Subscriber},
+    tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt},
+    tracing_subscriber::layer::Context,
+};
+
+#[cfg(feature = "otel")]
+const TRACER_NAME: &str = "iggy-server";
+
+/// `Telemetry` exports the spans using the OTLP/HTTP protocol.
+/// The layer returned by `layer()` is installed during the early logging initialization,
+/// but it doesn't record anything until the exporter is initialized based on the loaded configuration.
+#[derive(Debug, Clone, Default)]
+pub struct Telemetry {
+    #[cfg(feature = "otel")]
+    tracer_provider: Arc<OnceLock<TracerProvider>>,
+}
+
+impl Telemetry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[cfg(feature = "otel")]
+    pub fn layer<S>(&self) -> impl Layer<S>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        TelemetryLayer {
+            tracer_provider: self.tracer_provider.clone(),
+            layer: OnceLock::new(),
+        }
+    }
+
+    #[cfg(not(feature = "otel"))]
+    pub fn layer<S>(&self) -> impl Layer<S>
+    where
+        S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+    {
+        tracing_subscriber::layer::Identity::new()
+    }
+
+    #[cfg(feature = "otel")]
+    pub fn init(&self, config: &TelemetryConfig) -> Result<(), ServerError> {
+        if !config.enabled {
+            return Ok(());
+        }
+
+        let endpoint = format!("{}/v1/traces", config.endpoint.trim_end_matches('/'));
+        let tracer_provider =
+            opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .http()
+                        .with_endpoint(&endpoint),
+                )
+                .with_trace_config(Config::default().with_resource(Resource::new(vec![
+                    KeyValue::new("service.name", config.service_name.clone()),
+                ])))
+                .with_batch_config(
+                    BatchConfigBuilder::default()
+                        .with_scheduled_delay(config.export_interval.get_duration())
+                        .build(),
+                )
+                .install_batch(runtime::Tokio)
+                .map_err(|error| ServerError::CannotInitializeTelemetry(error.to_string()))?;
+        if self.tracer_provider.set(tracer_provider).is_err() {
+            return Err(ServerError::CannotInitializeTelemetry(
+                "Telemetry is already initialized.".into(),
+            ));
+        }
+
+        info!(
+            "Telemetry initialized, spans will be exported to: {endpoint} every: {}.",
+            config.export_interval
+        );
+        Ok(())
+    }
+
+    #[cfg(not(feature = "otel"))]
+    pub fn init(&self, config: &TelemetryConfig) -> Result<(), ServerError> {
+        if config.enabled {
+            tracing::warn!(
+                "Telemetry is enabled, but the server was built without the 'otel' feature, spans will not be exported."
+            );
+        }
+        Ok(())
+    }
+
+    /// Exports the remaining spans, should be called during graceful shutdown.
+    pub fn shutdown(&self) {
+        #[cfg(feature = "otel")]
+        if let Some(tracer_provider) = self.tracer_provider.get() {
+            if let Err(error) = tracer_provider.shutdown() {
+                tracing::error!("Failed to shutdown telemetry. Error: {error}");
+            }
+        }
+    }
+}
+
+/// Sets the trace parent propagated by the client as the parent of the span,
+/// must be called before the span is entered for the first time.
+#[cfg(feature = "otel")]
+pub fn set_parent(span: &Span, trace_parent: &TraceParent) {
+    let span_context = SpanContext::new(
+        TraceId::from(trace_parent.trace_id),
+        SpanId::from(trace_parent.parent_id),
+        TraceFlags::new(trace_parent.flags),
+        true,
+        TraceState::default(),
+    );
+    span.set_parent(opentelemetry::Context::new().with_remote_span_context(span_context));
+}
+
+#[cfg(not(feature = "otel"))]
+pub fn set_parent(_span: &Span, _trace_parent: &TraceParent) {}
+
+/// The layer forwarding to the OpenTelemetry layer once the tracer provider is initialized.
+/// Unlike the reloadable layer, it supports downcasting, which is required for setting the span parent,
+/// as the inner layer can be only set once and is never replaced.
+#[cfg(feature = "otel")]
+struct TelemetryLayer<S> {
+    tracer_provider: Arc<OnceLock<TracerProvider>>,
+    layer: OnceLock<OpenTelemetryLayer<S, Tracer>>,
+}
+
+#[cfg(feature = "otel")]
+impl<S> TelemetryLayer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    fn get_layer(&self) -> Option<&OpenTelemetryLayer<S, Tracer>> {
+        let tracer_provider = self.tracer_provider.get()?;
+        Some(self.layer.get_or_init(|| {
+            tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer(TRACER_NAME))
+        }))
+    }
+}
+
+#[cfg(feature = "otel")]
+impl<S> Layer<S> for TelemetryLayer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if let Some(layer) = self.get_layer() {
+            layer.on_new_span(attrs, id, ctx);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(layer) = self.get_layer() {
+            layer.on_record(id, values, ctx);
+        }
+    }
+
+    fn on_follows_from(&self, id: &Id, follows: &Id, ctx: Context<'_, S>) {
+        if let Some(layer) = self.get_layer() {
+            layer.on_follows_from(id, follows, ctx);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if let Some(layer) = self.get_layer() {
+            layer.on_event(event, ctx);
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(layer) = self.get_layer() {
+            layer.on_enter(id, ctx);
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(layer) = self.get_layer() {
+            layer.on_exit(id, ctx);
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        if let Some(layer) = self.get_layer() {
+            layer.on_close(id, ctx);
+        }
+    }
+
+    // SAFETY: the inner layer is never replaced once set, so the returned pointer is valid
+    // for the lifetime of `&self`.
+    unsafe fn downcast_raw(&self, id: TypeId) -> Option<*const ()> {
+        if id == TypeId::of::<Self>() {
+            return Some(self as *const _ as *const ());
+        }
+
+        self.get_layer()?.downcast_raw(id)
+    }
+}
@@ -1,18 +1,26 @@
+use crate::configs::server::TelemetryConfig;
 use crate::configs::system::LoggingConfig;
+use crate::log::telemetry::Telemetry;
 use crate::server_error::ServerError;
 use tracing_subscriber::prelude::*;
 
-pub struct Logging {}
+pub struct Logging {
+    telemetry: Telemetry,
+}
 
 impl Logging {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            telemetry: Telemetry::new(),
+        }
     }
 
     pub fn early_init(&mut self) {
         let console_layer = console_subscriber::spawn();
 
-        let subscriber = tracing_subscriber::registry().with(console_layer);
+        let subscriber = tracing_subscriber::registry()
+            .with(console_layer)
+            .with(self.telemetry.layer());
 
         tracing::subscriber::set_global_default(subscriber)
             .expect("Setting global default subscriber failed");
@@ -22,8 +30,13 @@ impl Logging {
         &mut self,
         _base_directory: String,
         _config: &LoggingConfig,
+        telemetry_config: &TelemetryConfig,
     ) -> Result<(), ServerError> {
-        Ok(())
+        self.telemetry.init(telemetry_config)
+    }
+
+    pub fn shutdown(&self) {
+        self.telemetry.shutdown();
     }
 }
 
@@ -4,9 +4,13 @@ use figlet_rs::FIGfont;
 use server::args::Args;
 use server::channels::commands::archive_state::ArchiveStateExecutor;
 use server::channels::commands::clean_personal_access_tokens::CleanPersonalAccessTokensExecutor;
+use server::channels::commands::compact_consumer_offsets::CompactConsumerOffsetsExecutor;
+use server::channels::commands::flush_consumer_offsets::FlushConsumerOffsetsExecutor;
 use server::channels::commands::maintain_messages::MaintainMessagesExecutor;
 use server::channels::commands::print_sysinfo::SysInfoPrintExecutor;
+use server::channels::commands::reencrypt_data::ReencryptDataExecutor;
 use server::channels::commands::save_messages::SaveMessagesExecutor;
+use server::channels::commands::snapshot_state::SnapshotStateExecutor;
 use server::channels::handler::ServerCommandHandler;
 use server::configs::config_provider;
 use server::configs::server::ServerConfig;
@@ -39,7 +43,15 @@ async fn main() -> Result<(), ServerError> {
     let config_provider = config_provider::resolve(&args.config_provider)?;
     let config = ServerConfig::load(config_provider.as_ref()).await?;
 
-    logging.late_init(config.system.get_system_path(), &config.system.logging)?;
+    logging.late_init(
+        config.system.get_system_path(),
+        &config.system.logging,
+        &config.telemetry,
+    )?;
+
+    if let Some(backup) = &args.restore_backup {
+        System::restore_backup(&config.system, backup).await?;
+    }
 
     let system = SharedSystem::new(System::new(
         config.system.clone(),
@@ -55,8 +67,12 @@ async fn main() -> Result<(), ServerError> {
 
     let _command_handler = ServerCommandHandler::new(system.clone(), &config)
         .install_handler(SaveMessagesExecutor)
+        .install_handler(FlushConsumerOffsetsExecutor)
+        .install_handler(CompactConsumerOffsetsExecutor)
         .install_handler(MaintainMessagesExecutor)
         .install_handler(ArchiveStateExecutor)
+        .install_handler(ReencryptDataExecutor::default())
+        .install_handler(SnapshotStateExecutor)
         .install_handler(CleanPersonalAccessTokensExecutor)
         .install_handler(SysInfoPrintExecutor);
 
@@ -127,5 +143,6 @@ async fn main() -> Result<(), ServerError> {
         "Iggy server has shutdown successfully. Shutdown took {} ms.",
         elapsed_time.as_millis()
     );
+    logging.shutdown();
     Ok(())
 }
@@ -6,6 +6,7 @@ use crate::command::ServerCommand;
 use crate::quic::quic_sender::QuicSender;
 use crate::server_error::ServerError;
 use crate::streaming::clients::client_manager::Transport;
+use crate::streaming::diagnostics::metrics::QUIC_TRANSPORT;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
 use anyhow::{anyhow, Context};
@@ -135,7 +136,13 @@ async fn handle_stream(
         send: send_stream,
         recv: recv_stream,
     };
-    command::handle(command, &mut sender, session.as_ref(), system.clone())
-        .await
-        .with_context(|| "Error when handling the QUIC request.")
+    command::handle(
+        command,
+        &mut sender,
+        session.as_ref(),
+        system.clone(),
+        QUIC_TRANSPORT,
+    )
+    .await
+    .with_context(|| "Error when handling the QUIC request.")
 }
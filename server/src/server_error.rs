@@ -53,4 +53,6 @@ pub enum ServerError {
     InvalidS3Credentials,
     #[error("File to archive not found: {0}")]
     FileToArchiveNotFound(String),
+    #[error("Cannot initialize telemetry: {0}")]
+    CannotInitializeTelemetry(String),
 }
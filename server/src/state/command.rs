@@ -1,12 +1,14 @@
-use crate::state::models::CreatePersonalAccessTokenWithHash;
+use crate::state::models::{CreatePersonalAccessTokenWithHash, CreateRoleWithId, RehashPassword};
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use iggy::bytes_serializable::BytesSerializable;
 use iggy::command::{
     Command, CHANGE_PASSWORD_CODE, CREATE_CONSUMER_GROUP_CODE, CREATE_PARTITIONS_CODE,
-    CREATE_PERSONAL_ACCESS_TOKEN_CODE, CREATE_STREAM_CODE, CREATE_TOPIC_CODE, CREATE_USER_CODE,
-    DELETE_CONSUMER_GROUP_CODE, DELETE_PARTITIONS_CODE, DELETE_PERSONAL_ACCESS_TOKEN_CODE,
-    DELETE_STREAM_CODE, DELETE_TOPIC_CODE, DELETE_USER_CODE, PURGE_STREAM_CODE, PURGE_TOPIC_CODE,
-    UPDATE_PERMISSIONS_CODE, UPDATE_STREAM_CODE, UPDATE_TOPIC_CODE, UPDATE_USER_CODE,
+    CREATE_PERSONAL_ACCESS_TOKEN_CODE, CREATE_ROLE_CODE, CREATE_STREAM_CODE, CREATE_TOPIC_CODE,
+    CREATE_USER_CODE, DELETE_CONSUMER_GROUP_CODE, DELETE_PARTITIONS_CODE,
+    DELETE_PERSONAL_ACCESS_TOKEN_CODE, DELETE_ROLE_CODE, DELETE_STREAM_CODE, DELETE_TOPIC_CODE,
+    DELETE_USER_CODE, PURGE_STREAM_CODE, PURGE_TOPIC_CODE, REHASH_PASSWORD_CODE,
+    UPDATE_PERMISSIONS_CODE, UPDATE_ROLES_CODE, UPDATE_ROLE_CODE, UPDATE_STREAM_CODE,
+    UPDATE_TOPIC_CODE, UPDATE_USER_CODE,
 };
 use iggy::consumer_groups::create_consumer_group::CreateConsumerGroup;
 use iggy::consumer_groups::delete_consumer_group::DeleteConsumerGroup;
@@ -14,6 +16,8 @@ use iggy::error::IggyError;
 use iggy::partitions::create_partitions::CreatePartitions;
 use iggy::partitions::delete_partitions::DeletePartitions;
 use iggy::personal_access_tokens::delete_personal_access_token::DeletePersonalAccessToken;
+use iggy::roles::delete_role::DeleteRole;
+use iggy::roles::update_role::UpdateRole;
 use iggy::streams::create_stream::CreateStream;
 use iggy::streams::delete_stream::DeleteStream;
 use iggy::streams::purge_stream::PurgeStream;
@@ -26,6 +30,7 @@ use iggy::users::change_password::ChangePassword;
 use iggy::users::create_user::CreateUser;
 use iggy::users::delete_user::DeleteUser;
 use iggy::users::update_permissions::UpdatePermissions;
+use iggy::users::update_roles::UpdateRoles;
 use iggy::users::update_user::UpdateUser;
 use std::fmt::{Display, Formatter};
 
@@ -47,9 +52,14 @@ pub enum EntryCommand {
     UpdateUser(UpdateUser),
     DeleteUser(DeleteUser),
     ChangePassword(ChangePassword),
+    RehashPassword(RehashPassword),
     UpdatePermissions(UpdatePermissions),
     CreatePersonalAccessToken(CreatePersonalAccessTokenWithHash),
     DeletePersonalAccessToken(DeletePersonalAccessToken),
+    CreateRole(CreateRoleWithId),
+    UpdateRole(UpdateRole),
+    DeleteRole(DeleteRole),
+    UpdateRoles(UpdateRoles),
 }
 
 impl BytesSerializable for EntryCommand {
@@ -71,6 +81,7 @@ impl BytesSerializable for EntryCommand {
             EntryCommand::UpdateUser(command) => (command.code(), command.to_bytes()),
             EntryCommand::DeleteUser(command) => (command.code(), command.to_bytes()),
             EntryCommand::ChangePassword(command) => (command.code(), command.to_bytes()),
+            EntryCommand::RehashPassword(command) => (command.code(), command.to_bytes()),
             EntryCommand::UpdatePermissions(command) => (command.code(), command.to_bytes()),
             EntryCommand::CreatePersonalAccessToken(command) => {
                 (command.code(), command.to_bytes())
@@ -78,6 +89,10 @@ impl BytesSerializable for EntryCommand {
             EntryCommand::DeletePersonalAccessToken(command) => {
                 (command.code(), command.to_bytes())
             }
+            EntryCommand::CreateRole(command) => (command.code(), command.to_bytes()),
+            EntryCommand::UpdateRole(command) => (command.code(), command.to_bytes()),
+            EntryCommand::DeleteRole(command) => (command.code(), command.to_bytes()),
+            EntryCommand::UpdateRoles(command) => (command.code(), command.to_bytes()),
         };
 
         let mut bytes = BytesMut::with_capacity(4 + 4 + command.len());
@@ -127,6 +142,9 @@ impl BytesSerializable for EntryCommand {
             CHANGE_PASSWORD_CODE => Ok(EntryCommand::ChangePassword(ChangePassword::from_bytes(
                 payload,
             )?)),
+            REHASH_PASSWORD_CODE => Ok(EntryCommand::RehashPassword(RehashPassword::from_bytes(
+                payload,
+            )?)),
             UPDATE_PERMISSIONS_CODE => Ok(EntryCommand::UpdatePermissions(
                 UpdatePermissions::from_bytes(payload)?,
             )),
@@ -136,6 +154,12 @@ impl BytesSerializable for EntryCommand {
             DELETE_PERSONAL_ACCESS_TOKEN_CODE => Ok(EntryCommand::DeletePersonalAccessToken(
                 DeletePersonalAccessToken::from_bytes(payload)?,
             )),
+            CREATE_ROLE_CODE => Ok(EntryCommand::CreateRole(CreateRoleWithId::from_bytes(
+                payload,
+            )?)),
+            UPDATE_ROLE_CODE => Ok(EntryCommand::UpdateRole(UpdateRole::from_bytes(payload)?)),
+            DELETE_ROLE_CODE => Ok(EntryCommand::DeleteRole(DeleteRole::from_bytes(payload)?)),
+            UPDATE_ROLES_CODE => Ok(EntryCommand::UpdateRoles(UpdateRoles::from_bytes(payload)?)),
             _ => Err(IggyError::InvalidCommand),
         }
     }
@@ -164,6 +188,7 @@ impl Display for EntryCommand {
             EntryCommand::UpdateUser(command) => write!(f, "UpdateUser({})", command),
             EntryCommand::DeleteUser(command) => write!(f, "DeleteUser({})", command),
             EntryCommand::ChangePassword(command) => write!(f, "ChangePassword({})", command),
+            EntryCommand::RehashPassword(command) => write!(f, "RehashPassword({})", command),
             EntryCommand::UpdatePermissions(command) => write!(f, "UpdatePermissions({})", command),
             EntryCommand::CreatePersonalAccessToken(command) => {
                 write!(f, "CreatePersonalAccessToken({})", command)
@@ -171,6 +196,10 @@ impl Display for EntryCommand {
             EntryCommand::DeletePersonalAccessToken(command) => {
                 write!(f, "DeletePersonalAccessToken({})", command)
             }
+            EntryCommand::CreateRole(command) => write!(f, "CreateRole({})", command),
+            EntryCommand::UpdateRole(command) => write!(f, "UpdateRole({})", command),
+            EntryCommand::DeleteRole(command) => write!(f, "DeleteRole({})", command),
+            EntryCommand::UpdateRoles(command) => write!(f, "UpdateRoles({})", command),
         }
     }
 }
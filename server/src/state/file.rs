@@ -1,7 +1,10 @@
 use crate::state::command::EntryCommand;
+use crate::state::snapshot::StateSnapshot;
+use crate::state::system::SystemState;
 use crate::state::{State, StateEntry};
 use crate::streaming::persistence::persister::Persister;
 use crate::streaming::utils::file;
+use crate::streaming::utils::key_ring::EncryptionKeyRing;
 use crate::versioning::SemanticVersion;
 use async_trait::async_trait;
 use bytes::{Buf, BufMut, Bytes, BytesMut};
@@ -13,12 +16,16 @@ use iggy::utils::timestamp::IggyTimestamp;
 use log::debug;
 use std::fmt::Debug;
 use std::path::Path;
-use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::io::{AsyncReadExt, BufReader};
+use tokio::sync::Mutex;
 use tracing::{error, info};
 
 const BUF_READER_CAPACITY_BYTES: usize = 512 * 1000;
+const REENCRYPTION_EXTENSION: &str = "reencrypted";
+const COMPACTION_EXTENSION: &str = "compacted";
+const SNAPSHOT_FILE_NAME: &str = "snapshot";
 
 #[derive(Debug)]
 pub struct FileState {
@@ -26,8 +33,12 @@ pub struct FileState {
     entries_count: AtomicU64,
     current_leader: AtomicU32,
     term: AtomicU64,
+    has_snapshot: AtomicBool,
+    /// Held while assigning the index and appending the entry, so the entries are stored in the order of their indexes.
+    append_lock: Mutex<()>,
     version: u32,
     path: String,
+    snapshot_path: String,
     persister: Arc<dyn Persister>,
     encryptor: Option<Arc<dyn Encryptor>>,
 }
@@ -44,7 +55,13 @@ impl FileState {
             entries_count: AtomicU64::new(0),
             current_leader: AtomicU32::new(0),
             term: AtomicU64::new(0),
+            has_snapshot: AtomicBool::new(false),
+            append_lock: Mutex::new(()),
             path: path.into(),
+            snapshot_path: Path::new(path)
+                .with_file_name(SNAPSHOT_FILE_NAME)
+                .to_string_lossy()
+                .into_owned(),
             persister,
             encryptor,
             version: version.get_numeric_version().expect("Invalid version"),
@@ -62,6 +79,10 @@ impl FileState {
     pub fn term(&self) -> u64 {
         self.term.load(Ordering::SeqCst)
     }
+
+    pub fn snapshot_path(&self) -> &str {
+        &self.snapshot_path
+    }
 }
 
 #[async_trait]
@@ -72,20 +93,333 @@ impl State for FileState {
             self.persister.overwrite(&self.path, &[]).await?;
         }
 
-        let entries = self.load_entries().await?;
+        let snapshot_index = self.read_snapshot_index().await?;
+        let mut entries = self.load_entries().await?;
+        if let Some((snapshot_index, snapshot_term)) = snapshot_index {
+            // The entries covered by the snapshot remain in the log if the server stopped before truncating it.
+            entries.retain(|entry| entry.index > snapshot_index);
+            if let Some(entry) = entries.first() {
+                if entry.index != snapshot_index + 1 {
+                    error!(
+                        "State file is corrupted, expected index: {} after the snapshot, got: {}",
+                        snapshot_index + 1,
+                        entry.index
+                    );
+                    return Err(IggyError::StateFileCorrupted);
+                }
+            }
+            self.has_snapshot.store(true, Ordering::SeqCst);
+            self.term.store(snapshot_term, Ordering::SeqCst);
+            info!(
+                "Loaded state snapshot with index: {snapshot_index}, {} state entries to replay",
+                entries.len()
+            );
+        }
+
         let entries_count = entries.len() as u64;
         self.entries_count.store(entries_count, Ordering::SeqCst);
-        if entries_count == 0 {
-            self.current_index.store(0, Ordering::SeqCst);
-        } else {
-            let last_index = entries[entries_count as usize - 1].index;
-            self.current_index.store(last_index, Ordering::SeqCst);
-        }
+        let current_index = match entries.last() {
+            Some(entry) => entry.index,
+            None => snapshot_index.map_or(0, |(index, _)| index),
+        };
+        self.current_index.store(current_index, Ordering::SeqCst);
 
         return Ok(entries);
     }
 
     async fn load_entries(&self) -> Result<Vec<StateEntry>, IggyError> {
+        let stored_entries = self.read_entries().await?;
+        let mut entries = Vec::with_capacity(stored_entries.len());
+        for stored_entry in stored_entries {
+            let command = match &self.encryptor {
+                Some(encryptor) => {
+                    debug!("Decrypting state entry with index: {}", stored_entry.index);
+                    map_command_payload(&stored_entry.command, |payload| {
+                        encryptor.decrypt(payload)
+                    })?
+                }
+                None => stored_entry.command,
+            };
+            EntryCommand::from_bytes(command.clone())?;
+            let calculated_checksum = StateEntry::calculate_checksum(
+                stored_entry.index,
+                stored_entry.term,
+                stored_entry.leader_id,
+                stored_entry.version,
+                stored_entry.flags,
+                stored_entry.timestamp,
+                stored_entry.user_id,
+                &stored_entry.context,
+                &command,
+            );
+            let entry = StateEntry::new(
+                stored_entry.index,
+                stored_entry.term,
+                stored_entry.leader_id,
+                stored_entry.version,
+                stored_entry.flags,
+                stored_entry.timestamp,
+                stored_entry.user_id,
+                calculated_checksum,
+                stored_entry.context,
+                command,
+            );
+            debug!("Read state entry: {entry}");
+            if entry.checksum != stored_entry.checksum {
+                return Err(IggyError::InvalidStateEntryChecksum(
+                    entry.checksum,
+                    stored_entry.checksum,
+                    entry.index,
+                ));
+            }
+
+            entries.push(entry);
+        }
+
+        if let Some(entry) = entries.last() {
+            info!(
+                "Loaded {} state entries, current index: {}",
+                entries.len(),
+                entry.index
+            );
+        }
+        Ok(entries)
+    }
+
+    async fn apply(&self, user_id: u32, command: EntryCommand) -> Result<(), IggyError> {
+        debug!("Applying state entry with command: {command}, user ID: {user_id}");
+        let _append_lock = self.append_lock.lock().await;
+        let timestamp = IggyTimestamp::now();
+        let index = if self.entries_count.load(Ordering::SeqCst) == 0
+            && !self.has_snapshot.load(Ordering::SeqCst)
+        {
+            0
+        } else {
+            self.current_index.fetch_add(1, Ordering::SeqCst) + 1
+        };
+        let term = self.term.load(Ordering::SeqCst);
+        let current_leader = self.current_leader.load(Ordering::SeqCst);
+        let version = self.version;
+        let flags = 0;
+        let context = Bytes::new();
+        let mut command = command.to_bytes();
+        let checksum = StateEntry::calculate_checksum(
+            index,
+            term,
+            current_leader,
+            version,
+            flags,
+            timestamp,
+            user_id,
+            &context,
+            &command,
+        );
+
+        if let Some(encryptor) = &self.encryptor {
+            debug!("Encrypting state entry command with index: {index}");
+            command = map_command_payload(&command, |payload| encryptor.encrypt(payload))?;
+        }
+
+        let entry = StateEntry::new(
+            index,
+            term,
+            current_leader,
+            version,
+            flags,
+            timestamp,
+            user_id,
+            checksum,
+            context,
+            command,
+        );
+        let bytes = entry.to_bytes();
+        self.entries_count.fetch_add(1, Ordering::SeqCst);
+        self.persister.append(&self.path, &bytes).await?;
+        debug!("Applied state entry: {entry}");
+        Ok(())
+    }
+
+    async fn load_snapshot(&self) -> Result<Option<StateSnapshot>, IggyError> {
+        let Some(bytes) = self.read_snapshot().await? else {
+            return Ok(None);
+        };
+
+        let snapshot = StateSnapshot::from_bytes(&bytes, self.encryptor.as_deref())?;
+        debug!("Read state snapshot: {snapshot}");
+        Ok(Some(snapshot))
+    }
+
+    async fn snapshot(&self, min_entries: u64) -> Result<Option<u64>, IggyError> {
+        let entries_count = self.entries_count();
+        if entries_count == 0 || entries_count < min_entries {
+            debug!(
+                "Skipping state snapshot, {entries_count} state entries since the previous one."
+            );
+            return Ok(None);
+        }
+
+        let previous_snapshot = self.load_snapshot().await?;
+        let Some((snapshot, applied_entries)) = self.build_snapshot(previous_snapshot).await?
+        else {
+            return Ok(None);
+        };
+
+        let index = snapshot.index;
+        let bytes = snapshot.to_bytes(self.encryptor.as_deref())?;
+        // The snapshot must be durable before the entries it covers are removed from the log,
+        // thus both files are always fsynced (along with the directory), regardless of the configured fsync.
+        let temporary_path = format!("{}.{}", self.snapshot_path, COMPACTION_EXTENSION);
+        file::overwrite_synced(&temporary_path, &bytes).await?;
+        file::rename_synced(&temporary_path, &self.snapshot_path).await?;
+        self.has_snapshot.store(true, Ordering::SeqCst);
+
+        // The entries appended in the meantime are kept, the snapshot covers only the ones loaded above.
+        let _append_lock = self.append_lock.lock().await;
+        let mut bytes = BytesMut::new();
+        let mut remaining_entries = 0;
+        for entry in self.read_entries().await? {
+            if entry.index > index {
+                bytes.extend(entry.to_bytes());
+                remaining_entries += 1;
+            }
+        }
+        let temporary_path = format!("{}.{}", self.path, COMPACTION_EXTENSION);
+        file::overwrite_synced(&temporary_path, &bytes).await?;
+        file::rename_synced(&temporary_path, &self.path).await?;
+        self.entries_count
+            .store(remaining_entries, Ordering::SeqCst);
+        info!(
+            "Created state snapshot with index: {index}, compacted {applied_entries} state entries."
+        );
+        Ok(Some(index))
+    }
+
+    async fn export_snapshot(&self, path: &str) -> Result<Option<u64>, IggyError> {
+        let previous_snapshot = self.load_snapshot().await?;
+        let snapshot = match self.build_snapshot(previous_snapshot).await? {
+            Some((snapshot, _)) => snapshot,
+            None => match self.load_snapshot().await? {
+                Some(snapshot) => snapshot,
+                None => return Ok(None),
+            },
+        };
+
+        let index = snapshot.index;
+        let bytes = snapshot.to_bytes(self.encryptor.as_deref())?;
+        self.persister.overwrite(path, &bytes).await?;
+        info!("Exported state snapshot with index: {index} to: {path}");
+        Ok(Some(index))
+    }
+
+    async fn reencrypt(&self, key_ring: &EncryptionKeyRing) -> Result<u64, IggyError> {
+        if !Path::new(&self.path).exists() {
+            return Err(IggyError::StateFileNotFound);
+        }
+
+        let mut reencrypted_snapshot = false;
+        if let Some(snapshot) = self.read_snapshot().await? {
+            let snapshot =
+                StateSnapshot::map_state(&snapshot, |state| match key_ring.reencrypt(state)? {
+                    Some(reencrypted_state) => {
+                        reencrypted_snapshot = true;
+                        Ok(reencrypted_state)
+                    }
+                    None => Ok(state.to_vec()),
+                })?;
+            if reencrypted_snapshot {
+                let temporary_path = format!("{}.{}", self.snapshot_path, REENCRYPTION_EXTENSION);
+                file::overwrite_synced(&temporary_path, &snapshot).await?;
+                file::rename_synced(&temporary_path, &self.snapshot_path).await?;
+                info!(
+                    "Re-encrypted state snapshot with the key with ID: {}",
+                    key_ring.get_active_key_id()
+                );
+            }
+        }
+
+        let mut entries = self.read_entries().await?;
+        let mut reencrypted_entries = 0;
+        for entry in entries.iter_mut() {
+            let mut reencrypted = false;
+            let command = map_command_payload(&entry.command, |payload| {
+                match key_ring.reencrypt(payload)? {
+                    Some(reencrypted_payload) => {
+                        reencrypted = true;
+                        Ok(reencrypted_payload)
+                    }
+                    None => Ok(payload.to_vec()),
+                }
+            })?;
+            if reencrypted {
+                entry.command = command;
+                reencrypted_entries += 1;
+            }
+        }
+
+        if reencrypted_entries == 0 {
+            debug!("All state entries are already encrypted with the active key.");
+            return Ok(reencrypted_snapshot as u64);
+        }
+
+        let mut bytes = BytesMut::new();
+        for entry in &entries {
+            bytes.extend(entry.to_bytes());
+        }
+
+        let temporary_path = format!("{}.{}", self.path, REENCRYPTION_EXTENSION);
+        file::overwrite_synced(&temporary_path, &bytes).await?;
+        file::rename_synced(&temporary_path, &self.path).await?;
+        info!(
+            "Re-encrypted {reencrypted_entries} state entries with the key with ID: {}",
+            key_ring.get_active_key_id()
+        );
+        Ok(reencrypted_entries + reencrypted_snapshot as u64)
+    }
+}
+
+impl FileState {
+    /// Materializes the state from the previous snapshot and the entries appended after it,
+    /// returns the new snapshot and the number of applied entries, or `None` if there are no new entries.
+    async fn build_snapshot(
+        &self,
+        previous_snapshot: Option<StateSnapshot>,
+    ) -> Result<Option<(StateSnapshot, usize)>, IggyError> {
+        let snapshot_index = previous_snapshot.as_ref().map(|snapshot| snapshot.index);
+        let mut entries = self.load_entries().await?;
+        entries.retain(|entry| snapshot_index.is_none_or(|index| entry.index > index));
+        let Some((index, term)) = entries.last().map(|entry| (entry.index, entry.term)) else {
+            return Ok(None);
+        };
+
+        let applied_entries = entries.len();
+        let state =
+            SystemState::restore(previous_snapshot.map(|snapshot| snapshot.state), entries).await?;
+        let snapshot = StateSnapshot::new(index, term, self.version, state);
+        Ok(Some((snapshot, applied_entries)))
+    }
+
+    /// Reads the snapshot as stored in the snapshot file, if it exists.
+    async fn read_snapshot(&self) -> Result<Option<Vec<u8>>, IggyError> {
+        if !Path::new(&self.snapshot_path).exists() {
+            return Ok(None);
+        }
+
+        let mut file = file::open(&self.snapshot_path).await?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).await?;
+        Ok(Some(bytes))
+    }
+
+    /// Reads the index and term of the snapshot, if it exists.
+    async fn read_snapshot_index(&self) -> Result<Option<(u64, u64)>, IggyError> {
+        match self.read_snapshot().await? {
+            Some(bytes) => Ok(Some(StateSnapshot::read_index(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Reads the entries as stored in the state file, without decrypting the commands and validating the checksums.
+    async fn read_entries(&self) -> Result<Vec<StateEntry>, IggyError> {
         if !Path::new(&self.path).exists() {
             return Err(IggyError::StateFileNotFound);
         }
@@ -143,31 +477,15 @@ impl State for FileState {
             total_size += context_length as u64;
             let code = reader.read_u32_le().await?;
             total_size += 4;
-            let mut command_length = reader.read_u32_le().await? as usize;
+            let command_length = reader.read_u32_le().await? as usize;
             total_size += 4;
-            let mut command = BytesMut::with_capacity(command_length);
+            let mut command = BytesMut::with_capacity(4 + 4 + command_length);
+            command.put_u32_le(code);
+            command.put_u32_le(command_length as u32);
             command.put_bytes(0, command_length);
-            reader.read_exact(&mut command).await?;
+            reader.read_exact(&mut command[8..]).await?;
             total_size += command_length as u64;
-            let command_payload;
-            if let Some(encryptor) = &self.encryptor {
-                debug!("Decrypting state entry with index: {index}");
-                command_payload = Bytes::from(encryptor.decrypt(&command.freeze())?);
-                command_length = command_payload.len();
-            } else {
-                command_payload = command.freeze();
-            }
-
-            let mut entry_command = BytesMut::with_capacity(4 + 4 + command_length);
-            entry_command.put_u32_le(code);
-            entry_command.put_u32_le(command_length as u32);
-            entry_command.extend(command_payload);
-            let command = entry_command.freeze();
-            EntryCommand::from_bytes(command.clone())?;
-            let calculated_checksum = StateEntry::calculate_checksum(
-                index, term, leader_id, version, flags, timestamp, user_id, &context, &command,
-            );
-            let entry = StateEntry::new(
+            entries.push(StateEntry::new(
                 index,
                 term,
                 leader_id,
@@ -175,85 +493,30 @@ impl State for FileState {
                 flags,
                 timestamp,
                 user_id,
-                calculated_checksum,
+                checksum,
                 context,
-                command,
-            );
-            debug!("Read state entry: {entry}");
-            if entry.checksum != checksum {
-                return Err(IggyError::InvalidStateEntryChecksum(
-                    entry.checksum,
-                    checksum,
-                    entry.index,
-                ));
-            }
-
-            entries.push(entry);
+                command.freeze(),
+            ));
             if total_size == file_size {
                 break;
             }
         }
 
-        info!("Loaded {entries_count} state entries, current index: {current_index}");
         Ok(entries)
     }
+}
 
-    async fn apply(&self, user_id: u32, command: EntryCommand) -> Result<(), IggyError> {
-        debug!("Applying state entry with command: {command}, user ID: {user_id}");
-        let timestamp = IggyTimestamp::now();
-        let index = if self.entries_count.load(Ordering::SeqCst) == 0 {
-            0
-        } else {
-            self.current_index.fetch_add(1, Ordering::SeqCst) + 1
-        };
-        let term = self.term.load(Ordering::SeqCst);
-        let current_leader = self.current_leader.load(Ordering::SeqCst);
-        let version = self.version;
-        let flags = 0;
-        let context = Bytes::new();
-        let mut command = command.to_bytes();
-        let checksum = StateEntry::calculate_checksum(
-            index,
-            term,
-            current_leader,
-            version,
-            flags,
-            timestamp,
-            user_id,
-            &context,
-            &command,
-        );
-
-        if let Some(encryptor) = &self.encryptor {
-            debug!("Encrypting state entry command with index: {index}");
-            let command_code = command.slice(0..4).get_u32_le();
-            let mut command_length = command.slice(4..8).get_u32_le() as usize;
-            let command_payload = command.slice(8..8 + command_length);
-            let encrypted_command_payload = encryptor.encrypt(&command_payload)?;
-            command_length = encrypted_command_payload.len();
-            let mut command_bytes = BytesMut::with_capacity(4 + 4 + command_length);
-            command_bytes.put_u32_le(command_code);
-            command_bytes.put_u32_le(command_length as u32);
-            command_bytes.extend(encrypted_command_payload);
-            command = command_bytes.freeze();
-        }
-
-        let entry = StateEntry::new(
-            index,
-            term,
-            current_leader,
-            version,
-            flags,
-            timestamp,
-            user_id,
-            checksum,
-            context,
-            command,
-        );
-        let bytes = entry.to_bytes();
-        self.entries_count.fetch_add(1, Ordering::SeqCst);
-        self.persister.append(&self.path, &bytes).await?;
-        debug!("Applied state entry: {entry}");
-        Ok(())
-    }
+/// Maps the payload of the serialized command (code, length and payload), keeping its code.
+pub fn map_command_payload(
+    command: &Bytes,
+    f: impl FnOnce(&[u8]) -> Result<Vec<u8>, IggyError>,
+) -> Result<Bytes, IggyError> {
+    let command_code = command.slice(0..4).get_u32_le();
+    let command_length = command.slice(4..8).get_u32_le() as usize;
+    let command_payload = f(&command[8..8 + command_length])?;
+    let mut command_bytes = BytesMut::with_capacity(4 + 4 + command_payload.len());
+    command_bytes.put_u32_le(command_code);
+    command_bytes.put_u32_le(command_payload.len() as u32);
+    command_bytes.extend(command_payload);
+    Ok(command_bytes.freeze())
 }
@@ -1,5 +1,7 @@
 use crate::state::command::EntryCommand;
 use crate::state::entry::StateEntry;
+use crate::state::snapshot::StateSnapshot;
+use crate::streaming::utils::key_ring::EncryptionKeyRing;
 use async_trait::async_trait;
 use iggy::error::IggyError;
 use std::fmt::Debug;
@@ -8,11 +10,24 @@ pub mod command;
 pub mod entry;
 pub mod file;
 pub mod models;
+pub mod snapshot;
 pub mod system;
 
 #[async_trait]
 pub trait State: Send + Sync + Debug {
+    /// Initializes the state and returns the entries appended after the snapshot (if any).
     async fn init(&self) -> Result<Vec<StateEntry>, IggyError>;
     async fn load_entries(&self) -> Result<Vec<StateEntry>, IggyError>;
+    async fn load_snapshot(&self) -> Result<Option<StateSnapshot>, IggyError>;
+    /// Writes the snapshot of the state materialized from the previous snapshot and the log entries,
+    /// then truncates the entries covered by it. The snapshot is skipped if fewer than `min_entries`
+    /// entries were appended since the previous one, otherwise the index of the snapshot is returned.
+    async fn snapshot(&self, min_entries: u64) -> Result<Option<u64>, IggyError>;
+    /// Writes the snapshot of the current state (including all the log entries) to the given path,
+    /// without compacting the log. Returns the index of the snapshot, or `None` if the state is empty.
+    async fn export_snapshot(&self, path: &str) -> Result<Option<u64>, IggyError>;
     async fn apply(&self, user_id: u32, command: EntryCommand) -> Result<(), IggyError>;
+    /// Rewrites the entries encrypted with any of the previous keys using the active key,
+    /// returns the number of re-encrypted entries, including the snapshot.
+    async fn reencrypt(&self, key_ring: &EncryptionKeyRing) -> Result<u64, IggyError>;
 }
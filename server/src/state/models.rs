@@ -1,8 +1,11 @@
 use bytes::{BufMut, Bytes, BytesMut};
 use iggy::bytes_serializable::BytesSerializable;
-use iggy::command::Command;
+use iggy::command::{Command, REHASH_PASSWORD_CODE};
 use iggy::error::IggyError;
+use iggy::models::role_info::RoleId;
+use iggy::models::user_info::UserId;
 use iggy::personal_access_tokens::create_personal_access_token::CreatePersonalAccessToken;
+use iggy::roles::create_role::CreateRole;
 use iggy::validatable::Validatable;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -64,3 +67,105 @@ impl Display for CreatePersonalAccessTokenWithHash {
         )
     }
 }
+
+/// `CreateRoleWithId` stores the ID assigned to the created role, so that it's restored with the same ID,
+/// regardless of the roles deleted before (the user roles refer to the numeric IDs).
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateRoleWithId {
+    pub role_id: RoleId,
+    pub command: CreateRole,
+}
+
+impl Validatable<IggyError> for CreateRoleWithId {
+    fn validate(&self) -> Result<(), IggyError> {
+        self.command.validate()
+    }
+}
+
+impl Command for CreateRoleWithId {
+    fn code(&self) -> u32 {
+        self.command.code()
+    }
+}
+
+impl BytesSerializable for CreateRoleWithId {
+    fn to_bytes(&self) -> Bytes {
+        let command_bytes = self.command.to_bytes();
+        let mut bytes = BytesMut::with_capacity(4 + command_bytes.len());
+        bytes.put_u32_le(self.role_id);
+        bytes.put_slice(&command_bytes);
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError>
+    where
+        Self: Sized,
+    {
+        if bytes.len() < 4 {
+            return Err(IggyError::InvalidCommand);
+        }
+
+        let role_id = u32::from_le_bytes(bytes[0..4].try_into()?);
+        let command = CreateRole::from_bytes(bytes.slice(4..))?;
+        Ok(Self { role_id, command })
+    }
+}
+
+impl Display for CreateRoleWithId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "CreateRoleWithId {{ role_id: {}, command: {} }}",
+            self.role_id, self.command
+        )
+    }
+}
+
+/// `RehashPassword` replaces the password hash of the user with the one computed using the currently configured
+/// hashing algorithm and parameters. Unlike the password change, the previous hash is not added to the password history.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RehashPassword {
+    pub user_id: UserId,
+    pub password_hash: String,
+}
+
+impl Validatable<IggyError> for RehashPassword {
+    fn validate(&self) -> Result<(), IggyError> {
+        Ok(())
+    }
+}
+
+impl Command for RehashPassword {
+    fn code(&self) -> u32 {
+        REHASH_PASSWORD_CODE
+    }
+}
+
+impl BytesSerializable for RehashPassword {
+    fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(4 + 4 + self.password_hash.len());
+        bytes.put_u32_le(self.user_id);
+        bytes.put_u32_le(self.password_hash.len() as u32);
+        bytes.put_slice(self.password_hash.as_bytes());
+        bytes.freeze()
+    }
+
+    fn from_bytes(bytes: Bytes) -> Result<Self, IggyError>
+    where
+        Self: Sized,
+    {
+        let user_id = u32::from_le_bytes(bytes[0..4].try_into()?);
+        let hash_length = u32::from_le_bytes(bytes[4..8].try_into()?);
+        let password_hash = from_utf8(&bytes[8..8 + hash_length as usize])?.to_string();
+        Ok(Self {
+            user_id,
+            password_hash,
+        })
+    }
+}
+
+impl Display for RehashPassword {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "RehashPassword {{ user_id: {} }}", self.user_id)
+    }
+}
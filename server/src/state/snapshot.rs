@@ -0,0 +1,158 @@
+use crate::state::system::SystemState;
+use anyhow::Context;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use iggy::error::IggyError;
+use iggy::utils::checksum;
+use iggy::utils::crypto::Encryptor;
+use iggy::utils::timestamp::IggyTimestamp;
+use std::fmt::{Display, Formatter};
+use tracing::error;
+
+const HEADER_SIZE: usize = 8 + 8 + 4 + 8 + 4 + 4;
+
+/// Snapshot of the state materialized from all the log entries up to the `index` (inclusive)
+/// - `index` - Index of the last entry applied to the state
+/// - `term` - Election term of the last applied entry
+/// - `version` - Server version based on semver as number e.g. 1.234.567 -> 1234567
+/// - `timestamp` - Timestamp when the snapshot was created
+/// - `checksum` - Checksum of the snapshot, calculated before the encryption
+/// - `state` - Serialized and optionally encrypted state
+#[derive(Debug)]
+pub struct StateSnapshot {
+    pub index: u64,
+    pub term: u64,
+    pub version: u32,
+    pub timestamp: IggyTimestamp,
+    pub state: SystemState,
+}
+
+impl StateSnapshot {
+    pub fn new(index: u64, term: u64, version: u32, state: SystemState) -> Self {
+        Self {
+            index,
+            term,
+            version,
+            timestamp: IggyTimestamp::now(),
+            state,
+        }
+    }
+
+    /// Serializes the snapshot, the state is encrypted if the encryptor is provided.
+    pub fn to_bytes(&self, encryptor: Option<&dyn Encryptor>) -> Result<Bytes, IggyError> {
+        let state = bincode::serialize(&self.state)
+            .with_context(|| "Failed to serialize state snapshot")
+            .map_err(IggyError::CannotSerializeResource)?;
+        let checksum =
+            Self::calculate_checksum(self.index, self.term, self.version, self.timestamp, &state);
+        let state = match encryptor {
+            Some(encryptor) => encryptor.encrypt(&state)?,
+            None => state,
+        };
+
+        let mut bytes = BytesMut::with_capacity(HEADER_SIZE + state.len());
+        bytes.put_u64_le(self.index);
+        bytes.put_u64_le(self.term);
+        bytes.put_u32_le(self.version);
+        bytes.put_u64_le(self.timestamp.into());
+        bytes.put_u32_le(checksum);
+        bytes.put_u32_le(state.len() as u32);
+        bytes.put_slice(&state);
+        Ok(bytes.freeze())
+    }
+
+    pub fn from_bytes(bytes: &[u8], encryptor: Option<&dyn Encryptor>) -> Result<Self, IggyError> {
+        let (index, term) = Self::read_index(bytes)?;
+        let mut header = &bytes[16..HEADER_SIZE];
+        let version = header.get_u32_le();
+        let timestamp = IggyTimestamp::from(header.get_u64_le());
+        let checksum = header.get_u32_le();
+        let state_length = header.get_u32_le() as usize;
+        if bytes.len() != HEADER_SIZE + state_length {
+            error!(
+                "State snapshot is corrupted, expected size: {}, got: {}",
+                HEADER_SIZE + state_length,
+                bytes.len()
+            );
+            return Err(IggyError::StateFileCorrupted);
+        }
+
+        let state = &bytes[HEADER_SIZE..];
+        let state = match encryptor {
+            Some(encryptor) => encryptor.decrypt(state)?,
+            None => state.to_vec(),
+        };
+        let calculated_checksum = Self::calculate_checksum(index, term, version, timestamp, &state);
+        if calculated_checksum != checksum {
+            return Err(IggyError::InvalidStateEntryChecksum(
+                calculated_checksum,
+                checksum,
+                index,
+            ));
+        }
+
+        let state = bincode::deserialize(&state)
+            .with_context(|| "Failed to deserialize state snapshot")
+            .map_err(IggyError::CannotDeserializeResource)?;
+        Ok(Self {
+            index,
+            term,
+            version,
+            timestamp,
+            state,
+        })
+    }
+
+    /// Reads the index and term of the serialized snapshot, without deserializing the state.
+    pub fn read_index(bytes: &[u8]) -> Result<(u64, u64), IggyError> {
+        if bytes.len() < HEADER_SIZE {
+            error!(
+                "State snapshot is corrupted, expected at least: {HEADER_SIZE} bytes, got: {}",
+                bytes.len()
+            );
+            return Err(IggyError::StateFileCorrupted);
+        }
+
+        let mut header = &bytes[..16];
+        Ok((header.get_u64_le(), header.get_u64_le()))
+    }
+
+    /// Maps the serialized (and optionally encrypted) state of the serialized snapshot, keeping its header.
+    pub fn map_state(
+        bytes: &[u8],
+        f: impl FnOnce(&[u8]) -> Result<Vec<u8>, IggyError>,
+    ) -> Result<Bytes, IggyError> {
+        Self::read_index(bytes)?;
+        let state = f(&bytes[HEADER_SIZE..])?;
+        let mut mapped_bytes = BytesMut::with_capacity(HEADER_SIZE + state.len());
+        mapped_bytes.put_slice(&bytes[..HEADER_SIZE - 4]);
+        mapped_bytes.put_u32_le(state.len() as u32);
+        mapped_bytes.put_slice(&state);
+        Ok(mapped_bytes.freeze())
+    }
+
+    fn calculate_checksum(
+        index: u64,
+        term: u64,
+        version: u32,
+        timestamp: IggyTimestamp,
+        state: &[u8],
+    ) -> u32 {
+        let mut bytes = BytesMut::with_capacity(8 + 8 + 4 + 8 + state.len());
+        bytes.put_u64_le(index);
+        bytes.put_u64_le(term);
+        bytes.put_u32_le(version);
+        bytes.put_u64_le(timestamp.into());
+        bytes.put_slice(state);
+        checksum::calculate(&bytes.freeze())
+    }
+}
+
+impl Display for StateSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "StateSnapshot {{ index: {}, term: {}, version: {}, timestamp: {} }}",
+            self.index, self.term, self.version, self.timestamp
+        )
+    }
+}
@@ -8,17 +8,22 @@ use iggy::models::user_status::UserStatus;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::timestamp::IggyTimestamp;
 use iggy::utils::topic_size::MaxTopicSize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt::Display;
 use tracing::debug;
 
-#[derive(Debug)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct SystemState {
     pub streams: HashMap<u32, StreamState>,
     pub users: HashMap<u32, UserState>,
+    pub roles: HashMap<u32, RoleState>,
+    pub current_stream_id: u32,
+    pub current_user_id: u32,
+    pub current_role_id: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct StreamState {
     pub id: u32,
     pub name: String,
@@ -27,7 +32,7 @@ pub struct StreamState {
     pub current_topic_id: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TopicState {
     pub id: u32,
     pub name: String,
@@ -41,30 +46,40 @@ pub struct TopicState {
     pub current_consumer_group_id: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PartitionState {
     pub id: u32,
     pub created_at: IggyTimestamp,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PersonalAccessTokenState {
     pub name: String,
     pub token_hash: String,
     pub expiry_at: Option<IggyTimestamp>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct UserState {
     pub id: u32,
     pub username: String,
     pub password_hash: String,
+    pub password_history: Vec<String>,
     pub status: UserStatus,
     pub permissions: Option<Permissions>,
+    pub roles: Vec<u32>,
     pub personal_access_tokens: HashMap<String, PersonalAccessTokenState>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoleState {
+    pub id: u32,
+    pub name: String,
+    pub created_at: IggyTimestamp,
+    pub permissions: Option<Permissions>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ConsumerGroupState {
     pub id: u32,
     pub name: String,
@@ -72,291 +87,379 @@ pub struct ConsumerGroupState {
 
 impl SystemState {
     pub async fn init(entries: Vec<StateEntry>) -> Result<Self, IggyError> {
-        let mut streams = HashMap::new();
-        let mut users = HashMap::new();
-        let mut current_stream_id = 0;
-        let mut current_user_id = 0;
+        Self::restore(None, entries).await
+    }
+
+    /// Restores the state from the snapshot (if any) by applying the entries appended after it.
+    pub async fn restore(
+        snapshot: Option<SystemState>,
+        entries: Vec<StateEntry>,
+    ) -> Result<Self, IggyError> {
+        let mut state = match snapshot {
+            Some(mut state) => {
+                state.remove_expired_personal_access_tokens();
+                state
+            }
+            None => SystemState::default(),
+        };
         for entry in entries {
-            debug!("Processing state entry: {entry}",);
-            match entry.command()? {
-                EntryCommand::CreateStream(command) => {
-                    let stream_id = command.stream_id.unwrap_or_else(|| {
-                        current_stream_id += 1;
-                        current_stream_id
-                    });
-                    let stream = StreamState {
-                        id: stream_id,
-                        name: command.name.clone(),
-                        topics: HashMap::new(),
-                        current_topic_id: 0,
-                        created_at: entry.timestamp,
-                    };
-                    streams.insert(stream.id, stream);
-                }
-                EntryCommand::UpdateStream(command) => {
-                    let stream_id = find_stream_id(&streams, &command.stream_id);
-                    let stream = streams
-                        .get_mut(&stream_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
-                    stream.name = command.name;
-                }
-                EntryCommand::DeleteStream(command) => {
-                    let stream_id = find_stream_id(&streams, &command.stream_id);
-                    streams.remove(&stream_id);
-                }
-                EntryCommand::PurgeStream(command) => {
-                    let stream_id = find_stream_id(&streams, &command.stream_id);
-                    streams
-                        .get(&stream_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
-                    // It only affects the segments which are not part of the state
-                }
-                EntryCommand::CreateTopic(command) => {
-                    let stream_id = find_stream_id(&streams, &command.stream_id);
-                    let stream = streams
-                        .get_mut(&stream_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
-                    let topic_id = command.topic_id.unwrap_or_else(|| {
-                        stream.current_topic_id += 1;
-                        stream.current_topic_id
-                    });
-                    let topic = TopicState {
-                        id: topic_id,
-                        name: command.name,
-                        consumer_groups: HashMap::new(),
-                        current_consumer_group_id: 0,
-                        compression_algorithm: command.compression_algorithm,
-                        message_expiry: command.message_expiry,
-                        max_topic_size: command.max_topic_size,
-                        replication_factor: command.replication_factor,
-                        created_at: entry.timestamp,
-                        partitions: if command.partitions_count > 0 {
-                            let mut partitions = HashMap::new();
-                            for i in 1..=command.partitions_count {
-                                partitions.insert(
-                                    i,
-                                    PartitionState {
-                                        id: i,
-                                        created_at: entry.timestamp,
-                                    },
-                                );
-                            }
-                            partitions
-                        } else {
-                            HashMap::new()
-                        },
-                    };
-                    stream.topics.insert(topic.id, topic);
-                }
-                EntryCommand::UpdateTopic(command) => {
-                    let stream_id = find_stream_id(&streams, &command.stream_id);
-                    let stream = streams
-                        .get_mut(&stream_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
-                    let topic_id = find_topic_id(&stream.topics, &command.topic_id);
-                    let topic = stream
-                        .topics
-                        .get_mut(&topic_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
-                    topic.name = command.name;
-                    topic.compression_algorithm = command.compression_algorithm;
-                    topic.message_expiry = command.message_expiry;
-                    topic.max_topic_size = command.max_topic_size;
-                    topic.replication_factor = command.replication_factor;
-                }
-                EntryCommand::DeleteTopic(command) => {
-                    let stream_id = find_stream_id(&streams, &command.stream_id);
-                    let stream = streams
-                        .get_mut(&stream_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
-                    let topic_id = find_topic_id(&stream.topics, &command.topic_id);
-                    stream.topics.remove(&topic_id);
-                }
-                EntryCommand::PurgeTopic(command) => {
-                    let stream_id = find_stream_id(&streams, &command.stream_id);
-                    let stream = streams
-                        .get(&stream_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
-                    let topic_id = find_topic_id(&stream.topics, &command.topic_id);
-                    stream
-                        .topics
-                        .get(&topic_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
-                    // It only affects the segments which are not part of the state
-                }
-                EntryCommand::CreatePartitions(command) => {
-                    let stream_id = find_stream_id(&streams, &command.stream_id);
-                    let stream = streams
-                        .get_mut(&stream_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
-                    let topic_id = find_topic_id(&stream.topics, &command.topic_id);
-                    let topic = stream
-                        .topics
-                        .get_mut(&topic_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
-                    let last_partition_id = if topic.partitions.is_empty() {
-                        0
-                    } else {
-                        topic
-                            .partitions
-                            .values()
-                            .map(|p| p.id)
-                            .max()
-                            .unwrap_or_else(|| panic!("No partition found"))
-                    };
-                    for i in 1..=command.partitions_count {
-                        topic.partitions.insert(
-                            last_partition_id + i,
-                            PartitionState {
-                                id: last_partition_id + i,
-                                created_at: entry.timestamp,
-                            },
-                        );
-                    }
-                }
-                EntryCommand::DeletePartitions(command) => {
-                    let stream_id = find_stream_id(&streams, &command.stream_id);
-                    let stream = streams
-                        .get_mut(&stream_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
-                    let topic_id = find_topic_id(&stream.topics, &command.topic_id);
-                    let topic = stream
-                        .topics
-                        .get_mut(&topic_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
-                    if topic.partitions.is_empty() {
-                        continue;
-                    }
+            state.apply(entry)?;
+        }
 
-                    let last_partition_id = topic
+        debug!("+++ State +++");
+        debug!("{state}");
+        debug!("+++ State +++");
+        Ok(state)
+    }
+
+    fn apply(&mut self, entry: StateEntry) -> Result<(), IggyError> {
+        debug!("Processing state entry: {entry}",);
+        match entry.command()? {
+            EntryCommand::CreateStream(command) => {
+                let stream_id = command.stream_id.unwrap_or_else(|| {
+                    self.current_stream_id += 1;
+                    self.current_stream_id
+                });
+                let stream = StreamState {
+                    id: stream_id,
+                    name: command.name.clone(),
+                    topics: HashMap::new(),
+                    current_topic_id: 0,
+                    created_at: entry.timestamp,
+                };
+                self.streams.insert(stream.id, stream);
+            }
+            EntryCommand::UpdateStream(command) => {
+                let stream_id = find_stream_id(&self.streams, &command.stream_id);
+                let stream = self
+                    .streams
+                    .get_mut(&stream_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                stream.name = command.name;
+            }
+            EntryCommand::DeleteStream(command) => {
+                let stream_id = find_stream_id(&self.streams, &command.stream_id);
+                self.streams.remove(&stream_id);
+            }
+            EntryCommand::PurgeStream(command) => {
+                let stream_id = find_stream_id(&self.streams, &command.stream_id);
+                self.streams
+                    .get(&stream_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                // It only affects the segments which are not part of the state
+            }
+            EntryCommand::CreateTopic(command) => {
+                let stream_id = find_stream_id(&self.streams, &command.stream_id);
+                let stream = self
+                    .streams
+                    .get_mut(&stream_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                let topic_id = command.topic_id.unwrap_or_else(|| {
+                    stream.current_topic_id += 1;
+                    stream.current_topic_id
+                });
+                let topic = TopicState {
+                    id: topic_id,
+                    name: command.name,
+                    consumer_groups: HashMap::new(),
+                    current_consumer_group_id: 0,
+                    compression_algorithm: command.compression_algorithm,
+                    message_expiry: command.message_expiry,
+                    max_topic_size: command.max_topic_size,
+                    replication_factor: command.replication_factor,
+                    created_at: entry.timestamp,
+                    partitions: if command.partitions_count > 0 {
+                        let mut partitions = HashMap::new();
+                        for i in 1..=command.partitions_count {
+                            partitions.insert(
+                                i,
+                                PartitionState {
+                                    id: i,
+                                    created_at: entry.timestamp,
+                                },
+                            );
+                        }
+                        partitions
+                    } else {
+                        HashMap::new()
+                    },
+                };
+                stream.topics.insert(topic.id, topic);
+            }
+            EntryCommand::UpdateTopic(command) => {
+                let stream_id = find_stream_id(&self.streams, &command.stream_id);
+                let stream = self
+                    .streams
+                    .get_mut(&stream_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                let topic_id = find_topic_id(&stream.topics, &command.topic_id);
+                let topic = stream
+                    .topics
+                    .get_mut(&topic_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
+                topic.name = command.name;
+                topic.compression_algorithm = command.compression_algorithm;
+                topic.message_expiry = command.message_expiry;
+                topic.max_topic_size = command.max_topic_size;
+                topic.replication_factor = command.replication_factor;
+            }
+            EntryCommand::DeleteTopic(command) => {
+                let stream_id = find_stream_id(&self.streams, &command.stream_id);
+                let stream = self
+                    .streams
+                    .get_mut(&stream_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                let topic_id = find_topic_id(&stream.topics, &command.topic_id);
+                stream.topics.remove(&topic_id);
+            }
+            EntryCommand::PurgeTopic(command) => {
+                let stream_id = find_stream_id(&self.streams, &command.stream_id);
+                let stream = self
+                    .streams
+                    .get(&stream_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                let topic_id = find_topic_id(&stream.topics, &command.topic_id);
+                stream
+                    .topics
+                    .get(&topic_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
+                // It only affects the segments which are not part of the state
+            }
+            EntryCommand::CreatePartitions(command) => {
+                let stream_id = find_stream_id(&self.streams, &command.stream_id);
+                let stream = self
+                    .streams
+                    .get_mut(&stream_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                let topic_id = find_topic_id(&stream.topics, &command.topic_id);
+                let topic = stream
+                    .topics
+                    .get_mut(&topic_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
+                let last_partition_id = if topic.partitions.is_empty() {
+                    0
+                } else {
+                    topic
                         .partitions
                         .values()
                         .map(|p| p.id)
                         .max()
-                        .unwrap_or_else(|| panic!("No partition found"));
-                    for i in 0..command.partitions_count {
-                        topic.partitions.remove(&(last_partition_id - i));
-                    }
-                }
-                EntryCommand::CreateConsumerGroup(command) => {
-                    let stream_id = find_stream_id(&streams, &command.stream_id);
-                    let stream = streams
-                        .get_mut(&stream_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
-                    let topic_id = find_topic_id(&stream.topics, &command.topic_id);
-                    let topic = stream
-                        .topics
-                        .get_mut(&topic_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
-                    let consumer_group_id = command.group_id.unwrap_or_else(|| {
-                        topic.current_consumer_group_id += 1;
-                        topic.current_consumer_group_id
-                    });
-                    let consumer_group = ConsumerGroupState {
-                        id: consumer_group_id,
-                        name: command.name,
-                    };
-                    topic
-                        .consumer_groups
-                        .insert(consumer_group.id, consumer_group);
-                }
-                EntryCommand::DeleteConsumerGroup(command) => {
-                    let stream_id = find_stream_id(&streams, &command.stream_id);
-                    let stream = streams
-                        .get_mut(&stream_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
-                    let topic_id = find_topic_id(&stream.topics, &command.topic_id);
-                    let topic = stream
-                        .topics
-                        .get_mut(&topic_id)
-                        .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
-                    let consumer_group_id =
-                        find_consumer_group_id(&topic.consumer_groups, &command.group_id);
-                    topic.consumer_groups.remove(&consumer_group_id);
-                }
-                EntryCommand::CreateUser(command) => {
-                    current_user_id += 1;
-                    let user = UserState {
-                        id: current_user_id,
-                        username: command.username,
-                        password_hash: command.password, // This is already hashed
-                        status: command.status,
-                        permissions: command.permissions,
-                        personal_access_tokens: HashMap::new(),
-                    };
-                    users.insert(user.id, user);
+                        .unwrap_or_else(|| panic!("No partition found"))
+                };
+                for i in 1..=command.partitions_count {
+                    topic.partitions.insert(
+                        last_partition_id + i,
+                        PartitionState {
+                            id: last_partition_id + i,
+                            created_at: entry.timestamp,
+                        },
+                    );
                 }
-                EntryCommand::UpdateUser(command) => {
-                    let user_id = find_user_id(&users, &command.user_id);
-                    let user = users
-                        .get_mut(&user_id)
-                        .unwrap_or_else(|| panic!("{}", format!("User: {user_id} not found")));
-                    if let Some(username) = &command.username {
-                        user.username.clone_from(username);
-                    }
-                    if let Some(status) = &command.status {
-                        user.status = *status;
-                    }
+            }
+            EntryCommand::DeletePartitions(command) => {
+                let stream_id = find_stream_id(&self.streams, &command.stream_id);
+                let stream = self
+                    .streams
+                    .get_mut(&stream_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                let topic_id = find_topic_id(&stream.topics, &command.topic_id);
+                let topic = stream
+                    .topics
+                    .get_mut(&topic_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
+                if topic.partitions.is_empty() {
+                    return Ok(());
                 }
-                EntryCommand::DeleteUser(command) => {
-                    let user_id = find_user_id(&users, &command.user_id);
-                    users.remove(&user_id);
+
+                let last_partition_id = topic
+                    .partitions
+                    .values()
+                    .map(|p| p.id)
+                    .max()
+                    .unwrap_or_else(|| panic!("No partition found"));
+                for i in 0..command.partitions_count {
+                    topic.partitions.remove(&(last_partition_id - i));
                 }
-                EntryCommand::ChangePassword(command) => {
-                    let user_id = find_user_id(&users, &command.user_id);
-                    let user = users
-                        .get_mut(&user_id)
-                        .unwrap_or_else(|| panic!("{}", format!("User: {user_id} not found")));
-                    user.password_hash = command.new_password // This is already hashed
+            }
+            EntryCommand::CreateConsumerGroup(command) => {
+                let stream_id = find_stream_id(&self.streams, &command.stream_id);
+                let stream = self
+                    .streams
+                    .get_mut(&stream_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                let topic_id = find_topic_id(&stream.topics, &command.topic_id);
+                let topic = stream
+                    .topics
+                    .get_mut(&topic_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
+                let consumer_group_id = command.group_id.unwrap_or_else(|| {
+                    topic.current_consumer_group_id += 1;
+                    topic.current_consumer_group_id
+                });
+                let consumer_group = ConsumerGroupState {
+                    id: consumer_group_id,
+                    name: command.name,
+                };
+                topic
+                    .consumer_groups
+                    .insert(consumer_group.id, consumer_group);
+            }
+            EntryCommand::DeleteConsumerGroup(command) => {
+                let stream_id = find_stream_id(&self.streams, &command.stream_id);
+                let stream = self
+                    .streams
+                    .get_mut(&stream_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Stream: {stream_id} not found")));
+                let topic_id = find_topic_id(&stream.topics, &command.topic_id);
+                let topic = stream
+                    .topics
+                    .get_mut(&topic_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Topic: {topic_id} not found")));
+                let consumer_group_id =
+                    find_consumer_group_id(&topic.consumer_groups, &command.group_id);
+                topic.consumer_groups.remove(&consumer_group_id);
+            }
+            EntryCommand::CreateUser(command) => {
+                self.current_user_id += 1;
+                let user = UserState {
+                    id: self.current_user_id,
+                    username: command.username,
+                    password_hash: command.password, // This is already hashed
+                    password_history: Vec::new(),
+                    status: command.status,
+                    permissions: command.permissions,
+                    roles: Vec::new(),
+                    personal_access_tokens: HashMap::new(),
+                };
+                self.users.insert(user.id, user);
+            }
+            EntryCommand::UpdateUser(command) => {
+                let user_id = find_user_id(&self.users, &command.user_id);
+                let user = self
+                    .users
+                    .get_mut(&user_id)
+                    .unwrap_or_else(|| panic!("{}", format!("User: {user_id} not found")));
+                if let Some(username) = &command.username {
+                    user.username.clone_from(username);
                 }
-                EntryCommand::UpdatePermissions(command) => {
-                    let user_id = find_user_id(&users, &command.user_id);
-                    let user = users
-                        .get_mut(&user_id)
-                        .unwrap_or_else(|| panic!("{}", format!("User: {user_id} not found")));
-                    user.permissions = command.permissions;
+                if let Some(status) = &command.status {
+                    user.status = *status;
                 }
-                EntryCommand::CreatePersonalAccessToken(command) => {
-                    let token_hash = command.hash;
-                    let user_id = find_user_id(&users, &entry.user_id.try_into()?);
-                    let user = users
-                        .get_mut(&user_id)
-                        .unwrap_or_else(|| panic!("{}", format!("User: {user_id} not found")));
-                    let expiry_at = PersonalAccessToken::calculate_expiry_at(
-                        entry.timestamp,
-                        command.command.expiry,
-                    );
-                    if let Some(expiry_at) = expiry_at {
-                        if expiry_at.as_micros() <= IggyTimestamp::now().as_micros() {
-                            debug!("Personal access token: {token_hash} has already expired.");
-                            continue;
-                        }
+            }
+            EntryCommand::DeleteUser(command) => {
+                let user_id = find_user_id(&self.users, &command.user_id);
+                self.users.remove(&user_id);
+            }
+            EntryCommand::ChangePassword(command) => {
+                let user_id = find_user_id(&self.users, &command.user_id);
+                let user = self
+                    .users
+                    .get_mut(&user_id)
+                    .unwrap_or_else(|| panic!("{}", format!("User: {user_id} not found")));
+                let previous_password_hash =
+                    std::mem::replace(&mut user.password_hash, command.new_password); // This is already hashed
+                user.password_history.insert(0, previous_password_hash);
+            }
+            EntryCommand::RehashPassword(command) => {
+                let user = self.users.get_mut(&command.user_id).unwrap_or_else(|| {
+                    panic!("{}", format!("User: {} not found", command.user_id))
+                });
+                user.password_hash = command.password_hash;
+            }
+            EntryCommand::UpdatePermissions(command) => {
+                let user_id = find_user_id(&self.users, &command.user_id);
+                let user = self
+                    .users
+                    .get_mut(&user_id)
+                    .unwrap_or_else(|| panic!("{}", format!("User: {user_id} not found")));
+                user.permissions = command.permissions;
+            }
+            EntryCommand::CreatePersonalAccessToken(command) => {
+                let token_hash = command.hash;
+                let user_id = find_user_id(&self.users, &entry.user_id.try_into()?);
+                let user = self
+                    .users
+                    .get_mut(&user_id)
+                    .unwrap_or_else(|| panic!("{}", format!("User: {user_id} not found")));
+                let expiry_at = PersonalAccessToken::calculate_expiry_at(
+                    entry.timestamp,
+                    command.command.expiry,
+                );
+                if let Some(expiry_at) = expiry_at {
+                    if expiry_at.as_micros() <= IggyTimestamp::now().as_micros() {
+                        debug!("Personal access token: {token_hash} has already expired.");
+                        return Ok(());
                     }
+                }
 
-                    user.personal_access_tokens.insert(
-                        command.command.name.clone(),
-                        PersonalAccessTokenState {
-                            name: command.command.name,
-                            token_hash,
-                            expiry_at,
-                        },
-                    );
+                user.personal_access_tokens.insert(
+                    command.command.name.clone(),
+                    PersonalAccessTokenState {
+                        name: command.command.name,
+                        token_hash,
+                        expiry_at,
+                    },
+                );
+            }
+            EntryCommand::DeletePersonalAccessToken(command) => {
+                let user_id = find_user_id(&self.users, &entry.user_id.try_into()?);
+                let user = self
+                    .users
+                    .get_mut(&user_id)
+                    .unwrap_or_else(|| panic!("{}", format!("User: {user_id} not found")));
+                user.personal_access_tokens.remove(&command.name);
+            }
+            EntryCommand::CreateRole(command) => {
+                self.current_role_id = self.current_role_id.max(command.role_id);
+                let role = RoleState {
+                    id: command.role_id,
+                    name: command.command.name,
+                    created_at: entry.timestamp,
+                    permissions: command.command.permissions,
+                };
+                self.roles.insert(role.id, role);
+            }
+            EntryCommand::UpdateRole(command) => {
+                let role_id = find_role_id(&self.roles, &command.role_id);
+                let role = self
+                    .roles
+                    .get_mut(&role_id)
+                    .unwrap_or_else(|| panic!("{}", format!("Role: {role_id} not found")));
+                if let Some(name) = command.name {
+                    role.name = name;
+                }
+                if let Some(permissions) = command.permissions {
+                    role.permissions = Some(permissions);
                 }
-                EntryCommand::DeletePersonalAccessToken(command) => {
-                    let user_id = find_user_id(&users, &entry.user_id.try_into()?);
-                    let user = users
-                        .get_mut(&user_id)
-                        .unwrap_or_else(|| panic!("{}", format!("User: {user_id} not found")));
-                    user.personal_access_tokens.remove(&command.name);
+            }
+            EntryCommand::DeleteRole(command) => {
+                let role_id = find_role_id(&self.roles, &command.role_id);
+                self.roles.remove(&role_id);
+                for user in self.users.values_mut() {
+                    user.roles.retain(|id| *id != role_id);
                 }
             }
+            EntryCommand::UpdateRoles(command) => {
+                let user_id = find_user_id(&self.users, &command.user_id);
+                let user = self
+                    .users
+                    .get_mut(&user_id)
+                    .unwrap_or_else(|| panic!("{}", format!("User: {user_id} not found")));
+                user.roles = command.roles;
+            }
         }
+        Ok(())
+    }
 
-        let state = SystemState { streams, users };
-        debug!("+++ State +++");
-        debug!("{state}");
-        debug!("+++ State +++");
-        Ok(state)
+    fn remove_expired_personal_access_tokens(&mut self) {
+        let now = IggyTimestamp::now().as_micros();
+        for user in self.users.values_mut() {
+            user.personal_access_tokens.retain(|_, token| {
+                token
+                    .expiry_at
+                    .is_none_or(|expiry_at| expiry_at.as_micros() > now)
+            });
+        }
     }
 }
 
@@ -432,6 +535,24 @@ fn find_user_id(users: &HashMap<u32, UserState>, user_id: &Identifier) -> u32 {
     }
 }
 
+fn find_role_id(roles: &HashMap<u32, RoleState>, role_id: &Identifier) -> u32 {
+    match role_id.kind {
+        IdKind::Numeric => role_id
+            .get_u32_value()
+            .unwrap_or_else(|_| panic!("{}", format!("Invalid role ID: {role_id}"))),
+        IdKind::String => {
+            let name = role_id
+                .get_cow_str_value()
+                .unwrap_or_else(|_| panic!("{}", format!("Invalid role name: {role_id}")));
+            let role = roles
+                .values()
+                .find(|s| s.name == name)
+                .unwrap_or_else(|| panic!("{}", format!("Role: {name} not found")));
+            role.id
+        }
+    }
+}
+
 impl Display for SystemState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Streams:")?;
@@ -444,6 +565,11 @@ impl Display for SystemState {
             write!(f, "\n================\n")?;
             write!(f, "{}", user.1)?;
         }
+        write!(f, "Roles:")?;
+        for role in self.roles.iter() {
+            write!(f, "\n================\n")?;
+            write!(f, "{}", role.1)?;
+        }
         Ok(())
     }
 }
@@ -455,6 +581,27 @@ impl Display for ConsumerGroupState {
 }
 
 impl Display for UserState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let permissions = if let Some(permissions) = &self.permissions {
+            permissions.to_string()
+        } else {
+            "no_permissions".to_string()
+        };
+        let roles = self
+            .roles
+            .iter()
+            .map(|role_id| role_id.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+        write!(
+            f,
+            "User -> ID: {}, Username: {}, Status: {}, Permissions: {}, Roles: {}",
+            self.id, self.username, self.status, permissions, roles
+        )
+    }
+}
+
+impl Display for RoleState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let permissions = if let Some(permissions) = &self.permissions {
             permissions.to_string()
@@ -463,8 +610,8 @@ impl Display for UserState {
         };
         write!(
             f,
-            "User -> ID: {}, Username: {}, Status: {}, Permissions: {}",
-            self.id, self.username, self.status, permissions
+            "Role -> ID: {}, Name: {}, Permissions: {}",
+            self.id, self.name, permissions
         )
     }
 }
@@ -2,3 +2,4 @@ pub mod appendable_batch_info;
 pub mod batch_filter;
 pub mod iterator;
 pub mod message_batch;
+pub mod polled_batches;
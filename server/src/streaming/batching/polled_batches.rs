@@ -0,0 +1,234 @@
+use crate::streaming::batching::message_batch::RetainedMessageBatch;
+use crate::streaming::models::messages::RetainedMessage;
+use crate::streaming::sizeable::Sizeable;
+use bytes::{BufMut, Bytes, BytesMut};
+use iggy::error::IggyError;
+use iggy::models::messages::PolledMessages;
+use std::fs::File;
+use std::io::SeekFrom;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+/// Part of the polled batches, containing the whole message batches in the same format as they're stored in the segment file.
+#[derive(Debug)]
+pub enum BatchesChunk {
+    /// The batches encoded in memory, e.g. from the cache or the unsaved buffer of the segment.
+    Memory(Bytes),
+    /// The region of the segment file, which can be sent directly from the file, without loading it into memory.
+    File {
+        file: File,
+        position: u64,
+        length: u64,
+    },
+}
+
+impl BatchesChunk {
+    pub fn from_batches<'a>(
+        batches: impl IntoIterator<Item = &'a Arc<RetainedMessageBatch>>,
+    ) -> Self {
+        let mut bytes = BytesMut::new();
+        for batch in batches {
+            batch.extend(&mut bytes);
+        }
+        BatchesChunk::Memory(bytes.freeze())
+    }
+
+    /// Encodes the messages with the consecutive offsets as a single batch.
+    pub fn from_messages(messages: &[RetainedMessage]) -> Self {
+        let (Some(first_message), Some(last_message)) = (messages.first(), messages.last()) else {
+            return BatchesChunk::Memory(Bytes::new());
+        };
+
+        let mut bytes = BytesMut::new();
+        let mut max_timestamp = 0;
+        for message in messages {
+            max_timestamp = max_timestamp.max(message.timestamp);
+            message.extend(&mut bytes);
+        }
+        let batch = RetainedMessageBatch::new(
+            first_message.offset,
+            (last_message.offset - first_message.offset) as u32,
+            max_timestamp,
+            bytes.len() as u32,
+            bytes.freeze(),
+        );
+        let mut bytes = BytesMut::with_capacity(batch.get_size_bytes() as usize);
+        batch.extend(&mut bytes);
+        BatchesChunk::Memory(bytes.freeze())
+    }
+
+    pub fn len(&self) -> u64 {
+        match self {
+            BatchesChunk::Memory(bytes) => bytes.len() as u64,
+            BatchesChunk::File { length, .. } => *length,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends the batches to the buffer, the region of the file is read at its position.
+    pub async fn read_into(self, buffer: &mut BytesMut) -> Result<(), IggyError> {
+        match self {
+            BatchesChunk::Memory(bytes) => buffer.put_slice(&bytes),
+            BatchesChunk::File {
+                file,
+                position,
+                length,
+            } => {
+                let mut file = tokio::fs::File::from_std(file);
+                file.seek(SeekFrom::Start(position)).await?;
+                let start = buffer.len();
+                buffer.put_bytes(0, length as usize);
+                file.read_exact(&mut buffer[start..]).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The messages polled from the partition as the whole batches, in the same format as stored in the segment files,
+/// so the persisted ones can be sent to the client directly from the files. The first and the last batch might contain
+/// the messages outside of the polled range, the client skips the messages before `first_offset` and reads at most
+/// `messages_count` messages.
+#[derive(Debug)]
+pub struct PolledBatches {
+    pub partition_id: u32,
+    pub current_offset: u64,
+    pub first_offset: u64,
+    pub messages_count: u32,
+    pub chunks: Vec<BatchesChunk>,
+}
+
+impl PolledBatches {
+    pub fn empty(partition_id: u32, current_offset: u64) -> Self {
+        Self {
+            partition_id,
+            current_offset,
+            first_offset: 0,
+            messages_count: 0,
+            chunks: Vec::new(),
+        }
+    }
+
+    /// Encodes the messages with the consecutive offsets as a single batch.
+    pub fn from_messages(
+        partition_id: u32,
+        current_offset: u64,
+        messages: &[RetainedMessage],
+    ) -> Self {
+        let mut polled_batches = PolledBatches::empty(partition_id, current_offset);
+        if let Some(first_message) = messages.first() {
+            polled_batches.first_offset = first_message.offset;
+            polled_batches.messages_count = messages.len() as u32;
+            polled_batches
+                .chunks
+                .push(BatchesChunk::from_messages(messages));
+        }
+        polled_batches
+    }
+
+    pub fn get_last_offset(&self) -> Option<u64> {
+        match self.messages_count {
+            0 => None,
+            count => Some(self.first_offset + count as u64 - 1),
+        }
+    }
+
+    pub fn get_size_bytes(&self) -> u64 {
+        self.chunks.iter().map(|chunk| chunk.len()).sum()
+    }
+}
+
+/// Encodes the polled messages (e.g. decrypted) as a single batch.
+impl From<PolledMessages> for PolledBatches {
+    fn from(polled_messages: PolledMessages) -> Self {
+        let messages = polled_messages
+            .messages
+            .iter()
+            .map(RetainedMessage::from)
+            .collect::<Vec<_>>();
+        PolledBatches::from_messages(
+            polled_messages.partition_id,
+            polled_messages.current_offset,
+            &messages,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::batching::iterator::IntoMessagesIterator;
+    use iggy::models::messages::{MessageState, PolledMessage};
+    use iggy::utils::checksum;
+    use iggy::utils::timestamp::IggyTimestamp;
+
+    #[tokio::test]
+    async fn polled_messages_should_be_encoded_as_single_batch_in_segment_format() {
+        let messages = (5..8).map(create_message).collect::<Vec<_>>();
+        let polled_batches = PolledBatches::from(PolledMessages {
+            partition_id: 1,
+            current_offset: 10,
+            messages,
+        });
+
+        assert_eq!(polled_batches.first_offset, 5);
+        assert_eq!(polled_batches.messages_count, 3);
+        assert_eq!(polled_batches.get_last_offset(), Some(7));
+        assert_eq!(polled_batches.chunks.len(), 1);
+
+        let size_bytes = polled_batches.get_size_bytes();
+        let mut bytes = BytesMut::new();
+        for chunk in polled_batches.chunks {
+            chunk.read_into(&mut bytes).await.unwrap();
+        }
+        assert_eq!(bytes.len() as u64, size_bytes);
+
+        let batch = RetainedMessageBatch::new(
+            u64::from_le_bytes(bytes[..8].try_into().unwrap()),
+            u32::from_le_bytes(bytes[12..16].try_into().unwrap()),
+            u64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+            u32::from_le_bytes(bytes[8..12].try_into().unwrap()),
+            bytes.freeze().slice(24..),
+        );
+        assert_eq!(batch.base_offset, 5);
+        assert_eq!(batch.get_last_offset(), 7);
+        let messages = batch.into_messages_iter().collect::<Vec<_>>();
+        assert_eq!(messages.len(), 3);
+        for (index, message) in messages.iter().enumerate() {
+            assert_eq!(message.offset, 5 + index as u64);
+            assert_eq!(
+                message.payload,
+                Bytes::from(format!("message {}", 5 + index))
+            );
+        }
+    }
+
+    #[test]
+    fn empty_polled_messages_should_not_contain_any_batches() {
+        let polled_batches = PolledBatches::from(PolledMessages {
+            partition_id: 1,
+            current_offset: 10,
+            messages: Vec::new(),
+        });
+
+        assert_eq!(polled_batches.messages_count, 0);
+        assert_eq!(polled_batches.get_last_offset(), None);
+        assert!(polled_batches.chunks.is_empty());
+    }
+
+    fn create_message(offset: u64) -> PolledMessage {
+        let payload = Bytes::from(format!("message {}", offset));
+        PolledMessage::create(
+            offset,
+            MessageState::Available,
+            IggyTimestamp::now(),
+            offset as u128,
+            payload.clone(),
+            checksum::calculate(&payload),
+            None,
+        )
+    }
+}
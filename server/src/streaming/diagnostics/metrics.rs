@@ -1,12 +1,84 @@
 use prometheus_client::encoding::text::encode;
+use prometheus_client::encoding::EncodeLabelSet;
 use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
 use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::metrics::histogram::{exponential_buckets, Histogram};
 use prometheus_client::registry::Registry;
+use std::sync::atomic::AtomicU64;
+use std::time::Duration;
 use tracing::error;
 
+pub(crate) const TCP_TRANSPORT: &str = "tcp";
+pub(crate) const QUIC_TRANSPORT: &str = "quic";
+pub(crate) const HTTP_TRANSPORT: &str = "http";
+
+type HistogramFamily<S> = Family<S, Histogram, fn() -> Histogram>;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct PartitionLabels {
+    stream_id: u32,
+    topic_id: u32,
+    partition_id: u32,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct TransportLabels {
+    transport: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ConsumerGroupLabels {
+    stream_id: u32,
+    topic_id: u32,
+    partition_id: u32,
+    consumer_group_id: u32,
+}
+
+/// `StorageMetrics` are recorded by the storage layer (segments, partitions and persisters),
+/// which is shared across the streaming components via `SystemStorage`.
+#[derive(Debug, Clone)]
+pub struct StorageMetrics {
+    fsync_duration: Histogram,
+    segment_persist_duration: Histogram,
+    cache_hits: Counter,
+    cache_misses: Counter,
+}
+
+impl Default for StorageMetrics {
+    fn default() -> Self {
+        Self {
+            fsync_duration: create_duration_histogram(),
+            segment_persist_duration: create_duration_histogram(),
+            cache_hits: Counter::default(),
+            cache_misses: Counter::default(),
+        }
+    }
+}
+
+impl StorageMetrics {
+    pub fn observe_fsync_duration(&self, duration: Duration) {
+        self.fsync_duration.observe(duration.as_secs_f64());
+    }
+
+    pub fn observe_segment_persist_duration(&self, duration: Duration) {
+        self.segment_persist_duration
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn increment_cache_hits(&self) {
+        self.cache_hits.inc();
+    }
+
+    pub fn increment_cache_misses(&self) {
+        self.cache_misses.inc();
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct Metrics {
     registry: Registry,
+    storage: StorageMetrics,
     http_requests: Counter,
     streams: Gauge,
     topics: Gauge,
@@ -15,12 +87,21 @@ pub(crate) struct Metrics {
     messages: Gauge,
     users: Gauge,
     clients: Gauge,
+    messages_sent: Family<PartitionLabels, Counter>,
+    bytes_sent: Family<PartitionLabels, Counter>,
+    messages_polled: Family<PartitionLabels, Counter>,
+    bytes_polled: Family<PartitionLabels, Counter>,
+    send_messages_latency: HistogramFamily<TransportLabels>,
+    poll_messages_latency: HistogramFamily<TransportLabels>,
+    cache_hit_ratio: Gauge<f64, AtomicU64>,
+    consumer_group_lag: Family<ConsumerGroupLabels, Gauge>,
 }
 
 impl Metrics {
-    pub fn init() -> Self {
+    pub fn init(storage: &StorageMetrics) -> Self {
         let mut metrics = Metrics {
             registry: <Registry>::default(),
+            storage: storage.clone(),
             http_requests: Counter::default(),
             streams: Gauge::default(),
             topics: Gauge::default(),
@@ -29,6 +110,14 @@ impl Metrics {
             messages: Gauge::default(),
             users: Gauge::default(),
             clients: Gauge::default(),
+            messages_sent: Family::default(),
+            bytes_sent: Family::default(),
+            messages_polled: Family::default(),
+            bytes_polled: Family::default(),
+            send_messages_latency: Family::new_with_constructor(create_duration_histogram),
+            poll_messages_latency: Family::new_with_constructor(create_duration_histogram),
+            cache_hit_ratio: Gauge::default(),
+            consumer_group_lag: Family::default(),
         };
 
         metrics.register_counter("http_requests", metrics.http_requests.clone());
@@ -39,6 +128,66 @@ impl Metrics {
         metrics.register_gauge("messages", metrics.messages.clone());
         metrics.register_gauge("users", metrics.users.clone());
         metrics.register_gauge("clients", metrics.clients.clone());
+        metrics.registry.register(
+            "messages_sent",
+            "total count of messages sent per partition",
+            metrics.messages_sent.clone(),
+        );
+        metrics.registry.register(
+            "bytes_sent",
+            "total size of message payloads sent per partition",
+            metrics.bytes_sent.clone(),
+        );
+        metrics.registry.register(
+            "messages_polled",
+            "total count of messages polled per partition",
+            metrics.messages_polled.clone(),
+        );
+        metrics.registry.register(
+            "bytes_polled",
+            "total size of message payloads polled per partition",
+            metrics.bytes_polled.clone(),
+        );
+        metrics.registry.register(
+            "send_messages_latency_seconds",
+            "latency of handling the send messages command per transport",
+            metrics.send_messages_latency.clone(),
+        );
+        metrics.registry.register(
+            "poll_messages_latency_seconds",
+            "latency of handling the poll messages command per transport",
+            metrics.poll_messages_latency.clone(),
+        );
+        metrics.registry.register(
+            "fsync_duration_seconds",
+            "duration of the fsync calls",
+            metrics.storage.fsync_duration.clone(),
+        );
+        metrics.registry.register(
+            "segment_persist_duration_seconds",
+            "duration of persisting the messages to the segments",
+            metrics.storage.segment_persist_duration.clone(),
+        );
+        metrics.registry.register(
+            "cache_hits",
+            "total count of messages reads served from the cache",
+            metrics.storage.cache_hits.clone(),
+        );
+        metrics.registry.register(
+            "cache_misses",
+            "total count of messages reads not served from the cache",
+            metrics.storage.cache_misses.clone(),
+        );
+        metrics.registry.register(
+            "cache_hit_ratio",
+            "ratio of messages reads served from the cache",
+            metrics.cache_hit_ratio.clone(),
+        );
+        metrics.registry.register(
+            "consumer_group_lag",
+            "count of messages not yet consumed by the consumer group per partition",
+            metrics.consumer_group_lag.clone(),
+        );
 
         metrics
     }
@@ -54,6 +203,12 @@ impl Metrics {
     }
 
     pub fn get_formatted_output(&self) -> String {
+        let hits = self.storage.cache_hits.get();
+        let lookups = hits + self.storage.cache_misses.get();
+        if lookups > 0 {
+            self.cache_hit_ratio.set(hits as f64 / lookups as f64);
+        }
+
         let mut buffer = String::new();
         if let Err(err) = encode(&mut buffer, &self.registry) {
             error!("Failed to encode metrics: {}", err);
@@ -120,4 +275,138 @@ impl Metrics {
     pub fn decrement_clients(&self, count: u32) {
         self.clients.dec_by(count as i64);
     }
+
+    pub fn increment_sent_messages(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+        messages_count: u64,
+        size_bytes: u64,
+    ) {
+        let labels = PartitionLabels {
+            stream_id,
+            topic_id,
+            partition_id,
+        };
+        self.messages_sent
+            .get_or_create(&labels)
+            .inc_by(messages_count);
+        self.bytes_sent.get_or_create(&labels).inc_by(size_bytes);
+    }
+
+    pub fn increment_polled_messages(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+        messages_count: u64,
+        size_bytes: u64,
+    ) {
+        let labels = PartitionLabels {
+            stream_id,
+            topic_id,
+            partition_id,
+        };
+        self.messages_polled
+            .get_or_create(&labels)
+            .inc_by(messages_count);
+        self.bytes_polled.get_or_create(&labels).inc_by(size_bytes);
+    }
+
+    /// Removes the metrics labelled with the given partition, so that the deleted partitions are no longer reported.
+    pub fn remove_partition(&self, stream_id: u32, topic_id: u32, partition_id: u32) {
+        let labels = PartitionLabels {
+            stream_id,
+            topic_id,
+            partition_id,
+        };
+        for family in [
+            &self.messages_sent,
+            &self.bytes_sent,
+            &self.messages_polled,
+            &self.bytes_polled,
+        ] {
+            family.remove(&labels);
+        }
+    }
+
+    pub fn observe_send_messages_latency(&self, transport: &'static str, latency: Duration) {
+        self.send_messages_latency
+            .get_or_create(&TransportLabels { transport })
+            .observe(latency.as_secs_f64());
+    }
+
+    pub fn observe_poll_messages_latency(&self, transport: &'static str, latency: Duration) {
+        self.poll_messages_latency
+            .get_or_create(&TransportLabels { transport })
+            .observe(latency.as_secs_f64());
+    }
+
+    /// Removes the previously set consumer group lags, so that the deleted consumer groups are no longer reported.
+    pub fn clear_consumer_group_lags(&self) {
+        self.consumer_group_lag.clear();
+    }
+
+    pub fn set_consumer_group_lag(
+        &self,
+        stream_id: u32,
+        topic_id: u32,
+        partition_id: u32,
+        consumer_group_id: u32,
+        lag: u64,
+    ) {
+        self.consumer_group_lag
+            .get_or_create(&ConsumerGroupLabels {
+                stream_id,
+                topic_id,
+                partition_id,
+                consumer_group_id,
+            })
+            .set(lag as i64);
+    }
+}
+
+/// Creates the histogram with the buckets from 100 µs to ~3.3 s.
+fn create_duration_histogram() -> Histogram {
+    Histogram::new(exponential_buckets(0.0001, 2.0, 16))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labelled_metrics_should_be_included_in_formatted_output() {
+        let storage = StorageMetrics::default();
+        let metrics = Metrics::init(&storage);
+        metrics.increment_sent_messages(1, 2, 3, 10, 1000);
+        metrics.observe_poll_messages_latency(TCP_TRANSPORT, Duration::from_millis(5));
+        metrics.set_consumer_group_lag(1, 2, 3, 4, 7);
+        storage.increment_cache_hits();
+        storage.increment_cache_hits();
+        storage.increment_cache_hits();
+        storage.increment_cache_misses();
+
+        let output = metrics.get_formatted_output();
+        assert!(output
+            .contains(r#"messages_sent_total{stream_id="1",topic_id="2",partition_id="3"} 10"#));
+        assert!(output
+            .contains(r#"bytes_sent_total{stream_id="1",topic_id="2",partition_id="3"} 1000"#));
+        assert!(output.contains(r#"poll_messages_latency_seconds_count{transport="tcp"} 1"#));
+        assert!(output.contains(
+            r#"consumer_group_lag{stream_id="1",topic_id="2",partition_id="3",consumer_group_id="4"} 7"#
+        ));
+        assert!(output.contains("cache_hit_ratio 0.75"));
+
+        metrics.clear_consumer_group_lags();
+        assert!(!metrics
+            .get_formatted_output()
+            .contains("consumer_group_lag{"));
+
+        metrics.remove_partition(1, 2, 3);
+        let output = metrics.get_formatted_output();
+        assert!(!output.contains("messages_sent_total{"));
+        assert!(!output.contains("bytes_sent_total{"));
+    }
 }
@@ -46,6 +46,20 @@ impl TryFrom<RetainedMessage> for PolledMessage {
     }
 }
 
+impl From<&PolledMessage> for RetainedMessage {
+    fn from(value: &PolledMessage) -> Self {
+        RetainedMessage {
+            id: value.id,
+            offset: value.offset,
+            timestamp: value.timestamp,
+            checksum: value.checksum,
+            message_state: value.state,
+            headers: value.headers.as_ref().map(|headers| headers.to_bytes()),
+            payload: value.payload.clone(),
+        }
+    }
+}
+
 impl RetainedMessage {
     pub fn new(offset: u64, timestamp: u64, message: Message) -> Self {
         RetainedMessage {
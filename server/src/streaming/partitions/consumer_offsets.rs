@@ -1,9 +1,11 @@
 use crate::streaming::partitions::partition::{ConsumerOffset, Partition};
 use crate::streaming::polling_consumer::PollingConsumer;
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 use iggy::consumer::ConsumerKind;
 use iggy::error::IggyError;
-use tracing::trace;
+use iggy::models::consumer_lag_info::ConsumerLagInfo;
+use std::sync::atomic::Ordering;
+use tracing::{info, trace};
 
 impl Partition {
     pub async fn get_consumer_offset(&self, consumer: PollingConsumer) -> Result<u64, IggyError> {
@@ -32,6 +34,47 @@ impl Partition {
         Ok(0)
     }
 
+    /// Returns the lag of the consumer or consumer group, which is the number (and size) of the messages
+    /// in the partition which were not yet consumed, based on the stored offset.
+    /// When there's no stored offset, all the available messages are counted.
+    pub async fn get_consumer_lag(
+        &self,
+        kind: ConsumerKind,
+        consumer_id: u32,
+    ) -> Result<ConsumerLagInfo, IggyError> {
+        let stored_offset = self
+            .get_consumer_offsets(kind)
+            .get(&consumer_id)
+            .map(|consumer_offset| consumer_offset.offset);
+        let mut lag = 0;
+        let mut lag_bytes = 0;
+        if self.should_increment_offset {
+            let first_offset = self
+                .segments
+                .first()
+                .map(|segment| segment.start_offset)
+                .unwrap_or_default();
+            let start_offset = stored_offset
+                .map(|offset| offset + 1)
+                .unwrap_or_default()
+                .max(first_offset);
+            if start_offset <= self.current_offset {
+                lag = self.current_offset - start_offset + 1;
+                for segment in self.segments.iter() {
+                    lag_bytes += segment.get_size_bytes_from_offset(start_offset).await?;
+                }
+            }
+        }
+
+        Ok(ConsumerLagInfo {
+            partition_id: self.partition_id,
+            current_offset: self.current_offset,
+            stored_offset,
+            lag,
+            lag_bytes,
+        })
+    }
+
     pub async fn store_consumer_offset(
         &self,
         consumer: PollingConsumer,
@@ -68,26 +111,125 @@ impl Partition {
         consumer_id: u32,
         offset: u64,
     ) -> Result<(), IggyError> {
-        let consumer_offsets = self.get_consumer_offsets(kind);
-        if let Some(mut consumer_offset) = consumer_offsets.get_mut(&consumer_id) {
-            consumer_offset.offset = offset;
+        let consumer_offset = ConsumerOffset::new(kind, consumer_id, offset);
+        if self.config.consumer_offsets.flush_interval.is_zero() {
             self.storage
                 .partition
-                .save_consumer_offset(&consumer_offset)
+                .save_consumer_offsets(
+                    &self.consumer_offsets_log_path,
+                    std::slice::from_ref(&consumer_offset),
+                )
                 .await?;
+            self.consumer_offsets_log_entries
+                .fetch_add(1, Ordering::SeqCst);
+            self.get_consumer_offsets(kind)
+                .insert(consumer_id, consumer_offset);
+            return Ok(());
+        }
+
+        self.get_consumer_offsets(kind)
+            .insert(consumer_id, consumer_offset);
+        self.get_unsaved_consumer_offsets(kind).insert(consumer_id);
+        Ok(())
+    }
+
+    /// Appends the latest offsets of the consumers and consumer groups stored since the previous flush
+    /// to the offsets log, and returns the number of the saved offsets.
+    pub async fn flush_consumer_offsets(&self) -> Result<usize, IggyError> {
+        let mut consumer_offsets = Vec::new();
+        for kind in [ConsumerKind::Consumer, ConsumerKind::ConsumerGroup] {
+            let unsaved_consumer_offsets = self.get_unsaved_consumer_offsets(kind);
+            let consumer_ids = unsaved_consumer_offsets
+                .iter()
+                .map(|consumer_id| *consumer_id)
+                .collect::<Vec<_>>();
+            for consumer_id in consumer_ids {
+                // The ID is removed before reading the offset, so the offset stored in the meantime is never lost.
+                unsaved_consumer_offsets.remove(&consumer_id);
+                if let Some(consumer_offset) = self.get_consumer_offsets(kind).get(&consumer_id) {
+                    consumer_offsets.push(consumer_offset.clone());
+                }
+            }
+        }
+
+        if consumer_offsets.is_empty() {
+            return Ok(0);
+        }
+
+        if let Err(error) = self
+            .storage
+            .partition
+            .save_consumer_offsets(&self.consumer_offsets_log_path, &consumer_offsets)
+            .await
+        {
+            for consumer_offset in &consumer_offsets {
+                self.get_unsaved_consumer_offsets(consumer_offset.kind)
+                    .insert(consumer_offset.consumer_id);
+            }
+            return Err(error);
+        }
+
+        self.consumer_offsets_log_entries
+            .fetch_add(consumer_offsets.len() as u64, Ordering::SeqCst);
+        Ok(consumer_offsets.len())
+    }
+
+    /// Rewrites the offsets log with only the latest offsets, if the number of its stale entries
+    /// reached the compaction threshold, and returns whether the log was compacted.
+    pub async fn compact_consumer_offsets(&mut self) -> Result<bool, IggyError> {
+        let mut consumer_offsets = self
+            .consumer_offsets
+            .iter()
+            .chain(self.consumer_group_offsets.iter())
+            .map(|consumer_offset| consumer_offset.clone())
+            .collect::<Vec<_>>();
+        let entries_count = self.consumer_offsets_log_entries.load(Ordering::SeqCst);
+        let stale_entries_count = entries_count.saturating_sub(consumer_offsets.len() as u64);
+        if stale_entries_count < self.config.consumer_offsets.compaction_threshold {
+            return Ok(false);
+        }
+
+        consumer_offsets.sort_by_key(|consumer_offset| {
+            (consumer_offset.kind.as_code(), consumer_offset.consumer_id)
+        });
+        self.storage
+            .partition
+            .compact_consumer_offsets(&self.consumer_offsets_log_path, &consumer_offsets)
+            .await?;
+        // The offsets stored in memory are all saved in the compacted log.
+        self.unsaved_consumer_offsets.clear();
+        self.unsaved_consumer_group_offsets.clear();
+        self.consumer_offsets_log_entries
+            .store(consumer_offsets.len() as u64, Ordering::SeqCst);
+        info!("Compacted consumer offsets log for partition with ID: {} for topic with ID: {} and stream with ID: {}, removed {} stale entries.",
+            self.partition_id, self.topic_id, self.stream_id, stale_entries_count);
+        Ok(true)
+    }
+
+    pub async fn delete_consumer_group_offset(
+        &self,
+        consumer_group_id: u32,
+    ) -> Result<(), IggyError> {
+        self.unsaved_consumer_group_offsets
+            .remove(&consumer_group_id);
+        if self
+            .consumer_group_offsets
+            .remove(&consumer_group_id)
+            .is_none()
+        {
             return Ok(());
         }
 
-        let path = match kind {
-            ConsumerKind::Consumer => &self.consumer_offsets_path,
-            ConsumerKind::ConsumerGroup => &self.consumer_group_offsets_path,
-        };
-        let consumer_offset = ConsumerOffset::new(kind, consumer_id, offset, path);
         self.storage
             .partition
-            .save_consumer_offset(&consumer_offset)
+            .delete_consumer_offset(
+                &self.consumer_offsets_log_path,
+                ConsumerKind::ConsumerGroup,
+                consumer_group_id,
+            )
             .await?;
-        consumer_offsets.insert(consumer_id, consumer_offset);
+        self.consumer_offsets_log_entries
+            .fetch_add(1, Ordering::SeqCst);
         Ok(())
     }
 
@@ -98,30 +240,19 @@ impl Partition {
                 self.topic_id,
                 self.stream_id
             );
-        self.load_consumer_offsets_from_storage(ConsumerKind::Consumer)
-            .await?;
-        self.load_consumer_offsets_from_storage(ConsumerKind::ConsumerGroup)
-            .await
-    }
-
-    async fn load_consumer_offsets_from_storage(
-        &self,
-        kind: ConsumerKind,
-    ) -> Result<(), IggyError> {
-        let path = match kind {
-            ConsumerKind::Consumer => &self.consumer_offsets_path,
-            ConsumerKind::ConsumerGroup => &self.consumer_group_offsets_path,
-        };
-        let loaded_consumer_offsets = self
+        let consumer_offsets_log = self
             .storage
             .partition
-            .load_consumer_offsets(kind, path)
+            .load_consumer_offsets(&self.consumer_offsets_log_path)
             .await?;
-        let consumer_offsets = self.get_consumer_offsets(kind);
-        for consumer_offset in loaded_consumer_offsets {
+        for consumer_offset in consumer_offsets_log.offsets {
             self.log_consumer_offset(&consumer_offset);
-            consumer_offsets.insert(consumer_offset.consumer_id, consumer_offset);
+            self.get_consumer_offsets(consumer_offset.kind)
+                .insert(consumer_offset.consumer_id, consumer_offset);
         }
+        self.consumer_offsets_log_entries
+            .store(consumer_offsets_log.entries_count, Ordering::SeqCst);
+        self.compact_consumer_offsets().await?;
         Ok(())
     }
 
@@ -132,6 +263,13 @@ impl Partition {
         }
     }
 
+    fn get_unsaved_consumer_offsets(&self, kind: ConsumerKind) -> &DashSet<u32> {
+        match kind {
+            ConsumerKind::Consumer => &self.unsaved_consumer_offsets,
+            ConsumerKind::ConsumerGroup => &self.unsaved_consumer_group_offsets,
+        }
+    }
+
     fn log_consumer_offset(&self, consumer_offset: &ConsumerOffset) {
         trace!("Loaded consumer offset value: {} for {} with ID: {} for partition with ID: {} for topic with ID: {} and stream with ID: {}.",
                 consumer_offset.offset,
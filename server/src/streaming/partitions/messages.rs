@@ -1,10 +1,12 @@
 use crate::streaming::batching::appendable_batch_info::AppendableBatchInfo;
 use crate::streaming::batching::batch_filter::BatchItemizer;
 use crate::streaming::batching::message_batch::RetainedMessageBatch;
+use crate::streaming::batching::polled_batches::{BatchesChunk, PolledBatches};
 use crate::streaming::models::messages::RetainedMessage;
 use crate::streaming::partitions::partition::Partition;
 use crate::streaming::polling_consumer::PollingConsumer;
 use crate::streaming::segments::segment::Segment;
+use crate::streaming::segments::time_index::find_time_index;
 use bytes::BytesMut;
 use iggy::messages::send_messages::Message;
 use iggy::models::messages::POLLED_MESSAGE_METADATA;
@@ -38,42 +40,24 @@ impl Partition {
 
         let timestamp = timestamp.as_micros();
         let mut found_index = None;
-        let mut start_offset = self.segments.last().unwrap().start_offset;
-        // Since index cache is configurable globally, not per segment we can handle it this way.
-        let cache_time_index = self.config.segment.cache_time_indexes;
-        if !cache_time_index {
-            for segment in self.segments.iter() {
-                let time_index = segment
-                    .storage
-                    .as_ref()
-                    .segment
-                    .try_load_time_index_for_timestamp(segment, timestamp)
-                    .await?;
-                if time_index.is_none() {
-                    continue;
-                } else {
-                    found_index = time_index;
-                    start_offset =
-                        segment.start_offset + found_index.unwrap().relative_offset as u64;
-                    break;
-                }
-            }
-        } else {
-            start_offset = self.segments.first().unwrap().start_offset;
-            for segment in self.segments.iter().rev() {
-                let time_indexes = segment.time_indexes.as_ref().unwrap();
-                let time_index = time_indexes
-                    .iter()
-                    .rposition(|time_index| time_index.timestamp <= timestamp)
-                    .map(|idx| time_indexes[idx]);
-                if time_index.is_none() {
-                    continue;
-                } else {
-                    found_index = time_index;
-                    start_offset =
-                        segment.start_offset + found_index.unwrap().relative_offset as u64;
-                    break;
+        let mut start_offset = self.segments.first().unwrap().start_offset;
+        // The last indexed batch not newer than the timestamp is searched for, starting from the newest segment,
+        // as the messages with the newer timestamps can only be found after it.
+        for segment in self.segments.iter().rev() {
+            let time_index = match &segment.time_indexes {
+                Some(time_indexes) => find_time_index(time_indexes.as_slice(), timestamp),
+                None => {
+                    segment
+                        .storage
+                        .segment
+                        .try_load_time_index_for_timestamp(segment, timestamp)
+                        .await?
                 }
+            };
+            if let Some(time_index) = time_index {
+                found_index = Some(time_index);
+                start_offset = segment.start_offset + time_index.relative_offset as u64;
+                break;
             }
         }
 
@@ -83,28 +67,37 @@ impl Partition {
             start_offset,
             timestamp
         );
-        if found_index.timestamp == timestamp {
-            return Ok(self
-                .get_messages_by_offset(start_offset, count)
-                .await?
-                .into_iter()
-                .filter(|msg| msg.timestamp >= timestamp)
-                .take(count as usize)
-                .collect());
-        }
 
-        let adjusted_count = self.calculate_adjusted_timestamp_message_count(
+        // As the index is sparse, the messages are fetched from the indexed batch until enough of them
+        // have the requested timestamp, or there are no more messages.
+        let fetch_count = self.calculate_adjusted_timestamp_message_count(
             count,
             timestamp,
             found_index.timestamp,
         );
-        Ok(self
-            .get_messages_by_offset(start_offset, adjusted_count)
-            .await?
-            .into_iter()
-            .filter(|msg| msg.timestamp >= timestamp)
-            .take(count as usize)
-            .collect())
+        let mut messages = Vec::with_capacity(count as usize);
+        let mut offset = start_offset;
+        while messages.len() < count as usize {
+            let fetched_messages = self.get_messages_by_offset(offset, fetch_count).await?;
+            let Some(last_message) = fetched_messages.last() else {
+                break;
+            };
+
+            offset = last_message.offset + 1;
+            let is_last_fetch = fetched_messages.len() < fetch_count as usize;
+            let remaining_count = count as usize - messages.len();
+            messages.extend(
+                fetched_messages
+                    .into_iter()
+                    .filter(|msg| msg.timestamp >= timestamp)
+                    .take(remaining_count),
+            );
+            if is_last_fetch {
+                break;
+            }
+        }
+
+        Ok(messages)
     }
 
     fn calculate_adjusted_timestamp_message_count(
@@ -146,9 +139,14 @@ impl Partition {
 
         let messages = self.try_get_messages_from_cache(start_offset, end_offset);
         if let Some(messages) = messages {
+            self.storage.metrics.increment_cache_hits();
             return Ok(messages);
         }
 
+        if self.cache.is_some() {
+            self.storage.metrics.increment_cache_misses();
+        }
+
         let segments = self.filter_segments_by_offsets(start_offset, end_offset);
         match segments.len() {
             0 => Ok(EMPTY_MESSAGES),
@@ -162,14 +160,8 @@ impl Partition {
     }
 
     pub async fn get_last_messages(&self, count: u32) -> Result<Vec<RetainedMessage>, IggyError> {
-        let mut count = count as u64;
-        if count > self.current_offset + 1 {
-            count = self.current_offset + 1
-        }
-
-        let start_offset = 1 + self.current_offset - count;
-        self.get_messages_by_offset(start_offset, count as u32)
-            .await
+        let (start_offset, count) = self.get_last_messages_range(count);
+        self.get_messages_by_offset(start_offset, count).await
     }
 
     pub async fn get_next_messages(
@@ -177,6 +169,103 @@ impl Partition {
         consumer: PollingConsumer,
         count: u32,
     ) -> Result<Vec<RetainedMessage>, IggyError> {
+        match self.get_next_offset(consumer) {
+            Some(offset) => self.get_messages_by_offset(offset, count).await,
+            None => Ok(EMPTY_MESSAGES),
+        }
+    }
+
+    /// Returns the whole batches containing the messages from the given offset range (see `PolledBatches`),
+    /// the batches are taken from the cache if available, otherwise from the segments.
+    pub async fn get_batches_by_offset(
+        &self,
+        start_offset: u64,
+        count: u32,
+    ) -> Result<PolledBatches, IggyError> {
+        trace!(
+            "Getting batches for start offset: {} for partition: {}...",
+            start_offset,
+            self.partition_id
+        );
+        let mut polled_batches = PolledBatches::empty(self.partition_id, self.current_offset);
+        if self.segments.is_empty() || start_offset > self.current_offset {
+            return Ok(polled_batches);
+        }
+
+        let start_offset = start_offset.max(self.segments[0].start_offset);
+        let end_offset = self.get_end_offset(start_offset, count);
+        if start_offset > end_offset {
+            return Ok(polled_batches);
+        }
+
+        polled_batches.first_offset = start_offset;
+        if let Some(chunk) = self.try_get_batches_from_cache(start_offset, end_offset) {
+            self.storage.metrics.increment_cache_hits();
+            polled_batches.messages_count = (end_offset - start_offset + 1) as u32;
+            polled_batches.chunks.push(chunk);
+            return Ok(polled_batches);
+        }
+
+        if self.cache.is_some() {
+            self.storage.metrics.increment_cache_misses();
+        }
+
+        let mut messages_count = 0;
+        for segment in self.filter_segments_by_offsets(start_offset, end_offset) {
+            messages_count += segment
+                .get_batches_chunks(start_offset, end_offset, &mut polled_batches.chunks)
+                .await?;
+        }
+        polled_batches.messages_count = messages_count as u32;
+        Ok(polled_batches)
+    }
+
+    /// The messages are filtered by the timestamp, so they're loaded and encoded as a single batch.
+    pub async fn get_batches_by_timestamp(
+        &self,
+        timestamp: IggyTimestamp,
+        count: u32,
+    ) -> Result<PolledBatches, IggyError> {
+        let messages = self.get_messages_by_timestamp(timestamp, count).await?;
+        Ok(PolledBatches::from_messages(
+            self.partition_id,
+            self.current_offset,
+            &messages,
+        ))
+    }
+
+    pub async fn get_first_batches(&self, count: u32) -> Result<PolledBatches, IggyError> {
+        self.get_batches_by_offset(0, count).await
+    }
+
+    pub async fn get_last_batches(&self, count: u32) -> Result<PolledBatches, IggyError> {
+        let (start_offset, count) = self.get_last_messages_range(count);
+        self.get_batches_by_offset(start_offset, count).await
+    }
+
+    pub async fn get_next_batches(
+        &self,
+        consumer: PollingConsumer,
+        count: u32,
+    ) -> Result<PolledBatches, IggyError> {
+        match self.get_next_offset(consumer) {
+            Some(offset) => self.get_batches_by_offset(offset, count).await,
+            None => Ok(PolledBatches::empty(self.partition_id, self.current_offset)),
+        }
+    }
+
+    fn get_last_messages_range(&self, count: u32) -> (u64, u32) {
+        let mut count = count as u64;
+        if count > self.current_offset + 1 {
+            count = self.current_offset + 1
+        }
+
+        (1 + self.current_offset - count, count as u32)
+    }
+
+    /// Returns the offset of the next message to be polled by the consumer,
+    /// or `None` if the consumer has already polled the latest one.
+    fn get_next_offset(&self, consumer: PollingConsumer) -> Option<u64> {
         let (consumer_offsets, consumer_id) = match consumer {
             PollingConsumer::Consumer(consumer_id, _) => (&self.consumer_offsets, consumer_id),
             PollingConsumer::ConsumerGroup(group_id, _) => (&self.consumer_group_offsets, group_id),
@@ -189,7 +278,7 @@ impl Partition {
                 consumer_id,
                 self.partition_id
             );
-            return self.get_first_messages(count).await;
+            return Some(0);
         }
 
         let consumer_offset = consumer_offset.unwrap();
@@ -200,7 +289,7 @@ impl Partition {
                 consumer_offset.offset,
                 self.partition_id
             );
-            return Ok(EMPTY_MESSAGES);
+            return None;
         }
 
         let offset = consumer_offset.offset + 1;
@@ -210,8 +299,7 @@ impl Partition {
             self.partition_id,
             offset
         );
-
-        self.get_messages_by_offset(offset, count).await
+        Some(offset)
     }
 
     fn get_end_offset(&self, offset: u64, count: u32) -> u64 {
@@ -277,6 +365,29 @@ impl Partition {
         None
     }
 
+    fn try_get_batches_from_cache(
+        &self,
+        start_offset: u64,
+        end_offset: u64,
+    ) -> Option<BatchesChunk> {
+        let cache = self.cache.as_ref()?;
+        if cache.is_empty()
+            || start_offset < cache[0].base_offset
+            || end_offset > cache[cache.len() - 1].get_last_offset()
+        {
+            return None;
+        }
+
+        trace!(
+            "Loading batches from cache, start offset: {}, end offset: {}...",
+            start_offset,
+            end_offset
+        );
+        Some(BatchesChunk::from_batches(cache.iter().filter(|batch| {
+            batch.base_offset <= end_offset && batch.get_last_offset() >= start_offset
+        })))
+    }
+
     pub async fn get_newest_messages_by_size(
         &self,
         size_bytes: u64,
@@ -3,6 +3,7 @@ use iggy::messages::send_messages;
 
 pub mod consumer_offsets;
 pub mod messages;
+pub mod offsets_log;
 pub mod partition;
 pub mod persistence;
 pub mod segments;
@@ -0,0 +1,198 @@
+use crate::streaming::partitions::partition::ConsumerOffset;
+use bytes::{BufMut, BytesMut};
+use iggy::consumer::ConsumerKind;
+use iggy::utils::checksum;
+use std::collections::BTreeMap;
+
+/// The size of the single entry in the offsets log:
+/// entry type (1) + consumer kind (1) + consumer ID (4) + offset (8) + checksum (4).
+pub const ENTRY_SIZE: usize = 1 + 1 + 4 + 8 + 4;
+const CHECKSUM_POSITION: usize = ENTRY_SIZE - 4;
+const STORED_ENTRY_CODE: u8 = 1;
+const DELETED_ENTRY_CODE: u8 = 2;
+
+/// The entry of the append-only consumer offsets log of the partition.
+/// The latest entry of the consumer (or consumer group) wins when the log is replayed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConsumerOffsetEntry {
+    Stored {
+        kind: ConsumerKind,
+        consumer_id: u32,
+        offset: u64,
+    },
+    Deleted {
+        kind: ConsumerKind,
+        consumer_id: u32,
+    },
+}
+
+/// The result of replaying the offsets log.
+/// - `offsets` - The latest offsets of the consumers and consumer groups, sorted by the kind and the ID
+/// - `entries_count` - The number of the valid entries in the log
+/// - `valid_size` - The size of the valid part of the log, anything past it is a partial or corrupted entry
+#[derive(Debug, Default)]
+pub struct ConsumerOffsetsLog {
+    pub offsets: Vec<ConsumerOffset>,
+    pub entries_count: u64,
+    pub valid_size: u64,
+}
+
+impl ConsumerOffsetEntry {
+    pub fn stored(consumer_offset: &ConsumerOffset) -> Self {
+        ConsumerOffsetEntry::Stored {
+            kind: consumer_offset.kind,
+            consumer_id: consumer_offset.consumer_id,
+            offset: consumer_offset.offset,
+        }
+    }
+
+    pub fn write_to(&self, bytes: &mut BytesMut) {
+        let position = bytes.len();
+        match self {
+            ConsumerOffsetEntry::Stored {
+                kind,
+                consumer_id,
+                offset,
+            } => {
+                bytes.put_u8(STORED_ENTRY_CODE);
+                bytes.put_u8(kind.as_code());
+                bytes.put_u32_le(*consumer_id);
+                bytes.put_u64_le(*offset);
+            }
+            ConsumerOffsetEntry::Deleted { kind, consumer_id } => {
+                bytes.put_u8(DELETED_ENTRY_CODE);
+                bytes.put_u8(kind.as_code());
+                bytes.put_u32_le(*consumer_id);
+                bytes.put_u64_le(0);
+            }
+        }
+        let checksum = checksum::calculate(&bytes[position..]);
+        bytes.put_u32_le(checksum);
+    }
+
+    /// Returns `None` if the entry is invalid (e.g. checksum mismatch or unknown type).
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != ENTRY_SIZE {
+            return None;
+        }
+
+        let checksum = u32::from_le_bytes(bytes[CHECKSUM_POSITION..].try_into().ok()?);
+        if checksum::calculate(&bytes[..CHECKSUM_POSITION]) != checksum {
+            return None;
+        }
+
+        let kind = ConsumerKind::from_code(bytes[1]).ok()?;
+        let consumer_id = u32::from_le_bytes(bytes[2..6].try_into().ok()?);
+        let offset = u64::from_le_bytes(bytes[6..14].try_into().ok()?);
+        match bytes[0] {
+            STORED_ENTRY_CODE => Some(ConsumerOffsetEntry::Stored {
+                kind,
+                consumer_id,
+                offset,
+            }),
+            DELETED_ENTRY_CODE => Some(ConsumerOffsetEntry::Deleted { kind, consumer_id }),
+            _ => None,
+        }
+    }
+}
+
+impl ConsumerOffsetsLog {
+    /// Replays the entries of the log, stops at the first partial or invalid entry.
+    pub fn replay(bytes: &[u8]) -> Self {
+        let mut offsets = BTreeMap::new();
+        let mut entries_count = 0;
+        for chunk in bytes.chunks(ENTRY_SIZE) {
+            let Some(entry) = ConsumerOffsetEntry::from_bytes(chunk) else {
+                break;
+            };
+
+            match entry {
+                ConsumerOffsetEntry::Stored {
+                    kind,
+                    consumer_id,
+                    offset,
+                } => {
+                    offsets.insert((kind.as_code(), consumer_id), (kind, offset));
+                }
+                ConsumerOffsetEntry::Deleted { kind, consumer_id } => {
+                    offsets.remove(&(kind.as_code(), consumer_id));
+                }
+            }
+            entries_count += 1;
+        }
+
+        ConsumerOffsetsLog {
+            offsets: offsets
+                .into_iter()
+                .map(|((_, consumer_id), (kind, offset))| {
+                    ConsumerOffset::new(kind, consumer_id, offset)
+                })
+                .collect(),
+            entries_count,
+            valid_size: entries_count * ENTRY_SIZE as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_replay_latest_offsets_and_skip_deleted_ones() {
+        let mut bytes = BytesMut::new();
+        for entry in [
+            stored(ConsumerKind::Consumer, 1, 10),
+            stored(ConsumerKind::ConsumerGroup, 1, 5),
+            stored(ConsumerKind::Consumer, 2, 3),
+            stored(ConsumerKind::Consumer, 1, 20),
+            ConsumerOffsetEntry::Deleted {
+                kind: ConsumerKind::Consumer,
+                consumer_id: 2,
+            },
+        ] {
+            entry.write_to(&mut bytes);
+        }
+
+        let log = ConsumerOffsetsLog::replay(&bytes);
+
+        assert_eq!(log.entries_count, 5);
+        assert_eq!(log.valid_size, bytes.len() as u64);
+        assert_eq!(
+            log.offsets,
+            vec![
+                ConsumerOffset::new(ConsumerKind::Consumer, 1, 20),
+                ConsumerOffset::new(ConsumerKind::ConsumerGroup, 1, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_stop_replaying_at_corrupted_or_partial_entry() {
+        let mut bytes = BytesMut::new();
+        stored(ConsumerKind::Consumer, 1, 10).write_to(&mut bytes);
+        stored(ConsumerKind::Consumer, 1, 20).write_to(&mut bytes);
+        stored(ConsumerKind::Consumer, 1, 30).write_to(&mut bytes);
+        bytes[ENTRY_SIZE + 6] ^= 0xFF;
+
+        let log = ConsumerOffsetsLog::replay(&bytes);
+        assert_eq!(log.entries_count, 1);
+        assert_eq!(log.valid_size, ENTRY_SIZE as u64);
+        assert_eq!(
+            log.offsets,
+            vec![ConsumerOffset::new(ConsumerKind::Consumer, 1, 10)]
+        );
+
+        let log = ConsumerOffsetsLog::replay(&bytes[..ENTRY_SIZE + 5]);
+        assert_eq!(log.entries_count, 1);
+        assert_eq!(log.valid_size, ENTRY_SIZE as u64);
+    }
+
+    fn stored(kind: ConsumerKind, consumer_id: u32, offset: u64) -> ConsumerOffsetEntry {
+        ConsumerOffsetEntry::Stored {
+            kind,
+            consumer_id,
+            offset,
+        }
+    }
+}
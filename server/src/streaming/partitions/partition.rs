@@ -5,7 +5,7 @@ use crate::streaming::cache::memory_tracker::CacheMemoryTracker;
 use crate::streaming::deduplication::message_deduplicator::MessageDeduplicator;
 use crate::streaming::segments::segment::Segment;
 use crate::streaming::storage::SystemStorage;
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 use iggy::consumer::ConsumerKind;
 use iggy::utils::duration::IggyDuration;
 use iggy::utils::expiry::IggyExpiry;
@@ -20,8 +20,7 @@ pub struct Partition {
     pub partition_id: u32,
     pub partition_path: String,
     pub offsets_path: String,
-    pub consumer_offsets_path: String,
-    pub consumer_group_offsets_path: String,
+    pub consumer_offsets_log_path: String,
     pub current_offset: u64,
     pub cache: Option<SmartCache<Arc<RetainedMessageBatch>>>,
     pub cached_memory_tracker: Option<Arc<CacheMemoryTracker>>,
@@ -40,6 +39,9 @@ pub struct Partition {
     pub(crate) message_expiry: IggyExpiry,
     pub(crate) consumer_offsets: DashMap<u32, ConsumerOffset>,
     pub(crate) consumer_group_offsets: DashMap<u32, ConsumerOffset>,
+    pub(crate) unsaved_consumer_offsets: DashSet<u32>,
+    pub(crate) unsaved_consumer_group_offsets: DashSet<u32>,
+    pub(crate) consumer_offsets_log_entries: AtomicU64,
     pub(crate) segments: Vec<Segment>,
     pub(crate) config: Arc<SystemConfig>,
     pub(crate) storage: Arc<SystemStorage>,
@@ -50,16 +52,14 @@ pub struct ConsumerOffset {
     pub kind: ConsumerKind,
     pub consumer_id: u32,
     pub offset: u64,
-    pub path: String,
 }
 
 impl ConsumerOffset {
-    pub fn new(kind: ConsumerKind, consumer_id: u32, offset: u64, path: &str) -> ConsumerOffset {
+    pub fn new(kind: ConsumerKind, consumer_id: u32, offset: u64) -> ConsumerOffset {
         ConsumerOffset {
             kind,
             consumer_id,
             offset,
-            path: format!("{path}/{consumer_id}"),
         }
     }
 }
@@ -83,10 +83,8 @@ impl Partition {
     ) -> Partition {
         let partition_path = config.get_partition_path(stream_id, topic_id, partition_id);
         let offsets_path = config.get_offsets_path(stream_id, topic_id, partition_id);
-        let consumer_offsets_path =
-            config.get_consumer_offsets_path(stream_id, topic_id, partition_id);
-        let consumer_group_offsets_path =
-            config.get_consumer_group_offsets_path(stream_id, topic_id, partition_id);
+        let consumer_offsets_log_path =
+            config.get_consumer_offsets_log_path(stream_id, topic_id, partition_id);
         let (cached_memory_tracker, messages) = match config.cache.enabled {
             false => (None, None),
             true => (
@@ -101,8 +99,7 @@ impl Partition {
             partition_id,
             partition_path,
             offsets_path,
-            consumer_offsets_path,
-            consumer_group_offsets_path,
+            consumer_offsets_log_path,
             message_expiry,
             cache: messages,
             cached_memory_tracker,
@@ -129,6 +126,9 @@ impl Partition {
             should_increment_offset: false,
             consumer_offsets: DashMap::new(),
             consumer_group_offsets: DashMap::new(),
+            unsaved_consumer_offsets: DashSet::new(),
+            unsaved_consumer_group_offsets: DashSet::new(),
+            consumer_offsets_log_entries: AtomicU64::new(0),
             config,
             storage,
             created_at,
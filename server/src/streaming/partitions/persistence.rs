@@ -38,13 +38,12 @@ impl Partition {
                 .fetch_sub(1, Ordering::SeqCst);
         }
         self.segments.clear();
+        self.unsaved_consumer_offsets.clear();
+        self.unsaved_consumer_group_offsets.clear();
+        self.consumer_offsets_log_entries.store(0, Ordering::SeqCst);
         self.storage
             .partition
-            .delete_consumer_offsets(&self.consumer_offsets_path)
-            .await?;
-        self.storage
-            .partition
-            .delete_consumer_offsets(&self.consumer_group_offsets_path)
+            .delete_consumer_offsets(&self.consumer_offsets_log_path)
             .await?;
         self.add_persisted_segment(0).await?;
 
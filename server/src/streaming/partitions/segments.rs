@@ -42,6 +42,31 @@ impl Partition {
         expired_segments
     }
 
+    /// Closes the active segment if it's older than the configured max age, even if no more messages
+    /// are appended to the partition, so that its messages can expire. Returns true if the segment was closed.
+    pub async fn close_aged_segment(&mut self, now: IggyTimestamp) -> Result<bool, IggyError> {
+        let Some(segment) = self.segments.last_mut() else {
+            return Ok(false);
+        };
+
+        if segment.is_closed || !segment.is_aged(now) {
+            return Ok(false);
+        }
+
+        segment.persist_messages().await?;
+        if !segment.is_closed {
+            segment.close().await;
+        }
+
+        let start_offset = segment.start_offset;
+        self.unsaved_messages_count = 0;
+        info!(
+            "Closed aged segment with start offset: {} for partition with ID: {}, stream with ID: {}, topic with ID: {}.",
+            start_offset, self.partition_id, self.stream_id, self.topic_id
+        );
+        Ok(true)
+    }
+
     pub async fn add_persisted_segment(&mut self, start_offset: u64) -> Result<(), IggyError> {
         info!(
             "Creating the new segment for partition with ID: {}, stream with ID: {}, topic with ID: {}...",
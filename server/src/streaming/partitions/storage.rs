@@ -1,5 +1,9 @@
 use crate::compat::message_conversion::message_converter::MessageFormatConverter;
+use crate::compat::offsets_conversion::convert_legacy_consumer_offsets;
 use crate::state::system::PartitionState;
+use crate::streaming::partitions::offsets_log::{
+    ConsumerOffsetEntry, ConsumerOffsetsLog, ENTRY_SIZE,
+};
 use crate::streaming::partitions::partition::{ConsumerOffset, Partition};
 use crate::streaming::persistence::persister::Persister;
 use crate::streaming::segments::segment::{Segment, LOG_EXTENSION};
@@ -7,16 +11,19 @@ use crate::streaming::storage::PartitionStorage;
 use crate::streaming::utils::file;
 use anyhow::Context;
 use async_trait::async_trait;
+use bytes::BytesMut;
 use iggy::consumer::ConsumerKind;
 use iggy::error::IggyError;
+use std::io::ErrorKind;
 use std::path::Path;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use tokio::fs;
 use tokio::fs::create_dir;
-use tokio::io::AsyncReadExt;
 use tracing::{error, info, trace, warn};
 
+const COMPACTED_LOG_SUFFIX: &str = ".compacted";
+
 #[derive(Debug)]
 pub struct FilePartitionStorage {
     persister: Arc<dyn Persister>,
@@ -26,6 +33,22 @@ impl FilePartitionStorage {
     pub fn new(persister: Arc<dyn Persister>) -> Self {
         Self { persister }
     }
+
+    async fn append_consumer_offset_entries(
+        &self,
+        path: &str,
+        bytes: &[u8],
+    ) -> Result<(), IggyError> {
+        match self.persister.append(path, bytes).await {
+            // The log is created without truncating it, as the offsets might be stored concurrently
+            // and another entry could have been appended to the created log in the meantime.
+            Err(IggyError::IoError(error)) if error.kind() == ErrorKind::NotFound => {
+                file::append_or_create(path).await?;
+                self.persister.append(path, bytes).await
+            }
+            result => result,
+        }
+    }
 }
 unsafe impl Send for FilePartitionStorage {}
 unsafe impl Sync for FilePartitionStorage {}
@@ -51,6 +74,7 @@ impl PartitionStorage for FilePartitionStorage {
             }
 
         let mut dir_entries = dir_entries.unwrap();
+        let mut start_offsets = Vec::new();
         while let Some(dir_entry) = dir_entries.next_entry().await.unwrap_or(None) {
             let metadata = dir_entry.metadata().await.unwrap();
             if metadata.is_dir() {
@@ -69,7 +93,12 @@ impl PartitionStorage for FilePartitionStorage {
                 .unwrap()
                 .replace(&format!(".{}", LOG_EXTENSION), "");
 
-            let start_offset = log_file_name.parse::<u64>().unwrap();
+            start_offsets.push(log_file_name.parse::<u64>().unwrap());
+        }
+
+        start_offsets.sort_unstable();
+        let last_start_offset = start_offsets.last().copied();
+        for start_offset in start_offsets {
             let mut segment = Segment::create(
                 partition.stream_id,
                 partition.topic_id,
@@ -120,6 +149,17 @@ impl PartitionStorage for FilePartitionStorage {
                 }
             }
 
+            // Only the last segment is active and might have been interrupted while appending,
+            // the closed segments are never truncated by the recovery.
+            if Some(start_offset) != last_start_offset {
+                segment.is_closed = true;
+            }
+
+            let recovery = segment.recover().await?;
+            if recovery.is_repaired() {
+                warn!("Repaired segment with start offset: {} for partition with ID: {} for topic with ID: {} and stream with ID: {}, {recovery}.", segment.start_offset, partition.partition_id, partition.topic_id, partition.stream_id);
+            }
+
             segment.load().await?;
             if !segment.is_closed {
                 segment.unsaved_batches = Some(Vec::new())
@@ -186,6 +226,14 @@ impl PartitionStorage for FilePartitionStorage {
             partition.current_offset = last_segment.current_offset;
         }
 
+        convert_legacy_consumer_offsets(
+            self,
+            &partition.config,
+            partition.stream_id,
+            partition.topic_id,
+            partition.partition_id,
+        )
+        .await?;
         partition.load_consumer_offsets().await?;
         info!(
             "Loaded partition with ID: {} for stream with ID: {} and topic with ID: {}, current offset: {}.",
@@ -224,36 +272,6 @@ impl PartitionStorage for FilePartitionStorage {
             ));
         }
 
-        if !Path::new(&partition.consumer_offsets_path).exists()
-            && create_dir(&partition.consumer_offsets_path).await.is_err()
-        {
-            error!(
-                "Failed to create consumer offsets directory for partition with ID: {} for stream with ID: {} and topic with ID: {}.",
-                partition.partition_id, partition.stream_id, partition.topic_id
-            );
-            return Err(IggyError::CannotCreatePartition(
-                partition.partition_id,
-                partition.stream_id,
-                partition.topic_id,
-            ));
-        }
-
-        if !Path::new(&partition.consumer_group_offsets_path).exists()
-            && create_dir(&partition.consumer_group_offsets_path)
-                .await
-                .is_err()
-        {
-            error!(
-                "Failed to create consumer group offsets directory for partition with ID: {} for stream with ID: {} and topic with ID: {}.",
-                partition.partition_id, partition.stream_id, partition.topic_id
-            );
-            return Err(IggyError::CannotCreatePartition(
-                partition.partition_id,
-                partition.stream_id,
-                partition.topic_id,
-            ));
-        }
-
         for segment in partition.get_segments() {
             segment.persist().await?;
         }
@@ -270,7 +288,7 @@ impl PartitionStorage for FilePartitionStorage {
         );
 
         if let Err(err) = self
-            .delete_consumer_offsets(&partition.consumer_offsets_path)
+            .delete_consumer_offsets(&partition.consumer_offsets_log_path)
             .await
         {
             error!("Cannot delete consumer offsets for partition with ID: {} for topic with ID: {} for stream with ID: {}. Error: {}", partition.partition_id, partition.topic_id, partition.stream_id, err);
@@ -281,18 +299,6 @@ impl PartitionStorage for FilePartitionStorage {
             ));
         }
 
-        if let Err(err) = self
-            .delete_consumer_offsets(&partition.consumer_group_offsets_path)
-            .await
-        {
-            error!("Cannot delete consumer group offsets for partition with ID: {} for topic with ID: {} for stream with ID: {}. Error: {}", partition.partition_id, partition.topic_id, partition.stream_id, err);
-            return Err(IggyError::CannotDeletePartition(
-                partition.partition_id,
-                partition.topic_id,
-                partition.stream_id,
-            ));
-        }
-
         if fs::remove_dir_all(&partition.partition_path).await.is_err() {
             error!("Cannot delete partition directory: {} for partition with ID: {} for topic with ID: {} for stream with ID: {}.", partition.partition_path, partition.partition_id, partition.topic_id, partition.stream_id);
             return Err(IggyError::CannotDeletePartitionDirectory(
@@ -308,99 +314,98 @@ impl PartitionStorage for FilePartitionStorage {
         Ok(())
     }
 
-    async fn save_consumer_offset(&self, offset: &ConsumerOffset) -> Result<(), IggyError> {
-        self.persister
-            .overwrite(&offset.path, &offset.offset.to_le_bytes())
-            .await?;
+    async fn save_consumer_offsets(
+        &self,
+        path: &str,
+        offsets: &[ConsumerOffset],
+    ) -> Result<(), IggyError> {
+        if offsets.is_empty() {
+            return Ok(());
+        }
+
+        let mut bytes = BytesMut::with_capacity(offsets.len() * ENTRY_SIZE);
+        for offset in offsets {
+            ConsumerOffsetEntry::stored(offset).write_to(&mut bytes);
+        }
+        self.append_consumer_offset_entries(path, &bytes).await?;
         trace!(
-            "Stored consumer offset value: {} for {} with ID: {}, path: {}",
-            offset.offset,
-            offset.kind,
-            offset.consumer_id,
-            offset.path
+            "Stored {} consumer offsets in the log: {path}.",
+            offsets.len()
         );
         Ok(())
     }
 
-    async fn load_consumer_offsets(
-        &self,
-        kind: ConsumerKind,
-        path: &str,
-    ) -> Result<Vec<ConsumerOffset>, IggyError> {
-        trace!("Loading consumer offsets from path: {path}...");
-        let dir_entries = fs::read_dir(&path).await;
-        if dir_entries.is_err() {
-            return Err(IggyError::CannotReadConsumerOffsets(path.to_owned()));
+    async fn load_consumer_offsets(&self, path: &str) -> Result<ConsumerOffsetsLog, IggyError> {
+        trace!("Loading consumer offsets from the log: {path}...");
+        if !Path::new(path).exists() {
+            return Ok(ConsumerOffsetsLog::default());
         }
 
-        let mut consumer_offsets = Vec::new();
-        let mut dir_entries = dir_entries.unwrap();
-        while let Some(dir_entry) = dir_entries.next_entry().await.unwrap_or(None) {
-            let metadata = dir_entry.metadata().await;
-            if metadata.is_err() {
-                break;
-            }
-
-            if metadata.unwrap().is_dir() {
-                continue;
-            }
-
-            let name = dir_entry.file_name().into_string().unwrap();
-            let consumer_id = name.parse::<u32>();
-            if consumer_id.is_err() {
-                error!("Invalid consumer ID file with name: '{}'.", name);
-                continue;
-            }
-
-            let path = dir_entry.path();
-            let path = path.to_str();
-            if path.is_none() {
-                error!("Invalid consumer ID path for file with name: '{}'.", name);
-                continue;
-            }
+        let Ok(bytes) = fs::read(path).await else {
+            return Err(IggyError::CannotReadConsumerOffsets(path.to_owned()));
+        };
 
-            let path = path.unwrap().to_string();
-            let consumer_id = consumer_id.unwrap();
-            let mut file = file::open(&path).await?;
-            let offset = file.read_u64_le().await?;
-
-            consumer_offsets.push(ConsumerOffset {
-                kind,
-                consumer_id,
-                offset,
-                path,
-            });
+        let log = ConsumerOffsetsLog::replay(&bytes);
+        let invalid_bytes = bytes.len() as u64 - log.valid_size;
+        if invalid_bytes > 0 {
+            warn!("Consumer offsets log: {path} contains {invalid_bytes} bytes of partial or corrupted entries after {} valid entries, the log will be truncated.", log.entries_count);
+            file::truncate(path, log.valid_size).await?;
         }
-
-        consumer_offsets.sort_by(|a, b| a.consumer_id.cmp(&b.consumer_id));
-        Ok(consumer_offsets)
+        Ok(log)
     }
 
-    async fn delete_consumer_offsets(&self, path: &str) -> Result<(), IggyError> {
-        if !Path::new(path).exists() {
-            trace!("Consumer offsets directory does not exist: {path}.");
-            return Ok(());
-        }
-
-        if fs::remove_dir_all(path).await.is_err() {
-            error!("Cannot delete consumer offsets directory: {}.", path);
-            return Err(IggyError::CannotDeleteConsumerOffsetsDirectory(
-                path.to_owned(),
-            ));
+    async fn compact_consumer_offsets(
+        &self,
+        path: &str,
+        offsets: &[ConsumerOffset],
+    ) -> Result<(), IggyError> {
+        let mut bytes = BytesMut::with_capacity(offsets.len() * ENTRY_SIZE);
+        for offset in offsets {
+            ConsumerOffsetEntry::stored(offset).write_to(&mut bytes);
         }
+        // The compacted log replaces the current one atomically, so it's never observed partially written.
+        // It's always synced before the rename (regardless of the enforced fsync), as the entries it replaces
+        // would be lost otherwise, and so is the directory after the rename.
+        let compacted_path = format!("{path}{COMPACTED_LOG_SUFFIX}");
+        file::overwrite_synced(&compacted_path, &bytes).await?;
+        file::rename_synced(&compacted_path, path).await?;
+        trace!(
+            "Compacted consumer offsets log: {path}, stored offsets: {}.",
+            offsets.len()
+        );
         Ok(())
     }
 
-    async fn delete_consumer_offset(&self, path: &str) -> Result<(), IggyError> {
-        if !Path::new(path).exists() {
-            trace!("Consumer offset file does not exist: {path}.");
+    async fn delete_consumer_offsets(&self, path: &str) -> Result<(), IggyError> {
+        let Ok(metadata) = fs::metadata(path).await else {
+            trace!("Consumer offsets do not exist: {path}.");
             return Ok(());
-        }
-
-        if fs::remove_file(path).await.is_err() {
-            error!("Cannot delete consumer offset file: {path}.");
+        };
+
+        if metadata.is_dir() {
+            if fs::remove_dir_all(path).await.is_err() {
+                error!("Cannot delete consumer offsets directory: {}.", path);
+                return Err(IggyError::CannotDeleteConsumerOffsetsDirectory(
+                    path.to_owned(),
+                ));
+            }
+        } else if fs::remove_file(path).await.is_err() {
+            error!("Cannot delete consumer offsets log: {path}.");
             return Err(IggyError::CannotDeleteConsumerOffsetFile(path.to_owned()));
         }
         Ok(())
     }
+
+    async fn delete_consumer_offset(
+        &self,
+        path: &str,
+        kind: ConsumerKind,
+        consumer_id: u32,
+    ) -> Result<(), IggyError> {
+        let mut bytes = BytesMut::with_capacity(ENTRY_SIZE);
+        ConsumerOffsetEntry::Deleted { kind, consumer_id }.write_to(&mut bytes);
+        self.append_consumer_offset_entries(path, &bytes).await?;
+        trace!("Deleted consumer offset for {kind} with ID: {consumer_id} in the log: {path}.");
+        Ok(())
+    }
 }
@@ -0,0 +1,816 @@
+use crate::streaming::diagnostics::metrics::StorageMetrics;
+use crate::streaming::persistence::persister::Persister;
+use crate::streaming::persistence::reader::{SegmentFile, SegmentReader};
+use crate::streaming::utils::file;
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use iggy::error::IggyError;
+use io_uring::{opcode, squeue, types, IoUring};
+use std::alloc::{alloc_zeroed, dealloc, handle_alloc_error, Layout};
+use std::fs::File;
+use std::future::Future;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::pin::Pin;
+use std::ptr::NonNull;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tokio::fs;
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
+use tracing::{error, info, warn};
+
+const RING_ENTRIES: usize = 256;
+/// Upper bound of the entries of the requests in flight, one entry is taken by the poll of the wakeup eventfd.
+const MAX_REQUEST_ENTRIES: usize = RING_ENTRIES - 1;
+/// The user data of the completion of the wakeup poll, the requests use their slot and chunk index instead.
+const WAKEUP_USER_DATA: u64 = u64::MAX;
+const BUFFERS_COUNT: usize = 64;
+const BUFFER_SIZE: usize = 128 * 1024;
+/// Upper bound of the buffers used by a single submission, the larger writes are split into multiple submissions.
+const MAX_BUFFERS_PER_REQUEST: usize = 16;
+/// The size of the read-ahead of the segment reader, read with a single submission of multiple buffers.
+const READ_AHEAD_BYTES: usize = 4 * BUFFER_SIZE;
+const PAGE_SIZE: usize = 4096;
+
+/// Handle to the io_uring instance driven by its own thread.
+///
+/// The data is copied from and to the pool of buffers registered with the ring. The requests are submitted
+/// as soon as they're received, also while the previous ones are in flight, and each of them is replied to
+/// as soon as all its completions are reaped, so e.g. a slow fsync doesn't hold back the reads submitted after it.
+/// The chunks of a single write are linked, so they're applied in order, which keeps the appends of the large payloads contiguous.
+#[derive(Debug, Clone)]
+pub struct IoUringRing {
+    queue: Arc<RequestQueue>,
+    pool: Arc<BufferPool>,
+}
+
+/// Sends the requests to the ring thread and wakes it up, as it might be waiting for the completions.
+#[derive(Debug)]
+struct RequestQueue {
+    sender: Option<Sender<Request>>,
+    wakeup: Arc<OwnedFd>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operation {
+    Write,
+    Read,
+    Fsync,
+}
+
+struct Request {
+    operation: Operation,
+    file: Arc<File>,
+    /// Position of the first chunk, `u64::MAX` uses the current position of the file (e.g. appends).
+    offset: u64,
+    lengths: Vec<usize>,
+    buffers: Option<BufferLease>,
+    reply: oneshot::Sender<Completion>,
+}
+
+struct Completion {
+    buffers: Option<BufferLease>,
+    result: io::Result<usize>,
+}
+
+struct InFlightRequest {
+    request: Request,
+    results: Vec<i32>,
+    remaining: usize,
+}
+
+impl IoUringRing {
+    pub fn new() -> io::Result<Self> {
+        let ring = IoUring::new(RING_ENTRIES as u32)?;
+        let pool = Arc::new(BufferPool::new());
+        let iovecs = pool.iovecs();
+        // Safety: the buffers are owned by the pool, which outlives the ring as the ring thread keeps it alive.
+        let fixed_buffers = match unsafe { ring.submitter().register_buffers(&iovecs) } {
+            Ok(()) => true,
+            Err(error) => {
+                warn!("Failed to register io_uring buffers, using regular reads and writes instead. {error}");
+                false
+            }
+        };
+
+        // Safety: the returned descriptor is valid and owned only by the `OwnedFd`.
+        let wakeup = unsafe { libc::eventfd(0, libc::EFD_NONBLOCK | libc::EFD_CLOEXEC) };
+        if wakeup < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let wakeup = Arc::new(unsafe { OwnedFd::from_raw_fd(wakeup) });
+
+        let (sender, receiver) = mpsc::channel();
+        let thread_pool = pool.clone();
+        let thread_wakeup = wakeup.clone();
+        std::thread::Builder::new()
+            .name("iggy-io-uring".to_string())
+            .spawn(move || run(ring, receiver, thread_pool, fixed_buffers, thread_wakeup))?;
+        info!(
+            "Initialized io_uring with {RING_ENTRIES} entries and {BUFFERS_COUNT} buffers of {BUFFER_SIZE} bytes, registered buffers: {fixed_buffers}."
+        );
+        Ok(Self {
+            queue: Arc::new(RequestQueue {
+                sender: Some(sender),
+                wakeup,
+            }),
+            pool,
+        })
+    }
+
+    /// Writes the bytes at the given offset, or at the current position of the file if not provided.
+    pub async fn write(
+        &self,
+        file: &Arc<File>,
+        offset: Option<u64>,
+        bytes: &[u8],
+    ) -> io::Result<()> {
+        let mut written = 0;
+        for part in bytes.chunks(MAX_BUFFERS_PER_REQUEST * BUFFER_SIZE) {
+            let chunks = part.chunks(BUFFER_SIZE).collect::<Vec<_>>();
+            let mut buffers = self.pool.clone().acquire(chunks.len()).await;
+            for (index, chunk) in chunks.iter().enumerate() {
+                buffers.get_mut(index)[..chunk.len()].copy_from_slice(chunk);
+            }
+            let offset = offset.map_or(u64::MAX, |offset| offset + written);
+            let lengths = chunks.iter().map(|chunk| chunk.len()).collect();
+            self.submit(Operation::Write, file, offset, lengths, Some(buffers))
+                .await?
+                .result?;
+            written += part.len() as u64;
+        }
+        Ok(())
+    }
+
+    /// Reads up to `length` bytes starting at the offset, fewer bytes are returned only at the end of the file.
+    pub async fn read(&self, file: &Arc<File>, offset: u64, length: usize) -> io::Result<BytesMut> {
+        let mut bytes = BytesMut::with_capacity(length);
+        while bytes.len() < length {
+            let remaining = length - bytes.len();
+            let chunks = remaining.div_ceil(BUFFER_SIZE).min(MAX_BUFFERS_PER_REQUEST);
+            let lengths = (0..chunks)
+                .map(|chunk| (remaining - chunk * BUFFER_SIZE).min(BUFFER_SIZE))
+                .collect::<Vec<_>>();
+            let requested = lengths.iter().sum::<usize>();
+            let buffers = self.pool.clone().acquire(chunks).await;
+            let completion = self
+                .submit(
+                    Operation::Read,
+                    file,
+                    offset + bytes.len() as u64,
+                    lengths,
+                    Some(buffers),
+                )
+                .await?;
+            let read = completion.result?;
+            if let Some(buffers) = &completion.buffers {
+                let mut left = read;
+                for index in 0..chunks {
+                    let chunk = left.min(BUFFER_SIZE);
+                    bytes.extend_from_slice(&buffers.get(index)[..chunk]);
+                    left -= chunk;
+                }
+            }
+            if read < requested {
+                break;
+            }
+        }
+        Ok(bytes)
+    }
+
+    pub async fn fsync(&self, file: &Arc<File>) -> io::Result<()> {
+        self.submit(Operation::Fsync, file, 0, Vec::new(), None)
+            .await?
+            .result?;
+        Ok(())
+    }
+
+    async fn submit(
+        &self,
+        operation: Operation,
+        file: &Arc<File>,
+        offset: u64,
+        lengths: Vec<usize>,
+        buffers: Option<BufferLease>,
+    ) -> io::Result<Completion> {
+        let (reply, receiver) = oneshot::channel();
+        self.queue.send(Request {
+            operation,
+            file: file.clone(),
+            offset,
+            lengths,
+            buffers,
+            reply,
+        })?;
+        receiver
+            .await
+            .map_err(|_| io::Error::other("io_uring thread has stopped"))
+    }
+}
+
+impl RequestQueue {
+    fn send(&self, request: Request) -> io::Result<()> {
+        self.sender
+            .as_ref()
+            .expect("Missing request sender")
+            .send(request)
+            .map_err(|_| io::Error::other("io_uring thread has stopped"))?;
+        wake(&self.wakeup);
+        Ok(())
+    }
+}
+
+impl Drop for RequestQueue {
+    fn drop(&mut self) {
+        // The sender is dropped first, so the woken up thread finds the channel disconnected and stops.
+        self.sender = None;
+        wake(&self.wakeup);
+    }
+}
+
+impl Request {
+    fn entries(&self) -> usize {
+        self.lengths.len().max(1)
+    }
+
+    fn reply(self, result: io::Result<usize>) {
+        // The receiver might be already dropped, the buffers are then returned to the pool here.
+        let _ = self.reply.send(Completion {
+            buffers: self.buffers,
+            result,
+        });
+    }
+}
+
+fn wake(wakeup: &OwnedFd) {
+    let value = 1u64;
+    // Safety: the eventfd is open and the value has the 8 bytes required by the eventfd.
+    unsafe {
+        libc::write(
+            wakeup.as_raw_fd(),
+            &value as *const u64 as *const libc::c_void,
+            size_of::<u64>(),
+        )
+    };
+}
+
+fn drain(wakeup: &OwnedFd) {
+    let mut value = 0u64;
+    // Safety: the eventfd is open and non-blocking, the value has the 8 bytes required by the eventfd.
+    unsafe {
+        libc::read(
+            wakeup.as_raw_fd(),
+            &mut value as *mut u64 as *mut libc::c_void,
+            size_of::<u64>(),
+        )
+    };
+}
+
+/// Submits the requests as soon as they're received and replies to each of them as soon as all its completions
+/// are reaped. The thread waits for the completions only, the new requests wake it up through the polled eventfd.
+/// It stops once all the handles to the ring are dropped and the requests in flight are completed.
+fn run(
+    mut ring: IoUring,
+    receiver: Receiver<Request>,
+    pool: Arc<BufferPool>,
+    fixed_buffers: bool,
+    wakeup: Arc<OwnedFd>,
+) {
+    let mut in_flight: Vec<Option<InFlightRequest>> = Vec::new();
+    let mut free_slots = Vec::new();
+    let mut entries = 0;
+    let mut pending = None;
+    let mut disconnected = false;
+    let mut wakeup_polled = false;
+    loop {
+        if !wakeup_polled {
+            let entry = opcode::PollAdd::new(types::Fd(wakeup.as_raw_fd()), libc::POLLIN as u32)
+                .build()
+                .user_data(WAKEUP_USER_DATA);
+            // Safety: the eventfd is kept open by the thread, the entries in flight never exceed the size of the ring.
+            unsafe { ring.submission().push(&entry) }.expect("Submission queue is full");
+            wakeup_polled = true;
+        }
+
+        while !disconnected {
+            let request = match pending.take() {
+                Some(request) => request,
+                None => match receiver.try_recv() {
+                    Ok(request) => request,
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        disconnected = true;
+                        break;
+                    }
+                },
+            };
+            if entries + request.entries() > MAX_REQUEST_ENTRIES {
+                pending = Some(request);
+                break;
+            }
+
+            let slot = free_slots.pop().unwrap_or_else(|| {
+                in_flight.push(None);
+                in_flight.len() - 1
+            });
+            push_entries(&mut ring, slot, &request, &pool, fixed_buffers);
+            entries += request.entries();
+            in_flight[slot] = Some(InFlightRequest {
+                results: vec![0; request.entries()],
+                remaining: request.entries(),
+                request,
+            });
+        }
+
+        if disconnected && entries == 0 {
+            return;
+        }
+
+        match ring.submit_and_wait(1) {
+            Ok(_) => {}
+            // The completion queue is full or the wait was interrupted, the submission is retried after reaping.
+            Err(error)
+                if error.kind() == io::ErrorKind::Interrupted
+                    || matches!(error.raw_os_error(), Some(libc::EBUSY | libc::EAGAIN)) => {}
+            Err(error) => {
+                error!("Failed to submit io_uring requests, the ring is stopped. {error}");
+                let raw_error = error.raw_os_error().unwrap_or(libc::EIO);
+                for in_flight_request in in_flight.into_iter().flatten() {
+                    in_flight_request
+                        .request
+                        .reply(Err(io::Error::from_raw_os_error(raw_error)));
+                }
+                return;
+            }
+        }
+
+        for entry in ring.completion() {
+            if entry.user_data() == WAKEUP_USER_DATA {
+                drain(&wakeup);
+                wakeup_polled = false;
+                continue;
+            }
+
+            let slot = (entry.user_data() >> 32) as usize;
+            let chunk = (entry.user_data() & u32::MAX as u64) as usize;
+            let in_flight_request = in_flight[slot].as_mut().expect("Missing request in flight");
+            in_flight_request.results[chunk] = entry.result();
+            in_flight_request.remaining -= 1;
+            if in_flight_request.remaining > 0 {
+                continue;
+            }
+
+            let in_flight_request = in_flight[slot].take().expect("Missing request in flight");
+            free_slots.push(slot);
+            entries -= in_flight_request.request.entries();
+            let result = complete(&in_flight_request.request, &in_flight_request.results);
+            in_flight_request.request.reply(result);
+        }
+    }
+}
+
+fn push_entries(
+    ring: &mut IoUring,
+    slot: usize,
+    request: &Request,
+    pool: &BufferPool,
+    fixed_buffers: bool,
+) {
+    let mut submission = ring.submission();
+    let fd = types::Fd(request.file.as_raw_fd());
+    let user_data = |chunk: usize| ((slot as u64) << 32) | chunk as u64;
+    if request.operation == Operation::Fsync {
+        let entry = opcode::Fsync::new(fd).build().user_data(user_data(0));
+        // Safety: the file is kept alive by the request until the completion is received,
+        // the entries in flight never exceed the size of the ring.
+        unsafe { submission.push(&entry) }.expect("Submission queue is full");
+        return;
+    }
+
+    let buffers = request.buffers.as_ref().expect("Missing buffers");
+    let mut offset = request.offset;
+    for (chunk, &length) in request.lengths.iter().enumerate() {
+        let buffer_index = buffers.indexes[chunk];
+        let pointer = pool.pointer(buffer_index);
+        let entry = match (request.operation, fixed_buffers) {
+            (Operation::Write, true) => {
+                opcode::WriteFixed::new(fd, pointer, length as u32, buffer_index)
+                    .offset(offset)
+                    .build()
+            }
+            (Operation::Write, false) => opcode::Write::new(fd, pointer, length as u32)
+                .offset(offset)
+                .build(),
+            (_, true) => opcode::ReadFixed::new(fd, pointer, length as u32, buffer_index)
+                .offset(offset)
+                .build(),
+            (_, false) => opcode::Read::new(fd, pointer, length as u32)
+                .offset(offset)
+                .build(),
+        };
+        let mut entry = entry.user_data(user_data(chunk));
+        // The failed write cancels the remaining chunks, so no gap is left in the file.
+        if request.operation == Operation::Write && chunk + 1 < request.lengths.len() {
+            entry = entry.flags(squeue::Flags::IO_LINK);
+        }
+        // Safety: the buffers are leased by the request, so they're not used by anyone else,
+        // and they're returned to the pool only after the completion is received.
+        // The entries in flight never exceed the size of the ring.
+        unsafe { submission.push(&entry) }.expect("Submission queue is full");
+        if offset != u64::MAX {
+            offset += length as u64;
+        }
+    }
+}
+
+fn complete(request: &Request, results: &[i32]) -> io::Result<usize> {
+    if request.operation == Operation::Fsync {
+        return match results[0] {
+            result if result < 0 => Err(io::Error::from_raw_os_error(-result)),
+            _ => Ok(0),
+        };
+    }
+
+    let mut total = 0;
+    for (&result, &length) in results.iter().zip(&request.lengths) {
+        if result < 0 {
+            return Err(io::Error::from_raw_os_error(-result));
+        }
+        total += result as usize;
+        if (result as usize) < length {
+            return match request.operation {
+                // The reads are short only at the end of the file.
+                Operation::Read => Ok(total),
+                _ => Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    format!("Short write of {result} bytes out of {length}."),
+                )),
+            };
+        }
+    }
+    Ok(total)
+}
+
+/// Memory of the buffers registered with the ring, each buffer is used exclusively by the holder of its lease.
+struct BufferPool {
+    pointer: NonNull<u8>,
+    free: Mutex<Vec<u16>>,
+    permits: Arc<Semaphore>,
+}
+
+// Safety: the memory is never reallocated and each buffer is accessed only through its lease.
+unsafe impl Send for BufferPool {}
+unsafe impl Sync for BufferPool {}
+
+impl std::fmt::Debug for BufferPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BufferPool")
+            .field("buffers", &BUFFERS_COUNT)
+            .field("buffer_size", &BUFFER_SIZE)
+            .finish()
+    }
+}
+
+impl BufferPool {
+    fn layout() -> Layout {
+        Layout::from_size_align(BUFFERS_COUNT * BUFFER_SIZE, PAGE_SIZE).expect("Invalid layout")
+    }
+
+    fn new() -> Self {
+        // Safety: the layout has a non-zero size, the memory is released when the pool is dropped.
+        let pointer = unsafe { alloc_zeroed(Self::layout()) };
+        let Some(pointer) = NonNull::new(pointer) else {
+            handle_alloc_error(Self::layout());
+        };
+        Self {
+            pointer,
+            free: Mutex::new((0..BUFFERS_COUNT as u16).collect()),
+            permits: Arc::new(Semaphore::new(BUFFERS_COUNT)),
+        }
+    }
+
+    fn iovecs(&self) -> Vec<libc::iovec> {
+        (0..BUFFERS_COUNT as u16)
+            .map(|index| libc::iovec {
+                iov_base: self.pointer(index) as *mut libc::c_void,
+                iov_len: BUFFER_SIZE,
+            })
+            .collect()
+    }
+
+    fn pointer(&self, index: u16) -> *mut u8 {
+        // Safety: the index is always lower than the count of the buffers.
+        unsafe { self.pointer.as_ptr().add(index as usize * BUFFER_SIZE) }
+    }
+
+    async fn acquire(self: Arc<Self>, count: usize) -> BufferLease {
+        let permit = self
+            .permits
+            .clone()
+            .acquire_many_owned(count as u32)
+            .await
+            .expect("Buffer pool semaphore is closed");
+        let indexes = {
+            let mut free = self.free.lock().unwrap();
+            let at = free.len() - count;
+            free.split_off(at)
+        };
+        BufferLease {
+            pool: self,
+            indexes,
+            _permit: permit,
+        }
+    }
+}
+
+impl Drop for BufferPool {
+    fn drop(&mut self) {
+        // Safety: the pool is dropped only after the ring thread and all the leases are gone.
+        unsafe { dealloc(self.pointer.as_ptr(), Self::layout()) }
+    }
+}
+
+struct BufferLease {
+    pool: Arc<BufferPool>,
+    indexes: Vec<u16>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl BufferLease {
+    fn get(&self, index: usize) -> &[u8] {
+        // Safety: the buffer is leased, so it's not written by anyone else.
+        unsafe { std::slice::from_raw_parts(self.pool.pointer(self.indexes[index]), BUFFER_SIZE) }
+    }
+
+    fn get_mut(&mut self, index: usize) -> &mut [u8] {
+        // Safety: the buffer is leased, so it's not accessed by anyone else.
+        unsafe {
+            std::slice::from_raw_parts_mut(self.pool.pointer(self.indexes[index]), BUFFER_SIZE)
+        }
+    }
+}
+
+impl Drop for BufferLease {
+    fn drop(&mut self) {
+        // The indexes are returned before the permit is released, when the lease fields are dropped.
+        self.pool.free.lock().unwrap().append(&mut self.indexes);
+    }
+}
+
+#[derive(Debug)]
+pub struct IoUringPersister {
+    ring: IoUringRing,
+    fsync: bool,
+    metrics: StorageMetrics,
+}
+
+impl IoUringPersister {
+    pub fn new(ring: IoUringRing, fsync: bool, metrics: StorageMetrics) -> Self {
+        Self {
+            ring,
+            fsync,
+            metrics,
+        }
+    }
+
+    async fn write(
+        &self,
+        file: tokio::fs::File,
+        offset: Option<u64>,
+        bytes: &[u8],
+    ) -> Result<(), IggyError> {
+        let file = Arc::new(file.into_std().await);
+        self.ring.write(&file, offset, bytes).await?;
+        if self.fsync {
+            let started_at = Instant::now();
+            self.ring.fsync(&file).await?;
+            self.metrics.observe_fsync_duration(started_at.elapsed());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Persister for IoUringPersister {
+    async fn append(&self, path: &str, bytes: &[u8]) -> Result<(), IggyError> {
+        self.write(file::append(path).await?, None, bytes).await
+    }
+
+    async fn overwrite(&self, path: &str, bytes: &[u8]) -> Result<(), IggyError> {
+        self.write(file::overwrite(path).await?, Some(0), bytes)
+            .await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), IggyError> {
+        fs::remove_file(path).await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct IoUringSegmentReader {
+    ring: IoUringRing,
+}
+
+impl IoUringSegmentReader {
+    pub fn new(ring: IoUringRing) -> Self {
+        Self { ring }
+    }
+}
+
+#[async_trait]
+impl SegmentReader for IoUringSegmentReader {
+    async fn open(&self, path: &str, position: u64) -> Result<SegmentFile, IggyError> {
+        let file = file::open(path).await?;
+        let size = file.metadata().await?.len();
+        let file = Arc::new(file.into_std().await);
+        Ok(SegmentFile {
+            size,
+            reader: Box::new(IoUringFileReader {
+                ring: self.ring.clone(),
+                file,
+                position,
+                size,
+                buffer: Bytes::new(),
+                pending: None,
+            }),
+        })
+    }
+}
+
+type ReadFuture = Pin<Box<dyn Future<Output = io::Result<BytesMut>> + Send>>;
+
+/// Reads the file sequentially, with the read-ahead of multiple buffers submitted at once.
+struct IoUringFileReader {
+    ring: IoUringRing,
+    file: Arc<File>,
+    position: u64,
+    size: u64,
+    buffer: Bytes,
+    pending: Option<ReadFuture>,
+}
+
+impl AsyncRead for IoUringFileReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            if !self.buffer.is_empty() {
+                let length = self.buffer.len().min(buf.remaining());
+                let bytes = self.buffer.split_to(length);
+                buf.put_slice(&bytes);
+                return Poll::Ready(Ok(()));
+            }
+
+            if self.pending.is_none() {
+                if self.position >= self.size {
+                    return Poll::Ready(Ok(()));
+                }
+                let ring = self.ring.clone();
+                let file = self.file.clone();
+                let position = self.position;
+                let length = READ_AHEAD_BYTES.min((self.size - position) as usize);
+                self.pending = Some(Box::pin(
+                    async move { ring.read(&file, position, length).await },
+                ));
+            }
+
+            let bytes = match self.pending.as_mut().unwrap().as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(result) => {
+                    self.pending = None;
+                    result?
+                }
+            };
+            if bytes.is_empty() {
+                // The file was truncated after it was opened.
+                self.size = self.position;
+            }
+            self.position += bytes.len() as u64;
+            self.buffer = bytes.freeze();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::io::AsyncReadExt;
+
+    #[tokio::test]
+    async fn should_write_and_read_payloads_spanning_multiple_buffers() {
+        let path = temp_path();
+        let ring = IoUringRing::new().unwrap();
+        let persister = IoUringPersister::new(ring.clone(), true, StorageMetrics::default());
+        let first = payload(3 * BUFFER_SIZE + 17, 1);
+        let second = payload(MAX_BUFFERS_PER_REQUEST * BUFFER_SIZE + 5, 7);
+
+        persister.overwrite(&path, &first).await.unwrap();
+        persister.append(&path, &second).await.unwrap();
+
+        let file = Arc::new(File::open(&path).unwrap());
+        let bytes = ring
+            .read(&file, 0, first.len() + second.len())
+            .await
+            .unwrap();
+        assert_eq!(&bytes[..first.len()], first.as_slice());
+        assert_eq!(&bytes[first.len()..], second.as_slice());
+
+        persister.overwrite(&path, &first[..10]).await.unwrap();
+        let file = Arc::new(File::open(&path).unwrap());
+        let bytes = ring.read(&file, 0, BUFFER_SIZE).await.unwrap();
+        assert_eq!(&bytes[..], &first[..10]);
+
+        persister.delete(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_return_short_read_at_end_of_file() {
+        let path = temp_path();
+        let ring = IoUringRing::new().unwrap();
+        let data = payload(BUFFER_SIZE + 100, 3);
+        std::fs::write(&path, &data).unwrap();
+
+        let file = Arc::new(File::open(&path).unwrap());
+        let bytes = ring.read(&file, 50, 2 * BUFFER_SIZE).await.unwrap();
+        assert_eq!(&bytes[..], &data[50..]);
+        let bytes = ring.read(&file, data.len() as u64, 10).await.unwrap();
+        assert!(bytes.is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_complete_request_while_previous_one_is_in_flight() {
+        let path = temp_path();
+        let ring = IoUringRing::new().unwrap();
+        let persister = IoUringPersister::new(ring.clone(), true, StorageMetrics::default());
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        let (pipe_reader, mut pipe_writer) =
+            unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) };
+        let pipe_reader = Arc::new(pipe_reader);
+
+        // The read of the empty pipe stays in flight until something is written to it.
+        let blocked_read = tokio::spawn({
+            let ring = ring.clone();
+            async move { ring.read(&pipe_reader, u64::MAX, 5).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let data = payload(BUFFER_SIZE + 10, 9);
+        tokio::time::timeout(Duration::from_secs(5), persister.overwrite(&path, &data))
+            .await
+            .expect("Write has waited for the blocked read")
+            .unwrap();
+        assert!(!blocked_read.is_finished());
+
+        std::io::Write::write_all(&mut pipe_writer, b"hello").unwrap();
+        let bytes = tokio::time::timeout(Duration::from_secs(5), blocked_read)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(&bytes[..], b"hello");
+
+        persister.delete(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn segment_reader_should_read_file_from_given_position() {
+        let path = temp_path();
+        let ring = IoUringRing::new().unwrap();
+        let data = payload(2 * READ_AHEAD_BYTES + 1234, 5);
+        std::fs::write(&path, &data).unwrap();
+
+        let reader = IoUringSegmentReader::new(ring);
+        let position = 999;
+        let mut segment_file = reader.open(&path, position).await.unwrap();
+        assert_eq!(segment_file.size, data.len() as u64);
+
+        let mut bytes = Vec::new();
+        segment_file.reader.read_to_end(&mut bytes).await.unwrap();
+        assert_eq!(bytes.as_slice(), &data[position as usize..]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    fn temp_path() -> String {
+        std::env::temp_dir()
+            .join(format!("iggy-io-uring-{}", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    fn payload(length: usize, seed: u8) -> Vec<u8> {
+        (0..length)
+            .map(|i| (i as u8).wrapping_mul(31).wrapping_add(seed))
+            .collect()
+    }
+}
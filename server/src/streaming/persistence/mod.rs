@@ -1 +1,4 @@
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+pub mod io_uring;
 pub mod persister;
+pub mod reader;
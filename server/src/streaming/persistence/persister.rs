@@ -1,8 +1,11 @@
+use crate::streaming::diagnostics::metrics::StorageMetrics;
 use crate::streaming::utils::file;
 use async_trait::async_trait;
 use iggy::error::IggyError;
 use std::fmt::Debug;
+use std::time::Instant;
 use tokio::fs;
+use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
 
 #[async_trait]
@@ -24,7 +27,22 @@ impl Debug for dyn Persister {
 pub struct FilePersister;
 
 #[derive(Debug)]
-pub struct FileWithSyncPersister;
+pub struct FileWithSyncPersister {
+    metrics: StorageMetrics,
+}
+
+impl FileWithSyncPersister {
+    pub fn new(metrics: StorageMetrics) -> Self {
+        Self { metrics }
+    }
+
+    async fn sync(&self, file: &File) -> Result<(), IggyError> {
+        let started_at = Instant::now();
+        file.sync_all().await?;
+        self.metrics.observe_fsync_duration(started_at.elapsed());
+        Ok(())
+    }
+}
 
 unsafe impl Send for FilePersister {}
 unsafe impl Sync for FilePersister {}
@@ -57,15 +75,13 @@ impl Persister for FileWithSyncPersister {
     async fn append(&self, path: &str, bytes: &[u8]) -> Result<(), IggyError> {
         let mut file = file::append(path).await?;
         file.write_all(bytes).await?;
-        file.sync_all().await?;
-        Ok(())
+        self.sync(&file).await
     }
 
     async fn overwrite(&self, path: &str, bytes: &[u8]) -> Result<(), IggyError> {
         let mut file = file::overwrite(path).await?;
         file.write_all(bytes).await?;
-        file.sync_all().await?;
-        Ok(())
+        self.sync(&file).await
     }
 
     async fn delete(&self, path: &str) -> Result<(), IggyError> {
@@ -0,0 +1,46 @@
+use crate::streaming::utils::file;
+use async_trait::async_trait;
+use iggy::error::IggyError;
+use std::fmt::Debug;
+use std::io::SeekFrom;
+use tokio::io::{AsyncRead, AsyncSeekExt, BufReader};
+
+const BUF_READER_CAPACITY_BYTES: usize = 512 * 1000;
+
+/// Segment log file opened for the sequential reads of the message batches.
+pub struct SegmentFile {
+    /// Size of the file at the time it was opened.
+    pub size: u64,
+    pub reader: Box<dyn AsyncRead + Unpin + Send>,
+}
+
+#[async_trait]
+pub trait SegmentReader: Sync + Send {
+    /// Opens the file for reading, starting at the given position.
+    async fn open(&self, path: &str, position: u64) -> Result<SegmentFile, IggyError>;
+}
+
+impl Debug for dyn SegmentReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SegmentReader")
+            .field("type", &"SegmentReader")
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+pub struct FileSegmentReader;
+
+#[async_trait]
+impl SegmentReader for FileSegmentReader {
+    async fn open(&self, path: &str, position: u64) -> Result<SegmentFile, IggyError> {
+        let file = file::open(path).await?;
+        let size = file.metadata().await?.len();
+        let mut reader = BufReader::with_capacity(BUF_READER_CAPACITY_BYTES, file);
+        reader.seek(SeekFrom::Start(position)).await?;
+        Ok(SegmentFile {
+            size,
+            reader: Box::new(reader),
+        })
+    }
+}
@@ -0,0 +1,92 @@
+use iggy::error::IggyError;
+use std::io::SeekFrom;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+pub const BATCH_HEADER_SIZE: u64 = 8 + 4 + 4 + 8;
+
+/// Header of the message batch stored in the segment log, followed by `length` bytes of the messages.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchHeader {
+    pub base_offset: u64,
+    pub length: u32,
+    pub last_offset_delta: u32,
+    pub max_timestamp: u64,
+}
+
+impl BatchHeader {
+    pub fn last_offset(&self) -> u64 {
+        self.base_offset + self.last_offset_delta as u64
+    }
+
+    pub fn size_bytes(&self) -> u64 {
+        BATCH_HEADER_SIZE + self.length as u64
+    }
+}
+
+pub async fn read_batch_header<R: AsyncReadExt + Unpin>(
+    reader: &mut R,
+) -> Result<BatchHeader, IggyError> {
+    let base_offset = reader
+        .read_u64_le()
+        .await
+        .map_err(|_| IggyError::CannotReadBatchBaseOffset)?;
+    let length = reader
+        .read_u32_le()
+        .await
+        .map_err(|_| IggyError::CannotReadBatchLength)?;
+    let last_offset_delta = reader
+        .read_u32_le()
+        .await
+        .map_err(|_| IggyError::CannotReadLastOffsetDelta)?;
+    let max_timestamp = reader
+        .read_u64_le()
+        .await
+        .map_err(|_| IggyError::CannotReadMaxTimestamp)?;
+    Ok(BatchHeader {
+        base_offset,
+        length,
+        last_offset_delta,
+        max_timestamp,
+    })
+}
+
+/// Reads only the headers of the batches, starting at the given position (which must be the start of a batch),
+/// until the batch containing the given offset is found. Returns the position and the header of that batch,
+/// or `None` if the log ends before the offset.
+pub async fn find_batch_header(
+    file: &mut File,
+    mut position: u64,
+    file_size: u64,
+    offset: u64,
+) -> Result<Option<(u64, BatchHeader)>, IggyError> {
+    while position + BATCH_HEADER_SIZE <= file_size {
+        file.seek(SeekFrom::Start(position)).await?;
+        let header = read_batch_header(file).await?;
+        if header.last_offset() >= offset {
+            return Ok(Some((position, header)));
+        }
+
+        position += header.size_bytes();
+    }
+
+    Ok(None)
+}
+
+/// Reads only the headers of the batches, starting at the given position (which must be the start of a batch),
+/// until the end of the log. Returns the position and the header of the last batch, if there is any.
+pub async fn find_last_batch_header(
+    file: &mut File,
+    mut position: u64,
+    file_size: u64,
+) -> Result<Option<(u64, BatchHeader)>, IggyError> {
+    let mut last_batch = None;
+    while position + BATCH_HEADER_SIZE <= file_size {
+        file.seek(SeekFrom::Start(position)).await?;
+        let header = read_batch_header(file).await?;
+        last_batch = Some((position, header));
+        position += header.size_bytes();
+    }
+
+    Ok(last_batch)
+}
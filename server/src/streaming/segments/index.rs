@@ -1,7 +1,10 @@
+use crate::streaming::segments::mapped_index::IndexEntry;
 use crate::streaming::segments::segment::Segment;
 use iggy::error::IggyError;
 use iggy::error::IggyError::InvalidOffset;
 
+/// Entry of the sparse offset index, pointing to the `position` of the batch in the log,
+/// which ends with the message at the `relative_offset`.
 #[derive(Debug, Eq, Clone, Copy, Default)]
 pub struct Index {
     pub relative_offset: u32,
@@ -14,46 +17,124 @@ impl PartialEq<Self> for Index {
     }
 }
 
+impl IndexEntry for Index {
+    const SIZE: usize = 8;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Index {
+            relative_offset: u32::from_le_bytes(bytes[..4].try_into().unwrap()),
+            position: u32::from_le_bytes(bytes[4..8].try_into().unwrap()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub struct IndexRange {
     pub start: Index,
     pub end: Index,
 }
+
+/// Read access to the index entries sorted by the relative offset, either cached in memory or memory-mapped.
+pub trait IndexEntries<T: Copy> {
+    fn count(&self) -> usize;
+    fn get(&self, position: usize) -> T;
+
+    /// Returns the number of the leading entries matching the predicate, using the binary search.
+    fn partition_point(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        let (mut low, mut high) = (0, self.count());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if predicate(&self.get(middle)) {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        low
+    }
+}
+
+impl<T: Copy> IndexEntries<T> for [T] {
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn get(&self, position: usize) -> T {
+        self[position]
+    }
+}
+
+/// Decides which batches get an entry in the sparse indexes: the first batch of the segment,
+/// and then the first batch starting at least `interval` bytes after the previously indexed one.
+#[derive(Debug, Clone, Copy)]
+pub struct IndexInterval {
+    interval: u32,
+    last_indexed_position: Option<u32>,
+}
+
+impl IndexInterval {
+    pub fn new(interval: u32) -> Self {
+        Self {
+            interval,
+            last_indexed_position: None,
+        }
+    }
+
+    pub fn set_last_indexed_position(&mut self, position: u32) {
+        self.last_indexed_position = Some(position);
+    }
+
+    /// Returns whether the batch starting at the given position should be indexed, and if so, marks it as indexed.
+    pub fn should_index(&mut self, position: u32) -> bool {
+        let should_index = match self.last_indexed_position {
+            Some(last_indexed_position) => position - last_indexed_position >= self.interval,
+            None => true,
+        };
+        if should_index {
+            self.last_indexed_position = Some(position);
+        }
+        should_index
+    }
+}
+
 impl Segment {
+    /// Returns the range of the log to read the batches containing the messages with the given relative offsets from.
+    /// As the index is sparse, the range starts at the last indexed batch preceding the start offset
+    /// (or the first batch), and ends with the first indexed batch containing or following the end offset,
+    /// or at the end of the log, if there's no such batch.
     pub fn load_highest_lower_bound_index(
         &self,
-        indices: &[Index],
+        indices: &(impl IndexEntries<Index> + ?Sized),
         start_offset: u32,
         end_offset: u32,
     ) -> Result<IndexRange, IggyError> {
-        let starting_offset_idx = binary_search_index(indices, start_offset);
-        let ending_offset_idx = binary_search_index(indices, end_offset);
-
-        match (starting_offset_idx, ending_offset_idx) {
-            (Some(starting_offset_idx), Some(ending_offset_idx)) => Ok(IndexRange {
-                start: indices[starting_offset_idx],
-                end: indices[ending_offset_idx],
-            }),
-            (Some(starting_offset_idx), None) => Ok(IndexRange {
-                start: indices[starting_offset_idx],
-                end: *indices.last().unwrap(),
-            }),
-            (None, _) => Err(InvalidOffset(start_offset as u64 + self.start_offset)),
-        }
+        let start = find_preceding_index(indices, start_offset)
+            .ok_or(InvalidOffset(start_offset as u64 + self.start_offset))?;
+        let end_index = indices.partition_point(|index| index.relative_offset < end_offset);
+        let end = match end_index < indices.count() {
+            true => indices.get(end_index),
+            false => IndexRange::max_range().end,
+        };
+        Ok(IndexRange { start, end })
     }
 }
-fn binary_search_index(indices: &[Index], offset: u32) -> Option<usize> {
-    match indices.binary_search_by(|index| index.relative_offset.cmp(&offset)) {
-        Ok(index) => Some(index),
-        Err(index) => {
-            if index < indices.len() {
-                Some(index)
-            } else {
-                None
-            }
-        }
+
+/// Returns the last indexed batch preceding the given relative offset (or the first batch),
+/// from which the log can be scanned to find the batch containing this offset.
+pub fn find_preceding_index(
+    indices: &(impl IndexEntries<Index> + ?Sized),
+    relative_offset: u32,
+) -> Option<Index> {
+    if indices.count() == 0 {
+        return None;
+    }
+
+    match indices.partition_point(|index| index.relative_offset < relative_offset) {
+        0 => Some(indices.get(0)),
+        index => Some(indices.get(index - 1)),
     }
 }
+
 impl IndexRange {
     pub fn max_range() -> Self {
         Self {
@@ -136,14 +217,14 @@ mod tests {
     }
 
     #[test]
-    fn should_find_both_indices() {
+    fn should_find_preceding_start_index_and_following_end_index() {
         let mut segment = create_segment();
         create_test_indices(&mut segment);
         let result = segment
-            .load_highest_lower_bound_index(segment.indexes.as_ref().unwrap(), 15, 45)
+            .load_highest_lower_bound_index(segment.indexes.as_deref().unwrap(), 15, 45)
             .unwrap();
 
-        assert_eq!(result.start.relative_offset, 20);
+        assert_eq!(result.start.relative_offset, 5);
         assert_eq!(result.end.relative_offset, 50);
     }
 
@@ -151,39 +232,77 @@ mod tests {
     fn start_and_end_index_should_be_equal() {
         let mut segment = create_segment();
         create_test_indices(&mut segment);
-        let result_end_range = segment
-            .load_highest_lower_bound_index(segment.indexes.as_ref().unwrap(), 65, 100)
-            .unwrap();
-
-        assert_eq!(result_end_range.start.relative_offset, 65);
-        assert_eq!(result_end_range.end.relative_offset, 65);
-
         let result_start_range = segment
-            .load_highest_lower_bound_index(segment.indexes.as_ref().unwrap(), 0, 5)
+            .load_highest_lower_bound_index(segment.indexes.as_deref().unwrap(), 0, 5)
             .unwrap();
+
         assert_eq!(result_start_range.start.relative_offset, 5);
         assert_eq!(result_start_range.end.relative_offset, 5);
+
+        let result_middle_range = segment
+            .load_highest_lower_bound_index(segment.indexes.as_deref().unwrap(), 36, 49)
+            .unwrap();
+        assert_eq!(result_middle_range.start.relative_offset, 35);
+        assert_eq!(result_middle_range.end.relative_offset, 50);
     }
 
     #[test]
-    fn should_clamp_last_index_when_out_of_range() {
+    fn should_read_until_end_of_log_when_end_offset_is_past_last_index() {
         let mut segment = create_segment();
         create_test_indices(&mut segment);
         let result = segment
-            .load_highest_lower_bound_index(segment.indexes.as_ref().unwrap(), 5, 100)
+            .load_highest_lower_bound_index(segment.indexes.as_deref().unwrap(), 5, 100)
             .unwrap();
 
         assert_eq!(result.start.relative_offset, 5);
-        assert_eq!(result.end.relative_offset, 65);
+        assert_eq!(result.end, IndexRange::max_range().end);
     }
 
     #[test]
-    fn should_return_err_when_both_indices_out_of_range() {
+    fn should_start_from_last_index_when_both_offsets_are_past_it() {
         let mut segment = create_segment();
         create_test_indices(&mut segment);
+        let result = segment
+            .load_highest_lower_bound_index(segment.indexes.as_deref().unwrap(), 100, 200)
+            .unwrap();
+
+        assert_eq!(result.start.relative_offset, 65);
+        assert_eq!(result.start.position, 400);
+        assert_eq!(result.end, IndexRange::max_range().end);
+    }
 
+    #[test]
+    fn should_return_err_when_there_are_no_indices() {
+        let segment = create_segment();
         let result =
-            segment.load_highest_lower_bound_index(segment.indexes.as_ref().unwrap(), 100, 200);
+            segment.load_highest_lower_bound_index(segment.indexes.as_deref().unwrap(), 0, 10);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn should_index_first_batch_and_then_batches_starting_after_interval() {
+        let mut index_interval = IndexInterval::new(100);
+        let indexed_positions = [0, 40, 80, 120, 160, 200, 300]
+            .into_iter()
+            .filter(|position| index_interval.should_index(*position))
+            .collect::<Vec<_>>();
+
+        assert_eq!(indexed_positions, vec![0, 120, 300]);
+    }
+
+    #[test]
+    fn should_index_every_batch_when_interval_is_zero() {
+        let mut index_interval = IndexInterval::new(0);
+        assert!(index_interval.should_index(0));
+        assert!(index_interval.should_index(10));
+        assert!(index_interval.should_index(20));
+    }
+
+    #[test]
+    fn should_continue_interval_from_last_indexed_position() {
+        let mut index_interval = IndexInterval::new(100);
+        index_interval.set_last_indexed_position(500);
+        assert!(!index_interval.should_index(550));
+        assert!(index_interval.should_index(600));
+    }
 }
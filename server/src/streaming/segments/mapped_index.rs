@@ -0,0 +1,199 @@
+use crate::streaming::segments::index::IndexEntries;
+use iggy::error::IggyError;
+use memmap2::Mmap;
+use std::fs::File;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+/// Entry of the index file with the fixed size.
+pub trait IndexEntry: Copy {
+    const SIZE: usize;
+    fn from_bytes(bytes: &[u8]) -> Self;
+}
+
+/// Index file mapped into memory, so that the entries can be binary searched without reading the whole file.
+#[derive(Debug)]
+pub struct MappedIndex<T: IndexEntry> {
+    mmap: Option<Mmap>,
+    count: usize,
+    _entry: PhantomData<T>,
+}
+
+impl<T: IndexEntry> MappedIndex<T> {
+    /// Maps the entries currently stored in the file, the ones appended later are not visible.
+    pub fn open(path: &str) -> Result<Self, IggyError> {
+        let file = File::open(path)?;
+        let count = file.metadata()?.len() as usize / T::SIZE;
+        if count == 0 {
+            return Ok(Self {
+                mmap: None,
+                count,
+                _entry: PhantomData,
+            });
+        }
+
+        // SAFETY: The index files are only appended to while the server is running, the existing entries
+        // are never modified in place (the rebuilt indexes replace the file or happen before it's read).
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(Self {
+            mmap: Some(mmap),
+            count,
+            _entry: PhantomData,
+        })
+    }
+
+    pub fn last(&self) -> Option<T> {
+        self.count.checked_sub(1).map(|position| self.get(position))
+    }
+}
+
+/// Mapping of the index file kept by the segment, so that the file is mapped once instead of on every lookup.
+/// It's reset whenever the file changes (the entries are appended to the active segment or the file is replaced),
+/// and the file is mapped again on the next lookup, so the closed segments are mapped only once.
+#[derive(Debug)]
+pub struct CachedMappedIndex<T: IndexEntry> {
+    mapped_index: Mutex<Option<Arc<MappedIndex<T>>>>,
+}
+
+impl<T: IndexEntry> CachedMappedIndex<T> {
+    pub fn new() -> Self {
+        Self {
+            mapped_index: Mutex::new(None),
+        }
+    }
+
+    /// Returns the cached mapping of the file, or maps the file if it's not mapped yet or has changed since.
+    pub fn get(&self, path: &str) -> Result<Arc<MappedIndex<T>>, IggyError> {
+        let mut mapped_index = self.mapped_index.lock().unwrap();
+        if let Some(mapped_index) = mapped_index.as_ref() {
+            return Ok(mapped_index.clone());
+        }
+
+        let index = Arc::new(MappedIndex::open(path)?);
+        *mapped_index = Some(index.clone());
+        Ok(index)
+    }
+
+    pub fn reset(&self) {
+        self.mapped_index.lock().unwrap().take();
+    }
+}
+
+impl<T: IndexEntry> Default for CachedMappedIndex<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: IndexEntry> IndexEntries<T> for MappedIndex<T> {
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn get(&self, position: usize) -> T {
+        let mmap = self.mmap.as_ref().expect("Index file is not mapped.");
+        let start = position * T::SIZE;
+        T::from_bytes(&mmap[start..start + T::SIZE])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::segments::index::{find_preceding_index, Index};
+    use crate::streaming::segments::time_index::{find_time_index, TimeIndex};
+    use bytes::BufMut;
+
+    #[test]
+    fn should_binary_search_entries_of_mapped_index_file() {
+        let path = temp_path();
+        let mut bytes = Vec::new();
+        for (relative_offset, position) in [(9, 0), (19, 1000), (29, 2000), (39, 3000)] {
+            bytes.put_u32_le(relative_offset);
+            bytes.put_u32_le(position);
+        }
+        // The partially written entry is ignored.
+        bytes.put_u32_le(49);
+        std::fs::write(&path, &bytes).unwrap();
+
+        let indexes = MappedIndex::<Index>::open(&path).unwrap();
+        assert_eq!(indexes.count(), 4);
+        assert_eq!(indexes.last().unwrap().position, 3000);
+        assert_eq!(find_preceding_index(&indexes, 5).unwrap().position, 0);
+        assert_eq!(find_preceding_index(&indexes, 25).unwrap().position, 1000);
+        assert_eq!(find_preceding_index(&indexes, 100).unwrap().position, 3000);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_find_last_time_index_not_newer_than_timestamp() {
+        let path = temp_path();
+        let mut bytes = Vec::new();
+        for (relative_offset, timestamp) in [(9, 100), (19, 200), (29, 300)] {
+            bytes.put_u32_le(relative_offset);
+            bytes.put_u64_le(timestamp);
+        }
+        std::fs::write(&path, &bytes).unwrap();
+
+        let time_indexes = MappedIndex::<TimeIndex>::open(&path).unwrap();
+        assert!(find_time_index(&time_indexes, 99).is_none());
+        assert_eq!(
+            find_time_index(&time_indexes, 200).unwrap().relative_offset,
+            19
+        );
+        assert_eq!(
+            find_time_index(&time_indexes, 250).unwrap().relative_offset,
+            19
+        );
+        assert_eq!(
+            find_time_index(&time_indexes, 1000)
+                .unwrap()
+                .relative_offset,
+            29
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn cached_index_should_be_mapped_again_only_after_reset() {
+        let path = temp_path();
+        let mut bytes = Vec::new();
+        bytes.put_u32_le(9);
+        bytes.put_u32_le(0);
+        std::fs::write(&path, &bytes).unwrap();
+
+        let cached_index = CachedMappedIndex::<Index>::new();
+        let indexes = cached_index.get(&path).unwrap();
+        assert_eq!(indexes.count(), 1);
+
+        bytes.put_u32_le(19);
+        bytes.put_u32_le(1000);
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(Arc::ptr_eq(&indexes, &cached_index.get(&path).unwrap()));
+
+        cached_index.reset();
+        let indexes = cached_index.get(&path).unwrap();
+        assert_eq!(indexes.count(), 2);
+        assert_eq!(indexes.last().unwrap().position, 1000);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn empty_index_file_should_not_be_mapped() {
+        let path = temp_path();
+        std::fs::write(&path, []).unwrap();
+
+        let indexes = MappedIndex::<Index>::open(&path).unwrap();
+        assert_eq!(indexes.count(), 0);
+        assert!(indexes.last().is_none());
+        assert!(find_preceding_index(&indexes, 0).is_none());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    fn temp_path() -> String {
+        std::env::temp_dir()
+            .join(format!("iggy-index-{}", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string()
+    }
+}
@@ -1,14 +1,18 @@
 use crate::streaming::batching::batch_filter::BatchItemizer;
 use crate::streaming::batching::message_batch::RetainedMessageBatch;
+use crate::streaming::batching::polled_batches::BatchesChunk;
 use crate::streaming::models::messages::RetainedMessage;
-use crate::streaming::segments::index::{Index, IndexRange};
+use crate::streaming::segments::batch_header::find_batch_header;
+use crate::streaming::segments::index::{find_preceding_index, Index, IndexRange};
 use crate::streaming::segments::segment::Segment;
 use crate::streaming::segments::time_index::TimeIndex;
 use crate::streaming::sizeable::Sizeable;
+use crate::streaming::utils::file;
 use bytes::BufMut;
 use iggy::error::IggyError;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Instant;
 use tracing::{info, trace, warn};
 
 const EMPTY_MESSAGES: Vec<RetainedMessage> = vec![];
@@ -22,6 +26,56 @@ impl Segment {
         self.current_offset - self.start_offset + 1
     }
 
+    /// Returns the size of the batches containing the messages starting from the given offset.
+    /// The unsaved batches are checked first, otherwise the cached (or mapped) sparse index points to the batch
+    /// preceding the offset, from which only the headers of the following batches are read, until the one containing
+    /// the offset is found, and the size of the log from its position is returned.
+    pub async fn get_size_bytes_from_offset(&self, offset: u64) -> Result<u64, IggyError> {
+        if self.get_messages_count() == 0 || offset > self.current_offset {
+            return Ok(0);
+        }
+
+        if offset <= self.start_offset {
+            return Ok(self.size_bytes as u64);
+        }
+
+        let mut unsaved_size_bytes = 0;
+        for batch in self
+            .unsaved_batches
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .rev()
+        {
+            unsaved_size_bytes += batch.get_size_bytes() as u64;
+            if batch.base_offset <= offset {
+                return Ok(unsaved_size_bytes);
+            }
+        }
+
+        let relative_offset = (offset - self.start_offset) as u32;
+        let index = match &self.indexes {
+            Some(indexes) => find_preceding_index(indexes.as_slice(), relative_offset),
+            None => {
+                let indexes = self.mapped_index.get(&self.index_path)?;
+                find_preceding_index(indexes.as_ref(), relative_offset)
+            }
+        };
+        let Some(index) = index else {
+            return Err(IggyError::InvalidOffset(offset));
+        };
+
+        let mut file = file::open(&self.log_path).await?;
+        let saved_size_bytes = self.size_bytes as u64 - unsaved_size_bytes;
+        let Some((position, _)) =
+            find_batch_header(&mut file, index.position as u64, saved_size_bytes, offset).await?
+        else {
+            return Err(IggyError::InvalidOffset(offset));
+        };
+
+        Ok(self.size_bytes as u64 - position)
+    }
+
     pub async fn get_messages(
         &self,
         mut offset: u64,
@@ -89,6 +143,114 @@ impl Segment {
         Ok(messages)
     }
 
+    /// Appends the chunks of the whole batches containing the messages from the given offset range, returns the number
+    /// of these messages. The persisted batches are returned as the region of the segment file found by the indexes,
+    /// so they can be sent without loading them, the unsaved ones are encoded from memory.
+    pub async fn get_batches_chunks(
+        &self,
+        start_offset: u64,
+        end_offset: u64,
+        chunks: &mut Vec<BatchesChunk>,
+    ) -> Result<u64, IggyError> {
+        if self.get_messages_count() == 0 {
+            return Ok(0);
+        }
+
+        let start_offset = start_offset.max(self.start_offset);
+        let end_offset = end_offset.min(self.current_offset);
+        if start_offset > end_offset {
+            return Ok(0);
+        }
+
+        let unsaved_batches = self.unsaved_batches.as_deref().unwrap_or_default();
+        let first_unsaved_offset = unsaved_batches
+            .first()
+            .map_or(u64::MAX, |batch| batch.base_offset);
+        let mut messages_count = 0;
+        if start_offset < first_unsaved_offset {
+            let persisted_end_offset = end_offset.min(first_unsaved_offset - 1);
+            let (chunk, persisted_messages_count) = self
+                .get_persisted_batches_chunk(start_offset, persisted_end_offset)
+                .await?;
+            chunks.push(chunk);
+            messages_count += persisted_messages_count;
+        }
+
+        if end_offset >= first_unsaved_offset {
+            let start_offset = start_offset.max(first_unsaved_offset);
+            chunks.push(BatchesChunk::from_batches(unsaved_batches.iter().filter(
+                |batch| batch.base_offset <= end_offset && batch.get_last_offset() >= start_offset,
+            )));
+            messages_count += end_offset - start_offset + 1;
+        }
+
+        Ok(messages_count)
+    }
+
+    async fn get_persisted_batches_chunk(
+        &self,
+        start_offset: u64,
+        end_offset: u64,
+    ) -> Result<(BatchesChunk, u64), IggyError> {
+        // The sparse index points to the batches preceding the offsets, from which only the headers of the following
+        // batches are read, until the ones containing the start and the end offset are found.
+        let relative_start_offset = (start_offset - self.start_offset) as u32;
+        let relative_end_offset = (end_offset - self.start_offset) as u32;
+        let (start_index, end_index) = match &self.indexes {
+            Some(indexes) => (
+                find_preceding_index(indexes.as_slice(), relative_start_offset),
+                find_preceding_index(indexes.as_slice(), relative_end_offset),
+            ),
+            None => {
+                let indexes = self.mapped_index.get(&self.index_path)?;
+                (
+                    find_preceding_index(indexes.as_ref(), relative_start_offset),
+                    find_preceding_index(indexes.as_ref(), relative_end_offset),
+                )
+            }
+        };
+        let (Some(start_index), Some(end_index)) = (start_index, end_index) else {
+            return Err(IggyError::InvalidOffset(start_offset));
+        };
+
+        let mut file = file::open(&self.log_path).await?;
+        let file_size = file.metadata().await?.len();
+        let Some((position, _)) = find_batch_header(
+            &mut file,
+            start_index.position as u64,
+            file_size,
+            start_offset,
+        )
+        .await?
+        else {
+            return Err(IggyError::InvalidOffset(start_offset));
+        };
+        let end_index_position = position.max(end_index.position as u64);
+        let Some((end_batch_position, end_batch)) =
+            find_batch_header(&mut file, end_index_position, file_size, end_offset).await?
+        else {
+            return Err(IggyError::InvalidOffset(end_offset));
+        };
+
+        let end_position = end_batch_position + end_batch.size_bytes();
+        trace!(
+            "Found batches with messages: {} - {} at position range: {} - {} in segment with start offset: {}.",
+            start_offset,
+            end_offset,
+            position,
+            end_position,
+            self.start_offset
+        );
+        Ok((
+            BatchesChunk::File {
+                file: file.into_std().await,
+                position,
+                length: end_position - position,
+            },
+            end_offset - start_offset + 1,
+        ))
+    }
+
     fn load_messages_from_unsaved_buffer(
         &self,
         start_offset: u64,
@@ -137,7 +299,7 @@ impl Segment {
             let relative_start_offset = (start_offset - self.start_offset) as u32;
             let relative_end_offset = (end_offset - self.start_offset) as u32;
             let index_range = match self.load_highest_lower_bound_index(
-                indices,
+                indices.as_slice(),
                 relative_start_offset,
                 relative_end_offset,
             ) {
@@ -220,6 +382,8 @@ impl Segment {
         let last_offset = batch.base_offset + batch.last_offset_delta as u64;
         self.current_offset = last_offset;
         self.end_offset = last_offset;
+        self.first_batch_timestamp
+            .get_or_insert(batch.max_timestamp);
 
         self.store_offset_and_timestamp_index_for_batch(last_offset, batch.max_timestamp);
 
@@ -250,6 +414,10 @@ impl Segment {
         batch_last_offset: u64,
         batch_max_timestamp: u64,
     ) {
+        if !self.index_interval.should_index(self.size_bytes) {
+            return;
+        }
+
         let relative_offset = (batch_last_offset - self.start_offset) as u32;
         match (&mut self.indexes, &mut self.time_indexes) {
             (Some(indexes), Some(time_indexes)) => {
@@ -306,11 +474,17 @@ impl Segment {
             self.partition_id
         );
 
+        let started_at = Instant::now();
         let saved_bytes = storage.save_batches(self, unsaved_batches).await?;
         storage.save_index(self).await?;
         self.unsaved_indexes.clear();
         storage.save_time_index(self).await?;
         self.unsaved_timestamps.clear();
+        // The appended entries are visible only to the new mappings of the index files.
+        self.reset_mapped_indexes();
+        self.storage
+            .metrics
+            .observe_segment_persist_duration(started_at.elapsed());
 
         trace!(
             "Saved {} messages on disk in segment with start offset: {} for partition with ID: {}, total bytes written: {}.",
@@ -321,17 +495,38 @@ impl Segment {
         );
 
         if self.is_full().await {
-            self.end_offset = self.current_offset;
-            self.is_closed = true;
-            self.unsaved_batches = None;
-            info!(
-                "Closed segment with start offset: {} for partition with ID: {}.",
-                self.start_offset, self.partition_id
-            );
+            self.close().await;
         } else {
             self.unsaved_batches.as_mut().unwrap().clear();
         }
 
         Ok(unsaved_messages_number as usize)
     }
+
+    /// Closes the segment, the subsequent messages are appended to the new segment.
+    /// The messages must be persisted before, as the unsaved ones are discarded.
+    pub async fn close(&mut self) {
+        self.end_offset = self.current_offset;
+        self.is_closed = true;
+        self.unsaved_batches = None;
+        self.release_preallocated_space().await;
+        info!(
+            "Closed segment with start offset: {} for partition with ID: {}.",
+            self.start_offset, self.partition_id
+        );
+    }
+
+    /// Releases the disk space pre-allocated for the log file, which won't be used by the closed segment.
+    pub(crate) async fn release_preallocated_space(&self) {
+        if !self.config.segment.preallocate {
+            return;
+        }
+
+        if let Err(error) = file::release_preallocated(&self.log_path).await {
+            warn!(
+                "Failed to release pre-allocated space of segment log file: {} for partition with ID: {}. Error: {}",
+                self.log_path, self.partition_id, error
+            );
+        }
+    }
 }
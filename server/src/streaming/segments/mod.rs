@@ -1,6 +1,10 @@
+pub mod batch_header;
 pub mod index;
+pub mod mapped_index;
 pub mod messages;
 pub mod persistence;
+pub mod recovery;
+pub mod reencryption;
 pub mod segment;
 pub mod storage;
 pub mod time_index;
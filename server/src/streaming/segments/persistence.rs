@@ -1,7 +1,15 @@
+use crate::streaming::segments::recovery::SegmentRecovery;
 use crate::streaming::segments::segment::Segment;
 use iggy::error::IggyError;
 
 impl Segment {
+    pub async fn recover(&self) -> Result<SegmentRecovery, IggyError> {
+        let recovery = self.storage.segment.recover(self).await;
+        // The index files might have been rebuilt.
+        self.reset_mapped_indexes();
+        recovery
+    }
+
     pub async fn load(&mut self) -> Result<(), IggyError> {
         let storage = self.storage.clone();
         storage.segment.load(self).await
@@ -0,0 +1,393 @@
+use crate::streaming::persistence::persister::Persister;
+use crate::streaming::segments::batch_header::{read_batch_header, BatchHeader, BATCH_HEADER_SIZE};
+use crate::streaming::segments::index::IndexInterval;
+use crate::streaming::segments::segment::Segment;
+use crate::streaming::segments::storage::{INDEX_SIZE, TIME_INDEX_SIZE};
+use crate::streaming::utils::file;
+use anyhow::anyhow;
+use bytes::BufMut;
+use iggy::error::IggyError;
+use iggy::utils::byte_size::IggyByteSize;
+use iggy::utils::checksum;
+use std::fmt::{Display, Formatter};
+use std::io::{ErrorKind, SeekFrom};
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, BufReader};
+use tracing::{error, warn};
+
+const MESSAGE_HEADER_SIZE: usize = 8 + 1 + 8 + 16 + 4 + 4;
+const BUF_READER_CAPACITY_BYTES: usize = 512 * 1000;
+
+/// Summary of the repairs applied to the segment files during the startup recovery.
+/// - `valid_batches` - Number of the valid batches found in the log, set only if the log had to be scanned
+/// - `truncated_bytes` - Number of bytes truncated from the tail of the log (partial or invalid batches)
+/// - `rebuilt_indexes` - Whether the `.index` file was rebuilt from the log
+/// - `rebuilt_time_indexes` - Whether the `.timeindex` file was rebuilt from the log
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentRecovery {
+    pub valid_batches: u32,
+    pub truncated_bytes: u64,
+    pub rebuilt_indexes: bool,
+    pub rebuilt_time_indexes: bool,
+}
+
+impl SegmentRecovery {
+    pub fn is_repaired(&self) -> bool {
+        self.truncated_bytes > 0 || self.rebuilt_indexes || self.rebuilt_time_indexes
+    }
+}
+
+impl Display for SegmentRecovery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "valid batches: {}, truncated: {}, rebuilt indexes: {}, rebuilt time indexes: {}",
+            self.valid_batches,
+            IggyByteSize::from(self.truncated_bytes),
+            self.rebuilt_indexes,
+            self.rebuilt_time_indexes
+        )
+    }
+}
+
+/// Result of scanning the segment log from the beginning up to the first partial or invalid batch.
+/// - `log_size` - Size of the scanned log file
+/// - `valid_size` - Size of the log containing only the valid batches
+/// - `batches` - Number of the valid batches
+/// - `messages` - Number of the messages in the valid batches
+/// - `invalid_batch` - Reason why the scan stopped before the end of the log, if it did
+/// - `indexes` - Content of the `.index` file rebuilt from the valid batches
+/// - `time_indexes` - Content of the `.timeindex` file rebuilt from the valid batches
+#[derive(Debug, Default)]
+pub struct LogScan {
+    pub log_size: u64,
+    pub valid_size: u64,
+    pub batches: u32,
+    pub messages: u64,
+    pub invalid_batch: Option<InvalidBatch>,
+    pub indexes: Vec<u8>,
+    pub time_indexes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidBatch {
+    Partial,
+    UnexpectedOffset(u64),
+    InvalidMessage(u64),
+    InvalidChecksum(u64),
+}
+
+impl Display for InvalidBatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidBatch::Partial => write!(f, "partial batch"),
+            InvalidBatch::UnexpectedOffset(offset) => {
+                write!(f, "batch with unexpected base offset: {offset}")
+            }
+            InvalidBatch::InvalidMessage(offset) => {
+                write!(f, "invalid message with offset: {offset}")
+            }
+            InvalidBatch::InvalidChecksum(offset) => {
+                write!(f, "invalid checksum of message with offset: {offset}")
+            }
+        }
+    }
+}
+
+/// Verifies that the tail of the segment log is complete and matches the last entries of the indexes.
+/// If it doesn't, the whole log is scanned, truncated at the first partial or invalid batch,
+/// and the indexes are rebuilt based on the remaining batches.
+/// Only the log of the active segment is truncated, the partial or invalid batch found in the closed segment
+/// is reported as an error instead, as it's not caused by the interrupted append (see `iggy-fsck` to quarantine it).
+pub(crate) async fn recover(
+    persister: &dyn Persister,
+    segment: &Segment,
+    validate_checksum: bool,
+) -> Result<SegmentRecovery, IggyError> {
+    let log_size = file::open(&segment.log_path).await?.metadata().await?.len();
+    if is_consistent(segment, log_size, validate_checksum).await? {
+        return Ok(SegmentRecovery::default());
+    }
+
+    warn!(
+        "Segment with start offset: {} for partition with ID: {} for topic with ID: {} and stream with ID: {} is inconsistent, scanning the log of size: {}...",
+        segment.start_offset,
+        segment.partition_id,
+        segment.topic_id,
+        segment.stream_id,
+        IggyByteSize::from(log_size)
+    );
+    let scan = scan_log(
+        &segment.log_path,
+        segment.start_offset,
+        segment.config.segment.index_interval.as_bytes_u64() as u32,
+        validate_checksum,
+    )
+    .await?;
+    if let Some(invalid_batch) = scan.invalid_batch {
+        if segment.is_closed {
+            error!(
+                "Found {invalid_batch} at position: {} in closed segment with start offset: {} for partition with ID: {} for topic with ID: {} and stream with ID: {}, the segment will not be truncated.",
+                scan.valid_size,
+                segment.start_offset,
+                segment.partition_id,
+                segment.topic_id,
+                segment.stream_id
+            );
+            return Err(IggyError::CannotLoadResource(anyhow!(
+                "Closed segment: {} contains {invalid_batch} at position: {}, run iggy-fsck to quarantine it.",
+                segment.log_path,
+                scan.valid_size
+            )));
+        }
+
+        error!(
+            "Found {invalid_batch} at position: {} in segment with start offset: {} for partition with ID: {}, {} of the log will be truncated.",
+            scan.valid_size,
+            segment.start_offset,
+            segment.partition_id,
+            IggyByteSize::from(log_size - scan.valid_size)
+        );
+    }
+
+    let mut recovery = SegmentRecovery {
+        valid_batches: scan.batches,
+        ..Default::default()
+    };
+
+    if scan.valid_size < log_size {
+        recovery.truncated_bytes = log_size - scan.valid_size;
+        file::truncate(&segment.log_path, scan.valid_size).await?;
+    }
+
+    if read_file(&segment.index_path).await?.as_deref() != Some(&scan.indexes[..]) {
+        persister
+            .overwrite(&segment.index_path, &scan.indexes)
+            .await?;
+        recovery.rebuilt_indexes = true;
+    }
+
+    if read_file(&segment.time_index_path).await?.as_deref() != Some(&scan.time_indexes[..]) {
+        persister
+            .overwrite(&segment.time_index_path, &scan.time_indexes)
+            .await?;
+        recovery.rebuilt_time_indexes = true;
+    }
+
+    Ok(recovery)
+}
+
+async fn is_consistent(
+    segment: &Segment,
+    log_size: u64,
+    validate_checksum: bool,
+) -> Result<bool, IggyError> {
+    let (Some(index_size), Some(time_index_size)) = (
+        file_size(&segment.index_path).await?,
+        file_size(&segment.time_index_path).await?,
+    ) else {
+        return Ok(false);
+    };
+
+    if index_size % INDEX_SIZE as u64 != 0
+        || time_index_size % TIME_INDEX_SIZE as u64 != 0
+        || index_size / INDEX_SIZE as u64 != time_index_size / TIME_INDEX_SIZE as u64
+    {
+        return Ok(false);
+    }
+
+    if index_size == 0 {
+        return Ok(log_size == 0);
+    }
+
+    let mut index_file = file::open(&segment.index_path).await?;
+    index_file
+        .seek(SeekFrom::Start(index_size - INDEX_SIZE as u64))
+        .await?;
+    let relative_offset = index_file.read_u32_le().await?;
+    let position = index_file.read_u32_le().await? as u64;
+
+    let mut time_index_file = file::open(&segment.time_index_path).await?;
+    time_index_file
+        .seek(SeekFrom::Start(time_index_size - TIME_INDEX_SIZE as u64))
+        .await?;
+    if time_index_file.read_u32_le().await? != relative_offset {
+        return Ok(false);
+    }
+
+    if position + BATCH_HEADER_SIZE > log_size {
+        return Ok(false);
+    }
+
+    let mut log_file = file::open(&segment.log_path).await?;
+    log_file.seek(SeekFrom::Start(position)).await?;
+    let header = read_batch_header(&mut log_file).await?;
+    if header.base_offset < segment.start_offset
+        || header.last_offset() != segment.start_offset + relative_offset as u64
+    {
+        return Ok(false);
+    }
+
+    // As the indexes are sparse, the last indexed batch can be followed by the batches without the index entries,
+    // as long as none of them should have been indexed.
+    let mut index_interval =
+        IndexInterval::new(segment.config.segment.index_interval.as_bytes_u64() as u32);
+    index_interval.set_last_indexed_position(position as u32);
+    let (mut position, mut header) = (position, header);
+    let mut next_position = position + header.size_bytes();
+    while next_position < log_size {
+        if next_position + BATCH_HEADER_SIZE > log_size
+            || index_interval.should_index(next_position as u32)
+        {
+            return Ok(false);
+        }
+
+        log_file.seek(SeekFrom::Start(next_position)).await?;
+        let next_header = read_batch_header(&mut log_file).await?;
+        if next_header.base_offset != header.last_offset() + 1 {
+            return Ok(false);
+        }
+
+        (position, header) = (next_position, next_header);
+        next_position += header.size_bytes();
+    }
+
+    if next_position != log_size {
+        return Ok(false);
+    }
+
+    log_file
+        .seek(SeekFrom::Start(position + BATCH_HEADER_SIZE))
+        .await?;
+    let mut payload = vec![0; header.length as usize];
+    log_file.read_exact(&mut payload).await?;
+    Ok(validate_batch(&header, &payload, validate_checksum).is_ok())
+}
+
+/// Scans the segment log, validating the headers of the batches, the framing of their messages
+/// and optionally the checksums, and rebuilds the sparse indexes (with the given interval) for the valid part of the log.
+pub async fn scan_log(
+    log_path: &str,
+    start_offset: u64,
+    index_interval: u32,
+    validate_checksum: bool,
+) -> Result<LogScan, IggyError> {
+    let file = file::open(log_path).await?;
+    let log_size = file.metadata().await?.len();
+    let mut scan = LogScan {
+        log_size,
+        ..Default::default()
+    };
+    let mut reader = BufReader::with_capacity(BUF_READER_CAPACITY_BYTES, file);
+    let mut next_offset = None;
+    let mut index_interval = IndexInterval::new(index_interval);
+    while scan.valid_size < log_size {
+        if scan.valid_size + BATCH_HEADER_SIZE > log_size {
+            scan.invalid_batch = Some(InvalidBatch::Partial);
+            break;
+        }
+
+        let header = read_batch_header(&mut reader).await?;
+        if header.length == 0 || scan.valid_size + header.size_bytes() > log_size {
+            scan.invalid_batch = Some(InvalidBatch::Partial);
+            break;
+        }
+
+        let is_expected_offset = match next_offset {
+            Some(next_offset) => header.base_offset == next_offset,
+            None => header.base_offset >= start_offset,
+        };
+        if !is_expected_offset {
+            scan.invalid_batch = Some(InvalidBatch::UnexpectedOffset(header.base_offset));
+            break;
+        }
+
+        let mut payload = vec![0; header.length as usize];
+        reader.read_exact(&mut payload).await?;
+        if let Err(invalid_batch) = validate_batch(&header, &payload, validate_checksum) {
+            scan.invalid_batch = Some(invalid_batch);
+            break;
+        }
+
+        if index_interval.should_index(scan.valid_size as u32) {
+            let relative_offset = (header.last_offset() - start_offset) as u32;
+            scan.indexes.put_u32_le(relative_offset);
+            scan.indexes.put_u32_le(scan.valid_size as u32);
+            scan.time_indexes.put_u32_le(relative_offset);
+            scan.time_indexes.put_u64_le(header.max_timestamp);
+        }
+        scan.valid_size += header.size_bytes();
+        scan.batches += 1;
+        scan.messages += header.last_offset_delta as u64 + 1;
+        next_offset = Some(header.last_offset() + 1);
+    }
+
+    Ok(scan)
+}
+
+/// Validates the framing of the messages in the batch payload, their offsets and optionally the checksums.
+fn validate_batch(
+    header: &BatchHeader,
+    payload: &[u8],
+    validate_checksum: bool,
+) -> Result<(), InvalidBatch> {
+    let mut position = 0;
+    let mut expected_offset = header.base_offset;
+    while position < payload.len() {
+        if position + 4 > payload.len() {
+            return Err(InvalidBatch::InvalidMessage(expected_offset));
+        }
+
+        let length = read_u32(payload, position) as usize;
+        position += 4;
+        if length < MESSAGE_HEADER_SIZE || position + length > payload.len() {
+            return Err(InvalidBatch::InvalidMessage(expected_offset));
+        }
+
+        let message = &payload[position..position + length];
+        let offset = u64::from_le_bytes(message[..8].try_into().unwrap());
+        let checksum = read_u32(message, 33);
+        let headers_length = read_u32(message, 37) as usize;
+        if offset != expected_offset || MESSAGE_HEADER_SIZE + headers_length > length {
+            return Err(InvalidBatch::InvalidMessage(expected_offset));
+        }
+
+        if validate_checksum
+            && checksum::calculate(&message[MESSAGE_HEADER_SIZE + headers_length..]) != checksum
+        {
+            return Err(InvalidBatch::InvalidChecksum(offset));
+        }
+
+        expected_offset += 1;
+        position += length;
+    }
+
+    if expected_offset != header.last_offset() + 1 {
+        return Err(InvalidBatch::InvalidMessage(expected_offset));
+    }
+
+    Ok(())
+}
+
+fn read_u32(bytes: &[u8], position: usize) -> u32 {
+    u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap())
+}
+
+async fn file_size(path: &str) -> Result<Option<u64>, IggyError> {
+    match tokio::fs::metadata(path).await {
+        Ok(metadata) => Ok(Some(metadata.len())),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+async fn read_file(path: &str) -> Result<Option<Vec<u8>>, IggyError> {
+    let mut file = match File::open(path).await {
+        Ok(file) => file,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error.into()),
+    };
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).await?;
+    Ok(Some(bytes))
+}
@@ -0,0 +1,298 @@
+use crate::streaming::batching::iterator::IntoMessagesIterator;
+use crate::streaming::batching::message_batch::RetainedMessageBatch;
+use crate::streaming::segments::index::{Index, IndexInterval};
+use crate::streaming::segments::segment::Segment;
+use crate::streaming::segments::time_index::TimeIndex;
+use crate::streaming::sizeable::Sizeable;
+use crate::streaming::utils::file;
+use crate::streaming::utils::key_ring::EncryptionKeyRing;
+use bytes::{BufMut, BytesMut};
+use iggy::error::IggyError;
+use iggy::utils::checksum;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader, BufWriter};
+use tracing::{info, warn};
+
+const REENCRYPTION_EXTENSION: &str = "reencrypted";
+const MANIFEST_EXTENSION: &str = "reencryption";
+const BUF_READER_CAPACITY_BYTES: usize = 512 * 1000;
+const BUF_WRITER_CAPACITY_BYTES: usize = 512 * 1000;
+
+/// The files of the closed segment to be re-encrypted, captured under the partition lock,
+/// so that the segment can be rewritten without holding it.
+#[derive(Debug, Clone)]
+pub struct SegmentReencryption {
+    pub start_offset: u64,
+    log_path: String,
+    index_path: String,
+    time_index_path: String,
+    index_interval: u32,
+}
+
+/// The re-encrypted copy of the segment, stored next to its files until it's swapped in by `Segment::complete_reencryption`.
+#[derive(Debug)]
+pub struct ReencryptedSegment {
+    pub start_offset: u64,
+    pub messages_count: u64,
+    log_path: String,
+    index_path: String,
+    time_index_path: String,
+    size_bytes: u32,
+    indexes: Vec<Index>,
+    time_indexes: Vec<TimeIndex>,
+}
+
+impl Segment {
+    /// Returns the files to be re-encrypted, only the closed segments are re-encrypted, as they no longer change.
+    pub fn prepare_reencryption(&self) -> Option<SegmentReencryption> {
+        if !self.is_closed {
+            return None;
+        }
+
+        Some(SegmentReencryption {
+            start_offset: self.start_offset,
+            log_path: self.log_path.clone(),
+            index_path: self.index_path.clone(),
+            time_index_path: self.time_index_path.clone(),
+            index_interval: self.config.segment.index_interval.as_bytes_u64() as u32,
+        })
+    }
+
+    /// Replaces the log and the indexes of the segment with the re-encrypted ones, returns the number of re-encrypted messages.
+    /// The renames are recorded in the manifest first, so that the interrupted swap is completed on the next load,
+    /// as the sizes of the batches change and the old indexes don't match the new log.
+    pub async fn complete_reencryption(
+        &mut self,
+        reencrypted_segment: ReencryptedSegment,
+    ) -> Result<u64, IggyError> {
+        if reencrypted_segment.log_path != self.log_path {
+            reencrypted_segment.discard().await;
+            return Ok(0);
+        }
+
+        let manifest_path = get_manifest_path(&self.log_path);
+        let manifest = [&self.log_path, &self.index_path, &self.time_index_path]
+            .map(|path| path.as_str())
+            .join("\n");
+        file::overwrite_synced(&manifest_path, manifest.as_bytes()).await?;
+        file::sync_parent_directory(&manifest_path).await?;
+        apply_manifest(&manifest_path).await?;
+        // The mappings of the replaced index files still point to the old ones, which are no longer valid for the new log.
+        self.reset_mapped_indexes();
+
+        let size_bytes = reencrypted_segment.size_bytes;
+        let size_bytes_delta = size_bytes as i64 - self.size_bytes as i64;
+        for parent_size in [
+            &self.size_of_parent_stream,
+            &self.size_of_parent_topic,
+            &self.size_of_parent_partition,
+        ] {
+            if size_bytes_delta >= 0 {
+                parent_size.fetch_add(size_bytes_delta as u64, Ordering::SeqCst);
+            } else {
+                parent_size.fetch_sub(size_bytes_delta.unsigned_abs(), Ordering::SeqCst);
+            }
+        }
+        self.size_bytes = size_bytes;
+        if self.indexes.is_some() {
+            self.indexes = Some(reencrypted_segment.indexes);
+        }
+        if self.time_indexes.is_some() {
+            self.time_indexes = Some(reencrypted_segment.time_indexes);
+        }
+
+        info!(
+            "Re-encrypted {} messages in segment with start offset: {} for partition with ID: {} for stream with ID: {} and topic with ID: {}.",
+            reencrypted_segment.messages_count, self.start_offset, self.partition_id, self.stream_id, self.topic_id
+        );
+        Ok(reencrypted_segment.messages_count)
+    }
+
+    /// Completes the swap of the re-encrypted files recorded in the manifest, if the server stopped in the middle of it,
+    /// otherwise removes the re-encrypted files left by the interrupted rewrite.
+    pub async fn recover_reencryption(&self) -> Result<(), IggyError> {
+        let manifest_path = get_manifest_path(&self.log_path);
+        if Path::new(&manifest_path).exists() {
+            warn!(
+                "Completing the interrupted re-encryption of segment with start offset: {} for partition with ID: {} for stream with ID: {} and topic with ID: {}.",
+                self.start_offset, self.partition_id, self.stream_id, self.topic_id
+            );
+            return apply_manifest(&manifest_path).await;
+        }
+
+        for path in [&self.log_path, &self.index_path, &self.time_index_path] {
+            let reencrypted_path = get_reencrypted_path(path);
+            if Path::new(&reencrypted_path).exists() {
+                file::remove(&reencrypted_path).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SegmentReencryption {
+    pub fn log_path(&self) -> &str {
+        &self.log_path
+    }
+
+    /// Writes the copy of the segment with the messages encrypted with any of the previous keys encrypted with the active key,
+    /// returns `None` if all the messages are already encrypted with the active key.
+    /// The indexes are rewritten as well, since the sizes of the batches change, and so might the indexed ones.
+    pub async fn rewrite(
+        &self,
+        key_ring: &EncryptionKeyRing,
+    ) -> Result<Option<ReencryptedSegment>, IggyError> {
+        let file = file::open(&self.log_path).await?;
+        let file_size = file.metadata().await?.len();
+        if file_size == 0 {
+            return Ok(None);
+        }
+
+        let reencrypted_log_path = get_reencrypted_path(&self.log_path);
+        let mut reader = BufReader::with_capacity(BUF_READER_CAPACITY_BYTES, file);
+        let mut log_writer = BufWriter::with_capacity(
+            BUF_WRITER_CAPACITY_BYTES,
+            file::overwrite(&reencrypted_log_path).await?,
+        );
+        let mut indexes = Vec::new();
+        let mut index_bytes = BytesMut::new();
+        let mut time_indexes = Vec::new();
+        let mut time_index_bytes = BytesMut::new();
+        let mut index_interval = IndexInterval::new(self.index_interval);
+        let mut read_bytes = 0;
+        let mut size_bytes = 0;
+        let mut messages_count = 0;
+        while read_bytes < file_size {
+            let base_offset = reader
+                .read_u64_le()
+                .await
+                .map_err(|_| IggyError::CannotReadBatchBaseOffset)?;
+            let length = reader
+                .read_u32_le()
+                .await
+                .map_err(|_| IggyError::CannotReadBatchLength)?;
+            let last_offset_delta = reader
+                .read_u32_le()
+                .await
+                .map_err(|_| IggyError::CannotReadLastOffsetDelta)?;
+            let max_timestamp = reader
+                .read_u64_le()
+                .await
+                .map_err(|_| IggyError::CannotReadMaxTimestamp)?;
+            let mut payload = BytesMut::with_capacity(length as usize);
+            payload.put_bytes(0, length as usize);
+            reader
+                .read_exact(&mut payload)
+                .await
+                .map_err(|_| IggyError::CannotReadBatchPayload)?;
+            read_bytes += 8 + 4 + 4 + 8 + length as u64;
+
+            let batch = RetainedMessageBatch::new(
+                base_offset,
+                last_offset_delta,
+                max_timestamp,
+                length,
+                payload.freeze(),
+            );
+            let mut messages = BytesMut::with_capacity(length as usize);
+            for mut message in batch.into_messages_iter() {
+                if let Some(payload) = key_ring.reencrypt(&message.payload)? {
+                    message.payload = payload.into();
+                    message.checksum = checksum::calculate(&message.payload);
+                    messages_count += 1;
+                }
+                message.extend(&mut messages);
+            }
+
+            let batch = RetainedMessageBatch::new(
+                base_offset,
+                last_offset_delta,
+                max_timestamp,
+                messages.len() as u32,
+                messages.freeze(),
+            );
+            let mut batch_bytes = BytesMut::with_capacity(batch.get_size_bytes() as usize);
+            batch.extend(&mut batch_bytes);
+            log_writer.write_all(&batch_bytes).await?;
+
+            if index_interval.should_index(size_bytes) {
+                let relative_offset = (batch.get_last_offset() - self.start_offset) as u32;
+                index_bytes.put_u32_le(relative_offset);
+                index_bytes.put_u32_le(size_bytes);
+                indexes.push(Index {
+                    relative_offset,
+                    position: size_bytes,
+                });
+                time_index_bytes.put_u32_le(relative_offset);
+                time_index_bytes.put_u64_le(max_timestamp);
+                time_indexes.push(TimeIndex {
+                    relative_offset,
+                    timestamp: max_timestamp,
+                });
+            }
+            size_bytes += batch.get_size_bytes();
+        }
+        log_writer.flush().await?;
+        log_writer.get_ref().sync_all().await?;
+        drop(log_writer);
+
+        if messages_count == 0 {
+            file::remove(&reencrypted_log_path).await?;
+            return Ok(None);
+        }
+
+        file::overwrite_synced(&get_reencrypted_path(&self.index_path), &index_bytes).await?;
+        file::overwrite_synced(
+            &get_reencrypted_path(&self.time_index_path),
+            &time_index_bytes,
+        )
+        .await?;
+        Ok(Some(ReencryptedSegment {
+            start_offset: self.start_offset,
+            messages_count,
+            log_path: self.log_path.clone(),
+            index_path: self.index_path.clone(),
+            time_index_path: self.time_index_path.clone(),
+            size_bytes,
+            indexes,
+            time_indexes,
+        }))
+    }
+}
+
+impl ReencryptedSegment {
+    /// Removes the re-encrypted files, e.g. when the segment has been deleted in the meantime.
+    pub async fn discard(&self) {
+        for path in [&self.log_path, &self.index_path, &self.time_index_path] {
+            let reencrypted_path = get_reencrypted_path(path);
+            if let Err(error) = file::remove(&reencrypted_path).await {
+                warn!("Failed to remove re-encrypted file: {reencrypted_path}. Error: {error}");
+            }
+        }
+    }
+}
+
+/// Renames the re-encrypted files listed in the manifest over the original ones and removes the manifest.
+/// The files already renamed before the crash are skipped, so the manifest can be applied more than once.
+async fn apply_manifest(manifest_path: &str) -> Result<(), IggyError> {
+    let manifest = tokio::fs::read_to_string(manifest_path).await?;
+    for path in manifest.lines().filter(|path| !path.is_empty()) {
+        let reencrypted_path = get_reencrypted_path(path);
+        if Path::new(&reencrypted_path).exists() {
+            file::rename(&reencrypted_path, path).await?;
+        }
+    }
+    file::sync_parent_directory(manifest_path).await?;
+    file::remove(manifest_path).await?;
+    file::sync_parent_directory(manifest_path).await?;
+    Ok(())
+}
+
+fn get_reencrypted_path(path: &str) -> String {
+    format!("{path}.{REENCRYPTION_EXTENSION}")
+}
+
+fn get_manifest_path(log_path: &str) -> String {
+    format!("{log_path}.{MANIFEST_EXTENSION}")
+}
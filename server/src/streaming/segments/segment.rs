@@ -8,7 +8,8 @@ use crate::compat::message_conversion::streams::retained_batch::RetainedBatchWri
 use crate::compat::message_conversion::streams::retained_message::RetainedMessageStream;
 use crate::configs::system::SystemConfig;
 use crate::streaming::batching::message_batch::RetainedMessageBatch;
-use crate::streaming::segments::index::Index;
+use crate::streaming::segments::index::{Index, IndexInterval};
+use crate::streaming::segments::mapped_index::CachedMappedIndex;
 use crate::streaming::segments::time_index::TimeIndex;
 use crate::streaming::sizeable::Sizeable;
 use crate::streaming::storage::SystemStorage;
@@ -47,13 +48,17 @@ pub struct Segment {
     pub messages_count_of_parent_topic: Arc<AtomicU64>,
     pub messages_count_of_parent_partition: Arc<AtomicU64>,
     pub is_closed: bool,
+    pub(crate) first_batch_timestamp: Option<u64>,
     pub(crate) message_expiry: IggyExpiry,
     pub(crate) unsaved_batches: Option<Vec<Arc<RetainedMessageBatch>>>,
     pub(crate) config: Arc<SystemConfig>,
     pub(crate) indexes: Option<Vec<Index>>,
     pub(crate) time_indexes: Option<Vec<TimeIndex>>,
+    pub(crate) index_interval: IndexInterval,
     pub(crate) unsaved_indexes: Vec<u8>,
     pub(crate) unsaved_timestamps: Vec<u8>,
+    pub(crate) mapped_index: CachedMappedIndex<Index>,
+    pub(crate) mapped_time_index: CachedMappedIndex<TimeIndex>,
     pub(crate) storage: Arc<SystemStorage>,
 }
 
@@ -100,10 +105,14 @@ impl Segment {
                 true => Some(Vec::new()),
                 false => None,
             },
+            index_interval: IndexInterval::new(config.segment.index_interval.as_bytes_u64() as u32),
             unsaved_indexes: Vec::new(),
             unsaved_timestamps: Vec::new(),
+            mapped_index: CachedMappedIndex::new(),
+            mapped_time_index: CachedMappedIndex::new(),
             unsaved_batches: None,
             is_closed: false,
+            first_batch_timestamp: None,
             size_of_parent_stream,
             size_of_parent_partition,
             size_of_parent_topic,
@@ -120,7 +129,26 @@ impl Segment {
             return true;
         }
 
-        self.is_expired(IggyTimestamp::now()).await
+        let now = IggyTimestamp::now();
+        if self.is_aged(now) {
+            return true;
+        }
+
+        self.is_expired(now).await
+    }
+
+    /// The segment is aged when its first batch is older than the configured max age,
+    /// it's then closed just like the full segment, so that its messages can expire.
+    pub fn is_aged(&self, now: IggyTimestamp) -> bool {
+        let max_age = self.config.segment.max_age;
+        if max_age.is_zero() {
+            return false;
+        }
+
+        match self.first_batch_timestamp {
+            Some(timestamp) => timestamp + max_age.as_micros() <= now.as_micros(),
+            None => false,
+        }
     }
 
     pub async fn is_expired(&self, now: IggyTimestamp) -> bool {
@@ -149,6 +177,12 @@ impl Segment {
         }
     }
 
+    /// Resets the cached mappings of the index files, which must be done whenever the files change.
+    pub(crate) fn reset_mapped_indexes(&self) {
+        self.mapped_index.reset();
+        self.mapped_time_index.reset();
+    }
+
     fn get_log_path(path: &str) -> String {
         format!("{}.{}", path, LOG_EXTENSION)
     }
@@ -375,4 +409,57 @@ mod tests {
         );
         assert!(segment.time_indexes.is_none());
     }
+
+    #[test]
+    fn should_be_aged_given_first_batch_older_than_max_age() {
+        let max_age = IggyDuration::from(3600 * 1_000_000);
+        let config = Arc::new(SystemConfig {
+            segment: SegmentConfig {
+                max_age,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let mut segment = create_segment(config);
+        let now = IggyTimestamp::now();
+        assert!(!segment.is_aged(now));
+
+        segment.first_batch_timestamp = Some(now.as_micros() - max_age.as_micros() + 1);
+        assert!(!segment.is_aged(now));
+
+        segment.first_batch_timestamp = Some(now.as_micros() - max_age.as_micros());
+        assert!(segment.is_aged(now));
+    }
+
+    #[test]
+    fn should_not_be_aged_given_max_age_disabled() {
+        let config = Arc::new(SystemConfig {
+            segment: SegmentConfig {
+                max_age: IggyDuration::from(0),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let mut segment = create_segment(config);
+        segment.first_batch_timestamp = Some(0);
+        assert!(!segment.is_aged(IggyTimestamp::now()));
+    }
+
+    fn create_segment(config: Arc<SystemConfig>) -> Segment {
+        Segment::create(
+            1,
+            2,
+            3,
+            0,
+            config,
+            Arc::new(get_test_system_storage()),
+            IggyExpiry::NeverExpire,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+        )
+    }
 }
@@ -2,24 +2,28 @@ use crate::streaming::batching::iterator::IntoMessagesIterator;
 use crate::streaming::batching::message_batch::RetainedMessageBatch;
 use crate::streaming::models::messages::RetainedMessage;
 use crate::streaming::persistence::persister::Persister;
-use crate::streaming::segments::index::{Index, IndexRange};
+use crate::streaming::persistence::reader::{SegmentFile, SegmentReader};
+use crate::streaming::segments::batch_header::{
+    find_last_batch_header, read_batch_header, BATCH_HEADER_SIZE,
+};
+use crate::streaming::segments::index::{Index, IndexEntries, IndexRange};
+use crate::streaming::segments::recovery;
+use crate::streaming::segments::recovery::SegmentRecovery;
 use crate::streaming::segments::segment::Segment;
-use crate::streaming::segments::time_index::TimeIndex;
+use crate::streaming::segments::time_index::{find_time_index, TimeIndex};
 use crate::streaming::sizeable::Sizeable;
 use crate::streaming::storage::SegmentStorage;
 use crate::streaming::utils::file;
-use crate::streaming::utils::head_tail_buf::HeadTailBuffer;
 use anyhow::Context;
 use async_trait::async_trait;
 use bytes::{BufMut, BytesMut};
 use iggy::error::IggyError;
 use iggy::utils::byte_size::IggyByteSize;
 use iggy::utils::checksum;
-use std::io::SeekFrom;
 use std::path::Path;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
-use tokio::io::{AsyncReadExt, AsyncSeekExt, BufReader};
+use tokio::io::{AsyncReadExt, BufReader};
 use tracing::{error, info, trace, warn};
 
 const EMPTY_INDEXES: Vec<Index> = vec![];
@@ -31,11 +35,12 @@ const BUF_READER_CAPACITY_BYTES: usize = 512 * 1000;
 #[derive(Debug)]
 pub struct FileSegmentStorage {
     persister: Arc<dyn Persister>,
+    reader: Arc<dyn SegmentReader>,
 }
 
 impl FileSegmentStorage {
-    pub fn new(persister: Arc<dyn Persister>) -> Self {
-        Self { persister }
+    pub fn new(persister: Arc<dyn Persister>, reader: Arc<dyn SegmentReader>) -> Self {
+        Self { persister, reader }
     }
 }
 
@@ -49,9 +54,14 @@ impl SegmentStorage for FileSegmentStorage {
             "Loading segment from disk for start offset: {} and partition with ID: {} for topic with ID: {} and stream with ID: {} ...",
             segment.start_offset, segment.partition_id, segment.topic_id, segment.stream_id
         );
-        let log_file = file::open(&segment.log_path).await?;
+        segment.recover_reencryption().await?;
+        let mut log_file = file::open(&segment.log_path).await?;
         let file_size = log_file.metadata().await.unwrap().len() as u64;
         segment.size_bytes = file_size as u32;
+        if file_size >= BATCH_HEADER_SIZE {
+            let first_batch = read_batch_header(&mut log_file).await?;
+            segment.first_batch_timestamp = Some(first_batch.max_timestamp);
+        }
 
         if segment.config.segment.cache_indexes {
             segment.indexes = Some(segment.storage.segment.load_all_indexes(segment).await?);
@@ -66,13 +76,7 @@ impl SegmentStorage for FileSegmentStorage {
         }
 
         if segment.config.segment.cache_time_indexes {
-            let time_indexes = self.load_all_time_indexes(segment).await?;
-            if !time_indexes.is_empty() {
-                let last_index = time_indexes.last().unwrap();
-                segment.current_offset = segment.start_offset + last_index.relative_offset as u64;
-                segment.time_indexes = Some(time_indexes);
-            }
-
+            segment.time_indexes = Some(self.load_all_time_indexes(segment).await?);
             info!(
                 "Loaded {} time indexes for segment with start offset: {} and partition with ID: {} for topic with ID: {} and stream with ID: {}.",
                 segment.time_indexes.as_ref().unwrap().len(),
@@ -81,22 +85,37 @@ impl SegmentStorage for FileSegmentStorage {
                 segment.topic_id,
                 segment.stream_id
             );
-        } else {
-            let last_time_index = self.load_last_time_index(segment).await?;
-            if let Some(last_index) = last_time_index {
-                segment.current_offset = segment.start_offset + last_index.relative_offset as u64;
-                info!(
-                "Loaded last time index for segment with start offset: {} and partition with ID: {} for topic with ID: {} and stream with ID: {}.",
+        }
+
+        // The indexes are sparse, so the current offset is read from the header of the last batch,
+        // which is found by scanning the log from the last indexed batch.
+        let last_index = match &segment.indexes {
+            Some(indexes) => indexes.last().copied(),
+            None => self.load_last_index(segment).await?,
+        };
+        if let Some(last_index) = last_index {
+            segment
+                .index_interval
+                .set_last_indexed_position(last_index.position);
+            let mut log_file = file::open(&segment.log_path).await?;
+            if let Some((_, last_batch)) =
+                find_last_batch_header(&mut log_file, last_index.position as u64, file_size).await?
+            {
+                segment.current_offset = last_batch.last_offset();
+            }
+            info!(
+                "Loaded last batch for segment with start offset: {} and partition with ID: {} for topic with ID: {} and stream with ID: {}.",
                 segment.start_offset,
                 segment.partition_id,
                 segment.topic_id,
                 segment.stream_id
             );
-            }
         }
 
         if segment.is_full().await {
             segment.is_closed = true;
+            // The segment might not have been closed before the server stopped.
+            segment.release_preallocated_space().await;
         }
 
         let messages_count = segment.get_messages_count();
@@ -131,16 +150,28 @@ impl SegmentStorage for FileSegmentStorage {
     async fn save(&self, segment: &Segment) -> Result<(), IggyError> {
         info!("Saving segment with start offset: {} for partition with ID: {} for topic with ID: {} and stream with ID: {}",
             segment.start_offset, segment.partition_id, segment.topic_id, segment.stream_id);
-        if !Path::new(&segment.log_path).exists()
-            && self
+        if !Path::new(&segment.log_path).exists() {
+            if self
                 .persister
                 .overwrite(&segment.log_path, &[])
                 .await
                 .is_err()
-        {
-            return Err(IggyError::CannotCreateSegmentLogFile(
-                segment.log_path.clone(),
-            ));
+            {
+                return Err(IggyError::CannotCreateSegmentLogFile(
+                    segment.log_path.clone(),
+                ));
+            }
+
+            if segment.config.segment.preallocate {
+                if let Err(error) =
+                    file::preallocate(&segment.log_path, segment.max_size_bytes as u64).await
+                {
+                    warn!(
+                        "Failed to pre-allocate segment log file: {} for partition with ID: {}. Error: {}",
+                        segment.log_path, segment.partition_id, error
+                    );
+                }
+            }
         }
 
         if !Path::new(&segment.time_index_path).exists()
@@ -180,6 +211,7 @@ impl SegmentStorage for FileSegmentStorage {
             "Deleting segment of size {segment_size} with start offset: {} for partition with ID: {} for stream with ID: {} and topic with ID: {}...",
             segment.start_offset, segment.partition_id, segment.stream_id, segment.topic_id,
         );
+        segment.reset_mapped_indexes();
         self.persister.delete(&segment.log_path).await?;
         self.persister.delete(&segment.index_path).await?;
         self.persister.delete(&segment.time_index_path).await?;
@@ -208,13 +240,22 @@ impl SegmentStorage for FileSegmentStorage {
         Ok(())
     }
 
+    async fn recover(&self, segment: &Segment) -> Result<SegmentRecovery, IggyError> {
+        recovery::recover(
+            self.persister.as_ref(),
+            segment,
+            segment.config.partition.validate_checksum,
+        )
+        .await
+    }
+
     async fn load_message_batches(
         &self,
         segment: &Segment,
         index_range: &IndexRange,
     ) -> Result<Vec<RetainedMessageBatch>, IggyError> {
         let mut batches = Vec::new();
-        load_batches_by_range(segment, index_range, |batch| {
+        load_batches_by_range(self.reader.as_ref(), segment, index_range, |batch| {
             batches.push(batch);
             Ok(())
         })
@@ -230,7 +271,7 @@ impl SegmentStorage for FileSegmentStorage {
     ) -> Result<Vec<RetainedMessageBatch>, IggyError> {
         let mut batches = Vec::new();
         let mut total_size_bytes = 0;
-        load_messages_by_size(segment, size_bytes, |batch| {
+        load_messages_by_size(self.reader.as_ref(), segment, size_bytes, |batch| {
             total_size_bytes += batch.get_size_bytes() as u64;
             batches.push(batch);
             Ok(())
@@ -271,39 +312,49 @@ impl SegmentStorage for FileSegmentStorage {
 
     async fn load_message_ids(&self, segment: &Segment) -> Result<Vec<u128>, IggyError> {
         let mut message_ids = Vec::new();
-        load_batches_by_range(segment, &IndexRange::max_range(), |batch| {
-            message_ids.extend(
-                batch
-                    .into_messages_iter()
-                    .map(|msg: RetainedMessage| msg.id),
-            );
-            Ok(())
-        })
+        load_batches_by_range(
+            self.reader.as_ref(),
+            segment,
+            &IndexRange::max_range(),
+            |batch| {
+                message_ids.extend(
+                    batch
+                        .into_messages_iter()
+                        .map(|msg: RetainedMessage| msg.id),
+                );
+                Ok(())
+            },
+        )
         .await?;
         trace!("Loaded {} message IDs from disk.", message_ids.len());
         Ok(message_ids)
     }
 
     async fn load_checksums(&self, segment: &Segment) -> Result<(), IggyError> {
-        load_batches_by_range(segment, &IndexRange::max_range(), |batch| {
-            for message in batch.into_messages_iter() {
-                let calculated_checksum = checksum::calculate(&message.payload);
-                trace!(
-                    "Loaded message for offset: {}, checksum: {}, expected: {}",
-                    message.offset,
-                    calculated_checksum,
-                    message.checksum
-                );
-                if calculated_checksum != message.checksum {
-                    return Err(IggyError::InvalidMessageChecksum(
-                        calculated_checksum,
-                        message.checksum,
+        load_batches_by_range(
+            self.reader.as_ref(),
+            segment,
+            &IndexRange::max_range(),
+            |batch| {
+                for message in batch.into_messages_iter() {
+                    let calculated_checksum = checksum::calculate(&message.payload);
+                    trace!(
+                        "Loaded message for offset: {}, checksum: {}, expected: {}",
                         message.offset,
-                    ));
+                        calculated_checksum,
+                        message.checksum
+                    );
+                    if calculated_checksum != message.checksum {
+                        return Err(IggyError::InvalidMessageChecksum(
+                            calculated_checksum,
+                            message.checksum,
+                            message.offset,
+                        ));
+                    }
                 }
-            }
-            Ok(())
-        })
+                Ok(())
+            },
+        )
         .await?;
         Ok(())
     }
@@ -375,49 +426,19 @@ impl SegmentStorage for FileSegmentStorage {
             return Ok(None);
         }
 
-        let file = file::open(&segment.index_path).await?;
-        let file_length = file.metadata().await?.len() as u32;
-        if file_length == 0 {
+        let indexes = segment.mapped_index.get(&segment.index_path)?;
+        if indexes.count() == 0 {
             trace!("Index file is empty.");
             return Ok(None);
         }
-        trace!("Index file length: {}.", file_length);
+
         let relative_start_offset = (index_start_offset - segment.start_offset) as u32;
         let relative_end_offset = (index_end_offset - segment.start_offset) as u32;
-
-        trace!(
-            "Seeking to index range: {}...{}",
+        let index_range = segment.load_highest_lower_bound_index(
+            indexes.as_ref(),
             relative_start_offset,
             relative_end_offset,
-        );
-        let mut idx_pred = HeadTailBuffer::new();
-        let mut index_range = IndexRange::default();
-        let mut read_bytes = 0;
-
-        let mut reader = BufReader::with_capacity(BUF_READER_CAPACITY_BYTES, file);
-        loop {
-            let relative_offset = reader.read_u32_le().await?;
-            let position = reader.read_u32_le().await?;
-            read_bytes += INDEX_SIZE;
-            let idx = Index {
-                relative_offset,
-                position,
-            };
-            idx_pred.push(idx);
-
-            if relative_offset >= relative_start_offset {
-                index_range.start = idx_pred.tail().unwrap_or_default();
-            }
-            if relative_offset >= relative_end_offset {
-                index_range.end = idx;
-                break;
-            }
-
-            if read_bytes == file_length {
-                break;
-            }
-        }
-
+        )?;
         trace!(
             "Loaded index range: {}...{}, position range: {}...{}",
             relative_start_offset,
@@ -446,33 +467,9 @@ impl SegmentStorage for FileSegmentStorage {
         segment: &Segment,
         timestamp: u64,
     ) -> Result<Option<TimeIndex>, IggyError> {
-        trace!("Loading time indexes from file...");
-        let file = file::open(&segment.time_index_path).await?;
-        let file_size = file.metadata().await?.len() as usize;
-        if file_size == 0 {
-            trace!("Time index file is empty.");
-            return Ok(Some(TimeIndex::default()));
-        }
-
-        let mut reader = BufReader::with_capacity(BUF_READER_CAPACITY_BYTES, file);
-        let mut read_bytes = 0;
-        let mut idx_pred = HeadTailBuffer::new();
-        loop {
-            let offset = reader.read_u32_le().await?;
-            let time = reader.read_u64_le().await?;
-            let idx = TimeIndex {
-                relative_offset: offset,
-                timestamp: time,
-            };
-            idx_pred.push(idx);
-            if time >= timestamp {
-                return Ok(idx_pred.tail());
-            }
-            read_bytes += TIME_INDEX_SIZE as usize;
-            if read_bytes == file_size {
-                return Ok(None);
-            }
-        }
+        trace!("Loading time index for timestamp: {timestamp} from file...");
+        let time_indexes = segment.mapped_time_index.get(&segment.time_index_path)?;
+        Ok(find_time_index(time_indexes.as_ref(), timestamp))
     }
 
     async fn load_all_time_indexes(&self, segment: &Segment) -> Result<Vec<TimeIndex>, IggyError> {
@@ -495,7 +492,7 @@ impl SegmentStorage for FileSegmentStorage {
                 );
                 error
             })?;
-            let timestamp = reader.read_u64().await.map_err(|error| {
+            let timestamp = reader.read_u64_le().await.map_err(|error| {
                 error!(
                     "Cannot read timestamp from index file for offset: {}. Error: {}",
                     offset, &error
@@ -520,30 +517,11 @@ impl SegmentStorage for FileSegmentStorage {
         Ok(indexes)
     }
 
-    async fn load_last_time_index(
-        &self,
-        segment: &Segment,
-    ) -> Result<Option<TimeIndex>, IggyError> {
-        trace!("Loading last time index from file...");
-        let mut file = file::open(&segment.time_index_path).await?;
-        let file_size = file.metadata().await?.len() as usize;
-        if file_size == 0 {
-            trace!("Time index file is empty.");
-            return Ok(None);
-        }
-
-        let last_index_position = file_size - TIME_INDEX_SIZE as usize;
-        file.seek(SeekFrom::Start(last_index_position as u64))
-            .await?;
-        let index_offset = file.read_u32_le().await?;
-        let timestamp = file.read_u64_le().await?;
-        let index = TimeIndex {
-            relative_offset: index_offset,
-            timestamp,
-        };
-
-        trace!("Loaded last time index from file: {:?}", index);
-        Ok(Some(index))
+    async fn load_last_index(&self, segment: &Segment) -> Result<Option<Index>, IggyError> {
+        trace!("Loading last index from file...");
+        let index = segment.mapped_index.get(&segment.index_path)?.last();
+        trace!("Loaded last index from file: {:?}", index);
+        Ok(index)
     }
 
     async fn save_time_index(&self, segment: &Segment) -> Result<(), IggyError> {
@@ -566,23 +544,23 @@ impl SegmentStorage for FileSegmentStorage {
 }
 
 async fn load_batches_by_range(
+    segment_reader: &dyn SegmentReader,
     segment: &Segment,
     index_range: &IndexRange,
     mut on_batch: impl FnMut(RetainedMessageBatch) -> Result<(), IggyError>,
 ) -> Result<(), IggyError> {
-    let file = file::open(&segment.log_path).await?;
-    let file_size = file.metadata().await?.len();
+    let SegmentFile {
+        size: file_size,
+        mut reader,
+    } = segment_reader
+        .open(&segment.log_path, index_range.start.position as u64)
+        .await?;
     if file_size == 0 {
         return Ok(());
     }
 
-    let mut reader = BufReader::with_capacity(BUF_READER_CAPACITY_BYTES, file);
-    reader
-        .seek(SeekFrom::Start(index_range.start.position as u64))
-        .await?;
-
-    let mut read_bytes: u64 = 0;
-    let mut last_batch_to_read = false;
+    let mut position = index_range.start.position as u64;
+    let mut last_batch_to_read = position >= file_size;
     while !last_batch_to_read {
         let batch_base_offset = reader
             .read_u64_le()
@@ -612,8 +590,8 @@ async fn load_batches_by_range(
             .await
             .map_err(|_| IggyError::CannotReadBatchPayload)?;
 
-        read_bytes += 8 + 4 + 4 + 8 + payload_len as u64;
-        last_batch_to_read = read_bytes == file_size || last_offset == index_last_offset;
+        position += 8 + 4 + 4 + 8 + payload_len as u64;
+        last_batch_to_read = position >= file_size || last_offset == index_last_offset;
 
         let batch = RetainedMessageBatch::new(
             batch_base_offset,
@@ -628,12 +606,15 @@ async fn load_batches_by_range(
 }
 
 async fn load_messages_by_size(
+    segment_reader: &dyn SegmentReader,
     segment: &Segment,
     size_bytes: u64,
     mut on_batch: impl FnMut(RetainedMessageBatch) -> Result<(), IggyError>,
 ) -> Result<(), IggyError> {
-    let file = file::open(&segment.log_path).await?;
-    let file_size = file.metadata().await?.len();
+    let SegmentFile {
+        size: file_size,
+        mut reader,
+    } = segment_reader.open(&segment.log_path, 0).await?;
     if file_size == 0 {
         return Ok(());
     }
@@ -641,7 +622,6 @@ async fn load_messages_by_size(
     let threshold = file_size.saturating_sub(size_bytes);
     let mut accumulated_size: u64 = 0;
 
-    let mut reader = BufReader::with_capacity(BUF_READER_CAPACITY_BYTES, file);
     loop {
         let batch_base_offset = reader
             .read_u64_le()
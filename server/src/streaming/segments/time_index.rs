@@ -1,3 +1,6 @@
+use crate::streaming::segments::index::IndexEntries;
+use crate::streaming::segments::mapped_index::IndexEntry;
+
 #[derive(Debug, Default, Eq, Clone, Copy)]
 pub struct TimeIndex {
     pub relative_offset: u32,
@@ -9,3 +12,26 @@ impl PartialEq<Self> for TimeIndex {
         self.relative_offset == other.relative_offset && self.timestamp == other.timestamp
     }
 }
+
+impl IndexEntry for TimeIndex {
+    const SIZE: usize = 12;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        TimeIndex {
+            relative_offset: u32::from_le_bytes(bytes[..4].try_into().unwrap()),
+            timestamp: u64::from_le_bytes(bytes[4..12].try_into().unwrap()),
+        }
+    }
+}
+
+/// Returns the last indexed batch with the max timestamp not newer than the given one,
+/// the messages with the newer timestamps can only be found after this batch.
+pub fn find_time_index(
+    time_indices: &(impl IndexEntries<TimeIndex> + ?Sized),
+    timestamp: u64,
+) -> Option<TimeIndex> {
+    match time_indices.partition_point(|time_index| time_index.timestamp <= timestamp) {
+        0 => None,
+        index => Some(time_indices.get(index - 1)),
+    }
+}
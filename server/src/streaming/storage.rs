@@ -1,10 +1,14 @@
 use super::batching::message_batch::RetainedMessageBatch;
 use crate::configs::system::SystemConfig;
 use crate::state::system::{PartitionState, StreamState, TopicState};
+use crate::streaming::diagnostics::metrics::StorageMetrics;
+use crate::streaming::partitions::offsets_log::ConsumerOffsetsLog;
 use crate::streaming::partitions::partition::{ConsumerOffset, Partition};
 use crate::streaming::partitions::storage::FilePartitionStorage;
 use crate::streaming::persistence::persister::Persister;
+use crate::streaming::persistence::reader::SegmentReader;
 use crate::streaming::segments::index::{Index, IndexRange};
+use crate::streaming::segments::recovery::SegmentRecovery;
 use crate::streaming::segments::segment::Segment;
 use crate::streaming::segments::storage::FileSegmentStorage;
 use crate::streaming::segments::time_index::TimeIndex;
@@ -46,14 +50,24 @@ pub trait PartitionStorage: Send + Sync {
         -> Result<(), IggyError>;
     async fn save(&self, partition: &Partition) -> Result<(), IggyError>;
     async fn delete(&self, partition: &Partition) -> Result<(), IggyError>;
-    async fn save_consumer_offset(&self, offset: &ConsumerOffset) -> Result<(), IggyError>;
-    async fn load_consumer_offsets(
+    async fn save_consumer_offsets(
+        &self,
+        path: &str,
+        offsets: &[ConsumerOffset],
+    ) -> Result<(), IggyError>;
+    async fn load_consumer_offsets(&self, path: &str) -> Result<ConsumerOffsetsLog, IggyError>;
+    async fn compact_consumer_offsets(
         &self,
-        kind: ConsumerKind,
         path: &str,
-    ) -> Result<Vec<ConsumerOffset>, IggyError>;
+        offsets: &[ConsumerOffset],
+    ) -> Result<(), IggyError>;
     async fn delete_consumer_offsets(&self, path: &str) -> Result<(), IggyError>;
-    async fn delete_consumer_offset(&self, path: &str) -> Result<(), IggyError>;
+    async fn delete_consumer_offset(
+        &self,
+        path: &str,
+        kind: ConsumerKind,
+        consumer_id: u32,
+    ) -> Result<(), IggyError>;
 }
 
 #[async_trait]
@@ -61,6 +75,7 @@ pub trait SegmentStorage: Send + Sync {
     async fn load(&self, segment: &mut Segment) -> Result<(), IggyError>;
     async fn save(&self, segment: &Segment) -> Result<(), IggyError>;
     async fn delete(&self, segment: &Segment) -> Result<(), IggyError>;
+    async fn recover(&self, segment: &Segment) -> Result<SegmentRecovery, IggyError>;
     async fn load_message_batches(
         &self,
         segment: &Segment,
@@ -92,8 +107,7 @@ pub trait SegmentStorage: Send + Sync {
         timestamp: u64,
     ) -> Result<Option<TimeIndex>, IggyError>;
     async fn load_all_time_indexes(&self, segment: &Segment) -> Result<Vec<TimeIndex>, IggyError>;
-    async fn load_last_time_index(&self, segment: &Segment)
-        -> Result<Option<TimeIndex>, IggyError>;
+    async fn load_last_index(&self, segment: &Segment) -> Result<Option<Index>, IggyError>;
     async fn save_time_index(&self, segment: &Segment) -> Result<(), IggyError>;
 }
 
@@ -105,10 +119,16 @@ pub struct SystemStorage {
     pub partition: Arc<dyn PartitionStorage>,
     pub segment: Arc<dyn SegmentStorage>,
     pub persister: Arc<dyn Persister>,
+    pub metrics: StorageMetrics,
 }
 
 impl SystemStorage {
-    pub fn new(config: Arc<SystemConfig>, persister: Arc<dyn Persister>) -> Self {
+    pub fn new(
+        config: Arc<SystemConfig>,
+        persister: Arc<dyn Persister>,
+        segment_reader: Arc<dyn SegmentReader>,
+        metrics: StorageMetrics,
+    ) -> Self {
         Self {
             info: Arc::new(FileSystemInfoStorage::new(
                 config.get_state_info_path(),
@@ -117,8 +137,9 @@ impl SystemStorage {
             stream: Arc::new(FileStreamStorage),
             topic: Arc::new(FileTopicStorage),
             partition: Arc::new(FilePartitionStorage::new(persister.clone())),
-            segment: Arc::new(FileSegmentStorage::new(persister.clone())),
+            segment: Arc::new(FileSegmentStorage::new(persister.clone(), segment_reader)),
             persister,
+            metrics,
         }
     }
 }
@@ -246,23 +267,39 @@ pub(crate) mod tests {
             Ok(())
         }
 
-        async fn save_consumer_offset(&self, _offset: &ConsumerOffset) -> Result<(), IggyError> {
+        async fn save_consumer_offsets(
+            &self,
+            _path: &str,
+            _offsets: &[ConsumerOffset],
+        ) -> Result<(), IggyError> {
             Ok(())
         }
 
         async fn load_consumer_offsets(
             &self,
-            _kind: ConsumerKind,
             _path: &str,
-        ) -> Result<Vec<ConsumerOffset>, IggyError> {
-            Ok(vec![])
+        ) -> Result<ConsumerOffsetsLog, IggyError> {
+            Ok(ConsumerOffsetsLog::default())
+        }
+
+        async fn compact_consumer_offsets(
+            &self,
+            _path: &str,
+            _offsets: &[ConsumerOffset],
+        ) -> Result<(), IggyError> {
+            Ok(())
         }
 
         async fn delete_consumer_offsets(&self, _path: &str) -> Result<(), IggyError> {
             Ok(())
         }
 
-        async fn delete_consumer_offset(&self, _path: &str) -> Result<(), IggyError> {
+        async fn delete_consumer_offset(
+            &self,
+            _path: &str,
+            _kind: ConsumerKind,
+            _consumer_id: u32,
+        ) -> Result<(), IggyError> {
             Ok(())
         }
     }
@@ -281,6 +318,10 @@ pub(crate) mod tests {
             Ok(())
         }
 
+        async fn recover(&self, _segment: &Segment) -> Result<SegmentRecovery, IggyError> {
+            Ok(SegmentRecovery::default())
+        }
+
         async fn load_message_batches(
             &self,
             _segment: &Segment,
@@ -345,10 +386,7 @@ pub(crate) mod tests {
             Ok(vec![])
         }
 
-        async fn load_last_time_index(
-            &self,
-            _segment: &Segment,
-        ) -> Result<Option<TimeIndex>, IggyError> {
+        async fn load_last_index(&self, _segment: &Segment) -> Result<Option<Index>, IggyError> {
             Ok(None)
         }
 
@@ -365,6 +403,7 @@ pub(crate) mod tests {
             partition: Arc::new(TestPartitionStorage {}),
             segment: Arc::new(TestSegmentStorage {}),
             persister: Arc::new(TestPersister {}),
+            metrics: StorageMetrics::default(),
         }
     }
 }
@@ -1,10 +1,11 @@
 use crate::streaming::streams::stream::Stream;
+use iggy::locking::IggySharedMutFn;
 
 impl Stream {
-    pub fn get_partitions_count(&self) -> u32 {
+    pub async fn get_partitions_count(&self) -> u32 {
         let mut partitions_count = 0;
         for topic in self.topics.values() {
-            partitions_count += topic.get_partitions_count();
+            partitions_count += topic.read().await.get_partitions_count();
         }
 
         partitions_count
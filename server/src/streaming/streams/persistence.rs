@@ -1,6 +1,7 @@
 use crate::state::system::StreamState;
 use crate::streaming::streams::stream::Stream;
 use iggy::error::IggyError;
+use iggy::locking::IggySharedMutFn;
 
 impl Stream {
     pub async fn load(&mut self, state: StreamState) -> Result<(), IggyError> {
@@ -14,7 +15,7 @@ impl Stream {
 
     pub async fn delete(&self) -> Result<(), IggyError> {
         for topic in self.get_topics() {
-            topic.delete().await?;
+            topic.read().await.delete().await?;
         }
 
         self.storage.stream.delete(self).await
@@ -23,7 +24,7 @@ impl Stream {
     pub async fn persist_messages(&self) -> Result<usize, IggyError> {
         let mut saved_messages_number = 0;
         for topic in self.get_topics() {
-            saved_messages_number += topic.persist_messages().await?;
+            saved_messages_number += topic.read().await.persist_messages().await?;
         }
 
         Ok(saved_messages_number)
@@ -31,7 +32,7 @@ impl Stream {
 
     pub async fn purge(&self) -> Result<(), IggyError> {
         for topic in self.get_topics() {
-            topic.purge().await?;
+            topic.read().await.purge().await?;
         }
         Ok(())
     }
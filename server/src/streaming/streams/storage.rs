@@ -5,6 +5,7 @@ use crate::streaming::topics::topic::Topic;
 use async_trait::async_trait;
 use futures::future::join_all;
 use iggy::error::IggyError;
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
 use iggy::utils::timestamp::IggyTimestamp;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -132,7 +133,9 @@ impl StreamStorage for FileStreamStorage {
             }
 
             stream.topics_ids.insert(topic.name.clone(), topic.topic_id);
-            stream.topics.insert(topic.topic_id, topic);
+            stream
+                .topics
+                .insert(topic.topic_id, IggySharedMut::new(topic));
         }
 
         info!(
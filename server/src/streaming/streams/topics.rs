@@ -3,7 +3,7 @@ use crate::streaming::topics::topic::Topic;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::error::IggyError;
 use iggy::identifier::{IdKind, Identifier};
-use iggy::locking::IggySharedMutFn;
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::text;
 use iggy::utils::topic_size::MaxTopicSize;
@@ -25,7 +25,7 @@ impl Stream {
         compression_algorithm: CompressionAlgorithm,
         max_topic_size: MaxTopicSize,
         replication_factor: u8,
-    ) -> Result<(), IggyError> {
+    ) -> Result<u32, IggyError> {
         let name = text::to_lowercase_non_whitespace(name);
         if self.topics_ids.contains_key(&name) {
             return Err(IggyError::TopicNameAlreadyExists(name, self.stream_id));
@@ -70,9 +70,9 @@ impl Stream {
         topic.persist().await?;
         info!("Created topic {}", topic);
         self.topics_ids.insert(name, id);
-        self.topics.insert(id, topic);
+        self.topics.insert(id, IggySharedMut::new(topic));
 
-        Ok(())
+        Ok(id)
     }
 
     pub async fn update_topic(
@@ -84,123 +84,89 @@ impl Stream {
         max_topic_size: MaxTopicSize,
         replication_factor: u8,
     ) -> Result<(), IggyError> {
-        let topic_id;
-        {
-            let topic = self.get_topic(id)?;
-            topic_id = topic.topic_id;
-        }
-
+        let topic = self.get_topic(id)?;
+        let mut topic = topic.write().await;
+        let topic_id = topic.topic_id;
         let updated_name = text::to_lowercase_non_whitespace(name);
-
-        {
-            if let Some(topic_id_by_name) = self.topics_ids.get(&updated_name) {
-                if *topic_id_by_name != topic_id {
-                    return Err(IggyError::TopicNameAlreadyExists(
-                        updated_name.to_string(),
-                        self.stream_id,
-                    ));
-                }
+        if let Some(topic_id_by_name) = self.topics_ids.get(&updated_name) {
+            if *topic_id_by_name != topic_id {
+                return Err(IggyError::TopicNameAlreadyExists(
+                    updated_name.to_string(),
+                    self.stream_id,
+                ));
             }
         }
 
-        let old_topic_name = {
-            let topic = self.get_topic(id)?;
-            topic.name.clone()
+        self.topics_ids.remove(&topic.name);
+        self.topics_ids.insert(updated_name.clone(), topic_id);
+        let max_topic_size = match max_topic_size {
+            MaxTopicSize::ServerDefault => topic.config.topic.max_size,
+            _ => max_topic_size,
         };
 
-        {
-            self.topics_ids.remove(&old_topic_name.clone());
-            self.topics_ids.insert(updated_name.clone(), topic_id);
-            let topic = self.get_topic_mut(id)?;
-
-            let max_topic_size = match max_topic_size {
-                MaxTopicSize::ServerDefault => topic.config.topic.max_size,
-                _ => max_topic_size,
-            };
-
-            topic.name = updated_name;
-            topic.message_expiry = message_expiry;
-            topic.compression_algorithm = compression_algorithm;
-            for partition in topic.partitions.values_mut() {
-                let mut partition = partition.write().await;
-                partition.message_expiry = message_expiry;
-                for segment in partition.segments.iter_mut() {
-                    segment.message_expiry = message_expiry;
-                }
+        topic.name = updated_name;
+        topic.message_expiry = message_expiry;
+        topic.compression_algorithm = compression_algorithm;
+        for partition in topic.partitions.values() {
+            let mut partition = partition.write().await;
+            partition.message_expiry = message_expiry;
+            for segment in partition.segments.iter_mut() {
+                segment.message_expiry = message_expiry;
             }
-            topic.max_topic_size = max_topic_size;
-            topic.replication_factor = replication_factor;
-            topic.persist().await?;
-            info!("Updated topic: {topic}");
         }
-
+        topic.max_topic_size = max_topic_size;
+        topic.replication_factor = replication_factor;
+        topic.persist().await?;
+        info!("Updated topic: {topic}");
         Ok(())
     }
 
-    pub fn remove_topic(&mut self, identifier: &Identifier) -> Result<Topic, IggyError> {
+    pub fn remove_topic(
+        &mut self,
+        identifier: &Identifier,
+    ) -> Result<IggySharedMut<Topic>, IggyError> {
         match identifier.kind {
             IdKind::Numeric => self.remove_topic_by_id(identifier.get_u32_value()?),
             IdKind::String => self.remove_topic_by_name(&identifier.get_cow_str_value()?),
         }
     }
 
-    pub fn get_topics(&self) -> Vec<&Topic> {
-        self.topics.values().collect()
+    pub fn get_topics(&self) -> Vec<IggySharedMut<Topic>> {
+        self.topics.values().cloned().collect()
     }
 
-    pub fn get_topic(&self, identifier: &Identifier) -> Result<&Topic, IggyError> {
+    pub fn get_topic(&self, identifier: &Identifier) -> Result<IggySharedMut<Topic>, IggyError> {
         match identifier.kind {
             IdKind::Numeric => self.get_topic_by_id(identifier.get_u32_value()?),
             IdKind::String => self.get_topic_by_name(&identifier.get_cow_str_value()?),
         }
     }
 
-    pub fn get_topic_mut(&mut self, identifier: &Identifier) -> Result<&mut Topic, IggyError> {
-        match identifier.kind {
-            IdKind::Numeric => self.get_topic_by_id_mut(identifier.get_u32_value()?),
-            IdKind::String => self.get_topic_by_name_mut(&identifier.get_cow_str_value()?),
-        }
-    }
-
-    fn get_topic_by_id(&self, id: u32) -> Result<&Topic, IggyError> {
+    fn get_topic_by_id(&self, id: u32) -> Result<IggySharedMut<Topic>, IggyError> {
         self.topics
             .get(&id)
+            .cloned()
             .ok_or(IggyError::TopicIdNotFound(id, self.stream_id))
     }
 
-    fn get_topic_by_name(&self, name: &str) -> Result<&Topic, IggyError> {
+    fn get_topic_by_name(&self, name: &str) -> Result<IggySharedMut<Topic>, IggyError> {
         self.topics_ids
             .get(name)
             .map(|topic_id| self.get_topic_by_id(*topic_id))
             .ok_or_else(|| IggyError::TopicNameNotFound(name.to_string(), self.stream_id))?
     }
 
-    fn get_topic_by_id_mut(&mut self, id: u32) -> Result<&mut Topic, IggyError> {
-        self.topics
-            .get_mut(&id)
-            .ok_or(IggyError::TopicIdNotFound(id, self.stream_id))
-    }
-
-    fn get_topic_by_name_mut(&mut self, name: &str) -> Result<&mut Topic, IggyError> {
-        self.topics_ids
-            .get(name)
-            .and_then(|topic_id| self.topics.get_mut(topic_id))
-            .ok_or_else(|| IggyError::TopicNameNotFound(name.to_string(), self.stream_id))
-    }
-
-    fn remove_topic_by_id(&mut self, id: u32) -> Result<Topic, IggyError> {
+    fn remove_topic_by_id(&mut self, id: u32) -> Result<IggySharedMut<Topic>, IggyError> {
         let topic = self
             .topics
             .remove(&id)
             .ok_or_else(|| IggyError::TopicIdNotFound(id, self.stream_id))?;
 
-        self.topics_ids
-            .remove(&topic.name)
-            .ok_or_else(|| IggyError::TopicNameNotFound(topic.name.clone(), self.stream_id))?;
+        self.topics_ids.retain(|_, topic_id| *topic_id != id);
         Ok(topic)
     }
 
-    fn remove_topic_by_name(&mut self, name: &str) -> Result<Topic, IggyError> {
+    fn remove_topic_by_name(&mut self, name: &str) -> Result<IggySharedMut<Topic>, IggyError> {
         let topic_id = self
             .topics_ids
             .remove(name)
@@ -211,8 +177,14 @@ impl Stream {
             .ok_or_else(|| IggyError::TopicIdNotFound(topic_id, self.stream_id))
     }
 
-    pub async fn delete_topic(&mut self, id: &Identifier) -> Result<Topic, IggyError> {
-        let topic = self.remove_topic(id)?;
+    /// Removes the topic from the stream and deletes its data. The topic is locked exclusively,
+    /// so any operation which has already acquired it completes before the data is removed.
+    pub async fn delete_topic(
+        &mut self,
+        id: &Identifier,
+    ) -> Result<IggySharedMut<Topic>, IggyError> {
+        let topic_handle = self.remove_topic(id)?;
+        let topic = topic_handle.write().await;
         let topic_id = topic.topic_id;
         let current_topic_id = self.current_topic_id.load(Ordering::SeqCst);
         if current_topic_id > topic_id {
@@ -223,7 +195,8 @@ impl Stream {
             debug!("Delete topic failed: {}", err);
             IggyError::CannotDeleteTopic(topic.topic_id, self.stream_id)
         })?;
-        Ok(topic)
+        drop(topic);
+        Ok(topic_handle)
     }
 }
 
@@ -263,6 +236,7 @@ mod tests {
         let topic = stream.get_topic(&Identifier::numeric(topic_id).unwrap());
         assert!(topic.is_ok());
         let topic = topic.unwrap();
+        let topic = topic.read().await;
         assert_eq!(topic.topic_id, topic_id);
         assert_eq!(topic.name, topic_name);
         assert_eq!(topic.compression_algorithm, compression_algorithm);
@@ -270,6 +244,7 @@ mod tests {
         let topic = stream.get_topic(&Identifier::named(topic_name).unwrap());
         assert!(topic.is_ok());
         let topic = topic.unwrap();
+        let topic = topic.read().await;
         assert_eq!(topic.topic_id, topic_id);
         assert_eq!(topic.name, topic_name);
         assert_eq!(topic.compression_algorithm, compression_algorithm);
@@ -0,0 +1,69 @@
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::System;
+use iggy::error::IggyError;
+use iggy::locking::IggySharedMutFn;
+use iggy::models::audit_log::AuditLogEntry;
+use iggy::models::user_info::UserId;
+use iggy::utils::timestamp::IggyTimestamp;
+use std::net::SocketAddr;
+use tracing::error;
+
+impl System {
+    pub async fn get_audit_log(
+        &self,
+        session: &Session,
+        count: u32,
+        user_id: Option<UserId>,
+    ) -> Result<Vec<AuditLogEntry>, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .get_audit_log(session.get_user_id())?;
+        let Some(audit_log) = self.audit_log.as_ref() else {
+            return Err(IggyError::FeatureUnavailable);
+        };
+
+        audit_log.load_entries(count, user_id).await
+    }
+
+    /// Returns true if the action should be recorded in the audit log.
+    /// The data commands (sending and polling messages, consumer offsets) are recorded only if configured explicitly.
+    pub fn is_audited(&self, is_data_command: bool) -> bool {
+        self.audit_log.is_some() && (!is_data_command || self.config.audit.include_data_commands)
+    }
+
+    pub async fn get_client_transport(&self, client_id: u32) -> Option<String> {
+        let client_manager = self.client_manager.read().await;
+        let client = client_manager.get_client_by_id(client_id).ok()?;
+        let client = client.read().await;
+        Some(client.transport.to_string())
+    }
+
+    pub async fn record_audit_entry(
+        &self,
+        user_id: UserId,
+        username: Option<&str>,
+        address: &SocketAddr,
+        transport: &str,
+        action: &str,
+        result: &str,
+    ) {
+        let Some(audit_log) = self.audit_log.as_ref() else {
+            return;
+        };
+
+        let entry = AuditLogEntry {
+            timestamp: IggyTimestamp::now(),
+            user_id: if user_id > 0 { Some(user_id) } else { None },
+            username: username.map(|username| username.to_string()),
+            address: address.to_string(),
+            transport: transport.to_string(),
+            action: action.to_string(),
+            result: result.to_string(),
+        };
+        if let Err(error) = audit_log.record(entry).await {
+            error!("Failed to record the audit log entry, error: {error}");
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use crate::archiver::Archiver;
+use crate::configs::system::SystemConfig;
+use crate::streaming::segments::segment::Segment;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::System;
+use crate::streaming::utils::file;
+use iggy::error::IggyError;
+use iggy::locking::IggySharedMutFn;
+use iggy::models::backup_info::BackupInfo;
+use iggy::utils::byte_size::IggyByteSize;
+use iggy::utils::timestamp::IggyTimestamp;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::io::AsyncReadExt;
+use tracing::{error, info, warn};
+
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+const LATEST_BACKUP: &str = "latest";
+
+impl System {
+    /// Prepares the consistent backup of the state and the streams in the backup directory, which is completed
+    /// by `PreparedBackup::complete` once the exclusive access to the system (write lock) is released.
+    /// The exclusive access prevents appending the new state entries and messages while the state snapshot is exported
+    /// and the sizes of the active segments are recorded, so they're copied later only up to these sizes.
+    /// The closed segments are immutable (rewritten only by replacing the files), so they are hard-linked if possible.
+    pub async fn prepare_backup(
+        &mut self,
+        session: &Session,
+        name: Option<&str>,
+        archive: bool,
+    ) -> Result<PreparedBackup, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .create_backup(session.get_user_id())?;
+        if archive && self.archiver.is_none() {
+            return Err(IggyError::FeatureUnavailable);
+        }
+
+        let created_at = IggyTimestamp::now();
+        let name = name.map_or_else(
+            || created_at.as_micros().to_string(),
+            |name| name.to_string(),
+        );
+        let backup_path = Path::new(&self.config.get_backup_path()).join(&name);
+        if backup_path.exists() {
+            return Err(IggyError::BackupAlreadyExists(name));
+        }
+
+        info!("Creating backup: {name}...");
+        self.persist_messages().await?;
+        self.flush_consumer_offsets().await?;
+        let mut backup = PreparedBackup {
+            backup: Backup {
+                system_path: PathBuf::from(self.config.get_system_path()),
+                path: backup_path,
+                files: Vec::new(),
+                size: 0,
+            },
+            info: BackupInfo {
+                name,
+                created_at,
+                state_index: 0,
+                partitions_count: 0,
+                segments_count: 0,
+                messages_count: 0,
+                size: IggyByteSize::default(),
+            },
+            pending_copies: Vec::new(),
+            archiver: archive.then(|| self.archiver.clone()).flatten(),
+        };
+        if let Err(error) = self.write_backup(&mut backup).await {
+            return Err(backup.fail(error).await);
+        }
+        Ok(backup)
+    }
+
+    /// Restores the state and the streams from the backup with the given name (or the latest one)
+    /// into the system directory, which must not contain any state or streams.
+    /// It must be invoked before the system is initialized.
+    pub async fn restore_backup(
+        config: &SystemConfig,
+        name: &str,
+    ) -> Result<BackupInfo, IggyError> {
+        let backups_path = config.get_backup_path();
+        let backup_info = if name == LATEST_BACKUP {
+            find_latest_backup(&backups_path)
+                .await?
+                .ok_or_else(|| IggyError::BackupNotFound(name.to_string()))?
+        } else {
+            read_manifest(&Path::new(&backups_path).join(name))
+                .await
+                .ok_or_else(|| IggyError::BackupNotFound(name.to_string()))?
+        };
+
+        let state_path = config.get_state_path();
+        let streams_path = config.get_streams_path();
+        for path in [&state_path, &streams_path] {
+            if !is_empty_dir(path).await? {
+                return Err(IggyError::CannotRestoreBackup(format!(
+                    "directory: {path} is not empty"
+                )));
+            }
+        }
+
+        info!("Restoring backup: {}...", backup_info.name);
+        let backup_path = Path::new(&backups_path).join(&backup_info.name);
+        let system_path = config.get_system_path();
+        for path in [&state_path, &streams_path] {
+            let relative_path = Path::new(path)
+                .strip_prefix(&system_path)
+                .map_err(|error| IggyError::CannotRestoreBackup(error.to_string()))?;
+            let source = backup_path.join(relative_path);
+            if source.exists() {
+                file::copy_dir(&source, Path::new(path)).await?;
+            }
+        }
+
+        info!(
+            "Restored backup: {} created at: {} with state index: {}, {} partitions, {} segments, {} messages.",
+            backup_info.name,
+            backup_info.created_at.to_local_string("%Y-%m-%d %H:%M:%S"),
+            backup_info.state_index,
+            backup_info.partitions_count,
+            backup_info.segments_count,
+            backup_info.messages_count
+        );
+        Ok(backup_info)
+    }
+
+    async fn write_backup(&self, backup: &mut PreparedBackup) -> Result<(), IggyError> {
+        // The snapshot covers all the state entries, so the log in the backup is empty.
+        let snapshot_path = backup
+            .backup
+            .prepare(&self.config.get_state_snapshot_path())
+            .await?;
+        backup.info.state_index = self
+            .state
+            .export_snapshot(&snapshot_path.to_string_lossy())
+            .await?
+            .unwrap_or_default();
+        if snapshot_path.exists() {
+            backup.backup.add_file(snapshot_path).await?;
+        }
+        let state_log_path = backup
+            .backup
+            .prepare(&self.config.get_state_log_path())
+            .await?;
+        fs::write(&state_log_path, []).await?;
+        backup.backup.add_file(state_log_path).await?;
+        let state_info_path = self.config.get_state_info_path();
+        if Path::new(&state_info_path).exists() {
+            backup.backup.copy(&state_info_path, None).await?;
+        }
+
+        for stream in self.get_streams() {
+            let stream = stream.read().await;
+            fs::create_dir_all(backup.backup.prepare(&stream.topics_path).await?).await?;
+            for topic in stream.get_topics() {
+                let topic = topic.read().await;
+                fs::create_dir_all(backup.backup.prepare(&topic.partitions_path).await?).await?;
+                for partition in topic.get_partitions() {
+                    let partition = partition.read().await;
+                    fs::create_dir_all(backup.backup.prepare(&partition.partition_path).await?)
+                        .await?;
+                    if Path::new(&partition.offsets_path).exists() {
+                        let offsets_path = backup.backup.prepare(&partition.offsets_path).await?;
+                        let (files, size) =
+                            file::copy_dir(Path::new(&partition.offsets_path), &offsets_path)
+                                .await?;
+                        backup.backup.files.extend(files);
+                        backup.backup.size += size;
+                    }
+
+                    for segment in partition.get_segments() {
+                        backup.add_segment(segment).await?;
+                        backup.info.segments_count += 1;
+                        backup.info.messages_count += segment.get_messages_count();
+                    }
+                    backup.info.partitions_count += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The backup prepared by `System::prepare_backup`, which still requires copying the segment files.
+pub struct PreparedBackup {
+    backup: Backup,
+    info: BackupInfo,
+    pending_copies: Vec<(String, Option<u64>)>,
+    archiver: Option<Arc<dyn Archiver>>,
+}
+
+impl PreparedBackup {
+    /// Copies the active segments (and the closed ones which couldn't be hard-linked) up to their sizes recorded
+    /// when the backup was prepared, writes the manifest and archives the backup, if requested.
+    /// It must be invoked without holding the access to the system, so that the messages can be appended meanwhile.
+    pub async fn complete(mut self) -> Result<BackupInfo, IggyError> {
+        let name = self.info.name.clone();
+        if let Err(error) = self.write_segments_and_manifest().await {
+            return Err(self.fail(error).await);
+        }
+
+        if let Some(archiver) = &self.archiver {
+            let files = self
+                .backup
+                .files
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect::<Vec<_>>();
+            let files = files.iter().map(|path| path.as_str()).collect::<Vec<_>>();
+            if let Err(error) = archiver.archive(&files, None).await {
+                error!("Failed to archive backup: {name}. Error: {error}");
+                return Err(IggyError::CannotCreateBackup(error.to_string()));
+            }
+            info!("Archived backup: {name}.");
+        }
+
+        info!(
+            "Created backup: {name} with state index: {}, {} partitions, {} segments, {} messages, size: {}.",
+            self.info.state_index,
+            self.info.partitions_count,
+            self.info.segments_count,
+            self.info.messages_count,
+            self.info.size
+        );
+        Ok(self.info)
+    }
+
+    /// Links the closed segment, or records the sizes of the active segment files, so that they're copied later
+    /// only up to these sizes, even though the new messages are appended meanwhile.
+    async fn add_segment(&mut self, segment: &Segment) -> Result<(), IggyError> {
+        for path in [
+            &segment.log_path,
+            &segment.index_path,
+            &segment.time_index_path,
+        ] {
+            if !Path::new(path).exists() {
+                continue;
+            }
+
+            if segment.is_closed && self.backup.link(path).await? {
+                continue;
+            }
+
+            let size = fs::metadata(path).await?.len();
+            self.pending_copies.push((path.clone(), Some(size)));
+        }
+        Ok(())
+    }
+
+    async fn write_segments_and_manifest(&mut self) -> Result<(), IggyError> {
+        for (path, size) in std::mem::take(&mut self.pending_copies) {
+            // The segment might have been deleted in the meantime (e.g. by the message expiry).
+            if !Path::new(&path).exists() {
+                warn!(
+                    "Segment file: {path} no longer exists, it will not be included in the backup."
+                );
+                continue;
+            }
+
+            self.backup.copy(&path, size).await?;
+        }
+
+        // The manifest is written as the last one, so only the complete backups can be restored.
+        self.info.size = IggyByteSize::from(self.backup.size);
+        let manifest_path = self.backup.path.join(MANIFEST_FILE_NAME);
+        let manifest = serde_json::to_vec_pretty(&self.info)
+            .map_err(|error| IggyError::CannotCreateBackup(error.to_string()))?;
+        fs::write(&manifest_path, manifest).await?;
+        self.backup.add_file(manifest_path).await?;
+        Ok(())
+    }
+
+    /// Removes the incomplete backup and returns the error to be reported.
+    async fn fail(&self, error: IggyError) -> IggyError {
+        let name = &self.info.name;
+        error!("Failed to create backup: {name}. Error: {error}");
+        if let Err(error) = fs::remove_dir_all(&self.backup.path).await {
+            warn!("Failed to remove incomplete backup: {name}. Error: {error}");
+        }
+        IggyError::CannotCreateBackup(error.to_string())
+    }
+}
+
+struct Backup {
+    system_path: PathBuf,
+    path: PathBuf,
+    files: Vec<PathBuf>,
+    size: u64,
+}
+
+impl Backup {
+    /// Returns the path in the backup corresponding to the given path in the system directory,
+    /// and creates its parent directory.
+    async fn prepare(&self, path: &str) -> Result<PathBuf, IggyError> {
+        let relative_path = Path::new(path)
+            .strip_prefix(&self.system_path)
+            .map_err(|error| IggyError::CannotCreateBackup(error.to_string()))?;
+        let backup_path = self.path.join(relative_path);
+        if let Some(parent) = backup_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        Ok(backup_path)
+    }
+
+    async fn add_file(&mut self, path: PathBuf) -> Result<(), IggyError> {
+        self.size += fs::metadata(&path).await?.len();
+        self.files.push(path);
+        Ok(())
+    }
+
+    /// Copies the file (up to the given size, if provided) to the backup.
+    async fn copy(&mut self, path: &str, size: Option<u64>) -> Result<(), IggyError> {
+        let backup_path = self.prepare(path).await?;
+        match size {
+            Some(size) => {
+                let mut source = file::open(path).await?.take(size);
+                let mut destination = file::overwrite(&backup_path.to_string_lossy()).await?;
+                tokio::io::copy(&mut source, &mut destination).await?;
+                destination.sync_all().await?;
+            }
+            None => {
+                fs::copy(path, &backup_path).await?;
+            }
+        }
+        self.add_file(backup_path).await
+    }
+
+    /// Hard-links the file to the backup, returns `false` if linking is not possible (e.g. another file system).
+    async fn link(&mut self, path: &str) -> Result<bool, IggyError> {
+        let backup_path = self.prepare(path).await?;
+        if let Err(error) = fs::hard_link(path, &backup_path).await {
+            warn!("Cannot hard-link file: {path} to the backup, it will be copied. Error: {error}");
+            return Ok(false);
+        }
+        self.add_file(backup_path).await?;
+        Ok(true)
+    }
+}
+
+async fn read_manifest(backup_path: &Path) -> Option<BackupInfo> {
+    let manifest = fs::read(backup_path.join(MANIFEST_FILE_NAME)).await.ok()?;
+    serde_json::from_slice(&manifest).ok()
+}
+
+async fn find_latest_backup(backups_path: &str) -> Result<Option<BackupInfo>, IggyError> {
+    if !Path::new(backups_path).exists() {
+        return Ok(None);
+    }
+
+    let mut latest_backup: Option<BackupInfo> = None;
+    let mut dir_entries = fs::read_dir(backups_path).await?;
+    while let Some(dir_entry) = dir_entries.next_entry().await? {
+        let Some(backup_info) = read_manifest(&dir_entry.path()).await else {
+            continue;
+        };
+
+        if latest_backup.as_ref().is_none_or(|latest_backup| {
+            backup_info.created_at.as_micros() > latest_backup.created_at.as_micros()
+        }) {
+            latest_backup = Some(backup_info);
+        }
+    }
+    Ok(latest_backup)
+}
+
+async fn is_empty_dir(path: &str) -> Result<bool, IggyError> {
+    if !Path::new(path).exists() {
+        return Ok(true);
+    }
+
+    let mut dir_entries = fs::read_dir(path).await?;
+    Ok(dir_entries.next_entry().await?.is_none())
+}
@@ -80,7 +80,10 @@ impl System {
         client_id: u32,
     ) -> Result<IggySharedMut<Client>, IggyError> {
         self.ensure_authenticated(session)?;
-        self.permissioner.get_client(session.get_user_id())?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .get_client(session.get_user_id())?;
         let client_manager = self.client_manager.read().await;
         client_manager.get_client_by_id(client_id)
     }
@@ -90,7 +93,10 @@ impl System {
         session: &Session,
     ) -> Result<Vec<IggySharedMut<Client>>, IggyError> {
         self.ensure_authenticated(session)?;
-        self.permissioner.get_clients(session.get_user_id())?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .get_clients(session.get_user_id())?;
         let client_manager = self.client_manager.read().await;
         Ok(client_manager.get_clients())
     }
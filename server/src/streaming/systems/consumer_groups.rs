@@ -3,21 +3,22 @@ use crate::streaming::systems::system::System;
 use crate::streaming::topics::consumer_group::ConsumerGroup;
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
-use iggy::locking::IggySharedMutFn;
-use tokio::sync::RwLock;
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
 
 impl System {
-    pub fn get_consumer_group(
+    pub async fn get_consumer_group(
         &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
         group_id: &Identifier,
-    ) -> Result<&RwLock<ConsumerGroup>, IggyError> {
+    ) -> Result<IggySharedMut<ConsumerGroup>, IggyError> {
         self.ensure_authenticated(session)?;
         let stream = self.get_stream(stream_id)?;
+        let stream = stream.read().await;
         let topic = stream.get_topic(topic_id)?;
-        self.permissioner.get_consumer_group(
+        let topic = topic.read().await;
+        self.permissioner.read().unwrap().get_consumer_group(
             session.get_user_id(),
             stream.stream_id,
             topic.topic_id,
@@ -26,16 +27,18 @@ impl System {
         topic.get_consumer_group(group_id)
     }
 
-    pub fn get_consumer_groups(
+    pub async fn get_consumer_groups(
         &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
-    ) -> Result<Vec<&RwLock<ConsumerGroup>>, IggyError> {
+    ) -> Result<Vec<IggySharedMut<ConsumerGroup>>, IggyError> {
         self.ensure_authenticated(session)?;
         let stream = self.get_stream(stream_id)?;
+        let stream = stream.read().await;
         let topic = stream.get_topic(topic_id)?;
-        self.permissioner.get_consumer_groups(
+        let topic = topic.read().await;
+        self.permissioner.read().unwrap().get_consumer_groups(
             session.get_user_id(),
             stream.stream_id,
             topic.topic_id,
@@ -45,30 +48,29 @@ impl System {
     }
 
     pub async fn create_consumer_group(
-        &mut self,
+        &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
         group_id: Option<u32>,
         name: &str,
-    ) -> Result<&RwLock<ConsumerGroup>, IggyError> {
+    ) -> Result<IggySharedMut<ConsumerGroup>, IggyError> {
         self.ensure_authenticated(session)?;
-        {
-            let stream = self.get_stream(stream_id)?;
-            let topic = stream.get_topic(topic_id)?;
-            self.permissioner.create_consumer_group(
-                session.get_user_id(),
-                stream.stream_id,
-                topic.topic_id,
-            )?;
-        }
+        let stream = self.get_stream(stream_id)?;
+        let stream = stream.read().await;
+        let topic = stream.get_topic(topic_id)?;
+        let mut topic = topic.write().await;
+        self.permissioner.read().unwrap().create_consumer_group(
+            session.get_user_id(),
+            stream.stream_id,
+            topic.topic_id,
+        )?;
 
-        let topic = self.get_stream_mut(stream_id)?.get_topic_mut(topic_id)?;
         topic.create_consumer_group(group_id, name).await
     }
 
     pub async fn delete_consumer_group(
-        &mut self,
+        &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
@@ -77,22 +79,19 @@ impl System {
         self.ensure_authenticated(session)?;
         let stream_id_value;
         let topic_id_value;
+        let consumer_group;
         {
             let stream = self.get_stream(stream_id)?;
+            let stream = stream.read().await;
             let topic = stream.get_topic(topic_id)?;
-            self.permissioner.delete_consumer_group(
+            let mut topic = topic.write().await;
+            self.permissioner.read().unwrap().delete_consumer_group(
                 session.get_user_id(),
                 stream.stream_id,
                 topic.topic_id,
             )?;
             stream_id_value = stream.stream_id;
             topic_id_value = topic.topic_id;
-        }
-
-        let consumer_group;
-        {
-            let stream = self.get_stream_mut(stream_id)?;
-            let topic = stream.get_topic_mut(topic_id)?;
             consumer_group = topic.delete_consumer_group(consumer_group_id).await?;
         }
 
@@ -123,22 +122,19 @@ impl System {
         self.ensure_authenticated(session)?;
         let stream_id_value;
         let topic_id_value;
+        let group_id;
         {
             let stream = self.get_stream(stream_id)?;
+            let stream = stream.read().await;
             let topic = stream.get_topic(topic_id)?;
-            self.permissioner.join_consumer_group(
+            let topic = topic.read().await;
+            self.permissioner.read().unwrap().join_consumer_group(
                 session.get_user_id(),
                 stream.stream_id,
                 topic.topic_id,
             )?;
             stream_id_value = stream.stream_id;
             topic_id_value = topic.topic_id;
-        }
-
-        let group_id;
-        {
-            let stream = self.get_stream(stream_id)?;
-            let topic = stream.get_topic(topic_id)?;
 
             {
                 let consumer_group = topic.get_consumer_group(consumer_group_id)?;
@@ -168,8 +164,10 @@ impl System {
         self.ensure_authenticated(session)?;
         {
             let stream = self.get_stream(stream_id)?;
+            let stream = stream.read().await;
             let topic = stream.get_topic(topic_id)?;
-            self.permissioner.leave_consumer_group(
+            let topic = topic.read().await;
+            self.permissioner.read().unwrap().leave_consumer_group(
                 session.get_user_id(),
                 stream.stream_id,
                 topic.topic_id,
@@ -197,7 +195,9 @@ impl System {
         let group_id;
         {
             let stream = self.get_stream(stream_id)?;
+            let stream = stream.read().await;
             let topic = stream.get_topic(topic_id)?;
+            let topic = topic.read().await;
 
             {
                 let consumer_group = topic.get_consumer_group(consumer_group_id)?;
@@ -1,8 +1,11 @@
 use crate::streaming::polling_consumer::PollingConsumer;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::System;
+use iggy::consumer::{Consumer, ConsumerKind};
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
+use iggy::locking::IggySharedMutFn;
+use iggy::models::consumer_lag_info::ConsumerLagInfo;
 use iggy::models::consumer_offset_info::ConsumerOffsetInfo;
 
 impl System {
@@ -16,8 +19,10 @@ impl System {
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
         let stream = self.get_stream(stream_id)?;
+        let stream = stream.read().await;
         let topic = stream.get_topic(topic_id)?;
-        self.permissioner.store_consumer_offset(
+        let topic = topic.read().await;
+        self.permissioner.read().unwrap().store_consumer_offset(
             session.get_user_id(),
             stream.stream_id,
             topic.topic_id,
@@ -35,8 +40,10 @@ impl System {
     ) -> Result<ConsumerOffsetInfo, IggyError> {
         self.ensure_authenticated(session)?;
         let stream = self.get_stream(stream_id)?;
+        let stream = stream.read().await;
         let topic = stream.get_topic(topic_id)?;
-        self.permissioner.get_consumer_offset(
+        let topic = topic.read().await;
+        self.permissioner.read().unwrap().get_consumer_offset(
             session.get_user_id(),
             stream.stream_id,
             topic.topic_id,
@@ -44,4 +51,38 @@ impl System {
 
         topic.get_consumer_offset(consumer).await
     }
+
+    pub async fn get_consumer_lag(
+        &self,
+        session: &Session,
+        consumer: &Consumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        partition_id: Option<u32>,
+    ) -> Result<Vec<ConsumerLagInfo>, IggyError> {
+        self.ensure_authenticated(session)?;
+        let stream = self.get_stream(stream_id)?;
+        let stream = stream.read().await;
+        let topic = stream.get_topic(topic_id)?;
+        let topic = topic.read().await;
+        self.permissioner.read().unwrap().get_consumer_offset(
+            session.get_user_id(),
+            stream.stream_id,
+            topic.topic_id,
+        )?;
+
+        let consumer_id = match consumer.kind {
+            ConsumerKind::Consumer => PollingConsumer::resolve_consumer_id(&consumer.id),
+            ConsumerKind::ConsumerGroup => {
+                topic
+                    .get_consumer_group(&consumer.id)?
+                    .read()
+                    .await
+                    .group_id
+            }
+        };
+        topic
+            .get_consumer_lag(consumer.kind, consumer_id, partition_id)
+            .await
+    }
 }
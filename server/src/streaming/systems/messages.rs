@@ -1,12 +1,16 @@
+use crate::streaming::batching::polled_batches::PolledBatches;
 use crate::streaming::cache::memory_tracker::CacheMemoryTracker;
 use crate::streaming::polling_consumer::PollingConsumer;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::System;
+use crate::streaming::topics::topic::Topic;
 use bytes::Bytes;
+use iggy::locking::IggySharedMutFn;
 use iggy::messages::poll_messages::PollingStrategy;
 use iggy::messages::send_messages::Message;
 use iggy::messages::send_messages::Partitioning;
 use iggy::models::messages::{PolledMessage, PolledMessages};
+use iggy::utils::crypto::Encryptor;
 use iggy::{error::IggyError, identifier::Identifier};
 use tracing::{error, trace};
 
@@ -25,22 +29,16 @@ impl System {
         }
 
         let stream = self.get_stream(stream_id)?;
+        let stream = stream.read().await;
         let topic = stream.get_topic(topic_id)?;
-        self.permissioner
-            .poll_messages(session.get_user_id(), stream.stream_id, topic.topic_id)?;
-
-        if !topic.has_partitions() {
-            return Err(IggyError::NoPartitions(topic.topic_id, topic.stream_id));
-        }
-
-        let partition_id = match consumer {
-            PollingConsumer::Consumer(_, partition_id) => partition_id,
-            PollingConsumer::ConsumerGroup(group_id, member_id) => {
-                let consumer_group = topic.get_consumer_group_by_id(group_id)?.read().await;
-                consumer_group.calculate_partition_id(member_id).await?
-            }
-        };
+        let topic = topic.read().await;
+        self.permissioner.read().unwrap().poll_messages(
+            session.get_user_id(),
+            stream.stream_id,
+            topic.topic_id,
+        )?;
 
+        let partition_id = Self::get_polled_partition_id(&topic, consumer).await?;
         let mut polled_messages = topic
             .get_messages(consumer, partition_id, args.strategy, args.count)
             .await?;
@@ -49,12 +47,28 @@ impl System {
             return Ok(polled_messages);
         }
 
+        let polled_size_bytes = polled_messages
+            .messages
+            .iter()
+            .map(|message| message.get_size_bytes() as u64)
+            .sum();
+        self.metrics.increment_polled_messages(
+            topic.stream_id,
+            topic.topic_id,
+            polled_messages.partition_id,
+            polled_messages.messages.len() as u64,
+            polled_size_bytes,
+        );
+
         let offset = polled_messages.messages.last().unwrap().offset;
         if args.auto_commit {
             trace!("Last offset: {} will be automatically stored for {}, stream: {}, topic: {}, partition: {}", offset, consumer, stream_id, topic_id, partition_id);
             topic.store_consumer_offset(consumer, offset).await?;
         }
 
+        // The messages are decrypted without holding the locks of the stream and topic.
+        drop(topic);
+        drop(stream);
         if self.encryptor.is_none() {
             return Ok(polled_messages);
         }
@@ -86,6 +100,81 @@ impl System {
         Ok(polled_messages)
     }
 
+    /// Polls the messages as the whole batches, in the same format as stored in the segment files (see `PolledBatches`),
+    /// so the persisted ones don't have to be loaded and can be sent to the client directly from the files.
+    /// When the encryption is enabled, the messages are polled and decrypted as usual, then encoded as a single batch.
+    pub async fn poll_batches(
+        &self,
+        session: &Session,
+        consumer: PollingConsumer,
+        stream_id: &Identifier,
+        topic_id: &Identifier,
+        args: PollingArgs,
+    ) -> Result<PolledBatches, IggyError> {
+        if self.encryptor.is_some() {
+            return Ok(self
+                .poll_messages(session, consumer, stream_id, topic_id, args)
+                .await?
+                .into());
+        }
+
+        self.ensure_authenticated(session)?;
+        if args.count == 0 {
+            return Err(IggyError::InvalidMessagesCount);
+        }
+
+        let stream = self.get_stream(stream_id)?;
+        let stream = stream.read().await;
+        let topic = stream.get_topic(topic_id)?;
+        let topic = topic.read().await;
+        self.permissioner.read().unwrap().poll_messages(
+            session.get_user_id(),
+            stream.stream_id,
+            topic.topic_id,
+        )?;
+
+        let partition_id = Self::get_polled_partition_id(&topic, consumer).await?;
+        let polled_batches = topic
+            .get_batches(consumer, partition_id, args.strategy, args.count)
+            .await?;
+        let Some(offset) = polled_batches.get_last_offset() else {
+            return Ok(polled_batches);
+        };
+
+        self.metrics.increment_polled_messages(
+            topic.stream_id,
+            topic.topic_id,
+            polled_batches.partition_id,
+            polled_batches.messages_count as u64,
+            polled_batches.get_size_bytes(),
+        );
+
+        if args.auto_commit {
+            trace!("Last offset: {} will be automatically stored for {}, stream: {}, topic: {}, partition: {}", offset, consumer, stream_id, topic_id, partition_id);
+            topic.store_consumer_offset(consumer, offset).await?;
+        }
+
+        Ok(polled_batches)
+    }
+
+    async fn get_polled_partition_id(
+        topic: &Topic,
+        consumer: PollingConsumer,
+    ) -> Result<u32, IggyError> {
+        if !topic.has_partitions() {
+            return Err(IggyError::NoPartitions(topic.topic_id, topic.stream_id));
+        }
+
+        match consumer {
+            PollingConsumer::Consumer(_, partition_id) => Ok(partition_id),
+            PollingConsumer::ConsumerGroup(group_id, member_id) => {
+                let consumer_group = topic.get_consumer_group_by_id(group_id)?;
+                let consumer_group = consumer_group.read().await;
+                consumer_group.calculate_partition_id(member_id).await
+            }
+        }
+    }
+
     pub async fn append_messages(
         &self,
         session: &Session,
@@ -95,14 +184,20 @@ impl System {
         messages: Vec<Message>,
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
-        let stream = self.get_stream(&stream_id)?;
-        let topic = stream.get_topic(&topic_id)?;
-        self.permissioner.append_messages(
-            session.get_user_id(),
-            stream.stream_id,
-            topic.topic_id,
-        )?;
+        {
+            let stream = self.get_stream(&stream_id)?;
+            let stream = stream.read().await;
+            let topic = stream.get_topic(&topic_id)?;
+            let topic = topic.read().await;
+            self.permissioner.read().unwrap().append_messages(
+                session.get_user_id(),
+                stream.stream_id,
+                topic.topic_id,
+            )?;
+        }
 
+        // The messages are encrypted and the cache is cleaned without holding the locks of the stream and topic,
+        // which are acquired again only for the duration of the append to the partition.
         let mut batch_size_bytes = 0;
         let mut messages = messages;
         if let Some(encryptor) = &self.encryptor {
@@ -128,11 +223,25 @@ impl System {
                 self.clean_cache(batch_size_bytes).await;
             }
         }
+        let stream = self.get_stream(&stream_id)?;
+        let stream = stream.read().await;
+        let topic = stream.get_topic(&topic_id)?;
+        let topic = topic.read().await;
         let messages_count = messages.len() as u64;
-        topic
+        let Some(partition_id) = topic
             .append_messages(batch_size_bytes, partitioning, messages)
-            .await?;
+            .await?
+        else {
+            return Ok(());
+        };
         self.metrics.increment_messages(messages_count);
+        self.metrics.increment_sent_messages(
+            topic.stream_id,
+            topic.topic_id,
+            partition_id,
+            messages_count,
+            batch_size_bytes,
+        );
         Ok(())
     }
 }
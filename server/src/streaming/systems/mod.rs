@@ -1,3 +1,5 @@
+pub mod audit;
+pub mod backup;
 pub mod clients;
 pub mod consumer_groups;
 pub mod consumer_offsets;
@@ -5,6 +7,7 @@ pub mod info;
 pub mod messages;
 pub mod partitions;
 pub mod personal_access_tokens;
+pub mod roles;
 pub mod stats;
 pub mod storage;
 pub mod streams;
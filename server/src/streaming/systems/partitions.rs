@@ -2,27 +2,27 @@ use crate::streaming::session::Session;
 use crate::streaming::systems::system::System;
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
+use iggy::locking::IggySharedMutFn;
 
 impl System {
     pub async fn create_partitions(
-        &mut self,
+        &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
         partitions_count: u32,
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
-        {
-            let stream = self.get_stream(stream_id)?;
-            let topic = stream.get_topic(topic_id)?;
-            self.permissioner.create_partitions(
-                session.get_user_id(),
-                stream.stream_id,
-                topic.topic_id,
-            )?;
-        }
+        let stream = self.get_stream(stream_id)?;
+        let stream = stream.read().await;
+        let topic = stream.get_topic(topic_id)?;
+        let mut topic = topic.write().await;
+        self.permissioner.read().unwrap().create_partitions(
+            session.get_user_id(),
+            stream.stream_id,
+            topic.topic_id,
+        )?;
 
-        let topic = self.get_stream_mut(stream_id)?.get_topic_mut(topic_id)?;
         topic.add_persisted_partitions(partitions_count).await?;
         topic.reassign_consumer_groups().await;
         self.metrics.increment_partitions(partitions_count);
@@ -31,30 +31,33 @@ impl System {
     }
 
     pub async fn delete_partitions(
-        &mut self,
+        &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
         partitions_count: u32,
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
-        {
-            let stream = self.get_stream(stream_id)?;
-            let topic = stream.get_topic(topic_id)?;
-            self.permissioner.delete_partitions(
-                session.get_user_id(),
-                stream.stream_id,
-                topic.topic_id,
-            )?;
-        }
+        let stream = self.get_stream(stream_id)?;
+        let stream = stream.read().await;
+        let topic = stream.get_topic(topic_id)?;
+        let mut topic = topic.write().await;
+        self.permissioner.read().unwrap().delete_partitions(
+            session.get_user_id(),
+            stream.stream_id,
+            topic.topic_id,
+        )?;
 
-        let topic = self.get_stream_mut(stream_id)?.get_topic_mut(topic_id)?;
         let partitions = topic.delete_persisted_partitions(partitions_count).await?;
         topic.reassign_consumer_groups().await;
         if let Some(partitions) = partitions {
             self.metrics.decrement_partitions(partitions_count);
             self.metrics.decrement_segments(partitions.segments_count);
             self.metrics.decrement_messages(partitions.messages_count);
+            for partition_id in partitions.partition_ids {
+                self.metrics
+                    .remove_partition(stream.stream_id, topic.topic_id, partition_id);
+            }
         }
         Ok(())
     }
@@ -1,32 +1,35 @@
 use crate::streaming::personal_access_tokens::personal_access_token::PersonalAccessToken;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::System;
+use crate::streaming::systems::users::get_user_mut;
 use crate::streaming::users::user::User;
 use iggy::error::IggyError;
+use iggy::identifier::Identifier;
+use iggy::models::user_info::UserId;
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::text;
 use iggy::utils::timestamp::IggyTimestamp;
-use tracing::{error, info};
+use tokio::sync::RwLockReadGuard;
+use tracing::{debug, error, info};
 
 impl System {
+    /// Returns the user of the session, whose personal access tokens stay locked for reading until the returned guard is dropped.
     pub async fn get_personal_access_tokens(
         &self,
         session: &Session,
-    ) -> Result<Vec<&PersonalAccessToken>, IggyError> {
+    ) -> Result<RwLockReadGuard<'_, User>, IggyError> {
         self.ensure_authenticated(session)?;
         let user_id = session.get_user_id();
-        let user = self.get_user(&user_id.try_into()?)?;
-        info!("Loading personal access tokens for user with ID: {user_id}...",);
-        let personal_access_tokens: Vec<_> = user.personal_access_tokens.values().collect();
+        let user = self.get_user(&user_id.try_into()?).await?;
         info!(
             "Loaded {} personal access tokens for user with ID: {user_id}.",
-            personal_access_tokens.len(),
+            user.personal_access_tokens.len(),
         );
-        Ok(personal_access_tokens)
+        Ok(user)
     }
 
     pub async fn create_personal_access_token(
-        &mut self,
+        &self,
         session: &Session,
         name: &str,
         expiry: IggyExpiry,
@@ -34,21 +37,19 @@ impl System {
         self.ensure_authenticated(session)?;
         let user_id = session.get_user_id();
         let identifier = user_id.try_into()?;
-        {
-            let user = self.get_user(&identifier)?;
-            let max_token_per_user = self.personal_access_token.max_tokens_per_user;
-            if user.personal_access_tokens.len() as u32 >= max_token_per_user {
-                error!(
+        let mut users = self.users.write().await;
+        let user = get_user_mut(&mut users, &identifier)?;
+        let max_token_per_user = self.personal_access_token.max_tokens_per_user;
+        if user.personal_access_tokens.len() as u32 >= max_token_per_user {
+            error!(
                 "User with ID: {user_id} has reached the maximum number of personal access tokens: {max_token_per_user}.",
             );
-                return Err(IggyError::PersonalAccessTokensLimitReached(
-                    user_id,
-                    max_token_per_user,
-                ));
-            }
+            return Err(IggyError::PersonalAccessTokensLimitReached(
+                user_id,
+                max_token_per_user,
+            ));
         }
 
-        let user = self.get_user_mut(&identifier)?;
         let name = text::to_lowercase_non_whitespace(name);
         if user
             .personal_access_tokens
@@ -69,13 +70,14 @@ impl System {
     }
 
     pub async fn delete_personal_access_token(
-        &mut self,
+        &self,
         session: &Session,
         name: &str,
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
         let user_id = session.get_user_id();
-        let user = self.get_user_mut(&user_id.try_into()?)?;
+        let mut users = self.users.write().await;
+        let user = get_user_mut(&mut users, &user_id.try_into()?)?;
         let name = text::to_lowercase_non_whitespace(name);
         let token;
 
@@ -98,39 +100,74 @@ impl System {
         Ok(())
     }
 
+    /// Removes the expired personal access tokens of all the users and returns their number.
+    pub async fn delete_expired_personal_access_tokens(&self, now: IggyTimestamp) -> usize {
+        let mut deleted_tokens_count = 0;
+        for user in self.users.write().await.values_mut() {
+            let expired_tokens = user
+                .personal_access_tokens
+                .values()
+                .filter(|token| token.is_expired(now))
+                .map(|token| token.token.clone())
+                .collect::<Vec<_>>();
+            for token in expired_tokens {
+                debug!(
+                    "Personal access token: {token} for user with ID: {} is expired.",
+                    user.id
+                );
+                deleted_tokens_count += 1;
+                user.personal_access_tokens.remove(&token);
+                debug!(
+                    "Deleted personal access token: {token} for user with ID: {}.",
+                    user.id
+                );
+            }
+        }
+        deleted_tokens_count
+    }
+
+    /// Logs in the user with the personal access token and returns its ID.
     pub async fn login_with_personal_access_token(
         &self,
         token: &str,
         session: Option<&Session>,
-    ) -> Result<&User, IggyError> {
+    ) -> Result<UserId, IggyError> {
         let token_hash = PersonalAccessToken::hash_token(token);
-        let mut personal_access_token = None;
-        for user in self.users.values() {
-            if let Some(pat) = user.personal_access_tokens.get(&token_hash) {
-                personal_access_token = Some(pat);
-                break;
+        let username = {
+            let users = self.users.read().await;
+            let mut personal_access_token = None;
+            for user in users.values() {
+                if let Some(pat) = user.personal_access_tokens.get(&token_hash) {
+                    personal_access_token = Some(pat);
+                    break;
+                }
             }
-        }
 
-        if personal_access_token.is_none() {
-            error!("Personal access token: {} does not exist.", token);
-            return Err(IggyError::ResourceNotFound(token.to_owned()));
-        }
+            if personal_access_token.is_none() {
+                error!("Personal access token: {} does not exist.", token);
+                return Err(IggyError::ResourceNotFound(token.to_owned()));
+            }
 
-        let personal_access_token = personal_access_token.unwrap();
-        if personal_access_token.is_expired(IggyTimestamp::now()) {
-            error!(
-                "Personal access token: {} for user with ID: {} has expired.",
-                personal_access_token.name, personal_access_token.user_id
-            );
-            return Err(IggyError::PersonalAccessTokenExpired(
-                personal_access_token.name.clone(),
-                personal_access_token.user_id,
-            ));
-        }
+            let personal_access_token = personal_access_token.unwrap();
+            if personal_access_token.is_expired(IggyTimestamp::now()) {
+                error!(
+                    "Personal access token: {} for user with ID: {} has expired.",
+                    personal_access_token.name, personal_access_token.user_id
+                );
+                return Err(IggyError::PersonalAccessTokenExpired(
+                    personal_access_token.name.clone(),
+                    personal_access_token.user_id,
+                ));
+            }
+
+            let user_id = Identifier::numeric(personal_access_token.user_id)?;
+            users
+                .get(&personal_access_token.user_id)
+                .map(|user| user.username.clone())
+                .ok_or(IggyError::ResourceNotFound(user_id.to_string()))?
+        };
 
-        let user = self.get_user(&personal_access_token.user_id.try_into()?)?;
-        self.login_user_with_credentials(&user.username, None, session)
+        self.login_user_with_credentials(&username, None, session)
             .await
     }
 }
@@ -0,0 +1,230 @@
+use crate::state::command::EntryCommand;
+use crate::state::models::CreateRoleWithId;
+use crate::state::system::RoleState;
+use crate::streaming::session::Session;
+use crate::streaming::systems::system::System;
+use crate::streaming::users::role::Role;
+use iggy::error::IggyError;
+use iggy::identifier::{IdKind, Identifier};
+use iggy::models::permissions::Permissions;
+use iggy::models::role_info::RoleId;
+use iggy::roles::create_role::CreateRole;
+use iggy::utils::text;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use tokio::sync::RwLockReadGuard;
+use tracing::{error, info};
+
+static ROLE_ID: AtomicU32 = AtomicU32::new(1);
+const MAX_ROLES: usize = u32::MAX as usize;
+
+impl System {
+    pub(crate) async fn load_roles(&mut self, roles: Vec<RoleState>) -> Result<(), IggyError> {
+        info!("Loading roles...");
+        let loaded_roles = self.roles.get_mut();
+        for role_state in roles.into_iter() {
+            let role = Role::with_created_at(
+                role_state.id,
+                &role_state.name,
+                role_state.created_at,
+                role_state.permissions,
+            );
+            loaded_roles.insert(role.id, role);
+        }
+
+        let current_role_id = loaded_roles.keys().max().unwrap_or(&0);
+        ROLE_ID.store(current_role_id + 1, Ordering::SeqCst);
+        info!("Initialized {} role(s).", loaded_roles.len());
+        Ok(())
+    }
+
+    pub async fn find_role(
+        &self,
+        session: &Session,
+        role_id: &Identifier,
+    ) -> Result<RwLockReadGuard<'_, Role>, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .get_role(session.get_user_id())?;
+        self.get_role(role_id).await
+    }
+
+    /// Returns the role, the roles stay locked for reading until the returned guard is dropped.
+    pub async fn get_role(
+        &self,
+        role_id: &Identifier,
+    ) -> Result<RwLockReadGuard<'_, Role>, IggyError> {
+        RwLockReadGuard::try_map(self.roles.read().await, |roles| {
+            get_role(roles, role_id).ok()
+        })
+        .map_err(|_| IggyError::ResourceNotFound(role_id.to_string()))
+    }
+
+    pub async fn get_roles(
+        &self,
+        session: &Session,
+    ) -> Result<RwLockReadGuard<'_, HashMap<RoleId, Role>>, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .get_roles(session.get_user_id())?;
+        Ok(self.roles.read().await)
+    }
+
+    /// Creates the role and applies the state entry, which contains the assigned ID,
+    /// so the role is restored with the same ID, even if the roles with the higher IDs were deleted.
+    pub async fn create_role(
+        &self,
+        session: &Session,
+        name: &str,
+        permissions: Option<Permissions>,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .create_role(session.get_user_id())?;
+        let name = text::to_lowercase_non_whitespace(name);
+        let mut roles = self.roles.write().await;
+        if roles.values().any(|role| role.name == name) {
+            error!("Role: {name} already exists.");
+            return Err(IggyError::RoleAlreadyExists);
+        }
+
+        if roles.len() >= MAX_ROLES {
+            error!("Available roles limit reached.");
+            return Err(IggyError::RolesLimitReached);
+        }
+
+        let role_id = ROLE_ID.fetch_add(1, Ordering::SeqCst);
+        info!("Creating role: {name} with ID: {role_id}...");
+        let role = Role::new(role_id, &name, permissions.clone());
+        self.permissioner
+            .write()
+            .unwrap()
+            .init_permissions_for_role(role_id, permissions.clone());
+        roles.insert(role.id, role);
+        info!("Created role: {name} with ID: {role_id}.");
+        self.state
+            .apply(
+                session.get_user_id(),
+                EntryCommand::CreateRole(CreateRoleWithId {
+                    role_id,
+                    command: CreateRole { name, permissions },
+                }),
+            )
+            .await
+    }
+
+    pub async fn update_role(
+        &self,
+        session: &Session,
+        role_id: &Identifier,
+        name: Option<String>,
+        permissions: Option<Permissions>,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .update_role(session.get_user_id())?;
+        let name = name.map(|name| text::to_lowercase_non_whitespace(&name));
+        let mut roles = self.roles.write().await;
+        if let Some(name) = &name {
+            let role = get_role(&roles, role_id)?;
+            if roles
+                .values()
+                .any(|existing_role| existing_role.name == *name && existing_role.id != role.id)
+            {
+                error!("Role: {name} already exists.");
+                return Err(IggyError::RoleAlreadyExists);
+            }
+        }
+
+        let role = get_role_mut(&mut roles, role_id)?;
+        if let Some(permissions) = &permissions {
+            self.permissioner
+                .write()
+                .unwrap()
+                .update_permissions_for_role(role.id, Some(permissions.clone()));
+        }
+
+        if let Some(name) = name {
+            role.name = name;
+        }
+
+        if permissions.is_some() {
+            role.permissions = permissions;
+        }
+
+        info!("Updated role: {} with ID: {}.", role.name, role.id);
+        Ok(())
+    }
+
+    pub async fn delete_role(
+        &self,
+        session: &Session,
+        role_id: &Identifier,
+    ) -> Result<Role, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .delete_role(session.get_user_id())?;
+        let mut roles = self.roles.write().await;
+        let existing_role_id: RoleId = get_role(&roles, role_id)?.id;
+        let role = roles
+            .remove(&existing_role_id)
+            .ok_or(IggyError::ResourceNotFound(role_id.to_string()))?;
+        info!("Deleting role: {} with ID: {}...", role.name, role.id);
+        for user in self.users.write().await.values_mut() {
+            user.roles.retain(|id| *id != existing_role_id);
+        }
+
+        self.permissioner
+            .write()
+            .unwrap()
+            .delete_permissions_for_role(existing_role_id);
+        info!("Deleted role: {} with ID: {}.", role.name, role.id);
+        Ok(role)
+    }
+}
+
+fn get_role<'a>(
+    roles: &'a HashMap<RoleId, Role>,
+    role_id: &Identifier,
+) -> Result<&'a Role, IggyError> {
+    match role_id.kind {
+        IdKind::Numeric => roles
+            .get(&role_id.get_u32_value()?)
+            .ok_or(IggyError::ResourceNotFound(role_id.to_string())),
+        IdKind::String => {
+            let name = role_id.get_cow_str_value()?;
+            roles
+                .values()
+                .find(|role| role.name == name)
+                .ok_or(IggyError::ResourceNotFound(role_id.to_string()))
+        }
+    }
+}
+
+fn get_role_mut<'a>(
+    roles: &'a mut HashMap<RoleId, Role>,
+    role_id: &Identifier,
+) -> Result<&'a mut Role, IggyError> {
+    match role_id.kind {
+        IdKind::Numeric => roles
+            .get_mut(&role_id.get_u32_value()?)
+            .ok_or(IggyError::ResourceNotFound(role_id.to_string())),
+        IdKind::String => {
+            let name = role_id.get_cow_str_value()?;
+            roles
+                .values_mut()
+                .find(|role| role.name == name)
+                .ok_or(IggyError::ResourceNotFound(role_id.to_string()))
+        }
+    }
+}
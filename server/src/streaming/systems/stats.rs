@@ -1,11 +1,14 @@
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::System;
+use crate::versioning::SemanticVersion;
+use iggy::consumer::ConsumerKind;
 use iggy::error::IggyError;
 use iggy::locking::IggySharedMutFn;
 use iggy::models::stats::Stats;
 use std::sync::OnceLock;
 use sysinfo::{Pid, System as SysinfoSystem};
 use tokio::sync::Mutex;
+use tracing::error;
 
 fn sysinfo() -> &'static Mutex<SysinfoSystem> {
     static SYSINFO: OnceLock<Mutex<SysinfoSystem>> = OnceLock::new();
@@ -19,7 +22,10 @@ fn sysinfo() -> &'static Mutex<SysinfoSystem> {
 impl System {
     pub async fn get_stats(&self, session: &Session) -> Result<Stats, IggyError> {
         self.ensure_authenticated(session)?;
-        self.permissioner.get_stats(session.get_user_id())?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .get_stats(session.get_user_id())?;
         self.get_stats_bypass_auth().await
     }
 
@@ -51,6 +57,7 @@ impl System {
             os_name,
             os_version,
             kernel_version,
+            iggy_server_version: SemanticVersion::current()?.to_string(),
             ..Default::default()
         };
 
@@ -72,24 +79,59 @@ impl System {
 
         drop(sys);
 
-        for stream in self.streams.values() {
+        for stream in self.get_streams() {
+            let stream = stream.read().await;
             stats.messages_count += stream.get_messages_count();
             stats.segments_count += stream.get_segments_count();
             stats.messages_size_bytes += stream.get_size();
             stats.streams_count += 1;
             stats.topics_count += stream.topics.len() as u32;
-            stats.partitions_count += stream
-                .topics
-                .values()
-                .map(|t| t.partitions.len() as u32)
-                .sum::<u32>();
-            stats.consumer_groups_count += stream
-                .topics
-                .values()
-                .map(|t| t.consumer_groups.len() as u32)
-                .sum::<u32>();
+            for topic in stream.topics.values() {
+                let topic = topic.read().await;
+                stats.partitions_count += topic.partitions.len() as u32;
+                stats.consumer_groups_count += topic.consumer_groups.len() as u32;
+            }
         }
 
         Ok(stats)
     }
+
+    /// Returns the metrics in the Prometheus text format, the consumer group lags are calculated on each scrape.
+    pub async fn get_metrics(&self) -> String {
+        self.metrics.clear_consumer_group_lags();
+        for stream in self.get_streams() {
+            let stream = stream.read().await;
+            for topic in stream.topics.values() {
+                let topic = topic.read().await;
+                for consumer_group in topic.get_consumer_groups() {
+                    let group_id = consumer_group.read().await.group_id;
+                    for partition in topic.partitions.values() {
+                        let partition = partition.read().await;
+                        let lag = match partition
+                            .get_consumer_lag(ConsumerKind::ConsumerGroup, group_id)
+                            .await
+                        {
+                            Ok(lag) => lag,
+                            Err(error) => {
+                                error!(
+                                    "Failed to get the lag of consumer group with ID: {group_id} for partition with ID: {}, topic with ID: {}, stream with ID: {}. Error: {error}",
+                                    partition.partition_id, topic.topic_id, stream.stream_id
+                                );
+                                continue;
+                            }
+                        };
+                        self.metrics.set_consumer_group_lag(
+                            stream.stream_id,
+                            topic.topic_id,
+                            partition.partition_id,
+                            group_id,
+                            lag.lag,
+                        );
+                    }
+                }
+            }
+        }
+
+        self.metrics.get_formatted_output()
+    }
 }
@@ -5,7 +5,7 @@ use crate::streaming::systems::system::System;
 use futures::future::try_join_all;
 use iggy::error::IggyError;
 use iggy::identifier::{IdKind, Identifier};
-use iggy::locking::IggySharedMutFn;
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
 use iggy::utils::text;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
@@ -96,13 +96,15 @@ impl System {
         });
         try_join_all(load_stream_tasks).await?;
 
+        let streams = self.streams.get_mut().unwrap();
+        let streams_ids = self.streams_ids.get_mut().unwrap();
         for stream in loaded_streams.take() {
-            if self.streams.contains_key(&stream.stream_id) {
+            if streams.contains_key(&stream.stream_id) {
                 error!("Stream with ID: '{}' already exists.", &stream.stream_id);
                 continue;
             }
 
-            if self.streams_ids.contains_key(&stream.name) {
+            if streams_ids.contains_key(&stream.name) {
                 error!("Stream with name: '{}' already exists.", &stream.name);
                 continue;
             }
@@ -110,26 +112,28 @@ impl System {
             self.metrics.increment_streams(1);
             self.metrics.increment_topics(stream.get_topics_count());
             self.metrics
-                .increment_partitions(stream.get_partitions_count());
+                .increment_partitions(stream.get_partitions_count().await);
             self.metrics.increment_segments(stream.get_segments_count());
             self.metrics.increment_messages(stream.get_messages_count());
 
-            self.streams_ids
-                .insert(stream.name.clone(), stream.stream_id);
-            self.streams.insert(stream.stream_id, stream);
+            streams_ids.insert(stream.name.clone(), stream.stream_id);
+            streams.insert(stream.stream_id, IggySharedMut::new(stream));
         }
 
-        info!("Loaded {} stream(s) from disk.", self.streams.len());
+        info!("Loaded {} stream(s) from disk.", streams.len());
         Ok(())
     }
 
-    pub fn get_streams(&self) -> Vec<&Stream> {
-        self.streams.values().collect()
+    pub fn get_streams(&self) -> Vec<IggySharedMut<Stream>> {
+        self.streams.read().unwrap().values().cloned().collect()
     }
 
-    pub fn find_streams(&self, session: &Session) -> Result<Vec<&Stream>, IggyError> {
+    pub fn find_streams(&self, session: &Session) -> Result<Vec<IggySharedMut<Stream>>, IggyError> {
         self.ensure_authenticated(session)?;
-        self.permissioner.get_streams(session.get_user_id())?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .get_streams(session.get_user_id())?;
         Ok(self.get_streams())
     }
 
@@ -137,79 +141,70 @@ impl System {
         &self,
         session: &Session,
         identifier: &Identifier,
-    ) -> Result<&Stream, IggyError> {
+    ) -> Result<IggySharedMut<Stream>, IggyError> {
         self.ensure_authenticated(session)?;
-        let stream = self.get_stream(identifier)?;
+        let stream_id = self.get_stream_id(identifier)?;
         self.permissioner
-            .get_stream(session.get_user_id(), stream.stream_id)?;
-        Ok(stream)
+            .read()
+            .unwrap()
+            .get_stream(session.get_user_id(), stream_id)?;
+        self.get_stream_by_id(stream_id)
     }
 
-    pub fn get_stream(&self, identifier: &Identifier) -> Result<&Stream, IggyError> {
-        match identifier.kind {
-            IdKind::Numeric => self.get_stream_by_id(identifier.get_u32_value()?),
-            IdKind::String => self.get_stream_by_name(&identifier.get_cow_str_value()?),
-        }
+    /// Returns the handle of the stream, which must be locked to access it.
+    /// The streams collection is locked only for the lookup, so the stream can be locked
+    /// exclusively (e.g. to create a topic) while the other streams remain accessible.
+    pub fn get_stream(&self, identifier: &Identifier) -> Result<IggySharedMut<Stream>, IggyError> {
+        self.get_stream_by_id(self.get_stream_id(identifier)?)
     }
 
-    pub fn get_stream_mut(&mut self, identifier: &Identifier) -> Result<&mut Stream, IggyError> {
+    /// Resolves the numeric ID of the stream without locking it.
+    pub fn get_stream_id(&self, identifier: &Identifier) -> Result<u32, IggyError> {
         match identifier.kind {
-            IdKind::Numeric => self.get_stream_by_id_mut(identifier.get_u32_value()?),
-            IdKind::String => self.get_stream_by_name_mut(&identifier.get_cow_str_value()?),
-        }
-    }
-
-    fn get_stream_by_name(&self, name: &str) -> Result<&Stream, IggyError> {
-        let stream_id = self.streams_ids.get(name);
-        if stream_id.is_none() {
-            return Err(IggyError::StreamNameNotFound(name.to_string()));
-        }
-
-        self.get_stream_by_id(*stream_id.unwrap())
-    }
-
-    fn get_stream_by_id(&self, stream_id: u32) -> Result<&Stream, IggyError> {
-        let stream = self.streams.get(&stream_id);
-        if stream.is_none() {
-            return Err(IggyError::StreamIdNotFound(stream_id));
-        }
-
-        Ok(stream.unwrap())
-    }
+            IdKind::Numeric => {
+                let stream_id = identifier.get_u32_value()?;
+                if !self.streams.read().unwrap().contains_key(&stream_id) {
+                    return Err(IggyError::StreamIdNotFound(stream_id));
+                }
 
-    fn get_stream_by_name_mut(&mut self, name: &str) -> Result<&mut Stream, IggyError> {
-        let stream_id;
-        {
-            let id = self.streams_ids.get_mut(name);
-            if id.is_none() {
-                return Err(IggyError::StreamNameNotFound(name.to_string()));
+                Ok(stream_id)
+            }
+            IdKind::String => {
+                let name = identifier.get_cow_str_value()?;
+                self.streams_ids
+                    .read()
+                    .unwrap()
+                    .get(name.as_ref())
+                    .copied()
+                    .ok_or_else(|| IggyError::StreamNameNotFound(name.to_string()))
             }
-
-            stream_id = *id.unwrap();
         }
-
-        self.get_stream_by_id_mut(stream_id)
     }
 
-    fn get_stream_by_id_mut(&mut self, stream_id: u32) -> Result<&mut Stream, IggyError> {
-        let stream = self.streams.get_mut(&stream_id);
-        if stream.is_none() {
-            return Err(IggyError::StreamIdNotFound(stream_id));
-        }
-
-        Ok(stream.unwrap())
+    fn get_stream_by_id(&self, stream_id: u32) -> Result<IggySharedMut<Stream>, IggyError> {
+        self.streams
+            .read()
+            .unwrap()
+            .get(&stream_id)
+            .cloned()
+            .ok_or(IggyError::StreamIdNotFound(stream_id))
     }
 
+    /// Creates the stream and returns its ID, which is assigned if not provided.
     pub async fn create_stream(
-        &mut self,
+        &self,
         session: &Session,
         stream_id: Option<u32>,
         name: &str,
-    ) -> Result<(), IggyError> {
+    ) -> Result<u32, IggyError> {
         self.ensure_authenticated(session)?;
-        self.permissioner.create_stream(session.get_user_id())?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .create_stream(session.get_user_id())?;
         let name = text::to_lowercase_non_whitespace(name);
-        if self.streams_ids.contains_key(&name) {
+        let _streams_changes = self.streams_changes.lock().await;
+        if self.streams_ids.read().unwrap().contains_key(&name) {
             return Err(IggyError::StreamNameAlreadyExists(name.to_string()));
         }
 
@@ -217,7 +212,7 @@ impl System {
         if stream_id.is_none() {
             id = CURRENT_STREAM_ID.fetch_add(1, Ordering::SeqCst);
             loop {
-                if self.streams.contains_key(&id) {
+                if self.streams.read().unwrap().contains_key(&id) {
                     if id == u32::MAX {
                         return Err(IggyError::StreamIdAlreadyExists(id));
                     }
@@ -230,38 +225,39 @@ impl System {
             id = stream_id.unwrap();
         }
 
-        if self.streams.contains_key(&id) {
+        if self.streams.read().unwrap().contains_key(&id) {
             return Err(IggyError::StreamIdAlreadyExists(id));
         }
 
         let stream = Stream::create(id, &name, self.config.clone(), self.storage.clone());
         stream.persist().await?;
         info!("Created stream with ID: {id}, name: '{name}'.");
-        self.streams_ids.insert(name, stream.stream_id);
-        self.streams.insert(stream.stream_id, stream);
+        self.streams
+            .write()
+            .unwrap()
+            .insert(stream.stream_id, IggySharedMut::new(stream));
+        self.streams_ids.write().unwrap().insert(name, id);
         self.metrics.increment_streams(1);
-        Ok(())
+        Ok(id)
     }
 
     pub async fn update_stream(
-        &mut self,
+        &self,
         session: &Session,
         id: &Identifier,
         name: &str,
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
-        let stream_id;
-        {
-            let stream = self.get_stream(id)?;
-            stream_id = stream.stream_id;
-        }
-
+        let _streams_changes = self.streams_changes.lock().await;
+        let stream_id = self.get_stream_id(id)?;
         self.permissioner
+            .read()
+            .unwrap()
             .update_stream(session.get_user_id(), stream_id)?;
         let updated_name = text::to_lowercase_non_whitespace(name);
 
         {
-            if let Some(stream_id_by_name) = self.streams_ids.get(&updated_name) {
+            if let Some(stream_id_by_name) = self.streams_ids.read().unwrap().get(&updated_name) {
                 if *stream_id_by_name != stream_id {
                     return Err(IggyError::StreamNameAlreadyExists(updated_name.clone()));
                 }
@@ -270,15 +266,17 @@ impl System {
 
         let old_name;
         {
-            let stream = self.get_stream_mut(id)?;
+            let stream = self.get_stream_by_id(stream_id)?;
+            let mut stream = stream.write().await;
             old_name = stream.name.clone();
             stream.name.clone_from(&updated_name);
             stream.persist().await?;
         }
 
         {
-            self.streams_ids.remove(&old_name);
-            self.streams_ids.insert(updated_name.clone(), stream_id);
+            let mut streams_ids = self.streams_ids.write().unwrap();
+            streams_ids.remove(&old_name);
+            streams_ids.insert(updated_name.clone(), stream_id);
         }
 
         info!(
@@ -289,15 +287,19 @@ impl System {
     }
 
     pub async fn delete_stream(
-        &mut self,
+        &self,
         session: &Session,
         id: &Identifier,
     ) -> Result<u32, IggyError> {
         self.ensure_authenticated(session)?;
-        let stream = self.get_stream(id)?;
-        let stream_id = stream.stream_id;
+        let _streams_changes = self.streams_changes.lock().await;
+        let stream_id = self.get_stream_id(id)?;
         self.permissioner
+            .read()
+            .unwrap()
             .delete_stream(session.get_user_id(), stream_id)?;
+        let stream = self.get_stream_by_id(stream_id)?;
+        let stream = stream.write().await;
         let stream_name = stream.name.clone();
         if stream.delete().await.is_err() {
             return Err(IggyError::CannotDeleteStream(stream_id));
@@ -306,11 +308,19 @@ impl System {
         self.metrics.decrement_streams(1);
         self.metrics.decrement_topics(stream.get_topics_count());
         self.metrics
-            .decrement_partitions(stream.get_partitions_count());
+            .decrement_partitions(stream.get_partitions_count().await);
         self.metrics.decrement_messages(stream.get_messages_count());
         self.metrics.decrement_segments(stream.get_segments_count());
-        self.streams.remove(&stream_id);
-        self.streams_ids.remove(&stream_name);
+        for topic in stream.get_topics() {
+            let topic = topic.read().await;
+            for &partition_id in topic.partitions.keys() {
+                self.metrics
+                    .remove_partition(stream_id, topic.topic_id, partition_id);
+            }
+        }
+        drop(stream);
+        self.streams_ids.write().unwrap().remove(&stream_name);
+        self.streams.write().unwrap().remove(&stream_id);
         let current_stream_id = CURRENT_STREAM_ID.load(Ordering::SeqCst);
         if current_stream_id > stream_id {
             CURRENT_STREAM_ID.store(stream_id, Ordering::SeqCst);
@@ -329,7 +339,10 @@ impl System {
         stream_id: &Identifier,
     ) -> Result<(), IggyError> {
         let stream = self.get_stream(stream_id)?;
+        let stream = stream.read().await;
         self.permissioner
+            .read()
+            .unwrap()
             .purge_stream(session.get_user_id(), stream.stream_id)?;
         stream.purge().await
     }
@@ -342,9 +355,11 @@ mod tests {
     use crate::configs::system::SystemConfig;
     use crate::state::command::EntryCommand;
     use crate::state::entry::StateEntry;
+    use crate::state::snapshot::StateSnapshot;
     use crate::state::State;
     use crate::streaming::storage::tests::get_test_system_storage;
     use crate::streaming::users::user::User;
+    use crate::streaming::utils::key_ring::EncryptionKeyRing;
     use async_trait::async_trait;
     use iggy::users::defaults::{DEFAULT_ROOT_PASSWORD, DEFAULT_ROOT_USERNAME};
     use std::{
@@ -358,7 +373,7 @@ mod tests {
         let stream_name = "test";
         let config = Arc::new(SystemConfig::default());
         let storage = get_test_system_storage();
-        let mut system = System::create(
+        let system = System::create(
             config,
             storage,
             Arc::new(TestState::default()),
@@ -366,7 +381,7 @@ mod tests {
             DataMaintenanceConfig::default(),
             PersonalAccessTokenConfig::default(),
         );
-        let root = User::root(DEFAULT_ROOT_USERNAME, DEFAULT_ROOT_PASSWORD);
+        let root = User::root(DEFAULT_ROOT_USERNAME, DEFAULT_ROOT_PASSWORD.to_string());
         let permissions = root.permissions.clone();
         let session = Session::new(
             1,
@@ -375,6 +390,8 @@ mod tests {
         );
         system
             .permissioner
+            .write()
+            .unwrap()
             .init_permissions_for_user(root.id, permissions);
         system
             .create_stream(&session, Some(stream_id), stream_name)
@@ -384,12 +401,14 @@ mod tests {
         let stream = system.get_stream(&Identifier::numeric(stream_id).unwrap());
         assert!(stream.is_ok());
         let stream = stream.unwrap();
+        let stream = stream.read().await;
         assert_eq!(stream.stream_id, stream_id);
         assert_eq!(stream.name, stream_name);
 
         let stream = system.get_stream(&Identifier::named(stream_name).unwrap());
         assert!(stream.is_ok());
         let stream = stream.unwrap();
+        let stream = stream.read().await;
         assert_eq!(stream.stream_id, stream_id);
         assert_eq!(stream.name, stream_name);
     }
@@ -407,8 +426,24 @@ mod tests {
             Ok(Vec::new())
         }
 
+        async fn load_snapshot(&self) -> Result<Option<StateSnapshot>, IggyError> {
+            Ok(None)
+        }
+
+        async fn snapshot(&self, _: u64) -> Result<Option<u64>, IggyError> {
+            Ok(None)
+        }
+
+        async fn export_snapshot(&self, _: &str) -> Result<Option<u64>, IggyError> {
+            Ok(None)
+        }
+
         async fn apply(&self, _: u32, _: EntryCommand) -> Result<(), IggyError> {
             Ok(())
         }
+
+        async fn reencrypt(&self, _: &EncryptionKeyRing) -> Result<u64, IggyError> {
+            Ok(0)
+        }
     }
 }
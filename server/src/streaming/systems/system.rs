@@ -1,35 +1,52 @@
 use crate::configs::server::{DataMaintenanceConfig, PersonalAccessTokenConfig};
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+use crate::configs::system::IoBackend;
 use crate::configs::system::SystemConfig;
 use crate::streaming::cache::memory_tracker::CacheMemoryTracker;
 use crate::streaming::clients::client_manager::ClientManager;
-use crate::streaming::diagnostics::metrics::Metrics;
+use crate::streaming::diagnostics::metrics::{Metrics, StorageMetrics};
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+use crate::streaming::persistence::io_uring::{
+    IoUringPersister, IoUringRing, IoUringSegmentReader,
+};
 use crate::streaming::persistence::persister::*;
+use crate::streaming::persistence::reader::{FileSegmentReader, SegmentReader};
 use crate::streaming::session::Session;
 use crate::streaming::storage::SystemStorage;
 use crate::streaming::streams::stream::Stream;
 use crate::streaming::users::permissioner::Permissioner;
 use iggy::error::IggyError;
-use iggy::utils::crypto::{Aes256GcmEncryptor, Encryptor};
+use iggy::utils::crypto::Encryptor;
 use std::collections::HashMap;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock as StdRwLock};
 use tokio::fs::{create_dir, remove_dir_all};
 use tokio::time::Instant;
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+use tracing::warn;
 use tracing::{info, trace};
 
 use crate::archiver::disk::DiskArchiver;
 use crate::archiver::s3::S3Archiver;
 use crate::archiver::{Archiver, ArchiverKind};
+use crate::audit::file::FileAuditLog;
+use crate::audit::AuditLog;
 use crate::state::file::FileState;
 use crate::state::system::SystemState;
 use crate::state::State;
+use crate::streaming::users::login_guard::LoginGuard;
+use crate::streaming::users::password_policy::PasswordPolicy;
+use crate::streaming::users::role::Role;
 use crate::streaming::users::user::User;
+use crate::streaming::utils::crypto::PasswordHasher;
+use crate::streaming::utils::key_ring::EncryptionKeyRing;
 use crate::versioning::SemanticVersion;
 use crate::{compat, map_toggle_str};
 use iggy::locking::IggySharedMut;
 use iggy::locking::IggySharedMutFn;
+use iggy::models::role_info::RoleId;
 use iggy::models::user_info::UserId;
-use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tokio::sync::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 #[derive(Debug)]
 pub struct SharedSystem {
@@ -60,19 +77,34 @@ impl Clone for SharedSystem {
     }
 }
 
+/// The streams, users, roles and permissions are guarded by their own locks, so they can be changed
+/// with the shared access to the system, without blocking the messages being sent and polled meanwhile.
+/// The locks are always taken in the order: state changes -> streams changes -> roles -> users -> permissioner.
 #[derive(Debug)]
 pub struct System {
-    pub permissioner: Permissioner,
+    /// Held only for the duration of the permission check or update, never across an await point.
+    pub permissioner: StdRwLock<Permissioner>,
     pub(crate) storage: Arc<SystemStorage>,
-    pub(crate) streams: HashMap<u32, Stream>,
-    pub(crate) streams_ids: HashMap<String, u32>,
-    pub(crate) users: HashMap<UserId, User>,
+    /// Held only to look up or insert and remove the stream handles, never across an await point.
+    pub(crate) streams: StdRwLock<HashMap<u32, IggySharedMut<Stream>>>,
+    pub(crate) streams_ids: StdRwLock<HashMap<String, u32>>,
+    /// Serializes creating, updating and deleting the streams, which modify both collections above.
+    pub(crate) streams_changes: Mutex<()>,
+    /// Held across the metadata change and applying its state entry,
+    /// so the entries are replayed in the same order as the changes were made.
+    pub(crate) state_changes: Mutex<()>,
+    pub(crate) users: RwLock<HashMap<UserId, User>>,
+    pub(crate) roles: RwLock<HashMap<RoleId, Role>>,
     pub(crate) config: Arc<SystemConfig>,
     pub(crate) client_manager: IggySharedMut<ClientManager>,
-    pub(crate) encryptor: Option<Arc<dyn Encryptor>>,
+    pub(crate) encryptor: Option<Arc<EncryptionKeyRing>>,
     pub(crate) metrics: Metrics,
     pub(crate) state: Arc<dyn State>,
     pub(crate) archiver: Option<Arc<dyn Archiver>>,
+    pub(crate) audit_log: Option<Arc<dyn AuditLog>>,
+    pub(crate) login_guard: LoginGuard,
+    pub(crate) password_policy: PasswordPolicy,
+    pub(crate) password_hasher: PasswordHasher,
     pub personal_access_token: PersonalAccessTokenConfig,
 }
 
@@ -92,26 +124,31 @@ impl System {
             map_toggle_str(config.encryption.enabled)
         );
 
-        let encryptor: Option<Arc<dyn Encryptor>> = match config.encryption.enabled {
-            true => Some(Arc::new(
-                Aes256GcmEncryptor::from_base64_key(&config.encryption.key).unwrap(),
-            )),
+        let encryptor = match config.encryption.enabled {
+            true => {
+                let key_ring = EncryptionKeyRing::from_config(&config.encryption).unwrap();
+                info!(
+                    "Using encryption key with ID: {}.",
+                    key_ring.get_active_key_id()
+                );
+                Some(Arc::new(key_ring))
+            }
             false => None,
         };
 
-        let persister: Arc<dyn Persister> = match config.partition.enforce_fsync {
-            true => Arc::new(FileWithSyncPersister {}),
-            false => Arc::new(FilePersister {}),
-        };
+        let storage_metrics = StorageMetrics::default();
+        let (persister, segment_reader) = Self::create_persistence(&config, &storage_metrics);
         let state = Arc::new(FileState::new(
             &config.get_state_log_path(),
             &version,
             persister.clone(),
-            encryptor.clone(),
+            encryptor
+                .clone()
+                .map(|key_ring| key_ring as Arc<dyn Encryptor>),
         ));
         Self::create(
             config.clone(),
-            SystemStorage::new(config, persister),
+            SystemStorage::new(config, persister, segment_reader, storage_metrics),
             state,
             encryptor,
             data_maintenance_config,
@@ -119,11 +156,51 @@ impl System {
         )
     }
 
+    fn create_persistence(
+        config: &SystemConfig,
+        storage_metrics: &StorageMetrics,
+    ) -> (Arc<dyn Persister>, Arc<dyn SegmentReader>) {
+        info!("Using {} I/O backend.", config.partition.io_backend);
+        match config.partition.io_backend {
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            IoBackend::IoUring => match IoUringRing::new() {
+                Ok(ring) => (
+                    Arc::new(IoUringPersister::new(
+                        ring.clone(),
+                        config.partition.enforce_fsync,
+                        storage_metrics.clone(),
+                    )),
+                    Arc::new(IoUringSegmentReader::new(ring)),
+                ),
+                // The kernel might not support io_uring or it might be blocked (e.g. by the seccomp profile of the container).
+                Err(error) => {
+                    warn!(
+                        "Cannot initialize io_uring, using {} I/O backend instead. Error: {error}",
+                        IoBackend::Tokio
+                    );
+                    Self::create_file_persistence(config, storage_metrics)
+                }
+            },
+            _ => Self::create_file_persistence(config, storage_metrics),
+        }
+    }
+
+    fn create_file_persistence(
+        config: &SystemConfig,
+        storage_metrics: &StorageMetrics,
+    ) -> (Arc<dyn Persister>, Arc<dyn SegmentReader>) {
+        let persister: Arc<dyn Persister> = match config.partition.enforce_fsync {
+            true => Arc::new(FileWithSyncPersister::new(storage_metrics.clone())),
+            false => Arc::new(FilePersister {}),
+        };
+        (persister, Arc::new(FileSegmentReader))
+    }
+
     pub fn create(
         system_config: Arc<SystemConfig>,
         storage: SystemStorage,
         state: Arc<dyn State>,
-        encryptor: Option<Arc<dyn Encryptor>>,
+        encryptor: Option<Arc<EncryptionKeyRing>>,
         data_maintenance_config: DataMaintenanceConfig,
         pat_config: PersonalAccessTokenConfig,
     ) -> System {
@@ -152,19 +229,58 @@ impl System {
             None
         };
 
+        let audit_log: Option<Arc<dyn AuditLog>> = if system_config.audit.enabled {
+            info!(
+                "Audit log is enabled, data commands are {}.",
+                if system_config.audit.include_data_commands {
+                    "included"
+                } else {
+                    "excluded"
+                }
+            );
+            Some(Arc::new(FileAuditLog::new(
+                &system_config.get_audit_path(),
+                system_config.audit.max_file_size,
+                system_config.audit.max_files,
+            )))
+        } else {
+            info!("Audit log is disabled.");
+            None
+        };
+
+        info!(
+            "Login brute-force protection is {}.",
+            map_toggle_str(system_config.login_protection.enabled)
+        );
+        let login_guard = LoginGuard::new(&system_config.login_protection);
+        info!(
+            "Passwords are hashed using {} algorithm.",
+            system_config.password_hashing.algorithm
+        );
+        let password_policy = PasswordPolicy::new(&system_config.password_policy);
+        let password_hasher = PasswordHasher::new(&system_config.password_hashing);
+        let metrics = Metrics::init(&storage.metrics);
+
         System {
             config: system_config,
-            streams: HashMap::new(),
-            streams_ids: HashMap::new(),
+            streams: StdRwLock::new(HashMap::new()),
+            streams_ids: StdRwLock::new(HashMap::new()),
+            streams_changes: Mutex::new(()),
+            state_changes: Mutex::new(()),
             storage: Arc::new(storage),
             encryptor,
             client_manager: IggySharedMut::new(ClientManager::default()),
-            permissioner: Permissioner::default(),
-            metrics: Metrics::init(),
-            users: HashMap::new(),
+            permissioner: StdRwLock::new(Permissioner::default()),
+            metrics,
+            users: RwLock::new(HashMap::new()),
+            roles: RwLock::new(HashMap::new()),
             state,
             personal_access_token: pat_config,
             archiver,
+            audit_log,
+            login_guard,
+            password_policy,
+            password_hasher,
         }
     }
 
@@ -207,10 +323,15 @@ impl System {
             .await?;
         }
 
+        let state_snapshot = self.state.load_snapshot().await?;
         let state_entries = self.state.init().await?;
-        let system_state = SystemState::init(state_entries).await?;
+        let system_state =
+            SystemState::restore(state_snapshot.map(|snapshot| snapshot.state), state_entries)
+                .await?;
         let now = Instant::now();
         self.load_version().await?;
+        self.load_roles(system_state.roles.into_values().collect())
+            .await?;
         self.load_users(system_state.users.into_values().collect())
             .await?;
         self.load_streams(system_state.streams.into_values().collect())
@@ -221,25 +342,69 @@ impl System {
                 .await
                 .expect("Failed to initialize archiver");
         }
+        if let Some(audit_log) = self.audit_log.as_ref() {
+            audit_log.init().await?;
+        }
         info!("Initialized system in {} ms.", now.elapsed().as_millis());
         Ok(())
     }
 
     pub async fn shutdown(&mut self) -> Result<(), IggyError> {
         self.persist_messages().await?;
+        self.flush_consumer_offsets().await?;
         Ok(())
     }
 
     pub async fn persist_messages(&self) -> Result<usize, IggyError> {
         trace!("Saving buffered messages on disk...");
         let mut saved_messages_number = 0;
-        for stream in self.streams.values() {
-            saved_messages_number += stream.persist_messages().await?;
+        for stream in self.get_streams() {
+            saved_messages_number += stream.read().await.persist_messages().await?;
         }
 
         Ok(saved_messages_number)
     }
 
+    /// Appends the buffered consumer offsets of all the partitions to their offsets logs,
+    /// and returns the number of the saved offsets.
+    pub async fn flush_consumer_offsets(&self) -> Result<usize, IggyError> {
+        trace!("Saving buffered consumer offsets on disk...");
+        let mut saved_offsets_number = 0;
+        for stream in self.get_streams() {
+            let stream = stream.read().await;
+            for topic in stream.get_topics() {
+                let topic = topic.read().await;
+                for partition in topic.get_partitions() {
+                    let partition = partition.read().await;
+                    saved_offsets_number += partition.flush_consumer_offsets().await?;
+                }
+            }
+        }
+
+        Ok(saved_offsets_number)
+    }
+
+    /// Compacts the offsets logs of the partitions, which reached the compaction threshold,
+    /// and returns the number of the compacted logs.
+    pub async fn compact_consumer_offsets(&self) -> Result<usize, IggyError> {
+        let mut compacted_logs_number = 0;
+        for stream in self.get_streams() {
+            let stream = stream.read().await;
+            for topic in stream.get_topics() {
+                let topic = topic.read().await;
+                for partition in topic.get_partitions() {
+                    // The write lock prevents storing the offsets while the log is being replaced.
+                    let mut partition = partition.write().await;
+                    if partition.compact_consumer_offsets().await? {
+                        compacted_logs_number += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(compacted_logs_number)
+    }
+
     pub fn ensure_authenticated(&self, session: &Session) -> Result<(), IggyError> {
         match session.is_authenticated() {
             true => Ok(()),
@@ -247,9 +412,13 @@ impl System {
         }
     }
 
+    /// Evicts the cached messages of all the partitions proportionally to their cache size.
+    /// It locks every stream and topic, so it must not be invoked while holding any of them.
     pub async fn clean_cache(&self, size_to_clean: u64) {
-        for stream in self.streams.values() {
+        for stream in self.get_streams() {
+            let stream = stream.read().await;
             for topic in stream.get_topics() {
+                let topic = topic.read().await;
                 for partition in topic.get_partitions().into_iter() {
                     tokio::task::spawn(async move {
                         let memory_tracker = CacheMemoryTracker::get_instance().unwrap();
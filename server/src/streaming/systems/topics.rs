@@ -4,40 +4,49 @@ use crate::streaming::topics::topic::Topic;
 use iggy::compression::compression_algorithm::CompressionAlgorithm;
 use iggy::error::IggyError;
 use iggy::identifier::Identifier;
-use iggy::locking::IggySharedMutFn;
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 
 impl System {
-    pub fn find_topic(
+    pub async fn find_topic(
         &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
-    ) -> Result<&Topic, IggyError> {
+    ) -> Result<IggySharedMut<Topic>, IggyError> {
         self.ensure_authenticated(session)?;
         let stream = self.get_stream(stream_id)?;
+        let stream = stream.read().await;
         let topic = stream.get_topic(topic_id)?;
-        self.permissioner
-            .get_topic(session.get_user_id(), stream.stream_id, topic.topic_id)?;
+        let topic_id = topic.read().await.topic_id;
+        self.permissioner.read().unwrap().get_topic(
+            session.get_user_id(),
+            stream.stream_id,
+            topic_id,
+        )?;
         Ok(topic)
     }
 
-    pub fn find_topics(
+    pub async fn find_topics(
         &self,
         session: &Session,
         stream_id: &Identifier,
-    ) -> Result<Vec<&Topic>, IggyError> {
+    ) -> Result<Vec<IggySharedMut<Topic>>, IggyError> {
         self.ensure_authenticated(session)?;
-        let stream = self.get_stream(stream_id)?;
+        let stream_id = self.get_stream_id(stream_id)?;
         self.permissioner
-            .get_topics(session.get_user_id(), stream.stream_id)?;
-        Ok(stream.get_topics())
+            .read()
+            .unwrap()
+            .get_topics(session.get_user_id(), stream_id)?;
+        let stream = self.get_stream(&Identifier::numeric(stream_id)?)?;
+        let topics = stream.read().await.get_topics();
+        Ok(topics)
     }
 
     #[allow(clippy::too_many_arguments)]
     pub async fn create_topic(
-        &mut self,
+        &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: Option<u32>,
@@ -47,15 +56,15 @@ impl System {
         compression_algorithm: CompressionAlgorithm,
         max_topic_size: MaxTopicSize,
         replication_factor: Option<u8>,
-    ) -> Result<(), IggyError> {
+    ) -> Result<u32, IggyError> {
         self.ensure_authenticated(session)?;
-        {
-            let stream = self.get_stream(stream_id)?;
-            self.permissioner
-                .create_topic(session.get_user_id(), stream.stream_id)?;
-        }
-
-        self.get_stream_mut(stream_id)?
+        let stream = self.get_stream(stream_id)?;
+        let mut stream = stream.write().await;
+        self.permissioner
+            .read()
+            .unwrap()
+            .create_topic(session.get_user_id(), stream.stream_id)?;
+        let topic_id = stream
             .create_topic(
                 topic_id,
                 name,
@@ -69,12 +78,12 @@ impl System {
         self.metrics.increment_topics(1);
         self.metrics.increment_partitions(partitions_count);
         self.metrics.increment_segments(partitions_count);
-        Ok(())
+        Ok(topic_id)
     }
 
     #[allow(clippy::too_many_arguments)]
     pub async fn update_topic(
-        &mut self,
+        &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
@@ -85,17 +94,19 @@ impl System {
         replication_factor: Option<u8>,
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
+        let stream = self.get_stream(stream_id)?;
+        let mut stream = stream.write().await;
         {
-            let stream = self.get_stream(stream_id)?;
             let topic = stream.get_topic(topic_id)?;
-            self.permissioner.update_topic(
+            let topic = topic.read().await;
+            self.permissioner.read().unwrap().update_topic(
                 session.get_user_id(),
                 stream.stream_id,
                 topic.topic_id,
             )?;
         }
 
-        self.get_stream_mut(stream_id)?
+        stream
             .update_topic(
                 topic_id,
                 name,
@@ -113,35 +124,38 @@ impl System {
     }
 
     pub async fn delete_topic(
-        &mut self,
+        &self,
         session: &Session,
         stream_id: &Identifier,
         topic_id: &Identifier,
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
-        let stream_id_value;
+        let stream = self.get_stream(stream_id)?;
+        let mut stream = stream.write().await;
+        let stream_id_value = stream.stream_id;
         {
-            let stream = self.get_stream(stream_id)?;
             let topic = stream.get_topic(topic_id)?;
-            self.permissioner.delete_topic(
+            let topic = topic.read().await;
+            self.permissioner.read().unwrap().delete_topic(
                 session.get_user_id(),
-                stream.stream_id,
+                stream_id_value,
                 topic.topic_id,
             )?;
-            stream_id_value = stream.stream_id;
         }
 
-        let topic = self
-            .get_stream_mut(stream_id)?
-            .delete_topic(topic_id)
-            .await?;
-
+        let topic = stream.delete_topic(topic_id).await?;
+        drop(stream);
+        let topic = topic.read().await;
         self.metrics.decrement_topics(1);
         self.metrics
             .decrement_partitions(topic.get_partitions_count());
         self.metrics.decrement_messages(topic.get_messages_count());
         self.metrics
             .decrement_segments(topic.get_segments_count().await);
+        for &partition_id in topic.partitions.keys() {
+            self.metrics
+                .remove_partition(stream_id_value, topic.topic_id, partition_id);
+        }
         let client_manager = self.client_manager.read().await;
         client_manager
             .delete_consumer_groups_for_topic(stream_id_value, topic.topic_id)
@@ -156,9 +170,14 @@ impl System {
         topic_id: &Identifier,
     ) -> Result<(), IggyError> {
         let stream = self.get_stream(stream_id)?;
+        let stream = stream.read().await;
         let topic = stream.get_topic(topic_id)?;
-        self.permissioner
-            .purge_topic(session.get_user_id(), stream.stream_id, topic.topic_id)?;
+        let topic = topic.read().await;
+        self.permissioner.read().unwrap().purge_topic(
+            session.get_user_id(),
+            stream.stream_id,
+            topic.topic_id,
+        )?;
         topic.purge().await
     }
 }
@@ -1,31 +1,49 @@
 use crate::state::command::EntryCommand;
+use crate::state::models::RehashPassword;
 use crate::state::system::UserState;
 use crate::streaming::personal_access_tokens::personal_access_token::PersonalAccessToken;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::System;
+use crate::streaming::users::role::Role;
 use crate::streaming::users::user::User;
 use crate::streaming::utils::crypto;
+use crate::streaming::utils::crypto::PasswordHasher;
 use iggy::error::IggyError;
 use iggy::identifier::{IdKind, Identifier};
 use iggy::locking::IggySharedMutFn;
 use iggy::models::permissions::Permissions;
+use iggy::models::role_info::RoleId;
+use iggy::models::user_info::UserId;
 use iggy::models::user_status::UserStatus;
 use iggy::users::create_user::CreateUser;
 use iggy::users::defaults::*;
 use iggy::utils::text;
+use std::collections::HashMap;
 use std::env;
+use std::net::IpAddr;
 use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use tokio::sync::RwLockReadGuard;
 use tracing::{error, info, warn};
 
 static USER_ID: AtomicU32 = AtomicU32::new(1);
 const MAX_USERS: usize = u32::MAX as usize;
 
+/// The password change with the new password already hashed, and the current hash used
+/// to detect whether the password has been changed before the new one is applied.
+#[derive(Debug)]
+pub struct PasswordChange {
+    pub user_id: u32,
+    pub current_password_hash: String,
+    pub new_password_hash: String,
+}
+
 impl System {
     pub(crate) async fn load_users(&mut self, users: Vec<UserState>) -> Result<(), IggyError> {
         info!("Loading users...");
         if users.is_empty() {
             info!("No users found, creating the root user...");
-            let root = Self::create_root_user();
+            let root = Self::create_root_user(&self.password_hasher);
             let command = CreateUser {
                 username: root.username.clone(),
                 password: root.password.clone(),
@@ -36,10 +54,11 @@ impl System {
                 .apply(0, EntryCommand::CreateUser(command))
                 .await?;
 
-            self.users.insert(root.id, root);
+            self.users.get_mut().insert(root.id, root);
             info!("Created the root user.");
         }
 
+        let loaded_users = self.users.get_mut();
         for user_state in users.into_iter() {
             let mut user = User::with_password(
                 user_state.id,
@@ -48,6 +67,10 @@ impl System {
                 user_state.status,
                 user_state.permissions,
             );
+            user.roles = user_state.roles;
+            user.password_history = user_state.password_history;
+            user.password_history
+                .truncate(self.password_policy.get_history_size());
 
             user.personal_access_tokens = user_state
                 .personal_access_tokens
@@ -64,20 +87,22 @@ impl System {
                     )
                 })
                 .collect();
-            self.users.insert(user_state.id, user);
+            loaded_users.insert(user_state.id, user);
         }
 
-        let users_count = self.users.len();
-        let current_user_id = self.users.keys().max().unwrap_or(&1);
+        let users_count = loaded_users.len();
+        let current_user_id = loaded_users.keys().max().unwrap_or(&1);
         USER_ID.store(current_user_id + 1, Ordering::SeqCst);
-        self.permissioner
-            .init(&self.users.values().collect::<Vec<&User>>());
+        self.permissioner.get_mut().unwrap().init(
+            &loaded_users.values().collect::<Vec<&User>>(),
+            &self.roles.get_mut().values().collect::<Vec<&Role>>(),
+        );
         self.metrics.increment_users(users_count as u32);
         info!("Initialized {} user(s).", users_count);
         Ok(())
     }
 
-    fn create_root_user() -> User {
+    fn create_root_user(password_hasher: &PasswordHasher) -> User {
         let username = env::var("IGGY_ROOT_USERNAME");
         let password = env::var("IGGY_ROOT_PASSWORD");
         if (username.is_ok() && password.is_err()) || (username.is_err() && password.is_ok()) {
@@ -107,149 +132,181 @@ impl System {
             panic!("Root password is too long.");
         }
 
-        User::root(&username, &password)
+        User::root(&username, password_hasher.hash(&password))
     }
 
-    pub fn find_user(&self, session: &Session, user_id: &Identifier) -> Result<&User, IggyError> {
+    pub async fn find_user(
+        &self,
+        session: &Session,
+        user_id: &Identifier,
+    ) -> Result<RwLockReadGuard<'_, User>, IggyError> {
         self.ensure_authenticated(session)?;
-        let user = self.get_user(user_id)?;
+        let user = self.get_user(user_id).await?;
         let session_user_id = session.get_user_id();
         if user.id != session_user_id {
-            self.permissioner.get_user(session_user_id)?;
+            self.permissioner
+                .read()
+                .unwrap()
+                .get_user(session_user_id)?;
         }
 
         Ok(user)
     }
 
-    pub fn get_user(&self, user_id: &Identifier) -> Result<&User, IggyError> {
-        match user_id.kind {
-            IdKind::Numeric => self
-                .users
-                .get(&user_id.get_u32_value()?)
-                .ok_or(IggyError::ResourceNotFound(user_id.to_string())),
-            IdKind::String => {
-                let username = user_id.get_cow_str_value()?;
-                self.users
-                    .iter()
-                    .find(|(_, user)| user.username == username)
-                    .map(|(_, user)| user)
-                    .ok_or(IggyError::ResourceNotFound(user_id.to_string()))
-            }
-        }
+    /// Returns the user, the users stay locked for reading until the returned guard is dropped.
+    pub async fn get_user(
+        &self,
+        user_id: &Identifier,
+    ) -> Result<RwLockReadGuard<'_, User>, IggyError> {
+        RwLockReadGuard::try_map(self.users.read().await, |users| {
+            get_user(users, user_id).ok()
+        })
+        .map_err(|_| IggyError::ResourceNotFound(user_id.to_string()))
     }
 
-    pub fn get_user_mut(&mut self, user_id: &Identifier) -> Result<&mut User, IggyError> {
-        match user_id.kind {
-            IdKind::Numeric => self
-                .users
-                .get_mut(&user_id.get_u32_value()?)
-                .ok_or(IggyError::ResourceNotFound(user_id.to_string())),
-            IdKind::String => {
-                let username = user_id.get_cow_str_value()?;
-                self.users
-                    .iter_mut()
-                    .find(|(_, user)| user.username == username)
-                    .map(|(_, user)| user)
-                    .ok_or(IggyError::ResourceNotFound(user_id.to_string()))
-            }
-        }
+    pub async fn get_users(
+        &self,
+        session: &Session,
+    ) -> Result<RwLockReadGuard<'_, HashMap<UserId, User>>, IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .get_users(session.get_user_id())?;
+        Ok(self.users.read().await)
     }
 
-    pub async fn get_users(&self, session: &Session) -> Result<Vec<&User>, IggyError> {
+    /// Validates the password of the new user against the password policy and hashes it.
+    /// Hashing is deliberately slow, so it's done without locking the users,
+    /// and the hash is then passed to `create_user`.
+    pub fn hash_new_password(
+        &self,
+        session: &Session,
+        password: &str,
+    ) -> Result<String, IggyError> {
         self.ensure_authenticated(session)?;
-        self.permissioner.get_users(session.get_user_id())?;
-        Ok(self.users.values().collect())
+        self.password_policy.validate(password)?;
+        Ok(self.password_hasher.hash(password))
     }
 
+    /// Creates the user and applies the state entry while the users are still locked,
+    /// as the entry doesn't contain the assigned ID, so the entries must be applied in the order of the IDs.
     pub async fn create_user(
-        &mut self,
+        &self,
         session: &Session,
         username: &str,
-        password: &str,
+        password_hash: &str,
         status: UserStatus,
         permissions: Option<Permissions>,
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
-        self.permissioner.create_user(session.get_user_id())?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .create_user(session.get_user_id())?;
         let username = text::to_lowercase_non_whitespace(username);
-        if self.users.iter().any(|(_, user)| user.username == username) {
+        let mut users = self.users.write().await;
+        if users.iter().any(|(_, user)| user.username == username) {
             error!("User: {username} already exists.");
             return Err(IggyError::UserAlreadyExists);
         }
 
-        if self.users.len() >= MAX_USERS {
+        if users.len() >= MAX_USERS {
             error!("Available users limit reached.");
             return Err(IggyError::UsersLimitReached);
         }
 
         let user_id = USER_ID.fetch_add(1, Ordering::SeqCst);
         info!("Creating user: {username} with ID: {user_id}...");
-        let user = User::new(user_id, &username, password, status, permissions.clone());
+        let user = User::with_password(
+            user_id,
+            &username,
+            password_hash.to_string(),
+            status,
+            permissions.clone(),
+        );
         self.permissioner
-            .init_permissions_for_user(user_id, permissions);
-        self.users.insert(user.id, user);
+            .write()
+            .unwrap()
+            .init_permissions_for_user(user_id, permissions.clone());
+        users.insert(user.id, user);
         info!("Created user: {username} with ID: {user_id}.");
         self.metrics.increment_users(1);
-        Ok(())
+        self.state
+            .apply(
+                session.get_user_id(),
+                EntryCommand::CreateUser(CreateUser {
+                    username,
+                    password: password_hash.to_string(),
+                    status,
+                    permissions,
+                }),
+            )
+            .await
     }
 
     pub async fn delete_user(
-        &mut self,
+        &self,
         session: &Session,
         user_id: &Identifier,
     ) -> Result<User, IggyError> {
         self.ensure_authenticated(session)?;
-        let existing_user_id;
-        let existing_username;
-        {
-            self.permissioner.delete_user(session.get_user_id())?;
-            let user = self.get_user(user_id)?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .delete_user(session.get_user_id())?;
+        let user = {
+            let mut users = self.users.write().await;
+            let user = get_user(&users, user_id)?;
             if user.is_root() {
                 error!("Cannot delete the root user.");
                 return Err(IggyError::CannotDeleteUser(user.id));
             }
 
-            existing_user_id = user.id;
-            existing_username = user.username.clone();
-        }
+            info!("Deleting user: {} with ID: {user_id}...", user.username);
+            let existing_user_id = user.id;
+            let user = users
+                .remove(&existing_user_id)
+                .ok_or(IggyError::ResourceNotFound(user_id.to_string()))?;
+            self.permissioner
+                .write()
+                .unwrap()
+                .delete_permissions_for_user(existing_user_id);
+            user
+        };
 
-        info!("Deleting user: {existing_username} with ID: {user_id}...");
-        let user = self
-            .users
-            .remove(&existing_user_id)
-            .ok_or(IggyError::ResourceNotFound(user_id.to_string()))?;
-        self.permissioner
-            .delete_permissions_for_user(existing_user_id);
         let mut client_manager = self.client_manager.write().await;
-        client_manager
-            .delete_clients_for_user(existing_user_id)
-            .await?;
-        info!("Deleted user: {existing_username} with ID: {user_id}.");
+        client_manager.delete_clients_for_user(user.id).await?;
+        info!("Deleted user: {} with ID: {user_id}.", user.username);
         self.metrics.decrement_users(1);
         Ok(user)
     }
 
     pub async fn update_user(
-        &mut self,
+        &self,
         session: &Session,
         user_id: &Identifier,
         username: Option<String>,
         status: Option<UserStatus>,
-    ) -> Result<&User, IggyError> {
+    ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
-        self.permissioner.update_user(session.get_user_id())?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .update_user(session.get_user_id())?;
 
+        let mut users = self.users.write().await;
         if let Some(username) = username.clone() {
             let username = text::to_lowercase_non_whitespace(&username);
-            let user = self.get_user(user_id)?;
-            let existing_user = self.get_user(&username.clone().try_into()?);
+            let user = get_user(&users, user_id)?;
+            let existing_user = get_user(&users, &username.clone().try_into()?);
             if existing_user.is_ok() && existing_user.unwrap().id != user.id {
                 error!("User: {username} already exists.");
                 return Err(IggyError::UserAlreadyExists);
             }
         }
 
-        let user = self.get_user_mut(user_id)?;
+        let user = get_user_mut(&mut users, user_id)?;
         if let Some(username) = username {
             user.username = username;
         }
@@ -259,155 +316,390 @@ impl System {
         }
 
         info!("Updated user: {} with ID: {}.", user.username, user.id);
-        Ok(user)
+        Ok(())
     }
 
     pub async fn update_permissions(
-        &mut self,
+        &self,
         session: &Session,
         user_id: &Identifier,
         permissions: Option<Permissions>,
     ) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .update_permissions(session.get_user_id())?;
+
+        let mut users = self.users.write().await;
+        let user = get_user_mut(&mut users, user_id)?;
+        if user.is_root() {
+            error!("Cannot change the root user permissions.");
+            return Err(IggyError::CannotChangePermissions(user.id));
+        }
 
-        {
-            self.permissioner
-                .update_permissions(session.get_user_id())?;
-            let user = self.get_user(user_id)?;
-            if user.is_root() {
-                error!("Cannot change the root user permissions.");
-                return Err(IggyError::CannotChangePermissions(user.id));
-            }
+        self.permissioner
+            .write()
+            .unwrap()
+            .update_permissions_for_user(user.id, permissions.clone());
+        user.permissions = permissions;
+        info!(
+            "Updated permissions for user: {} with ID: {user_id}.",
+            user.username
+        );
+        Ok(())
+    }
 
-            self.permissioner
-                .update_permissions_for_user(user.id, permissions.clone());
+    pub async fn update_roles(
+        &self,
+        session: &Session,
+        user_id: &Identifier,
+        roles: Vec<RoleId>,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .update_roles(session.get_user_id())?;
+
+        let mut roles = roles;
+        let existing_roles = self.roles.read().await;
+        let mut users = self.users.write().await;
+        let user = get_user_mut(&mut users, user_id)?;
+        if user.is_root() {
+            error!("Cannot change the root user roles.");
+            return Err(IggyError::CannotChangePermissions(user.id));
         }
 
-        {
-            let user = self.get_user_mut(user_id)?;
-            user.permissions = permissions;
-            info!(
-                "Updated permissions for user: {} with ID: {user_id}.",
-                user.username
-            );
+        if let Some(role_id) = roles.iter().find(|id| !existing_roles.contains_key(id)) {
+            error!("Role with ID: {role_id} was not found.");
+            return Err(IggyError::ResourceNotFound(role_id.to_string()));
         }
 
+        roles.sort_unstable();
+        roles.dedup();
+        self.permissioner
+            .write()
+            .unwrap()
+            .update_roles_for_user(user.id, roles.clone());
+        user.roles = roles;
+        info!(
+            "Updated roles for user: {} with ID: {user_id}.",
+            user.username
+        );
         Ok(())
     }
 
-    pub async fn change_password(
-        &mut self,
+    /// Verifies the current password and the new one against the password policy, and hashes the new password.
+    /// Verifying and hashing are deliberately slow, so they're done without locking the users,
+    /// and the password is then replaced by `change_password`.
+    pub async fn prepare_password_change(
+        &self,
         session: &Session,
         user_id: &Identifier,
         current_password: &str,
         new_password: &str,
-    ) -> Result<(), IggyError> {
+    ) -> Result<PasswordChange, IggyError> {
         self.ensure_authenticated(session)?;
+        let (id, username, password_hash, password_history) = {
+            let user = self.get_user(user_id).await?;
+            (
+                user.id,
+                user.username.clone(),
+                user.password.clone(),
+                user.password_history.clone(),
+            )
+        };
+        let session_user_id = session.get_user_id();
+        if id != session_user_id {
+            self.permissioner
+                .read()
+                .unwrap()
+                .change_password(session_user_id)?;
+        }
 
-        {
-            let user = self.get_user(user_id)?;
-            let session_user_id = session.get_user_id();
-            if user.id != session_user_id {
-                self.permissioner.change_password(session_user_id)?;
-            }
+        if !crypto::verify_password(current_password, &password_hash) {
+            error!("Invalid current password for user: {username} with ID: {user_id}.");
+            return Err(IggyError::InvalidCredentials);
         }
 
-        let user = self.get_user_mut(user_id)?;
-        if !crypto::verify_password(current_password, &user.password) {
+        self.password_policy.validate(new_password)?;
+        self.password_policy
+            .ensure_not_reused(new_password, &password_hash, &password_history)?;
+
+        Ok(PasswordChange {
+            user_id: id,
+            current_password_hash: password_hash,
+            new_password_hash: self.password_hasher.hash(new_password),
+        })
+    }
+
+    /// Replaces the password of the user prepared by `prepare_password_change`.
+    /// Fails if the password has been changed in the meantime.
+    pub async fn change_password(
+        &self,
+        session: &Session,
+        password_change: &PasswordChange,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        let history_size = self.password_policy.get_history_size();
+        let mut users = self.users.write().await;
+        let user = get_user_mut(&mut users, &Identifier::numeric(password_change.user_id)?)?;
+        if user.password != password_change.current_password_hash {
             error!(
-                "Invalid current password for user: {} with ID: {user_id}.",
-                user.username
+                "Password for user: {} with ID: {} has been changed concurrently.",
+                user.username, user.id
             );
             return Err(IggyError::InvalidCredentials);
         }
 
-        user.password = crypto::hash_password(new_password);
+        let previous_password = std::mem::replace(
+            &mut user.password,
+            password_change.new_password_hash.clone(),
+        );
+        if history_size > 0 {
+            user.password_history.insert(0, previous_password);
+            user.password_history.truncate(history_size);
+        }
         info!(
-            "Changed password for user: {} with ID: {user_id}.",
-            user.username
+            "Changed password for user: {} with ID: {}.",
+            user.username, user.id
         );
         Ok(())
     }
 
+    /// Logs in the user and returns its ID.
     pub async fn login_user(
         &self,
         username: &str,
         password: &str,
+        ip_address: IpAddr,
         session: Option<&Session>,
-    ) -> Result<&User, IggyError> {
-        self.login_user_with_credentials(username, Some(password), session)
+    ) -> Result<UserId, IggyError> {
+        self.login_guard
+            .ensure_login_allowed(username, ip_address)?;
+        match self
+            .login_user_with_credentials(username, Some(password), session)
+            .await
+        {
+            Ok(user_id) => {
+                self.login_guard.reset(username);
+                Ok(user_id)
+            }
+            Err(IggyError::InvalidCredentials) => {
+                let user = match username.try_into() {
+                    Ok(user_id) => self.get_user(&user_id).await.ok(),
+                    Err(_) => None,
+                };
+                // The root user is never locked, only the login delay and the limit per IP address apply,
+                // otherwise the failed attempts could lock out the only administrator.
+                let can_lock_user = !user.as_ref().is_some_and(|user| user.is_root());
+                if let Some(locked_until) =
+                    self.login_guard
+                        .record_failed_login(username, ip_address, can_lock_user)
+                {
+                    if let Some(user) = user {
+                        warn!(
+                            "User: {username} with ID: {} has been locked due to too many failed login attempts.",
+                            user.id
+                        );
+                        user.lock(locked_until);
+                    }
+                }
+                Err(IggyError::InvalidCredentials)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Rehashes the password of the user using the currently configured hashing algorithm and parameters,
+    /// returns `None` if the password is already hashed this way.
+    /// The password must be already verified, e.g. by the successful login, so only the hashing is done here,
+    /// without locking the users, and the new hash is then applied by `apply_password_rehash`.
+    pub async fn rehash_password(&self, user_id: UserId, password: &str) -> Option<PasswordChange> {
+        let current_password_hash = {
+            let user = self
+                .get_user(&Identifier::numeric(user_id).ok()?)
+                .await
+                .ok()?;
+            if !self.password_hasher.needs_rehash(&user.password) {
+                return None;
+            }
+
+            user.password.clone()
+        };
+
+        Some(PasswordChange {
+            user_id,
+            current_password_hash,
+            new_password_hash: self.password_hasher.hash(password),
+        })
+    }
+
+    /// Replaces the password hash of the user prepared by `rehash_password`,
+    /// unless the password has been changed in the meantime.
+    pub async fn apply_password_rehash(
+        &self,
+        password_rehash: PasswordChange,
+    ) -> Result<(), IggyError> {
+        let id = password_rehash.user_id;
+        let user_id = Identifier::numeric(id)?;
+        let mut users = self.users.write().await;
+        let user = get_user_mut(&mut users, &user_id)?;
+        if user.password != password_rehash.current_password_hash {
+            return Err(IggyError::InvalidCredentials);
+        }
+
+        user.password = password_rehash.new_password_hash.clone();
+        info!(
+            "Rehashed password for user: {} with ID: {user_id}.",
+            user.username
+        );
+        self.state
+            .apply(
+                id,
+                EntryCommand::RehashPassword(RehashPassword {
+                    user_id: id,
+                    password_hash: password_rehash.new_password_hash,
+                }),
+            )
             .await
     }
 
+    pub fn get_failed_login_delay(&self, username: &str) -> Duration {
+        self.login_guard.get_remaining_delay(username)
+    }
+
+    pub async fn unlock_user(
+        &self,
+        session: &Session,
+        user_id: &Identifier,
+    ) -> Result<(), IggyError> {
+        self.ensure_authenticated(session)?;
+        self.permissioner
+            .read()
+            .unwrap()
+            .unlock_user(session.get_user_id())?;
+        let user = self.get_user(user_id).await?;
+        user.unlock();
+        self.login_guard.reset(&user.username);
+        info!("Unlocked user: {} with ID: {}.", user.username, user.id);
+        Ok(())
+    }
+
+    /// Logs in the user and returns its ID, the password (if provided) is verified without locking the users.
     pub async fn login_user_with_credentials(
         &self,
         username: &str,
         password: Option<&str>,
         session: Option<&Session>,
-    ) -> Result<&User, IggyError> {
-        let user = match self.get_user(&username.try_into()?) {
-            Ok(user) => user,
-            Err(_) => {
-                error!("Cannot login user: {username} (not found).");
-                return Err(IggyError::InvalidCredentials);
+    ) -> Result<UserId, IggyError> {
+        let (user_id, password_hash) = {
+            let user = match self.get_user(&username.try_into()?).await {
+                Ok(user) => user,
+                Err(_) => {
+                    error!("Cannot login user: {username} (not found).");
+                    return Err(IggyError::InvalidCredentials);
+                }
+            };
+
+            info!("Logging in user: {username} with ID: {}...", user.id);
+            if !user.is_active() {
+                warn!("User: {username} with ID: {} is inactive.", user.id);
+                return Err(IggyError::UserInactive);
             }
-        };
 
-        info!("Logging in user: {username} with ID: {}...", user.id);
-        if !user.is_active() {
-            warn!("User: {username} with ID: {} is inactive.", user.id);
-            return Err(IggyError::UserInactive);
-        }
+            if user.is_locked() {
+                warn!("User: {username} with ID: {} is locked.", user.id);
+                return Err(IggyError::UserLocked(user.id));
+            }
+
+            (user.id, user.password.clone())
+        };
 
         if let Some(password) = password {
-            if !crypto::verify_password(password, &user.password) {
-                warn!(
-                    "Invalid password for user: {username} with ID: {}.",
-                    user.id
-                );
+            if !crypto::verify_password(password, &password_hash) {
+                warn!("Invalid password for user: {username} with ID: {user_id}.");
                 return Err(IggyError::InvalidCredentials);
             }
         }
 
-        info!("Logged in user: {username} with ID: {}.", user.id);
+        info!("Logged in user: {username} with ID: {user_id}.");
         if session.is_none() {
-            return Ok(user);
+            return Ok(user_id);
         }
 
         let session = session.unwrap();
         if session.is_authenticated() {
             warn!(
-                "User: {} with ID: {} was already authenticated, removing the previous session...",
-                user.username,
+                "User: {username} with ID: {} was already authenticated, removing the previous session...",
                 session.get_user_id()
             );
             self.logout_user(session).await?;
         }
 
-        session.set_user_id(user.id);
+        session.set_user_id(user_id);
         let mut client_manager = self.client_manager.write().await;
         client_manager
-            .set_user_id(session.client_id, user.id)
+            .set_user_id(session.client_id, user_id)
             .await?;
-        Ok(user)
+        Ok(user_id)
     }
 
     pub async fn logout_user(&self, session: &Session) -> Result<(), IggyError> {
         self.ensure_authenticated(session)?;
-        let user = self.get_user(&Identifier::numeric(session.get_user_id())?)?;
-        info!(
-            "Logging out user: {} with ID: {}...",
-            user.username, user.id
-        );
+        let (user_id, username) = {
+            let user = self
+                .get_user(&Identifier::numeric(session.get_user_id())?)
+                .await?;
+            (user.id, user.username.clone())
+        };
+        info!("Logging out user: {username} with ID: {user_id}...");
         if session.client_id > 0 {
             let mut client_manager = self.client_manager.write().await;
             client_manager.clear_user_id(session.client_id).await?;
             info!(
-                "Cleared user ID: {} for client: {}.",
-                user.id, session.client_id
+                "Cleared user ID: {user_id} for client: {}.",
+                session.client_id
             );
         }
-        info!("Logged out user: {} with ID: {}.", user.username, user.id);
+        info!("Logged out user: {username} with ID: {user_id}.");
         Ok(())
     }
 }
+
+pub(crate) fn get_user<'a>(
+    users: &'a HashMap<UserId, User>,
+    user_id: &Identifier,
+) -> Result<&'a User, IggyError> {
+    match user_id.kind {
+        IdKind::Numeric => users
+            .get(&user_id.get_u32_value()?)
+            .ok_or(IggyError::ResourceNotFound(user_id.to_string())),
+        IdKind::String => {
+            let username = user_id.get_cow_str_value()?;
+            users
+                .values()
+                .find(|user| user.username == username)
+                .ok_or(IggyError::ResourceNotFound(user_id.to_string()))
+        }
+    }
+}
+
+pub(crate) fn get_user_mut<'a>(
+    users: &'a mut HashMap<UserId, User>,
+    user_id: &Identifier,
+) -> Result<&'a mut User, IggyError> {
+    match user_id.kind {
+        IdKind::Numeric => users
+            .get_mut(&user_id.get_u32_value()?)
+            .ok_or(IggyError::ResourceNotFound(user_id.to_string())),
+        IdKind::String => {
+            let username = user_id.get_cow_str_value()?;
+            users
+                .values_mut()
+                .find(|user| user.username == username)
+                .ok_or(IggyError::ResourceNotFound(user_id.to_string()))
+        }
+    }
+}
@@ -2,10 +2,9 @@ use crate::streaming::topics::consumer_group::ConsumerGroup;
 use crate::streaming::topics::topic::Topic;
 use iggy::error::IggyError;
 use iggy::identifier::{IdKind, Identifier};
-use iggy::locking::IggySharedMutFn;
+use iggy::locking::{IggySharedMut, IggySharedMutFn};
 use iggy::utils::text;
 use std::sync::atomic::Ordering;
-use tokio::sync::RwLock;
 use tracing::info;
 
 impl Topic {
@@ -19,20 +18,20 @@ impl Topic {
             "Reassigning consumer groups for topic with ID: {} for stream with ID with {}, partitions count: {}",
             self.topic_id, self.stream_id, partitions_count
         );
-        for (_, consumer_group) in self.consumer_groups.iter_mut() {
+        for consumer_group in self.consumer_groups.values() {
             let mut consumer_group = consumer_group.write().await;
             consumer_group.reassign_partitions(partitions_count).await;
         }
     }
 
-    pub fn get_consumer_groups(&self) -> Vec<&RwLock<ConsumerGroup>> {
-        self.consumer_groups.values().collect()
+    pub fn get_consumer_groups(&self) -> Vec<IggySharedMut<ConsumerGroup>> {
+        self.consumer_groups.values().cloned().collect()
     }
 
     pub fn get_consumer_group(
         &self,
         identifier: &Identifier,
-    ) -> Result<&RwLock<ConsumerGroup>, IggyError> {
+    ) -> Result<IggySharedMut<ConsumerGroup>, IggyError> {
         match identifier.kind {
             IdKind::Numeric => self.get_consumer_group_by_id(identifier.get_u32_value().unwrap()),
             IdKind::String => self.get_consumer_group_by_name(&identifier.get_cow_str_value()?),
@@ -42,7 +41,7 @@ impl Topic {
     pub fn get_consumer_group_by_name(
         &self,
         name: &str,
-    ) -> Result<&RwLock<ConsumerGroup>, IggyError> {
+    ) -> Result<IggySharedMut<ConsumerGroup>, IggyError> {
         let group_id = self.consumer_groups_ids.get(name);
         if group_id.is_none() {
             return Err(IggyError::ConsumerGroupNameNotFound(
@@ -54,20 +53,23 @@ impl Topic {
         self.get_consumer_group_by_id(*group_id.unwrap())
     }
 
-    pub fn get_consumer_group_by_id(&self, id: u32) -> Result<&RwLock<ConsumerGroup>, IggyError> {
+    pub fn get_consumer_group_by_id(
+        &self,
+        id: u32,
+    ) -> Result<IggySharedMut<ConsumerGroup>, IggyError> {
         let consumer_group = self.consumer_groups.get(&id);
         if consumer_group.is_none() {
             return Err(IggyError::ConsumerGroupIdNotFound(id, self.topic_id));
         }
 
-        Ok(consumer_group.unwrap())
+        Ok(consumer_group.unwrap().clone())
     }
 
     pub async fn create_consumer_group(
         &mut self,
         group_id: Option<u32>,
         name: &str,
-    ) -> Result<&RwLock<ConsumerGroup>, IggyError> {
+    ) -> Result<IggySharedMut<ConsumerGroup>, IggyError> {
         let name = text::to_lowercase_non_whitespace(name);
         if self.consumer_groups_ids.contains_key(&name) {
             return Err(IggyError::ConsumerGroupNameAlreadyExists(
@@ -103,7 +105,8 @@ impl Topic {
 
         let consumer_group =
             ConsumerGroup::new(self.topic_id, id, &name, self.partitions.len() as u32);
-        self.consumer_groups.insert(id, RwLock::new(consumer_group));
+        self.consumer_groups
+            .insert(id, IggySharedMut::new(consumer_group));
         self.consumer_groups_ids.insert(name, id);
         let consumer_group = self.get_consumer_group_by_id(id)?;
         info!(
@@ -116,7 +119,7 @@ impl Topic {
     pub async fn delete_consumer_group(
         &mut self,
         id: &Identifier,
-    ) -> Result<RwLock<ConsumerGroup>, IggyError> {
+    ) -> Result<IggySharedMut<ConsumerGroup>, IggyError> {
         let group_id;
         {
             let consumer_group = self.get_consumer_group(id)?;
@@ -141,12 +144,7 @@ impl Topic {
 
             for (_, partition) in self.partitions.iter() {
                 let partition = partition.read().await;
-                if let Some((_, offset)) = partition.consumer_group_offsets.remove(&group_id) {
-                    self.storage
-                        .partition
-                        .delete_consumer_offset(&offset.path)
-                        .await?;
-                }
+                partition.delete_consumer_group_offset(group_id).await?;
             }
 
             info!(
@@ -209,7 +207,8 @@ mod tests {
         let result = topic.create_consumer_group(Some(group_id), name).await;
         assert!(result.is_ok());
         {
-            let created_consumer_group = result.unwrap().read().await;
+            let created_consumer_group = result.unwrap();
+            let created_consumer_group = created_consumer_group.read().await;
             assert_eq!(created_consumer_group.group_id, group_id);
             assert_eq!(created_consumer_group.name, name);
             assert_eq!(created_consumer_group.topic_id, topic_id);
@@ -310,9 +309,8 @@ mod tests {
         assert!(result.is_ok());
         let consumer_group = topic
             .get_consumer_group(&Identifier::numeric(group_id).unwrap())
-            .unwrap()
-            .read()
-            .await;
+            .unwrap();
+        let consumer_group = consumer_group.read().await;
         let members = consumer_group.get_members();
         assert_eq!(members.len(), 1);
     }
@@ -337,9 +335,8 @@ mod tests {
         assert!(result.is_ok());
         let consumer_group = topic
             .get_consumer_group(&Identifier::numeric(group_id).unwrap())
-            .unwrap()
-            .read()
-            .await;
+            .unwrap();
+        let consumer_group = consumer_group.read().await;
         let members = consumer_group.get_members();
         assert!(members.is_empty())
     }
@@ -1,9 +1,11 @@
 use crate::streaming::partitions::partition::Partition;
 use crate::streaming::polling_consumer::PollingConsumer;
 use crate::streaming::topics::topic::Topic;
+use iggy::consumer::ConsumerKind;
 use iggy::error::IggyError;
 use iggy::locking::IggySharedMut;
 use iggy::locking::IggySharedMutFn;
+use iggy::models::consumer_lag_info::ConsumerLagInfo;
 use iggy::models::consumer_offset_info::ConsumerOffsetInfo;
 
 impl Topic {
@@ -31,6 +33,35 @@ impl Topic {
         })
     }
 
+    /// Returns the lag of the consumer or consumer group for the given partition, or for all the partitions if not specified.
+    pub async fn get_consumer_lag(
+        &self,
+        kind: ConsumerKind,
+        consumer_id: u32,
+        partition_id: Option<u32>,
+    ) -> Result<Vec<ConsumerLagInfo>, IggyError> {
+        if let Some(partition_id) = partition_id {
+            let partition =
+                self.partitions
+                    .get(&partition_id)
+                    .ok_or(IggyError::PartitionNotFound(
+                        partition_id,
+                        self.topic_id,
+                        self.stream_id,
+                    ))?;
+            let partition = partition.read().await;
+            return Ok(vec![partition.get_consumer_lag(kind, consumer_id).await?]);
+        }
+
+        let mut lags = Vec::with_capacity(self.partitions.len());
+        for partition in self.partitions.values() {
+            let partition = partition.read().await;
+            lags.push(partition.get_consumer_lag(kind, consumer_id).await?);
+        }
+        lags.sort_by_key(|lag| lag.partition_id);
+        Ok(lags)
+    }
+
     async fn resolve_partition(
         &self,
         consumer: PollingConsumer,
@@ -38,7 +69,8 @@ impl Topic {
         let partition_id = match consumer {
             PollingConsumer::Consumer(_, partition_id) => Ok(partition_id),
             PollingConsumer::ConsumerGroup(group_id, member_id) => {
-                let consumer_group = self.get_consumer_group_by_id(group_id)?.read().await;
+                let consumer_group = self.get_consumer_group_by_id(group_id)?;
+                let consumer_group = consumer_group.read().await;
                 consumer_group.get_current_partition_id(member_id).await
             }
         }?;
@@ -1,5 +1,6 @@
 use crate::streaming::batching::appendable_batch_info::AppendableBatchInfo;
 use crate::streaming::batching::message_batch::RetainedMessageBatch;
+use crate::streaming::batching::polled_batches::PolledBatches;
 use crate::streaming::polling_consumer::PollingConsumer;
 use crate::streaming::sizeable::Sizeable;
 use crate::streaming::topics::topic::Topic;
@@ -68,12 +69,49 @@ impl Topic {
         })
     }
 
+    /// Returns the polled messages as the whole batches (see `PolledBatches`).
+    pub async fn get_batches(
+        &self,
+        consumer: PollingConsumer,
+        partition_id: u32,
+        strategy: PollingStrategy,
+        count: u32,
+    ) -> Result<PolledBatches, IggyError> {
+        if !self.has_partitions() {
+            return Err(IggyError::NoPartitions(self.topic_id, self.stream_id));
+        }
+
+        let partition = self
+            .partitions
+            .get(&partition_id)
+            .ok_or(IggyError::PartitionNotFound(
+                partition_id,
+                self.topic_id,
+                self.stream_id,
+            ))?;
+        let partition = partition.read().await;
+        let value = strategy.value;
+        match strategy.kind {
+            PollingKind::Offset => partition.get_batches_by_offset(value, count).await,
+            PollingKind::Timestamp => {
+                partition
+                    .get_batches_by_timestamp(value.into(), count)
+                    .await
+            }
+            PollingKind::First => partition.get_first_batches(count).await,
+            PollingKind::Last => partition.get_last_batches(count).await,
+            PollingKind::Next => partition.get_next_batches(consumer, count).await,
+        }
+    }
+
+    /// Appends the messages to the partition selected by the partitioning, returns the ID of that partition,
+    /// or `None` if there are no messages to append.
     pub async fn append_messages(
         &self,
         batch_size: u64,
         partitioning: Partitioning,
         messages: Vec<Message>,
-    ) -> Result<(), IggyError> {
+    ) -> Result<Option<u32>, IggyError> {
         if !self.has_partitions() {
             return Err(IggyError::NoPartitions(self.topic_id, self.stream_id));
         }
@@ -82,8 +120,9 @@ impl Topic {
             return Err(IggyError::TopicFull(self.topic_id, self.stream_id));
         }
 
+        // The empty batch shouldn't advance the round-robin of the balanced partitioning.
         if messages.is_empty() {
-            return Ok(());
+            return Ok(None);
         }
 
         let partition_id = match partitioning.kind {
@@ -98,7 +137,8 @@ impl Topic {
 
         let appendable_batch_info = AppendableBatchInfo::new(batch_size, partition_id);
         self.append_messages_to_partition(appendable_batch_info, messages)
-            .await
+            .await?;
+        Ok(Some(partition_id))
     }
 
     async fn append_messages_to_partition(
@@ -306,6 +346,28 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn given_no_messages_balanced_partitioning_should_not_advance_to_next_partition() {
+        let topic = init_topic(3);
+        let partitioning = Partitioning::balanced();
+
+        let partition_id = topic
+            .append_messages(0, partitioning.clone(), Vec::new())
+            .await
+            .unwrap();
+        assert!(partition_id.is_none());
+
+        for expected_partition_id in 1..=2 {
+            let messages = vec![Message::new(None, Bytes::new(), None)];
+            let batch_size = messages.iter().map(|msg| msg.get_size_bytes() as u64).sum();
+            let partition_id = topic
+                .append_messages(batch_size, partitioning.clone(), messages)
+                .await
+                .unwrap();
+            assert_eq!(partition_id, Some(expected_partition_id));
+        }
+    }
+
     #[tokio::test]
     async fn given_messages_key_key_messages_should_be_appended_to_the_calculated_partitions() {
         let partitions_count = 3;
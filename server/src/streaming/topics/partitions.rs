@@ -76,7 +76,9 @@ impl Topic {
 
         let mut segments_count = 0;
         let mut messages_count = 0;
-        for partition_id in current_partitions_count - count + 1..=current_partitions_count {
+        let partition_ids =
+            (current_partitions_count - count + 1..=current_partitions_count).collect::<Vec<_>>();
+        for &partition_id in &partition_ids {
             let partition = self.partitions.remove(&partition_id).unwrap();
             let partition = partition.read().await;
             let partition_messages_count = partition.get_messages_count();
@@ -85,6 +87,7 @@ impl Topic {
             partition.delete().await?;
         }
         Ok(Some(DeletedPartitions {
+            partition_ids,
             segments_count,
             messages_count,
         }))
@@ -92,6 +95,7 @@ impl Topic {
 }
 
 pub struct DeletedPartitions {
+    pub partition_ids: Vec<u32>,
     pub segments_count: u32,
     pub messages_count: u64,
 }
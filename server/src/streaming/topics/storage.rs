@@ -15,7 +15,7 @@ use std::path::Path;
 use std::sync::Arc;
 use tokio::fs;
 use tokio::fs::create_dir;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::Mutex;
 use tracing::{error, info, warn};
 
 #[derive(Debug)]
@@ -53,7 +53,7 @@ impl TopicStorage for FileTopicStorage {
             );
             topic
                 .consumer_groups
-                .insert(consumer_group.group_id, RwLock::new(consumer_group));
+                .insert(consumer_group.group_id, IggySharedMut::new(consumer_group));
         }
 
         let dir_entries = fs::read_dir(&topic.partitions_path).await
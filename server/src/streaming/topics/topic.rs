@@ -13,7 +13,6 @@ use iggy::utils::topic_size::MaxTopicSize;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
 
 const ALMOST_FULL_THRESHOLD: f64 = 0.9;
 
@@ -32,7 +31,7 @@ pub struct Topic {
     pub(crate) config: Arc<SystemConfig>,
     pub(crate) partitions: HashMap<u32, IggySharedMut<Partition>>,
     pub(crate) storage: Arc<SystemStorage>,
-    pub(crate) consumer_groups: HashMap<u32, RwLock<ConsumerGroup>>,
+    pub(crate) consumer_groups: HashMap<u32, IggySharedMut<ConsumerGroup>>,
     pub(crate) consumer_groups_ids: HashMap<String, u32>,
     pub(crate) current_consumer_group_id: AtomicU32,
     pub(crate) current_partition_id: AtomicU32,
@@ -0,0 +1,333 @@
+use crate::configs::system::LoginProtectionConfig;
+use iggy::error::IggyError;
+use iggy::utils::timestamp::IggyTimestamp;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::warn;
+
+/// The number of tracked usernames or IP addresses above which the stale entries are removed.
+const MAX_TRACKED_ENTRIES: usize = 10_000;
+
+#[derive(Debug, Default)]
+struct FailedLoginAttempts {
+    count: u32,
+    last_attempt_at: u64,
+    next_attempt_at: u64,
+    locked_until: u64,
+}
+
+/// `LoginGuard` tracks the failed login attempts per username and per client IP address.
+/// Each failed attempt for the username requires the exponentially growing delay before the next one is accepted,
+/// and once the configured limit is reached, the username or the IP address is locked out temporarily.
+/// The usernames which can't be locked (e.g. root) are only delayed, so the account can't be locked out by the attacker.
+#[derive(Debug)]
+pub struct LoginGuard {
+    enabled: bool,
+    max_failed_attempts: u32,
+    max_failed_attempts_per_ip: u32,
+    base_delay: u64,
+    max_delay: u64,
+    lockout_duration: u64,
+    usernames: Mutex<HashMap<String, FailedLoginAttempts>>,
+    ip_addresses: Mutex<HashMap<IpAddr, FailedLoginAttempts>>,
+}
+
+impl LoginGuard {
+    pub fn new(config: &LoginProtectionConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            max_failed_attempts: config.max_failed_attempts,
+            max_failed_attempts_per_ip: config.max_failed_attempts_per_ip,
+            base_delay: config.base_delay.as_micros(),
+            max_delay: config.max_delay.as_micros(),
+            lockout_duration: config.lockout_duration.as_micros(),
+            usernames: Mutex::new(HashMap::new()),
+            ip_addresses: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns an error if the login attempt for the given username from the given IP address
+    /// was made too early after the previous failed one or the IP address is locked out.
+    pub fn ensure_login_allowed(
+        &self,
+        username: &str,
+        ip_address: IpAddr,
+    ) -> Result<(), IggyError> {
+        self.ensure_login_allowed_at(username, ip_address, IggyTimestamp::now().as_micros())
+    }
+
+    /// Records the failed login attempt and returns the timestamp until which the user should be locked,
+    /// if the user can be locked and the maximum number of failed attempts for the given username has been reached.
+    pub fn record_failed_login(
+        &self,
+        username: &str,
+        ip_address: IpAddr,
+        can_lock_user: bool,
+    ) -> Option<u64> {
+        self.record_failed_login_at(
+            username,
+            ip_address,
+            can_lock_user,
+            IggyTimestamp::now().as_micros(),
+        )
+    }
+
+    /// Returns the remaining delay before the next login attempt for the given username is accepted.
+    pub fn get_remaining_delay(&self, username: &str) -> Duration {
+        self.get_remaining_delay_at(username, IggyTimestamp::now().as_micros())
+    }
+
+    /// Resets the failed login attempts for the given username.
+    pub fn reset(&self, username: &str) {
+        if !self.enabled {
+            return;
+        }
+
+        self.usernames.lock().unwrap().remove(username);
+    }
+
+    fn ensure_login_allowed_at(
+        &self,
+        username: &str,
+        ip_address: IpAddr,
+        now: u64,
+    ) -> Result<(), IggyError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if let Some(attempts) = self.ip_addresses.lock().unwrap().get(&ip_address) {
+            if attempts.locked_until > now {
+                warn!("Login attempt from IP address: {ip_address} was rejected, too many failed login attempts.");
+                return Err(IggyError::TooManyLoginAttempts);
+            }
+        }
+
+        if let Some(attempts) = self.usernames.lock().unwrap().get(username) {
+            if attempts.next_attempt_at > now {
+                warn!("Login attempt for user: {username} was rejected, too many failed login attempts.");
+                return Err(IggyError::TooManyLoginAttempts);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_remaining_delay_at(&self, username: &str, now: u64) -> Duration {
+        if !self.enabled {
+            return Duration::ZERO;
+        }
+
+        match self.usernames.lock().unwrap().get(username) {
+            Some(attempts) => Duration::from_micros(attempts.next_attempt_at.saturating_sub(now)),
+            None => Duration::ZERO,
+        }
+    }
+
+    fn record_failed_login_at(
+        &self,
+        username: &str,
+        ip_address: IpAddr,
+        can_lock_user: bool,
+        now: u64,
+    ) -> Option<u64> {
+        if !self.enabled {
+            return None;
+        }
+
+        let mut ip_addresses = self.ip_addresses.lock().unwrap();
+        if let Some(locked_until) = self.record_failed_attempt(
+            &mut ip_addresses,
+            ip_address,
+            self.max_failed_attempts_per_ip,
+            now,
+        ) {
+            ip_addresses.get_mut(&ip_address).unwrap().locked_until = locked_until;
+            warn!("IP address: {ip_address} has been locked out due to too many failed login attempts.");
+        }
+        drop(ip_addresses);
+
+        let max_failed_attempts = if can_lock_user {
+            self.max_failed_attempts
+        } else {
+            u32::MAX
+        };
+        let mut usernames = self.usernames.lock().unwrap();
+        self.record_failed_attempt(
+            &mut usernames,
+            username.to_string(),
+            max_failed_attempts,
+            now,
+        )
+    }
+
+    fn record_failed_attempt<K: Eq + Hash>(
+        &self,
+        entries: &mut HashMap<K, FailedLoginAttempts>,
+        key: K,
+        max_failed_attempts: u32,
+        now: u64,
+    ) -> Option<u64> {
+        if entries.len() >= MAX_TRACKED_ENTRIES {
+            entries.retain(|_, attempts| !self.is_stale(attempts, now));
+        }
+
+        let attempts = entries.entry(key).or_default();
+        if self.is_stale(attempts, now) {
+            *attempts = FailedLoginAttempts::default();
+        }
+
+        attempts.count += 1;
+        attempts.last_attempt_at = now;
+        if attempts.count >= max_failed_attempts {
+            attempts.count = 0;
+            attempts.next_attempt_at = 0;
+            return Some(now + self.lockout_duration);
+        }
+
+        attempts.next_attempt_at = now + self.get_delay(attempts.count);
+        None
+    }
+
+    /// The delay is doubled after each failed attempt, starting from the base delay, up to the maximum delay.
+    fn get_delay(&self, failed_attempts: u32) -> u64 {
+        let multiplier = 1u64 << (failed_attempts.saturating_sub(1)).min(32);
+        self.base_delay
+            .saturating_mul(multiplier)
+            .min(self.max_delay)
+    }
+
+    fn is_stale(&self, attempts: &FailedLoginAttempts, now: u64) -> bool {
+        attempts.locked_until <= now && attempts.last_attempt_at + self.lockout_duration <= now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iggy::utils::duration::IggyDuration;
+    use std::net::Ipv4Addr;
+    use std::str::FromStr;
+
+    const SECOND: u64 = 1_000_000;
+
+    fn create_guard(max_failed_attempts: u32, max_failed_attempts_per_ip: u32) -> LoginGuard {
+        LoginGuard::new(&LoginProtectionConfig {
+            enabled: true,
+            max_failed_attempts,
+            max_failed_attempts_per_ip,
+            base_delay: IggyDuration::from_str("1s").unwrap(),
+            max_delay: IggyDuration::from_str("4s").unwrap(),
+            lockout_duration: IggyDuration::from_str("60s").unwrap(),
+        })
+    }
+
+    #[test]
+    fn login_attempts_should_be_delayed_exponentially_after_failures() {
+        let guard = create_guard(10, 100);
+        let ip_address = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let now = 100 * SECOND;
+        assert!(guard
+            .ensure_login_allowed_at("user", ip_address, now)
+            .is_ok());
+
+        assert!(guard
+            .record_failed_login_at("user", ip_address, true, now)
+            .is_none());
+        assert!(guard
+            .ensure_login_allowed_at("user", ip_address, now)
+            .is_err());
+        assert!(guard
+            .ensure_login_allowed_at("other", ip_address, now)
+            .is_ok());
+        assert_eq!(
+            guard.get_remaining_delay_at("user", now),
+            Duration::from_secs(1)
+        );
+        assert!(guard
+            .ensure_login_allowed_at("user", ip_address, now + SECOND)
+            .is_ok());
+
+        let now = now + SECOND;
+        assert!(guard
+            .record_failed_login_at("user", ip_address, true, now)
+            .is_none());
+        assert!(guard
+            .ensure_login_allowed_at("user", ip_address, now + SECOND)
+            .is_err());
+        assert!(guard
+            .ensure_login_allowed_at("user", ip_address, now + 2 * SECOND)
+            .is_ok());
+        assert_eq!(guard.get_delay(3), 4 * SECOND);
+        assert_eq!(guard.get_delay(10), 4 * SECOND);
+    }
+
+    #[test]
+    fn user_should_be_locked_after_reaching_max_failed_attempts() {
+        let guard = create_guard(3, 100);
+        let ip_address = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let mut now = 100 * SECOND;
+        for _ in 0..2 {
+            assert!(guard
+                .record_failed_login_at("user", ip_address, true, now)
+                .is_none());
+            now += 10 * SECOND;
+        }
+
+        let locked_until = guard.record_failed_login_at("user", ip_address, true, now);
+        assert_eq!(locked_until, Some(now + 60 * SECOND));
+
+        guard.reset("user");
+        assert!(guard
+            .ensure_login_allowed_at("user", ip_address, now)
+            .is_ok());
+    }
+
+    #[test]
+    fn ip_address_should_be_locked_out_after_reaching_max_failed_attempts() {
+        let guard = create_guard(100, 3);
+        let ip_address = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let mut now = 100 * SECOND;
+        for index in 0..3 {
+            guard.record_failed_login_at(&format!("user{index}"), ip_address, true, now);
+            now += 10 * SECOND;
+        }
+
+        assert!(guard
+            .ensure_login_allowed_at("other", ip_address, now)
+            .is_err());
+        assert!(guard
+            .ensure_login_allowed_at("other", IpAddr::V4(Ipv4Addr::UNSPECIFIED), now)
+            .is_ok());
+        assert!(guard
+            .ensure_login_allowed_at("other", ip_address, now + 60 * SECOND)
+            .is_ok());
+    }
+
+    #[test]
+    fn user_which_cannot_be_locked_should_only_be_delayed() {
+        let guard = create_guard(3, 100);
+        let ip_address = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let mut now = 100 * SECOND;
+        for _ in 0..10 {
+            assert!(guard
+                .record_failed_login_at("root", ip_address, false, now)
+                .is_none());
+            assert!(guard
+                .ensure_login_allowed_at("root", ip_address, now)
+                .is_err());
+            now += 10 * SECOND;
+        }
+
+        assert_eq!(
+            guard.get_remaining_delay_at("root", now - 10 * SECOND),
+            Duration::from_secs(4)
+        );
+        assert!(guard
+            .ensure_login_allowed_at("root", ip_address, now)
+            .is_ok());
+    }
+}
@@ -1,3 +1,6 @@
+pub mod login_guard;
+pub mod password_policy;
 pub mod permissioner;
 pub mod permissioner_rules;
+pub mod role;
 pub mod user;
@@ -0,0 +1,166 @@
+use crate::configs::system::PasswordPolicyConfig;
+use crate::streaming::utils::crypto;
+use iggy::error::IggyError;
+use tracing::warn;
+
+/// `PasswordPolicy` validates the new passwords against the configured requirements
+/// and ensures that the recently used passwords are not reused.
+#[derive(Debug)]
+pub struct PasswordPolicy {
+    min_length: usize,
+    require_uppercase: bool,
+    require_lowercase: bool,
+    require_digit: bool,
+    require_special_character: bool,
+    history_size: usize,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self::new(&PasswordPolicyConfig::default())
+    }
+}
+
+impl PasswordPolicy {
+    pub fn new(config: &PasswordPolicyConfig) -> Self {
+        Self {
+            min_length: config.min_length as usize,
+            require_uppercase: config.require_uppercase,
+            require_lowercase: config.require_lowercase,
+            require_digit: config.require_digit,
+            require_special_character: config.require_special_character,
+            history_size: config.history_size as usize,
+        }
+    }
+
+    /// Returns the maximum number of the previous password hashes which have to be kept for the reuse check.
+    pub fn get_history_size(&self) -> usize {
+        self.history_size.saturating_sub(1)
+    }
+
+    pub fn validate(&self, password: &str) -> Result<(), IggyError> {
+        if password.chars().count() < self.min_length {
+            return Err(self.violation(format!(
+                "must be at least {} characters long",
+                self.min_length
+            )));
+        }
+
+        if self.require_uppercase && !password.chars().any(|c| c.is_uppercase()) {
+            return Err(self.violation("must contain an uppercase letter".to_string()));
+        }
+
+        if self.require_lowercase && !password.chars().any(|c| c.is_lowercase()) {
+            return Err(self.violation("must contain a lowercase letter".to_string()));
+        }
+
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            return Err(self.violation("must contain a digit".to_string()));
+        }
+
+        if self.require_special_character && password.chars().all(|c| c.is_alphanumeric()) {
+            return Err(self.violation("must contain a special character".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Returns an error if the password matches the current password hash or one of the previous password hashes
+    /// (the most recent first) within the configured history size, which includes the current password.
+    pub fn ensure_not_reused(
+        &self,
+        password: &str,
+        current_password_hash: &str,
+        password_history: &[String],
+    ) -> Result<(), IggyError> {
+        if self.history_size == 0 {
+            return Ok(());
+        }
+
+        let reused = std::iter::once(current_password_hash)
+            .chain(
+                password_history
+                    .iter()
+                    .take(self.get_history_size())
+                    .map(|hash| hash.as_str()),
+            )
+            .any(|hash| crypto::verify_password(password, hash));
+        if reused {
+            warn!("Password was used recently and cannot be reused.");
+            return Err(IggyError::PasswordReused);
+        }
+
+        Ok(())
+    }
+
+    fn violation(&self, reason: String) -> IggyError {
+        warn!("Password does not meet the password policy: {reason}.");
+        IggyError::PasswordPolicyViolation(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::utils::crypto::PasswordHasher;
+
+    fn create_policy(history_size: u32) -> PasswordPolicy {
+        PasswordPolicy::new(&PasswordPolicyConfig {
+            min_length: 8,
+            require_uppercase: true,
+            require_lowercase: true,
+            require_digit: true,
+            require_special_character: true,
+            history_size,
+        })
+    }
+
+    #[test]
+    fn password_meeting_all_requirements_should_be_valid() {
+        let policy = create_policy(0);
+        assert!(policy.validate("Secret123!").is_ok());
+    }
+
+    #[test]
+    fn password_not_meeting_the_requirements_should_be_invalid() {
+        let policy = create_policy(0);
+        let invalid_passwords = [
+            "Sec12!",
+            "secret123!",
+            "SECRET123!",
+            "Secret!!!",
+            "Secret123",
+        ];
+        for password in invalid_passwords {
+            assert!(matches!(
+                policy.validate(password),
+                Err(IggyError::PasswordPolicyViolation(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn recently_used_password_should_not_be_reused() {
+        let policy = create_policy(3);
+        let hasher = PasswordHasher::default();
+        let current_password_hash = hasher.hash("current");
+        let password_history = vec![
+            hasher.hash("previous1"),
+            hasher.hash("previous2"),
+            hasher.hash("previous3"),
+        ];
+
+        for password in ["current", "previous1", "previous2"] {
+            assert!(matches!(
+                policy.ensure_not_reused(password, &current_password_hash, &password_history),
+                Err(IggyError::PasswordReused)
+            ));
+        }
+        assert!(policy
+            .ensure_not_reused("previous3", &current_password_hash, &password_history)
+            .is_ok());
+        assert!(create_policy(0)
+            .ensure_not_reused("current", &current_password_hash, &password_history)
+            .is_ok());
+    }
+}
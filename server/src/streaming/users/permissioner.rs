@@ -1,5 +1,9 @@
+use crate::streaming::users::role::Role;
 use crate::streaming::users::user::User;
-use iggy::models::permissions::{GlobalPermissions, Permissions, StreamPermissions};
+use iggy::models::permissions::{
+    GlobalPermissions, Permissions, StreamPermissions, TopicPermissions,
+};
+use iggy::models::role_info::RoleId;
 use iggy::models::user_info::UserId;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -12,16 +16,117 @@ pub struct Permissioner {
     pub(super) users_that_can_send_messages_to_all_streams: HashSet<UserId>,
     pub(super) users_that_can_poll_messages_from_specific_streams: HashSet<(UserId, u32)>,
     pub(super) users_that_can_send_messages_to_specific_streams: HashSet<(UserId, u32)>,
+    pub(super) users_own_permissions: HashMap<UserId, Permissions>,
+    pub(super) users_roles: HashMap<UserId, Vec<RoleId>>,
+    pub(super) roles_permissions: HashMap<RoleId, Permissions>,
 }
 
 impl Permissioner {
-    pub fn init(&mut self, users: &[&User]) {
+    pub fn init(&mut self, users: &[&User], roles: &[&Role]) {
+        for role in roles {
+            self.init_permissions_for_role(role.id, role.permissions.clone());
+        }
+
         for user in users {
+            if !user.roles.is_empty() {
+                self.users_roles.insert(user.id, user.roles.clone());
+            }
             self.init_permissions_for_user(user.id, user.permissions.clone());
         }
     }
 
     pub fn init_permissions_for_user(&mut self, user_id: UserId, permissions: Option<Permissions>) {
+        match permissions {
+            Some(permissions) => self.users_own_permissions.insert(user_id, permissions),
+            None => self.users_own_permissions.remove(&user_id),
+        };
+        self.apply_effective_permissions(user_id);
+    }
+
+    pub fn update_permissions_for_user(
+        &mut self,
+        user_id: UserId,
+        permissions: Option<Permissions>,
+    ) {
+        self.init_permissions_for_user(user_id, permissions);
+    }
+
+    pub fn update_roles_for_user(&mut self, user_id: UserId, roles: Vec<RoleId>) {
+        if roles.is_empty() {
+            self.users_roles.remove(&user_id);
+        } else {
+            self.users_roles.insert(user_id, roles);
+        }
+        self.apply_effective_permissions(user_id);
+    }
+
+    pub fn delete_permissions_for_user(&mut self, user_id: UserId) {
+        self.users_own_permissions.remove(&user_id);
+        self.users_roles.remove(&user_id);
+        self.clear_effective_permissions(user_id);
+    }
+
+    pub fn init_permissions_for_role(&mut self, role_id: RoleId, permissions: Option<Permissions>) {
+        self.roles_permissions
+            .insert(role_id, permissions.unwrap_or_default());
+    }
+
+    pub fn update_permissions_for_role(
+        &mut self,
+        role_id: RoleId,
+        permissions: Option<Permissions>,
+    ) {
+        self.init_permissions_for_role(role_id, permissions);
+        for user_id in self.get_users_with_role(role_id) {
+            self.apply_effective_permissions(user_id);
+        }
+    }
+
+    pub fn delete_permissions_for_role(&mut self, role_id: RoleId) {
+        self.roles_permissions.remove(&role_id);
+        for user_id in self.get_users_with_role(role_id) {
+            if let Some(roles) = self.users_roles.get_mut(&user_id) {
+                roles.retain(|id| *id != role_id);
+                if roles.is_empty() {
+                    self.users_roles.remove(&user_id);
+                }
+            }
+            self.apply_effective_permissions(user_id);
+        }
+    }
+
+    fn get_users_with_role(&self, role_id: RoleId) -> Vec<UserId> {
+        self.users_roles
+            .iter()
+            .filter(|(_, roles)| roles.contains(&role_id))
+            .map(|(user_id, _)| *user_id)
+            .collect()
+    }
+
+    /// The effective permissions of the user are the union of its own permissions and the permissions of all its roles.
+    fn get_effective_permissions(&self, user_id: UserId) -> Option<Permissions> {
+        let mut effective_permissions = self.users_own_permissions.get(&user_id).cloned();
+        let Some(roles) = self.users_roles.get(&user_id) else {
+            return effective_permissions;
+        };
+
+        for role_id in roles {
+            let Some(role_permissions) = self.roles_permissions.get(role_id) else {
+                continue;
+            };
+
+            match effective_permissions.as_mut() {
+                Some(permissions) => union_permissions(permissions, role_permissions),
+                None => effective_permissions = Some(role_permissions.clone()),
+            }
+        }
+
+        effective_permissions
+    }
+
+    fn apply_effective_permissions(&mut self, user_id: UserId) {
+        self.clear_effective_permissions(user_id);
+        let permissions = self.get_effective_permissions(user_id);
         if permissions.is_none() {
             return;
         }
@@ -59,16 +164,7 @@ impl Permissioner {
         }
     }
 
-    pub fn update_permissions_for_user(
-        &mut self,
-        user_id: UserId,
-        permissions: Option<Permissions>,
-    ) {
-        self.delete_permissions_for_user(user_id);
-        self.init_permissions_for_user(user_id, permissions);
-    }
-
-    pub fn delete_permissions_for_user(&mut self, user_id: UserId) {
+    fn clear_effective_permissions(&mut self, user_id: UserId) {
         self.users_permissions.remove(&user_id);
         self.users_that_can_poll_messages_from_all_streams
             .remove(&user_id);
@@ -82,3 +178,141 @@ impl Permissioner {
             .retain(|(id, _)| *id != user_id);
     }
 }
+
+fn union_permissions(target: &mut Permissions, other: &Permissions) {
+    let global = &mut target.global;
+    global.manage_servers |= other.global.manage_servers;
+    global.read_servers |= other.global.read_servers;
+    global.manage_users |= other.global.manage_users;
+    global.read_users |= other.global.read_users;
+    global.manage_streams |= other.global.manage_streams;
+    global.read_streams |= other.global.read_streams;
+    global.manage_topics |= other.global.manage_topics;
+    global.read_topics |= other.global.read_topics;
+    global.poll_messages |= other.global.poll_messages;
+    global.send_messages |= other.global.send_messages;
+
+    let Some(other_streams) = &other.streams else {
+        return;
+    };
+
+    let streams = target.streams.get_or_insert_with(HashMap::new);
+    for (stream_id, other_stream) in other_streams {
+        match streams.get_mut(stream_id) {
+            Some(stream) => union_stream_permissions(stream, other_stream),
+            None => {
+                streams.insert(*stream_id, other_stream.clone());
+            }
+        }
+    }
+}
+
+fn union_stream_permissions(target: &mut StreamPermissions, other: &StreamPermissions) {
+    target.manage_stream |= other.manage_stream;
+    target.read_stream |= other.read_stream;
+    target.manage_topics |= other.manage_topics;
+    target.read_topics |= other.read_topics;
+    target.poll_messages |= other.poll_messages;
+    target.send_messages |= other.send_messages;
+
+    let Some(other_topics) = &other.topics else {
+        return;
+    };
+
+    let topics = target.topics.get_or_insert_with(HashMap::new);
+    for (topic_id, other_topic) in other_topics {
+        match topics.get_mut(topic_id) {
+            Some(topic) => union_topic_permissions(topic, other_topic),
+            None => {
+                topics.insert(*topic_id, other_topic.clone());
+            }
+        }
+    }
+}
+
+fn union_topic_permissions(target: &mut TopicPermissions, other: &TopicPermissions) {
+    target.manage_topic |= other.manage_topic;
+    target.read_topic |= other.read_topic;
+    target.poll_messages |= other.poll_messages;
+    target.send_messages |= other.send_messages;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_permissions_should_be_extended_by_assigned_roles() {
+        let mut permissioner = Permissioner::default();
+        let user_id = 2;
+        let stream_id = 1;
+        let topic_id = 1;
+        permissioner.init_permissions_for_user(
+            user_id,
+            Some(Permissions {
+                global: GlobalPermissions {
+                    read_users: true,
+                    ..Default::default()
+                },
+                streams: None,
+            }),
+        );
+        permissioner.init_permissions_for_role(
+            1,
+            Some(Permissions {
+                global: GlobalPermissions::default(),
+                streams: Some(HashMap::from([(
+                    stream_id,
+                    StreamPermissions {
+                        read_stream: true,
+                        poll_messages: true,
+                        topics: Some(HashMap::new()),
+                        ..Default::default()
+                    },
+                )])),
+            }),
+        );
+
+        assert!(permissioner.get_users(user_id).is_ok());
+        assert!(permissioner.get_stream(user_id, stream_id).is_err());
+        assert!(permissioner
+            .poll_messages(user_id, stream_id, topic_id)
+            .is_err());
+
+        permissioner.update_roles_for_user(user_id, vec![1]);
+        assert!(permissioner.get_users(user_id).is_ok());
+        assert!(permissioner.get_stream(user_id, stream_id).is_ok());
+        assert!(permissioner
+            .poll_messages(user_id, stream_id, topic_id)
+            .is_ok());
+        assert!(permissioner
+            .append_messages(user_id, stream_id, topic_id)
+            .is_err());
+    }
+
+    #[test]
+    fn role_changes_should_be_applied_to_users_having_the_role() {
+        let mut permissioner = Permissioner::default();
+        let user_id = 2;
+        permissioner.init_permissions_for_role(1, None);
+        permissioner.init_permissions_for_user(user_id, None);
+        permissioner.update_roles_for_user(user_id, vec![1]);
+        assert!(permissioner.get_streams(user_id).is_err());
+
+        permissioner.update_permissions_for_role(
+            1,
+            Some(Permissions {
+                global: GlobalPermissions {
+                    read_streams: true,
+                    ..Default::default()
+                },
+                streams: None,
+            }),
+        );
+        assert!(permissioner.get_streams(user_id).is_ok());
+
+        permissioner.delete_permissions_for_role(1);
+        assert!(permissioner.get_streams(user_id).is_err());
+        assert!(!permissioner.users_roles.contains_key(&user_id));
+    }
+}
@@ -0,0 +1,28 @@
+use crate::streaming::users::permissioner::Permissioner;
+use iggy::error::IggyError;
+
+impl Permissioner {
+    pub fn get_role(&self, user_id: u32) -> Result<(), IggyError> {
+        self.read_users(user_id)
+    }
+
+    pub fn get_roles(&self, user_id: u32) -> Result<(), IggyError> {
+        self.read_users(user_id)
+    }
+
+    pub fn create_role(&self, user_id: u32) -> Result<(), IggyError> {
+        self.manager_users(user_id)
+    }
+
+    pub fn delete_role(&self, user_id: u32) -> Result<(), IggyError> {
+        self.manager_users(user_id)
+    }
+
+    pub fn update_role(&self, user_id: u32) -> Result<(), IggyError> {
+        self.manager_users(user_id)
+    }
+
+    pub fn update_roles(&self, user_id: u32) -> Result<(), IggyError> {
+        self.manager_users(user_id)
+    }
+}
@@ -14,6 +14,20 @@ impl Permissioner {
         self.get_server_info(user_id)
     }
 
+    pub fn get_audit_log(&self, user_id: u32) -> Result<(), IggyError> {
+        if let Some(global_permissions) = self.users_permissions.get(&user_id) {
+            if global_permissions.manage_servers {
+                return Ok(());
+            }
+        }
+
+        Err(IggyError::Unauthorized)
+    }
+
+    pub fn create_backup(&self, user_id: u32) -> Result<(), IggyError> {
+        self.get_audit_log(user_id)
+    }
+
     fn get_server_info(&self, user_id: u32) -> Result<(), IggyError> {
         if let Some(global_permissions) = self.users_permissions.get(&user_id) {
             if global_permissions.manage_servers || global_permissions.read_servers {
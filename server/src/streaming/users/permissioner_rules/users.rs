@@ -26,11 +26,15 @@ impl Permissioner {
         self.manager_users(user_id)
     }
 
+    pub fn unlock_user(&self, user_id: u32) -> Result<(), IggyError> {
+        self.manager_users(user_id)
+    }
+
     pub fn change_password(&self, user_id: u32) -> Result<(), IggyError> {
         self.manager_users(user_id)
     }
 
-    fn manager_users(&self, user_id: u32) -> Result<(), IggyError> {
+    pub(super) fn manager_users(&self, user_id: u32) -> Result<(), IggyError> {
         if let Some(global_permissions) = self.users_permissions.get(&user_id) {
             if global_permissions.manage_users {
                 return Ok(());
@@ -40,7 +44,7 @@ impl Permissioner {
         Err(IggyError::Unauthorized)
     }
 
-    fn read_users(&self, user_id: u32) -> Result<(), IggyError> {
+    pub(super) fn read_users(&self, user_id: u32) -> Result<(), IggyError> {
         if let Some(global_permissions) = self.users_permissions.get(&user_id) {
             if global_permissions.manage_users || global_permissions.read_users {
                 return Ok(());
@@ -0,0 +1,31 @@
+use iggy::models::permissions::Permissions;
+use iggy::models::role_info::RoleId;
+use iggy::utils::timestamp::IggyTimestamp;
+
+#[derive(Debug)]
+pub struct Role {
+    pub id: RoleId,
+    pub name: String,
+    pub created_at: IggyTimestamp,
+    pub permissions: Option<Permissions>,
+}
+
+impl Role {
+    pub fn new(id: RoleId, name: &str, permissions: Option<Permissions>) -> Self {
+        Self::with_created_at(id, name, IggyTimestamp::now(), permissions)
+    }
+
+    pub fn with_created_at(
+        id: RoleId,
+        name: &str,
+        created_at: IggyTimestamp,
+        permissions: Option<Permissions>,
+    ) -> Self {
+        Self {
+            id,
+            name: name.into(),
+            created_at,
+            permissions,
+        }
+    }
+}
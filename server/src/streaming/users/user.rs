@@ -1,10 +1,11 @@
 use crate::streaming::personal_access_tokens::personal_access_token::PersonalAccessToken;
-use crate::streaming::utils::crypto;
+use iggy::models::role_info::RoleId;
 use iggy::models::user_status::UserStatus;
 use iggy::models::{permissions::Permissions, user_info::UserId};
 use iggy::users::defaults::*;
 use iggy::utils::timestamp::IggyTimestamp;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 #[derive(Debug)]
 pub struct User {
@@ -14,7 +15,12 @@ pub struct User {
     pub password: String,
     pub created_at: IggyTimestamp,
     pub permissions: Option<Permissions>,
+    pub roles: Vec<RoleId>,
     pub personal_access_tokens: HashMap<String, PersonalAccessToken>,
+    /// The timestamp (in microseconds) until which the user is locked due to too many failed login attempts, 0 if not locked.
+    pub locked_until: AtomicU64,
+    /// The hashes of the previous passwords (the most recent first), used to prevent reusing them.
+    pub password_history: Vec<String>,
 }
 
 impl Default for User {
@@ -26,7 +32,10 @@ impl Default for User {
             password: "secret".to_string(),
             created_at: IggyTimestamp::now(),
             permissions: None,
+            roles: Vec::new(),
             personal_access_tokens: HashMap::new(),
+            locked_until: AtomicU64::new(0),
+            password_history: Vec::new(),
         }
     }
 }
@@ -39,22 +48,6 @@ impl User {
         }
     }
 
-    pub fn new(
-        id: u32,
-        username: &str,
-        password: &str,
-        status: UserStatus,
-        permissions: Option<Permissions>,
-    ) -> Self {
-        Self::with_password(
-            id,
-            username,
-            crypto::hash_password(password),
-            status,
-            permissions,
-        )
-    }
-
     pub fn with_password(
         id: u32,
         username: &str,
@@ -69,12 +62,15 @@ impl User {
             created_at: IggyTimestamp::now(),
             status,
             permissions,
+            roles: Vec::new(),
             personal_access_tokens: HashMap::new(),
+            locked_until: AtomicU64::new(0),
+            password_history: Vec::new(),
         }
     }
 
-    pub fn root(username: &str, password: &str) -> Self {
-        Self::new(
+    pub fn root(username: &str, password: String) -> Self {
+        Self::with_password(
             DEFAULT_ROOT_USER_ID,
             username,
             password,
@@ -90,15 +86,41 @@ impl User {
     pub fn is_active(&self) -> bool {
         self.status == UserStatus::Active
     }
+
+    /// Returns the current status of the user, which is `Locked` if the user is temporarily locked.
+    pub fn get_status(&self) -> UserStatus {
+        if self.is_locked() {
+            return UserStatus::Locked;
+        }
+
+        self.status
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked_until.load(Ordering::SeqCst) > IggyTimestamp::now().as_micros()
+    }
+
+    pub fn lock(&self, locked_until: u64) {
+        self.locked_until.store(locked_until, Ordering::SeqCst);
+    }
+
+    pub fn unlock(&self) {
+        self.locked_until.store(0, Ordering::SeqCst);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::streaming::utils::crypto;
+    use crate::streaming::utils::crypto::PasswordHasher;
 
     #[test]
     fn given_root_user_data_and_credentials_should_be_valid() {
-        let user = User::root(DEFAULT_ROOT_USERNAME, DEFAULT_ROOT_PASSWORD);
+        let user = User::root(
+            DEFAULT_ROOT_USERNAME,
+            PasswordHasher::default().hash(DEFAULT_ROOT_PASSWORD),
+        );
         assert_eq!(user.id, DEFAULT_ROOT_USER_ID);
         assert_eq!(user.username, DEFAULT_ROOT_USERNAME);
         assert_ne!(user.password, DEFAULT_ROOT_PASSWORD);
@@ -113,7 +135,22 @@ mod tests {
     #[test]
     fn should_be_created_given_specific_status() {
         let status = UserStatus::Inactive;
-        let user = User::new(1, "test", "test", status, None);
+        let user = User::with_password(1, "test", "test".to_string(), status, None);
         assert_eq!(user.status, status);
     }
+
+    #[test]
+    fn should_have_locked_status_until_lock_expires() {
+        let user = User::with_password(1, "test", "test".to_string(), UserStatus::Active, None);
+        user.lock(IggyTimestamp::now().as_micros() + 60_000_000);
+        assert!(user.is_locked());
+        assert_eq!(user.get_status(), UserStatus::Locked);
+
+        user.unlock();
+        assert!(!user.is_locked());
+        assert_eq!(user.get_status(), UserStatus::Active);
+
+        user.lock(IggyTimestamp::now().as_micros() - 1);
+        assert_eq!(user.get_status(), UserStatus::Active);
+    }
 }
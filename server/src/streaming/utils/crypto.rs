@@ -1,9 +1,158 @@
+use crate::configs::system::{PasswordHashingAlgorithm, PasswordHashingConfig};
+use argon2::password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
 use bcrypt::{hash, verify};
+use ring::rand::SecureRandom;
 
-pub fn hash_password(password: &str) -> String {
-    hash(password, 4).unwrap()
+const ARGON2_PREFIX: &str = "$argon2";
+const ARGON2_SALT_LENGTH: usize = 16;
+
+/// `PasswordHasher` hashes the passwords using the configured algorithm and parameters.
+/// The verification of the passwords doesn't depend on the configuration, as the algorithm
+/// and its parameters are always read from the hash itself.
+#[derive(Debug)]
+pub struct PasswordHasher {
+    algorithm: PasswordHashingAlgorithm,
+    bcrypt_cost: u32,
+    argon2_params: Params,
+}
+
+impl Default for PasswordHasher {
+    fn default() -> Self {
+        Self::new(&PasswordHashingConfig::default())
+    }
+}
+
+impl PasswordHasher {
+    pub fn new(config: &PasswordHashingConfig) -> Self {
+        Self {
+            algorithm: config.algorithm,
+            bcrypt_cost: config.bcrypt_cost,
+            argon2_params: Params::new(
+                config.argon2_memory_cost,
+                config.argon2_iterations,
+                config.argon2_parallelism,
+                None,
+            )
+            .unwrap_or_default(),
+        }
+    }
+
+    pub fn hash(&self, password: &str) -> String {
+        match self.algorithm {
+            PasswordHashingAlgorithm::Bcrypt => hash(password, self.bcrypt_cost).unwrap(),
+            PasswordHashingAlgorithm::Argon2id => {
+                let mut salt = [0u8; ARGON2_SALT_LENGTH];
+                ring::rand::SystemRandom::new().fill(&mut salt).unwrap();
+                let salt = SaltString::encode_b64(&salt).unwrap();
+                Argon2::new(
+                    Algorithm::Argon2id,
+                    Version::V0x13,
+                    self.argon2_params.clone(),
+                )
+                .hash_password(password.as_bytes(), &salt)
+                .unwrap()
+                .to_string()
+            }
+        }
+    }
+
+    /// Returns `true` if the hash was computed using the different algorithm or parameters than the configured ones.
+    pub fn needs_rehash(&self, hash: &str) -> bool {
+        match self.algorithm {
+            PasswordHashingAlgorithm::Bcrypt => get_bcrypt_cost(hash) != Some(self.bcrypt_cost),
+            PasswordHashingAlgorithm::Argon2id => {
+                let Ok(hash) = PasswordHash::new(hash) else {
+                    return true;
+                };
+                if hash.algorithm != Algorithm::Argon2id.ident() {
+                    return true;
+                }
+
+                let Ok(params) = Params::try_from(&hash) else {
+                    return true;
+                };
+                params.m_cost() != self.argon2_params.m_cost()
+                    || params.t_cost() != self.argon2_params.t_cost()
+                    || params.p_cost() != self.argon2_params.p_cost()
+            }
+        }
+    }
 }
 
 pub fn verify_password(password: &str, hash: &str) -> bool {
+    if hash.starts_with(ARGON2_PREFIX) {
+        return match PasswordHash::new(hash) {
+            Ok(hash) => Argon2::default()
+                .verify_password(password.as_bytes(), &hash)
+                .is_ok(),
+            Err(_) => false,
+        };
+    }
+
     verify(password, hash).unwrap_or(false)
 }
+
+/// The bcrypt hash has the format of `$2b$<cost>$<salt and hash>`.
+fn get_bcrypt_cost(hash: &str) -> Option<u32> {
+    let mut parts = hash.split('$').skip(1);
+    if !parts.next()?.starts_with('2') {
+        return None;
+    }
+
+    parts.next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_hasher(algorithm: PasswordHashingAlgorithm, bcrypt_cost: u32) -> PasswordHasher {
+        PasswordHasher::new(&PasswordHashingConfig {
+            algorithm,
+            bcrypt_cost,
+            argon2_memory_cost: 256,
+            argon2_iterations: 1,
+            argon2_parallelism: 1,
+        })
+    }
+
+    #[test]
+    fn password_hashed_using_bcrypt_should_be_verified() {
+        let hasher = create_hasher(PasswordHashingAlgorithm::Bcrypt, 4);
+        let hash = hasher.hash("secret");
+        assert!(verify_password("secret", &hash));
+        assert!(!verify_password("invalid", &hash));
+        assert!(!hasher.needs_rehash(&hash));
+    }
+
+    #[test]
+    fn password_hashed_using_argon2id_should_be_verified() {
+        let hasher = create_hasher(PasswordHashingAlgorithm::Argon2id, 4);
+        let hash = hasher.hash("secret");
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(verify_password("secret", &hash));
+        assert!(!verify_password("invalid", &hash));
+        assert!(!hasher.needs_rehash(&hash));
+    }
+
+    #[test]
+    fn hash_should_require_rehash_when_algorithm_or_parameters_change() {
+        let bcrypt_hasher = create_hasher(PasswordHashingAlgorithm::Bcrypt, 4);
+        let argon2_hasher = create_hasher(PasswordHashingAlgorithm::Argon2id, 4);
+        let bcrypt_hash = bcrypt_hasher.hash("secret");
+        let argon2_hash = argon2_hasher.hash("secret");
+
+        assert!(argon2_hasher.needs_rehash(&bcrypt_hash));
+        assert!(bcrypt_hasher.needs_rehash(&argon2_hash));
+        assert!(create_hasher(PasswordHashingAlgorithm::Bcrypt, 5).needs_rehash(&bcrypt_hash));
+        assert!(PasswordHasher::new(&PasswordHashingConfig {
+            algorithm: PasswordHashingAlgorithm::Argon2id,
+            bcrypt_cost: 4,
+            argon2_memory_cost: 512,
+            argon2_iterations: 1,
+            argon2_parallelism: 1,
+        })
+        .needs_rehash(&argon2_hash));
+    }
+}
@@ -1,6 +1,7 @@
 use atone::Vc;
 use std::path::{Path, PathBuf};
-use tokio::fs::{read_dir, remove_file, File, OpenOptions};
+use tokio::fs::{copy, create_dir_all, read_dir, remove_file, File, OpenOptions};
+use tokio::io::AsyncWriteExt;
 
 pub async fn open(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().read(true).open(path).await
@@ -10,6 +11,15 @@ pub async fn append(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().read(true).append(true).open(path).await
 }
 
+/// Opens the file for appending and creates it if it doesn't exist, without truncating it (unlike `overwrite`).
+pub async fn append_or_create(path: &str) -> Result<File, std::io::Error> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+}
+
 pub async fn overwrite(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new()
         .create(true)
@@ -18,6 +28,53 @@ pub async fn overwrite(path: &str) -> Result<File, std::io::Error> {
         .open(path)
         .await
 }
+pub async fn truncate(path: &str, size: u64) -> Result<(), std::io::Error> {
+    let file = OpenOptions::new().write(true).open(path).await?;
+    file.set_len(size).await?;
+    file.sync_all().await
+}
+
+/// Allocates the disk space for the file up to the given size, without changing the size of the file,
+/// so that the appended data is less fragmented on disk. Only supported on Linux, a no-op elsewhere.
+#[cfg(target_os = "linux")]
+pub async fn preallocate(path: &str, size: u64) -> Result<(), std::io::Error> {
+    use std::os::fd::AsRawFd;
+
+    let file = OpenOptions::new()
+        .write(true)
+        .open(path)
+        .await?
+        .into_std()
+        .await;
+    tokio::task::spawn_blocking(move || {
+        let result = unsafe {
+            libc::fallocate(
+                file.as_raw_fd(),
+                libc::FALLOC_FL_KEEP_SIZE,
+                0,
+                size as libc::off_t,
+            )
+        };
+        match result {
+            0 => Ok(()),
+            _ => Err(std::io::Error::last_os_error()),
+        }
+    })
+    .await?
+}
+
+#[cfg(not(target_os = "linux"))]
+pub async fn preallocate(_path: &str, _size: u64) -> Result<(), std::io::Error> {
+    Ok(())
+}
+
+/// Releases the disk space allocated past the end of the file (e.g. by `preallocate`).
+pub async fn release_preallocated(path: &str) -> Result<(), std::io::Error> {
+    let file = OpenOptions::new().write(true).open(path).await?;
+    let size = file.metadata().await?.len();
+    file.set_len(size).await
+}
+
 pub async fn remove(path: &str) -> Result<(), std::io::Error> {
     remove_file(path).await
 }
@@ -26,6 +83,60 @@ pub async fn rename(old_path: &str, new_path: &str) -> Result<(), std::io::Error
     tokio::fs::rename(Path::new(old_path), Path::new(new_path)).await
 }
 
+/// Writes the bytes to the file, replacing its content, and flushes them to disk.
+pub async fn overwrite_synced(path: &str, bytes: &[u8]) -> Result<(), std::io::Error> {
+    let mut file = overwrite(path).await?;
+    file.write_all(bytes).await?;
+    file.sync_all().await
+}
+
+/// Renames the file and flushes the parent directory to disk, so that the rename survives the crash.
+/// The renamed file must be already flushed, e.g. by `overwrite_synced`.
+pub async fn rename_synced(old_path: &str, new_path: &str) -> Result<(), std::io::Error> {
+    rename(old_path, new_path).await?;
+    sync_parent_directory(new_path).await
+}
+
+/// Flushes the directory containing the given file to disk, e.g. after creating, renaming or removing the file.
+#[cfg(unix)]
+pub async fn sync_parent_directory(path: &str) -> Result<(), std::io::Error> {
+    let directory = match Path::new(path).parent() {
+        Some(directory) if !directory.as_os_str().is_empty() => directory,
+        _ => Path::new("."),
+    };
+    File::open(directory).await?.sync_all().await
+}
+
+#[cfg(not(unix))]
+pub async fn sync_parent_directory(_path: &str) -> Result<(), std::io::Error> {
+    Ok(())
+}
+
+/// Recursively copies the directory, returns the paths of the copied files and their total size.
+pub async fn copy_dir(source: &Path, destination: &Path) -> std::io::Result<(Vec<PathBuf>, u64)> {
+    let mut copied_files = Vec::new();
+    let mut total_size = 0;
+    let mut queue: Vc<PathBuf> = Vc::new();
+    queue.push_back(PathBuf::new());
+
+    while let Some(relative_path) = queue.pop_front() {
+        create_dir_all(destination.join(&relative_path)).await?;
+        let mut entries = read_dir(source.join(&relative_path)).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let relative_path = relative_path.join(entry.file_name());
+            if entry.file_type().await?.is_dir() {
+                queue.push_back(relative_path);
+                continue;
+            }
+
+            let destination_path = destination.join(&relative_path);
+            total_size += copy(entry.path(), &destination_path).await?;
+            copied_files.push(destination_path);
+        }
+    }
+    Ok((copied_files, total_size))
+}
+
 pub async fn folder_size<P>(path: P) -> std::io::Result<u64>
 where
     P: Into<PathBuf> + AsRef<Path>,
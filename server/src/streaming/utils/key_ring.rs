@@ -0,0 +1,173 @@
+use crate::configs::system::EncryptionConfig;
+use iggy::error::IggyError;
+use iggy::utils::crypto::{Aes256GcmEncryptor, Encryptor};
+use std::collections::HashMap;
+
+const KEY_ID_SIZE: usize = 4;
+const NONCE_SIZE: usize = 12;
+
+/// `EncryptionKeyRing` holds the active encryption key used for encrypting the data
+/// and the previous keys, which are still used for decrypting the data encrypted before the key rotation.
+/// The encrypted data is prefixed with the ID of the key used for encryption (u32, little endian).
+/// The data encrypted before the key IDs were introduced has no prefix, and is decrypted by trying each key,
+/// which is safe, as AES-256-GCM authenticates the ciphertext.
+#[derive(Debug)]
+pub struct EncryptionKeyRing {
+    active_key_id: u32,
+    keys: HashMap<u32, Aes256GcmEncryptor>,
+}
+
+impl EncryptionKeyRing {
+    pub fn new(active_key_id: u32, active_key: Aes256GcmEncryptor) -> Self {
+        Self {
+            active_key_id,
+            keys: HashMap::from([(active_key_id, active_key)]),
+        }
+    }
+
+    pub fn from_config(config: &EncryptionConfig) -> Result<Self, IggyError> {
+        let mut key_ring = Self::new(
+            config.key_id,
+            Aes256GcmEncryptor::from_base64_key(&config.key)?,
+        );
+        for previous_key in &config.previous_keys {
+            key_ring.add_key(
+                previous_key.id,
+                Aes256GcmEncryptor::from_base64_key(&previous_key.key)?,
+            )?;
+        }
+        Ok(key_ring)
+    }
+
+    /// Adds the key which can be used only for decrypting the data.
+    pub fn add_key(&mut self, key_id: u32, key: Aes256GcmEncryptor) -> Result<(), IggyError> {
+        if self.keys.contains_key(&key_id) {
+            return Err(IggyError::InvalidEncryptionKey);
+        }
+
+        self.keys.insert(key_id, key);
+        Ok(())
+    }
+
+    /// Adds the new key and makes it the active one, the previous keys are still used for decrypting the data.
+    pub fn rotate(&mut self, key_id: u32, key: Aes256GcmEncryptor) -> Result<(), IggyError> {
+        self.add_key(key_id, key)?;
+        self.active_key_id = key_id;
+        Ok(())
+    }
+
+    pub fn get_active_key_id(&self) -> u32 {
+        self.active_key_id
+    }
+
+    /// Returns the data encrypted with the active key, or `None` if it's already encrypted with the active key.
+    pub fn reencrypt(&self, data: &[u8]) -> Result<Option<Vec<u8>>, IggyError> {
+        let (key_id, decrypted_data) = self.decrypt_with_key_id(data)?;
+        if key_id == Some(self.active_key_id) {
+            return Ok(None);
+        }
+
+        Ok(Some(self.encrypt(&decrypted_data)?))
+    }
+
+    fn decrypt_with_key_id(&self, data: &[u8]) -> Result<(Option<u32>, Vec<u8>), IggyError> {
+        if data.len() > KEY_ID_SIZE + NONCE_SIZE {
+            let key_id = u32::from_le_bytes(data[..KEY_ID_SIZE].try_into()?);
+            if let Some(key) = self.keys.get(&key_id) {
+                if let Ok(decrypted_data) = key.decrypt(&data[KEY_ID_SIZE..]) {
+                    return Ok((Some(key_id), decrypted_data));
+                }
+            }
+        }
+
+        if data.len() <= NONCE_SIZE {
+            return Err(IggyError::CannotDecryptData);
+        }
+
+        let active_key = &self.keys[&self.active_key_id];
+        if let Ok(decrypted_data) = active_key.decrypt(data) {
+            return Ok((None, decrypted_data));
+        }
+
+        for (key_id, key) in &self.keys {
+            if *key_id == self.active_key_id {
+                continue;
+            }
+
+            if let Ok(decrypted_data) = key.decrypt(data) {
+                return Ok((None, decrypted_data));
+            }
+        }
+
+        Err(IggyError::CannotDecryptData)
+    }
+}
+
+impl Encryptor for EncryptionKeyRing {
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, IggyError> {
+        let encrypted_data = self.keys[&self.active_key_id].encrypt(data)?;
+        let mut payload = Vec::with_capacity(KEY_ID_SIZE + encrypted_data.len());
+        payload.extend_from_slice(&self.active_key_id.to_le_bytes());
+        payload.extend(encrypted_data);
+        Ok(payload)
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, IggyError> {
+        self.decrypt_with_key_id(data)
+            .map(|(_, decrypted_data)| decrypted_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_key(value: u8) -> Aes256GcmEncryptor {
+        Aes256GcmEncryptor::new(&[value; 32]).unwrap()
+    }
+
+    #[test]
+    fn data_encrypted_with_previous_key_should_be_decrypted_after_rotation() {
+        let mut key_ring = EncryptionKeyRing::new(1, create_key(1));
+        let data = b"Hello World!";
+        let encrypted_data = key_ring.encrypt(data).unwrap();
+        assert_eq!(encrypted_data[..KEY_ID_SIZE], 1u32.to_le_bytes());
+
+        key_ring.rotate(2, create_key(2)).unwrap();
+        assert_eq!(key_ring.get_active_key_id(), 2);
+        let decrypted_data = key_ring.decrypt(&encrypted_data).unwrap();
+        assert_eq!(data, decrypted_data.as_slice());
+
+        let encrypted_data = key_ring.encrypt(data).unwrap();
+        assert_eq!(encrypted_data[..KEY_ID_SIZE], 2u32.to_le_bytes());
+        assert!(key_ring.rotate(1, create_key(3)).is_err());
+    }
+
+    #[test]
+    fn data_encrypted_without_key_id_should_be_decrypted() {
+        let legacy_key = create_key(1);
+        let data = b"Hello World!";
+        let encrypted_data = legacy_key.encrypt(data).unwrap();
+        let mut key_ring = EncryptionKeyRing::new(1, legacy_key);
+        key_ring.rotate(2, create_key(2)).unwrap();
+
+        let decrypted_data = key_ring.decrypt(&encrypted_data).unwrap();
+        assert_eq!(data, decrypted_data.as_slice());
+    }
+
+    #[test]
+    fn data_should_be_reencrypted_only_when_not_encrypted_with_active_key() {
+        let mut key_ring = EncryptionKeyRing::new(1, create_key(1));
+        let data = b"Hello World!";
+        let encrypted_data = key_ring.encrypt(data).unwrap();
+        assert!(key_ring.reencrypt(&encrypted_data).unwrap().is_none());
+
+        key_ring.rotate(2, create_key(2)).unwrap();
+        let reencrypted_data = key_ring.reencrypt(&encrypted_data).unwrap().unwrap();
+        assert_eq!(reencrypted_data[..KEY_ID_SIZE], 2u32.to_le_bytes());
+        assert!(key_ring.reencrypt(&reencrypted_data).unwrap().is_none());
+
+        let other_key_ring = EncryptionKeyRing::new(3, create_key(3));
+        assert!(other_key_ring.decrypt(&reencrypted_data).is_err());
+    }
+}
@@ -1,5 +1,5 @@
 pub mod crypto;
 pub mod file;
 pub mod hash;
-pub mod head_tail_buf;
+pub mod key_ring;
 pub mod random_id;
@@ -3,6 +3,7 @@ use crate::binary::sender::Sender;
 use crate::command::ServerCommand;
 use crate::server_error::ServerError;
 use crate::streaming::clients::client_manager::Transport;
+use crate::streaming::diagnostics::metrics::TCP_TRANSPORT;
 use crate::streaming::session::Session;
 use crate::streaming::systems::system::SharedSystem;
 use bytes::{BufMut, BytesMut};
@@ -69,7 +70,7 @@ pub(crate) async fn handle_connection(
         }
 
         debug!("Received a TCP command: {command}, payload size: {length}");
-        command::handle(command, sender, &session, system.clone()).await?;
+        command::handle(command, sender, &session, system.clone(), TCP_TRANSPORT).await?;
         debug!("Sent a TCP response.");
     }
 }
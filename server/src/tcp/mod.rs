@@ -1,5 +1,7 @@
 pub mod connection_handler;
 pub mod sender;
+#[cfg(target_os = "linux")]
+mod sendfile;
 pub mod tcp_listener;
 mod tcp_sender;
 pub mod tcp_server;
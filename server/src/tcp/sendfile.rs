@@ -0,0 +1,115 @@
+use crate::streaming::batching::polled_batches::BatchesChunk;
+use crate::tcp::sender::STATUS_OK;
+use bytes::{BufMut, BytesMut};
+use iggy::error::IggyError;
+use std::fs::File;
+use std::io::{self, ErrorKind};
+use std::mem::size_of;
+use std::os::fd::AsRawFd;
+use tokio::io::{AsyncWriteExt, Interest};
+use tokio::net::TcpStream;
+use tracing::{debug, error};
+
+/// Sends the OK response with the polled batches, the regions of the segment files are sent with `sendfile`,
+/// directly from the page cache to the socket, without copying them to the user space. The socket is corked
+/// for the duration of the response, so the header and the batches are sent in full segments.
+pub(crate) async fn send_ok_response_with_batches(
+    stream: &mut TcpStream,
+    header: &[u8],
+    chunks: Vec<BatchesChunk>,
+) -> Result<(), IggyError> {
+    let length = header.len() as u64 + chunks.iter().map(|chunk| chunk.len()).sum::<u64>();
+    let mut buffer = BytesMut::with_capacity(STATUS_OK.len() + size_of::<u32>() + header.len());
+    buffer.put_slice(STATUS_OK);
+    buffer.put_u32_le(length as u32);
+    buffer.put_slice(header);
+
+    debug!(
+        "Sending response with status: {:?} and polled batches...",
+        STATUS_OK
+    );
+    set_cork(stream, true)?;
+    let result = match send_batches(stream, &buffer, chunks).await {
+        Ok(()) => set_cork(stream, false),
+        Err(error) => Err(error),
+    };
+    if let Err(error) = result {
+        // The length of the payload might have been already sent, so the error response would be read
+        // by the client as a part of the payload, the connection is closed instead.
+        error!("Failed to send the polled batches, closing the connection. {error}");
+        let _ = stream.shutdown().await;
+        return Err(IggyError::ConnectionClosed);
+    }
+    debug!("Sent response with status: {:?}", STATUS_OK);
+    Ok(())
+}
+
+async fn send_batches(
+    stream: &mut TcpStream,
+    header: &[u8],
+    chunks: Vec<BatchesChunk>,
+) -> Result<(), IggyError> {
+    stream.write_all(header).await?;
+    for chunk in chunks {
+        match chunk {
+            BatchesChunk::Memory(bytes) => stream.write_all(&bytes).await?,
+            BatchesChunk::File {
+                file,
+                position,
+                length,
+            } => send_file(stream, &file, position, length).await?,
+        }
+    }
+    Ok(())
+}
+
+async fn send_file(
+    stream: &TcpStream,
+    file: &File,
+    position: u64,
+    length: u64,
+) -> Result<(), IggyError> {
+    let mut offset = position as libc::off_t;
+    let mut remaining = length as usize;
+    while remaining > 0 {
+        stream.writable().await?;
+        let result = stream.try_io(Interest::WRITABLE, || {
+            // SAFETY: both descriptors are valid for the duration of the call and the offset is a valid pointer.
+            let sent = unsafe {
+                libc::sendfile(stream.as_raw_fd(), file.as_raw_fd(), &mut offset, remaining)
+            };
+            if sent < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(sent as usize)
+        });
+        match result {
+            // The file is shorter than expected, e.g. it was truncated after the batches were found.
+            Ok(0) => return Err(IggyError::from(io::Error::from(ErrorKind::UnexpectedEof))),
+            Ok(sent) => remaining -= sent,
+            Err(error)
+                if error.kind() == ErrorKind::WouldBlock
+                    || error.kind() == ErrorKind::Interrupted => {}
+            Err(error) => return Err(IggyError::from(error)),
+        }
+    }
+    Ok(())
+}
+
+fn set_cork(stream: &TcpStream, enabled: bool) -> Result<(), IggyError> {
+    let value = enabled as libc::c_int;
+    // SAFETY: the descriptor is valid and the option value points to the integer of the given size.
+    let result = unsafe {
+        libc::setsockopt(
+            stream.as_raw_fd(),
+            libc::IPPROTO_TCP,
+            libc::TCP_CORK,
+            &value as *const libc::c_int as *const libc::c_void,
+            size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    if result < 0 {
+        return Err(IggyError::from(io::Error::last_os_error()));
+    }
+    Ok(())
+}
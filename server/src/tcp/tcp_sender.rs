@@ -1,5 +1,9 @@
 use crate::binary::sender::Sender;
+#[cfg(target_os = "linux")]
+use crate::streaming::batching::polled_batches::BatchesChunk;
 use crate::tcp::sender;
+#[cfg(target_os = "linux")]
+use crate::tcp::sendfile;
 use async_trait::async_trait;
 use iggy::error::IggyError;
 use tokio::net::TcpStream;
@@ -29,4 +33,13 @@ impl Sender for TcpSender {
     async fn send_error_response(&mut self, error: IggyError) -> Result<(), IggyError> {
         sender::send_error_response(&mut self.stream, error).await
     }
+
+    #[cfg(target_os = "linux")]
+    async fn send_ok_response_with_batches(
+        &mut self,
+        header: &[u8],
+        chunks: Vec<BatchesChunk>,
+    ) -> Result<(), IggyError> {
+        sendfile::send_ok_response_with_batches(&mut self.stream, header, chunks).await
+    }
 }
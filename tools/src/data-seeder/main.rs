@@ -1,3 +1,4 @@
+mod profile;
 mod seeder;
 
 use anyhow::Result;
@@ -8,7 +9,11 @@ use iggy::client_provider;
 use iggy::client_provider::ClientProviderConfig;
 use iggy::clients::client::{IggyClient, IggyClientBackgroundConfig};
 use iggy::utils::crypto::{Aes256GcmEncryptor, Encryptor};
+use profile::SeederProfile;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::error::Error;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tracing::info;
 
@@ -23,12 +28,25 @@ pub struct DataSeederArgs {
 
     #[arg(long, default_value = "iggy")]
     pub password: String,
+
+    /// Path to the TOML profile describing the streams, topics and messages to seed,
+    /// if not provided, the fixed set of streams and topics with simple messages is created
+    #[arg(long)]
+    pub profile: Option<PathBuf>,
+
+    /// Seed of the random values generated from the profile, so the dataset can be reproduced
+    #[arg(long, requires = "profile")]
+    pub seed: Option<u64>,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
     let args = DataSeederArgs::parse();
     let iggy_args = Args::from(vec![args.iggy.clone()]);
+    let profile = match &args.profile {
+        Some(path) => Some(SeederProfile::load(path)?),
+        None => None,
+    };
 
     tracing_subscriber::fmt::init();
     let encryptor: Option<Box<dyn Encryptor>> = match iggy_args.encryption_key.is_empty() {
@@ -51,7 +69,16 @@ async fn main() -> Result<(), Box<dyn Error>> {
     );
     client.login_user(&username, &password).await.unwrap();
     info!("Data seeder has started...");
-    seeder::seed(&client).await.unwrap();
+    match profile {
+        Some(profile) => {
+            let mut rng = match args.seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_entropy(),
+            };
+            seeder::seed_profile(&client, &profile, &mut rng).await?
+        }
+        None => seeder::seed(&client).await.unwrap(),
+    }
     info!("Data seeder has finished.");
     Ok(())
 }
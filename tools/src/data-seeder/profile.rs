@@ -0,0 +1,420 @@
+use anyhow::{anyhow, bail, Context, Result};
+use iggy::utils::expiry::IggyExpiry;
+use iggy::utils::timestamp::IggyTimestamp;
+use iggy::utils::topic_size::MaxTopicSize;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::Deserialize;
+use std::path::Path;
+use std::str::FromStr;
+
+const DEFAULT_MESSAGES_PER_BATCH: u32 = 100;
+
+/// Workload profile of the data seeder, describing the streams and topics to create
+/// and the messages to send to them.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeederProfile {
+    pub streams: Vec<StreamProfile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StreamProfile {
+    pub name: String,
+    #[serde(default)]
+    pub id: Option<u32>,
+    #[serde(default)]
+    pub topics: Vec<TopicProfile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TopicProfile {
+    pub name: String,
+    #[serde(default)]
+    pub id: Option<u32>,
+    pub partitions_count: u32,
+    /// Message expiry time in human-readable format like 1h 15min, "none" or "server_default".
+    #[serde(default)]
+    pub message_expiry: Option<String>,
+    /// Max topic size in human-readable format like 10 GB, "unlimited" or "server_default".
+    #[serde(default)]
+    pub max_topic_size: Option<String>,
+    #[serde(default)]
+    pub consumer_groups: Vec<String>,
+    pub messages_count: u64,
+    #[serde(default = "default_messages_per_batch")]
+    pub messages_per_batch: u32,
+    /// Messages sent per second, unlimited if not set.
+    #[serde(default)]
+    pub rate: Option<u32>,
+    #[serde(default)]
+    pub keys: KeyDistribution,
+    /// Template of the payload, see [`MessageTemplate`] for the supported placeholders.
+    pub payload: String,
+    #[serde(default)]
+    pub headers: Vec<HeaderProfile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HeaderProfile {
+    pub key: String,
+    /// Template of the value, rendered the same way as the payload.
+    pub value: String,
+    /// Probability of the header being set on the message.
+    #[serde(default = "default_probability")]
+    pub probability: f64,
+}
+
+/// Distribution of the message keys used to partition the messages.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum KeyDistribution {
+    /// No keys, the messages are balanced across the partitions.
+    #[default]
+    Balanced,
+    /// Every key out of `keys` is equally likely.
+    Uniform { keys: u32 },
+    /// Few keys are much more frequent than the others (hot keys), with the frequency
+    /// of the key of rank `k` proportional to `1 / k^exponent`.
+    Zipf { keys: u32, exponent: f64 },
+}
+
+fn default_messages_per_batch() -> u32 {
+    DEFAULT_MESSAGES_PER_BATCH
+}
+
+fn default_probability() -> f64 {
+    1.0
+}
+
+impl SeederProfile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read profile file: {}", path.display()))?;
+        content
+            .parse()
+            .with_context(|| format!("Invalid profile file: {}", path.display()))
+    }
+
+    fn validate(&self) -> Result<()> {
+        for stream in &self.streams {
+            for topic in &stream.topics {
+                topic
+                    .validate()
+                    .with_context(|| format!("Invalid topic: {}/{}", stream.name, topic.name))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SeederProfile {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let profile: SeederProfile = toml::from_str(s)?;
+        profile.validate()?;
+        Ok(profile)
+    }
+}
+
+impl TopicProfile {
+    pub fn message_expiry(&self) -> Result<IggyExpiry> {
+        match &self.message_expiry {
+            Some(expiry) => IggyExpiry::from_str(expiry).map_err(|error| anyhow!(error)),
+            None => Ok(IggyExpiry::NeverExpire),
+        }
+    }
+
+    pub fn max_topic_size(&self) -> Result<MaxTopicSize> {
+        match &self.max_topic_size {
+            Some(size) => MaxTopicSize::from_str(size).map_err(|error| anyhow!(error)),
+            None => Ok(MaxTopicSize::ServerDefault),
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.partitions_count == 0 {
+            bail!("Partitions count must be greater than 0");
+        }
+        if self.messages_per_batch == 0 {
+            bail!("Messages per batch must be greater than 0");
+        }
+        if self.rate == Some(0) {
+            bail!("Rate must be greater than 0");
+        }
+        self.message_expiry()?;
+        self.max_topic_size()?;
+        KeySampler::new(&self.keys)?;
+        self.payload
+            .parse::<MessageTemplate>()
+            .context("Invalid payload template")?;
+        for header in &self.headers {
+            if !(0.0..=1.0).contains(&header.probability) {
+                bail!(
+                    "Probability of header: {} must be between 0 and 1",
+                    header.key
+                );
+            }
+            header
+                .value
+                .parse::<MessageTemplate>()
+                .with_context(|| format!("Invalid template of header: {}", header.key))?;
+        }
+        Ok(())
+    }
+}
+
+/// Draws the message keys (from 1 to the number of keys) for the given distribution.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeySampler {
+    Balanced,
+    Uniform(u32),
+    /// Cumulative distribution of the keys, key `k` is at the index `k - 1`.
+    Weighted(Vec<f64>),
+}
+
+impl KeySampler {
+    pub fn new(distribution: &KeyDistribution) -> Result<Self> {
+        match *distribution {
+            KeyDistribution::Balanced => Ok(KeySampler::Balanced),
+            KeyDistribution::Uniform { keys } => {
+                if keys == 0 {
+                    bail!("Number of keys must be greater than 0");
+                }
+                Ok(KeySampler::Uniform(keys))
+            }
+            KeyDistribution::Zipf { keys, exponent } => {
+                if keys == 0 {
+                    bail!("Number of keys must be greater than 0");
+                }
+                if !exponent.is_finite() || exponent < 0.0 {
+                    bail!("Zipf exponent must be a non-negative number");
+                }
+                let mut total = 0.0;
+                let mut cumulative = Vec::with_capacity(keys as usize);
+                for rank in 1..=keys {
+                    total += 1.0 / (rank as f64).powf(exponent);
+                    cumulative.push(total);
+                }
+                cumulative.iter_mut().for_each(|weight| *weight /= total);
+                Ok(KeySampler::Weighted(cumulative))
+            }
+        }
+    }
+
+    pub fn sample(&self, rng: &mut impl Rng) -> Option<u32> {
+        match self {
+            KeySampler::Balanced => None,
+            KeySampler::Uniform(keys) => Some(rng.gen_range(1..=*keys)),
+            KeySampler::Weighted(cumulative) => {
+                let point = rng.gen::<f64>();
+                let index = cumulative.partition_point(|weight| *weight < point);
+                Some(index.min(cumulative.len() - 1) as u32 + 1)
+            }
+        }
+    }
+}
+
+/// Values available to the placeholders while rendering the message.
+#[derive(Debug, Clone, Copy)]
+pub struct TemplateContext<'a> {
+    /// Number of the message in the topic, starting from 1.
+    pub sequence: u64,
+    pub key: Option<&'a str>,
+}
+
+/// Text template with the placeholders replaced by the random values, e.g.
+/// `{"order_id": "{{uuid}}", "amount": {{float 1 500}}, "status": "{{choice new paid shipped}}"}`.
+///
+/// Supported placeholders:
+/// - `{{uuid}}` - random UUID,
+/// - `{{int MIN MAX}}` - random integer from the inclusive range,
+/// - `{{float MIN MAX}}` - random number from the range, with 2 decimal places,
+/// - `{{string LENGTH}}` - random alphanumeric string,
+/// - `{{choice A B ...}}` - one of the given words,
+/// - `{{bool}}` - `true` or `false`,
+/// - `{{timestamp}}` - current time in microseconds,
+/// - `{{sequence}}` - number of the message in the topic,
+/// - `{{key}}` - key used to partition the message, empty if the messages are balanced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageTemplate {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Uuid,
+    Int(i64, i64),
+    Float(f64, f64),
+    String(usize),
+    Choice(Vec<String>),
+    Bool,
+    Timestamp,
+    Sequence,
+    Key,
+}
+
+impl FromStr for MessageTemplate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        let mut rest = s;
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                segments.push(Segment::Text(rest[..start].to_owned()));
+            }
+            let Some(end) = rest[start..].find("}}") else {
+                bail!("Unclosed placeholder: {}", &rest[start..]);
+            };
+            segments.push(Segment::parse(&rest[start + 2..start + end])?);
+            rest = &rest[start + end + 2..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Text(rest.to_owned()));
+        }
+        Ok(Self { segments })
+    }
+}
+
+impl MessageTemplate {
+    pub fn render(&self, rng: &mut impl Rng, context: &TemplateContext) -> String {
+        let mut output = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => output.push_str(text),
+                Segment::Uuid => {
+                    let uuid = uuid::Builder::from_random_bytes(rng.gen()).into_uuid();
+                    output.push_str(&uuid.to_string());
+                }
+                Segment::Int(min, max) => output.push_str(&rng.gen_range(*min..=*max).to_string()),
+                Segment::Float(min, max) => {
+                    output.push_str(&format!("{:.2}", rng.gen_range(*min..=*max)))
+                }
+                Segment::String(length) => {
+                    output.extend(rng.sample_iter(&Alphanumeric).take(*length).map(char::from))
+                }
+                Segment::Choice(choices) => {
+                    output.push_str(&choices[rng.gen_range(0..choices.len())])
+                }
+                Segment::Bool => output.push_str(&rng.gen::<bool>().to_string()),
+                Segment::Timestamp => {
+                    output.push_str(&IggyTimestamp::now().as_micros().to_string())
+                }
+                Segment::Sequence => output.push_str(&context.sequence.to_string()),
+                Segment::Key => output.push_str(context.key.unwrap_or_default()),
+            }
+        }
+        output
+    }
+}
+
+impl Segment {
+    fn parse(placeholder: &str) -> Result<Self> {
+        let mut parts = placeholder.split_whitespace();
+        let Some(name) = parts.next() else {
+            bail!("Empty placeholder");
+        };
+        let arguments = parts.collect::<Vec<_>>();
+        let segment = match (name, arguments.as_slice()) {
+            ("uuid", []) => Segment::Uuid,
+            ("int", [min, max]) => {
+                let (min, max) = (min.parse()?, max.parse()?);
+                if min > max {
+                    bail!("Invalid range of placeholder: {placeholder}");
+                }
+                Segment::Int(min, max)
+            }
+            ("float", [min, max]) => {
+                let (min, max): (f64, f64) = (min.parse()?, max.parse()?);
+                if !min.is_finite() || !max.is_finite() || min > max {
+                    bail!("Invalid range of placeholder: {placeholder}");
+                }
+                Segment::Float(min, max)
+            }
+            ("string", [length]) => Segment::String(length.parse()?),
+            ("choice", choices) if !choices.is_empty() => {
+                Segment::Choice(choices.iter().map(|choice| choice.to_string()).collect())
+            }
+            ("bool", []) => Segment::Bool,
+            ("timestamp", []) => Segment::Timestamp,
+            ("sequence", []) => Segment::Sequence,
+            ("key", []) => Segment::Key,
+            _ => bail!("Unsupported placeholder: {placeholder}"),
+        };
+        Ok(segment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const CONTEXT: TemplateContext = TemplateContext {
+        sequence: 7,
+        key: Some("key-3"),
+    };
+
+    #[test]
+    fn example_profile_should_be_valid() {
+        let profile = include_str!("profiles/ecommerce.toml")
+            .parse::<SeederProfile>()
+            .unwrap();
+
+        assert!(!profile.streams.is_empty());
+    }
+
+    #[test]
+    fn template_should_be_rendered_with_random_values_in_bounds() {
+        let template = "{{sequence}}|{{key}}|{{int 1 5}}|{{float 0.5 1}}|{{choice new paid}}|{{string 8}}|{{bool}}"
+            .parse::<MessageTemplate>()
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..100 {
+            let rendered = template.render(&mut rng, &CONTEXT);
+            let values = rendered.split('|').collect::<Vec<_>>();
+            assert_eq!(values[0], "7");
+            assert_eq!(values[1], "key-3");
+            assert!((1..=5).contains(&values[2].parse::<i64>().unwrap()));
+            assert!((0.5..=1.0).contains(&values[3].parse::<f64>().unwrap()));
+            assert!(["new", "paid"].contains(&values[4]));
+            assert_eq!(values[5].len(), 8);
+            assert!(values[6].parse::<bool>().is_ok());
+        }
+    }
+
+    #[test]
+    fn invalid_placeholders_should_be_rejected() {
+        for template in ["{{unknown}}", "{{int 5 1}}", "{{choice}}", "{{uuid"] {
+            assert!(template.parse::<MessageTemplate>().is_err(), "{template}");
+        }
+    }
+
+    #[test]
+    fn zipf_keys_should_favour_lower_ranks() {
+        let sampler = KeySampler::new(&KeyDistribution::Zipf {
+            keys: 100,
+            exponent: 1.2,
+        })
+        .unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut counts = vec![0; 100];
+
+        for _ in 0..10_000 {
+            let key = sampler.sample(&mut rng).unwrap();
+            counts[key as usize - 1] += 1;
+        }
+
+        assert!(counts[0] > counts[1]);
+        assert!(counts[1] > counts[99]);
+        assert!(counts[0] > 10_000 / 10);
+    }
+}
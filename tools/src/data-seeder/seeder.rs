@@ -1,13 +1,20 @@
-use iggy::client::{MessageClient, StreamClient, TopicClient};
+use crate::profile::{
+    KeySampler, MessageTemplate, SeederProfile, StreamProfile, TemplateContext, TopicProfile,
+};
+use iggy::client::{ConsumerGroupClient, MessageClient, StreamClient, TopicClient};
 use iggy::clients::client::IggyClient;
 use iggy::error::IggyError;
+use iggy::identifier::Identifier;
 use iggy::messages::send_messages::{Message, Partitioning};
 use iggy::models::header::{HeaderKey, HeaderValue};
 use iggy::utils::expiry::IggyExpiry;
 use iggy::utils::topic_size::MaxTopicSize;
 use rand::Rng;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
+use std::time::Duration;
+use tokio::time::{sleep_until, Instant};
+use tracing::info;
 
 const PROD_STREAM_ID: u32 = 1;
 const TEST_STREAM_ID: u32 = 2;
@@ -144,3 +151,125 @@ async fn send_messages(client: &IggyClient) -> Result<(), IggyError> {
     }
     Ok(())
 }
+
+pub async fn seed_profile(
+    client: &IggyClient,
+    profile: &SeederProfile,
+    rng: &mut impl Rng,
+) -> anyhow::Result<()> {
+    for stream in &profile.streams {
+        create_stream_from_profile(client, stream).await?;
+        let stream_id = Identifier::named(&stream.name)?;
+        for topic in &stream.topics {
+            send_messages_from_profile(client, &stream_id, topic, rng).await?;
+            info!(
+                "Sent {} messages to topic: {}/{}",
+                topic.messages_count, stream.name, topic.name
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn create_stream_from_profile(
+    client: &IggyClient,
+    stream: &StreamProfile,
+) -> anyhow::Result<()> {
+    client.create_stream(&stream.name, stream.id).await?;
+    let stream_id = Identifier::named(&stream.name)?;
+    for topic in &stream.topics {
+        client
+            .create_topic(
+                &stream_id,
+                &topic.name,
+                topic.partitions_count,
+                Default::default(),
+                None,
+                topic.id,
+                topic.message_expiry()?,
+                topic.max_topic_size()?,
+            )
+            .await?;
+        let topic_id = Identifier::named(&topic.name)?;
+        for consumer_group in &topic.consumer_groups {
+            client
+                .create_consumer_group(&stream_id, &topic_id, consumer_group, None)
+                .await?;
+        }
+    }
+    info!(
+        "Created stream: {} with {} topics",
+        stream.name,
+        stream.topics.len()
+    );
+    Ok(())
+}
+
+async fn send_messages_from_profile(
+    client: &IggyClient,
+    stream_id: &Identifier,
+    topic: &TopicProfile,
+    rng: &mut impl Rng,
+) -> anyhow::Result<()> {
+    let topic_id = Identifier::named(&topic.name)?;
+    let keys = KeySampler::new(&topic.keys)?;
+    let payload = topic.payload.parse::<MessageTemplate>()?;
+    let headers = topic
+        .headers
+        .iter()
+        .map(|header| {
+            Ok((
+                HeaderKey::new(&header.key)?,
+                header.value.parse::<MessageTemplate>()?,
+                header.probability,
+            ))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let start = Instant::now();
+    let mut sent_messages = 0;
+    while sent_messages < topic.messages_count {
+        if let Some(rate) = topic.rate {
+            sleep_until(start + Duration::from_secs_f64(sent_messages as f64 / rate as f64)).await;
+        }
+
+        // Partitioning applies to the whole batch, so the messages are grouped by their keys.
+        let messages_count =
+            (topic.messages_count - sent_messages).min(topic.messages_per_batch as u64);
+        let mut batches: BTreeMap<Option<u32>, Vec<Message>> = BTreeMap::new();
+        for sequence in sent_messages + 1..=sent_messages + messages_count {
+            let key = keys.sample(rng);
+            let key_name = key.map(|key| format!("key-{key}"));
+            let context = TemplateContext {
+                sequence,
+                key: key_name.as_deref(),
+            };
+            let mut message = Message::from_str(&payload.render(rng, &context))?;
+            let mut message_headers = HashMap::new();
+            for (header_key, value, probability) in &headers {
+                if rng.gen_bool(*probability) {
+                    message_headers.insert(
+                        header_key.clone(),
+                        HeaderValue::from_str(&value.render(rng, &context))?,
+                    );
+                }
+            }
+            if !message_headers.is_empty() {
+                message.headers = Some(message_headers);
+            }
+            batches.entry(key).or_default().push(message);
+        }
+
+        for (key, mut messages) in batches {
+            let partitioning = match key {
+                Some(key) => Partitioning::messages_key_str(&format!("key-{key}"))?,
+                None => Partitioning::balanced(),
+            };
+            client
+                .send_messages(stream_id, &topic_id, &partitioning, &mut messages)
+                .await?;
+        }
+        sent_messages += messages_count;
+    }
+    Ok(())
+}
@@ -0,0 +1,166 @@
+mod report;
+mod segments;
+mod state;
+
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use iggy::utils::crypto::Aes256GcmEncryptor;
+use iggy::utils::timestamp::IggyTimestamp;
+use report::{Report, Resolution};
+use server::configs::config_provider::FileConfigProvider;
+use server::configs::server::ServerConfig;
+use server::configs::system::SystemConfig;
+use server::streaming::utils::key_ring::EncryptionKeyRing;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tracing::info;
+
+const QUARANTINE_DIRECTORY: &str = "quarantine";
+
+#[derive(Parser, Debug, Clone)]
+#[command(author, version, about, long_about = None)]
+pub struct FsckArgs {
+    /// Path to the configuration file of the server, loaded the same way as by the server (including
+    /// the `IGGY_` environment variables), so that the data is verified with the same segment settings
+    /// (e.g. the index interval), paths and encryption keys, the defaults are used if not provided
+    #[arg(long)]
+    pub config: Option<String>,
+
+    /// Path to the data directory of the server (`system.path` of the configuration by default),
+    /// the server must not be running
+    #[arg(long)]
+    pub path: Option<String>,
+
+    /// Base64 encoded key of the server-side encryption, required to verify the encrypted state,
+    /// the keys of the configuration are used if not provided and the encryption is enabled there
+    #[arg(long)]
+    pub encryption_key: Option<String>,
+
+    /// ID of the server-side encryption key
+    #[arg(long, default_value_t = 1)]
+    pub encryption_key_id: u32,
+
+    /// Skips verifying the checksums of the messages, only the batches and indexes are verified
+    #[arg(long, default_value_t = false)]
+    pub skip_checksums: bool,
+
+    /// Fixes the found issues, `repair` truncates the logs at the first invalid entry or batch
+    /// and rebuilds the indexes, `quarantine` does the same, but first moves the original files
+    /// and the orphaned partitions to the `quarantine` directory
+    #[arg(long, value_enum)]
+    pub fix: Option<FixMode>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FixMode {
+    Repair,
+    Quarantine,
+}
+
+pub struct Fsck {
+    config: SystemConfig,
+    fix: Option<FixMode>,
+    encryptor: Option<EncryptionKeyRing>,
+    validate_checksum: bool,
+    quarantine_path: PathBuf,
+    report: Report,
+}
+
+impl Fsck {
+    /// Preserves the original file in the quarantine (if enabled) before it gets repaired in place.
+    async fn preserve(&self, path: &str) -> Result<Resolution> {
+        match self.fix {
+            Some(FixMode::Quarantine) => {
+                fs::copy(path, self.prepare_quarantine_path(path).await?).await?;
+                Ok(Resolution::Quarantined)
+            }
+            Some(FixMode::Repair) => Ok(Resolution::Repaired),
+            None => Ok(Resolution::Unresolved),
+        }
+    }
+
+    /// Moves the file or directory to the quarantine (if enabled).
+    async fn quarantine(&self, path: &str) -> Result<Resolution> {
+        if self.fix != Some(FixMode::Quarantine) {
+            return Ok(Resolution::Unresolved);
+        }
+
+        fs::rename(path, self.prepare_quarantine_path(path).await?).await?;
+        Ok(Resolution::Quarantined)
+    }
+
+    async fn prepare_quarantine_path(&self, path: &str) -> Result<PathBuf> {
+        let path = Path::new(path);
+        let relative_path = path.strip_prefix(&self.config.path).unwrap_or(path);
+        let quarantine_path = self.quarantine_path.join(relative_path);
+        if let Some(parent) = quarantine_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        Ok(quarantine_path)
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = FsckArgs::parse();
+    tracing_subscriber::fmt::init();
+    let mut config = match &args.config {
+        Some(config_path) => {
+            let server_config =
+                ServerConfig::load(&FileConfigProvider::new(config_path.clone())).await?;
+            // The configuration has just been loaded, so its system part is not shared yet.
+            Arc::into_inner(server_config.system).expect("System config is shared")
+        }
+        None => SystemConfig::default(),
+    };
+    if let Some(path) = &args.path {
+        config.path = path.clone();
+    }
+    if !Path::new(&config.path).exists() {
+        return Err(format!("Data directory: {} does not exist.", config.path).into());
+    }
+
+    let encryptor = match &args.encryption_key {
+        Some(key) => Some(EncryptionKeyRing::new(
+            args.encryption_key_id,
+            Aes256GcmEncryptor::from_base64_key(key)?,
+        )),
+        None if config.encryption.enabled => {
+            Some(EncryptionKeyRing::from_config(&config.encryption)?)
+        }
+        None => None,
+    };
+    let quarantine_path = Path::new(&config.path)
+        .join(QUARANTINE_DIRECTORY)
+        .join(IggyTimestamp::now().as_micros().to_string());
+    let mut fsck = Fsck {
+        config,
+        fix: args.fix,
+        encryptor,
+        validate_checksum: !args.skip_checksums,
+        quarantine_path,
+        report: Report::default(),
+    };
+
+    info!(
+        "Verifying the state in data directory: {}...",
+        fsck.config.path
+    );
+    let system_state = state::check_state(&mut fsck).await?;
+    if system_state.is_none() {
+        info!("State could not be restored, skipping the verification of orphaned and missing directories.");
+    }
+    info!(
+        "Verifying the streams in data directory: {}...",
+        fsck.config.path
+    );
+    segments::check_streams(&mut fsck, system_state.as_ref()).await?;
+    fsck.report.print_summary();
+    if fsck.report.unresolved_issues() > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
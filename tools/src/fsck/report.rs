@@ -0,0 +1,71 @@
+use std::fmt::{Display, Formatter};
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Unresolved,
+    Repaired,
+    Quarantined,
+}
+
+#[derive(Debug)]
+pub struct Issue {
+    pub path: String,
+    pub description: String,
+    pub resolution: Resolution,
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let resolution = match self.resolution {
+            Resolution::Unresolved => "",
+            Resolution::Repaired => " [repaired]",
+            Resolution::Quarantined => " [quarantined]",
+        };
+        write!(f, "{}: {}{resolution}", self.path, self.description)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub state_entries: u64,
+    pub segments: u64,
+    pub messages: u64,
+    pub issues: Vec<Issue>,
+}
+
+impl Report {
+    pub fn add_issue(&mut self, path: &str, description: String, resolution: Resolution) {
+        let issue = Issue {
+            path: path.to_string(),
+            description,
+            resolution,
+        };
+        warn!("{issue}");
+        self.issues.push(issue);
+    }
+
+    pub fn unresolved_issues(&self) -> usize {
+        self.count(Resolution::Unresolved)
+    }
+
+    pub fn print_summary(&self) {
+        info!(
+            "Checked {} state entries, {} segments with {} messages, found {} issues: {} repaired, {} quarantined, {} unresolved.",
+            self.state_entries,
+            self.segments,
+            self.messages,
+            self.issues.len(),
+            self.count(Resolution::Repaired),
+            self.count(Resolution::Quarantined),
+            self.unresolved_issues()
+        );
+    }
+
+    fn count(&self, resolution: Resolution) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.resolution == resolution)
+            .count()
+    }
+}
@@ -0,0 +1,212 @@
+use crate::report::Resolution;
+use crate::Fsck;
+use anyhow::Result;
+use iggy::utils::byte_size::IggyByteSize;
+use server::state::system::SystemState;
+use server::streaming::segments::recovery::scan_log;
+use server::streaming::segments::segment::{INDEX_EXTENSION, LOG_EXTENSION, TIME_INDEX_EXTENSION};
+use server::streaming::utils::file;
+use std::collections::BTreeSet;
+use std::path::Path;
+use tokio::fs;
+
+/// Verifies the segments of all the partitions and reports the stream, topic and partition directories
+/// not referenced by the state, or the ones missing for the state, if it was successfully restored.
+pub async fn check_streams(fsck: &mut Fsck, state: Option<&SystemState>) -> Result<()> {
+    let streams_path = fsck.config.get_streams_path();
+    let stream_ids = read_ids(&streams_path).await?;
+    for stream_id in &stream_ids {
+        let stream_id = *stream_id;
+        let stream_path = fsck.config.get_stream_path(stream_id);
+        let stream_state = state.map(|state| state.streams.get(&stream_id));
+        if let Some(None) = stream_state {
+            report_orphan(fsck, &stream_path, "stream").await?;
+            continue;
+        }
+
+        let topics_path = fsck.config.get_topics_path(stream_id);
+        let topic_ids = read_ids(&topics_path).await?;
+        for topic_id in &topic_ids {
+            let topic_id = *topic_id;
+            let topic_path = fsck.config.get_topic_path(stream_id, topic_id);
+            let topic_state = stream_state
+                .flatten()
+                .map(|stream| stream.topics.get(&topic_id));
+            if let Some(None) = topic_state {
+                report_orphan(fsck, &topic_path, "topic").await?;
+                continue;
+            }
+
+            let partitions_path = fsck.config.get_partitions_path(stream_id, topic_id);
+            let partition_ids = read_ids(&partitions_path).await?;
+            for partition_id in &partition_ids {
+                let partition_id = *partition_id;
+                let partition_path =
+                    fsck.config
+                        .get_partition_path(stream_id, topic_id, partition_id);
+                let partition_state = topic_state
+                    .flatten()
+                    .map(|topic| topic.partitions.get(&partition_id));
+                if let Some(None) = partition_state {
+                    report_orphan(fsck, &partition_path, "partition").await?;
+                    continue;
+                }
+
+                check_partition(fsck, &partition_path).await?;
+            }
+
+            if let Some(topic) = topic_state.flatten() {
+                for partition_id in topic.partitions.keys() {
+                    if !partition_ids.contains(partition_id) {
+                        let partition_path =
+                            fsck.config
+                                .get_partition_path(stream_id, topic_id, *partition_id);
+                        report_missing(fsck, &partition_path, "partition");
+                    }
+                }
+            }
+        }
+
+        if let Some(stream) = stream_state.flatten() {
+            for topic_id in stream.topics.keys() {
+                if !topic_ids.contains(topic_id) {
+                    let topic_path = fsck.config.get_topic_path(stream_id, *topic_id);
+                    report_missing(fsck, &topic_path, "topic");
+                }
+            }
+        }
+    }
+
+    if let Some(state) = state {
+        for stream_id in state.streams.keys() {
+            if !stream_ids.contains(stream_id) {
+                let stream_path = fsck.config.get_stream_path(*stream_id);
+                report_missing(fsck, &stream_path, "stream");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn check_partition(fsck: &mut Fsck, partition_path: &str) -> Result<()> {
+    let mut log_paths = Vec::new();
+    let mut dir_entries = fs::read_dir(partition_path).await?;
+    while let Some(dir_entry) = dir_entries.next_entry().await? {
+        let path = dir_entry.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == LOG_EXTENSION)
+        {
+            log_paths.push(path);
+        }
+    }
+
+    log_paths.sort();
+    for log_path in log_paths {
+        let Some(start_offset) = log_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse::<u64>().ok())
+        else {
+            continue;
+        };
+
+        check_segment(fsck, &log_path, start_offset).await?;
+    }
+
+    Ok(())
+}
+
+async fn check_segment(fsck: &mut Fsck, log_path: &Path, start_offset: u64) -> Result<()> {
+    let log_path_str = log_path.to_string_lossy().to_string();
+    let index_interval = fsck.config.segment.index_interval.as_bytes_u64() as u32;
+    let scan = scan_log(
+        &log_path_str,
+        start_offset,
+        index_interval,
+        fsck.validate_checksum,
+    )
+    .await?;
+    fsck.report.segments += 1;
+    fsck.report.messages += scan.messages;
+    if let Some(invalid_batch) = scan.invalid_batch {
+        let description = format!(
+            "found {invalid_batch} at position: {}, {} of the log cannot be read",
+            scan.valid_size,
+            IggyByteSize::from(scan.log_size - scan.valid_size)
+        );
+        let resolution = fsck.preserve(&log_path_str).await?;
+        if resolution != Resolution::Unresolved {
+            file::truncate(&log_path_str, scan.valid_size).await?;
+        }
+        fsck.report
+            .add_issue(&log_path_str, description, resolution);
+    }
+
+    for (extension, indexes) in [
+        (INDEX_EXTENSION, &scan.indexes),
+        (TIME_INDEX_EXTENSION, &scan.time_indexes),
+    ] {
+        let index_path = log_path.with_extension(extension);
+        let description = match fs::read(&index_path).await {
+            Ok(bytes) if &bytes == indexes => continue,
+            Ok(_) => "index is inconsistent with the log",
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => "index is missing",
+            Err(error) => return Err(error.into()),
+        };
+
+        // Indexes can always be rebuilt from the log, so there is no need to quarantine them.
+        let resolution = match fsck.fix {
+            Some(_) => {
+                fs::write(&index_path, indexes).await?;
+                Resolution::Repaired
+            }
+            None => Resolution::Unresolved,
+        };
+        fsck.report.add_issue(
+            &index_path.to_string_lossy(),
+            description.to_string(),
+            resolution,
+        );
+    }
+
+    Ok(())
+}
+
+async fn report_orphan(fsck: &mut Fsck, path: &str, kind: &str) -> Result<()> {
+    let description = format!("{kind} directory is not referenced by the state");
+    let resolution = fsck.quarantine(path).await?;
+    fsck.report.add_issue(path, description, resolution);
+    Ok(())
+}
+
+fn report_missing(fsck: &mut Fsck, path: &str, kind: &str) {
+    let description = format!("{kind} directory referenced by the state is missing");
+    fsck.report
+        .add_issue(path, description, Resolution::Unresolved);
+}
+
+/// Returns the IDs based on the names of the directories in the given path, if it exists.
+async fn read_ids(path: &str) -> Result<BTreeSet<u32>> {
+    let mut ids = BTreeSet::new();
+    if !Path::new(path).exists() {
+        return Ok(ids);
+    }
+
+    let mut dir_entries = fs::read_dir(path).await?;
+    while let Some(dir_entry) = dir_entries.next_entry().await? {
+        if !dir_entry.file_type().await?.is_dir() {
+            continue;
+        }
+
+        if let Some(id) = dir_entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u32>().ok())
+        {
+            ids.insert(id);
+        }
+    }
+    Ok(ids)
+}
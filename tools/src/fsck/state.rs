@@ -0,0 +1,222 @@
+use crate::report::Resolution;
+use crate::Fsck;
+use anyhow::Result;
+use bytes::Bytes;
+use iggy::bytes_serializable::BytesSerializable;
+use iggy::utils::byte_size::IggyByteSize;
+use iggy::utils::crypto::Encryptor;
+use server::state::command::EntryCommand;
+use server::state::entry::StateEntry;
+use server::state::file::map_command_payload;
+use server::state::snapshot::StateSnapshot;
+use server::state::system::SystemState;
+use server::streaming::utils::file;
+use std::path::Path;
+use tokio::fs;
+use tracing::info;
+
+const ENTRY_HEADER_SIZE: usize = 8 + 8 + 4 + 4 + 8 + 8 + 4 + 4 + 4;
+
+/// Verifies the state snapshot and the state log entries (their order, commands and checksums),
+/// returns the restored state if it's valid, so it can be used to find the orphaned partitions.
+pub async fn check_state(fsck: &mut Fsck) -> Result<Option<SystemState>> {
+    let encryptor = fsck
+        .encryptor
+        .as_ref()
+        .map(|encryptor| encryptor as &dyn Encryptor);
+    let snapshot_path = fsck.config.get_state_snapshot_path();
+    let snapshot = if Path::new(&snapshot_path).exists() {
+        let bytes = fs::read(&snapshot_path).await?;
+        match StateSnapshot::from_bytes(&bytes, encryptor) {
+            Ok(snapshot) => {
+                info!("Verified state snapshot: {snapshot}");
+                Some(snapshot)
+            }
+            Err(error) => {
+                let description = format!(
+                    "cannot read state snapshot: {error}{}",
+                    encryption_hint(fsck)
+                );
+                fsck.report
+                    .add_issue(&snapshot_path, description, Resolution::Unresolved);
+                return Ok(None);
+            }
+        }
+    } else {
+        None
+    };
+
+    let log_path = fsck.config.get_state_log_path();
+    if !Path::new(&log_path).exists() {
+        info!("State log: {log_path} does not exist, skipping the state verification.");
+        return Ok(None);
+    }
+
+    let bytes = Bytes::from(fs::read(&log_path).await?);
+    let snapshot_index = snapshot.as_ref().map(|snapshot| snapshot.index);
+    let mut entries = Vec::new();
+    let mut previous_index = None;
+    let mut position = 0;
+    let mut invalid_entry = None;
+    while position < bytes.len() {
+        let Some(entry_size) = read_entry_size(&bytes[position..]) else {
+            invalid_entry = Some("partial entry".to_string());
+            break;
+        };
+
+        let entry = StateEntry::from_bytes(bytes.slice(position..position + entry_size))?;
+        let is_expected_index = match (previous_index, snapshot_index) {
+            (Some(previous_index), _) => entry.index == previous_index + 1,
+            (None, Some(snapshot_index)) => entry.index <= snapshot_index + 1,
+            (None, None) => true,
+        };
+        if !is_expected_index {
+            invalid_entry = Some(format!("entry with unexpected index: {}", entry.index));
+            break;
+        }
+
+        if let Err(error) = validate_entry(&entry, encryptor) {
+            invalid_entry = Some(format!(
+                "invalid entry with index: {}, {error}{}",
+                entry.index,
+                encryption_hint(fsck)
+            ));
+            break;
+        }
+
+        previous_index = Some(entry.index);
+        position += entry_size;
+        fsck.report.state_entries += 1;
+        if snapshot_index.is_none_or(|snapshot_index| entry.index > snapshot_index) {
+            entries.push(entry);
+        }
+    }
+
+    if let Some(invalid_entry) = invalid_entry {
+        let description = format!(
+            "found {invalid_entry} at position: {position}, {} of the log cannot be read",
+            IggyByteSize::from((bytes.len() - position) as u64)
+        );
+        let resolution = fsck.preserve(&log_path).await?;
+        if resolution != Resolution::Unresolved {
+            file::truncate(&log_path, position as u64).await?;
+        }
+        fsck.report.add_issue(&log_path, description, resolution);
+        if resolution == Resolution::Unresolved {
+            return Ok(None);
+        }
+    }
+
+    match SystemState::restore(snapshot.map(|snapshot| snapshot.state), entries).await {
+        Ok(state) => Ok(Some(state)),
+        Err(error) => {
+            let description = format!("cannot restore the state from the log: {error}");
+            fsck.report
+                .add_issue(&log_path, description, Resolution::Unresolved);
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the size of the serialized entry, or `None` if the bytes contain only its part.
+fn read_entry_size(bytes: &[u8]) -> Option<usize> {
+    if bytes.len() < ENTRY_HEADER_SIZE {
+        return None;
+    }
+
+    let context_length = read_u32(bytes, ENTRY_HEADER_SIZE - 4) as usize;
+    let command_position = ENTRY_HEADER_SIZE + context_length;
+    if bytes.len() < command_position + 8 {
+        return None;
+    }
+
+    let command_length = read_u32(bytes, command_position + 4) as usize;
+    let entry_size = command_position + 8 + command_length;
+    if bytes.len() < entry_size {
+        return None;
+    }
+
+    Some(entry_size)
+}
+
+fn validate_entry(entry: &StateEntry, encryptor: Option<&dyn Encryptor>) -> Result<()> {
+    let command = match encryptor {
+        Some(encryptor) => {
+            map_command_payload(&entry.command, |payload| encryptor.decrypt(payload))?
+        }
+        None => entry.command.clone(),
+    };
+    let checksum = StateEntry::calculate_checksum(
+        entry.index,
+        entry.term,
+        entry.leader_id,
+        entry.version,
+        entry.flags,
+        entry.timestamp,
+        entry.user_id,
+        &entry.context,
+        &command,
+    );
+    if checksum != entry.checksum {
+        anyhow::bail!("invalid checksum: {checksum}, expected: {}", entry.checksum);
+    }
+
+    EntryCommand::from_bytes(command)?;
+    Ok(())
+}
+
+fn encryption_hint(fsck: &Fsck) -> &'static str {
+    match fsck.encryptor {
+        Some(_) => "",
+        None => " (if the state is encrypted, provide the encryption key)",
+    }
+}
+
+fn read_u32(bytes: &[u8], position: usize) -> u32 {
+    u32::from_le_bytes(bytes[position..position + 4].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iggy::streams::create_stream::CreateStream;
+    use iggy::utils::timestamp::IggyTimestamp;
+
+    #[test]
+    fn entry_size_should_be_read_only_for_complete_entry() {
+        let command = EntryCommand::CreateStream(CreateStream {
+            stream_id: Some(1),
+            name: "test".to_string(),
+        })
+        .to_bytes();
+        let context = Bytes::from_static(b"context");
+        let checksum = StateEntry::calculate_checksum(
+            0,
+            0,
+            0,
+            1,
+            0,
+            IggyTimestamp::now(),
+            1,
+            &context,
+            &command,
+        );
+        let entry = StateEntry::new(
+            0,
+            0,
+            0,
+            1,
+            0,
+            IggyTimestamp::now(),
+            1,
+            checksum,
+            context,
+            command,
+        )
+        .to_bytes();
+
+        assert_eq!(read_entry_size(&entry), Some(entry.len()));
+        assert_eq!(read_entry_size(&entry[..entry.len() - 1]), None);
+        assert_eq!(read_entry_size(&entry[..ENTRY_HEADER_SIZE]), None);
+    }
+}